@@ -25,6 +25,18 @@ pub static MAX_COMPUTATION_DEPTH: LazyLock<u32> =
 pub static TRANSACTION_CACHE_SIZE: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_TRANSACTION_CACHE_SIZE", usize, 10_000);
 
+/// The maximum number of queries which can be prepared on a single connection.
+pub static MAX_PREPARED_QUERIES: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_MAX_PREPARED_QUERIES", usize, 1_000);
+
+/// The maximum number of transactions spanning several requests which a single connection can have open.
+pub static MAX_INTERACTIVE_TRANSACTIONS: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_MAX_INTERACTIVE_TRANSACTIONS", usize, 10);
+
+/// The number of seconds after which an unused transaction spanning several requests is cancelled.
+pub static INTERACTIVE_TRANSACTION_TIMEOUT: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_INTERACTIVE_TRANSACTION_TIMEOUT", u64, 30);
+
 /// The maximum number of keys that should be scanned at once in general queries.
 pub static NORMAL_FETCH_SIZE: LazyLock<u32> = lazy_env_parse!("SURREAL_NORMAL_FETCH_SIZE", u32, 50);
 
@@ -40,6 +52,23 @@ pub static MAX_STREAM_BATCH_SIZE: LazyLock<u32> =
 pub static INDEXING_BATCH_SIZE: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_INDEXING_BATCH_SIZE", u32, 250);
 
+/// The maximum number of elements a conditional HNSW search visits before it stops traversing the graph.
+pub static HNSW_MAX_CHECKED_ELEMENTS: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_HNSW_MAX_CHECKED_ELEMENTS", usize, 10_000);
+
+/// The number of consecutive failed signin attempts after which signin is locked, without a signin policy.
+/// Signin attempts are not throttled without a signin policy when this is zero.
+pub static SIGNIN_FALLBACK_ATTEMPTS: LazyLock<u32> =
+	lazy_env_parse!("SURREAL_SIGNIN_FALLBACK_ATTEMPTS", u32, 0);
+
+/// The number of seconds for which signin is locked, without a signin policy.
+pub static SIGNIN_FALLBACK_LOCKOUT: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_SIGNIN_FALLBACK_LOCKOUT", u64, 900);
+
+/// The number of times an entry is stored in the audit log before it is given up on.
+pub static AUDIT_MAX_ATTEMPTS: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_AUDIT_MAX_ATTEMPTS", usize, 5);
+
 /// The maximum stack size of the JavaScript function runtime (defaults to 256 KiB)
 pub static SCRIPTING_MAX_STACK_SIZE: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_SCRIPTING_MAX_STACK_SIZE", usize, 256 * 1024);
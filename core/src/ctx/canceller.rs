@@ -1,13 +1,22 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
-#[derive(Default, Clone)]
+#[derive(Debug, Default, Clone)]
 #[non_exhaustive]
 pub struct Canceller {
 	/// A reference to the canceled value of a context.
-	cancelled: Arc<AtomicBool>,
+	pub(crate) cancelled: Arc<AtomicBool>,
 }
 
+impl PartialEq for Canceller {
+	fn eq(&self, other: &Self) -> bool {
+		// Cancellers are equal when they cancel the same contexts
+		Arc::ptr_eq(&self.cancelled, &other.cancelled)
+	}
+}
+
+impl Eq for Canceller {}
+
 impl Canceller {
 	/// Create a new Canceller
 	pub fn new(cancelled: Arc<AtomicBool>) -> Canceller {
@@ -19,4 +28,8 @@ impl Canceller {
 	pub fn cancel(&self) {
 		self.cancelled.store(true, Ordering::Relaxed);
 	}
+	/// Check if the context has been cancelled.
+	pub fn is_cancelled(&self) -> bool {
+		self.cancelled.load(Ordering::Relaxed)
+	}
 }
@@ -5,6 +5,7 @@ use crate::ctx::reason::Reason;
 use crate::dbs::capabilities::NetTarget;
 use crate::dbs::{Capabilities, Notification};
 use crate::err::Error;
+use crate::iam::audit::AuditLog;
 use crate::idx::planner::executor::QueryExecutor;
 use crate::idx::planner::{IterationStage, QueryPlanner};
 use crate::idx::trees::store::IndexStores;
@@ -51,6 +52,8 @@ pub struct MutableContext {
 	values: HashMap<Cow<'static, str>, Arc<Value>>,
 	// Stores the notification channel if available
 	notifications: Option<Sender<Notification>>,
+	// Stores the audit log if enabled
+	audit: Option<Arc<AuditLog>>,
 	// An optional query planner
 	query_planner: Option<Arc<QueryPlanner>>,
 	// An optional query executor
@@ -112,6 +115,7 @@ impl MutableContext {
 			deadline: None,
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: None,
+			audit: None,
 			query_planner: None,
 			query_executor: None,
 			iteration_stage: None,
@@ -137,6 +141,7 @@ impl MutableContext {
 			deadline: None,
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: None,
+			audit: None,
 			query_planner: None,
 			query_executor: None,
 			iteration_stage: None,
@@ -158,6 +163,7 @@ impl MutableContext {
 			deadline: parent.deadline,
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: parent.notifications.clone(),
+			audit: parent.audit.clone(),
 			query_planner: parent.query_planner.clone(),
 			query_executor: parent.query_executor.clone(),
 			iteration_stage: parent.iteration_stage.clone(),
@@ -190,6 +196,7 @@ impl MutableContext {
 			deadline: parent.deadline,
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: parent.notifications.clone(),
+			audit: parent.audit.clone(),
 			query_planner: parent.query_planner.clone(),
 			query_executor: parent.query_executor.clone(),
 			iteration_stage: parent.iteration_stage.clone(),
@@ -212,6 +219,7 @@ impl MutableContext {
 			deadline: None,
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: from.notifications.clone(),
+			audit: from.audit.clone(),
 			query_planner: from.query_planner.clone(),
 			query_executor: from.query_executor.clone(),
 			iteration_stage: from.iteration_stage.clone(),
@@ -243,6 +251,12 @@ impl MutableContext {
 		Canceller::new(cancelled)
 	}
 
+	/// Link the context to an existing canceller, so that the context and
+	/// it's children are cancelled once the canceller is called.
+	pub(crate) fn add_canceller(&mut self, canceller: &Canceller) {
+		self.cancelled = canceller.cancelled.clone();
+	}
+
 	/// Add a deadline to the context. If the current deadline is sooner than
 	/// the provided deadline, this method does nothing.
 	pub fn add_deadline(&mut self, deadline: Instant) {
@@ -271,6 +285,12 @@ impl MutableContext {
 		self.notifications = chn.cloned()
 	}
 
+	/// Add the audit log to the context, so that privileged
+	/// operations can be recorded as they are processed.
+	pub(crate) fn add_audit(&mut self, audit: Option<&Arc<AuditLog>>) {
+		self.audit = audit.cloned()
+	}
+
 	pub(crate) fn set_query_planner(&mut self, qp: QueryPlanner) {
 		self.query_planner = Some(Arc::new(qp));
 	}
@@ -303,6 +323,10 @@ impl MutableContext {
 		self.notifications.clone()
 	}
 
+	pub(crate) fn get_audit(&self) -> Option<&AuditLog> {
+		self.audit.as_deref()
+	}
+
 	pub(crate) fn get_query_planner(&self) -> Option<&QueryPlanner> {
 		self.query_planner.as_ref().map(|qp| qp.as_ref())
 	}
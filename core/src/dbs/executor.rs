@@ -4,6 +4,7 @@ use crate::dbs::Force;
 use crate::dbs::Notification;
 use crate::dbs::Options;
 use crate::dbs::QueryType;
+use crate::dbs::{Row, RowSender};
 use crate::err::Error;
 use crate::iam::Action;
 use crate::iam::ResourceKind;
@@ -16,8 +17,9 @@ use crate::sql::query::Query;
 use crate::sql::statement::Statement;
 use crate::sql::value::Value;
 use crate::sql::Base;
-use channel::Receiver;
+use channel::{Receiver, Sender};
 use futures::StreamExt;
+use reblessive::tree::Stk;
 use reblessive::TreeStack;
 use std::sync::Arc;
 #[cfg(not(target_arch = "wasm32"))]
@@ -31,6 +33,12 @@ pub(crate) struct Executor<'a> {
 	err: bool,
 	kvs: &'a Datastore,
 	txn: Option<Arc<Transaction>>,
+	// Whether the transaction spans several requests, and is completed by the caller
+	interactive: bool,
+	// The live query notifications which are sent once the caller commits the transaction
+	notifications: Vec<Notification>,
+	// The channel which the rows of SELECT statements are streamed to
+	rows: Option<Sender<Row>>,
 }
 
 impl<'a> Executor<'a> {
@@ -39,9 +47,35 @@ impl<'a> Executor<'a> {
 			kvs,
 			txn: None,
 			err: false,
+			interactive: false,
+			notifications: vec![],
+			rows: None,
 		}
 	}
 
+	/// Creates an executor which runs all statements within a transaction spanning several requests
+	pub fn new_interactive(kvs: &'a Datastore, txn: Arc<Transaction>) -> Executor<'a> {
+		Executor {
+			kvs,
+			txn: Some(txn),
+			err: false,
+			interactive: true,
+			notifications: vec![],
+			rows: None,
+		}
+	}
+
+	/// Streams the rows of SELECT statements to a channel, rather than returning them
+	pub fn with_rows(mut self, rows: Option<Sender<Row>>) -> Self {
+		self.rows = rows;
+		self
+	}
+
+	/// Takes the live query notifications which are sent once the transaction is committed
+	pub fn take_notifications(&mut self) -> Vec<Notification> {
+		std::mem::take(&mut self.notifications)
+	}
+
 	fn txn(&self) -> Arc<Transaction> {
 		self.txn.clone().expect("unreachable: txn was None after successful begin")
 	}
@@ -115,6 +149,20 @@ impl<'a> Executor<'a> {
 		}
 	}
 
+	/// Computes a statement, sending the rows of a SELECT statement to the channel if specified
+	async fn compute(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		stm: &Statement,
+		rows: Option<RowSender>,
+	) -> Result<Value, Error> {
+		match (stm, rows) {
+			(Statement::Select(stm), Some(rows)) => stm.stream(stk, ctx, opt, rows).await,
+			(stm, _) => stm.compute(stk, ctx, opt, None).await,
+		}
+	}
+
 	fn buf_cancel(&self, v: Response) -> Response {
 		Response {
 			time: v.time,
@@ -144,6 +192,10 @@ impl<'a> Executor<'a> {
 
 	/// Consume the live query notifications
 	async fn clear(&self, _: &Context, mut rcv: Receiver<Notification>) {
+		// Notifications are kept until the caller completes the transaction
+		if self.interactive {
+			return;
+		}
 		spawn(async move {
 			while rcv.next().await.is_some() {
 				// Ignore notification
@@ -154,6 +206,10 @@ impl<'a> Executor<'a> {
 	/// Flush notifications from a buffer channel (live queries) to the committed notification channel.
 	/// This is because we don't want to broadcast notifications to the user for failed transactions.
 	async fn flush(&self, ctx: &Context, mut rcv: Receiver<Notification>) {
+		// Notifications are kept until the caller completes the transaction
+		if self.interactive {
+			return;
+		}
 		let sender = ctx.notifications();
 		spawn(async move {
 			while let Some(notification) = rcv.next().await {
@@ -255,6 +311,12 @@ impl<'a> Executor<'a> {
 					// Continue
 					continue;
 				}
+				// Transactions spanning several requests are completed by the caller
+				Statement::Begin(_) | Statement::Cancel(_) | Statement::Commit(_)
+					if self.interactive =>
+				{
+					Err(Error::TxStatementNotAllowed)
+				}
 				// Begin a new transaction
 				Statement::Begin(_) => {
 					self.begin(Write).await;
@@ -358,6 +420,13 @@ impl<'a> Executor<'a> {
 					false => {
 						// Create a transaction
 						let loc = self.begin(stm.writeable().into()).await;
+						// Rows are not streamed within a BEGIN and COMMIT block, as they could be cancelled
+						let rows = match loc || self.interactive {
+							true => {
+								self.rows.clone().map(|v| RowSender::new(out.len() + buf.len(), v))
+							}
+							false => None,
+						};
 						// Check the transaction
 						match self.err {
 							// We failed to create a transaction
@@ -377,7 +446,9 @@ impl<'a> Executor<'a> {
 											let c = ctx.freeze();
 											// Process the statement
 											let res = stack
-												.enter(|stk| stm.compute(stk, &c, &opt, None))
+												.enter(|stk| {
+													Self::compute(stk, &c, &opt, &stm, rows)
+												})
 												.finish()
 												.await;
 											ctx = MutableContext::unfreeze(c)?;
@@ -393,7 +464,7 @@ impl<'a> Executor<'a> {
 										ctx.set_transaction(self.txn());
 										let c = ctx.freeze();
 										let r = stack
-											.enter(|stk| stm.compute(stk, &c, &opt, None))
+											.enter(|stk| Self::compute(stk, &c, &opt, &stm, rows))
 											.finish()
 											.await;
 										ctx = MutableContext::unfreeze(c)?;
@@ -403,10 +474,10 @@ impl<'a> Executor<'a> {
 								// Check if this is a RETURN statement
 								let can_return =
 									matches!(stm, Statement::Output(_) | Statement::Value(_));
-								// Catch global timeout
-								let res = match ctx.is_timedout() {
-									true => Err(Error::QueryTimedout),
-									false => match res {
+								// Catch global timeout or cancellation
+								let res = match ctx.done() {
+									Some(reason) => Err(reason.into()),
+									None => match res {
 										Err(Error::Return {
 											value,
 										}) if can_return => {
@@ -467,6 +538,13 @@ impl<'a> Executor<'a> {
 				out.push(res)
 			}
 		}
+		// Output the responses of a transaction spanning several requests
+		if self.interactive {
+			out.append(&mut buf);
+			while let Ok(v) = recv.try_recv() {
+				self.notifications.push(v);
+			}
+		}
 		// Return responses
 		Ok(out)
 	}
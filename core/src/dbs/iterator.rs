@@ -6,6 +6,7 @@ use crate::dbs::distinct::SyncDistinct;
 use crate::dbs::plan::Plan;
 use crate::dbs::result::Results;
 use crate::dbs::Options;
+use crate::dbs::RowSender;
 use crate::dbs::Statement;
 use crate::doc::Document;
 use crate::err::Error;
@@ -72,6 +73,12 @@ pub(crate) struct Iterator {
 	entries: Vec<Iterable>,
 	// Set if the iterator can be cancelled once it reaches start/limit
 	cancel_on_limit: Option<u32>,
+	// Iterator output rows, if the results are streamed
+	rows: Option<RowSender>,
+	// Whether the rows are sent as soon as they are produced
+	streaming: bool,
+	// The number of rows produced while streaming
+	produced: u64,
 }
 
 impl Clone for Iterator {
@@ -84,6 +91,9 @@ impl Clone for Iterator {
 			results: Results::default(),
 			entries: self.entries.clone(),
 			cancel_on_limit: None,
+			rows: None,
+			streaming: false,
+			produced: 0,
 		}
 	}
 }
@@ -99,6 +109,11 @@ impl Iterator {
 		self.entries.push(val)
 	}
 
+	/// Sends the results as rows, rather than returning them
+	pub fn stream(&mut self, rows: RowSender) {
+		self.rows = Some(rows)
+	}
+
 	/// Prepares a value for processing
 	pub async fn prepare(
 		&mut self,
@@ -307,6 +322,13 @@ impl Iterator {
 		)?;
 		// Extract the expected behaviour depending on the presence of EXPLAIN with or without FULL
 		let mut plan = Plan::new(ctx, stm, &self.entries, &self.results);
+		// Rows can be sent as they are produced if the results need no further processing
+		self.streaming = self.rows.is_some()
+			&& plan.explanation.is_none()
+			&& stm.split().is_none()
+			&& stm.group().is_none()
+			&& stm.order().is_none()
+			&& stm.fetch().is_none();
 		if plan.do_iterate {
 			// Process prepared values
 			if let Some(qp) = ctx.get_query_planner() {
@@ -359,6 +381,16 @@ impl Iterator {
 			}
 		}
 
+		// Send any results which could not be streamed as they were produced
+		if let Some(rows) = self.rows.take() {
+			for v in results {
+				if !rows.send(v).await {
+					break;
+				}
+			}
+			return Ok(Value::None);
+		}
+
 		// Output the results
 		Ok(results.into())
 	}
@@ -655,6 +687,10 @@ impl Iterator {
 				self.run.cancel();
 				return;
 			}
+			Ok(v) if self.streaming => {
+				self.send(v).await;
+				return;
+			}
 			Ok(v) => {
 				if let Err(e) = self.results.push(stk, ctx, opt, stm, v).await {
 					self.error = Some(e);
@@ -670,4 +706,34 @@ impl Iterator {
 			}
 		}
 	}
+
+	/// Send a row as soon as it is produced, applying any START and LIMIT clauses
+	async fn send(&mut self, val: Value) {
+		self.produced += 1;
+		// Skip the rows before the START clause
+		let start = self.start.unwrap_or(0) as u64;
+		if self.produced <= start {
+			return;
+		}
+		// Ignore any rows after the LIMIT clause
+		if let Some(l) = self.limit {
+			if self.produced > start + l as u64 {
+				self.run.cancel();
+				return;
+			}
+		}
+		// Stop iterating if the receiver has gone away
+		if let Some(rows) = &self.rows {
+			if !rows.send(val).await {
+				self.run.cancel();
+				return;
+			}
+		}
+		// Stop iterating once the LIMIT clause is reached
+		if let Some(l) = self.limit {
+			if self.produced == start + l as u64 {
+				self.run.cancel();
+			}
+		}
+	}
 }
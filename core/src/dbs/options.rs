@@ -440,7 +440,7 @@ impl Options {
 	///
 	/// TODO: This method is called a lot during data operations, so we decided to bypass the system's authorization mechanism.
 	/// This is a temporary solution, until we optimize the new authorization system.
-	pub fn check_perms(&self, action: Action) -> Result<bool, Error> {
+	pub fn check_perms(&self, action: Action, res: ResourceKind) -> Result<bool, Error> {
 		// If permissions are disabled, don't check permissions
 		if !self.perms {
 			return Ok(false);
@@ -456,6 +456,8 @@ impl Options {
 			[Role::Viewer, Role::Editor, Role::Owner].iter().any(|r| self.auth.has_role(r));
 		// Is the actor allowed to edit?
 		let can_edit = [Role::Editor, Role::Owner].iter().any(|r| self.auth.has_role(r));
+		// Has the actor been granted the action by a custom role?
+		let is_granted = self.auth.has_grant(&action, &res);
 		// Is the target database in the actor's level?
 		let db_in_actor_level = self.auth.is_root()
 			|| self.auth.is_ns() && self.auth.level().ns().unwrap() == self.ns()?
@@ -464,14 +466,14 @@ impl Options {
 				&& self.auth.level().db().unwrap() == self.db()?;
 
 		// Is the actor allowed to do the action on the selected database?
-		let is_allowed = match action {
+		let is_allowed = match action.group() {
 			Action::View => {
-				// Today all users have at least View permissions, so if the target database belongs to the user's level, don't check permissions
-				can_view && db_in_actor_level
+				// Built-in roles all have View permissions, so if the target database belongs to the user's level, don't check permissions
+				(can_view || is_granted) && db_in_actor_level
 			}
-			Action::Edit => {
+			_ => {
 				// Editor and Owner roles are allowed to edit, but only if the target database belongs to the user's level
-				can_edit && db_in_actor_level
+				(can_edit || is_granted) && db_in_actor_level
 			}
 		};
 
@@ -9,6 +9,7 @@ use crate::idx::planner::iterators::{CollectorRecord, IteratorRef, ThingIterator
 use crate::idx::planner::IterationStage;
 use crate::key::{graph, thing};
 use crate::kvs::Transaction;
+use crate::sql::after::Cursor;
 use crate::sql::dir::Dir;
 use crate::sql::id::range::IdRange;
 use crate::sql::{Edges, Table, Thing, Value};
@@ -310,8 +311,15 @@ impl<'a> Processor<'a> {
 		let txn = ctx.tx();
 		// Check that the table exists
 		txn.check_ns_db_tb(opt.ns()?, opt.db()?, v, opt.strict).await?;
-		// Prepare the start and end keys
-		let beg = thing::prefix(opt.ns()?, opt.db()?, v);
+		// Prepare the start and end keys, resuming after any cursor
+		let beg = match Self::after(stk, ctx, opt, stm, v).await? {
+			Some(cursor) => {
+				let mut key = thing::new(opt.ns()?, opt.db()?, v, &cursor.rid.id).encode().unwrap();
+				key.push(0x00);
+				key
+			}
+			None => thing::prefix(opt.ns()?, opt.db()?, v),
+		};
 		let end = thing::suffix(opt.ns()?, opt.db()?, v);
 		// Create a new iterable range
 		let mut stream = txn.stream(beg..end, opt.version);
@@ -354,7 +362,7 @@ impl<'a> Processor<'a> {
 		// Check that the table exists
 		txn.check_ns_db_tb(opt.ns()?, opt.db()?, &tb, opt.strict).await?;
 		// Prepare the range start key
-		let beg = match &r.beg {
+		let mut beg = match &r.beg {
 			Bound::Unbounded => thing::prefix(opt.ns()?, opt.db()?, &tb),
 			Bound::Included(v) => thing::new(opt.ns()?, opt.db()?, &tb, v).encode().unwrap(),
 			Bound::Excluded(v) => {
@@ -373,6 +381,18 @@ impl<'a> Processor<'a> {
 				key
 			}
 		};
+		// Resume after any cursor within the range
+		if let Some(cursor) = Self::after(stk, ctx, opt, stm, &tb).await? {
+			let mut key = thing::new(opt.ns()?, opt.db()?, &tb, &cursor.rid.id).encode().unwrap();
+			key.push(0x00);
+			if key > beg {
+				beg = key;
+			}
+		}
+		// Check that the range is not empty
+		if beg >= end {
+			return Ok(());
+		}
 		// Create a new iterable range
 		let mut stream = txn.stream(beg..end, None);
 		// Loop until no more entries
@@ -529,6 +549,10 @@ impl<'a> Processor<'a> {
 		ctx.tx().check_ns_db_tb(opt.ns()?, opt.db()?, &table.0, opt.strict).await?;
 		if let Some(exe) = ctx.get_query_executor() {
 			if let Some(mut iterator) = exe.new_iterator(opt, irf).await? {
+				// Resume after any cursor
+				if let Some(cursor) = Self::after(stk, ctx, opt, stm, &table.0).await? {
+					exe.seek_iterator(stk, ctx, opt, irf, &mut iterator, &cursor).await?;
+				}
 				// Get the first batch
 				let mut to_process = Self::next_batch(ctx, opt, &mut iterator).await?;
 
@@ -558,6 +582,28 @@ impl<'a> Processor<'a> {
 		})
 	}
 
+	/// Returns the cursor which the iteration of a table resumes after, if
+	/// an AFTER clause was specified with a record of this table
+	async fn after(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		stm: &Statement<'_>,
+		tb: &str,
+	) -> Result<Option<Cursor>, Error> {
+		match stm.after() {
+			Some(v) => {
+				// The cursor follows the order of the keys, not the order of the results
+				if stm.order().is_some() {
+					return Err(Error::InvalidAfterOrder);
+				}
+				let cursor = v.process(stk, ctx, opt, None).await?;
+				Ok((cursor.rid.tb == tb).then_some(cursor))
+			}
+			None => Ok(None),
+		}
+	}
+
 	async fn next_batch(
 		ctx: &Context,
 		opt: &Options,
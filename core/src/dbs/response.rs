@@ -1,5 +1,6 @@
 use crate::err::Error;
 use crate::sql::Value as CoreValue;
+use channel::Sender;
 use revision::revisioned;
 use revision::Revisioned;
 use serde::ser::SerializeStruct;
@@ -119,3 +120,59 @@ impl Revisioned for Response {
 		1
 	}
 }
+
+/// A row of a statement result, which is sent as soon as it has been produced
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Row {
+	/// The index of the statement within the query
+	pub query: usize,
+	/// The value of the row
+	pub value: CoreValue,
+}
+
+/// A batch of rows from the same statement, which is sent before the response of a streamed request
+#[revisioned(revision = 1)]
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Batch {
+	/// The index of the statement within the query
+	pub query: usize,
+	/// The rows in the batch
+	pub rows: Vec<CoreValue>,
+}
+
+impl Batch {
+	pub fn new(query: usize, rows: Vec<CoreValue>) -> Self {
+		Self {
+			query,
+			rows,
+		}
+	}
+}
+
+/// Sends the rows of a single statement result
+#[derive(Clone)]
+pub(crate) struct RowSender {
+	query: usize,
+	sender: Sender<Row>,
+}
+
+impl RowSender {
+	pub(crate) fn new(query: usize, sender: Sender<Row>) -> Self {
+		Self {
+			query,
+			sender,
+		}
+	}
+
+	/// Sends a row, waiting until there is space in the channel.
+	/// Returns false if the receiver has been dropped.
+	pub(crate) async fn send(&self, value: CoreValue) -> bool {
+		let row = Row {
+			query: self.query,
+			value,
+		};
+		self.sender.send(row).await.is_ok()
+	}
+}
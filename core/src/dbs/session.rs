@@ -1,4 +1,4 @@
-use crate::ctx::MutableContext;
+use crate::ctx::{Canceller, MutableContext};
 use crate::iam::Auth;
 use crate::iam::{Level, Role};
 use crate::sql::value::Value;
@@ -25,12 +25,16 @@ pub struct Session {
 	pub db: Option<String>,
 	/// The current access method
 	pub ac: Option<String>,
+	/// The current access grant, when the session can be revoked
+	pub gr: Option<String>,
 	/// The current authentication token
 	pub tk: Option<Value>,
 	/// The current record authentication data
 	pub rd: Option<Value>,
 	/// The current expiration time of the session
 	pub exp: Option<i64>,
+	/// The canceller which stops the queries of the current request
+	pub cancel: Option<Canceller>,
 }
 
 impl Session {
@@ -58,6 +62,12 @@ impl Session {
 		self
 	}
 
+	/// Set the canceller which stops the queries run with the session
+	pub fn with_canceller(mut self, canceller: Canceller) -> Session {
+		self.cancel = Some(canceller);
+		self
+	}
+
 	/// Retrieves the selected namespace
 	pub(crate) fn ns(&self) -> Option<Arc<str>> {
 		self.ns.as_deref().map(Into::into)
@@ -98,6 +108,7 @@ impl Session {
 			"ac".to_string() => self.ac.to_owned().into(),
 			"exp".to_string() => self.exp.to_owned().into(),
 			"db".to_string() => self.db.to_owned().into(),
+			"gr".to_string() => self.gr.to_owned().into(),
 			"id".to_string() => self.id.to_owned().into(),
 			"ip".to_string() => self.ip.to_owned().into(),
 			"ns".to_string() => self.ns.to_owned().into(),
@@ -106,6 +117,10 @@ impl Session {
 			"tk".to_string() => self.tk.to_owned().into(),
 		});
 		ctx.add_value("session", val.into());
+		// Add the request cancellation
+		if let Some(canceller) = &self.cancel {
+			ctx.add_canceller(canceller);
+		}
 	}
 
 	/// Create a system session for a given level and role
@@ -135,6 +150,7 @@ impl Session {
 	pub fn for_record(ns: &str, db: &str, ac: &str, rid: Value) -> Session {
 		Session {
 			ac: Some(ac.to_owned()),
+			gr: None,
 			au: Arc::new(Auth::for_record(rid.to_string(), ns, db, ac)),
 			rt: false,
 			ip: None,
@@ -145,6 +161,7 @@ impl Session {
 			tk: None,
 			rd: Some(rid),
 			exp: None,
+			cancel: None,
 		}
 	}
 
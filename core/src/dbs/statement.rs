@@ -1,3 +1,4 @@
+use crate::sql::after::After;
 use crate::sql::cond::Cond;
 use crate::sql::data::Data;
 use crate::sql::fetch::Fetchs;
@@ -207,6 +208,14 @@ impl<'a> Statement<'a> {
 			_ => None,
 		}
 	}
+	/// Returns any AFTER clause if specified
+	#[inline]
+	pub fn after(&self) -> Option<&After> {
+		match self {
+			Statement::Select(v) => v.after.as_ref(),
+			_ => None,
+		}
+	}
 	/// Returns any LIMIT clause if specified
 	#[inline]
 	pub fn limit(&self) -> Option<&Limit> {
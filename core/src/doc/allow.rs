@@ -1,8 +1,10 @@
 use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::dbs::Statement;
+use crate::doc::CursorDoc;
 use crate::doc::Document;
 use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
 use crate::sql::permission::Permission;
 use reblessive::tree::Stk;
 
@@ -17,7 +19,7 @@ impl Document {
 		// Check if this record exists
 		if self.id.is_some() {
 			// Should we run permissions checks?
-			if opt.check_perms(stm.into())? {
+			if opt.check_perms(stm.into(), ResourceKind::Record)? {
 				// Check that record authentication matches session
 				if opt.auth.is_record() {
 					let ns = opt.ns()?;
@@ -36,40 +38,65 @@ impl Document {
 				// Get the table
 				let tb = self.tb(ctx, opt).await?;
 				// Get the permission clause
-				let perms = if stm.is_delete() {
-					&tb.permissions.delete
+				let (perms, action) = if stm.is_delete() {
+					(&tb.permissions.delete, Action::Delete)
 				} else if stm.is_select() {
-					&tb.permissions.select
+					(&tb.permissions.select, Action::Select)
 				} else if self.is_new() {
-					&tb.permissions.create
+					(&tb.permissions.create, Action::Create)
 				} else {
-					&tb.permissions.update
+					(&tb.permissions.update, Action::Update)
+				};
+				// Get the document to check
+				let doc = match stm.is_delete() {
+					true => &self.initial,
+					false => &self.current,
 				};
 				// Process the table permissions
 				match perms {
 					Permission::None => return Err(Error::Ignore),
-					Permission::Full => return Ok(()),
-					Permission::Specific(e) => {
+					Permission::Full => (),
+					Permission::Specific(_) | Permission::Policy(_) => {
+						// Fetch the permission condition
+						let e = perms.condition(ctx, opt).await?;
 						// Disable permissions
 						let opt = &opt.new_with_perms(false);
 						// Process the PERMISSION clause
-						if !e
-							.compute(
-								stk,
-								ctx,
-								opt,
-								Some(match stm.is_delete() {
-									true => &self.initial,
-									false => &self.current,
-								}),
-							)
-							.await?
-							.is_truthy()
-						{
+						if !e.compute(stk, ctx, opt, Some(doc)).await?.is_truthy() {
 							return Err(Error::Ignore);
 						}
 					}
 				}
+				// Process the attached policies
+				self.allow_policies(stk, ctx, opt, &tb.name, &action, doc).await?;
+			}
+		}
+		// Carry on
+		Ok(())
+	}
+	/// Check any policies which are attached to this table
+	pub(super) async fn allow_policies(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		tb: &str,
+		action: &Action,
+		doc: &CursorDoc,
+	) -> Result<(), Error> {
+		// Get the NS and DB
+		let ns = opt.ns()?;
+		let db = opt.db()?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Get the policies which are attached to this table
+		let pls = txn.all_tb_policies(ns, db, tb).await?;
+		// Disable permissions
+		let opt = &opt.new_with_perms(false);
+		// Process the policies which apply to this action
+		for pl in pls.iter().filter(|pl| pl.applies(action)) {
+			if !pl.cond.compute(stk, ctx, opt, Some(doc)).await?.is_truthy() {
+				return Err(Error::Ignore);
 			}
 		}
 		// Carry on
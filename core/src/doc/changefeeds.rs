@@ -25,13 +25,22 @@ impl Document {
 		if let Some(cf) = db.as_ref().changefeed.as_ref().or(tb.as_ref().changefeed.as_ref()) {
 			// Create the changefeed entry
 			if let Some(id) = &self.id {
+				// Encrypt the values of any encrypted fields, as they are stored
+				let initial = match Self::encrypt_fields(ctx, opt, id, &self.initial.doc).await? {
+					Some(v) => v.into(),
+					None => self.initial.doc.clone(),
+				};
+				let current = match Self::encrypt_fields(ctx, opt, id, &self.current.doc).await? {
+					Some(v) => v.into(),
+					None => self.current.doc.clone(),
+				};
 				txn.lock().await.record_change(
 					opt.ns()?,
 					opt.db()?,
 					tb.name.as_str(),
 					id.as_ref(),
-					self.initial.doc.clone(),
-					self.current.doc.clone(),
+					initial,
+					current,
 					cf.store_diff,
 				);
 			}
@@ -34,16 +34,20 @@ impl Document {
 			};
 			// Setup a new document
 			let mut doc = Document::new(pro.rid, pro.ir, ins.0, ins.1);
-			// Process the statement
-			let res = match stm {
-				Statement::Select(_) => doc.select(stk, ctx, opt, stm).await,
-				Statement::Create(_) => doc.create(stk, ctx, opt, stm).await,
-				Statement::Upsert(_) => doc.upsert(stk, ctx, opt, stm).await,
-				Statement::Update(_) => doc.update(stk, ctx, opt, stm).await,
-				Statement::Relate(_) => doc.relate(stk, ctx, opt, stm).await,
-				Statement::Delete(_) => doc.delete(stk, ctx, opt, stm).await,
-				Statement::Insert(_) => doc.insert(stk, ctx, opt, stm).await,
-				_ => unreachable!(),
+			// Decrypt the encrypted fields of the stored record
+			let res = match doc.decrypt(ctx, opt).await {
+				// Process the statement
+				Ok(_) => match stm {
+					Statement::Select(_) => doc.select(stk, ctx, opt, stm).await,
+					Statement::Create(_) => doc.create(stk, ctx, opt, stm).await,
+					Statement::Upsert(_) => doc.upsert(stk, ctx, opt, stm).await,
+					Statement::Update(_) => doc.update(stk, ctx, opt, stm).await,
+					Statement::Relate(_) => doc.relate(stk, ctx, opt, stm).await,
+					Statement::Delete(_) => doc.delete(stk, ctx, opt, stm).await,
+					Statement::Insert(_) => doc.insert(stk, ctx, opt, stm).await,
+					_ => unreachable!(),
+				},
+				Err(e) => Err(e),
 			};
 			// Check the result
 			let res = match res {
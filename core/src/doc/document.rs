@@ -213,7 +213,7 @@ impl Document {
 				value: _,
 			}) => {
 				// Allowed to run?
-				opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Db)?;
+				opt.is_allowed(Action::Define, ResourceKind::Table, &Base::Db)?;
 				// We can create the table automatically
 				txn.ensure_ns_db_tb(opt.ns()?, opt.db()?, &rid.tb, opt.strict).await
 			}
@@ -3,10 +3,12 @@ use crate::dbs::Options;
 use crate::dbs::Statement;
 use crate::doc::Document;
 use crate::err::Error;
-use crate::iam::Action;
+use crate::iam::{Action, ResourceKind};
 use crate::sql::permission::Permission;
+use crate::sql::statements::DefineFieldStatement;
 use crate::sql::value::Value;
 use crate::sql::Kind;
+use crate::sql::Thing;
 use reblessive::tree::Stk;
 use std::sync::Arc;
 
@@ -145,7 +147,12 @@ impl Document {
 					}
 				}
 				// Check for a PERMISSIONS clause
-				if opt.check_perms(Action::Edit)? {
+				let action = if self.is_new() {
+					Action::Create
+				} else {
+					Action::Update
+				};
+				if opt.check_perms(action, ResourceKind::Record)? {
 					// Get the permission clause
 					let perms = if self.is_new() {
 						&fd.permissions.create
@@ -166,7 +173,9 @@ impl Document {
 						// is a custom expression, so
 						// we check the expression and
 						// revert the field if denied.
-						Permission::Specific(e) => {
+						Permission::Specific(_) | Permission::Policy(_) => {
+							// Fetch the permission condition
+							let e = perms.condition(ctx, opt).await?;
 							// Disable permissions
 							let opt = &opt.new_with_perms(false);
 							// Configure the context
@@ -194,4 +203,74 @@ impl Document {
 		// Carry on
 		Ok(())
 	}
+
+	/// Decrypts the encrypted fields of the record as it was loaded from storage,
+	/// so that the document is processed with the values of its fields in clear
+	pub(super) async fn decrypt(&mut self, ctx: &Context, opt: &Options) -> Result<(), Error> {
+		// Check if this record exists
+		let Some(rid) = self.id.clone() else {
+			return Ok(());
+		};
+		// Decrypt the stored record
+		if let Some(val) = Self::decrypt_fields(ctx, opt, &rid, self.initial.doc.as_ref()).await? {
+			let val = Arc::new(val);
+			self.initial.doc = val.clone().into();
+			self.current.doc = val.into();
+		}
+		// Carry on
+		Ok(())
+	}
+
+	/// Returns a copy of a stored record with the values of its encrypted fields
+	/// in clear, or nothing if the record has no encrypted fields
+	pub(crate) async fn decrypt_fields(
+		ctx: &Context,
+		opt: &Options,
+		rid: &Thing,
+		val: &Value,
+	) -> Result<Option<Value>, Error> {
+		Self::convert_fields(ctx, opt, rid, val, |fd, v| fd.decrypt_value(Some(rid), v)).await
+	}
+
+	/// Returns a copy of a record with the values of its encrypted fields
+	/// encrypted, as it is stored, or nothing if the record has no encrypted fields
+	pub(crate) async fn encrypt_fields(
+		ctx: &Context,
+		opt: &Options,
+		rid: &Thing,
+		val: &Value,
+	) -> Result<Option<Value>, Error> {
+		Self::convert_fields(ctx, opt, rid, val, |fd, v| fd.encrypt_value(Some(rid), &v)).await
+	}
+
+	/// Converts the values of the encrypted fields of a record
+	async fn convert_fields<F>(
+		ctx: &Context,
+		opt: &Options,
+		rid: &Thing,
+		val: &Value,
+		convert: F,
+	) -> Result<Option<Value>, Error>
+	where
+		F: Fn(&DefineFieldStatement, Value) -> Result<Value, Error>,
+	{
+		// Check if the record exists
+		if !val.is_some() {
+			return Ok(None);
+		}
+		// Check if there are any encrypted fields
+		let fds = ctx.tx().all_tb_fields(opt.ns()?, opt.db()?, &rid.tb).await?;
+		if !fds.iter().any(|fd| fd.encrypted.is_some()) {
+			return Ok(None);
+		}
+		// Convert the values of the encrypted fields
+		let mut val = val.clone();
+		for fd in fds.iter().filter(|fd| fd.encrypted.is_some()) {
+			for k in val.each(&fd.name).iter() {
+				let v = convert(fd, val.pick(k))?;
+				val.put(k, v);
+			}
+		}
+		Ok(Some(val))
+	}
 }
@@ -4,6 +4,7 @@ use crate::dbs::{Force, Statement};
 use crate::doc::{CursorDoc, Document};
 use crate::err::Error;
 use crate::idx::ft::FtIndex;
+use crate::idx::sparse::SparseIndex;
 use crate::idx::trees::mtree::MTreeIndex;
 use crate::idx::IndexKeyBase;
 use crate::key;
@@ -11,7 +12,7 @@ use crate::key;
 use crate::kvs::ConsumeResult;
 use crate::kvs::TransactionType;
 use crate::sql::array::Array;
-use crate::sql::index::{HnswParams, Index, MTreeParams, SearchParams};
+use crate::sql::index::{HnswParams, Index, MTreeParams, SearchParams, SparseParams};
 use crate::sql::statements::DefineIndexStatement;
 use crate::sql::{Part, Thing, Value};
 use reblessive::tree::Stk;
@@ -94,6 +95,7 @@ impl Document {
 			Index::Search(p) => ic.index_full_text(stk, ctx, p).await?,
 			Index::MTree(p) => ic.index_mtree(stk, ctx, p).await?,
 			Index::Hnsw(p) => ic.index_hnsw(ctx, p).await?,
+			Index::Sparse(p) => ic.index_sparse(ctx, p).await?,
 		}
 		Ok(())
 	}
@@ -102,6 +104,8 @@ impl Document {
 	/// Eg. IF the index is composed of the columns `name` and `instrument`
 	/// Given this doc: { "id": 1, "instrument":"piano", "name":"Tobie" }
 	/// It will return: ["Tobie", "piano"]
+	/// If the index is partial, and the document does not match its condition, it returns None.
+	/// The values of encrypted fields are expected in clear, and are returned encrypted.
 	pub(crate) async fn build_opt_values(
 		stk: &mut Stk,
 		ctx: &Context,
@@ -112,9 +116,20 @@ impl Document {
 		if !doc.doc.as_ref().is_some() {
 			return Ok(None);
 		}
+		if let Some(cond) = &ix.cond {
+			if !cond.compute(stk, ctx, opt, Some(doc)).await?.is_truthy() {
+				return Ok(None);
+			}
+		}
+		let fds = ctx.tx().all_tb_fields(opt.ns()?, opt.db()?, &ix.what).await?;
 		let mut o = Vec::with_capacity(ix.cols.len());
 		for i in ix.cols.iter() {
 			let v = i.compute(stk, ctx, opt, Some(doc)).await?;
+			// Encrypted fields are indexed as they are stored
+			let v = match fds.iter().find(|fd| fd.encrypted.is_some() && fd.name == *i) {
+				Some(fd) => fd.encrypt_value(None, &v)?,
+				None => v,
+			};
 			o.push(v);
 		}
 		Ok(Some(o))
@@ -440,4 +455,20 @@ impl<'a> IndexOperation<'a> {
 		}
 		Ok(())
 	}
+
+	async fn index_sparse(&mut self, ctx: &Context, p: &SparseParams) -> Result<(), Error> {
+		let txn = ctx.tx();
+		let ikb = IndexKeyBase::new(self.opt.ns()?, self.opt.db()?, self.ix)?;
+		let mut sp =
+			SparseIndex::new(ctx.get_index_stores(), &txn, ikb, p, TransactionType::Write).await?;
+		// Delete the old index data
+		if let Some(o) = self.o.take() {
+			sp.remove_document(&txn, self.rid, &o).await?;
+		}
+		// Create the new index data
+		if let Some(n) = self.n.take() {
+			sp.index_document(&txn, self.rid, &n).await?;
+		}
+		sp.finish(&txn).await
+	}
 }
@@ -6,6 +6,8 @@ use crate::dbs::Statement;
 use crate::doc::CursorDoc;
 use crate::doc::Document;
 use crate::err::Error;
+use crate::iam::verify::resolve_grants;
+use crate::iam::{Action as IamAction, ResourceKind};
 use crate::sql::paths::AC;
 use crate::sql::paths::META;
 use crate::sql::paths::RD;
@@ -47,13 +49,6 @@ impl Document {
 				} else {
 					Value::from("UPDATE")
 				};
-				let current = self.current.doc.as_arc();
-				let initial = self.initial.doc.as_arc();
-				// Check if this is a delete statement
-				let doc = match stm.is_delete() {
-					true => &self.initial,
-					false => &self.current,
-				};
 				// Ensure that a session exists on the LIVE query
 				let sess = match lv.session.as_ref() {
 					Some(v) => v,
@@ -80,22 +75,42 @@ impl Document {
 				lqctx.add_value("auth", sess.pick(RD.as_ref()).into());
 				lqctx.add_value("token", sess.pick(TK.as_ref()).into());
 				lqctx.add_value("session", sess.clone().into());
-				// Add $before, $after, $value, and $event params
-				// to this LIVE query so the user can use these
-				// within field projections and WHERE clauses.
-				lqctx.add_value("event", met.into());
-				lqctx.add_value("value", current.clone());
-				lqctx.add_value("after", current);
-				lqctx.add_value("before", initial);
+				let lqctx = lqctx.freeze();
 				// We need to create a new options which we will
 				// use for processing this LIVE query statement.
 				// This ensures that we are using the auth data
 				// of the user who created the LIVE query.
+				// The grants of its custom roles are resolved now,
+				// so that the changes to a role apply to the query.
+				let auth = if auth.custom_roles().is_empty() {
+					auth
+				} else {
+					let grants =
+						resolve_grants(&ctx.tx(), auth.custom_roles(), auth.level()).await?;
+					auth.with_grants(grants)
+				};
 				let lqopt = opt.new_with_perms(true).with_auth(Arc::from(auth));
+				// Mask any encrypted fields which may not be
+				// seen in clear by the user who created the
+				// LIVE query, before these are made available.
+				let (mut initial, mut current) = self.masked(stk, &lqctx, &lqopt).await?;
+				// Add $before, $after, $value, and $event params
+				// to this LIVE query so the user can use these
+				// within field projections and WHERE clauses.
+				let mut lqctx = MutableContext::new(&lqctx);
+				lqctx.add_value("event", met.into());
+				lqctx.add_value("value", current.doc.as_arc());
+				lqctx.add_value("after", current.doc.as_arc());
+				lqctx.add_value("before", initial.doc.as_arc());
+				let lqctx = lqctx.freeze();
+				// Check if this is a delete statement
+				let (doc, out) = match stm.is_delete() {
+					true => (&self.initial, &initial),
+					false => (&self.current, &current),
+				};
 				// First of all, let's check to see if the WHERE
 				// clause of the LIVE query is matched by this
 				// document. If it is then we can continue.
-				let lqctx = lqctx.freeze();
 				match self.lq_check(stk, &lqctx, &lqopt, &lq, doc).await {
 					Err(Error::Ignore) => continue,
 					Err(e) => return Err(e),
@@ -124,7 +139,7 @@ impl Document {
 								let lqopt: &Options = &lqopt.new_with_futures(true);
 								// Output the full document before any changes were applied
 								let mut value =
-									doc.doc.as_ref().compute(stk, &lqctx, lqopt, Some(doc)).await?;
+									out.doc.as_ref().compute(stk, &lqctx, lqopt, Some(out)).await?;
 								// Remove metadata fields on output
 								value.del(stk, &lqctx, lqopt, &*META).await?;
 								// Output result
@@ -195,14 +210,16 @@ impl Document {
 		doc: &CursorDoc,
 	) -> Result<(), Error> {
 		// Should we run permissions checks?
-		if opt.check_perms(stm.into())? {
+		if opt.check_perms(stm.into(), ResourceKind::Record)? {
 			// Get the table
 			let tb = self.tb(ctx, opt).await?;
 			// Process the table permissions
 			match &tb.permissions.select {
 				Permission::None => return Err(Error::Ignore),
-				Permission::Full => return Ok(()),
-				Permission::Specific(e) => {
+				Permission::Full => (),
+				Permission::Specific(_) | Permission::Policy(_) => {
+					// Fetch the permission condition
+					let e = tb.permissions.select.condition(ctx, opt).await?;
 					// Disable permissions
 					let opt = &opt.new_with_perms(false);
 					// Process the PERMISSION clause
@@ -211,6 +228,8 @@ impl Document {
 					}
 				}
 			}
+			// Process the attached policies
+			self.allow_policies(stk, ctx, opt, &tb.name, &IamAction::Select, doc).await?;
 		}
 		// Carry on
 		Ok(())
@@ -1,13 +1,14 @@
 use crate::ctx::{Context, MutableContext};
 use crate::dbs::Options;
 use crate::dbs::Statement;
-use crate::doc::Document;
+use crate::doc::{CursorDoc, Document};
 use crate::err::Error;
-use crate::iam::Action;
+use crate::iam::{Action, ResourceKind};
 use crate::sql::idiom::Idiom;
 use crate::sql::output::Output;
 use crate::sql::paths::META;
 use crate::sql::permission::Permission;
+use crate::sql::statements::DefineFieldStatement;
 use crate::sql::value::Value;
 use reblessive::tree::Stk;
 use std::sync::Arc;
@@ -25,6 +26,8 @@ impl Document {
 	) -> Result<Value, Error> {
 		// Ensure futures are run
 		let opt = &opt.new_with_futures(true);
+		// Mask any encrypted fields which may not be seen in clear
+		let (mut initial, mut current) = self.masked(stk, ctx, opt).await?;
 		// Process the desired output
 		let mut out = match stm.output() {
 			Some(v) => match v {
@@ -32,58 +35,50 @@ impl Document {
 				Output::Null => Ok(Value::Null),
 				Output::Diff => {
 					// Output a DIFF of any changes applied to the document
-					Ok(self
-						.initial
-						.doc
-						.as_ref()
-						.diff(self.current.doc.as_ref(), Idiom::default())
-						.into())
+					Ok(initial.doc.as_ref().diff(current.doc.as_ref(), Idiom::default()).into())
 				}
 				Output::After => {
 					// Output the full document after all changes were applied
-					self.current.doc.as_ref().compute(stk, ctx, opt, Some(&self.current)).await
+					current.doc.as_ref().compute(stk, ctx, opt, Some(&current)).await
 				}
 				Output::Before => {
 					// Output the full document before any changes were applied
-					self.initial.doc.as_ref().compute(stk, ctx, opt, Some(&self.initial)).await
+					initial.doc.as_ref().compute(stk, ctx, opt, Some(&initial)).await
 				}
 				Output::Fields(v) => {
 					// Configure the context
 					let mut ctx = MutableContext::new(ctx);
-					ctx.add_value("after", self.current.doc.as_arc());
-					ctx.add_value("before", self.initial.doc.as_arc());
+					ctx.add_value("after", current.doc.as_arc());
+					ctx.add_value("before", initial.doc.as_arc());
 					let ctx = ctx.freeze();
 					// Output the specified fields
-					v.compute(stk, &ctx, opt, Some(&self.current), false).await
+					v.compute(stk, &ctx, opt, Some(&current), false).await
 				}
 			},
 			None => match stm {
 				Statement::Live(s) => match s.expr.len() {
-					0 => Ok(self
-						.initial
-						.doc
-						.as_ref()
-						.diff(self.current.doc.as_ref(), Idiom::default())
-						.into()),
-					_ => s.expr.compute(stk, ctx, opt, Some(&self.current), false).await,
+					0 => {
+						Ok(initial.doc.as_ref().diff(current.doc.as_ref(), Idiom::default()).into())
+					}
+					_ => s.expr.compute(stk, ctx, opt, Some(&current), false).await,
 				},
 				Statement::Select(s) => {
-					s.expr.compute(stk, ctx, opt, Some(&self.current), s.group.is_some()).await
+					s.expr.compute(stk, ctx, opt, Some(&current), s.group.is_some()).await
 				}
 				Statement::Create(_) => {
-					self.current.doc.as_ref().compute(stk, ctx, opt, Some(&self.current)).await
+					current.doc.as_ref().compute(stk, ctx, opt, Some(&current)).await
 				}
 				Statement::Upsert(_) => {
-					self.current.doc.as_ref().compute(stk, ctx, opt, Some(&self.current)).await
+					current.doc.as_ref().compute(stk, ctx, opt, Some(&current)).await
 				}
 				Statement::Update(_) => {
-					self.current.doc.as_ref().compute(stk, ctx, opt, Some(&self.current)).await
+					current.doc.as_ref().compute(stk, ctx, opt, Some(&current)).await
 				}
 				Statement::Relate(_) => {
-					self.current.doc.as_ref().compute(stk, ctx, opt, Some(&self.current)).await
+					current.doc.as_ref().compute(stk, ctx, opt, Some(&current)).await
 				}
 				Statement::Insert(_) => {
-					self.current.doc.as_ref().compute(stk, ctx, opt, Some(&self.current)).await
+					current.doc.as_ref().compute(stk, ctx, opt, Some(&current)).await
 				}
 				_ => Err(Error::Ignore),
 			},
@@ -91,7 +86,7 @@ impl Document {
 		// Check if this record exists
 		if self.id.is_some() {
 			// Should we run permissions checks?
-			if opt.check_perms(Action::View)? {
+			if opt.check_perms(Action::Select, ResourceKind::Record)? {
 				// Loop through all field statements
 				for fd in self.fd(ctx, opt).await?.iter() {
 					// Loop over each field in document
@@ -100,7 +95,9 @@ impl Document {
 						match &fd.permissions.select {
 							Permission::Full => (),
 							Permission::None => out.del(stk, ctx, opt, k).await?,
-							Permission::Specific(e) => {
+							Permission::Specific(_) | Permission::Policy(_) => {
+								// Fetch the permission condition
+								let e = fd.permissions.select.condition(ctx, opt).await?;
 								// Disable permissions
 								let opt = &opt.new_with_perms(false);
 								// Get the current value
@@ -134,4 +131,61 @@ impl Document {
 		// Output result
 		Ok(out)
 	}
+
+	/// Returns the initial and current documents with the values of their encrypted
+	/// fields masked, unless the current session may see these values in clear
+	pub(super) async fn masked(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+	) -> Result<(CursorDoc, CursorDoc), Error> {
+		let initial = CursorDoc::new(
+			self.initial.rid.clone(),
+			self.initial.ir.clone(),
+			self.initial.doc.as_arc(),
+		);
+		let current = CursorDoc::new(
+			self.current.rid.clone(),
+			self.current.ir.clone(),
+			self.current.doc.as_arc(),
+		);
+		// Users who may edit records always see values in clear
+		if self.id.is_none() || !opt.check_perms(Action::Edit, ResourceKind::Record)? {
+			return Ok((initial, current));
+		}
+		// Check if there are any encrypted fields
+		let fds = self.fd(ctx, opt).await?;
+		if !fds.iter().any(|fd| fd.encrypted.is_some()) {
+			return Ok((initial, current));
+		}
+		// Mask the values of the encrypted fields
+		let initial = Self::mask(stk, ctx, opt, &fds, initial).await?;
+		let current = Self::mask(stk, ctx, opt, &fds, current).await?;
+		Ok((initial, current))
+	}
+
+	/// Masks the values of the encrypted fields of a document
+	async fn mask(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		fds: &[DefineFieldStatement],
+		doc: CursorDoc,
+	) -> Result<CursorDoc, Error> {
+		let mut val = doc.doc.as_ref().clone();
+		for fd in fds.iter() {
+			if let Some(enc) = &fd.encrypted {
+				// Loop over each field in document
+				for k in val.each(&fd.name).iter() {
+					let v = val.pick(k);
+					// Process the REVEAL clause
+					if !enc.reveal(stk, ctx, opt, &doc, &v).await? {
+						val.put(k, enc.mask(&v));
+					}
+				}
+			}
+		}
+		Ok(CursorDoc::new(doc.rid, doc.ir, val))
+	}
 }
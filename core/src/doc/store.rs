@@ -23,12 +23,15 @@ impl Document {
 		let txn = ctx.tx();
 		// Get the record id
 		let rid = self.id.as_ref().unwrap();
+		// Encrypt the values of any encrypted fields
+		let val = Self::encrypt_fields(ctx, opt, rid, self.current.doc.as_ref()).await?;
+		let val = val.as_ref().unwrap_or(self.current.doc.as_ref());
 		// Store the record data
 		let key = crate::key::thing::new(opt.ns()?, opt.db()?, &rid.tb, &rid.id);
 		// Match the statement type
 		match stm {
 			// This is a CREATE statement so try to insert the key
-			Statement::Create(_) => match txn.put(key, self, opt.version).await {
+			Statement::Create(_) => match txn.put(key, val, opt.version).await {
 				// The key already exists, so return an error
 				Err(Error::TxKeyAlreadyExists) => Err(Error::RecordExists {
 					thing: rid.to_string(),
@@ -39,9 +42,9 @@ impl Document {
 				Ok(v) => Ok(v),
 			},
 			// INSERT can be versioned
-			Statement::Insert(_) => txn.set(key, self, opt.version).await,
+			Statement::Insert(_) => txn.set(key, val, opt.version).await,
 			// This is not a CREATE statement, so update the key
-			_ => txn.set(key, self, None).await,
+			_ => txn.set(key, val, None).await,
 		}?;
 		// Carry on
 		Ok(())
@@ -66,6 +66,10 @@ pub enum Error {
 	#[error("There was a problem with a datastore transaction: {0}")]
 	Tx(String),
 
+	/// There was a problem encrypting or decrypting a stored value
+	#[error("There was a problem with encryption at rest: {0}")]
+	Encryption(String),
+
 	/// There was an error when starting a new datastore transaction
 	#[error("There was an error when starting a new datastore transaction")]
 	TxFailure,
@@ -98,6 +102,42 @@ pub enum Error {
 	#[error("Transaction is too large")]
 	TxTooLarge,
 
+	/// The transaction spanning several requests does not exist
+	#[error("The transaction '{0}' does not exist, or has timed out")]
+	TxNotFound(String),
+
+	/// Too many transactions spanning several requests are open on the connection
+	#[error("No more than {0} transactions can be open on a connection")]
+	TxLimit(usize),
+
+	/// A statement in the transaction spanning several requests failed
+	#[error("The transaction can not be committed, as one of its statements failed")]
+	TxStatementFailed,
+
+	/// A transaction statement was used in a transaction spanning several requests
+	#[error("Transactions which span several requests can not be started, committed, or cancelled with a statement")]
+	TxStatementNotAllowed,
+
+	/// The prepared query does not exist
+	#[error("The prepared query '{0}' does not exist")]
+	PreparedNotFound(String),
+
+	/// Too many queries have been prepared on the connection
+	#[error("No more than {0} queries can be prepared on a connection")]
+	PreparedLimit(usize),
+
+	/// The result of a query can not be represented as Arrow record batches
+	#[error("The query result can not be converted to Arrow: {0}")]
+	ArrowConversion(String),
+
+	/// A pagination cursor was used with an ordering other than by record id
+	#[error("A cursor can only be used when ordering by record id")]
+	InvalidCursor,
+
+	/// A condition was used which could write data, or select records other than those filtered
+	#[error("A condition can only read the fields of the records which it filters")]
+	InvalidCondition,
+
 	/// No namespace has been selected
 	#[error("Specify a namespace to use")]
 	NsEmpty,
@@ -195,6 +235,29 @@ pub enum Error {
 		value: String,
 	},
 
+	/// The AFTER clause must evaluate to a record id, or a record
+	#[error("Found {value} but the AFTER clause must evaluate to a record id, or a record")]
+	InvalidAfter {
+		value: String,
+	},
+
+	/// The AFTER clause can not be used when the records are ordered
+	#[error("The AFTER clause can not be used with an ORDER BY clause")]
+	InvalidAfterOrder,
+
+	/// The AFTER clause was used with an index which can not seek to a record
+	#[error("The AFTER clause can not be used when iterating the index '{index}'")]
+	InvalidAfterIndex {
+		index: String,
+	},
+
+	/// The AFTER clause was given a record id, or a record which is not in the index being iterated
+	#[error("The AFTER clause must be given the record {rid}, as found in the index '{index}'")]
+	InvalidAfterRecord {
+		rid: String,
+		index: String,
+	},
+
 	/// There was an error with the provided JavaScript code
 	#[error("Problem with embedded script function. {message}")]
 	InvalidScript {
@@ -431,6 +494,59 @@ pub enum Error {
 		db: String,
 	},
 
+	/// The requested root role does not exist
+	#[error("The root role '{value}' does not exist")]
+	RoleRootNotFound {
+		value: String,
+	},
+
+	/// The requested namespace role does not exist
+	#[error("The role '{value}' does not exist in the namespace '{ns}'")]
+	RoleNsNotFound {
+		value: String,
+		ns: String,
+	},
+
+	/// The requested database role does not exist
+	#[error("The role '{value}' does not exist in the database '{db}'")]
+	RoleDbNotFound {
+		value: String,
+		ns: String,
+		db: String,
+	},
+
+	/// The requested namespace policy does not exist
+	#[error("The policy '{value}' does not exist in the namespace '{ns}'")]
+	PlNsNotFound {
+		value: String,
+		ns: String,
+	},
+
+	/// The requested database policy does not exist
+	#[error("The policy '{value}' does not exist in the database '{db}'")]
+	PlDbNotFound {
+		value: String,
+		ns: String,
+		db: String,
+	},
+
+	/// The requested root signin policy does not exist
+	#[error("The root signin policy does not exist")]
+	SpRootNotFound,
+
+	/// The requested namespace signin policy does not exist
+	#[error("The signin policy does not exist in the namespace '{ns}'")]
+	SpNsNotFound {
+		ns: String,
+	},
+
+	/// The requested database signin policy does not exist
+	#[error("The signin policy does not exist in the database '{db}'")]
+	SpDbNotFound {
+		ns: String,
+		db: String,
+	},
+
 	/// Unable to perform the realtime query
 	#[error("Unable to perform the realtime query")]
 	RealtimeDisabled,
@@ -817,6 +933,29 @@ pub enum Error {
 	#[error("The password did not verify")]
 	InvalidPass,
 
+	/// The user has a second authentication factor which was not provided
+	#[error("A second authentication factor is required for this user")]
+	MissingSecondFactor,
+
+	/// The provided second authentication factor did not verify
+	#[error("The second authentication factor did not verify")]
+	InvalidSecondFactor,
+
+	/// The secret for the time-based one-time passwords is not valid base32
+	#[error("The TOTP secret is not a valid base32 string")]
+	InvalidTotpSecret,
+
+	/// The password does not satisfy the signin policy of the base
+	#[error("The password must be at least {length} characters long and use at least {classes} of lowercase letters, uppercase letters, digits and symbols")]
+	PasswordTooWeak {
+		length: u32,
+		classes: u8,
+	},
+
+	/// There were too many failed signin attempts recently
+	#[error("Too many failed signin attempts, please try again later")]
+	SigninLocked,
+
 	/// There was an error with authentication
 	#[error("There was a problem with authentication")]
 	InvalidAuth,
@@ -934,6 +1073,76 @@ pub enum Error {
 		db: String,
 	},
 
+	/// The requested root role already exists
+	#[error("The root role '{value}' already exists")]
+	RoleRootAlreadyExists {
+		value: String,
+	},
+
+	/// The requested namespace role already exists
+	#[error("The role '{value}' already exists in the namespace '{ns}'")]
+	RoleNsAlreadyExists {
+		value: String,
+		ns: String,
+	},
+
+	/// The requested database role already exists
+	#[error("The role '{value}' already exists in the database '{db}'")]
+	RoleDbAlreadyExists {
+		value: String,
+		ns: String,
+		db: String,
+	},
+
+	/// The requested namespace policy already exists
+	#[error("The policy '{value}' already exists in the namespace '{ns}'")]
+	PlNsAlreadyExists {
+		value: String,
+		ns: String,
+	},
+
+	/// The requested database policy already exists
+	#[error("The policy '{value}' already exists in the database '{db}'")]
+	PlDbAlreadyExists {
+		value: String,
+		ns: String,
+		db: String,
+	},
+
+	/// The requested root signin policy already exists
+	#[error("The root signin policy already exists")]
+	SpRootAlreadyExists,
+
+	/// The requested namespace signin policy already exists
+	#[error("The signin policy already exists in the namespace '{ns}'")]
+	SpNsAlreadyExists {
+		ns: String,
+	},
+
+	/// The requested database signin policy already exists
+	#[error("The signin policy already exists in the database '{db}'")]
+	SpDbAlreadyExists {
+		ns: String,
+		db: String,
+	},
+
+	/// The role name is reserved for a built-in role
+	#[error("The role '{value}' is a built-in role and can not be redefined")]
+	RoleReserved {
+		value: String,
+	},
+
+	/// The condition of a partial index reads something else than the fields of the record
+	#[error("The condition of the index '{index}' can only read the fields of the record, found '{value}'")]
+	InvalidIndexCondition {
+		index: String,
+		value: String,
+	},
+
+	/// The audit log was requested on a datastore which does not record one
+	#[error("The audit log is not enabled on this datastore")]
+	AuditDisabled,
+
 	/// A database index entry for the specified table is already building
 	#[error("Database index `{index}` is currently building")]
 	IndexAlreadyBuilding {
@@ -946,6 +1155,12 @@ pub enum Error {
 	#[error("The session has expired")]
 	ExpiredSession,
 
+	/// The session has been revoked, either because the grant
+	/// which it was established with was revoked, or because
+	/// the session was explicitly invalidated
+	#[error("The session has been revoked")]
+	RevokedSession,
+
 	/// A node task has failed
 	#[error("A node task has failed: {0}")]
 	NodeAgent(&'static str),
@@ -0,0 +1,365 @@
+//! Records authentication attempts and privileged operations in a
+//! tamper-evident audit log, stored in the system key space.
+//!
+//! Every entry is stored under its own key, and is signed with an HMAC
+//! using a key held by the server, so that modified or forged entries can
+//! be detected. As entries do not share any key, recording an entry never
+//! conflicts with other transactions. Removed entries can be detected by
+//! comparing the stored entries with those streamed to a sink.
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::iam::{Auth, Level};
+use crate::kvs::Transaction;
+use crate::sql::{Datetime, Object, Uuid, Value};
+use chrono::DateTime;
+use derive::Store;
+use revision::revisioned;
+use revision::Revisioned;
+use ring::hmac;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::Write;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Mutex;
+
+/// The kinds of events which are recorded in the audit log
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum AuditEvent {
+	Signin,
+	SigninFailure,
+	Signup,
+	SignupFailure,
+	AuthenticateFailure,
+	Define,
+	Remove,
+	Alter,
+	Grant,
+	Revoke,
+}
+
+impl Display for AuditEvent {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Signin => f.write_str("signin"),
+			Self::SigninFailure => f.write_str("signin_failure"),
+			Self::Signup => f.write_str("signup"),
+			Self::SignupFailure => f.write_str("signup_failure"),
+			Self::AuthenticateFailure => f.write_str("authenticate_failure"),
+			Self::Define => f.write_str("define"),
+			Self::Remove => f.write_str("remove"),
+			Self::Alter => f.write_str("alter"),
+			Self::Grant => f.write_str("grant"),
+			Self::Revoke => f.write_str("revoke"),
+		}
+	}
+}
+
+/// A single entry in the audit log
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[non_exhaustive]
+pub struct AuditEntry {
+	pub id: Uuid,
+	pub time: Datetime,
+	pub event: AuditEvent,
+	pub actor: Option<String>,
+	pub level: String,
+	pub ns: Option<String>,
+	pub db: Option<String>,
+	pub detail: String,
+	pub hash: String,
+}
+
+impl AuditEntry {
+	pub(crate) fn new(event: AuditEvent, detail: impl Into<String>) -> Self {
+		let time = Datetime::default();
+		Self {
+			id: Uuid::new_v7_from_datetime(time.clone()),
+			time,
+			event,
+			actor: None,
+			level: Level::No.level_name().to_owned(),
+			ns: None,
+			db: None,
+			detail: detail.into(),
+			hash: String::new(),
+		}
+	}
+
+	/// Attributes the entry to an authenticated actor
+	pub(crate) fn with_auth(mut self, auth: &Auth) -> Self {
+		let level = auth.level();
+		self.actor = (!auth.is_anon()).then(|| auth.id().to_owned());
+		self.level = level.level_name().to_owned();
+		self.ns = level.ns().map(ToOwned::to_owned);
+		self.db = level.db().map(ToOwned::to_owned);
+		self
+	}
+
+	/// Attributes the entry to the actor running a statement,
+	/// on the namespace and database the statement applies to
+	pub(crate) fn with_opt(mut self, opt: &Options) -> Self {
+		self = self.with_auth(&opt.auth);
+		self.ns = opt.ns().ok().map(ToOwned::to_owned);
+		self.db = opt.db().ok().map(ToOwned::to_owned);
+		self
+	}
+
+	/// Attributes the entry to the target of an authentication attempt
+	pub(crate) fn with_target(
+		mut self,
+		actor: Option<String>,
+		ns: Option<String>,
+		db: Option<String>,
+	) -> Self {
+		self.level = match (&ns, &db) {
+			(None, _) => "Root",
+			(Some(_), None) => "Namespace",
+			(Some(_), Some(_)) => "Database",
+		}
+		.to_owned();
+		self.actor = actor;
+		self.ns = ns;
+		self.db = db;
+		self
+	}
+
+	/// Attributes the entry to the target of a signin or signup attempt
+	pub(crate) fn with_vars(self, vars: &Object) -> Self {
+		let get = |a: &str, b: &str| vars.get(a).or_else(|| vars.get(b)).map(Value::to_raw_string);
+		let user = vars.get("user").map(Value::to_raw_string);
+		self.with_target(user, get("NS", "ns"), get("DB", "db"))
+	}
+
+	/// The timestamp used to order entries in the key space
+	fn timestamp(&self) -> u64 {
+		self.time.timestamp_nanos_opt().unwrap_or_default() as u64
+	}
+
+	/// The serialized entry which is signed, covering every field but the hash itself
+	fn message(&self) -> Result<Vec<u8>, Error> {
+		let entry = AuditEntry {
+			hash: String::new(),
+			..self.clone()
+		};
+		let mut buf = Vec::new();
+		entry.serialize_revisioned(&mut buf)?;
+		Ok(buf)
+	}
+
+	/// Signs this entry with the key of the audit log
+	fn sign(&mut self, key: &hmac::Key) -> Result<(), Error> {
+		self.hash = hex::encode(hmac::sign(key, &self.message()?));
+		Ok(())
+	}
+
+	/// Checks that this entry was signed with the key of the audit log, and has not been modified since
+	fn verify(&self, key: &hmac::Key) -> Result<bool, Error> {
+		let Ok(tag) = hex::decode(&self.hash) else {
+			return Ok(false);
+		};
+		Ok(hmac::verify(key, &self.message()?, &tag).is_ok())
+	}
+
+	/// Converts this entry to JSON, as it is written to a sink
+	pub(crate) fn json(&self) -> serde_json::Value {
+		Value::from(Object::from(self.clone())).into_json()
+	}
+
+	/// Checks whether this entry applies to the specified namespace and database
+	pub(crate) fn applies(&self, ns: Option<&str>, db: Option<&str>) -> bool {
+		(ns.is_none() || self.ns.as_deref() == ns) && (db.is_none() || self.db.as_deref() == db)
+	}
+}
+
+impl From<AuditEntry> for Object {
+	fn from(v: AuditEntry) -> Self {
+		Object::from(map! {
+			"id".to_string() => Value::from(v.id),
+			"time".to_string() => Value::from(v.time),
+			"event".to_string() => Value::from(v.event.to_string()),
+			"actor".to_string() => Value::from(v.actor),
+			"level".to_string() => Value::from(v.level),
+			"ns".to_string() => Value::from(v.ns),
+			"db".to_string() => Value::from(v.db),
+			"detail".to_string() => Value::from(v.detail),
+			"hash".to_string() => Value::from(v.hash),
+		})
+	}
+}
+
+/// A destination to which audit entries are streamed as they are recorded
+#[non_exhaustive]
+pub enum AuditSink {
+	/// Emits every entry as a structured log event
+	Log,
+	/// Appends every entry to a file as a line of JSON
+	#[cfg(not(target_arch = "wasm32"))]
+	File(Mutex<std::fs::File>),
+}
+
+impl AuditSink {
+	/// Opens a file sink, appending to the file if it already exists
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+		let file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+		Ok(Self::File(Mutex::new(file)))
+	}
+
+	fn write(&self, entry: &AuditEntry) {
+		let json = entry.json();
+		match self {
+			Self::Log => info!(target: "surrealdb::audit", "{json}"),
+			#[cfg(not(target_arch = "wasm32"))]
+			Self::File(file) => {
+				if let Ok(mut file) = file.lock() {
+					if let Err(e) = writeln!(file, "{json}") {
+						warn!("Unable to write to the audit log file: {e}");
+					}
+				}
+			}
+		}
+	}
+}
+
+/// The audit log of a datastore
+#[non_exhaustive]
+pub struct AuditLog {
+	key: hmac::Key,
+	sink: Option<AuditSink>,
+}
+
+impl AuditLog {
+	/// Creates an audit log which signs its entries with the specified secret
+	pub fn new(secret: &[u8]) -> Self {
+		Self {
+			key: hmac::Key::new(hmac::HMAC_SHA256, secret),
+			sink: None,
+		}
+	}
+
+	/// Streams recorded entries to a sink, in addition to storing them
+	pub fn with_sink(mut self, sink: Option<AuditSink>) -> Self {
+		self.sink = sink;
+		self
+	}
+
+	/// Signs an entry, timestamping it as it is recorded
+	pub(crate) fn seal(&self, mut entry: AuditEntry) -> Result<AuditEntry, Error> {
+		entry.time = Datetime::default();
+		entry.id = Uuid::new_v7_from_datetime(entry.time.clone());
+		entry.sign(&self.key)?;
+		Ok(entry)
+	}
+
+	/// Stores a signed entry as part of a transaction. Every entry
+	/// has its own key, so this never conflicts with other writers.
+	pub(crate) async fn store(&self, txn: &Transaction, entry: &AuditEntry) -> Result<(), Error> {
+		let key = crate::key::root::au::new(entry.timestamp(), *entry.id);
+		txn.put(key, entry.clone(), None).await
+	}
+
+	/// Streams a signed entry to the sink, if one is configured
+	pub(crate) fn stream(&self, entry: &AuditEntry) {
+		if let Some(sink) = &self.sink {
+			sink.write(entry);
+		}
+	}
+
+	/// Records an entry as part of a transaction. The entry is streamed
+	/// to the sink straight away, so entries of transactions which are
+	/// later cancelled may appear in the sink without being stored.
+	pub(crate) async fn record(&self, txn: &Transaction, entry: AuditEntry) -> Result<(), Error> {
+		let entry = self.seal(entry)?;
+		self.store(txn, &entry).await?;
+		self.stream(&entry);
+		Ok(())
+	}
+
+	/// Fetches the entries recorded since a point in time which apply to the specified
+	/// namespace and database, checking the signature of every entry. Each entry is
+	/// returned with whether it was signed with the key of this audit log and is
+	/// unmodified. Entries which can not be read are returned as invalid, with the
+	/// time and id of their key, as they can not be attributed to a namespace.
+	pub(crate) async fn entries(
+		&self,
+		txn: &Transaction,
+		since: Option<&Datetime>,
+		ns: Option<&str>,
+		db: Option<&str>,
+		limit: usize,
+	) -> Result<Vec<Object>, Error> {
+		let beg = match since {
+			Some(v) => {
+				crate::key::root::au::prefix_ts(v.timestamp_nanos_opt().unwrap_or_default() as u64)
+			}
+			None => crate::key::root::au::prefix(),
+		};
+		let end = crate::key::root::au::suffix();
+		let mut out = Vec::new();
+		// Scan the entries in batches, until enough entries have been found
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			if out.len() >= limit {
+				break;
+			}
+			let batch = txn.batch(rng, *NORMAL_FETCH_SIZE, true).await?;
+			next = batch.next;
+			for (k, v) in batch.values {
+				if out.len() >= limit {
+					break;
+				}
+				let (mut obj, valid) = match AuditEntry::deserialize_revisioned(&mut v.as_slice()) {
+					Ok(entry) => {
+						if !entry.applies(ns, db) {
+							continue;
+						}
+						// The entry must be stored under the key it was recorded with
+						let key = crate::key::root::au::new(entry.timestamp(), *entry.id);
+						let valid = key.encode()? == k && entry.verify(&self.key)?;
+						(Object::from(entry), valid)
+					}
+					Err(_) => {
+						let mut obj = Object::default();
+						if let Ok(key) = crate::key::root::au::Au::decode(&k) {
+							let time = DateTime::from_timestamp_nanos(key.ts as i64);
+							obj.insert("id".to_string(), Uuid::from(key.id).into());
+							obj.insert("time".to_string(), Datetime::from(time).into());
+						}
+						(obj, false)
+					}
+				};
+				obj.insert("valid".to_string(), valid.into());
+				out.push(obj);
+			}
+		}
+		Ok(out)
+	}
+}
+
+/// Describes the method used by a signin or signup attempt
+pub(crate) fn method(vars: &Object) -> String {
+	match vars.get("AC").or_else(|| vars.get("ac")) {
+		Some(ac) => format!("access {}", ac.to_raw_string()),
+		None => "user credentials".to_owned(),
+	}
+}
+
+/// Records an entry in the audit log of the context, if auditing is enabled
+pub(crate) async fn record<F>(ctx: &Context, entry: F) -> Result<(), Error>
+where
+	F: FnOnce() -> AuditEntry,
+{
+	if let Some(audit) = ctx.get_audit() {
+		audit.record(&ctx.tx(), entry()).await?;
+	}
+	Ok(())
+}
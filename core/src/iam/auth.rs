@@ -2,7 +2,7 @@ use crate::sql::statements::{DefineAccessStatement, DefineUserStatement};
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 
-use super::{is_allowed, Action, Actor, Error, Level, Resource, Role};
+use super::{is_allowed, Action, Actor, Error, Grant, Level, Resource, ResourceKind, Role};
 
 /// Specifies the current authentication for the datastore execution context.
 #[revisioned(revision = 1)]
@@ -87,6 +87,22 @@ impl Auth {
 	pub fn has_role(&self, role: &Role) -> bool {
 		self.actor.has_role(role)
 	}
+
+	/// Returns the names of the custom roles assigned to the current actor
+	pub fn custom_roles(&self) -> &[String] {
+		self.actor.custom_roles()
+	}
+
+	/// Adds the grants resolved from the custom roles of the current actor
+	pub fn with_grants(mut self, grants: Vec<Grant>) -> Self {
+		self.actor = self.actor.with_grants(grants);
+		self
+	}
+
+	/// Checks if the current actor has been granted an action on a resource kind by a custom role
+	pub fn has_grant(&self, action: &Action, kind: &ResourceKind) -> bool {
+		self.actor.has_grant(action, kind)
+	}
 }
 
 impl std::convert::From<(&DefineUserStatement, Level)> for Auth {
@@ -1,12 +1,44 @@
-use super::Auth;
+use super::{Auth, Level};
 use crate::dbs::Session;
 use crate::err::Error;
+use crate::iam::audit::{AuditEntry, AuditEvent};
+use crate::kvs::{delete_grant_queries, Datastore, LockType::*, TransactionType::*};
+use crate::sql::Datetime;
 use std::sync::Arc;
 
 pub fn clear(session: &mut Session) -> Result<(), Error> {
 	session.au = Arc::new(Auth::default());
 	session.tk = None;
 	session.ac = None;
+	session.gr = None;
 	session.rd = None;
 	Ok(())
 }
+
+/// Clears the session, revoking the access grant which it was established
+/// with, so that any token issued alongside it can no longer be used
+pub async fn revoke(kvs: &Datastore, session: &mut Session) -> Result<(), Error> {
+	if let (Level::Record(ns, db, _), Some(ac), Some(gr)) =
+		(session.au.level(), &session.ac, &session.gr)
+	{
+		// Create a new writeable transaction
+		let tx = kvs.transaction(Write, Optimistic).await?;
+		// Fetch the access grant from storage
+		let mut grant = (*tx.get_db_access_grant(ns, db, ac, gr).await?).clone();
+		// Revoke the access grant, if still active
+		if grant.revocation.is_none() {
+			grant.revocation = Some(Datetime::default());
+			let key = crate::key::database::access::gr::new(ns, db, ac, gr);
+			tx.set(key, &grant, None).await?;
+		}
+		// End the live queries started with the access grant
+		catch!(tx, delete_grant_queries(&tx, ns, db, gr).await);
+		tx.commit().await?;
+		// Record the revoked grant in the audit log
+		kvs.audit(|| {
+			AuditEntry::new(AuditEvent::Revoke, format!("INVALIDATE ({gr})")).with_auth(&session.au)
+		})
+		.await;
+	}
+	clear(session)
+}
@@ -1,15 +1,29 @@
+use std::collections::HashSet;
 use std::str::FromStr;
 
 use cedar_policy::{Entity, EntityId, EntityTypeName, EntityUid};
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
 
 use crate::dbs::Statement;
 
-// TODO(sgirones): For now keep it simple. In the future, we will allow for custom roles and policies using a more exhaustive list of actions and resources.
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd)]
+/// The actions which can be authorised on a resource.
+///
+/// `View` and `Edit` group the fine-grained actions, which
+/// can be granted individually through a custom role.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub enum Action {
 	View,
 	Edit,
+	Select,
+	Create,
+	Update,
+	Delete,
+	Define,
+	Remove,
 }
 
 impl std::fmt::Display for Action {
@@ -17,6 +31,12 @@ impl std::fmt::Display for Action {
 		match self {
 			Action::View => write!(f, "View"),
 			Action::Edit => write!(f, "Edit"),
+			Action::Select => write!(f, "Select"),
+			Action::Create => write!(f, "Create"),
+			Action::Update => write!(f, "Update"),
+			Action::Delete => write!(f, "Delete"),
+			Action::Define => write!(f, "Define"),
+			Action::Remove => write!(f, "Remove"),
 		}
 	}
 }
@@ -25,6 +45,26 @@ impl Action {
 	pub fn id(&self) -> String {
 		self.to_string()
 	}
+
+	/// Returns the action group which this action belongs to
+	pub fn group(&self) -> Action {
+		match self {
+			Action::View | Action::Select => Action::View,
+			_ => Action::Edit,
+		}
+	}
+
+	/// Returns the fine-grained actions which can be granted in a role
+	pub fn grantable() -> [Action; 6] {
+		[
+			Action::Select,
+			Action::Create,
+			Action::Update,
+			Action::Delete,
+			Action::Define,
+			Action::Remove,
+		]
+	}
 }
 
 impl std::convert::From<&Action> for EntityUid {
@@ -38,22 +78,27 @@ impl std::convert::From<&Action> for EntityUid {
 
 impl std::convert::From<&Action> for Entity {
 	fn from(action: &Action) -> Self {
-		Entity::new(action.into(), Default::default(), Default::default())
+		let mut parents = HashSet::with_capacity(1);
+		// Fine-grained actions are members of their action group
+		if !matches!(action, Action::View | Action::Edit) {
+			parents.insert((&action.group()).into());
+		}
+		Entity::new(action.into(), Default::default(), parents)
 	}
 }
 
 impl From<&Statement<'_>> for Action {
 	fn from(stmt: &Statement) -> Self {
 		match stmt {
-			Statement::Live(_) => Action::View,
-			Statement::Select(_) => Action::View,
-			Statement::Show(_) => Action::View,
-			Statement::Create(_) => Action::Edit,
-			Statement::Upsert(_) => Action::Edit,
-			Statement::Update(_) => Action::Edit,
-			Statement::Relate(_) => Action::Edit,
-			Statement::Delete(_) => Action::Edit,
-			Statement::Insert(_) => Action::Edit,
+			Statement::Live(_) => Action::Select,
+			Statement::Select(_) => Action::Select,
+			Statement::Show(_) => Action::Select,
+			Statement::Create(_) => Action::Create,
+			Statement::Upsert(_) => Action::Update,
+			Statement::Update(_) => Action::Update,
+			Statement::Relate(_) => Action::Create,
+			Statement::Delete(_) => Action::Delete,
+			Statement::Insert(_) => Action::Create,
 			Statement::Access(_) => Action::Edit,
 		}
 	}
@@ -7,19 +7,25 @@ use cedar_policy::{Entity, EntityId, EntityTypeName, EntityUid, RestrictedExpres
 use serde::{Deserialize, Serialize};
 
 use super::{Level, Resource, ResourceKind};
-use crate::iam::Role;
+use crate::iam::{Action, Grant, Role};
 use crate::sql::statements::{DefineAccessStatement, DefineUserStatement};
 
 //
 // User
 //
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub struct Actor {
 	res: Resource,
 	roles: Vec<Role>,
+	/// The names of the custom roles assigned to the actor
+	#[revision(start = 2)]
+	custom_roles: Vec<String>,
+	/// The grants of the custom roles, resolved when a request is processed
+	#[revision(start = 2)]
+	grants: Vec<Grant>,
 }
 
 impl Default for Actor {
@@ -27,6 +33,8 @@ impl Default for Actor {
 		Self {
 			res: ResourceKind::Actor.on_level(Level::No),
 			roles: Vec::new(),
+			custom_roles: Vec::new(),
+			grants: Vec::new(),
 		}
 	}
 }
@@ -53,20 +61,58 @@ impl Actor {
 		Self {
 			res: Resource::new(id, super::ResourceKind::Actor, level),
 			roles,
+			custom_roles: Vec::new(),
+			grants: Vec::new(),
 		}
 	}
 
+	/// Assigns custom roles to the actor.
+	/// Their grants are resolved whenever a request is processed, so that a change
+	/// to the definition of a role applies to the sessions already authenticated.
+	pub fn with_custom_roles(mut self, custom_roles: Vec<String>) -> Self {
+		self.custom_roles = custom_roles;
+		self
+	}
+
+	/// Adds the grants resolved from the custom roles assigned to the actor.
+	pub fn with_grants(mut self, grants: Vec<Grant>) -> Self {
+		self.grants = grants;
+		self
+	}
+
+	/// Returns the names of the custom roles assigned to the actor.
+	pub fn custom_roles(&self) -> &[String] {
+		&self.custom_roles
+	}
+
 	/// Checks if the actor has the given role.
 	pub fn has_role(&self, role: &Role) -> bool {
 		self.roles.contains(role)
 	}
 
+	/// Checks if the actor has been granted the action on the given resource kind by a custom role.
+	pub fn has_grant(&self, action: &Action, kind: &ResourceKind) -> bool {
+		self.grants.iter().any(|g| g.allows(action, kind))
+	}
+
 	// Cedar policy helpers
 	pub fn cedar_attrs(&self) -> HashMap<String, RestrictedExpression> {
 		[
 			("type", self.kind().into()),
 			("level", self.level().into()),
 			("roles", RestrictedExpression::new_set(self.roles.iter().map(|r| r.into()))),
+			(
+				"privileges",
+				RestrictedExpression::new_record(Action::grantable().iter().map(|a| {
+					// The resource kinds on which the action has been granted
+					let kinds = self
+						.grants
+						.iter()
+						.filter(|g| g.actions.contains(a))
+						.flat_map(|g| g.resources.iter().map(|k| k.into()));
+					(a.id().to_lowercase(), RestrictedExpression::new_set(kinds))
+				})),
+			),
 		]
 		.into_iter()
 		.map(|(x, v)| (x.into(), v))
@@ -120,7 +166,8 @@ impl std::convert::From<&Actor> for Entity {
 
 impl std::convert::From<(&DefineUserStatement, Level)> for Actor {
 	fn from(val: (&DefineUserStatement, Level)) -> Self {
-		Self::new(val.0.name.to_string(), val.0.roles.iter().map(Role::from).collect(), val.1)
+		let (roles, custom_roles) = Role::parse_all(val.0.roles.iter().map(|r| r.as_str()));
+		Self::new(val.0.name.to_string(), roles, val.1).with_custom_roles(custom_roles)
 	}
 }
 
@@ -9,7 +9,7 @@ use super::Level;
 use cedar_policy::{Entity, EntityId, EntityTypeName, EntityUid, RestrictedExpression};
 use serde::{Deserialize, Serialize};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Default, Debug, Eq, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -33,6 +33,10 @@ pub enum ResourceKind {
 
 	// IAM
 	Actor,
+	#[revision(start = 2)]
+	Policy,
+	#[revision(start = 3)]
+	Audit,
 }
 
 impl std::fmt::Display for ResourceKind {
@@ -54,6 +58,8 @@ impl std::fmt::Display for ResourceKind {
 			ResourceKind::Index => write!(f, "Index"),
 			ResourceKind::Access => write!(f, "Access"),
 			ResourceKind::Actor => write!(f, "Actor"),
+			ResourceKind::Policy => write!(f, "Policy"),
+			ResourceKind::Audit => write!(f, "Audit"),
 		}
 	}
 }
@@ -1,11 +1,12 @@
-use crate::iam::Error;
+use crate::iam::{Action, Error, ResourceKind};
+use crate::sql::fmt::Fmt;
 use crate::sql::Ident;
 use cedar_policy::{Entity, EntityTypeName, EntityUid, RestrictedExpression};
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-// In the future, we will allow for custom roles. For now, provide predefined roles.
+// Predefined roles. Custom roles are defined with DEFINE ROLE, and are resolved into grants.
 #[revisioned(revision = 1)]
 #[derive(Hash, Clone, Default, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -39,6 +40,23 @@ impl FromStr for Role {
 	}
 }
 
+impl Role {
+	/// Splits role names into the built-in roles, and the names of the custom roles
+	pub(crate) fn parse_all<'a>(
+		names: impl IntoIterator<Item = &'a str>,
+	) -> (Vec<Self>, Vec<String>) {
+		let mut roles = Vec::new();
+		let mut custom = Vec::new();
+		for name in names {
+			match Role::from_str(name) {
+				Ok(role) => roles.push(role),
+				Err(_) => custom.push(name.to_owned()),
+			}
+		}
+		(roles, custom)
+	}
+}
+
 impl std::convert::From<&Ident> for Role {
 	fn from(id: &Ident) -> Self {
 		Role::from_str(id).unwrap()
@@ -71,3 +89,90 @@ impl std::convert::From<&Role> for RestrictedExpression {
 		format!("{}", EntityUid::from(role)).parse().unwrap()
 	}
 }
+
+/// A set of actions granted on a set of resource kinds by a custom role
+#[revisioned(revision = 1)]
+#[derive(Hash, Clone, Default, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct Grant {
+	pub actions: Vec<Action>,
+	pub resources: Vec<ResourceKind>,
+}
+
+impl Grant {
+	pub fn new(actions: Vec<Action>, resources: Vec<ResourceKind>) -> Self {
+		Self {
+			actions,
+			resources,
+		}
+	}
+
+	/// Checks if this grant allows the action on the given resource kind
+	pub fn allows(&self, action: &Action, kind: &ResourceKind) -> bool {
+		self.actions.contains(action)
+			&& self.resources.iter().any(|r| r == kind || r == &ResourceKind::Any)
+	}
+
+	/// Returns the name of an action, as written in a role definition
+	pub fn action_name(action: &Action) -> String {
+		action.id().to_lowercase()
+	}
+
+	/// Returns the name of a resource kind, as written in a role definition
+	pub fn resource_name(kind: &ResourceKind) -> String {
+		match kind {
+			ResourceKind::Parameter => "param".to_string(),
+			ResourceKind::Actor => "user".to_string(),
+			kind => kind.to_string().to_lowercase(),
+		}
+	}
+
+	/// Parses the name of a grantable action, as written in a role definition
+	pub fn parse_action(s: &str) -> Option<Action> {
+		match s.to_ascii_lowercase().as_str() {
+			"select" => Some(Action::Select),
+			"create" => Some(Action::Create),
+			"update" => Some(Action::Update),
+			"delete" => Some(Action::Delete),
+			"define" => Some(Action::Define),
+			"remove" => Some(Action::Remove),
+			_ => None,
+		}
+	}
+
+	/// Parses the name of a resource kind, as written in a role definition
+	pub fn parse_resource(s: &str) -> Option<ResourceKind> {
+		match s.to_ascii_lowercase().as_str() {
+			"any" => Some(ResourceKind::Any),
+			"namespace" | "ns" => Some(ResourceKind::Namespace),
+			"database" | "db" => Some(ResourceKind::Database),
+			"record" => Some(ResourceKind::Record),
+			"table" => Some(ResourceKind::Table),
+			"document" => Some(ResourceKind::Document),
+			"option" => Some(ResourceKind::Option),
+			"function" => Some(ResourceKind::Function),
+			"analyzer" => Some(ResourceKind::Analyzer),
+			"param" => Some(ResourceKind::Parameter),
+			"model" => Some(ResourceKind::Model),
+			"event" => Some(ResourceKind::Event),
+			"field" => Some(ResourceKind::Field),
+			"index" => Some(ResourceKind::Index),
+			"access" => Some(ResourceKind::Access),
+			"user" => Some(ResourceKind::Actor),
+			"policy" => Some(ResourceKind::Policy),
+			_ => None,
+		}
+	}
+}
+
+impl std::fmt::Display for Grant {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(
+			f,
+			"{} ON {}",
+			Fmt::comma_separated(self.actions.iter().map(Grant::action_name)),
+			Fmt::comma_separated(self.resources.iter().map(Grant::resource_name))
+		)
+	}
+}
@@ -47,6 +47,7 @@ pub static DEFAULT_CEDAR_SCHEMA: LazyLock<serde_json::Value> = LazyLock::new(||
 					"Field": {"shape": {"type": "Resource"}, "memberOfTypes": ["Level"]},
 					"Index": {"shape": {"type": "Resource"}, "memberOfTypes": ["Level"]},
 					"Access": {"shape": {"type": "Resource"}, "memberOfTypes": ["Level"]},
+					"Audit": {"shape": {"type": "Resource"}, "memberOfTypes": ["Level"]},
 
 					// IAM resource types
 					"Role": {},
@@ -57,6 +58,18 @@ pub static DEFAULT_CEDAR_SCHEMA: LazyLock<serde_json::Value> = LazyLock::new(||
 								"type": { "type": "String", "required": true },
 								"level" : { "type": "Entity", "name": "Level", "required": true },
 								"roles": { "type": "Set", "element": { "type": "Entity", "name": "Role" }, "required": true},
+								"privileges": {
+									"type": "Record",
+									"attributes": {
+										"select": { "type": "Set", "element": { "type": "String" }, "required": true },
+										"create": { "type": "Set", "element": { "type": "String" }, "required": true },
+										"update": { "type": "Set", "element": { "type": "String" }, "required": true },
+										"delete": { "type": "Set", "element": { "type": "String" }, "required": true },
+										"define": { "type": "Set", "element": { "type": "String" }, "required": true },
+										"remove": { "type": "Set", "element": { "type": "String" }, "required": true },
+									},
+									"required": true,
+								},
 							},
 						},
 						"memberOfTypes": ["Level"],
@@ -66,14 +79,56 @@ pub static DEFAULT_CEDAR_SCHEMA: LazyLock<serde_json::Value> = LazyLock::new(||
 					"View": {
 						"appliesTo": {
 							"principalTypes": [ "Actor" ],
-							"resourceTypes": [ "Any", "Namespace", "Database", "Record", "Table", "Document", "Option", "Function", "Analyzer", "Parameter", "Event", "Field", "Index", "Access", "Actor" ],
+							"resourceTypes": [ "Any", "Namespace", "Database", "Record", "Table", "Document", "Option", "Function", "Analyzer", "Parameter", "Event", "Field", "Index", "Access", "Audit", "Actor" ],
 
 						},
 					},
 					"Edit": {
 						"appliesTo": {
 							"principalTypes": [ "Actor" ],
-							"resourceTypes": [ "Any", "Namespace", "Database", "Record", "Table", "Document", "Option", "Function", "Analyzer", "Parameter", "Event", "Field", "Index", "Access", "Actor" ],
+							"resourceTypes": [ "Any", "Namespace", "Database", "Record", "Table", "Document", "Option", "Function", "Analyzer", "Parameter", "Event", "Field", "Index", "Access", "Audit", "Actor" ],
+						},
+					},
+					"Select": {
+						"memberOf": [ { "id": "View" } ],
+						"appliesTo": {
+							"principalTypes": [ "Actor" ],
+							"resourceTypes": [ "Any", "Namespace", "Database", "Record", "Table", "Document", "Option", "Function", "Analyzer", "Parameter", "Event", "Field", "Index", "Access", "Audit", "Actor" ],
+						},
+					},
+					"Create": {
+						"memberOf": [ { "id": "Edit" } ],
+						"appliesTo": {
+							"principalTypes": [ "Actor" ],
+							"resourceTypes": [ "Any", "Namespace", "Database", "Record", "Table", "Document", "Option", "Function", "Analyzer", "Parameter", "Event", "Field", "Index", "Access", "Audit", "Actor" ],
+						},
+					},
+					"Update": {
+						"memberOf": [ { "id": "Edit" } ],
+						"appliesTo": {
+							"principalTypes": [ "Actor" ],
+							"resourceTypes": [ "Any", "Namespace", "Database", "Record", "Table", "Document", "Option", "Function", "Analyzer", "Parameter", "Event", "Field", "Index", "Access", "Audit", "Actor" ],
+						},
+					},
+					"Delete": {
+						"memberOf": [ { "id": "Edit" } ],
+						"appliesTo": {
+							"principalTypes": [ "Actor" ],
+							"resourceTypes": [ "Any", "Namespace", "Database", "Record", "Table", "Document", "Option", "Function", "Analyzer", "Parameter", "Event", "Field", "Index", "Access", "Audit", "Actor" ],
+						},
+					},
+					"Define": {
+						"memberOf": [ { "id": "Edit" } ],
+						"appliesTo": {
+							"principalTypes": [ "Actor" ],
+							"resourceTypes": [ "Any", "Namespace", "Database", "Record", "Table", "Document", "Option", "Function", "Analyzer", "Parameter", "Event", "Field", "Index", "Access", "Audit", "Actor" ],
+						},
+					},
+					"Remove": {
+						"memberOf": [ { "id": "Edit" } ],
+						"appliesTo": {
+							"principalTypes": [ "Actor" ],
+							"resourceTypes": [ "Any", "Namespace", "Database", "Record", "Table", "Document", "Option", "Function", "Analyzer", "Parameter", "Event", "Field", "Index", "Access", "Audit", "Actor" ],
 						},
 					},
 				},
@@ -93,6 +148,6 @@ mod tests {
 	#[test]
 	fn test_default_schema() {
 		let schema = default_schema();
-		assert_eq!(schema.action_entities().unwrap().iter().count(), 2);
+		assert_eq!(schema.action_entities().unwrap().iter().count(), 8);
 	}
 }
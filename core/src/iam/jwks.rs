@@ -79,17 +79,71 @@ pub(super) async fn config(
 	url: &str,
 	token_alg: jsonwebtoken::Algorithm,
 ) -> Result<(DecodingKey, Validation), Error> {
+	// Attempt to fetch relevant JWK object either from local cache or remote location
+	let jwk = find_jwk(kvs, kid, &JwksSource::Url(url)).await?;
+	// Generate the verification configuration from the JWK object
+	config_from_jwk(&jwk, token_alg)
+}
+
+// Generates a verification configuration for tokens issued by an OpenID Connect provider
+// The location of the JWKS object is discovered from the metadata of the provider
+// Validates that the token was issued by the provider and, if specified, for the audience
+// Source: https://openid.net/specs/openid-connect-discovery-1_0.html
+pub(super) async fn issuer_config(
+	kvs: &Datastore,
+	kid: &str,
+	issuer: &str,
+	audience: Option<&str>,
+	token_alg: jsonwebtoken::Algorithm,
+) -> Result<(DecodingKey, Validation), Error> {
+	// Attempt to fetch relevant JWK object either from local cache or remote location
+	let jwk = find_jwk(kvs, kid, &JwksSource::Issuer(issuer)).await?;
+	// Generate the verification configuration from the JWK object
+	let (dec, mut val) = config_from_jwk(&jwk, token_alg)?;
+	// The token must have been issued by the provider
+	val.set_issuer(&[issuer]);
+	val.required_spec_claims.insert("iss".to_string());
+	// The token must have been issued for the audience, if specified
+	if let Some(aud) = audience {
+		val.set_audience(&[aud]);
+		val.validate_aud = true;
+		val.required_spec_claims.insert("aud".to_string());
+	}
+
+	Ok((dec, val))
+}
+
+// The location from which a JWKS object is retrieved
+enum JwksSource<'a> {
+	// The JWKS object is hosted in a known remote location
+	Url(&'a str),
+	// The JWKS object is discovered from the metadata of an OpenID Connect provider
+	Issuer(&'a str),
+}
+
+impl JwksSource<'_> {
+	// Returns the location by which the JWKS object is cached
+	fn location(&self) -> String {
+		match self {
+			JwksSource::Url(url) => url.to_string(),
+			JwksSource::Issuer(issuer) => discovery_url(issuer),
+		}
+	}
+}
+
+// Attempts to find a relevant JWK object either from local cache or remote location
+async fn find_jwk(kvs: &Datastore, kid: &str, source: &JwksSource<'_>) -> Result<Jwk, Error> {
 	// Retrieve JWKS cache
 	let cache = kvs.jwks_cache();
-	// Attempt to fetch relevant JWK object either from local cache or remote location
-	let jwk = match fetch_jwks_from_cache(cache, url).await {
+	// Attempt to fetch JWKS object from local cache
+	match fetch_jwks_from_cache(cache, &source.location()).await {
 		Some(jwks) => {
 			trace!("Successfully fetched JWKS object from local cache");
 			// Check that the cached JWKS object has not expired yet
 			if Utc::now().signed_duration_since(jwks.time) < *CACHE_EXPIRATION {
 				// Attempt to find JWK in JWKS object from local cache
 				match jwks.jwks.find(kid) {
-					Some(jwk) => jwk.to_owned(),
+					Some(jwk) => Ok(jwk.to_owned()),
 					_ => {
 						trace!("Could not find valid JWK object with key identifier '{kid}' in cached JWKS object");
 						// Check that the cached JWKS object has not been recently updated
@@ -97,26 +151,44 @@ pub(super) async fn config(
 							debug!("Refused to refresh cache before cooldown period is over");
 							return Err(Error::InvalidAuth); // Return opaque error
 						}
-						find_jwk_from_url(kvs, url, kid).await?
+						find_jwk_from_source(kvs, source, kid).await
 					}
 				}
 			} else {
 				trace!("Fetched JWKS object from local cache has expired");
-				find_jwk_from_url(kvs, url, kid).await?
+				find_jwk_from_source(kvs, source, kid).await
 			}
 		}
 		None => {
 			trace!("Could not fetch JWKS object from local cache");
-			find_jwk_from_url(kvs, url, kid).await?
+			find_jwk_from_source(kvs, source, kid).await
 		}
-	};
+	}
+}
 
+// Attempts to find a relevant JWK object from the remote location of the source
+async fn find_jwk_from_source(
+	kvs: &Datastore,
+	source: &JwksSource<'_>,
+	kid: &str,
+) -> Result<Jwk, Error> {
+	match source {
+		JwksSource::Url(url) => find_jwk_from_url(kvs, url, url, kid).await,
+		JwksSource::Issuer(issuer) => find_jwk_from_issuer(kvs, issuer, kid).await,
+	}
+}
+
+// Validates a JWK object according to RFC 7517 and generates a verification configuration from it
+fn config_from_jwk(
+	jwk: &Jwk,
+	token_alg: jsonwebtoken::Algorithm,
+) -> Result<(DecodingKey, Validation), Error> {
 	// Use algorithm provided, if specified
 	// This parameter is not required to be present, although is usually expected
 	// When missing, tokens must be validated using only the required key type parameter
 	// This is discouraged, as it requires relying on the algorithm specified in the token
 	// Source: https://datatracker.ietf.org/doc/html/rfc7517#section-4.4
-	let alg = match jwk.common.key_algorithm {
+	let alg = match &jwk.common.key_algorithm {
 		Some(alg) => match alg {
 			KeyAlgorithm::HS256 => HS256,
 			KeyAlgorithm::HS384 => HS384,
@@ -202,14 +274,12 @@ pub(super) async fn config(
 	}
 
 	// Return verification configuration if a decoding key can be retrieved from the JWK object
-	match DecodingKey::from_jwk(&jwk) {
+	match DecodingKey::from_jwk(jwk) {
 		Ok(dec) => {
 			let mut val = Validation::new(alg);
 
-			// TODO(gguillemas): This keeps the existing behavior as of SurrealDB 2.0.0-alpha.9.
-			// Up to that point, a fork of the "jsonwebtoken" crate in version 8.3.0 was being used.
-			// Now that the audience claim is validated by default, we could allow users to leverage this.
-			// This will most likely involve defining an audience string via "DEFINE ACCESS ... TYPE JWT".
+			// The audience claim is only validated when defined via "DEFINE ACCESS ... TYPE JWT ISSUER"
+			// This keeps the existing behavior as of SurrealDB 2.0.0-alpha.9 for JWKS locations
 			val.validate_aud = false;
 
 			Ok((dec, val))
@@ -221,9 +291,37 @@ pub(super) async fn config(
 	}
 }
 
+// Checks if network access to the location of an OpenID Connect provider is allowed
+// Attempts to find a relevant JWK object inside the JWKS object advertised by the provider
+// The JWKS object is cached by the location of the provider metadata
+async fn find_jwk_from_issuer(kvs: &Datastore, issuer: &str, kid: &str) -> Result<Jwk, Error> {
+	let location = discovery_url(issuer);
+	// Check that the datastore capabilities allow connections to the issuer host
+	if let Err(err) = check_capabilities_url(kvs, &location) {
+		warn!("Network access to OpenID Connect provider is not allowed: '{}'", err);
+		return Err(Error::InvalidAuth); // Return opaque error
+	}
+	// Attempt to discover the location of the JWKS object
+	let url = match fetch_jwks_url_from_issuer(issuer, &location).await {
+		Ok(url) => url,
+		Err(err) => {
+			warn!("Failed to discover JWKS location from OpenID Connect provider: '{}'", err);
+			return Err(Error::InvalidAuth); // Return opaque error
+		}
+	};
+
+	find_jwk_from_url(kvs, &url, &location, kid).await
+}
+
 // Checks if network access to a remote location is allowed by the datastore capabilities
 // Attempts to find a relevant JWK object inside a JWKS object fetched from the remote location
-async fn find_jwk_from_url(kvs: &Datastore, url: &str, kid: &str) -> Result<Jwk, Error> {
+// The JWKS object is cached by the provided location, which is usually its own URL
+async fn find_jwk_from_url(
+	kvs: &Datastore,
+	url: &str,
+	location: &str,
+	kid: &str,
+) -> Result<Jwk, Error> {
 	// Check that the datastore capabilities allow connections to the URL host
 	if let Err(err) = check_capabilities_url(kvs, url) {
 		warn!("Network access to JWKS location is not allowed: '{}'", err);
@@ -233,7 +331,7 @@ async fn find_jwk_from_url(kvs: &Datastore, url: &str, kid: &str) -> Result<Jwk,
 	// Retrieve JWKS cache
 	let cache = kvs.jwks_cache();
 	// Attempt to fetch JWKS object from remote location
-	match fetch_jwks_from_url(cache, url).await {
+	match fetch_jwks_from_url(cache, url, location).await {
 		Ok(jwks) => {
 			trace!("Successfully fetched JWKS object from remote location");
 			// Attempt to find JWK in JWKS by the key identifier
@@ -285,8 +383,57 @@ fn check_capabilities_url(kvs: &Datastore, url: &str) -> Result<(), Error> {
 	Ok(())
 }
 
+// The subset of the OpenID Connect provider metadata which is required for verification
+// Source: https://openid.net/specs/openid-connect-discovery-1_0.html#ProviderMetadata
+#[derive(Deserialize)]
+struct ProviderMetadata {
+	issuer: String,
+	jwks_uri: String,
+}
+
+// Returns the location of the metadata of an OpenID Connect provider
+// Source: https://openid.net/specs/openid-connect-discovery-1_0.html#ProviderConfigurationRequest
+fn discovery_url(issuer: &str) -> String {
+	format!("{}/.well-known/openid-configuration", issuer.trim_end_matches('/'))
+}
+
+// Attempts to fetch the metadata of an OpenID Connect provider and returns the JWKS location
+async fn fetch_jwks_url_from_issuer(issuer: &str, location: &str) -> Result<String, Error> {
+	let client = Client::new();
+	#[cfg(not(target_arch = "wasm32"))]
+	let res = client.get(location).timeout((*REMOTE_TIMEOUT).to_std().unwrap()).send().await?;
+	#[cfg(target_arch = "wasm32")]
+	let res = client.get(location).send().await?;
+	if !res.status().is_success() {
+		warn!("Unsuccessful HTTP status code received when fetching OpenID Connect provider metadata: '{:?}'", res.status());
+		return Err(Error::InvalidAuth); // Return opaque error
+	}
+	let metadata = res.bytes().await?;
+
+	match serde_json::from_slice::<ProviderMetadata>(&metadata) {
+		// The issuer in the metadata must be identical to the issuer used to retrieve it
+		// Source: https://openid.net/specs/openid-connect-discovery-1_0.html#ProviderConfigurationValidation
+		Ok(metadata) if metadata.issuer == issuer => Ok(metadata.jwks_uri),
+		Ok(metadata) => {
+			warn!(
+				"Issuer '{}' in OpenID Connect provider metadata does not match '{}'",
+				metadata.issuer, issuer
+			);
+			Err(Error::InvalidAuth) // Return opaque error
+		}
+		Err(err) => {
+			warn!("Failed to parse malformed OpenID Connect provider metadata: '{}'", err);
+			Err(Error::InvalidAuth) // Return opaque error
+		}
+	}
+}
+
 // Attempts to fetch a JWKS object from a remote location and stores it in the cache if successful
-async fn fetch_jwks_from_url(cache: &Arc<RwLock<JwksCache>>, url: &str) -> Result<JwkSet, Error> {
+async fn fetch_jwks_from_url(
+	cache: &Arc<RwLock<JwksCache>>,
+	url: &str,
+	location: &str,
+) -> Result<JwkSet, Error> {
 	let client = Client::new();
 	#[cfg(not(target_arch = "wasm32"))]
 	let res = client.get(url).timeout((*REMOTE_TIMEOUT).to_std().unwrap()).send().await?;
@@ -300,8 +447,8 @@ async fn fetch_jwks_from_url(cache: &Arc<RwLock<JwksCache>>, url: &str) -> Resul
 
 	match serde_json::from_slice::<JwkSet>(&jwks) {
 		Ok(jwks) => {
-			// If successful, cache the JWKS object by its URL
-			match store_jwks_in_cache(cache, jwks.clone(), url).await {
+			// If successful, cache the JWKS object by its location
+			match store_jwks_in_cache(cache, jwks.clone(), location).await {
 				None => trace!("Successfully added JWKS object to local cache"),
 				Some(_) => trace!("Successfully updated JWKS object in local cache"),
 			};
@@ -928,4 +1075,88 @@ mod tests {
 			"Remote request was not aborted immediately after timeout"
 		);
 	}
+
+	#[tokio::test]
+	async fn test_issuer_golden_path() {
+		let ds = Datastore::new("memory").await.unwrap().with_capabilities(
+			Capabilities::default().with_network_targets(Targets::<NetTarget>::Some(
+				[NetTarget::from_str("127.0.0.1").unwrap()].into(),
+			)),
+		);
+		let jwks = DEFAULT_JWKS.clone();
+
+		let issuer_path = random_path();
+		let mock_server = MockServer::start().await;
+		let issuer = format!("{}/{}", mock_server.uri(), issuer_path);
+		let metadata = serde_json::json!({
+			"issuer": issuer,
+			"jwks_uri": format!("{}/certs", issuer),
+		});
+		Mock::given(method("GET"))
+			.and(path(format!("/{}/.well-known/openid-configuration", issuer_path)))
+			.respond_with(ResponseTemplate::new(200).set_body_json(metadata))
+			.expect(1)
+			.mount(&mock_server)
+			.await;
+		Mock::given(method("GET"))
+			.and(path(format!("/{}/certs", issuer_path)))
+			.respond_with(ResponseTemplate::new(200).set_body_json(jwks))
+			.expect(1)
+			.mount(&mock_server)
+			.await;
+
+		// Get first token configuration from the discovered remote location
+		let res = issuer_config(
+			&ds,
+			"test_1",
+			&issuer,
+			Some("surrealdb"),
+			jsonwebtoken::Algorithm::RS256,
+		)
+		.await;
+		let (_, val) = res.expect("Failed to validate token the first time");
+		assert_eq!(val.iss, Some([issuer.clone()].into()));
+		assert_eq!(val.aud, Some(["surrealdb".to_string()].into()));
+		assert!(val.validate_aud);
+
+		// Get second token configuration from local cache
+		let res = issuer_config(&ds, "test_2", &issuer, None, jsonwebtoken::Algorithm::RS256).await;
+		let (_, val) = res.expect("Failed to validate token the second time");
+		assert_eq!(val.iss, Some([issuer.clone()].into()));
+		assert!(!val.validate_aud);
+	}
+
+	#[tokio::test]
+	async fn test_issuer_mismatch() {
+		let ds = Datastore::new("memory").await.unwrap().with_capabilities(
+			Capabilities::default().with_network_targets(Targets::<NetTarget>::Some(
+				[NetTarget::from_str("127.0.0.1").unwrap()].into(),
+			)),
+		);
+		let jwks = DEFAULT_JWKS.clone();
+
+		let issuer_path = random_path();
+		let mock_server = MockServer::start().await;
+		let issuer = format!("{}/{}", mock_server.uri(), issuer_path);
+		// The metadata advertises a different issuer than the one it was retrieved from
+		let metadata = serde_json::json!({
+			"issuer": "https://issuer.example.com",
+			"jwks_uri": format!("{}/certs", issuer),
+		});
+		Mock::given(method("GET"))
+			.and(path(format!("/{}/.well-known/openid-configuration", issuer_path)))
+			.respond_with(ResponseTemplate::new(200).set_body_json(metadata))
+			.mount(&mock_server)
+			.await;
+		Mock::given(method("GET"))
+			.and(path(format!("/{}/certs", issuer_path)))
+			.respond_with(ResponseTemplate::new(200).set_body_json(jwks))
+			.expect(0)
+			.mount(&mock_server)
+			.await;
+
+		// Get token configuration from an impersonated issuer
+		let res = issuer_config(&ds, "test_1", &issuer, None, jsonwebtoken::Algorithm::RS256).await;
+		assert!(res.is_err(), "Unexpected success validating token from mismatched issuer");
+	}
 }
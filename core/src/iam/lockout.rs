@@ -0,0 +1,358 @@
+//! Throttling of failed signin attempts, as configured by the signin policy of each base
+//!
+//! Failed attempts are stored in the datastore, so that they are shared between the nodes
+//! of a cluster and kept on restart. They are tracked for each user from each client IP
+//! address, so that failures from one client do not lock the user out of other clients,
+//! and for each client IP address, so that attempts against many users are throttled.
+//! Bases without a signin policy are throttled by a fallback lockout, when one is configured.
+//! Only invalid credentials and second factors count as failed attempts, not internal errors.
+
+use crate::cnf::{SIGNIN_FALLBACK_ATTEMPTS, SIGNIN_FALLBACK_LOCKOUT};
+use crate::err::Error;
+use crate::kvs::{Datastore, Key, LockType::*, Transaction, TransactionType::*};
+use crate::sql::statements::DefineSigninPolicyStatement;
+use crate::sql::{Datetime, Duration};
+use chrono::{DateTime, Utc};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::Arc;
+
+/// The number of times failed attempts are stored before they are given up on
+const MAX_ATTEMPTS: usize = 3;
+
+/// Fetches the signin policy defined on a base, if any
+pub(crate) async fn policy(
+	txn: &Transaction,
+	ns: Option<&str>,
+	db: Option<&str>,
+) -> Result<Option<Arc<DefineSigninPolicyStatement>>, Error> {
+	let res = match (ns, db) {
+		(Some(ns), Some(db)) => txn.get_db_signin_policy(ns, db).await,
+		(Some(ns), None) => txn.get_ns_signin_policy(ns).await,
+		(None, None) => txn.get_root_signin_policy().await,
+		(None, Some(_)) => return Ok(None),
+	};
+	match res {
+		Ok(v) => Ok(Some(v)),
+		Err(Error::SpRootNotFound) => Ok(None),
+		Err(Error::SpNsNotFound {
+			..
+		}) => Ok(None),
+		Err(Error::SpDbNotFound {
+			..
+		}) => Ok(None),
+		Err(e) => Err(e),
+	}
+}
+
+/// The signin policy which throttles attempts on bases without a signin policy, if configured
+fn fallback() -> Option<DefineSigninPolicyStatement> {
+	match *SIGNIN_FALLBACK_ATTEMPTS {
+		0 => None,
+		attempts => Some(DefineSigninPolicyStatement {
+			attempts,
+			backoff: Duration::from_secs(0),
+			lockout: Duration::from_secs(*SIGNIN_FALLBACK_LOCKOUT),
+			..Default::default()
+		}),
+	}
+}
+
+/// Who is attempting to sign in, and to which base
+pub(crate) struct Subject {
+	ns: Option<String>,
+	db: Option<String>,
+	// The system user, which is not known in advance for record access
+	user: Option<String>,
+	// The client IP address, which is not known for embedded datastores
+	ip: Option<String>,
+}
+
+impl Subject {
+	pub(crate) fn new(
+		ns: Option<String>,
+		db: Option<String>,
+		user: Option<String>,
+		ip: Option<String>,
+	) -> Self {
+		Self {
+			ns,
+			db,
+			user,
+			ip,
+		}
+	}
+
+	/// The key under which failed attempts are tracked for the user from the client
+	/// IP address, or for the base when neither the user nor the address are known
+	fn user_key(&self) -> Option<String> {
+		let base = format!("{:?}/{:?}", self.ns, self.db);
+		match (&self.user, &self.ip) {
+			(Some(user), Some(ip)) => Some(format!("{base}/user/{user}/ip/{ip}")),
+			(Some(user), None) => Some(format!("{base}/user/{user}")),
+			(None, Some(_)) => None,
+			(None, None) => Some(format!("{base}/any")),
+		}
+	}
+
+	/// The key under which failed attempts are tracked for the client IP address
+	fn ip_key(&self) -> Option<String> {
+		self.ip.as_ref().map(|v| format!("{:?}/{:?}/ip/{v}", self.ns, self.db))
+	}
+
+	fn keys(&self) -> Vec<String> {
+		self.user_key().into_iter().chain(self.ip_key()).collect()
+	}
+}
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Store)]
+#[non_exhaustive]
+pub(crate) struct Failures {
+	// The number of consecutive failed attempts
+	count: u32,
+	// The time of the last failed attempt
+	last: Datetime,
+	// The time after which the failed attempts are forgotten
+	expires: Datetime,
+}
+
+impl Failures {
+	/// The time until which further attempts are rejected
+	fn until(&self, policy: &DefineSigninPolicyStatement) -> DateTime<Utc> {
+		let delay = if policy.attempts > 0 && self.count >= policy.attempts {
+			*policy.lockout
+		} else {
+			// The delay doubles with each failed attempt, up to the lockout duration
+			let factor = 2u32.saturating_pow(self.count.saturating_sub(1));
+			policy.backoff.saturating_mul(factor).min(*policy.lockout)
+		};
+		chrono::Duration::from_std(delay)
+			.ok()
+			.and_then(|v| self.last.checked_add_signed(v))
+			.unwrap_or(DateTime::<Utc>::MAX_UTC)
+	}
+
+	/// Checks whether further attempts are rejected at a point in time
+	fn locked(&self, policy: &DefineSigninPolicyStatement, now: DateTime<Utc>) -> bool {
+		now < *self.expires && now < self.until(policy)
+	}
+
+	/// Checks whether the failed attempts are forgotten at a point in time
+	pub(crate) fn expired(&self, now: DateTime<Utc>) -> bool {
+		now >= *self.expires
+	}
+
+	/// Records a further failed attempt
+	fn fail(
+		failures: Option<Self>,
+		policy: &DefineSigninPolicyStatement,
+		now: DateTime<Utc>,
+	) -> Self {
+		let expires = chrono::Duration::from_std(*policy.lockout)
+			.ok()
+			.and_then(|v| now.checked_add_signed(v))
+			.unwrap_or(DateTime::<Utc>::MAX_UTC);
+		let count = match failures {
+			Some(v) if !v.expired(now) => v.count,
+			_ => 0,
+		};
+		Self {
+			count: count.saturating_add(1),
+			last: now.into(),
+			expires: expires.into(),
+		}
+	}
+}
+
+/// Rejects an attempt if any of the keys is currently throttled
+async fn check(
+	tx: &Transaction,
+	policy: &DefineSigninPolicyStatement,
+	keys: &[String],
+	now: DateTime<Utc>,
+) -> Result<(), Error> {
+	for key in keys {
+		if let Some(v) = tx.get(crate::key::root::lo::new(key), None).await? {
+			if Failures::from(v).locked(policy, now) {
+				return Err(Error::SigninLocked);
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Records a failed attempt for each of the keys, or forgets the failed attempts
+async fn store(
+	kvs: &Datastore,
+	policy: &DefineSigninPolicyStatement,
+	keys: &[String],
+	failed: bool,
+) -> Result<(), Error> {
+	let tx = kvs.transaction(Write, Optimistic).await?;
+	for key in keys {
+		let key: Key = crate::key::root::lo::new(key).into();
+		if failed {
+			let prev = catch!(tx, tx.get(key.clone(), None).await).map(Failures::from);
+			let next = Failures::fail(prev, policy, Utc::now());
+			catch!(tx, tx.set(key, next, None).await);
+		} else {
+			catch!(tx, tx.del(key).await);
+		}
+	}
+	tx.commit().await
+}
+
+/// Runs a signin attempt, unless the subject is throttled by the signin policy of the base
+/// Failed attempts are tracked for both the user and the client IP address, while
+/// a successful attempt only resets the failures of the user, so that attempts
+/// against many users from the same client IP address are still throttled.
+pub(crate) async fn guard<T, F>(kvs: &Datastore, subject: &Subject, attempt: F) -> Result<T, Error>
+where
+	F: Future<Output = Result<T, Error>>,
+{
+	let keys = subject.keys();
+	// Fetch the signin policy of the base, and check that the subject is not throttled
+	let tx = kvs.transaction(Read, Optimistic).await?;
+	let res = async {
+		let policy = match policy(&tx, subject.ns.as_deref(), subject.db.as_deref()).await? {
+			Some(v) => Some(v),
+			None => fallback().map(Arc::new),
+		};
+		if let Some(policy) = &policy {
+			check(&tx, policy, &keys, Utc::now()).await?;
+		}
+		Ok::<_, Error>(policy)
+	}
+	.await;
+	tx.cancel().await?;
+	// Attempt to sign in, without throttling when there is no signin policy
+	let Some(policy) = res? else {
+		return attempt.await;
+	};
+	let res = attempt.await;
+	let keys = match &res {
+		Ok(_) => subject.user_key().into_iter().collect(),
+		// Only invalid credentials count as failed attempts, which record
+		// access reports by not finding a record for the credentials
+		Err(Error::InvalidAuth | Error::InvalidSecondFactor | Error::NoRecordFound) => keys,
+		Err(_) => return res,
+	};
+	// Store the outcome, retrying when concurrent attempts conflict
+	for attempt in 1..=MAX_ATTEMPTS {
+		match store(kvs, &policy, &keys, res.is_err()).await {
+			Ok(_) => break,
+			Err(e) if attempt < MAX_ATTEMPTS => trace!("Retrying to store signin failures: {e}"),
+			Err(e) => warn!("Unable to store signin failures: {e}"),
+		}
+	}
+	res
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_keys() {
+		// Users are tracked from each client IP address
+		let subject = Subject::new(None, None, Some("user".into()), Some("127.0.0.1".into()));
+		assert_eq!(
+			subject.keys(),
+			vec!["None/None/user/user/ip/127.0.0.1", "None/None/ip/127.0.0.1"]
+		);
+		// Users are tracked without a client IP address
+		let subject = Subject::new(None, None, Some("user".into()), None);
+		assert_eq!(subject.keys(), vec!["None/None/user/user"]);
+		// The base is tracked without a user or a client IP address
+		let subject = Subject::new(Some("ns".into()), Some("db".into()), None, None);
+		assert_eq!(subject.keys(), vec!["Some(\"ns\")/Some(\"db\")/any"]);
+	}
+
+	#[test]
+	fn test_backoff() {
+		let policy = DefineSigninPolicyStatement {
+			attempts: 0,
+			backoff: Duration::from_secs(1),
+			lockout: Duration::from_mins(1),
+			..Default::default()
+		};
+		let now = Utc::now();
+		let v = Failures::fail(None, &policy, now);
+		assert!(v.locked(&policy, now));
+		assert!(!v.locked(&policy, now + chrono::Duration::seconds(1)));
+		// The delay doubles after each failed attempt
+		let mut v = Failures::fail(Some(v), &policy, now);
+		assert!(v.locked(&policy, now + chrono::Duration::seconds(1)));
+		assert!(!v.locked(&policy, now + chrono::Duration::seconds(2)));
+		// The delay is limited to the lockout duration
+		for _ in 0..10 {
+			v = Failures::fail(Some(v), &policy, now);
+		}
+		assert!(v.locked(&policy, now + chrono::Duration::seconds(59)));
+		assert!(!v.locked(&policy, now + chrono::Duration::seconds(60)));
+	}
+
+	#[test]
+	fn test_lockout() {
+		let policy = DefineSigninPolicyStatement {
+			attempts: 3,
+			backoff: Duration::from_secs(0),
+			lockout: Duration::from_mins(15),
+			..Default::default()
+		};
+		let now = Utc::now();
+		let mut v = None;
+		for _ in 0..2 {
+			let next = Failures::fail(v, &policy, now);
+			assert!(!next.locked(&policy, now));
+			v = Some(next);
+		}
+		let v = Failures::fail(v, &policy, now);
+		assert!(v.locked(&policy, now + chrono::Duration::minutes(14)));
+		// The failed attempts are forgotten after the lockout
+		let later = now + chrono::Duration::minutes(15);
+		assert!(!v.locked(&policy, later));
+		let v = Failures::fail(Some(v), &policy, later);
+		assert!(!v.locked(&policy, later));
+	}
+
+	#[tokio::test]
+	async fn test_fallback() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let subject = Subject::new(None, None, Some("user".into()), Some("127.0.0.1".into()));
+		// Attempts are not throttled without a signin policy, unless configured
+		for _ in 0..20 {
+			let res = guard(&ds, &subject, async { Err::<(), _>(Error::InvalidAuth) }).await;
+			assert!(matches!(res, Err(Error::InvalidAuth)), "Unexpected result: {res:?}");
+		}
+		assert!(guard(&ds, &subject, async { Ok(()) }).await.is_ok());
+	}
+
+	#[tokio::test]
+	async fn test_failures() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = crate::dbs::Session::owner();
+		ds.execute("DEFINE SIGNIN POLICY ON ROOT ATTEMPTS 2 BACKOFF 0s LOCKOUT 15m", &ses, None)
+			.await
+			.unwrap();
+		let subject = Subject::new(None, None, Some("user".into()), Some("127.0.0.1".into()));
+		// Errors other than invalid credentials do not count as failed attempts
+		for _ in 0..5 {
+			let res = guard(&ds, &subject, async { Err::<(), _>(Error::TxFailure) }).await;
+			assert!(matches!(res, Err(Error::TxFailure)), "Unexpected result: {res:?}");
+		}
+		// Invalid credentials and second factors count as failed attempts
+		let res = guard(&ds, &subject, async { Err::<(), _>(Error::InvalidAuth) }).await;
+		assert!(matches!(res, Err(Error::InvalidAuth)), "Unexpected result: {res:?}");
+		let res = guard(&ds, &subject, async { Err::<(), _>(Error::InvalidSecondFactor) }).await;
+		assert!(matches!(res, Err(Error::InvalidSecondFactor)), "Unexpected result: {res:?}");
+		let res = guard(&ds, &subject, async { Ok(()) }).await;
+		assert!(matches!(res, Err(Error::SigninLocked)), "Unexpected result: {res:?}");
+		// The user can still sign in from another client
+		let other = Subject::new(None, None, Some("user".into()), Some("127.0.0.2".into()));
+		assert!(guard(&ds, &other, async { Ok(()) }).await.is_ok());
+	}
+}
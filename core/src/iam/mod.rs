@@ -2,6 +2,7 @@ use cedar_policy::Context;
 pub use entities::Level;
 use thiserror::Error;
 
+pub mod audit;
 pub mod auth;
 pub mod base;
 pub mod check;
@@ -10,10 +11,12 @@ pub mod entities;
 pub mod issue;
 #[cfg(feature = "jwks")]
 pub mod jwks;
+pub(crate) mod lockout;
 pub mod policies;
 pub mod signin;
 pub mod signup;
 pub mod token;
+pub(crate) mod totp;
 pub mod verify;
 
 pub use self::auth::*;
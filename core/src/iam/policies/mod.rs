@@ -17,16 +17,27 @@ pub fn is_allowed(
 		Some(actor.into()),
 		Some(action.into()),
 		Some(resource.into()),
-		Entities::from_entities(_get_entities(Some(actor), Some(resource))).unwrap(),
+		Entities::from_entities(_get_entities(Some(actor), Some(action), Some(resource))).unwrap(),
 		context,
 	)
 }
 
-fn _get_entities(actor: Option<&Actor>, resource: Option<&Resource>) -> Vec<Entity> {
+fn _get_entities(
+	actor: Option<&Actor>,
+	action: Option<&Action>,
+	resource: Option<&Resource>,
+) -> Vec<Entity> {
 	let mut entities = Vec::new();
 	if let Some(actor) = actor {
 		entities.extend(actor.cedar_entities());
 	}
+	if let Some(action) = action {
+		entities.push(action.into());
+		// Include the action group, so that policies can match on it
+		if action.group() != *action {
+			entities.push((&action.group()).into());
+		}
+	}
 	if let Some(resource) = resource {
 		entities.extend(resource.cedar_entities());
 	}
@@ -55,7 +66,7 @@ fn _is_allowed(
 mod tests {
 	use cedar_policy::{ValidationMode, ValidationResult, Validator};
 
-	use crate::iam::{default_schema, entities::Level, ResourceKind, Role};
+	use crate::iam::{default_schema, entities::Level, Grant, ResourceKind, Role};
 
 	use super::*;
 
@@ -93,4 +104,77 @@ mod tests {
 		let (allowed, _) = is_allowed(&actor, &Action::View, &res, Context::empty());
 		assert!(!allowed);
 	}
+
+	#[test]
+	fn test_is_allowed_action_group() {
+		// Fine-grained actions are allowed by the roles allowing their action group
+		let actor = Actor::new("test".into(), vec![Role::Editor], Level::Root);
+		let res = ResourceKind::Index.on_db("test", "test");
+
+		let (allowed, _) = is_allowed(&actor, &Action::Define, &res, Context::empty());
+		assert!(allowed);
+
+		let res = ResourceKind::Actor.on_db("test", "test");
+
+		let (allowed, _) = is_allowed(&actor, &Action::Define, &res, Context::empty());
+		assert!(!allowed);
+	}
+
+	#[test]
+	fn test_is_allowed_grants() {
+		// Returns true if the actor has been granted the action on the resource kind
+		let actor =
+			Actor::new("test".into(), vec![], Level::Database("test".into(), "test".into()))
+				.with_grants(vec![Grant::new(vec![Action::Define], vec![ResourceKind::Index])]);
+		let res = ResourceKind::Index.on_db("test", "test");
+
+		let (allowed, _) = is_allowed(&actor, &Action::Define, &res, Context::empty());
+		assert!(allowed);
+
+		// Returns false if the action has not been granted on the resource kind
+		let (allowed, _) = is_allowed(&actor, &Action::Remove, &res, Context::empty());
+		assert!(!allowed);
+
+		let res = ResourceKind::Actor.on_db("test", "test");
+
+		let (allowed, _) = is_allowed(&actor, &Action::Define, &res, Context::empty());
+		assert!(!allowed);
+
+		// Returns false if the resource is not on the actor level hierarchy
+		let res = ResourceKind::Index.on_db("test", "other");
+
+		let (allowed, _) = is_allowed(&actor, &Action::Define, &res, Context::empty());
+		assert!(!allowed);
+
+		// A grant on any resource kind applies to all resource kinds
+		let actor = Actor::new("test".into(), vec![], Level::Root)
+			.with_grants(vec![Grant::new(vec![Action::Select], vec![ResourceKind::Any])]);
+		let res = ResourceKind::Table.on_db("test", "test");
+
+		let (allowed, _) = is_allowed(&actor, &Action::Select, &res, Context::empty());
+		assert!(allowed);
+
+		let (allowed, _) = is_allowed(&actor, &Action::Create, &res, Context::empty());
+		assert!(!allowed);
+	}
+
+	#[test]
+	fn test_is_allowed_audit() {
+		// Only owners are allowed to view the audit log
+		let res = ResourceKind::Audit.on_root();
+
+		let actor = Actor::new("test".into(), vec![Role::Owner], Level::Root);
+		let (allowed, _) = is_allowed(&actor, &Action::Select, &res, Context::empty());
+		assert!(allowed);
+
+		let actor = Actor::new("test".into(), vec![Role::Viewer], Level::Root);
+		let (allowed, _) = is_allowed(&actor, &Action::Select, &res, Context::empty());
+		assert!(!allowed);
+
+		// Grants on any resource kind do not apply to the audit log
+		let actor = Actor::new("test".into(), vec![], Level::Root)
+			.with_grants(vec![Grant::new(vec![Action::Select], vec![ResourceKind::Any])]);
+		let (allowed, _) = is_allowed(&actor, &Action::Select, &res, Context::empty());
+		assert!(!allowed);
+	}
 }
@@ -9,7 +9,7 @@ pub static POLICY_SET: LazyLock<PolicySet> = LazyLock::new(|| {
     // All roles can view all resources on the same level hierarchy or below
     permit(
         principal,
-        action == Action::"View",
+        action in Action::"View",
         resource
     ) when {
         principal.roles.containsAny([Role::"Viewer", Role::"Editor", Role::"Owner"]) &&
@@ -19,7 +19,7 @@ pub static POLICY_SET: LazyLock<PolicySet> = LazyLock::new(|| {
     // Editor role can edit all non-IAM resources on the same level hierarchy or below
     permit(
         principal,
-        action == Action::"Edit",
+        action in Action::"Edit",
         resource
     ) when {
         principal.roles.contains(Role::"Editor") &&
@@ -30,11 +30,77 @@ pub static POLICY_SET: LazyLock<PolicySet> = LazyLock::new(|| {
     // Owner role can edit all resources on the same level hierarchy or below
     permit(
         principal,
-        action == Action::"Edit",
+        action in Action::"Edit",
         resource
     ) when {
         principal.roles.contains(Role::"Owner") &&
         resource.level in principal.level
     };
+
+    // Custom roles can perform the granted actions on the granted resource types on the same level hierarchy or below
+    permit(
+        principal,
+        action == Action::"Select",
+        resource
+    ) when {
+        resource.level in principal.level &&
+        principal.privileges.select.containsAny(["Any", resource.type])
+    };
+
+    permit(
+        principal,
+        action == Action::"Create",
+        resource
+    ) when {
+        resource.level in principal.level &&
+        principal.privileges.create.containsAny(["Any", resource.type])
+    };
+
+    permit(
+        principal,
+        action == Action::"Update",
+        resource
+    ) when {
+        resource.level in principal.level &&
+        principal.privileges.update.containsAny(["Any", resource.type])
+    };
+
+    permit(
+        principal,
+        action == Action::"Delete",
+        resource
+    ) when {
+        resource.level in principal.level &&
+        principal.privileges.delete.containsAny(["Any", resource.type])
+    };
+
+    permit(
+        principal,
+        action == Action::"Define",
+        resource
+    ) when {
+        resource.level in principal.level &&
+        principal.privileges.define.containsAny(["Any", resource.type])
+    };
+
+    permit(
+        principal,
+        action == Action::"Remove",
+        resource
+    ) when {
+        resource.level in principal.level &&
+        principal.privileges.remove.containsAny(["Any", resource.type])
+    };
+
+    // Only the owner role can access the audit log
+    forbid(
+        principal,
+        action,
+        resource
+    ) when {
+        resource.type == "Audit"
+    } unless {
+        principal.roles.contains(Role::"Owner")
+    };
 "#).unwrap()
 });
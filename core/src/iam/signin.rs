@@ -1,12 +1,15 @@
 use super::verify::{
-	authenticate_generic, authenticate_record, verify_db_creds, verify_ns_creds, verify_root_creds,
+	authenticate_generic, authenticate_record, user_actor, verify_db_creds, verify_ns_creds,
+	verify_root_creds, verify_second_factor,
 };
 use super::{Actor, Level, Role};
 use crate::cnf::{EXPERIMENTAL_BEARER_ACCESS, INSECURE_FORWARD_ACCESS_ERRORS, SERVER_NAME};
 use crate::dbs::Session;
 use crate::err::Error;
+use crate::iam::audit::{self, AuditEntry, AuditEvent};
 use crate::iam::issue::{config, expiration};
-use crate::iam::token::{Claims, HEADER};
+use crate::iam::lockout;
+use crate::iam::token::{Claims, Token, HEADER};
 use crate::iam::Auth;
 use crate::kvs::{Datastore, LockType::*, TransactionType::*};
 use crate::sql::statements::{access, AccessGrant};
@@ -20,7 +23,36 @@ use std::sync::Arc;
 use subtle::ConstantTimeEq;
 use uuid::Uuid;
 
-pub async fn signin(kvs: &Datastore, session: &mut Session, vars: Object) -> Result<String, Error> {
+pub async fn signin(kvs: &Datastore, session: &mut Session, vars: Object) -> Result<Token, Error> {
+	// Describe the attempt before the variables are consumed
+	let method = audit::method(&vars);
+	let target = AuditEntry::new(AuditEvent::SigninFailure, "").with_vars(&vars);
+	// Describe the subject of the attempt, to throttle failed attempts
+	let subject = lockout::Subject::new(
+		vars.get("NS").or_else(|| vars.get("ns")).map(Value::to_raw_string),
+		vars.get("DB").or_else(|| vars.get("db")).map(Value::to_raw_string),
+		// The record of an access method is only known after signing in
+		match vars.get("AC").or_else(|| vars.get("ac")) {
+			Some(_) => None,
+			None => vars.get("user").map(Value::to_raw_string),
+		},
+		session.ip.clone(),
+	);
+	// Attempt to signin with the specified variables
+	let res = lockout::guard(kvs, &subject, attempt(kvs, session, vars)).await;
+	// Record the attempt in the audit log
+	kvs.audit(|| match &res {
+		Ok(_) => AuditEntry::new(AuditEvent::Signin, method).with_auth(&session.au),
+		Err(e) => AuditEntry {
+			detail: format!("{method}: {e}"),
+			..target
+		},
+	})
+	.await;
+	res
+}
+
+async fn attempt(kvs: &Datastore, session: &mut Session, vars: Object) -> Result<Token, Error> {
 	// Parse the specified variables
 	let ns = vars.get("NS").or_else(|| vars.get("ns"));
 	let db = vars.get("DB").or_else(|| vars.get("db"));
@@ -41,6 +73,8 @@ pub async fn signin(kvs: &Datastore, session: &mut Session, vars: Object) -> Res
 			// Get the provided user and pass
 			let user = vars.get("user");
 			let pass = vars.get("pass");
+			// Get the provided second factor, if any
+			let code = vars.get("code").map(Value::to_raw_string);
 			// Validate the user and pass
 			match (user, pass) {
 				// There is a username and password
@@ -51,7 +85,9 @@ pub async fn signin(kvs: &Datastore, session: &mut Session, vars: Object) -> Res
 					let user = user.to_raw_string();
 					let pass = pass.to_raw_string();
 					// Attempt to signin to database
-					super::signin::db_user(kvs, session, ns, db, user, pass).await
+					super::signin::db_user(kvs, session, ns, db, user, pass, code)
+						.await
+						.map(Token::Access)
 				}
 				_ => Err(Error::MissingUserOrPass),
 			}
@@ -62,13 +98,15 @@ pub async fn signin(kvs: &Datastore, session: &mut Session, vars: Object) -> Res
 			let ns = ns.to_raw_string();
 			let ac = ac.to_raw_string();
 			// Attempt to signin using specified access method
-			super::signin::ns_access(kvs, session, ns, ac, vars).await
+			super::signin::ns_access(kvs, session, ns, ac, vars).await.map(Token::Access)
 		}
 		// NS signin with user credentials
 		(Some(ns), None, None) => {
 			// Get the provided user and pass
 			let user = vars.get("user");
 			let pass = vars.get("pass");
+			// Get the provided second factor, if any
+			let code = vars.get("code").map(Value::to_raw_string);
 			// Validate the user and pass
 			match (user, pass) {
 				// There is a username and password
@@ -78,7 +116,9 @@ pub async fn signin(kvs: &Datastore, session: &mut Session, vars: Object) -> Res
 					let user = user.to_raw_string();
 					let pass = pass.to_raw_string();
 					// Attempt to signin to namespace
-					super::signin::ns_user(kvs, session, ns, user, pass).await
+					super::signin::ns_user(kvs, session, ns, user, pass, code)
+						.await
+						.map(Token::Access)
 				}
 				_ => Err(Error::MissingUserOrPass),
 			}
@@ -88,6 +128,8 @@ pub async fn signin(kvs: &Datastore, session: &mut Session, vars: Object) -> Res
 			// Get the provided user and pass
 			let user = vars.get("user");
 			let pass = vars.get("pass");
+			// Get the provided second factor, if any
+			let code = vars.get("code").map(Value::to_raw_string);
 			// Validate the user and pass
 			match (user, pass) {
 				// There is a username and password
@@ -96,7 +138,9 @@ pub async fn signin(kvs: &Datastore, session: &mut Session, vars: Object) -> Res
 					let user = user.to_raw_string();
 					let pass = pass.to_raw_string();
 					// Attempt to signin to root
-					super::signin::root_user(kvs, session, user, pass).await
+					super::signin::root_user(kvs, session, user, pass, code)
+						.await
+						.map(Token::Access)
 				}
 				_ => Err(Error::MissingUserOrPass),
 			}
@@ -112,7 +156,7 @@ pub async fn db_access(
 	db: String,
 	ac: String,
 	vars: Object,
-) -> Result<String, Error> {
+) -> Result<Token, Error> {
 	// Create a new readonly transaction
 	let tx = kvs.transaction(Read, Optimistic).await?;
 	// Fetch the specified access method from storage
@@ -133,86 +177,129 @@ pub async fn db_access(
 						Some(iss) => iss.clone(),
 						_ => return Err(Error::AccessMethodMismatch),
 					};
-					match &at.signin {
-						// This record access allows signin
-						Some(val) => {
-							// Setup the query params
-							let vars = Some(vars.0);
-							// Setup the system session for finding the signin record
-							let mut sess = Session::editor().with_ns(&ns).with_db(&db);
-							sess.ip.clone_from(&session.ip);
-							sess.or.clone_from(&session.or);
-							// Compute the value with the params
-							match kvs.evaluate(val, &sess, vars).await {
-								// The signin value succeeded
-								Ok(val) => {
-									match val.record() {
+					// Find the record to sign in as, either from a previously issued
+					// refresh token, or from the result of the SIGNIN clause
+					let (mut rid, prev) = match vars.get("refresh") {
+						// This record access allows refreshing tokens
+						Some(key) if at.refresh => {
+							let gr =
+								verify_refresh(kvs, &ns, &db, &ac, key.to_raw_string()).await?;
+							let rid = match &gr.subject {
+								Some(access::Subject::Record(rid)) => rid.clone(),
+								// Return opaque error as this code should not be reachable.
+								_ => return Err(Error::InvalidAuth),
+							};
+							(rid, Some(gr))
+						}
+						// Return opaque error to avoid leaking the access method configuration.
+						Some(_) => return Err(Error::InvalidAuth),
+						None => match &at.signin {
+							// This record access allows signin
+							Some(val) => {
+								// Setup the query params
+								let vars = Some(vars.0);
+								// Setup the system session for finding the signin record
+								let mut sess = Session::editor().with_ns(&ns).with_db(&db);
+								sess.ip.clone_from(&session.ip);
+								sess.or.clone_from(&session.or);
+								// Compute the value with the params
+								match kvs.evaluate(val, &sess, vars).await {
+									// The signin value succeeded
+									Ok(val) => match val.record() {
 										// There is a record returned
-										Some(mut rid) => {
-											// Create the authentication key
-											let key = config(iss.alg, &iss.key)?;
-											// Create the authentication claim
-											let claims = Claims {
-												iss: Some(SERVER_NAME.to_owned()),
-												iat: Some(Utc::now().timestamp()),
-												nbf: Some(Utc::now().timestamp()),
-												exp: expiration(av.duration.token)?,
-												jti: Some(Uuid::new_v4().to_string()),
-												ns: Some(ns.to_owned()),
-												db: Some(db.to_owned()),
-												ac: Some(ac.to_owned()),
-												id: Some(rid.to_raw()),
-												..Claims::default()
-											};
-											// AUTHENTICATE clause
-											if let Some(au) = &av.authenticate {
-												// Setup the system session for finding the signin record
-												let mut sess =
-													Session::editor().with_ns(&ns).with_db(&db);
-												sess.rd = Some(rid.clone().into());
-												sess.tk = Some((&claims).into());
-												sess.ip.clone_from(&session.ip);
-												sess.or.clone_from(&session.or);
-												rid = authenticate_record(kvs, &sess, au).await?;
-											}
-											// Log the authenticated access method info
-											trace!(
-												"Signing in to database with access method `{}`",
-												ac
-											);
-											// Create the authentication token
-											let enc =
-												encode(&Header::new(iss.alg.into()), &claims, &key);
-											// Set the authentication on the session
-											session.tk = Some((&claims).into());
-											session.ns = Some(ns.to_owned());
-											session.db = Some(db.to_owned());
-											session.ac = Some(ac.to_owned());
-											session.rd = Some(Value::from(rid.to_owned()));
-											session.exp = expiration(av.duration.session)?;
-											session.au = Arc::new(Auth::new(Actor::new(
-												rid.to_string(),
-												Default::default(),
-												Level::Record(ns, db, rid.to_string()),
-											)));
-											// Check the authentication token
-											match enc {
-												// The auth token was created successfully
-												Ok(tk) => Ok(tk),
-												_ => Err(Error::TokenMakingFailed),
-											}
+										Some(rid) => (rid, None),
+										_ => return Err(Error::NoRecordFound),
+									},
+									Err(e) => {
+										return match e {
+											Error::Thrown(_) => Err(e),
+											e if *INSECURE_FORWARD_ACCESS_ERRORS => Err(e),
+											_ => Err(Error::AccessRecordSigninQueryFailed),
 										}
-										_ => Err(Error::NoRecordFound),
 									}
 								}
-								Err(e) => match e {
-									Error::Thrown(_) => Err(e),
-									e if *INSECURE_FORWARD_ACCESS_ERRORS => Err(e),
-									_ => Err(Error::AccessRecordSigninQueryFailed),
-								},
 							}
-						}
-						_ => Err(Error::AccessRecordNoSignin),
+							_ => return Err(Error::AccessRecordNoSignin),
+						},
+					};
+					// Create a refresh grant for the record, if enabled
+					let grant = match at.refresh {
+						true => Some(access::GrantBearer::new()),
+						false => None,
+					};
+					// Create the authentication key
+					let key = config(iss.alg, &iss.key)?;
+					// Create the authentication claim
+					let claims = Claims {
+						iss: Some(SERVER_NAME.to_owned()),
+						iat: Some(Utc::now().timestamp()),
+						nbf: Some(Utc::now().timestamp()),
+						exp: expiration(av.duration.token)?,
+						// Tokens are identified by their refresh grant, so they can be revoked
+						jti: match &grant {
+							Some(grant) => Some(grant.id.to_raw()),
+							None => Some(Uuid::new_v4().to_string()),
+						},
+						ns: Some(ns.to_owned()),
+						db: Some(db.to_owned()),
+						ac: Some(ac.to_owned()),
+						id: Some(rid.to_raw()),
+						..Claims::default()
+					};
+					// The refresh grant is issued for the record before authentication
+					let subject = rid.clone();
+					// AUTHENTICATE clause
+					if let Some(au) = &av.authenticate {
+						// Setup the system session for finding the signin record
+						let mut sess = Session::editor().with_ns(&ns).with_db(&db);
+						sess.rd = Some(rid.clone().into());
+						sess.tk = Some((&claims).into());
+						sess.ip.clone_from(&session.ip);
+						sess.or.clone_from(&session.or);
+						rid = authenticate_record(kvs, &sess, au).await?;
+					}
+					// Store the refresh grant, replacing any previous grant
+					if let Some(grant) = &grant {
+						let gr = AccessGrant {
+							ac: av.name.clone(),
+							// In the case of refresh grants, the key identifier.
+							id: grant.id.clone(),
+							creation: Datetime::default(),
+							// Current time plus grant duration. Only if set.
+							expiration: av.duration.grant.map(|d| d + Datetime::default()),
+							revocation: None,
+							subject: Some(access::Subject::Record(subject)),
+							grant: access::Grant::Bearer(grant.clone()),
+						};
+						store_refresh(kvs, &ns, &db, &gr, prev.as_deref()).await?;
+					}
+					// Log the authenticated access method info
+					trace!("Signing in to database with access method `{}`", ac);
+					// Create the authentication token
+					let enc = encode(&Header::new(iss.alg.into()), &claims, &key);
+					// Set the authentication on the session
+					session.tk = Some((&claims).into());
+					session.ns = Some(ns.to_owned());
+					session.db = Some(db.to_owned());
+					session.ac = Some(ac.to_owned());
+					session.gr = grant.as_ref().map(|v| v.id.to_raw());
+					session.rd = Some(Value::from(rid.to_owned()));
+					session.exp = expiration(av.duration.session)?;
+					session.au = Arc::new(Auth::new(Actor::new(
+						rid.to_string(),
+						Default::default(),
+						Level::Record(ns, db, rid.to_string()),
+					)));
+					// Check the authentication token
+					match (enc, grant) {
+						// The auth token was created successfully
+						(Ok(tk), None) => Ok(Token::Access(tk)),
+						// The auth token was created alongside a refresh token
+						(Ok(tk), Some(grant)) => Ok(Token::WithRefresh {
+							access: tk,
+							refresh: grant.key.to_raw(),
+						}),
+						_ => Err(Error::TokenMakingFailed),
 					}
 				}
 				AccessType::Bearer(at) => {
@@ -305,11 +392,13 @@ pub async fn db_access(
 					session.exp = expiration(av.duration.session)?;
 					match &gr.subject {
 						Some(access::Subject::User(user)) => {
-							session.au = Arc::new(Auth::new(Actor::new(
-								user.to_string(),
-								roles.iter().map(Role::from).collect(),
-								Level::Database(ns, db),
-							)));
+							let level = Level::Database(ns, db);
+							let (roles, custom_roles) =
+								Role::parse_all(roles.iter().map(|r| r.as_str()));
+							session.au = Arc::new(Auth::new(
+								Actor::new(user.to_string(), roles, level)
+									.with_custom_roles(custom_roles),
+							));
 						}
 						Some(access::Subject::Record(rid)) => {
 							session.au = Arc::new(Auth::new(Actor::new(
@@ -317,6 +406,7 @@ pub async fn db_access(
 								Default::default(),
 								Level::Record(ns, db, rid.to_string()),
 							)));
+							session.gr = None;
 							session.rd = Some(Value::from(rid.to_owned()));
 						}
 						// Return opaque error as this code should not be reachable.
@@ -325,7 +415,7 @@ pub async fn db_access(
 					// Check the authentication token.
 					match enc {
 						// The authentication token was created successfully.
-						Ok(tk) => Ok(tk),
+						Ok(tk) => Ok(Token::Access(tk)),
 						_ => Err(Error::TokenMakingFailed),
 					}
 				}
@@ -343,9 +433,12 @@ pub async fn db_user(
 	db: String,
 	user: String,
 	pass: String,
+	code: Option<String>,
 ) -> Result<String, Error> {
 	match verify_db_creds(kvs, &ns, &db, &user, &pass).await {
 		Ok(u) => {
+			// Check the second factor, if the user has one
+			verify_second_factor(kvs, &u, Some(&ns), Some(&db), code.as_deref()).await?;
 			// Create the authentication key
 			let key = EncodingKey::from_secret(u.code.as_ref());
 			// Create the authentication claim
@@ -369,7 +462,8 @@ pub async fn db_user(
 			session.ns = Some(ns.to_owned());
 			session.db = Some(db.to_owned());
 			session.exp = expiration(u.duration.session)?;
-			session.au = Arc::new((&u, Level::Database(ns.to_owned(), db.to_owned())).into());
+			session.au =
+				Arc::new(Auth::new(user_actor(&u, Level::Database(ns.to_owned(), db.to_owned()))));
 			// Check the authentication token
 			match enc {
 				// The auth token was created successfully
@@ -485,11 +579,13 @@ pub async fn ns_access(
 					session.exp = expiration(av.duration.session)?;
 					match &gr.subject {
 						Some(access::Subject::User(user)) => {
-							session.au = Arc::new(Auth::new(Actor::new(
-								user.to_string(),
-								roles.iter().map(Role::from).collect(),
-								Level::Namespace(ns),
-							)));
+							let level = Level::Namespace(ns);
+							let (roles, custom_roles) =
+								Role::parse_all(roles.iter().map(|r| r.as_str()));
+							session.au = Arc::new(Auth::new(
+								Actor::new(user.to_string(), roles, level)
+									.with_custom_roles(custom_roles),
+							));
 						}
 						// Return opaque error as this code should not be reachable.
 						_ => return Err(Error::InvalidAuth),
@@ -514,9 +610,12 @@ pub async fn ns_user(
 	ns: String,
 	user: String,
 	pass: String,
+	code: Option<String>,
 ) -> Result<String, Error> {
 	match verify_ns_creds(kvs, &ns, &user, &pass).await {
 		Ok(u) => {
+			// Check the second factor, if the user has one
+			verify_second_factor(kvs, &u, Some(&ns), None, code.as_deref()).await?;
 			// Create the authentication key
 			let key = EncodingKey::from_secret(u.code.as_ref());
 			// Create the authentication claim
@@ -538,7 +637,7 @@ pub async fn ns_user(
 			session.tk = Some((&val).into());
 			session.ns = Some(ns.to_owned());
 			session.exp = expiration(u.duration.session)?;
-			session.au = Arc::new((&u, Level::Namespace(ns.to_owned())).into());
+			session.au = Arc::new(Auth::new(user_actor(&u, Level::Namespace(ns.to_owned()))));
 			// Check the authentication token
 			match enc {
 				// The auth token was created successfully
@@ -556,9 +655,12 @@ pub async fn root_user(
 	session: &mut Session,
 	user: String,
 	pass: String,
+	code: Option<String>,
 ) -> Result<String, Error> {
 	match verify_root_creds(kvs, &user, &pass).await {
 		Ok(u) => {
+			// Check the second factor, if the user has one
+			verify_second_factor(kvs, &u, None, None, code.as_deref()).await?;
 			// Create the authentication key
 			let key = EncodingKey::from_secret(u.code.as_ref());
 			// Create the authentication claim
@@ -578,7 +680,7 @@ pub async fn root_user(
 			// Set the authentication on the session
 			session.tk = Some(val.into());
 			session.exp = expiration(u.duration.session)?;
-			session.au = Arc::new((&u, Level::Root).into());
+			session.au = Arc::new(Auth::new(user_actor(&u, Level::Root)));
 			// Check the authentication token
 			match enc {
 				// The auth token was created successfully
@@ -692,11 +794,13 @@ pub async fn root_access(
 					session.exp = expiration(av.duration.session)?;
 					match &gr.subject {
 						Some(access::Subject::User(user)) => {
-							session.au = Arc::new(Auth::new(Actor::new(
-								user.to_string(),
-								roles.iter().map(Role::from).collect(),
-								Level::Root,
-							)));
+							let level = Level::Root;
+							let (roles, custom_roles) =
+								Role::parse_all(roles.iter().map(|r| r.as_str()));
+							session.au = Arc::new(Auth::new(
+								Actor::new(user.to_string(), roles, level)
+									.with_custom_roles(custom_roles),
+							));
 						}
 						// Return opaque error as this code should not be reachable.
 						_ => return Err(Error::InvalidAuth),
@@ -721,6 +825,10 @@ pub fn validate_grant_bearer(vars: Object) -> Result<(String, String), Error> {
 		Some(key) => key.to_raw_string(),
 		None => return Err(Error::AccessBearerMissingKey),
 	};
+	parse_grant_bearer(key)
+}
+
+fn parse_grant_bearer(key: String) -> Result<(String, String), Error> {
 	if key.len() != access::GRANT_BEARER_LENGTH {
 		return Err(Error::AccessGrantBearerInvalid);
 	}
@@ -770,10 +878,68 @@ pub fn verify_grant_bearer(gr: &Arc<AccessGrant>, key: String) -> Result<(), Err
 	Ok(())
 }
 
+/// Checks a refresh token against the refresh grant which it identifies
+async fn verify_refresh(
+	kvs: &Datastore,
+	ns: &str,
+	db: &str,
+	ac: &str,
+	key: String,
+) -> Result<Arc<AccessGrant>, Error> {
+	// Extract key identifier and key from the provided refresh token.
+	let (kid, key) = parse_grant_bearer(key)?;
+	// Create a new readonly transaction
+	let tx = kvs.transaction(Read, Optimistic).await?;
+	// Fetch the specified refresh grant from storage
+	let gr = match tx.get_db_access_grant(ns, db, ac, &kid).await {
+		Ok(gr) => gr,
+		// Return opaque error to avoid leaking existence of the grant.
+		_ => return Err(Error::InvalidAuth),
+	};
+	// Ensure that the transaction is cancelled.
+	tx.cancel().await?;
+	// Authenticate refresh key against stored grant.
+	verify_grant_bearer(&gr, key)?;
+	Ok(gr)
+}
+
+/// Stores a refresh grant, revoking the grant which it replaces so that
+/// each refresh token can only be exchanged once
+async fn store_refresh(
+	kvs: &Datastore,
+	ns: &str,
+	db: &str,
+	gr: &AccessGrant,
+	prev: Option<&AccessGrant>,
+) -> Result<(), Error> {
+	// Create a new writeable transaction
+	let tx = kvs.transaction(Write, Optimistic).await?;
+	// Revoke the replaced refresh grant
+	if let Some(prev) = prev {
+		let ac = prev.ac.to_raw();
+		let id = prev.id.to_raw();
+		let mut prev = (*tx.get_db_access_grant(ns, db, &ac, &id).await?).clone();
+		// The refresh token was exchanged concurrently
+		if prev.revocation.is_some() {
+			tx.cancel().await?;
+			return Err(Error::InvalidAuth);
+		}
+		prev.revocation = Some(Datetime::default());
+		let key = crate::key::database::access::gr::new(ns, db, &ac, &id);
+		tx.set(key, &prev, None).await?;
+	}
+	// Store the new refresh grant
+	let ac = gr.ac.to_raw();
+	let id = gr.id.to_raw();
+	let key = crate::key::database::access::gr::new(ns, db, &ac, &id);
+	tx.set(key, gr, None).await?;
+	tx.commit().await
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::iam::Role;
+	use crate::iam::{totp, Role};
 	use chrono::Duration;
 	use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
 	use std::collections::HashMap;
@@ -1026,7 +1192,7 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 				let val = Validation::new(Algorithm::RS256);
 				// Check that token can be verified with the defined public key
 				let token_data = decode::<Claims>(
-					&tk,
+					tk.access(),
 					&DecodingKey::from_rsa_pem(public_key.as_ref()).unwrap(),
 					&val,
 				)
@@ -1058,6 +1224,150 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 		}
 	}
 
+	#[tokio::test]
+	async fn test_signin_record_with_refresh() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute(
+			r#"
+			DEFINE ACCESS user ON DATABASE TYPE RECORD
+				SIGNIN (
+					SELECT * FROM user WHERE name = $user AND crypto::argon2::compare(pass, $pass)
+				)
+				WITH REFRESH
+				DURATION FOR GRANT 15d, FOR SESSION 2h
+			;
+
+			CREATE user:test CONTENT {
+				name: 'user',
+				pass: crypto::argon2::generate('pass')
+			}
+			"#,
+			&sess,
+			None,
+		)
+		.await
+		.unwrap();
+
+		// Signin with the user
+		let mut sess = Session {
+			ns: Some("test".to_string()),
+			db: Some("test".to_string()),
+			..Default::default()
+		};
+		let mut vars: HashMap<&str, Value> = HashMap::new();
+		vars.insert("user", "user".into());
+		vars.insert("pass", "pass".into());
+		let res = db_access(
+			&ds,
+			&mut sess,
+			"test".to_string(),
+			"test".to_string(),
+			"user".to_string(),
+			vars.into(),
+		)
+		.await;
+
+		// A refresh token is issued alongside the access token
+		let (access, refresh) = match res {
+			Ok(Token::WithRefresh {
+				access,
+				refresh,
+			}) => (access, refresh),
+			res => panic!("Expected an access and refresh token, but instead received: {res:?}"),
+		};
+		assert_eq!(sess.au.id(), "user:test");
+		// The access token is identified by the refresh grant
+		let (kid, _) = parse_grant_bearer(refresh.clone()).unwrap();
+		let token_data = decode::<Claims>(&access, &DecodingKey::from_secret(&[]), &{
+			let mut validation = Validation::new(Algorithm::HS512);
+			validation.insecure_disable_signature_validation();
+			validation.validate_nbf = false;
+			validation.validate_exp = false;
+			validation
+		})
+		.unwrap();
+		assert_eq!(token_data.claims.jti, Some(kid.clone()));
+		assert_eq!(sess.gr, Some(kid.clone()));
+
+		// Refresh the access token with the refresh token
+		let mut sess = Session::default();
+		let mut vars: HashMap<&str, Value> = HashMap::new();
+		vars.insert("refresh", refresh.clone().into());
+		let res = db_access(
+			&ds,
+			&mut sess,
+			"test".to_string(),
+			"test".to_string(),
+			"user".to_string(),
+			vars.into(),
+		)
+		.await;
+		let (access, refreshed) = match res {
+			Ok(Token::WithRefresh {
+				access,
+				refresh,
+			}) => (access, refresh),
+			res => panic!("Expected an access and refresh token, but instead received: {res:?}"),
+		};
+		assert_ne!(refresh, refreshed, "Refresh tokens are expected to be rotated");
+		assert_eq!(sess.au.id(), "user:test");
+
+		// The previous refresh token can not be used again
+		let mut vars: HashMap<&str, Value> = HashMap::new();
+		vars.insert("refresh", refresh.into());
+		let res = db_access(
+			&ds,
+			&mut Session::default(),
+			"test".to_string(),
+			"test".to_string(),
+			"user".to_string(),
+			vars.into(),
+		)
+		.await;
+		assert!(
+			matches!(res, Err(Error::InvalidAuth)),
+			"Expected a used refresh token to be rejected, but instead received: {res:?}"
+		);
+
+		// The new access token can be used to authenticate
+		let mut sess = Session::default();
+		crate::iam::verify::token(&ds, &mut sess, &access).await.unwrap();
+		ds.execute("SELECT * FROM $auth", &sess, None).await.unwrap();
+
+		// Start a live query within the session
+		sess.rt = true;
+		ds.execute("LIVE SELECT * FROM user", &sess, None).await.unwrap().remove(0).result.unwrap();
+		let tx = ds.transaction(Read, Optimistic).await.unwrap();
+		assert_eq!(tx.all_tb_lives("test", "test", "user").await.unwrap().len(), 1);
+		tx.cancel().await.unwrap();
+
+		// Revoking the refresh grant ends the session and revokes its access token
+		let (kid, _) = parse_grant_bearer(refreshed).unwrap();
+		let owner = Session::owner().with_ns("test").with_db("test");
+		let res = ds.execute(&format!("ACCESS user REVOKE `{kid}`"), &owner, None).await;
+		res.unwrap().remove(0).result.unwrap();
+		let res = ds.execute("SELECT * FROM $auth", &sess, None).await;
+		assert!(
+			matches!(res, Err(Error::RevokedSession)),
+			"Expected the session to be revoked, but instead received: {res:?}"
+		);
+		let res = crate::iam::verify::token(&ds, &mut Session::default(), &access).await;
+		assert!(
+			matches!(res, Err(Error::InvalidAuth)),
+			"Expected a revoked access token to be rejected, but instead received: {res:?}"
+		);
+		// The live queries of the session are ended along with it
+		let tx = ds.transaction(Read, Optimistic).await.unwrap();
+		assert!(tx.all_tb_lives("test", "test", "user").await.unwrap().is_empty());
+		tx.cancel().await.unwrap();
+		// The revoked grants are purged when garbage is collected
+		ds.garbage_collect().await.unwrap();
+		let tx = ds.transaction(Read, Optimistic).await.unwrap();
+		assert!(tx.all_db_access_grants("test", "test", "user").await.unwrap().is_empty());
+		tx.cancel().await.unwrap();
+	}
+
 	#[tokio::test]
 	async fn test_signin_db_user() {
 		//
@@ -1081,6 +1391,7 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 				"test".to_string(),
 				"user".to_string(),
 				"pass".to_string(),
+				None,
 			)
 			.await;
 
@@ -1124,6 +1435,7 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 				"test".to_string(),
 				"user".to_string(),
 				"pass".to_string(),
+				None,
 			)
 			.await;
 
@@ -1195,6 +1507,7 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 				"test".to_string(),
 				"user".to_string(),
 				"pass".to_string(),
+				None,
 			)
 			.await;
 
@@ -1267,6 +1580,7 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 				"test".to_string(),
 				"user".to_string(),
 				"invalid".to_string(),
+				None,
 			)
 			.await;
 
@@ -1289,9 +1603,15 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 				ns: Some("test".to_string()),
 				..Default::default()
 			};
-			let res =
-				ns_user(&ds, &mut sess, "test".to_string(), "user".to_string(), "pass".to_string())
-					.await;
+			let res = ns_user(
+				&ds,
+				&mut sess,
+				"test".to_string(),
+				"user".to_string(),
+				"pass".to_string(),
+				None,
+			)
+			.await;
 
 			assert!(res.is_ok(), "Failed to signin with credentials: {:?}", res);
 			assert_eq!(sess.ns, Some("test".to_string()));
@@ -1323,9 +1643,15 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 				ns: Some("test".to_string()),
 				..Default::default()
 			};
-			let res =
-				ns_user(&ds, &mut sess, "test".to_string(), "user".to_string(), "pass".to_string())
-					.await;
+			let res = ns_user(
+				&ds,
+				&mut sess,
+				"test".to_string(),
+				"user".to_string(),
+				"pass".to_string(),
+				None,
+			)
+			.await;
 
 			assert!(res.is_ok(), "Failed to signin with credentials: {:?}", res);
 			assert_eq!(sess.ns, Some("test".to_string()));
@@ -1385,9 +1711,15 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 				ns: Some("test".to_string()),
 				..Default::default()
 			};
-			let res =
-				ns_user(&ds, &mut sess, "test".to_string(), "user".to_string(), "pass".to_string())
-					.await;
+			let res = ns_user(
+				&ds,
+				&mut sess,
+				"test".to_string(),
+				"user".to_string(),
+				"pass".to_string(),
+				None,
+			)
+			.await;
 
 			assert!(res.is_ok(), "Failed to signin with credentials: {:?}", res);
 			assert_eq!(sess.ns, Some("test".to_string()));
@@ -1455,6 +1787,7 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 				"test".to_string(),
 				"user".to_string(),
 				"invalid".to_string(),
+				None,
 			)
 			.await;
 
@@ -1476,7 +1809,7 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 			let mut sess = Session {
 				..Default::default()
 			};
-			let res = root_user(&ds, &mut sess, "user".to_string(), "pass".to_string()).await;
+			let res = root_user(&ds, &mut sess, "user".to_string(), "pass".to_string(), None).await;
 
 			assert!(res.is_ok(), "Failed to signin with credentials: {:?}", res);
 			assert_eq!(sess.au.id(), "user");
@@ -1499,7 +1832,7 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 			let mut sess = Session {
 				..Default::default()
 			};
-			let res = root_user(&ds, &mut sess, "user".to_string(), "pass".to_string()).await;
+			let res = root_user(&ds, &mut sess, "user".to_string(), "pass".to_string(), None).await;
 
 			assert!(res.is_ok(), "Failed to signin with credentials: {:?}", res);
 			assert_eq!(sess.au.id(), "user");
@@ -1556,7 +1889,7 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 			let mut sess = Session {
 				..Default::default()
 			};
-			let res = root_user(&ds, &mut sess, "user".to_string(), "pass".to_string()).await;
+			let res = root_user(&ds, &mut sess, "user".to_string(), "pass".to_string(), None).await;
 
 			assert!(res.is_ok(), "Failed to signin with credentials: {:?}", res);
 			assert_eq!(sess.au.id(), "user");
@@ -1616,12 +1949,169 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 			let mut sess = Session {
 				..Default::default()
 			};
-			let res = root_user(&ds, &mut sess, "user".to_string(), "invalid".to_string()).await;
+			let res =
+				root_user(&ds, &mut sess, "user".to_string(), "invalid".to_string(), None).await;
 
 			assert!(res.is_err(), "Unexpected successful signin: {:?}", res);
 		}
 	}
 
+	#[tokio::test]
+	async fn test_signin_root_user_with_totp() {
+		let secret = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner();
+		ds.execute(
+			&format!("DEFINE USER user ON ROOT PASSWORD 'pass' TOTP '{secret}' RECOVERY 'first', 'second'"),
+			&sess,
+			None,
+		)
+		.await
+		.unwrap();
+
+		// Signin without a second factor
+		{
+			let mut sess = Session::default();
+			let res = root_user(&ds, &mut sess, "user".to_string(), "pass".to_string(), None).await;
+			assert!(matches!(res, Err(Error::MissingSecondFactor)), "Unexpected result: {:?}", res);
+		}
+		// Signin with an invalid second factor
+		{
+			let mut sess = Session::default();
+			let code = Some("third".to_string());
+			let res = root_user(&ds, &mut sess, "user".to_string(), "pass".to_string(), code).await;
+			assert!(matches!(res, Err(Error::InvalidSecondFactor)), "Unexpected result: {:?}", res);
+		}
+		// Signin with a valid second factor but an invalid password
+		{
+			let mut sess = Session::default();
+			let code = totp::generate(secret, Utc::now().timestamp());
+			let res =
+				root_user(&ds, &mut sess, "user".to_string(), "invalid".to_string(), code).await;
+			assert!(matches!(res, Err(Error::InvalidAuth)), "Unexpected result: {:?}", res);
+		}
+		// Signin with a valid time-based one-time password, which can only be used once
+		{
+			let mut sess = Session::default();
+			let code = totp::generate(secret, Utc::now().timestamp()).unwrap();
+			let mut vars: HashMap<&str, Value> = HashMap::new();
+			vars.insert("user", "user".into());
+			vars.insert("pass", "pass".into());
+			vars.insert("code", code.clone().into());
+			let res = signin(&ds, &mut sess, vars.into()).await;
+			assert!(res.is_ok(), "Failed to signin with second factor: {:?}", res);
+			assert_eq!(sess.au.id(), "user");
+			let code = Some(code);
+			let res = root_user(&ds, &mut sess, "user".to_string(), "pass".to_string(), code).await;
+			assert!(matches!(res, Err(Error::InvalidSecondFactor)), "Unexpected result: {:?}", res);
+		}
+		// Signin with a recovery code, which can only be used once
+		{
+			let mut sess = Session::default();
+			let code = Some("first".to_string());
+			let res = root_user(&ds, &mut sess, "user".to_string(), "pass".to_string(), code).await;
+			assert!(res.is_ok(), "Failed to signin with recovery code: {:?}", res);
+			let code = Some("first".to_string());
+			let res = root_user(&ds, &mut sess, "user".to_string(), "pass".to_string(), code).await;
+			assert!(matches!(res, Err(Error::InvalidSecondFactor)), "Unexpected result: {:?}", res);
+			let code = Some("second".to_string());
+			let res = root_user(&ds, &mut sess, "user".to_string(), "pass".to_string(), code).await;
+			assert!(res.is_ok(), "Failed to signin with recovery code: {:?}", res);
+		}
+		// Basic authentication can not provide a second factor
+		{
+			let mut sess = Session::default();
+			let res = crate::iam::verify::basic(&ds, &mut sess, "user", "pass", None, None).await;
+			assert!(matches!(res, Err(Error::MissingSecondFactor)), "Unexpected result: {:?}", res);
+		}
+	}
+
+	#[tokio::test]
+	async fn test_signin_with_signin_policy() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute(
+			r#"
+			DEFINE SIGNIN POLICY ON ROOT PASSWORD LENGTH 8 CLASSES 3 ATTEMPTS 3 BACKOFF 0s LOCKOUT 1h;
+			DEFINE SIGNIN POLICY ON DATABASE ATTEMPTS 1 BACKOFF 0s LOCKOUT 1h;
+			DEFINE ACCESS user ON DATABASE TYPE RECORD
+				SIGNIN (
+					SELECT * FROM user WHERE name = $user AND crypto::argon2::compare(pass, $pass)
+				);
+			"#,
+			&sess,
+			None,
+		)
+		.await
+		.unwrap();
+
+		// Passwords must satisfy the policy of the base
+		{
+			let mut res =
+				ds.execute("DEFINE USER user ON ROOT PASSWORD 'pass'", &sess, None).await.unwrap();
+			let res = res.remove(0).result;
+			assert!(
+				matches!(
+					res,
+					Err(Error::PasswordTooWeak {
+						length: 8,
+						classes: 3
+					})
+				),
+				"Unexpected result: {:?}",
+				res
+			);
+			let mut res = ds
+				.execute("DEFINE USER user ON ROOT PASSWORD 'Passw0rd'", &sess, None)
+				.await
+				.unwrap();
+			let res = res.remove(0).result;
+			assert!(res.is_ok(), "Failed to define user: {:?}", res);
+		}
+		// System users are locked after too many failed attempts
+		{
+			let ds = &ds;
+			let attempt = |ip: &str, pass: &str| {
+				let mut sess = Session::default();
+				sess.ip = Some(ip.to_string());
+				let mut vars: HashMap<&str, Value> = HashMap::new();
+				vars.insert("user", "user".into());
+				vars.insert("pass", pass.into());
+				async move { signin(ds, &mut sess, vars.into()).await }
+			};
+			for _ in 0..3 {
+				let res = attempt("1.1.1.1", "invalid").await;
+				assert!(matches!(res, Err(Error::InvalidAuth)), "Unexpected result: {:?}", res);
+			}
+			let res = attempt("1.1.1.1", "Passw0rd").await;
+			assert!(matches!(res, Err(Error::SigninLocked)), "Unexpected result: {:?}", res);
+			// The user is only locked from the client IP address of the failed attempts
+			let res = attempt("2.2.2.2", "Passw0rd").await;
+			assert!(res.is_ok(), "Failed to signin from another client: {:?}", res);
+		}
+		// Record access is locked for the client IP address
+		{
+			let ds = &ds;
+			let attempt = |ip: &str| {
+				let mut sess = Session::default();
+				sess.ip = Some(ip.to_string());
+				let mut vars: HashMap<&str, Value> = HashMap::new();
+				vars.insert("NS", "test".into());
+				vars.insert("DB", "test".into());
+				vars.insert("AC", "user".into());
+				vars.insert("user", "user".into());
+				vars.insert("pass", "invalid".into());
+				async move { signin(ds, &mut sess, vars.into()).await }
+			};
+			let res = attempt("1.1.1.1").await;
+			assert!(matches!(res, Err(Error::NoRecordFound)), "Unexpected result: {:?}", res);
+			let res = attempt("1.1.1.1").await;
+			assert!(matches!(res, Err(Error::SigninLocked)), "Unexpected result: {:?}", res);
+			let res = attempt("2.2.2.2").await;
+			assert!(matches!(res, Err(Error::NoRecordFound)), "Unexpected result: {:?}", res);
+		}
+	}
+
 	#[tokio::test]
 	async fn test_signin_record_and_authenticate_clause() {
 		// Test with correct credentials
@@ -1,6 +1,7 @@
 use crate::cnf::{INSECURE_FORWARD_ACCESS_ERRORS, SERVER_NAME};
 use crate::dbs::Session;
 use crate::err::Error;
+use crate::iam::audit::{self, AuditEntry, AuditEvent};
 use crate::iam::issue::{config, expiration};
 use crate::iam::token::Claims;
 use crate::iam::Auth;
@@ -18,6 +19,28 @@ pub async fn signup(
 	kvs: &Datastore,
 	session: &mut Session,
 	vars: Object,
+) -> Result<Option<String>, Error> {
+	// Describe the attempt before the variables are consumed
+	let method = audit::method(&vars);
+	let target = AuditEntry::new(AuditEvent::SignupFailure, "").with_vars(&vars);
+	// Attempt to signup with the specified variables
+	let res = attempt(kvs, session, vars).await;
+	// Record the attempt in the audit log
+	kvs.audit(|| match &res {
+		Ok(_) => AuditEntry::new(AuditEvent::Signup, method).with_auth(&session.au),
+		Err(e) => AuditEntry {
+			detail: format!("{method}: {e}"),
+			..target
+		},
+	})
+	.await;
+	res
+}
+
+async fn attempt(
+	kvs: &Datastore,
+	session: &mut Session,
+	vars: Object,
 ) -> Result<Option<String>, Error> {
 	// Parse the specified variables
 	let ns = vars.get("NS").or_else(|| vars.get("ns"));
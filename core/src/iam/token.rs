@@ -73,6 +73,61 @@ pub struct Claims {
 	pub custom_claims: Option<HashMap<String, serde_json::Value>>,
 }
 
+/// The tokens returned from a successful signin
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Token {
+	/// A single access token
+	Access(String),
+	/// An access token, alongside a refresh token which can
+	/// be exchanged for a new access token once it expires
+	WithRefresh {
+		access: String,
+		refresh: String,
+	},
+}
+
+impl Token {
+	/// Returns the access token
+	pub fn access(&self) -> &str {
+		match self {
+			Token::Access(access) => access,
+			Token::WithRefresh {
+				access,
+				..
+			} => access,
+		}
+	}
+
+	/// Returns the refresh token, if one was issued
+	pub fn refresh(&self) -> Option<&str> {
+		match self {
+			Token::Access(_) => None,
+			Token::WithRefresh {
+				refresh,
+				..
+			} => Some(refresh),
+		}
+	}
+}
+
+impl From<Token> for Value {
+	fn from(v: Token) -> Value {
+		match v {
+			// A single access token is returned as is
+			Token::Access(access) => access.into(),
+			// Refresh tokens are returned alongside the access token
+			Token::WithRefresh {
+				access,
+				refresh,
+			} => Value::from(map! {
+				"token".to_string() => access.into(),
+				"refresh".to_string() => refresh.into(),
+			}),
+		}
+	}
+}
+
 impl From<Claims> for Value {
 	fn from(v: Claims) -> Value {
 		// Set default value
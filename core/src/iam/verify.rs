@@ -1,17 +1,20 @@
 use crate::cnf::INSECURE_FORWARD_ACCESS_ERRORS;
 use crate::dbs::Session;
 use crate::err::Error;
+use crate::iam::audit::{AuditEntry, AuditEvent};
 #[cfg(feature = "jwks")]
 use crate::iam::jwks;
-use crate::iam::{issue::expiration, token::Claims, Actor, Auth, Level, Role};
-use crate::kvs::{Datastore, LockType::*, TransactionType::*};
-use crate::sql::access_type::{AccessType, Jwt, JwtAccessVerify};
-use crate::sql::{statements::DefineUserStatement, Algorithm, Thing, Value};
+use crate::iam::{
+	issue::expiration, lockout, token::Claims, totp, Actor, Auth, Grant, Level, Role,
+};
+use crate::kvs::{Datastore, Key, LockType::*, Transaction, TransactionType::*};
+use crate::sql::access_type::{AccessType, Jwt, JwtAccessVerify, JwtAccessVerifyIssuer};
+use crate::sql::{statements::DefineUserStatement, Algorithm, Id, Thing, Value};
 use crate::syn;
 use argon2::{Argon2, PasswordHash, PasswordVerifier};
 use chrono::Utc;
-use jsonwebtoken::{decode, DecodingKey, Validation};
-use std::str::{self, FromStr};
+use jsonwebtoken::{decode, DecodingKey, Header, Validation};
+use std::str;
 use std::sync::Arc;
 use std::sync::LazyLock;
 
@@ -67,6 +70,90 @@ fn config(alg: Algorithm, key: &[u8]) -> Result<(DecodingKey, Validation), Error
 	Ok((dec, val))
 }
 
+// Obtains the configuration to verify a token based on the access method
+#[cfg_attr(not(feature = "jwks"), allow(unused_variables))]
+async fn access_config(
+	kvs: &Datastore,
+	verify: &JwtAccessVerify,
+	header: &Header,
+) -> Result<(DecodingKey, Validation), Error> {
+	match verify {
+		JwtAccessVerify::Key(key) => config(key.alg, key.key.as_bytes()),
+		#[cfg(feature = "jwks")]
+		JwtAccessVerify::Jwks(jwks) => match &header.kid {
+			Some(kid) => jwks::config(kvs, kid, &jwks.url, header.alg).await,
+			None => Err(Error::MissingTokenHeader("kid".to_string())),
+		},
+		#[cfg(feature = "jwks")]
+		JwtAccessVerify::Issuer(iss) => match &header.kid {
+			Some(kid) => {
+				jwks::issuer_config(kvs, kid, &iss.url, iss.audience.as_deref(), header.alg).await
+			}
+			None => Err(Error::MissingTokenHeader("kid".to_string())),
+		},
+		#[cfg(not(feature = "jwks"))]
+		_ => Err(Error::AccessMethodMismatch),
+	}
+}
+
+// Finds the value of a claim, which can be nested inside other claims using dot notation
+// Claims with names containing dots, such as namespaced claims, take precedence
+fn find_claim(claims: &Claims, path: &str) -> Option<serde_json::Value> {
+	let claims = serde_json::to_value(claims).ok()?;
+	match claims.get(path) {
+		Some(v) => Some(v.to_owned()),
+		None => path.split('.').try_fold(&claims, |v, k| v.get(k)).cloned(),
+	}
+}
+
+// Obtains the role names from the token claims
+// Access methods verifying with an issuer can read the roles from a custom claim
+fn token_roles(verify: &JwtAccessVerify, claims: &Claims) -> Option<Vec<String>> {
+	match verify {
+		JwtAccessVerify::Issuer(JwtAccessVerifyIssuer {
+			roles: Some(path),
+			..
+		}) => match find_claim(claims, path)? {
+			serde_json::Value::String(role) => Some(vec![role]),
+			serde_json::Value::Array(roles) => Some(
+				roles
+					.into_iter()
+					.filter_map(|v| match v {
+						serde_json::Value::String(role) => Some(role),
+						_ => None,
+					})
+					.collect(),
+			),
+			_ => None,
+		},
+		_ => claims.roles.to_owned(),
+	}
+}
+
+// Obtains the record identified by the token claims
+// Access methods verifying with an issuer can identify the record from a custom claim
+fn token_record(verify: &JwtAccessVerify, claims: &Claims) -> Result<Option<Thing>, Error> {
+	match verify {
+		JwtAccessVerify::Issuer(JwtAccessVerifyIssuer {
+			record: Some(rc),
+			..
+		}) => match find_claim(claims, &rc.claim) {
+			Some(serde_json::Value::String(id)) => {
+				Ok(Some(Thing::from((rc.tb.to_raw(), Id::from(id)))))
+			}
+			Some(serde_json::Value::Number(id)) => match id.as_i64() {
+				Some(id) => Ok(Some(Thing::from((rc.tb.to_raw(), Id::from(id))))),
+				None => Err(Error::InvalidAuth),
+			},
+			_ => {
+				trace!("The '{}' claim in the authentication token was invalid", rc.claim);
+				Err(Error::InvalidAuth)
+			}
+		},
+		_ => Ok(None),
+	}
+}
+
 static KEY: LazyLock<DecodingKey> = LazyLock::new(|| DecodingKey::from_secret(&[]));
 
 static DUD: LazyLock<Validation> = LazyLock::new(|| {
@@ -85,6 +172,36 @@ pub async fn basic(
 	pass: &str,
 	ns: Option<&str>,
 	db: Option<&str>,
+) -> Result<(), Error> {
+	// Describe the subject of the attempt, to throttle failed attempts
+	let subject = lockout::Subject::new(
+		ns.map(ToOwned::to_owned),
+		db.map(ToOwned::to_owned),
+		Some(user.to_owned()),
+		session.ip.clone(),
+	);
+	// Attempt to authenticate with the specified credentials
+	let res = lockout::guard(kvs, &subject, attempt_basic(kvs, session, user, pass, ns, db)).await;
+	// Record the attempt in the audit log
+	kvs.audit(|| match &res {
+		Ok(_) => AuditEntry::new(AuditEvent::Signin, "basic").with_auth(&session.au),
+		Err(e) => AuditEntry::new(AuditEvent::SigninFailure, format!("basic: {e}")).with_target(
+			Some(user.to_owned()),
+			ns.map(ToOwned::to_owned),
+			db.map(ToOwned::to_owned),
+		),
+	})
+	.await;
+	res
+}
+
+async fn attempt_basic(
+	kvs: &Datastore,
+	session: &mut Session,
+	user: &str,
+	pass: &str,
+	ns: Option<&str>,
+	db: Option<&str>,
 ) -> Result<(), Error> {
 	// Log the authentication type
 	trace!("Attempting basic authentication");
@@ -93,9 +210,14 @@ pub async fn basic(
 		// DB signin
 		(Some(ns), Some(db)) => match verify_db_creds(kvs, ns, db, user, pass).await {
 			Ok(u) => {
+				// Basic authentication can not provide a second factor
+				verify_second_factor(kvs, &u, Some(ns), Some(db), None).await?;
 				debug!("Authenticated as database user '{}'", user);
 				session.exp = expiration(u.duration.session)?;
-				session.au = Arc::new((&u, Level::Database(ns.to_owned(), db.to_owned())).into());
+				session.au = Arc::new(Auth::new(user_actor(
+					&u,
+					Level::Database(ns.to_owned(), db.to_owned()),
+				)));
 				Ok(())
 			}
 			Err(err) => Err(err),
@@ -103,9 +225,11 @@ pub async fn basic(
 		// NS signin
 		(Some(ns), None) => match verify_ns_creds(kvs, ns, user, pass).await {
 			Ok(u) => {
+				// Basic authentication can not provide a second factor
+				verify_second_factor(kvs, &u, Some(ns), None, None).await?;
 				debug!("Authenticated as namespace user '{}'", user);
 				session.exp = expiration(u.duration.session)?;
-				session.au = Arc::new((&u, Level::Namespace(ns.to_owned())).into());
+				session.au = Arc::new(Auth::new(user_actor(&u, Level::Namespace(ns.to_owned()))));
 				Ok(())
 			}
 			Err(err) => Err(err),
@@ -113,9 +237,11 @@ pub async fn basic(
 		// Root signin
 		(None, None) => match verify_root_creds(kvs, user, pass).await {
 			Ok(u) => {
+				// Basic authentication can not provide a second factor
+				verify_second_factor(kvs, &u, None, None, None).await?;
 				debug!("Authenticated as root user '{}'", user);
 				session.exp = expiration(u.duration.session)?;
-				session.au = Arc::new((&u, Level::Root).into());
+				session.au = Arc::new(Auth::new(user_actor(&u, Level::Root)));
 				Ok(())
 			}
 			Err(err) => Err(err),
@@ -124,7 +250,151 @@ pub async fn basic(
 	}
 }
 
+/// Authenticates a client which presented a certificate signed by a trusted authority.
+/// The name is either a system user at the level of the namespace and database, or the
+/// id of a record to authenticate with the specified record access method. The level is
+/// bound by the certificate itself, and the user or the record access method must allow
+/// authentication with client certificates.
+pub async fn certificate(
+	kvs: &Datastore,
+	session: &mut Session,
+	name: &str,
+	ac: Option<&str>,
+	ns: Option<&str>,
+	db: Option<&str>,
+) -> Result<(), Error> {
+	// Attempt to authenticate with the specified certificate subject
+	let res = attempt_certificate(kvs, session, name, ac, ns, db).await;
+	// Record the attempt in the audit log
+	kvs.audit(|| match &res {
+		Ok(_) => AuditEntry::new(AuditEvent::Signin, "certificate").with_auth(&session.au),
+		Err(e) => AuditEntry::new(AuditEvent::SigninFailure, format!("certificate: {e}"))
+			.with_target(
+				Some(name.to_owned()),
+				ns.map(ToOwned::to_owned),
+				db.map(ToOwned::to_owned),
+			),
+	})
+	.await;
+	res
+}
+
+async fn attempt_certificate(
+	kvs: &Datastore,
+	session: &mut Session,
+	name: &str,
+	ac: Option<&str>,
+	ns: Option<&str>,
+	db: Option<&str>,
+) -> Result<(), Error> {
+	// Log the authentication type
+	trace!("Attempting certificate authentication");
+	// Check if the parameters exist
+	match (ac, ns, db) {
+		// Record access
+		(Some(ac), Some(ns), Some(db)) => {
+			// Create a new readonly transaction
+			let tx = kvs.transaction(Read, Optimistic).await?;
+			// Get the database access method
+			let de = tx.get_db_access(ns, db, ac).await?;
+			// Ensure that the transaction is cancelled
+			tx.cancel().await?;
+			// Only record access methods can authenticate records
+			let AccessType::Record(at) = &de.kind else {
+				return Err(Error::AccessMethodMismatch);
+			};
+			// The access method must allow client certificates
+			if !at.certificate {
+				trace!("Record access method `{}` does not allow client certificates", ac);
+				return Err(Error::InvalidAuth);
+			}
+			// Parse the record id
+			let mut rid = syn::thing(name).map_err(|_| Error::InvalidAuth)?;
+			// AUTHENTICATE clause
+			if let Some(au) = &de.authenticate {
+				// Setup the system session for finding the signin record
+				let mut sess = Session::editor().with_ns(ns).with_db(db);
+				sess.rd = Some(rid.clone().into());
+				sess.ip.clone_from(&session.ip);
+				sess.or.clone_from(&session.or);
+				rid = authenticate_record(kvs, &sess, au).await?;
+			}
+			// Log the success
+			debug!("Authenticated with record access method `{}`", ac);
+			// Set the session
+			session.ns = Some(ns.to_owned());
+			session.db = Some(db.to_owned());
+			session.ac = Some(ac.to_owned());
+			session.rd = Some(Value::from(rid.to_owned()));
+			session.exp = expiration(de.duration.session)?;
+			session.au = Arc::new(Auth::new(Actor::new(
+				rid.to_string(),
+				Default::default(),
+				Level::Record(ns.to_string(), db.to_string(), rid.to_string()),
+			)));
+			Ok(())
+		}
+		// Record access requires a namespace and a database
+		(Some(_), _, _) => Err(Error::InvalidAuth),
+		// System users
+		(None, ns, db) => {
+			// Get the level of the user
+			let level = match (ns, db) {
+				(Some(ns), Some(db)) => Level::Database(ns.to_owned(), db.to_owned()),
+				(Some(ns), None) => Level::Namespace(ns.to_owned()),
+				(None, None) => Level::Root,
+				(None, Some(_)) => return Err(Error::InvalidAuth),
+			};
+			// Create a new readonly transaction
+			let tx = kvs.transaction(Read, Optimistic).await?;
+			// Fetch the specified user from storage
+			let user = match (ns, db) {
+				(Some(ns), Some(db)) => tx.get_db_user(ns, db, name).await,
+				(Some(ns), None) => tx.get_ns_user(ns, name).await,
+				_ => tx.get_root_user(name).await,
+			};
+			// Ensure that the transaction is cancelled
+			tx.cancel().await?;
+			let user = user.map_err(|e| {
+				trace!("Error while authenticating with a certificate: {e}");
+				Error::InvalidAuth
+			})?;
+			// The user must allow client certificates
+			if !user.certificate {
+				trace!("User '{}' does not allow client certificates", name);
+				return Err(Error::InvalidAuth);
+			}
+			// Certificate authentication can not provide a second factor
+			verify_second_factor(kvs, &user, ns, db, None).await?;
+			// Log the success
+			debug!("Authenticated as user '{}' with a certificate", name);
+			// Set the session
+			session.exp = expiration(user.duration.session)?;
+			session.au = Arc::new(Auth::new(user_actor(&user, level)));
+			Ok(())
+		}
+	}
+}
+
 pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Result<(), Error> {
+	// Attempt to authenticate with the specified token
+	let res = attempt_token(kvs, session, token).await;
+	// Record failed attempts in the audit log
+	if let Err(e) = &res {
+		kvs.audit(|| {
+			let entry = AuditEntry::new(AuditEvent::AuthenticateFailure, format!("token: {e}"));
+			// The claims are only used to describe the attempt
+			match decode::<Claims>(token, &KEY, &DUD) {
+				Ok(v) => entry.with_target(v.claims.id, v.claims.ns, v.claims.db),
+				Err(_) => entry,
+			}
+		})
+		.await;
+	}
+	res
+}
+
+async fn attempt_token(kvs: &Datastore, session: &mut Session, token: &str) -> Result<(), Error> {
 	// Log the authentication type
 	trace!("Attempting token authentication");
 	// Decode the token without verifying
@@ -167,23 +437,34 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			tx.cancel().await?;
 			// Obtain the configuration to verify the token based on the access method
 			let cf = match &de.kind {
-				AccessType::Record(at) => match &at.jwt.verify {
-					JwtAccessVerify::Key(key) => config(key.alg, key.key.as_bytes()),
-					#[cfg(feature = "jwks")]
-					JwtAccessVerify::Jwks(jwks) => {
-						if let Some(kid) = token_data.header.kid {
-							jwks::config(kvs, &kid, &jwks.url, token_data.header.alg).await
-						} else {
-							Err(Error::MissingTokenHeader("kid".to_string()))
-						}
-					}
-					#[cfg(not(feature = "jwks"))]
-					_ => return Err(Error::AccessMethodMismatch),
-				}?,
+				AccessType::Record(at) => {
+					access_config(kvs, &at.jwt.verify, &token_data.header).await?
+				}
 				_ => return Err(Error::AccessMethodMismatch),
 			};
 			// Verify the token
 			decode::<Claims>(token, &cf.0, &cf.1)?;
+			// Tokens issued alongside a refresh token are identified by its grant
+			let gr = match &de.kind {
+				AccessType::Record(at) if at.refresh => {
+					let Some(jti) = &token_data.claims.jti else {
+						return Err(Error::InvalidAuth);
+					};
+					// Create a new readonly transaction
+					let tx = kvs.transaction(Read, Optimistic).await?;
+					// Fetch the refresh grant from storage
+					let gr = tx.get_db_access_grant(ns, db, ac, jti).await;
+					// Ensure that the transaction is cancelled
+					tx.cancel().await?;
+					// The token is revoked along with the grant
+					match gr {
+						Ok(gr) if gr.revocation.is_none() => Some(jti.to_owned()),
+						// Return opaque error to avoid leaking the revocation status
+						_ => return Err(Error::InvalidAuth),
+					}
+				}
+				_ => None,
+			};
 			// AUTHENTICATE clause
 			if let Some(au) = &de.authenticate {
 				// Setup the system session for finding the signin record
@@ -201,6 +482,7 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			session.ns = Some(ns.to_owned());
 			session.db = Some(db.to_owned());
 			session.ac = Some(ac.to_owned());
+			session.gr = gr;
 			session.rd = Some(Value::from(rid.to_owned()));
 			session.exp = expiration(de.duration.session)?;
 			session.au = Arc::new(Auth::new(Actor::new(
@@ -230,19 +512,7 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			match &de.kind {
 				// If the access type is Jwt or Bearer, this is database access
 				AccessType::Jwt(_) | AccessType::Bearer(_) => {
-					let cf = match &de.kind.jwt().verify {
-						JwtAccessVerify::Key(key) => config(key.alg, key.key.as_bytes()),
-						#[cfg(feature = "jwks")]
-						JwtAccessVerify::Jwks(jwks) => {
-							if let Some(kid) = token_data.header.kid {
-								jwks::config(kvs, &kid, &jwks.url, token_data.header.alg).await
-							} else {
-								Err(Error::MissingTokenHeader("kid".to_string()))
-							}
-						}
-						#[cfg(not(feature = "jwks"))]
-						_ => return Err(Error::AccessMethodMismatch),
-					}?;
+					let cf = access_config(kvs, &de.kind.jwt().verify, &token_data.header).await?;
 					// Verify the token
 					decode::<Claims>(token, &cf.0, &cf.1)?;
 					// AUTHENTICATE clause
@@ -255,17 +525,13 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 						authenticate_generic(kvs, &sess, au).await?;
 					}
 					// Parse the roles
-					let roles = match &token_data.claims.roles {
-						// If no role is provided, grant the viewer role
-						None => vec![Role::Viewer],
-						// If roles are provided, parse them
-						Some(roles) => roles
-							.iter()
-							.map(|r| -> Result<Role, Error> {
-								Role::from_str(r.as_str()).map_err(Error::IamError)
-							})
-							.collect::<Result<Vec<_>, _>>()?,
-					};
+					let (roles, custom_roles) =
+						match token_roles(&de.kind.jwt().verify, &token_data.claims) {
+							// If no role is provided, grant the viewer role
+							None => (vec![Role::Viewer], vec![]),
+							// If roles are provided, parse them
+							Some(roles) => Role::parse_all(roles.iter().map(String::as_str)),
+						};
 					// Log the success
 					debug!("Authenticated to database `{}` with access method `{}`", db, ac);
 					// Set the session
@@ -274,58 +540,56 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 					session.db = Some(db.to_owned());
 					session.ac = Some(ac.to_owned());
 					session.exp = expiration(de.duration.session)?;
-					session.au = Arc::new(Auth::new(Actor::new(
-						de.name.to_string(),
-						roles,
-						Level::Database(ns.to_string(), db.to_string()),
-					)));
+					session.au = Arc::new(Auth::new(
+						Actor::new(
+							de.name.to_string(),
+							roles,
+							Level::Database(ns.to_string(), db.to_string()),
+						)
+						.with_custom_roles(custom_roles),
+					));
 				}
 				// If the access type is Record, this is record access
-				// Record access without an "id" claim is only possible if there is an AUTHENTICATE clause
-				// The clause can make up for the missing "id" claim by resolving other claims to a specific record
-				AccessType::Record(at) => match &de.authenticate {
-					Some(au) => {
-						trace!("Access method `{}` is record access with authenticate clause", ac);
-						let cf = match &at.jwt.verify {
-							JwtAccessVerify::Key(key) => config(key.alg, key.key.as_bytes()),
-							#[cfg(feature = "jwks")]
-							JwtAccessVerify::Jwks(jwks) => {
-								if let Some(kid) = token_data.header.kid {
-									jwks::config(kvs, &kid, &jwks.url, token_data.header.alg).await
-								} else {
-									Err(Error::MissingTokenHeader("kid".to_string()))
-								}
-							}
-							#[cfg(not(feature = "jwks"))]
-							_ => return Err(Error::AccessMethodMismatch),
-						}?;
-
-						// Verify the token
-						decode::<Claims>(token, &cf.0, &cf.1)?;
+				// Record access without an "id" claim is only possible if the record can be resolved otherwise
+				// The access method can map the record from another claim in the token
+				// An AUTHENTICATE clause can resolve other claims to a specific record
+				AccessType::Record(at) => {
+					trace!("Access method `{}` is record access without an \"id\" claim", ac);
+					let cf = access_config(kvs, &at.jwt.verify, &token_data.header).await?;
+					// Verify the token
+					decode::<Claims>(token, &cf.0, &cf.1)?;
+					// Resolve the record from the claim defined in the access method
+					let rid = token_record(&at.jwt.verify, &token_data.claims)?;
+					let rid = match (&de.authenticate, rid) {
 						// AUTHENTICATE clause
-						// Setup the system session for finding the signin record
-						let mut sess = Session::editor().with_ns(ns).with_db(db);
-						sess.tk = Some((&token_data.claims).into());
-						sess.ip.clone_from(&session.ip);
-						sess.or.clone_from(&session.or);
-						let rid = authenticate_record(kvs, &sess, au).await?;
-						// Log the success
-						debug!("Authenticated with record access method `{}`", ac);
-						// Set the session
-						session.tk = Some(value);
-						session.ns = Some(ns.to_owned());
-						session.db = Some(db.to_owned());
-						session.ac = Some(ac.to_owned());
-						session.rd = Some(Value::from(rid.to_owned()));
-						session.exp = expiration(de.duration.session)?;
-						session.au = Arc::new(Auth::new(Actor::new(
-							rid.to_string(),
-							Default::default(),
-							Level::Record(ns.to_string(), db.to_string(), rid.to_string()),
-						)));
-					}
-					_ => return Err(Error::AccessMethodMismatch),
-				},
+						(Some(au), rid) => {
+							// Setup the system session for finding the signin record
+							let mut sess = Session::editor().with_ns(ns).with_db(db);
+							sess.rd = rid.map(Value::from);
+							sess.tk = Some((&token_data.claims).into());
+							sess.ip.clone_from(&session.ip);
+							sess.or.clone_from(&session.or);
+							authenticate_record(kvs, &sess, au).await?
+						}
+						(None, Some(rid)) => rid,
+						(None, None) => return Err(Error::AccessMethodMismatch),
+					};
+					// Log the success
+					debug!("Authenticated with record access method `{}`", ac);
+					// Set the session
+					session.tk = Some(value);
+					session.ns = Some(ns.to_owned());
+					session.db = Some(db.to_owned());
+					session.ac = Some(ac.to_owned());
+					session.gr = None;
+					session.rd = Some(Value::from(rid.to_owned()));
+					session.exp = expiration(de.duration.session)?;
+					session.au = Arc::new(Auth::new(Actor::new(
+						rid.to_string(),
+						Default::default(),
+						Level::Record(ns.to_string(), db.to_string(), rid.to_string()),
+					)));
+				}
 			};
 			Ok(())
 		}
@@ -358,9 +622,8 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			session.ns = Some(ns.to_owned());
 			session.db = Some(db.to_owned());
 			session.exp = expiration(de.duration.session)?;
-			session.au = Arc::new(Auth::new(Actor::new(
-				id.to_string(),
-				de.roles.iter().map(|r| r.into()).collect(),
+			session.au = Arc::new(Auth::new(user_actor(
+				&de,
 				Level::Database(ns.to_string(), db.to_string()),
 			)));
 			Ok(())
@@ -381,19 +644,9 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			tx.cancel().await?;
 			// Obtain the configuration to verify the token based on the access method
 			let cf = match &de.kind {
-				AccessType::Jwt(_) | AccessType::Bearer(_) => match &de.kind.jwt().verify {
-					JwtAccessVerify::Key(key) => config(key.alg, key.key.as_bytes()),
-					#[cfg(feature = "jwks")]
-					JwtAccessVerify::Jwks(jwks) => {
-						if let Some(kid) = token_data.header.kid {
-							jwks::config(kvs, &kid, &jwks.url, token_data.header.alg).await
-						} else {
-							Err(Error::MissingTokenHeader("kid".to_string()))
-						}
-					}
-					#[cfg(not(feature = "jwks"))]
-					_ => return Err(Error::AccessMethodMismatch),
-				},
+				AccessType::Jwt(_) | AccessType::Bearer(_) => {
+					access_config(kvs, &de.kind.jwt().verify, &token_data.header).await
+				}
 				_ => return Err(Error::AccessMethodMismatch),
 			}?;
 			// Verify the token
@@ -408,16 +661,12 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 				authenticate_generic(kvs, &sess, au).await?;
 			}
 			// Parse the roles
-			let roles = match &token_data.claims.roles {
+			let (roles, custom_roles) = match token_roles(&de.kind.jwt().verify, &token_data.claims)
+			{
 				// If no role is provided, grant the viewer role
-				None => vec![Role::Viewer],
+				None => (vec![Role::Viewer], vec![]),
 				// If roles are provided, parse them
-				Some(roles) => roles
-					.iter()
-					.map(|r| -> Result<Role, Error> {
-						Role::from_str(r.as_str()).map_err(Error::IamError)
-					})
-					.collect::<Result<Vec<_>, _>>()?,
+				Some(roles) => Role::parse_all(roles.iter().map(String::as_str)),
 			};
 			// Log the success
 			trace!("Authenticated to namespace `{}` with access method `{}`", ns, ac);
@@ -426,11 +675,10 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			session.ns = Some(ns.to_owned());
 			session.ac = Some(ac.to_owned());
 			session.exp = expiration(de.duration.session)?;
-			session.au = Arc::new(Auth::new(Actor::new(
-				de.name.to_string(),
-				roles,
-				Level::Namespace(ns.to_string()),
-			)));
+			session.au = Arc::new(Auth::new(
+				Actor::new(de.name.to_string(), roles, Level::Namespace(ns.to_string()))
+					.with_custom_roles(custom_roles),
+			));
 			Ok(())
 		}
 		// Check if this is namespace authentication with user credentials
@@ -460,11 +708,7 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			session.tk = Some(value);
 			session.ns = Some(ns.to_owned());
 			session.exp = expiration(de.duration.session)?;
-			session.au = Arc::new(Auth::new(Actor::new(
-				id.to_string(),
-				de.roles.iter().map(|r| r.into()).collect(),
-				Level::Namespace(ns.to_string()),
-			)));
+			session.au = Arc::new(Auth::new(user_actor(&de, Level::Namespace(ns.to_string()))));
 			Ok(())
 		}
 		// Check if this is root access
@@ -482,19 +726,9 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			tx.cancel().await?;
 			// Obtain the configuration to verify the token based on the access method
 			let cf = match &de.kind {
-				AccessType::Jwt(_) | AccessType::Bearer(_) => match &de.kind.jwt().verify {
-					JwtAccessVerify::Key(key) => config(key.alg, key.key.as_bytes()),
-					#[cfg(feature = "jwks")]
-					JwtAccessVerify::Jwks(jwks) => {
-						if let Some(kid) = token_data.header.kid {
-							jwks::config(kvs, &kid, &jwks.url, token_data.header.alg).await
-						} else {
-							Err(Error::MissingTokenHeader("kid".to_string()))
-						}
-					}
-					#[cfg(not(feature = "jwks"))]
-					_ => return Err(Error::AccessMethodMismatch),
-				},
+				AccessType::Jwt(_) | AccessType::Bearer(_) => {
+					access_config(kvs, &de.kind.jwt().verify, &token_data.header).await
+				}
 				_ => return Err(Error::AccessMethodMismatch),
 			}?;
 			// Verify the token
@@ -509,16 +743,12 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 				authenticate_generic(kvs, &sess, au).await?;
 			}
 			// Parse the roles
-			let roles = match &token_data.claims.roles {
+			let (roles, custom_roles) = match token_roles(&de.kind.jwt().verify, &token_data.claims)
+			{
 				// If no role is provided, grant the viewer role
-				None => vec![Role::Viewer],
+				None => (vec![Role::Viewer], vec![]),
 				// If roles are provided, parse them
-				Some(roles) => roles
-					.iter()
-					.map(|r| -> Result<Role, Error> {
-						Role::from_str(r.as_str()).map_err(Error::IamError)
-					})
-					.collect::<Result<Vec<_>, _>>()?,
+				Some(roles) => Role::parse_all(roles.iter().map(String::as_str)),
 			};
 			// Log the success
 			trace!("Authenticated to root with access method `{}`", ac);
@@ -526,7 +756,9 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			session.tk = Some(value);
 			session.ac = Some(ac.to_owned());
 			session.exp = expiration(de.duration.session)?;
-			session.au = Arc::new(Auth::new(Actor::new(de.name.to_string(), roles, Level::Root)));
+			session.au = Arc::new(Auth::new(
+				Actor::new(de.name.to_string(), roles, Level::Root).with_custom_roles(custom_roles),
+			));
 			Ok(())
 		}
 		// Check if this is root authentication with user credentials
@@ -554,11 +786,7 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			// Set the session
 			session.tk = Some(value);
 			session.exp = expiration(de.duration.session)?;
-			session.au = Arc::new(Auth::new(Actor::new(
-				id.to_string(),
-				de.roles.iter().map(|r| r.into()).collect(),
-				Level::Root,
-			)));
+			session.au = Arc::new(Auth::new(user_actor(&de, Level::Root)));
 			Ok(())
 		}
 		// There was an auth error
@@ -566,6 +794,46 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 	}
 }
 
+/// Resolves the grants of the custom roles assigned to an actor.
+/// A role which does not exist, for instance because it has been removed, grants nothing.
+pub(crate) async fn resolve_grants(
+	tx: &Transaction,
+	names: &[String],
+	level: &Level,
+) -> Result<Vec<Grant>, Error> {
+	let mut grants = Vec::new();
+	for name in names {
+		let role = match level {
+			Level::Root => tx.get_root_role(name).await,
+			Level::Namespace(ns) => tx.get_ns_role(ns, name).await,
+			Level::Database(ns, db) => tx.get_db_role(ns, db, name).await,
+			_ => continue,
+		};
+		match role {
+			Ok(role) => grants.extend(role.grants.iter().cloned()),
+			Err(
+				Error::RoleRootNotFound {
+					..
+				}
+				| Error::RoleNsNotFound {
+					..
+				}
+				| Error::RoleDbNotFound {
+					..
+				},
+			) => trace!("Ignoring the role `{name}` which does not exist"),
+			Err(e) => return Err(e),
+		}
+	}
+	Ok(grants)
+}
+
+/// Builds the actor of an authenticated system user.
+/// Its custom roles are resolved when a request is processed.
+pub(crate) fn user_actor(user: &DefineUserStatement, level: Level) -> Actor {
+	(user, level).into()
+}
+
 pub async fn verify_root_creds(
 	ds: &Datastore,
 	user: &str,
@@ -635,6 +903,93 @@ pub async fn verify_db_creds(
 	Ok(user)
 }
 
+/// Verifies the second authentication factor of a system user, if the user has one
+/// The code can either be a time-based one-time password or a single-use recovery code
+/// The code is checked and consumed within a single transaction, so that concurrent
+/// attempts can not use the same code more than once
+pub(crate) async fn verify_second_factor(
+	kvs: &Datastore,
+	user: &DefineUserStatement,
+	ns: Option<&str>,
+	db: Option<&str>,
+	code: Option<&str>,
+) -> Result<(), Error> {
+	// Users without a second factor only need a password
+	let Some(secret) = &user.totp else {
+		return Ok(());
+	};
+	// Let the caller know that the second factor is still required
+	let Some(code) = code else {
+		return Err(Error::MissingSecondFactor);
+	};
+	// Create a new writeable transaction
+	let tx = kvs.transaction(Write, Optimistic).await?;
+	// Consume the code, committing only if the code was valid
+	match consume_second_factor(&tx, secret, &user.name, ns, db, code).await {
+		Ok(_) => tx.commit().await,
+		Err(e) => {
+			tx.cancel().await?;
+			Err(e)
+		}
+	}
+}
+
+async fn consume_second_factor(
+	tx: &Transaction,
+	secret: &str,
+	name: &str,
+	ns: Option<&str>,
+	db: Option<&str>,
+	code: &str,
+) -> Result<(), Error> {
+	// Fetch the last time step which was accepted for the user
+	let key: Key = match (ns, db) {
+		(Some(ns), Some(db)) => crate::key::database::tf::new(ns, db, name).into(),
+		(Some(ns), None) => crate::key::namespace::tf::new(ns, name).into(),
+		_ => crate::key::root::tf::new(name).into(),
+	};
+	let last = match tx.get(key.clone(), None).await? {
+		Some(v) => Some(u64::from_be_bytes(
+			v.try_into().map_err(|_| Error::Unreachable("consume_second_factor"))?,
+		)),
+		None => None,
+	};
+	// Check the code against the time-based one-time passwords which were not used yet
+	if let Some(step) = totp::verify(secret, code, Utc::now().timestamp(), last) {
+		return tx.set(key, step.to_be_bytes().to_vec(), None).await;
+	}
+	// Fetch the stored user, which holds the recovery codes not used yet
+	let user = match (ns, db) {
+		(Some(ns), Some(db)) => tx.get_db_user(ns, db, name).await?,
+		(Some(ns), None) => tx.get_ns_user(ns, name).await?,
+		_ => tx.get_root_user(name).await?,
+	};
+	// Check the code against the recovery codes
+	let Some(idx) = user.recovery.iter().position(|hash| {
+		PasswordHash::new(hash)
+			.is_ok_and(|hash| Argon2::default().verify_password(code.as_ref(), &hash).is_ok())
+	}) else {
+		return Err(Error::InvalidSecondFactor);
+	};
+	// Remove the recovery code so that it can not be used again
+	let mut user = (*user).clone();
+	user.recovery.remove(idx);
+	match (ns, db) {
+		(Some(ns), Some(db)) => {
+			let key = crate::key::database::us::new(ns, db, name);
+			tx.set(key, user, None).await
+		}
+		(Some(ns), None) => {
+			let key = crate::key::namespace::us::new(ns, name);
+			tx.set(key, user, None).await
+		}
+		_ => {
+			let key = crate::key::root::us::new(name);
+			tx.set(key, user, None).await
+		}
+	}
+}
+
 fn verify_pass(pass: &str, hash: &str) -> Result<(), Error> {
 	// Compute the hash and verify the password
 	let hash = PasswordHash::new(hash).unwrap();
@@ -954,6 +1309,156 @@ mod tests {
 		}
 	}
 
+	#[tokio::test]
+	async fn test_certificate_user() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute(
+			r#"
+			DEFINE USER user ON DATABASE PASSWORD 'pass' ROLES EDITOR CERTIFICATE;
+			DEFINE USER other ON DATABASE PASSWORD 'pass' ROLES EDITOR;
+			"#,
+			&sess,
+			None,
+		)
+		.await
+		.unwrap();
+
+		//
+		// Test with an existing user
+		//
+		{
+			let mut sess = Session {
+				ns: Some("test".to_string()),
+				db: Some("test".to_string()),
+				..Default::default()
+			};
+			let res = certificate(&ds, &mut sess, "user", None, Some("test"), Some("test")).await;
+
+			assert!(res.is_ok(), "Failed to authenticate with a certificate: {:?}", res);
+			assert_eq!(sess.au.id(), "user");
+			assert!(sess.au.is_db());
+			assert_eq!(sess.au.level().ns(), Some("test"));
+			assert_eq!(sess.au.level().db(), Some("test"));
+			assert!(sess.au.has_role(&Role::Editor), "Auth user expected to have Editor role");
+		}
+
+		//
+		// Test with a user on a different level
+		//
+		{
+			let mut sess = Session::default();
+			let res = certificate(&ds, &mut sess, "user", None, None, None).await;
+
+			assert!(res.is_err(), "Unexpected successful authentication: {:?}", res);
+		}
+
+		//
+		// Test with a user which does not allow client certificates
+		//
+		{
+			let mut sess = Session::default();
+			let res = certificate(&ds, &mut sess, "other", None, Some("test"), Some("test")).await;
+
+			assert!(res.is_err(), "Unexpected successful authentication: {:?}", res);
+		}
+	}
+
+	#[tokio::test]
+	async fn test_certificate_record() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute(
+			r#"
+			DEFINE ACCESS services ON DATABASE TYPE RECORD WITH CERTIFICATE DURATION FOR SESSION 1h;
+			DEFINE ACCESS users ON DATABASE TYPE RECORD DURATION FOR SESSION 1h;
+			DEFINE ACCESS token ON DATABASE TYPE JWT ALGORITHM HS512 KEY 'secret';
+			"#,
+			&sess,
+			None,
+		)
+		.await
+		.unwrap();
+
+		//
+		// Test with a record access method
+		//
+		{
+			let mut sess = Session::default();
+			let res = certificate(
+				&ds,
+				&mut sess,
+				"service:billing",
+				Some("services"),
+				Some("test"),
+				Some("test"),
+			)
+			.await;
+
+			assert!(res.is_ok(), "Failed to authenticate with a certificate: {:?}", res);
+			assert_eq!(sess.ns, Some("test".to_string()));
+			assert_eq!(sess.db, Some("test".to_string()));
+			assert_eq!(sess.ac, Some("services".to_string()));
+			assert_eq!(sess.au.id(), "service:billing");
+			assert!(sess.au.is_record());
+			assert!(sess.exp.is_some(), "Session expiration is expected to be set");
+		}
+
+		//
+		// Test with an access method which is not a record access method
+		//
+		{
+			let mut sess = Session::default();
+			let res = certificate(
+				&ds,
+				&mut sess,
+				"service:billing",
+				Some("token"),
+				Some("test"),
+				Some("test"),
+			)
+			.await;
+
+			assert!(res.is_err(), "Unexpected successful authentication: {:?}", res);
+		}
+
+		//
+		// Test with a record access method which does not allow client certificates
+		//
+		{
+			let mut sess = Session::default();
+			let res = certificate(
+				&ds,
+				&mut sess,
+				"service:billing",
+				Some("users"),
+				Some("test"),
+				Some("test"),
+			)
+			.await;
+
+			assert!(res.is_err(), "Unexpected successful authentication: {:?}", res);
+		}
+
+		//
+		// Test with a subject which is not a record id
+		//
+		{
+			let mut sess = Session::default();
+			let res = certificate(
+				&ds,
+				&mut sess,
+				"billing",
+				Some("services"),
+				Some("test"),
+				Some("test"),
+			)
+			.await;
+
+			assert!(res.is_err(), "Unexpected successful authentication: {:?}", res);
+		}
+	}
+
 	#[tokio::test]
 	async fn test_token_root() {
 		let secret = "jwt_secret";
@@ -2,7 +2,7 @@ pub(crate) mod analyzer;
 mod doclength;
 pub(crate) mod highlighter;
 mod offsets;
-mod postings;
+pub(super) mod postings;
 pub(super) mod scorer;
 pub(super) mod termdocs;
 pub(crate) mod terms;
@@ -7,9 +7,9 @@ use crate::idx::trees::store::{IndexStores, TreeNodeProvider};
 use crate::idx::{IndexKeyBase, VersionedStore};
 use crate::kvs::{Key, Transaction, TransactionType};
 
-pub(super) type TermFrequency = u64;
+pub(in crate::idx) type TermFrequency = u64;
 
-pub(super) struct Postings {
+pub(in crate::idx) struct Postings {
 	ixs: IndexStores,
 	state_key: Key,
 	index_key_base: IndexKeyBase,
@@ -18,7 +18,7 @@ pub(super) struct Postings {
 }
 
 impl Postings {
-	pub(super) async fn new(
+	pub(in crate::idx) async fn new(
 		ixs: &IndexStores,
 		tx: &Transaction,
 		index_key_base: IndexKeyBase,
@@ -49,7 +49,7 @@ impl Postings {
 		})
 	}
 
-	pub(super) async fn update_posting(
+	pub(in crate::idx) async fn update_posting(
 		&mut self,
 		tx: &Transaction,
 		term_id: TermId,
@@ -60,7 +60,7 @@ impl Postings {
 		self.btree.insert(tx, &mut self.store, key, term_freq).await
 	}
 
-	pub(super) async fn get_term_frequency(
+	pub(in crate::idx) async fn get_term_frequency(
 		&self,
 		tx: &Transaction,
 		term_id: TermId,
@@ -70,7 +70,7 @@ impl Postings {
 		self.btree.search(tx, &self.store, &key).await
 	}
 
-	pub(super) async fn remove_posting(
+	pub(in crate::idx) async fn remove_posting(
 		&mut self,
 		tx: &Transaction,
 		term_id: TermId,
@@ -80,11 +80,11 @@ impl Postings {
 		self.btree.delete(tx, &mut self.store, key).await
 	}
 
-	pub(super) async fn statistics(&self, tx: &Transaction) -> Result<BStatistics, Error> {
+	pub(in crate::idx) async fn statistics(&self, tx: &Transaction) -> Result<BStatistics, Error> {
 		self.btree.statistics(tx, &self.store).await
 	}
 
-	pub(super) async fn finish(&mut self, tx: &Transaction) -> Result<(), Error> {
+	pub(in crate::idx) async fn finish(&mut self, tx: &Transaction) -> Result<(), Error> {
 		if let Some(new_cache) = self.store.finish(tx).await? {
 			let state = self.btree.inc_generation();
 			tx.set(self.state_key.clone(), VersionedStore::try_into(state)?, None).await?;
@@ -9,18 +9,18 @@ use std::sync::Arc;
 
 pub(in crate::idx) type TermsDocs = Arc<Vec<Option<(TermId, RoaringTreemap)>>>;
 
-pub(super) struct TermDocs {
+pub(in crate::idx) struct TermDocs {
 	index_key_base: IndexKeyBase,
 }
 
 impl TermDocs {
-	pub(super) fn new(index_key_base: IndexKeyBase) -> Self {
+	pub(in crate::idx) fn new(index_key_base: IndexKeyBase) -> Self {
 		Self {
 			index_key_base,
 		}
 	}
 
-	pub(super) async fn set_doc(
+	pub(in crate::idx) async fn set_doc(
 		&self,
 		tx: &Transaction,
 		term_id: TermId,
@@ -36,7 +36,7 @@ impl TermDocs {
 		Ok(())
 	}
 
-	pub(super) async fn get_docs(
+	pub(in crate::idx) async fn get_docs(
 		&self,
 		tx: &Transaction,
 		term_id: TermId,
@@ -50,7 +50,7 @@ impl TermDocs {
 		}
 	}
 
-	pub(super) async fn remove_doc(
+	pub(in crate::idx) async fn remove_doc(
 		&self,
 		tx: &Transaction,
 		term_id: TermId,
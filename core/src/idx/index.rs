@@ -2,11 +2,12 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::err::Error;
 use crate::idx::ft::FtIndex;
+use crate::idx::sparse::SparseIndex;
 use crate::idx::trees::mtree::MTreeIndex;
 use crate::idx::IndexKeyBase;
 use crate::key;
 use crate::kvs::TransactionType;
-use crate::sql::index::{HnswParams, MTreeParams, SearchParams};
+use crate::sql::index::{HnswParams, MTreeParams, SearchParams, SparseParams};
 use crate::sql::statements::DefineIndexStatement;
 use crate::sql::{Array, Index, Part, Thing, Value};
 use reblessive::tree::Stk;
@@ -49,6 +50,7 @@ impl<'a> IndexOperation<'a> {
 			Index::Search(p) => self.index_full_text(stk, p).await,
 			Index::MTree(p) => self.index_mtree(stk, p).await,
 			Index::Hnsw(p) => self.index_hnsw(p).await,
+			Index::Sparse(p) => self.index_sparse(p).await,
 		}
 	}
 
@@ -197,6 +199,23 @@ impl<'a> IndexOperation<'a> {
 		}
 		Ok(())
 	}
+
+	async fn index_sparse(&mut self, p: &SparseParams) -> Result<(), Error> {
+		let txn = self.ctx.tx();
+		let ikb = IndexKeyBase::new(self.opt.ns()?, self.opt.db()?, self.ix)?;
+		let mut sp =
+			SparseIndex::new(self.ctx.get_index_stores(), &txn, ikb, p, TransactionType::Write)
+				.await?;
+		// Delete the old index data
+		if let Some(o) = self.o.take() {
+			sp.remove_document(&txn, self.rid, &o).await?;
+		}
+		// Create the new index data
+		if let Some(n) = self.n.take() {
+			sp.index_document(&txn, self.rid, &n).await?;
+		}
+		sp.finish(&txn).await
+	}
 }
 
 /// Extract from the given document, the values required by the index and put then in an array.
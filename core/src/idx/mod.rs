@@ -2,6 +2,7 @@ pub mod docids;
 pub(crate) mod ft;
 pub(crate) mod index;
 pub mod planner;
+pub(crate) mod sparse;
 pub mod trees;
 
 use crate::err::Error;
@@ -25,6 +26,7 @@ use crate::key::index::hd::Hd;
 use crate::key::index::he::He;
 use crate::key::index::hi::Hi;
 use crate::key::index::hl::Hl;
+use crate::key::index::hq::Hq;
 use crate::key::index::hs::Hs;
 use crate::key::index::hv::Hv;
 use crate::key::index::vm::Vm;
@@ -240,6 +242,16 @@ impl IndexKeyBase {
 		.into()
 	}
 
+	fn new_hq_key(&self) -> Key {
+		Hq::new(
+			self.inner.ns.as_str(),
+			self.inner.db.as_str(),
+			self.inner.tb.as_str(),
+			self.inner.ix.as_str(),
+		)
+		.into()
+	}
+
 	fn new_hs_key(&self) -> Key {
 		Hs::new(
 			self.inner.ns.as_str(),
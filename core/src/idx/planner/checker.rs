@@ -1,3 +1,4 @@
+use crate::cnf::HNSW_MAX_CHECKED_ELEMENTS;
 use crate::ctx::Context;
 use crate::dbs::{Iterable, Options};
 use crate::doc::CursorDoc;
@@ -7,9 +8,10 @@ use crate::idx::planner::iterators::KnnIteratorResult;
 use crate::idx::trees::hnsw::docs::HnswDocs;
 use crate::idx::trees::knn::Ids64;
 use crate::kvs::Transaction;
-use crate::sql::{Cond, Thing, Value};
+use crate::sql::{Cond, Idiom, Thing, Value};
 use ahash::HashMap;
 use reblessive::tree::Stk;
+use roaring::RoaringTreemap;
 use std::collections::hash_map::Entry;
 use std::collections::VecDeque;
 use std::sync::Arc;
@@ -34,10 +36,83 @@ impl<'a> HnswConditionChecker<'a> {
 			ctx,
 			opt,
 			cond,
+			filter: None,
+			visits: 0,
+			collected: Default::default(),
 			cache: Default::default(),
 		})
 	}
 
+	/// Restricts the documents to the given doc ids.
+	/// The doc ids come from another index and are checked before the condition is evaluated.
+	/// The vectors of the documents are read from the given field when the search
+	/// falls back to computing the distance of every document of the filter.
+	pub(in crate::idx) fn with_filter(mut self, filter: RoaringTreemap, col: Idiom) -> Self {
+		if let Self::HnswCondition(c) = &mut self {
+			c.filter = Some((filter, col));
+		}
+		self
+	}
+
+	/// Counts a visited element.
+	/// Returns false once the search visited more elements than it is allowed to.
+	pub(in crate::idx) fn visit(&mut self) -> bool {
+		match self {
+			Self::HnswCondition(c) => {
+				c.visits += 1;
+				c.visits <= *HNSW_MAX_CHECKED_ELEMENTS
+			}
+			Self::Hnsw(_) => true,
+		}
+	}
+
+	/// Returns true if the search visited more elements than it is allowed to
+	pub(in crate::idx) fn is_exhausted(&self) -> bool {
+		match self {
+			Self::HnswCondition(c) => c.visits > *HNSW_MAX_CHECKED_ELEMENTS,
+			Self::Hnsw(_) => false,
+		}
+	}
+
+	/// Returns the doc ids of the filter, if the vectors of its documents can be read
+	pub(in crate::idx) fn filter_doc_ids(&self) -> Option<RoaringTreemap> {
+		match self {
+			Self::HnswCondition(c) => c.filter.as_ref().map(|(f, _)| f.clone()),
+			Self::Hnsw(_) => None,
+		}
+	}
+
+	/// Returns the value of the indexed field of a document which has been checked
+	pub(in crate::idx) fn field_value(&self, doc_id: DocId) -> Option<Value> {
+		match self {
+			Self::HnswCondition(c) => c.field_value(doc_id),
+			Self::Hnsw(_) => None,
+		}
+	}
+
+	/// Keeps the checked documents of an element while the element is part of the search
+	pub(in crate::idx) fn collect(&mut self, doc_ids: &Ids64) {
+		if let Self::HnswCondition(c) = self {
+			c.collect(doc_ids)
+		}
+	}
+
+	/// Releases the documents of an element which is no longer part of the search.
+	/// The documents which are not held by another element are expired.
+	pub(in crate::idx) fn release(&mut self, doc_ids: Ids64) {
+		if let Self::HnswCondition(c) = self {
+			c.release(doc_ids)
+		}
+	}
+
+	/// Returns true if the filter rejects every document
+	pub(in crate::idx) fn is_empty_filter(&self) -> bool {
+		match self {
+			Self::HnswCondition(c) => c.filter.as_ref().is_some_and(|(f, _)| f.is_empty()),
+			Self::Hnsw(_) => false,
+		}
+	}
+
 	pub(in crate::idx) async fn check_truthy(
 		&mut self,
 		tx: &Transaction,
@@ -51,9 +126,12 @@ impl<'a> HnswConditionChecker<'a> {
 		}
 	}
 
-	pub(in crate::idx) fn expire(&mut self, doc_id: u64) {
-		if let Self::HnswCondition(c) = self {
-			c.expire(doc_id)
+	/// Keeps only the documents matching the condition.
+	/// The documents must have been checked previously.
+	pub(in crate::idx) fn truthy_docs(&self, doc_ids: Ids64) -> Ids64 {
+		match self {
+			Self::HnswCondition(c) => c.truthy_docs(doc_ids),
+			Self::Hnsw(_) => doc_ids,
 		}
 	}
 
@@ -278,6 +356,9 @@ pub struct HnswCondChecker<'a> {
 	ctx: &'a Context,
 	opt: &'a Options,
 	cond: Arc<Cond>,
+	filter: Option<(RoaringTreemap, Idiom)>,
+	visits: usize,
+	collected: HashMap<DocId, usize>,
 	cache: HashMap<DocId, CheckerCacheEntry>,
 }
 
@@ -298,10 +379,17 @@ impl<'a> HnswCondChecker<'a> {
 			if match self.cache.entry(doc_id) {
 				Entry::Occupied(e) => e.get().truthy,
 				Entry::Vacant(e) => {
-					let rid = docs.get_thing(tx, doc_id).await?;
-					let ent =
+					let ent = if self.filter.as_ref().is_some_and(|(f, _)| !f.contains(doc_id)) {
+						// Rejected by the filter, there is no need to fetch the record
+						CheckerCacheEntry {
+							record: None,
+							truthy: false,
+						}
+					} else {
+						let rid = docs.get_thing(tx, doc_id).await?;
 						CheckerCacheEntry::build(stk, self.ctx, self.opt, rid, self.cond.as_ref())
-							.await?;
+							.await?
+					};
 					let truthy = ent.truthy;
 					e.insert(ent);
 					truthy
@@ -313,6 +401,40 @@ impl<'a> HnswCondChecker<'a> {
 		Ok(res)
 	}
 
+	fn truthy_docs(&self, doc_ids: Ids64) -> Ids64 {
+		doc_ids.filter(|doc_id| self.cache.get(&doc_id).is_some_and(|e| e.truthy))
+	}
+
+	fn field_value(&self, doc_id: DocId) -> Option<Value> {
+		let (_, col) = self.filter.as_ref()?;
+		match self.cache.get(&doc_id) {
+			Some(CheckerCacheEntry {
+				record: Some((_, value)),
+				truthy: true,
+			}) => Some(value.pick(col)),
+			_ => None,
+		}
+	}
+
+	fn collect(&mut self, doc_ids: &Ids64) {
+		for doc_id in doc_ids.iter() {
+			*self.collected.entry(doc_id).or_default() += 1;
+		}
+	}
+
+	fn release(&mut self, doc_ids: Ids64) {
+		for doc_id in doc_ids.iter() {
+			if let Entry::Occupied(mut e) = self.collected.entry(doc_id) {
+				*e.get_mut() -= 1;
+				if *e.get() > 0 {
+					continue;
+				}
+				e.remove();
+			}
+			self.expire(doc_id);
+		}
+	}
+
 	fn expire(&mut self, doc_id: DocId) {
 		self.cache.remove(&doc_id);
 	}
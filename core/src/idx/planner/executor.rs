@@ -1,6 +1,7 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::ctx::Context;
 use crate::dbs::Options;
-use crate::doc::CursorDoc;
+use crate::doc::{CursorDoc, Document};
 use crate::err::Error;
 use crate::idx::docids::DocIds;
 use crate::idx::ft::analyzer::{Analyzer, TermsList, TermsSet};
@@ -11,7 +12,7 @@ use crate::idx::ft::terms::Terms;
 use crate::idx::ft::{FtIndex, MatchRef};
 use crate::idx::planner::checker::{HnswConditionChecker, MTreeConditionChecker};
 use crate::idx::planner::iterators::{
-	IndexEqualThingIterator, IndexJoinThingIterator, IndexRangeThingIterator,
+	CollectorRecord, IndexEqualThingIterator, IndexJoinThingIterator, IndexRangeThingIterator,
 	IndexUnionThingIterator, IteratorRecord, IteratorRef, KnnIterator, KnnIteratorResult,
 	MatchesThingIterator, MultipleIterators, ThingIterator, UniqueEqualThingIterator,
 	UniqueJoinThingIterator, UniqueRangeThingIterator, UniqueUnionThingIterator,
@@ -21,13 +22,16 @@ use crate::idx::planner::plan::IndexOperator::Matches;
 use crate::idx::planner::plan::{IndexOperator, IndexOption, RangeValue};
 use crate::idx::planner::tree::{IdiomPosition, IndexRef, IndexesMap};
 use crate::idx::planner::IterationStage;
+use crate::idx::sparse::SparseIndex;
 use crate::idx::trees::mtree::MTreeIndex;
 use crate::idx::trees::store::hnsw::SharedHnswIndex;
 use crate::idx::IndexKeyBase;
+use crate::key::index::Index as IndexKey;
 use crate::kvs::{Key, TransactionType};
+use crate::sql::after::Cursor;
 use crate::sql::index::{Distance, Index};
 use crate::sql::statements::DefineIndexStatement;
-use crate::sql::{Cond, Expression, Idiom, Number, Object, Table, Thing, Value};
+use crate::sql::{Array, Cond, Expression, Idiom, Number, Object, Operator, Table, Thing, Value};
 use num_traits::{FromPrimitive, ToPrimitive};
 use reblessive::tree::Stk;
 use rust_decimal::Decimal;
@@ -59,6 +63,8 @@ impl KnnBruteForceExpression {
 pub(super) type KnnBruteForceExpressions = HashMap<Arc<Expression>, KnnBruteForceExpression>;
 
 pub(super) type KnnExpressions = HashSet<Arc<Expression>>;
+/// The records an approximate KNN search is restricted to
+type KnnFilter = Arc<Vec<Arc<Thing>>>;
 
 #[derive(Clone)]
 pub(crate) struct QueryExecutor(Arc<InnerQueryExecutor>);
@@ -72,6 +78,7 @@ pub(super) struct InnerQueryExecutor {
 	index_definitions: Vec<DefineIndexStatement>,
 	mt_entries: HashMap<Arc<Expression>, MtEntry>,
 	hnsw_entries: HashMap<Arc<Expression>, HnswEntry>,
+	sparse_entries: HashMap<Arc<Expression>, SparseEntry>,
 	knn_bruteforce_entries: HashMap<Arc<Expression>, KnnBruteForceEntry>,
 }
 
@@ -122,8 +129,18 @@ impl InnerQueryExecutor {
 		let mut mt_entries = HashMap::default();
 		let mut hnsw_map: HashMap<IndexRef, SharedHnswIndex> = HashMap::default();
 		let mut hnsw_entries = HashMap::default();
+		let mut sparse_entries = HashMap::default();
 		let mut knn_bruteforce_entries = HashMap::with_capacity(knns.len());
 		let knn_condition = knn_condition.map(Arc::new);
+		// The records matching the condition of an approximate KNN search, if other indexes can resolve it
+		let knn_filter = match &knn_condition {
+			Some(cond)
+				if im.options.iter().any(|(_, io)| matches!(io.op(), IndexOperator::Ann(..))) =>
+			{
+				Self::build_knn_filter(ctx, opt, &im, cond).await?
+			}
+			_ => None,
+		};
 
 		// Create all the instances of FtIndex
 		// Build the FtEntries and map them to Idioms and MatchRef
@@ -218,6 +235,8 @@ impl InnerQueryExecutor {
 										*k,
 										*ef,
 										knn_condition.clone(),
+										knn_filter.clone(),
+										&idx_def.cols[0],
 									)
 									.await?
 								}
@@ -238,6 +257,8 @@ impl InnerQueryExecutor {
 										*k,
 										*ef,
 										knn_condition.clone(),
+										knn_filter.clone(),
+										&idx_def.cols[0],
 									)
 									.await?;
 									e.insert(hnsw);
@@ -247,6 +268,25 @@ impl InnerQueryExecutor {
 							hnsw_entries.insert(exp, entry);
 						}
 					}
+					Index::Sparse(p) => {
+						if let IndexOperator::SparseKnn(v, k) = io.op() {
+							let ikb = IndexKeyBase::new(opt.ns()?, opt.db()?, idx_def)?;
+							let tx = ctx.tx();
+							let sp = SparseIndex::new(
+								ctx.get_index_stores(),
+								&tx,
+								ikb,
+								p,
+								TransactionType::Read,
+							)
+							.await?;
+							drop(tx);
+							let entry =
+								SparseEntry::new(stk, ctx, opt, &sp, v, *k, knn_condition.clone())
+									.await?;
+							sparse_entries.insert(exp, entry);
+						}
+					}
 					_ => {}
 				}
 			}
@@ -266,10 +306,70 @@ impl InnerQueryExecutor {
 			index_definitions: im.definitions,
 			mt_entries,
 			hnsw_entries,
+			sparse_entries,
 			knn_bruteforce_entries,
 		})
 	}
 
+	/// Collects the records matching the equality conditions which are combined
+	/// with an approximate KNN search, and which can be resolved by a regular index.
+	/// The search uses them to discard the other records while traversing the graph.
+	#[allow(clippy::mutable_key_type)]
+	async fn build_knn_filter(
+		ctx: &Context,
+		opt: &Options,
+		im: &IndexesMap,
+		cond: &Cond,
+	) -> Result<Option<KnnFilter>, Error> {
+		let mut conjuncts = Vec::new();
+		Self::collect_conjuncts(&cond.0, &mut conjuncts);
+		let mut filter: Option<HashSet<Arc<Thing>>> = None;
+		for (exp, io) in &im.options {
+			if !conjuncts.contains(&exp.as_ref()) {
+				continue;
+			}
+			let Some(ix) = im.definitions.get(io.ix_ref() as usize) else {
+				continue;
+			};
+			let Some(mut it) = QueryExecutor::new_equality_iterator(opt, ix, io)? else {
+				continue;
+			};
+			let txn = ctx.tx();
+			let mut things = HashSet::new();
+			loop {
+				let batch: Vec<CollectorRecord> =
+					it.next_batch(ctx, &txn, *NORMAL_FETCH_SIZE).await?;
+				if batch.is_empty() {
+					break;
+				}
+				things.extend(batch.into_iter().map(|(t, _, _)| t));
+			}
+			// Every conjunct has to be satisfied
+			filter = Some(match filter {
+				Some(f) => f.intersection(&things).cloned().collect(),
+				None => things,
+			});
+		}
+		Ok(filter.map(|f| Arc::new(f.into_iter().collect())))
+	}
+
+	/// Collects the expressions which are combined with AND at the top of the condition
+	fn collect_conjuncts<'a>(v: &'a Value, conjuncts: &mut Vec<&'a Expression>) {
+		if let Value::Expression(e) = v {
+			if let Expression::Binary {
+				l,
+				o: Operator::And,
+				r,
+			} = e.as_ref()
+			{
+				Self::collect_conjuncts(l, conjuncts);
+				Self::collect_conjuncts(r, conjuncts);
+			} else {
+				conjuncts.push(e);
+			}
+		}
+	}
+
 	pub(super) fn add_iterator(&mut self, it_entry: IteratorEntry) -> IteratorRef {
 		let ir = self.it_entries.len();
 		self.it_entries.push(it_entry);
@@ -360,6 +460,67 @@ impl QueryExecutor {
 		}
 	}
 
+	/// Moves an iterator past the index entry of a record, so that the
+	/// iteration resumes with the entry which follows it. The index entry
+	/// is computed from the record given to the cursor, without reading it.
+	pub(crate) async fn seek_iterator(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		irf: IteratorRef,
+		iterator: &mut ThingIterator,
+		cursor: &Cursor,
+	) -> Result<(), Error> {
+		let ir = match self.0.it_entries.get(irf as usize) {
+			Some(IteratorEntry::Single(_, io)) => io.ix_ref(),
+			Some(IteratorEntry::Range(_, ir, _, _)) => *ir,
+			None => return Ok(()),
+		};
+		let Some(ix) = self.get_index_def(ir) else {
+			return Ok(());
+		};
+		let rid = &cursor.rid;
+		// Entries of unique indexes are not keyed by record id
+		let id = match ix.index {
+			Index::Idx => Some(&rid.id),
+			Index::Uniq => None,
+			_ => {
+				return Err(Error::InvalidAfterIndex {
+					index: ix.name.to_raw(),
+				})
+			}
+		};
+		let invalid = || Error::InvalidAfterRecord {
+			rid: rid.to_string(),
+			index: ix.name.to_raw(),
+		};
+		// Compute the values of the record which are stored in the index
+		let Some(val) = &cursor.doc else {
+			return Err(invalid());
+		};
+		let doc = CursorDoc::new(Some(Arc::new(rid.clone())), None, val.clone());
+		let Some(fd) = Document::build_opt_values(stk, ctx, opt, ix, &doc).await? else {
+			return Err(invalid());
+		};
+		// Check that the record has this entry in the index
+		let fd = Array(fd);
+		let key = IndexKey::new(opt.ns()?, opt.db()?, &ix.what, &ix.name, &fd, id);
+		let key = key.encode()?;
+		match ctx.tx().get(key.clone(), None).await? {
+			// Entries of unique indexes hold the id of their record
+			Some(ref v) if id.is_some() || Thing::from(v.clone()) == *rid => {}
+			_ => return Err(invalid()),
+		}
+		// Skip the entries up to and including the entry of the record
+		if !iterator.seek(&key) {
+			return Err(Error::InvalidAfterIndex {
+				index: ix.name.to_raw(),
+			});
+		}
+		Ok(())
+	}
+
 	async fn new_single_iterator(
 		&self,
 		opt: &Options,
@@ -375,6 +536,7 @@ impl QueryExecutor {
 				} => self.new_search_index_iterator(irf, io.clone()).await,
 				Index::MTree(_) => Ok(self.new_mtree_index_knn_iterator(irf)),
 				Index::Hnsw(_) => Ok(self.new_hnsw_index_ann_iterator(irf)),
+				Index::Sparse(_) => Ok(self.new_sparse_index_knn_iterator(irf)),
 			}
 		} else {
 			Ok(None)
@@ -426,6 +588,28 @@ impl QueryExecutor {
 		})
 	}
 
+	/// Returns an iterator over the records matching an equality on a regular index
+	fn new_equality_iterator(
+		opt: &Options,
+		ix: &DefineIndexStatement,
+		io: &IndexOption,
+	) -> Result<Option<ThingIterator>, Error> {
+		let value = match io.op() {
+			IndexOperator::Equality(value) | IndexOperator::Exactness(value) => value,
+			_ => return Ok(None),
+		};
+		let values = if let Value::Number(n) = value.as_ref() {
+			Self::get_number_variants(n)
+		} else {
+			vec![value.as_ref().clone()]
+		};
+		Ok(match ix.index {
+			Index::Idx => Some(Self::new_multiple_index_equal_iterators(0, opt, ix, values)?),
+			Index::Uniq => Some(Self::new_multiple_unique_equal_iterators(0, opt, ix, values)?),
+			_ => None,
+		})
+	}
+
 	fn new_index_equal_iterator(
 		irf: IteratorRef,
 		opt: &Options,
@@ -646,6 +830,16 @@ impl QueryExecutor {
 		None
 	}
 
+	fn new_sparse_index_knn_iterator(&self, irf: IteratorRef) -> Option<ThingIterator> {
+		if let Some(IteratorEntry::Single(Some(exp), ..)) = self.0.it_entries.get(irf as usize) {
+			if let Some(se) = self.0.sparse_entries.get(exp) {
+				let it = KnnIterator::new(irf, se.res.clone());
+				return Some(ThingIterator::Knn(it));
+			}
+		}
+		None
+	}
+
 	async fn build_iterators(
 		&self,
 		opt: &Options,
@@ -919,17 +1113,49 @@ impl HnswEntry {
 		n: u32,
 		ef: u32,
 		cond: Option<Arc<Cond>>,
+		filter: Option<KnnFilter>,
+		col: &Idiom,
 	) -> Result<Self, Error> {
+		let h = h.read().await;
+		let tx = ctx.tx();
 		let cond_checker = if let Some(cond) = cond {
-			HnswConditionChecker::new_cond(ctx, opt, cond)
+			let chk = HnswConditionChecker::new_cond(ctx, opt, cond);
+			if let Some(filter) = filter {
+				chk.with_filter(h.get_doc_ids(&tx, &filter).await?, col.clone())
+			} else {
+				chk
+			}
 		} else {
 			HnswConditionChecker::new()
 		};
-		let res = h
-			.read()
-			.await
-			.knn_search(&ctx.tx(), stk, v, n as usize, ef as usize, cond_checker)
-			.await?;
+		let res = h.knn_search(&tx, stk, v, n as usize, ef as usize, cond_checker).await?;
+		Ok(Self {
+			res,
+		})
+	}
+}
+
+#[derive(Clone)]
+pub(super) struct SparseEntry {
+	res: VecDeque<KnnIteratorResult>,
+}
+
+impl SparseEntry {
+	async fn new(
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		sp: &SparseIndex,
+		v: &Value,
+		k: u32,
+		cond: Option<Arc<Cond>>,
+	) -> Result<Self, Error> {
+		let cond_checker = if let Some(cond) = cond {
+			MTreeConditionChecker::new_cond(ctx, opt, cond)
+		} else {
+			MTreeConditionChecker::new(ctx)
+		};
+		let res = sp.knn_search(stk, ctx, v, k as usize, cond_checker).await?;
 		Ok(Self {
 			res,
 		})
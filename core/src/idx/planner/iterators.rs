@@ -137,6 +137,38 @@ impl ThingIterator {
 			Self::Multiples(i) => Box::pin(i.next_batch(ctx, txn, size)).await,
 		}
 	}
+
+	/// Skips the index entries up to and including the given key, returning
+	/// false if the iterator can not be resumed from an index entry
+	pub(crate) fn seek(&mut self, key: &[u8]) -> bool {
+		match self {
+			Self::IndexEqual(i) => i.seek(key),
+			Self::UniqueEqual(i) => i.seek(key),
+			Self::IndexRange(i) => i.r.seek(key),
+			Self::UniqueRange(i) => i.seek(key),
+			Self::Multiples(i) => return i.seek(key),
+			_ => return false,
+		}
+		true
+	}
+
+	/// Whether the index entry with the given key is one of the entries of this iterator
+	fn contains(&self, key: &[u8]) -> bool {
+		match self {
+			Self::IndexEqual(i) => i.contains(key),
+			Self::UniqueEqual(i) => i.contains(key),
+			_ => false,
+		}
+	}
+}
+
+/// Moves the beginning of a scan past the given key, without moving it past the end
+fn seek_beg(beg: &mut Vec<u8>, end: &[u8], key: &[u8]) {
+	let mut key = key.to_vec();
+	key.push(0x00);
+	if key > *beg {
+		*beg = key.min(end.to_vec());
+	}
 }
 
 pub(crate) type CollectorRecord = (Arc<Thing>, IteratorRecord, Option<Arc<Value>>);
@@ -193,6 +225,14 @@ impl IndexEqualThingIterator {
 	) -> Result<B, Error> {
 		Self::next_scan(tx, self.irf, &mut self.beg, &self.end, limit).await
 	}
+
+	fn seek(&mut self, key: &[u8]) {
+		seek_beg(&mut self.beg, &self.end, key);
+	}
+
+	fn contains(&self, key: &[u8]) -> bool {
+		self.beg.as_slice() <= key && key < self.end.as_slice()
+	}
 }
 
 struct RangeScan {
@@ -222,6 +262,10 @@ impl RangeScan {
 		}
 	}
 
+	fn seek(&mut self, key: &[u8]) {
+		seek_beg(&mut self.beg, &self.end, key);
+	}
+
 	fn matches(&mut self, k: &Key) -> bool {
 		if let Some(b) = &self.beg_excl {
 			if b.eq(k) {
@@ -543,6 +587,16 @@ impl UniqueEqualThingIterator {
 		}
 	}
 
+	fn seek(&mut self, key: &[u8]) {
+		if self.key.as_deref().is_some_and(|k| k <= key) {
+			self.key = None;
+		}
+	}
+
+	fn contains(&self, key: &[u8]) -> bool {
+		self.key.as_deref() == Some(key)
+	}
+
 	async fn next_batch<B: IteratorBatch>(&mut self, tx: &Transaction) -> Result<B, Error> {
 		if let Some(key) = self.key.take() {
 			if let Some(val) = tx.get(key, None).await? {
@@ -624,6 +678,14 @@ impl UniqueRangeThingIterator {
 			.unwrap()
 	}
 
+	fn seek(&mut self, key: &[u8]) {
+		self.r.seek(key);
+		// The last entry of the range is read separately
+		if key >= self.r.end.as_slice() {
+			self.done = true;
+		}
+	}
+
 	async fn next_batch<B: IteratorBatch>(
 		&mut self,
 		tx: &Transaction,
@@ -844,6 +906,19 @@ impl MultipleIterators {
 		}
 	}
 
+	/// Seeks the iterator which contains the given key, skipping the
+	/// iterators before it, as their entries have already been returned
+	fn seek(&mut self, key: &[u8]) -> bool {
+		while let Some(mut i) = self.iterators.pop_front() {
+			if i.contains(key) {
+				let res = i.seek(key);
+				self.current = Some(i);
+				return res;
+			}
+		}
+		false
+	}
+
 	async fn next_batch<B: IteratorBatch>(
 		&mut self,
 		ctx: &Context,
@@ -56,6 +56,16 @@ impl PlanBuilder {
 
 		// If every boolean operator are AND then we can use the single index plan
 		if b.all_and {
+			// A KNN expression has to drive the iteration, the other conditions are filtering its results
+			if let Some(pos) = b.non_range_indexes.iter().position(|(_, io)| {
+				matches!(
+					io.op(),
+					IndexOperator::Knn(..) | IndexOperator::Ann(..) | IndexOperator::SparseKnn(..)
+				)
+			}) {
+				let (e, i) = b.non_range_indexes.swap_remove(pos);
+				return Ok(Plan::SingleIndex(Some(e), i));
+			}
 			// TODO: This is currently pretty arbitrary
 			// We take the "first" range query if one is available
 			if let Some((_, group)) = b.groups.into_iter().next() {
@@ -189,6 +199,7 @@ pub(super) enum IndexOperator {
 	Matches(String, Option<MatchRef>),
 	Knn(Arc<Vec<Number>>, u32),
 	Ann(Arc<Vec<Number>>, u32, u32),
+	SparseKnn(Arc<Value>, u32),
 	Order(bool),
 }
 
@@ -283,6 +294,11 @@ impl IndexOption {
 				e.insert("operator", op);
 				e.insert("value", val);
 			}
+			IndexOperator::SparseKnn(v, k) => {
+				let op = Value::from(Operator::Knn(*k, None).to_string());
+				e.insert("operator", op);
+				e.insert("value", v.as_ref().clone());
+			}
 			IndexOperator::Order(asc) => {
 				e.insert("operator", Value::from("Order"));
 				e.insert("ascending", Value::from(*asc));
@@ -6,6 +6,8 @@ use crate::idx::planner::executor::{
 };
 use crate::idx::planner::plan::{IndexOperator, IndexOption};
 use crate::idx::planner::rewriter::KnnConditionRewriter;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::kvs::BuildingState;
 use crate::kvs::Transaction;
 use crate::sql::index::Index;
 use crate::sql::statements::{DefineFieldStatement, DefineIndexStatement};
@@ -38,7 +40,7 @@ impl Tree {
 		with: Option<&With>,
 		order: Option<&Orders>,
 	) -> Result<Self, Error> {
-		let mut b = TreeBuilder::new(ctx, opt, table, with, order);
+		let mut b = TreeBuilder::new(ctx, opt, table, cond, with, order);
 		if let Some(cond) = cond {
 			b.eval_cond(stk, cond).await?;
 		}
@@ -58,6 +60,7 @@ struct TreeBuilder<'a> {
 	ctx: &'a Context,
 	opt: &'a Options,
 	table: &'a Table,
+	cond: Option<&'a Cond>,
 	with: Option<&'a With>,
 	first_order: Option<&'a Order>,
 	schemas: HashMap<Table, SchemaCache>,
@@ -88,6 +91,7 @@ impl<'a> TreeBuilder<'a> {
 		ctx: &'a Context,
 		opt: &'a Options,
 		table: &'a Table,
+		cond: Option<&'a Cond>,
 		with: Option<&'a With>,
 		orders: Option<&'a Orders>,
 	) -> Self {
@@ -104,6 +108,7 @@ impl<'a> TreeBuilder<'a> {
 			ctx,
 			opt,
 			table,
+			cond,
 			with,
 			first_order,
 			schemas: Default::default(),
@@ -129,7 +134,13 @@ impl<'a> TreeBuilder<'a> {
 		if self.schemas.contains_key(table) {
 			return Ok(());
 		}
-		let l = SchemaCache::new(self.opt, table, tx).await?;
+		// Partial indexes are only considered for the queried table
+		let cond = if table == self.table {
+			self.cond
+		} else {
+			None
+		};
+		let l = SchemaCache::new(self.opt, table, tx, cond).await?;
 		self.schemas.insert(table.clone(), l);
 		Ok(())
 	}
@@ -138,7 +149,14 @@ impl<'a> TreeBuilder<'a> {
 		if let Some(o) = self.first_order {
 			if !o.random {
 				if let Node::IndexedField(id, irf) = self.resolve_idiom(&o.order).await? {
-					if let Some(ix_ref) = irf.first().cloned() {
+					// Encrypted values are not stored in order
+					let ix_ref = irf.first().cloned().filter(|ir| {
+						self.index_map
+							.definitions
+							.get(*ir as usize)
+							.is_some_and(|ix| self.encrypted_fields(ix).is_empty())
+					});
+					if let Some(ix_ref) = ix_ref {
 						self.index_map.order_limit = Some(IndexOption::new(
 							ix_ref,
 							id,
@@ -438,13 +456,14 @@ impl<'a> TreeBuilder<'a> {
 		for ir in irs {
 			if let Some(ix) = self.index_map.definitions.get(*ir as usize) {
 				let op = match &ix.index {
-					Index::Idx => self.eval_index_operator(op, n, p),
-					Index::Uniq => self.eval_index_operator(op, n, p),
+					Index::Idx => self.eval_field_index_operator(ix, op, n, p)?,
+					Index::Uniq => self.eval_field_index_operator(ix, op, n, p)?,
 					Index::Search {
 						..
 					} => Self::eval_matches_operator(op, n),
 					Index::MTree(_) => self.eval_mtree_knn(e, op, n)?,
 					Index::Hnsw(_) => self.eval_hnsw_knn(e, op, n)?,
+					Index::Sparse(_) => self.eval_sparse_knn(e, op, n),
 				};
 				if let Some(op) = op {
 					let io = IndexOption::new(*ir, id.clone(), p, op);
@@ -509,6 +528,23 @@ impl<'a> TreeBuilder<'a> {
 		Ok(None)
 	}
 
+	fn eval_sparse_knn(
+		&mut self,
+		exp: &Arc<Expression>,
+		op: &Operator,
+		n: &Node,
+	) -> Option<IndexOperator> {
+		if let Operator::Knn(k, None) = op {
+			if let Node::Computed(v) = n {
+				if let Value::Object(_) = v.as_ref() {
+					self.knn_expressions.insert(exp.clone());
+					return Some(IndexOperator::SparseKnn(v.clone(), *k));
+				}
+			}
+		}
+		None
+	}
+
 	fn eval_bruteforce_knn(
 		&mut self,
 		id: &Idiom,
@@ -528,6 +564,61 @@ impl<'a> TreeBuilder<'a> {
 		Ok(())
 	}
 
+	/// Returns the definitions of the encrypted fields among the columns of an index
+	fn encrypted_fields(&self, ix: &DefineIndexStatement) -> Vec<&DefineFieldStatement> {
+		let Some(schema) = self.schemas.get(&Table(ix.what.0.clone())) else {
+			return vec![];
+		};
+		ix.cols
+			.iter()
+			.filter_map(|col| {
+				schema.fields.iter().find(|fd| fd.encrypted.is_some() && fd.name.eq(col))
+			})
+			.collect()
+	}
+
+	/// Evaluates the operator of a standard or unique index.
+	/// The values of encrypted fields can only be looked up by equality, in a single
+	/// column index of a field which is encrypted deterministically, in which case the
+	/// looked up values are encrypted, as they are stored in the index.
+	fn eval_field_index_operator(
+		&self,
+		ix: &DefineIndexStatement,
+		op: &Operator,
+		n: &Node,
+		p: IdiomPosition,
+	) -> Result<Option<IndexOperator>, Error> {
+		let fds = self.encrypted_fields(ix);
+		let fd = match fds.as_slice() {
+			[] => return Ok(self.eval_index_operator(op, n, p)),
+			[fd] if ix.cols.len() == 1 => fd,
+			_ => return Ok(None),
+		};
+		if !fd.encrypted.as_ref().is_some_and(|v| v.deterministic) {
+			return Ok(None);
+		}
+		let io = match self.eval_index_operator(op, n, p) {
+			Some(IndexOperator::Equality(v)) => {
+				Some(IndexOperator::Equality(Arc::new(fd.encrypt_value(None, &v)?)))
+			}
+			Some(IndexOperator::Exactness(v)) => {
+				Some(IndexOperator::Exactness(Arc::new(fd.encrypt_value(None, &v)?)))
+			}
+			Some(IndexOperator::Union(v)) => match v.as_ref() {
+				Value::Array(a) => {
+					let a = a
+						.iter()
+						.map(|v| fd.encrypt_value(None, v))
+						.collect::<Result<Vec<_>, _>>()?;
+					Some(IndexOperator::Union(Arc::new(Value::Array(a.into()))))
+				}
+				_ => None,
+			},
+			_ => None,
+		};
+		Ok(io)
+	}
+
 	fn eval_index_operator(
 		&self,
 		op: &Operator,
@@ -592,14 +683,83 @@ struct SchemaCache {
 }
 
 impl SchemaCache {
-	async fn new(opt: &Options, table: &Table, tx: &Transaction) -> Result<Self, Error> {
-		let indexes = tx.all_tb_indexes(opt.ns()?, opt.db()?, table).await?;
+	async fn new(
+		opt: &Options,
+		table: &Table,
+		tx: &Transaction,
+		cond: Option<&Cond>,
+	) -> Result<Self, Error> {
+		let mut indexes = tx.all_tb_indexes(opt.ns()?, opt.db()?, table).await?;
+		// An index whose concurrent building has been cancelled or has failed is incomplete
+		#[cfg(not(target_arch = "wasm32"))]
+		if indexes.iter().any(|ix| ix.concurrently) {
+			let mut usable = Vec::with_capacity(indexes.len());
+			for ix in indexes.iter() {
+				if ix.concurrently {
+					let st = BuildingState::get(tx, opt.ns()?, opt.db()?, ix).await?;
+					if st.is_some_and(|st| !st.is_usable()) {
+						continue;
+					}
+				}
+				usable.push(ix.clone());
+			}
+			indexes = usable.into();
+		}
+		// A partial index only contains the records matching its condition,
+		// it can only be used if the condition of the query implies the condition of the index.
+		if indexes.iter().any(|ix| ix.cond.is_some()) {
+			indexes = indexes
+				.iter()
+				.filter(|ix| match &ix.cond {
+					Some(ic) => cond.is_some_and(|qc| Self::implies(qc, ic)),
+					None => true,
+				})
+				.cloned()
+				.collect();
+		}
 		let fields = tx.all_tb_fields(opt.ns()?, opt.db()?, table).await?;
 		Ok(Self {
 			indexes,
 			fields,
 		})
 	}
+
+	/// Checks that every conjunction of the index condition is also a conjunction of the query condition.
+	/// The check is purely structural: the conjunctions are compared as written, so a query condition
+	/// which is logically equivalent but written differently (e.g. `5 < age` for `age > 5`,
+	/// or a narrower range) does not use the partial index.
+	fn implies(qc: &Cond, ic: &Cond) -> bool {
+		let mut qcs = vec![];
+		Self::conjunctions(&qc.0, &mut qcs);
+		let mut ics = vec![];
+		Self::conjunctions(&ic.0, &mut ics);
+		ics.iter().all(|c| qcs.contains(c))
+	}
+
+	fn conjunctions<'a>(v: &'a Value, res: &mut Vec<&'a Value>) {
+		match v {
+			Value::Expression(e) => {
+				if let Expression::Binary {
+					l,
+					o: Operator::And,
+					r,
+				} = e.as_ref()
+				{
+					Self::conjunctions(l, res);
+					Self::conjunctions(r, res);
+					return;
+				}
+			}
+			Value::Subquery(s) => {
+				if let Subquery::Value(v) = s.as_ref() {
+					Self::conjunctions(v, res);
+					return;
+				}
+			}
+			_ => {}
+		}
+		res.push(v);
+	}
 }
 
 pub(super) type GroupRef = u16;
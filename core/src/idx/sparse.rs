@@ -0,0 +1,289 @@
+use crate::ctx::Context;
+use crate::err::Error;
+use crate::idx::docids::{DocId, DocIds};
+use crate::idx::ft::postings::{Postings, TermFrequency};
+use crate::idx::ft::termdocs::TermDocs;
+use crate::idx::ft::terms::TermId;
+use crate::idx::planner::checker::MTreeConditionChecker;
+use crate::idx::planner::iterators::KnnIteratorResult;
+use crate::idx::trees::btree::BStatistics;
+use crate::idx::trees::store::IndexStores;
+use crate::idx::IndexKeyBase;
+use crate::kvs::{Transaction, TransactionType};
+use crate::sql::index::SparseParams;
+use crate::sql::{Object, Thing, Value};
+use ahash::{HashMap, HashMapExt};
+use reblessive::tree::Stk;
+use std::collections::VecDeque;
+
+/// A sparse vector, as a list of non-zero dimensions and their weights
+type SparseVector = Vec<(TermId, f64)>;
+
+/// Index of sparse vectors (`{ dimension: weight }` objects).
+/// Every dimension is stored as a posting list, the weight being stored as the term frequency.
+/// The score of a document is the dot product with the query vector.
+pub(crate) struct SparseIndex {
+	doc_ids: DocIds,
+	postings: Postings,
+	term_docs: TermDocs,
+}
+
+impl SparseIndex {
+	pub(crate) async fn new(
+		ixs: &IndexStores,
+		tx: &Transaction,
+		ikb: IndexKeyBase,
+		p: &SparseParams,
+		tt: TransactionType,
+	) -> Result<Self, Error> {
+		let doc_ids =
+			DocIds::new(ixs, tx, tt, ikb.clone(), p.doc_ids_order, p.doc_ids_cache).await?;
+		let postings =
+			Postings::new(ixs, tx, ikb.clone(), p.postings_order, tt, p.postings_cache).await?;
+		Ok(Self {
+			doc_ids,
+			postings,
+			term_docs: TermDocs::new(ikb),
+		})
+	}
+
+	pub(crate) async fn index_document(
+		&mut self,
+		tx: &Transaction,
+		rid: &Thing,
+		content: &[Value],
+	) -> Result<(), Error> {
+		// Resolve the doc_id
+		let doc_id = *self.doc_ids.resolve_doc_id(tx, rid.into()).await?.doc_id();
+		// Index the values
+		for v in content {
+			for (dim, weight) in Self::extract_vector(v)? {
+				self.postings.update_posting(tx, dim, doc_id, weight.to_bits()).await?;
+				self.term_docs.set_doc(tx, dim, doc_id).await?;
+			}
+		}
+		Ok(())
+	}
+
+	pub(crate) async fn remove_document(
+		&mut self,
+		tx: &Transaction,
+		rid: &Thing,
+		content: &[Value],
+	) -> Result<(), Error> {
+		if let Some(doc_id) = self.doc_ids.remove_doc(tx, rid.into()).await? {
+			for v in content {
+				for (dim, _) in Self::extract_vector(v)? {
+					self.postings.remove_posting(tx, dim, doc_id).await?;
+					self.term_docs.remove_doc(tx, dim, doc_id).await?;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Extracts the non-zero dimensions of a sparse vector
+	fn extract_vector(v: &Value) -> Result<SparseVector, Error> {
+		let Value::Object(o) = v else {
+			return Err(Error::InvalidVectorValue(v.clone().to_raw_string()));
+		};
+		let mut vec = Vec::with_capacity(o.len());
+		for (k, w) in o.iter() {
+			let dim: TermId = k.parse().map_err(|_| {
+				Error::InvalidVectorValue(format!("The dimension '{k}' is not a positive integer"))
+			})?;
+			let weight = match w {
+				Value::Number(n) if n.to_float().is_finite() => n.to_float(),
+				_ => return Err(Error::InvalidVectorValue(w.clone().to_raw_string())),
+			};
+			if weight != 0.0 {
+				vec.push((dim, weight));
+			}
+		}
+		Ok(vec)
+	}
+
+	pub(crate) async fn knn_search(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		query: &Value,
+		k: usize,
+		mut chk: MTreeConditionChecker<'_>,
+	) -> Result<VecDeque<KnnIteratorResult>, Error> {
+		let query = Self::extract_vector(query)?;
+		let tx = ctx.tx();
+		// Accumulate the dot product of every document sharing a dimension with the query
+		let mut scores: HashMap<DocId, f64> = HashMap::new();
+		for (dim, q_weight) in query {
+			if let Some(docs) = self.term_docs.get_docs(&tx, dim).await? {
+				for doc_id in docs {
+					if let Some(tf) = self.postings.get_term_frequency(&tx, dim, doc_id).await? {
+						*scores.entry(doc_id).or_default() += q_weight * Self::weight(tf);
+					}
+				}
+			}
+		}
+		// The best score comes first. The distance is the negated dot product.
+		let mut scores: Vec<(DocId, f64)> = scores.into_iter().map(|(d, s)| (d, -s)).collect();
+		scores.sort_by(|(d1, s1), (d2, s2)| s1.total_cmp(s2).then(d1.cmp(d2)));
+		let mut res = VecDeque::with_capacity(k);
+		for (doc_id, dist) in scores {
+			if res.len() >= k {
+				break;
+			}
+			if chk.check_truthy(stk, &self.doc_ids, doc_id).await? {
+				res.push_back((doc_id, dist));
+			}
+		}
+		// Resolve the doc_id to Thing and the optional value
+		chk.convert_result(&self.doc_ids, res).await
+	}
+
+	fn weight(tf: TermFrequency) -> f64 {
+		f64::from_bits(tf)
+	}
+
+	pub(crate) async fn statistics(&self, tx: &Transaction) -> Result<SparseStatistics, Error> {
+		Ok(SparseStatistics {
+			doc_ids: self.doc_ids.statistics(tx).await?,
+			postings: self.postings.statistics(tx).await?,
+		})
+	}
+
+	pub(crate) async fn finish(&mut self, tx: &Transaction) -> Result<(), Error> {
+		self.doc_ids.finish(tx).await?;
+		self.postings.finish(tx).await?;
+		Ok(())
+	}
+}
+
+pub(crate) struct SparseStatistics {
+	doc_ids: BStatistics,
+	postings: BStatistics,
+}
+
+impl From<SparseStatistics> for Value {
+	fn from(stats: SparseStatistics) -> Self {
+		let mut res = Object::default();
+		res.insert("doc_ids".to_owned(), Value::from(stats.doc_ids));
+		res.insert("postings".to_owned(), Value::from(stats.postings));
+		Value::from(res)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::ctx::{Context, MutableContext};
+	use crate::err::Error;
+	use crate::idx::planner::checker::MTreeConditionChecker;
+	use crate::idx::sparse::SparseIndex;
+	use crate::idx::IndexKeyBase;
+	use crate::kvs::{Datastore, LockType::*, TransactionType};
+	use crate::sql::index::SparseParams;
+	use crate::sql::{Thing, Value};
+	use crate::syn::Parse;
+	use reblessive::tree::Stk;
+	use std::sync::Arc;
+	use test_log::test;
+
+	const PARAMS: SparseParams = SparseParams {
+		doc_ids_order: 5,
+		doc_ids_cache: 100,
+		postings_order: 5,
+		postings_cache: 100,
+	};
+
+	async fn new_operation(ds: &Datastore, tt: TransactionType) -> (Context, SparseIndex) {
+		let tx = ds.transaction(tt, Optimistic).await.unwrap();
+		let ix = SparseIndex::new(ds.index_store(), &tx, IndexKeyBase::default(), &PARAMS, tt)
+			.await
+			.unwrap();
+		let mut ctx = MutableContext::default();
+		ctx.set_transaction(Arc::new(tx));
+		(ctx.freeze(), ix)
+	}
+
+	async fn finish(ctx: &Context, mut ix: SparseIndex) {
+		let tx = ctx.tx();
+		ix.finish(&tx).await.unwrap();
+		tx.commit().await.unwrap();
+	}
+
+	async fn search(
+		stk: &mut Stk,
+		ctx: &Context,
+		ix: &SparseIndex,
+		q: &str,
+		k: usize,
+	) -> Vec<(String, f64)> {
+		let res = ix
+			.knn_search(stk, ctx, &Value::parse(q), k, MTreeConditionChecker::new(ctx))
+			.await
+			.unwrap();
+		res.into_iter().map(|(t, d, _)| (t.to_string(), d)).collect()
+	}
+
+	#[test(tokio::test)]
+	async fn test_sparse_index() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let docs = [
+			("doc1", "{ 1: 1.0, 5: 2.0 }"),
+			("doc2", "{ 1: 0.5, 7: 3.0 }"),
+			("doc3", "{ 5: 0.5, 9: 1.0 }"),
+		];
+		{
+			let (ctx, mut ix) = new_operation(&ds, TransactionType::Write).await;
+			let tx = ctx.tx();
+			for (id, v) in docs {
+				let rid: Thing = ("t", id).into();
+				ix.index_document(&tx, &rid, &[Value::parse(v)]).await.unwrap();
+			}
+			drop(tx);
+			finish(&ctx, ix).await;
+		}
+		let mut stack = reblessive::TreeStack::new();
+		stack
+			.enter(|stk| async {
+				let (ctx, ix) = new_operation(&ds, TransactionType::Read).await;
+				// doc1: 2.0 + 4.0 = 6.0, doc3: 1.0, doc2: 1.0
+				let res = search(stk, &ctx, &ix, "{ 1: 2.0, 5: 2.0 }", 2).await;
+				assert_eq!(res[0], ("t:doc1".to_string(), -6.0));
+				assert_eq!(res.len(), 2);
+				// Documents without any common dimension are not returned
+				let res = search(stk, &ctx, &ix, "{ 7: 1.0 }", 3).await;
+				assert_eq!(res, vec![("t:doc2".to_string(), -3.0)]);
+				let res = search(stk, &ctx, &ix, "{ 42: 1.0 }", 3).await;
+				assert!(res.is_empty());
+			})
+			.finish()
+			.await;
+		{
+			// Remove a document
+			let (ctx, mut ix) = new_operation(&ds, TransactionType::Write).await;
+			let tx = ctx.tx();
+			let rid: Thing = ("t", "doc2").into();
+			ix.remove_document(&tx, &rid, &[Value::parse(docs[1].1)]).await.unwrap();
+			drop(tx);
+			finish(&ctx, ix).await;
+		}
+		stack
+			.enter(|stk| async {
+				let (ctx, ix) = new_operation(&ds, TransactionType::Read).await;
+				let res = search(stk, &ctx, &ix, "{ 1: 1.0, 7: 1.0 }", 3).await;
+				assert_eq!(res, vec![("t:doc1".to_string(), -1.0)]);
+			})
+			.finish()
+			.await;
+	}
+
+	#[test]
+	fn test_invalid_sparse_vector() {
+		for v in ["[1, 2, 3]", "{ a: 1.0 }", "{ 1: 'x' }"] {
+			let res = SparseIndex::extract_vector(&Value::parse(v));
+			assert!(matches!(res, Err(Error::InvalidVectorValue(_))), "{v}");
+		}
+		let res = SparseIndex::extract_vector(&Value::parse("{ 3: 0, 4: -1.5 }")).unwrap();
+		assert_eq!(res, vec![(4, -1.5)]);
+	}
+}
@@ -28,6 +28,10 @@ where
 		}
 	}
 
+	pub(in crate::idx::trees) fn len(&self) -> usize {
+		self.nodes.len()
+	}
+
 	#[inline]
 	pub(super) fn new_edges(&self) -> S {
 		S::with_capacity(self.capacity)
@@ -113,10 +117,6 @@ impl<S> UndirectedGraph<S>
 where
 	S: DynamicSet,
 {
-	pub(in crate::idx::trees) fn len(&self) -> usize {
-		self.nodes.len()
-	}
-
 	pub(in crate::idx::trees) fn nodes(&self) -> &HashMap<ElementId, S> {
 		&self.nodes
 	}
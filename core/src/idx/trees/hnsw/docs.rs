@@ -66,6 +66,20 @@ impl HnswDocs {
 		}
 	}
 
+	pub(super) async fn get_doc_id(
+		&self,
+		tx: &Transaction,
+		id: Id,
+	) -> Result<Option<DocId>, Error> {
+		let id_key = self.ikb.new_hi_key(id);
+		if let Some(v) = tx.get(id_key, None).await? {
+			let doc_id = u64::from_be_bytes(v.try_into().unwrap());
+			Ok(Some(doc_id))
+		} else {
+			Ok(None)
+		}
+	}
+
 	fn next_doc_id(&mut self) -> DocId {
 		self.state_updated = true;
 		if let Some(doc_id) = self.state.available.iter().next() {
@@ -78,6 +92,10 @@ impl HnswDocs {
 		}
 	}
 
+	pub(super) fn table(&self) -> &str {
+		&self.tb
+	}
+
 	pub(in crate::idx) async fn get_thing(
 		&self,
 		tx: &Transaction,
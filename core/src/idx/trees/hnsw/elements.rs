@@ -1,25 +1,36 @@
 use crate::err::Error;
+use crate::idx::trees::hnsw::quantizer::{QuantizedVector, Quantizer, PQ_TRAINING_SIZE};
 use crate::idx::trees::hnsw::ElementId;
 use crate::idx::trees::vector::{SerializedVector, SharedVector, Vector};
 use crate::idx::{IndexKeyBase, VersionedStore};
 use crate::kvs::Transaction;
-use crate::sql::index::Distance;
+use crate::sql::index::{Distance, HnswParams};
 use dashmap::DashMap;
 
+/// The in-memory form of an element
+enum CachedVector {
+	Full(SharedVector),
+	Quantized(QuantizedVector),
+}
+
 pub(super) struct HnswElements {
 	ikb: IndexKeyBase,
-	elements: DashMap<ElementId, SharedVector>,
+	elements: DashMap<ElementId, CachedVector>,
 	next_element_id: ElementId,
 	dist: Distance,
+	quantizer: Option<Quantizer>,
 }
 
 impl HnswElements {
-	pub(super) fn new(ikb: IndexKeyBase, dist: Distance) -> Self {
+	pub(super) fn new(ikb: IndexKeyBase, p: &HnswParams) -> Self {
+		let quantizer =
+			p.quantization.as_ref().map(|q| Quantizer::new(&ikb, p.dimension as usize, q));
 		Self {
 			ikb,
 			elements: Default::default(),
 			next_element_id: 0,
-			dist,
+			dist: p.distance.clone(),
+			quantizer,
 		}
 	}
 
@@ -46,6 +57,83 @@ impl HnswElements {
 		self.elements.contains_key(e_id)
 	}
 
+	/// Returns the quantizer, if the vectors are currently compressed
+	fn active_quantizer(&self) -> Option<&Quantizer> {
+		self.quantizer.as_ref().filter(|q| q.is_ready())
+	}
+
+	pub(super) fn is_quantized(&self) -> bool {
+		self.active_quantizer().is_some()
+	}
+
+	/// Converts a vector into its in-memory form, and returns the vector used by the graph
+	fn compress(&self, vec: Vector) -> (CachedVector, SharedVector) {
+		if let Some(q) = self.active_quantizer() {
+			if let Some(c) = q.encode(&vec) {
+				let pt: SharedVector = q.decode(&c).into();
+				return (CachedVector::Quantized(c), pt);
+			}
+		}
+		let pt: SharedVector = vec.into();
+		(CachedVector::Full(pt.clone()), pt)
+	}
+
+	fn restore(&self, c: &CachedVector) -> SharedVector {
+		match (c, &self.quantizer) {
+			(CachedVector::Full(pt), _) => pt.clone(),
+			(CachedVector::Quantized(c), Some(q)) => q.decode(c).into(),
+			// Compressed elements are only cached when there is a quantizer
+			(CachedVector::Quantized(_), None) => unreachable!(),
+		}
+	}
+
+	/// The query has to be comparable with the vectors held by the graph
+	pub(super) fn prepare_query(&self, pt: &SharedVector) -> SharedVector {
+		if let Some(q) = self.active_quantizer() {
+			q.prepare_query(pt).into()
+		} else {
+			pt.clone()
+		}
+	}
+
+	/// Loads a codebook possibly trained by another node
+	pub(super) async fn check_quantizer(&mut self, tx: &Transaction) -> Result<(), Error> {
+		if let Some(q) = &mut self.quantizer {
+			if q.load(tx).await? {
+				// The cached elements have to be compressed with the new codebook
+				self.elements.clear();
+			}
+		}
+		Ok(())
+	}
+
+	/// Trains the product quantizer, if the index holds enough elements and no codebook exists
+	pub(super) async fn train_quantizer(&mut self, tx: &Transaction) -> Result<(), Error> {
+		let Some(q) = &self.quantizer else {
+			return Ok(());
+		};
+		if q.is_ready() || (self.next_element_id as usize) < PQ_TRAINING_SIZE {
+			return Ok(());
+		}
+		let mut samples = Vec::with_capacity(PQ_TRAINING_SIZE);
+		for e_id in 0..self.next_element_id {
+			if let Some(v) = self.get_full_vector(tx, &e_id).await? {
+				samples.push(v.to_f32_vec());
+				if samples.len() == PQ_TRAINING_SIZE {
+					break;
+				}
+			}
+		}
+		if samples.len() < PQ_TRAINING_SIZE {
+			return Ok(());
+		}
+		// The codebook is loaded once the transaction is committed
+		if let Some(q) = &mut self.quantizer {
+			q.train(tx, &samples).await?;
+		}
+		Ok(())
+	}
+
 	pub(super) async fn insert(
 		&mut self,
 		tx: &Transaction,
@@ -56,8 +144,8 @@ impl HnswElements {
 		let key = self.ikb.new_he_key(id);
 		let val = VersionedStore::try_into(ser_vec)?;
 		tx.set(key, val, None).await?;
-		let pt: SharedVector = vec.into();
-		self.elements.insert(id, pt.clone());
+		let (c, pt) = self.compress(vec);
+		self.elements.insert(id, c);
 		Ok(pt)
 	}
 
@@ -67,17 +155,36 @@ impl HnswElements {
 		e_id: &ElementId,
 	) -> Result<Option<SharedVector>, Error> {
 		if let Some(r) = self.elements.get(e_id) {
-			return Ok(Some(r.value().clone()));
+			return Ok(Some(self.restore(r.value())));
+		}
+		let key = self.ikb.new_he_key(*e_id);
+		match tx.get(key, None).await? {
+			None => Ok(None),
+			Some(val) => {
+				let vec: SerializedVector = VersionedStore::try_from(val)?;
+				let (c, pt) = self.compress(Vector::from(vec));
+				self.elements.insert(*e_id, c);
+				Ok(Some(pt))
+			}
+		}
+	}
+
+	/// Returns the full-precision vector of an element.
+	/// Unless the index is quantized, this is the vector held by the graph.
+	pub(super) async fn get_full_vector(
+		&self,
+		tx: &Transaction,
+		e_id: &ElementId,
+	) -> Result<Option<SharedVector>, Error> {
+		if !self.is_quantized() {
+			return self.get_vector(tx, e_id).await;
 		}
 		let key = self.ikb.new_he_key(*e_id);
 		match tx.get(key, None).await? {
 			None => Ok(None),
 			Some(val) => {
 				let vec: SerializedVector = VersionedStore::try_from(val)?;
-				let vec = Vector::from(vec);
-				let vec: SharedVector = vec.into();
-				self.elements.insert(*e_id, vec.clone());
-				Ok(Some(vec))
+				Ok(Some(Vector::from(vec).into()))
 			}
 		}
 	}
@@ -101,4 +208,53 @@ impl HnswElements {
 		tx.del(key).await?;
 		Ok(())
 	}
+
+	/// Estimates the mean relative error introduced by the quantization, on a sample of elements
+	pub(super) async fn quantization_error(
+		&self,
+		tx: &Transaction,
+		sample: usize,
+	) -> Result<Option<f64>, Error> {
+		let Some(q) = self.active_quantizer() else {
+			return Ok(None);
+		};
+		let mut total = 0.0;
+		let mut count = 0;
+		for e_id in 0..self.next_element_id {
+			if count == sample {
+				break;
+			}
+			if let Some(full) = self.get_full_vector(tx, &e_id).await? {
+				if let Some(c) = q.encode(&full) {
+					let a = full.to_f32_vec();
+					let b = q.decode(&c).to_f32_vec();
+					let err =
+						a.iter().zip(&b).map(|(x, y)| (*x as f64 - *y as f64).powi(2)).sum::<f64>();
+					let norm = a.iter().map(|x| (*x as f64).powi(2)).sum::<f64>();
+					if norm > 0.0 {
+						total += (err / norm).sqrt();
+						count += 1;
+					}
+				}
+			}
+		}
+		if count > 0 {
+			Ok(Some(total / count as f64))
+		} else {
+			Ok(None)
+		}
+	}
+
+	/// Returns the number of bytes used by one element in memory, and by its full-precision vector
+	pub(super) fn vector_sizes(&self, dim: usize, full_size: usize) -> (usize, usize) {
+		let full = dim * full_size;
+		match self.active_quantizer() {
+			Some(q) => (q.code_size(), full),
+			None => (full, full),
+		}
+	}
+
+	pub(super) fn codebook_size(&self) -> usize {
+		self.quantizer.as_ref().map(|q| q.codebook_size()).unwrap_or(0)
+	}
 }
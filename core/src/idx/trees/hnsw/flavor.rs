@@ -3,7 +3,7 @@ use crate::idx::planner::checker::HnswConditionChecker;
 use crate::idx::trees::dynamicset::{AHashSet, ArraySet};
 use crate::idx::trees::hnsw::docs::HnswDocs;
 use crate::idx::trees::hnsw::docs::VecDocs;
-use crate::idx::trees::hnsw::{ElementId, Hnsw, HnswSearch};
+use crate::idx::trees::hnsw::{ElementId, Hnsw, HnswGraphStatistics, HnswSearch};
 use crate::idx::trees::vector::{SharedVector, Vector};
 use crate::idx::IndexKeyBase;
 use crate::kvs::Transaction;
@@ -214,6 +214,47 @@ impl HnswFlavor {
 			HnswFlavor::Hset(h) => h.get_vector(tx, e_id).await,
 		}
 	}
+	pub(super) async fn train_quantizer(&mut self, tx: &Transaction) -> Result<(), Error> {
+		match self {
+			HnswFlavor::H5_9(h) => h.train_quantizer(tx).await,
+			HnswFlavor::H5_17(h) => h.train_quantizer(tx).await,
+			HnswFlavor::H5_25(h) => h.train_quantizer(tx).await,
+			HnswFlavor::H5set(h) => h.train_quantizer(tx).await,
+			HnswFlavor::H9_17(h) => h.train_quantizer(tx).await,
+			HnswFlavor::H9_25(h) => h.train_quantizer(tx).await,
+			HnswFlavor::H9set(h) => h.train_quantizer(tx).await,
+			HnswFlavor::H13_25(h) => h.train_quantizer(tx).await,
+			HnswFlavor::H13set(h) => h.train_quantizer(tx).await,
+			HnswFlavor::H17set(h) => h.train_quantizer(tx).await,
+			HnswFlavor::H21set(h) => h.train_quantizer(tx).await,
+			HnswFlavor::H25set(h) => h.train_quantizer(tx).await,
+			HnswFlavor::H29set(h) => h.train_quantizer(tx).await,
+			HnswFlavor::Hset(h) => h.train_quantizer(tx).await,
+		}
+	}
+	pub(super) async fn statistics(
+		&self,
+		tx: &Transaction,
+		dim: usize,
+		component_size: usize,
+	) -> Result<HnswGraphStatistics, Error> {
+		match self {
+			HnswFlavor::H5_9(h) => h.statistics(tx, dim, component_size).await,
+			HnswFlavor::H5_17(h) => h.statistics(tx, dim, component_size).await,
+			HnswFlavor::H5_25(h) => h.statistics(tx, dim, component_size).await,
+			HnswFlavor::H5set(h) => h.statistics(tx, dim, component_size).await,
+			HnswFlavor::H9_17(h) => h.statistics(tx, dim, component_size).await,
+			HnswFlavor::H9_25(h) => h.statistics(tx, dim, component_size).await,
+			HnswFlavor::H9set(h) => h.statistics(tx, dim, component_size).await,
+			HnswFlavor::H13_25(h) => h.statistics(tx, dim, component_size).await,
+			HnswFlavor::H13set(h) => h.statistics(tx, dim, component_size).await,
+			HnswFlavor::H17set(h) => h.statistics(tx, dim, component_size).await,
+			HnswFlavor::H21set(h) => h.statistics(tx, dim, component_size).await,
+			HnswFlavor::H25set(h) => h.statistics(tx, dim, component_size).await,
+			HnswFlavor::H29set(h) => h.statistics(tx, dim, component_size).await,
+			HnswFlavor::Hset(h) => h.statistics(tx, dim, component_size).await,
+		}
+	}
 	#[cfg(test)]
 	pub(super) fn check_hnsw_properties(&self, expected_count: usize) {
 		match self {
@@ -4,21 +4,26 @@ use crate::idx::planner::iterators::KnnIteratorResult;
 use crate::idx::trees::hnsw::docs::{HnswDocs, VecDocs};
 use crate::idx::trees::hnsw::elements::HnswElements;
 use crate::idx::trees::hnsw::flavor::HnswFlavor;
-use crate::idx::trees::hnsw::{ElementId, HnswSearch};
-use crate::idx::trees::knn::{KnnResult, KnnResultBuilder};
+use crate::idx::trees::hnsw::{ElementId, HnswGraphStatistics, HnswSearch};
+use crate::idx::trees::knn::{Ids64, KnnResult, KnnResultBuilder};
 use crate::idx::trees::vector::{SharedVector, Vector};
 use crate::idx::IndexKeyBase;
 use crate::kvs::Transaction;
-use crate::sql::index::{HnswParams, VectorType};
-use crate::sql::{Id, Number, Value};
+use crate::sql::index::{Distance, HnswParams, VectorQuantization, VectorType};
+use crate::sql::{Id, Number, Object, Thing, Value};
 #[cfg(debug_assertions)]
 use ahash::HashMap;
 use reblessive::tree::Stk;
+use roaring::RoaringTreemap;
 use std::collections::VecDeque;
+use std::sync::Arc;
 
 pub struct HnswIndex {
 	dim: usize,
 	vector_type: VectorType,
+	distance: Distance,
+	quantization: Option<VectorQuantization>,
+	rerank: bool,
 	hnsw: HnswFlavor,
 	docs: HnswDocs,
 	vec_docs: VecDocs,
@@ -80,6 +85,9 @@ impl HnswIndex {
 		Ok(Self {
 			dim: p.dimension as usize,
 			vector_type: p.vector_type,
+			distance: p.distance.clone(),
+			quantization: p.quantization,
+			rerank: p.quantization.is_some() && p.rerank,
 			hnsw: HnswFlavor::new(ikb.clone(), p),
 			docs: HnswDocs::new(tx, tb, ikb.clone()).await?,
 			vec_docs: VecDocs::new(ikb),
@@ -98,11 +106,10 @@ impl HnswIndex {
 		let doc_id = self.docs.resolve(tx, id).await?;
 		// Index the values
 		for value in content {
-			// Extract the vector
-			let vector = Vector::try_from_value(self.vector_type, self.dim, value)?;
-			vector.check_dimension(self.dim)?;
-			// Insert the vector
-			self.vec_docs.insert(tx, vector, doc_id, &mut self.hnsw).await?;
+			for vector in self.extract_vectors(value)? {
+				// Insert the vector
+				self.vec_docs.insert(tx, vector, doc_id, &mut self.hnsw).await?;
+			}
 		}
 		self.docs.finish(tx).await?;
 		Ok(())
@@ -118,17 +125,39 @@ impl HnswIndex {
 			// Ensure the layers are up-to-date
 			self.hnsw.check_state(tx).await?;
 			for v in content {
-				// Extract the vector
-				let vector = Vector::try_from_value(self.vector_type, self.dim, v)?;
-				vector.check_dimension(self.dim)?;
-				// Remove the vector
-				self.vec_docs.remove(tx, &vector, doc_id, &mut self.hnsw).await?;
+				for vector in self.extract_vectors(v)? {
+					// Remove the vector
+					self.vec_docs.remove(tx, &vector, doc_id, &mut self.hnsw).await?;
+				}
 			}
 			self.docs.finish(tx).await?;
 		}
 		Ok(())
 	}
 
+	/// Extracts the vectors of a field value.
+	/// The value is either a single vector, or an array of vectors (multi-vector field).
+	fn extract_vectors(&self, value: &Value) -> Result<Vec<Vector>, Error> {
+		let vector = Vector::try_from_value(self.vector_type, self.dim, value)?;
+		if vector.check_dimension(self.dim).is_ok() {
+			return Ok(vec![vector]);
+		}
+		if let Value::Array(a) = value {
+			if !a.is_empty() && a.iter().all(|v| matches!(v, Value::Array(_))) {
+				let mut vectors = Vec::with_capacity(a.len());
+				for v in a.iter() {
+					let vector = Vector::try_from_value(self.vector_type, self.dim, v)?;
+					vector.check_dimension(self.dim)?;
+					vectors.push(vector);
+				}
+				return Ok(vectors);
+			}
+		}
+		// Returns the dimension error
+		vector.check_dimension(self.dim)?;
+		Ok(vec![vector])
+	}
+
 	// Ensure the layers are up-to-date
 	pub async fn check_state(&mut self, tx: &Transaction) -> Result<(), Error> {
 		self.hnsw.check_state(tx).await
@@ -153,6 +182,23 @@ impl HnswIndex {
 		Ok(res)
 	}
 
+	/// Returns the doc ids of the given records, ignoring the records which are not indexed
+	pub(crate) async fn get_doc_ids(
+		&self,
+		tx: &Transaction,
+		things: &[Arc<Thing>],
+	) -> Result<RoaringTreemap, Error> {
+		let mut doc_ids = RoaringTreemap::new();
+		for t in things {
+			if t.tb.eq(self.docs.table()) {
+				if let Some(doc_id) = self.docs.get_doc_id(tx, t.id.clone()).await? {
+					doc_ids.insert(doc_id);
+				}
+			}
+		}
+		Ok(doc_ids)
+	}
+
 	pub(super) async fn search(
 		&self,
 		tx: &Transaction,
@@ -160,35 +206,126 @@ impl HnswIndex {
 		search: &HnswSearch,
 		chk: &mut HnswConditionChecker<'_>,
 	) -> Result<KnnResult, Error> {
+		// No document can match the filter
+		if chk.is_empty_filter() {
+			return Ok(KnnResultBuilder::new(search.k).build(
+				#[cfg(debug_assertions)]
+				HashMap::default(),
+			));
+		}
+		// Every candidate is collected, the result is reduced to k once the
+		// exact distances are known, and once the vectors are grouped per record
+		let candidates;
+		let graph_search = if search.ef > search.k {
+			candidates = HnswSearch::new(search.pt.clone(), search.ef, search.ef);
+			&candidates
+		} else {
+			search
+		};
 		// Do the search
 		let neighbors = match chk {
-			HnswConditionChecker::Hnsw(_) => self.hnsw.knn_search(tx, search).await?,
+			HnswConditionChecker::Hnsw(_) => self.hnsw.knn_search(tx, graph_search).await?,
 			HnswConditionChecker::HnswCondition(_) => {
-				self.hnsw
-					.knn_search_checked(tx, stk, search, &self.docs, &self.vec_docs, chk)
-					.await?
+				let neighbors = self
+					.hnsw
+					.knn_search_checked(tx, stk, graph_search, &self.docs, &self.vec_docs, chk)
+					.await?;
+				// The traversal stopped before it found enough matching elements,
+				// the distance of every document of the filter is computed instead
+				if chk.is_exhausted() {
+					if let Some(doc_ids) = chk.filter_doc_ids() {
+						return self
+							.search_filter(tx, stk, &search.pt, search.k, doc_ids, chk)
+							.await;
+					}
+				}
+				neighbors
 			}
 		};
-		self.build_result(tx, neighbors, search.k, chk).await
+		self.build_result(tx, &search.pt, neighbors, search.k, chk).await
+	}
+
+	/// Computes the distance of every document matching the filter and the condition
+	async fn search_filter(
+		&self,
+		tx: &Transaction,
+		stk: &mut Stk,
+		pt: &SharedVector,
+		n: usize,
+		doc_ids: RoaringTreemap,
+		chk: &mut HnswConditionChecker<'_>,
+	) -> Result<KnnResult, Error> {
+		let mut builder = KnnResultBuilder::new(n);
+		for doc_id in doc_ids {
+			if !chk.check_truthy(tx, stk, &self.docs, Ids64::One(doc_id)).await? {
+				chk.expires(Ids64::One(doc_id));
+				continue;
+			}
+			// The closest vector of a multi-vector record is its distance
+			let mut dist = None;
+			if let Some(value) = chk.field_value(doc_id) {
+				for vector in self.extract_vectors(&value)? {
+					let d = self.distance.calculate(&vector, pt);
+					dist = Some(dist.map_or(d, |dist: f64| dist.min(d)));
+				}
+			}
+			match dist {
+				Some(dist) if builder.check_add(dist) => {
+					let evicted_docs = builder.add(dist, Ids64::One(doc_id));
+					chk.expires(evicted_docs);
+				}
+				_ => chk.expires(Ids64::One(doc_id)),
+			}
+		}
+		Ok(builder.build(
+			#[cfg(debug_assertions)]
+			HashMap::default(),
+		))
 	}
 
 	async fn build_result(
 		&self,
 		tx: &Transaction,
+		pt: &SharedVector,
 		neighbors: Vec<(f64, ElementId)>,
 		n: usize,
 		chk: &mut HnswConditionChecker<'_>,
 	) -> Result<KnnResult, Error> {
 		let mut builder = KnnResultBuilder::new(n);
-		for (e_dist, e_id) in neighbors {
-			if builder.check_add(e_dist) {
+		// Re-rank using the full-precision vectors
+		let neighbors = if self.rerank {
+			let mut reranked = Vec::with_capacity(neighbors.len());
+			for (_, e_id) in neighbors {
 				if let Some(v) = self.hnsw.get_vector(tx, &e_id).await? {
-					if let Some(docs) = self.vec_docs.get_docs(tx, &v).await? {
-						let evicted_docs = builder.add(e_dist, docs);
-						chk.expires(evicted_docs);
-					}
+					reranked.push((self.distance.calculate(&v, pt), v));
 				}
 			}
+			reranked.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+			reranked
+		} else {
+			let mut vectors = Vec::with_capacity(neighbors.len());
+			for (e_dist, e_id) in neighbors {
+				if let Some(v) = self.hnsw.get_vector(tx, &e_id).await? {
+					vectors.push((e_dist, v));
+				}
+			}
+			vectors
+		};
+		// The neighbors are sorted by distance, so the first occurrence of a record
+		// is its closest vector (max-sim aggregation of multi-vector records)
+		for (e_dist, v) in neighbors {
+			if !builder.check_add(e_dist) {
+				break;
+			}
+			if let Some(docs) = self.vec_docs.get_docs(tx, &v).await? {
+				// Documents sharing the vector may not match the condition
+				let docs = chk.truthy_docs(docs).filter(|d| !builder.contains(d));
+				if docs.is_empty() {
+					continue;
+				}
+				let evicted_docs = builder.add(e_dist, docs);
+				chk.expires(evicted_docs);
+			}
 		}
 		Ok(builder.build(
 			#[cfg(debug_assertions)]
@@ -196,8 +333,67 @@ impl HnswIndex {
 		))
 	}
 
+	/// Trains the codebook of a product quantizer, once the index holds enough elements.
+	/// This is not done on insertion, as training is too expensive to run in the
+	/// transaction of a write.
+	pub(crate) async fn train_quantizer(&mut self, tx: &Transaction) -> Result<(), Error> {
+		self.hnsw.train_quantizer(tx).await
+	}
+
+	pub(crate) async fn statistics(&self, tx: &Transaction) -> Result<HnswStatistics, Error> {
+		let component_size = match self.vector_type {
+			VectorType::F64 | VectorType::I64 => 8,
+			VectorType::F32 | VectorType::I32 => 4,
+			VectorType::I16 => 2,
+		};
+		Ok(HnswStatistics {
+			quantization: self.quantization,
+			rerank: self.rerank,
+			graph: self.hnsw.statistics(tx, self.dim, component_size).await?,
+		})
+	}
+
 	#[cfg(test)]
 	pub(super) fn check_hnsw_properties(&self, expected_count: usize) {
 		self.hnsw.check_hnsw_properties(expected_count)
 	}
 }
+
+pub(crate) struct HnswStatistics {
+	quantization: Option<VectorQuantization>,
+	rerank: bool,
+	graph: HnswGraphStatistics,
+}
+
+impl From<HnswStatistics> for Value {
+	fn from(stats: HnswStatistics) -> Self {
+		let g = stats.graph;
+		let mut res = Object::default();
+		res.insert("elements".to_owned(), Value::from(g.elements));
+		res.insert("layers".to_owned(), Value::from(g.layers));
+		res.insert(
+			"quantization".to_owned(),
+			stats.quantization.map(|q| Value::from(q.to_string())).unwrap_or(Value::None),
+		);
+		// A product quantizer is not active until its codebook has been trained
+		res.insert("quantized".to_owned(), Value::from(g.quantized));
+		res.insert("rerank".to_owned(), Value::from(stats.rerank));
+		res.insert("element_bytes".to_owned(), Value::from(g.element_size));
+		res.insert("full_element_bytes".to_owned(), Value::from(g.full_element_size));
+		res.insert("codebook_bytes".to_owned(), Value::from(g.codebook_size));
+		res.insert(
+			"memory_bytes".to_owned(),
+			Value::from(g.elements * g.element_size + g.codebook_size),
+		);
+		res.insert("full_memory_bytes".to_owned(), Value::from(g.elements * g.full_element_size));
+		res.insert(
+			"compression_ratio".to_owned(),
+			Value::from(g.full_element_size as f64 / g.element_size.max(1) as f64),
+		);
+		res.insert(
+			"quantization_error".to_owned(),
+			g.quantization_error.map(Value::from).unwrap_or(Value::None),
+		);
+		Value::from(res)
+	}
+}
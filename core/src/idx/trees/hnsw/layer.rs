@@ -5,7 +5,7 @@ use crate::idx::trees::graph::UndirectedGraph;
 use crate::idx::trees::hnsw::heuristic::Heuristic;
 use crate::idx::trees::hnsw::index::HnswCheckedSearchContext;
 use crate::idx::trees::hnsw::{ElementId, HnswElements};
-use crate::idx::trees::knn::DoublePriorityQueue;
+use crate::idx::trees::knn::{DoublePriorityQueue, Ids64};
 use crate::idx::trees::vector::SharedVector;
 use crate::idx::IndexKeyBase;
 use crate::kvs::Transaction;
@@ -46,6 +46,10 @@ where
 		}
 	}
 
+	pub(super) fn len(&self) -> usize {
+		self.graph.len()
+	}
+
 	pub(super) fn m_max(&self) -> usize {
 		self.m_max
 	}
@@ -199,12 +203,22 @@ where
 		mut w: DoublePriorityQueue,
 		chk: &mut HnswConditionChecker<'_>,
 	) -> Result<DoublePriorityQueue, Error> {
-		let mut f_dist = w.peek_last_dist().unwrap_or(f64::MAX);
-
 		let ef = search.ef();
 		let pt = search.pt();
 		let elements = search.elements();
 
+		// The elements rejected by the condition are not collected in `w`.
+		// The traversal can't be pruned until `w` holds `ef` matching elements,
+		// otherwise a restrictive condition would return fewer than `k` results.
+		let bound = |w: &DoublePriorityQueue| {
+			if w.len() < ef {
+				f64::MAX
+			} else {
+				w.peek_last_dist().unwrap_or(f64::MAX)
+			}
+		};
+		let mut f_dist = bound(&w);
+
 		while let Some((dist, doc)) = candidates.pop_first() {
 			if dist > f_dist {
 				break;
@@ -215,16 +229,20 @@ where
 					if !visited.insert(e_id) {
 						continue;
 					}
+					// A restrictive condition can't make the search visit the whole graph
+					if !chk.visit() {
+						return Ok(w);
+					}
 					if let Some(e_pt) = elements.get_vector(tx, &e_id).await? {
 						let e_dist = elements.distance(&e_pt, pt);
-						if e_dist < f_dist || w.len() < ef {
+						if e_dist < f_dist {
 							candidates.push(e_dist, e_id);
 							if Self::add_if_truthy(
 								tx, stk, search, &mut w, &e_pt, e_dist, e_id, chk,
 							)
 							.await?
 							{
-								f_dist = w.peek_last_dist().unwrap(); // w can't be empty
+								f_dist = bound(&w);
 							}
 						}
 					}
@@ -245,20 +263,46 @@ where
 		e_id: ElementId,
 		chk: &mut HnswConditionChecker<'_>,
 	) -> Result<bool, Error> {
-		if let Some(docs) = search.vec_docs().get_docs(tx, e_pt).await? {
-			if chk.check_truthy(tx, stk, search.docs(), docs).await? {
+		// The documents are keyed by the full-precision vector
+		let docs = if search.elements().is_quantized() {
+			match search.elements().get_full_vector(tx, &e_id).await? {
+				Some(v) => search.vec_docs().get_docs(tx, &v).await?,
+				None => None,
+			}
+		} else {
+			search.vec_docs().get_docs(tx, e_pt).await?
+		};
+		if let Some(docs) = docs {
+			if chk.check_truthy(tx, stk, search.docs(), docs.clone()).await? {
+				chk.collect(&docs);
 				w.push(e_dist, e_id);
 				if w.len() > search.ef() {
 					if let Some((_, id)) = w.pop_last() {
-						chk.expire(id);
+						if let Some(docs) = Self::get_docs(tx, search, id).await? {
+							chk.release(docs);
+						}
 					}
 				}
 				return Ok(true);
 			}
+			// None of the documents matches the condition
+			chk.expires(docs);
 		}
 		Ok(false)
 	}
 
+	/// Returns the documents of an element, which are keyed by its full-precision vector
+	async fn get_docs(
+		tx: &Transaction,
+		search: &HnswCheckedSearchContext<'_>,
+		e_id: ElementId,
+	) -> Result<Option<Ids64>, Error> {
+		match search.elements().get_full_vector(tx, &e_id).await? {
+			Some(v) => search.vec_docs().get_docs(tx, &v).await,
+			None => Ok(None),
+		}
+	}
+
 	pub(super) async fn insert(
 		&mut self,
 		(tx, st): (&Transaction, &mut LayerState),
@@ -4,6 +4,7 @@ mod flavor;
 mod heuristic;
 pub mod index;
 mod layer;
+mod quantizer;
 
 use crate::err::Error;
 use crate::idx::planner::checker::HnswConditionChecker;
@@ -73,6 +74,19 @@ where
 
 pub(crate) type ElementId = u64;
 
+/// The number of elements used to estimate the quantization error
+const QUANTIZATION_SAMPLE: usize = 100;
+
+pub(super) struct HnswGraphStatistics {
+	elements: usize,
+	layers: usize,
+	quantized: bool,
+	element_size: usize,
+	full_element_size: usize,
+	codebook_size: usize,
+	quantization_error: Option<f64>,
+}
+
 impl<L0, L> Hnsw<L0, L>
 where
 	L0: DynamicSet,
@@ -89,7 +103,7 @@ where
 			ml: p.ml.to_float(),
 			layer0: HnswLayer::new(ikb.clone(), 0, m0),
 			layers: Vec::default(),
-			elements: HnswElements::new(ikb.clone(), p.distance.clone()),
+			elements: HnswElements::new(ikb.clone(), p),
 			rng: SmallRng::from_entropy(),
 			heuristic: p.into(),
 			ikb,
@@ -127,6 +141,8 @@ where
 		// Set the enter_point
 		self.elements.set_next_element_id(st.next_element_id);
 		self.state = st;
+		// Load the codebook if it has been trained in the meantime
+		self.elements.check_quantizer(tx).await?;
 		Ok(())
 	}
 
@@ -284,6 +300,10 @@ where
 		Ok(res)
 	}
 
+	async fn train_quantizer(&mut self, tx: &Transaction) -> Result<(), Error> {
+		self.elements.train_quantizer(tx).await
+	}
+
 	async fn remove(&mut self, tx: &Transaction, e_id: ElementId) -> Result<bool, Error> {
 		let mut removed = false;
 
@@ -339,10 +359,11 @@ where
 		tx: &Transaction,
 		search: &HnswSearch,
 	) -> Result<Vec<(f64, ElementId)>, Error> {
-		if let Some((ep_dist, ep_id)) = self.search_ep(tx, &search.pt).await? {
+		let pt = self.elements.prepare_query(&search.pt);
+		if let Some((ep_dist, ep_id)) = self.search_ep(tx, &pt).await? {
 			let w = self
 				.layer0
-				.search_single(tx, &self.elements, &search.pt, ep_dist, ep_id, search.ef)
+				.search_single(tx, &self.elements, &pt, ep_dist, ep_id, search.ef)
 				.await?;
 			Ok(w.to_vec_limit(search.k))
 		} else {
@@ -359,13 +380,14 @@ where
 		vec_docs: &VecDocs,
 		chk: &mut HnswConditionChecker<'_>,
 	) -> Result<Vec<(f64, ElementId)>, Error> {
-		if let Some((ep_dist, ep_id)) = self.search_ep(tx, &search.pt).await? {
+		let pt = self.elements.prepare_query(&search.pt);
+		if let Some((ep_dist, ep_id)) = self.search_ep(tx, &pt).await? {
 			if let Some(ep_pt) = self.elements.get_vector(tx, &ep_id).await? {
 				let search_ctx = HnswCheckedSearchContext::new(
 					&self.elements,
 					hnsw_docs,
 					vec_docs,
-					&search.pt,
+					&pt,
 					search.ef,
 				);
 				let w = self
@@ -406,12 +428,31 @@ where
 		Ok(None)
 	}
 
+	/// Returns the full-precision vector of an element
 	async fn get_vector(
 		&self,
 		tx: &Transaction,
 		e_id: &ElementId,
 	) -> Result<Option<SharedVector>, Error> {
-		self.elements.get_vector(tx, e_id).await
+		self.elements.get_full_vector(tx, e_id).await
+	}
+
+	async fn statistics(
+		&self,
+		tx: &Transaction,
+		dim: usize,
+		component_size: usize,
+	) -> Result<HnswGraphStatistics, Error> {
+		let (element_size, full_element_size) = self.elements.vector_sizes(dim, component_size);
+		Ok(HnswGraphStatistics {
+			elements: self.layer0.len(),
+			layers: self.layers.len() + 1,
+			quantized: self.elements.is_quantized(),
+			element_size,
+			full_element_size,
+			codebook_size: self.elements.codebook_size(),
+			quantization_error: self.elements.quantization_error(tx, QUANTIZATION_SAMPLE).await?,
+		})
 	}
 	#[cfg(test)]
 	fn check_hnsw_properties(&self, expected_count: usize) {
@@ -446,7 +487,7 @@ mod tests {
 	use crate::idx::IndexKeyBase;
 	use crate::kvs::LockType::Optimistic;
 	use crate::kvs::{Datastore, Transaction, TransactionType};
-	use crate::sql::index::{Distance, HnswParams, VectorType};
+	use crate::sql::index::{Distance, HnswParams, VectorQuantization, VectorType};
 	use crate::sql::{Id, Value};
 	use ahash::{HashMap, HashSet};
 	use ndarray::Array1;
@@ -569,6 +610,8 @@ mod tests {
 			efc as u16,
 			extend_candidates,
 			keep_pruned_connections,
+			None,
+			false,
 		)
 	}
 
@@ -852,7 +895,12 @@ mod tests {
 			let content = vec![Value::from(obj.deref())];
 			h.index_document(&tx, Id::Number(*doc_id as i64), &content).await?;
 		}
+		h.train_quantizer(&tx).await?;
 		tx.commit().await?;
+		// Load the codebook once it has been committed
+		let tx = new_ctx(&ds, TransactionType::Read).await.tx();
+		h.check_state(&tx).await?;
+		tx.cancel().await?;
 
 		let h = Arc::new(h);
 
@@ -950,6 +998,39 @@ mod tests {
 		.await
 	}
 
+	#[test(tokio::test(flavor = "multi_thread"))]
+	async fn test_recall_euclidean_scalar_quantization() -> Result<(), Error> {
+		let mut p = new_params(20, VectorType::F32, Distance::Euclidean, 8, 100, false, false);
+		p.quantization = Some(VectorQuantization::Scalar);
+		p.rerank = true;
+		test_recall(
+			"hnsw-random-9000-20-euclidean.gz",
+			1000,
+			"hnsw-random-5000-20-euclidean.gz",
+			300,
+			p,
+			&[(10, 0.9), (40, 0.98)],
+		)
+		.await
+	}
+
+	#[test(tokio::test(flavor = "multi_thread"))]
+	async fn test_recall_euclidean_product_quantization() -> Result<(), Error> {
+		let mut p = new_params(20, VectorType::F32, Distance::Euclidean, 8, 100, false, false);
+		p.quantization = Some(VectorQuantization::Product(10));
+		p.rerank = true;
+		// The collection is large enough to train the codebook
+		test_recall(
+			"hnsw-random-9000-20-euclidean.gz",
+			1500,
+			"hnsw-random-5000-20-euclidean.gz",
+			200,
+			p,
+			&[(10, 0.7), (40, 0.9)],
+		)
+		.await
+	}
+
 	impl TestCollection {
 		fn knn(&self, pt: &SharedVector, dist: Distance, n: usize) -> KnnResult {
 			let mut b = KnnResultBuilder::new(n);
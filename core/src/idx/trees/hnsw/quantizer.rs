@@ -0,0 +1,356 @@
+use crate::err::Error;
+use crate::idx::trees::vector::Vector;
+use crate::idx::{IndexKeyBase, VersionedStore};
+use crate::kvs::{Key, Transaction, Val};
+use crate::sql::index::VectorQuantization;
+use ndarray::Array1;
+use rand::prelude::SmallRng;
+use rand::seq::index::sample;
+use rand::SeedableRng;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// The number of centroids per subspace, so that every code fits in a byte
+const PQ_CENTROIDS: usize = 256;
+/// The number of elements required before the codebook of a product quantizer can be trained
+pub(super) const PQ_TRAINING_SIZE: usize = 1024;
+/// The number of k-means iterations used to train the codebook
+const PQ_TRAINING_ITERATIONS: usize = 8;
+
+/// The compressed form of a vector, as held in memory by the graph
+pub(super) enum QuantizedVector {
+	/// One byte per dimension, with the range required to restore the values
+	Scalar {
+		min: f32,
+		scale: f32,
+		codes: Box<[u8]>,
+	},
+	/// One byte per subspace, being the position of the nearest centroid
+	Product(Box<[u8]>),
+}
+
+#[revisioned(revision = 1)]
+#[derive(Serialize, Deserialize)]
+#[non_exhaustive]
+struct Codebook {
+	/// For each subspace, the flattened list of its centroids
+	centroids: Vec<Vec<f32>>,
+}
+
+impl VersionedStore for Codebook {}
+
+enum QuantizerKind {
+	Scalar,
+	Product {
+		key: Key,
+		subspaces: usize,
+		codebook: Option<Codebook>,
+	},
+}
+
+pub(super) struct Quantizer {
+	dim: usize,
+	kind: QuantizerKind,
+}
+
+impl Quantizer {
+	pub(super) fn new(ikb: &IndexKeyBase, dim: usize, q: &VectorQuantization) -> Self {
+		let kind = match q {
+			VectorQuantization::Scalar => QuantizerKind::Scalar,
+			VectorQuantization::Product(subspaces) => QuantizerKind::Product {
+				key: ikb.new_hq_key(),
+				subspaces: (*subspaces as usize).clamp(1, dim.max(1)),
+				codebook: None,
+			},
+		};
+		Self {
+			dim,
+			kind,
+		}
+	}
+
+	/// A product quantizer can't compress vectors until its codebook has been trained
+	pub(super) fn is_ready(&self) -> bool {
+		match &self.kind {
+			QuantizerKind::Scalar => true,
+			QuantizerKind::Product {
+				codebook,
+				..
+			} => codebook.is_some(),
+		}
+	}
+
+	/// Loads the codebook, if it has been trained in the meantime (possibly by another node).
+	/// Returns true if a codebook has been loaded.
+	pub(super) async fn load(&mut self, tx: &Transaction) -> Result<bool, Error> {
+		if let QuantizerKind::Product {
+			key,
+			codebook,
+			..
+		} = &mut self.kind
+		{
+			if codebook.is_none() {
+				if let Some(val) = tx.get(key.clone(), None).await? {
+					*codebook = Some(VersionedStore::try_from(val)?);
+					return Ok(true);
+				}
+			}
+		}
+		Ok(false)
+	}
+
+	/// Trains the codebook of a product quantizer on the given samples, and persists it.
+	/// The codebook is not used until it is loaded, so that a codebook is only used
+	/// once the transaction which persisted it has been committed.
+	pub(super) async fn train(
+		&mut self,
+		tx: &Transaction,
+		samples: &[Vec<f32>],
+	) -> Result<(), Error> {
+		let dim = self.dim;
+		if let QuantizerKind::Product {
+			key,
+			subspaces,
+			..
+		} = &self.kind
+		{
+			if samples.is_empty() {
+				return Ok(());
+			}
+			// The seed is fixed, so the training is reproducible
+			let mut rng = SmallRng::seed_from_u64(samples.len() as u64);
+			let centroids = (0..*subspaces)
+				.map(|s| Self::kmeans(&mut rng, samples, Self::subspace(dim, *subspaces, s)))
+				.collect();
+			let cb = Codebook {
+				centroids,
+			};
+			let val: Val = VersionedStore::try_into(&cb)?;
+			// A single codebook is ever written, if another node
+			// trained one in the meantime, that codebook is used
+			match tx.putc(key.clone(), val, None).await {
+				Ok(()) | Err(Error::TxConditionNotMet) => {}
+				Err(e) => return Err(e),
+			}
+		}
+		Ok(())
+	}
+
+	/// Returns the compressed vector, or None if the quantizer is not ready yet
+	pub(super) fn encode(&self, v: &Vector) -> Option<QuantizedVector> {
+		let values = v.to_f32_vec();
+		match &self.kind {
+			QuantizerKind::Scalar => {
+				let (min, max) = values
+					.iter()
+					.fold((f32::MAX, f32::MIN), |(min, max), &x| (min.min(x), max.max(x)));
+				let scale = if max > min {
+					(max - min) / 255.0
+				} else {
+					0.0
+				};
+				let codes = values
+					.iter()
+					.map(|&x| {
+						if scale > 0.0 {
+							((x - min) / scale).round() as u8
+						} else {
+							0
+						}
+					})
+					.collect();
+				Some(QuantizedVector::Scalar {
+					min,
+					scale,
+					codes,
+				})
+			}
+			QuantizerKind::Product {
+				subspaces,
+				codebook: Some(cb),
+				..
+			} => {
+				let codes = (0..*subspaces)
+					.map(|s| {
+						let sub = &values[Self::subspace(self.dim, *subspaces, s)];
+						Self::nearest(&cb.centroids[s], sub) as u8
+					})
+					.collect();
+				Some(QuantizedVector::Product(codes))
+			}
+			QuantizerKind::Product {
+				codebook: None,
+				..
+			} => None,
+		}
+	}
+
+	/// Restores an approximation of the original vector
+	pub(super) fn decode(&self, q: &QuantizedVector) -> Vector {
+		let values = match (q, &self.kind) {
+			(
+				QuantizedVector::Scalar {
+					min,
+					scale,
+					codes,
+				},
+				_,
+			) => codes.iter().map(|&c| min + c as f32 * scale).collect(),
+			(
+				QuantizedVector::Product(codes),
+				QuantizerKind::Product {
+					subspaces,
+					codebook: Some(cb),
+					..
+				},
+			) => {
+				let mut values = Vec::with_capacity(self.dim);
+				for (s, &c) in codes.iter().enumerate() {
+					let len = Self::subspace(self.dim, *subspaces, s).len();
+					let c = c as usize;
+					values.extend_from_slice(&cb.centroids[s][c * len..(c + 1) * len]);
+				}
+				values
+			}
+			// Product codes are only produced once the codebook exists
+			(QuantizedVector::Product(_), _) => vec![0.0; self.dim],
+		};
+		Vector::F32(Array1::from_vec(values))
+	}
+
+	/// The graph is navigated using restored vectors, the query must have the same type
+	pub(super) fn prepare_query(&self, v: &Vector) -> Vector {
+		Vector::F32(Array1::from_vec(v.to_f32_vec()))
+	}
+
+	/// The number of bytes used by a compressed vector
+	pub(super) fn code_size(&self) -> usize {
+		match &self.kind {
+			QuantizerKind::Scalar => self.dim + 8,
+			QuantizerKind::Product {
+				subspaces,
+				..
+			} => *subspaces,
+		}
+	}
+
+	/// The number of bytes used by the codebook
+	pub(super) fn codebook_size(&self) -> usize {
+		match &self.kind {
+			QuantizerKind::Product {
+				codebook: Some(cb),
+				..
+			} => cb.centroids.iter().map(|c| c.len() * 4).sum(),
+			_ => 0,
+		}
+	}
+
+	fn subspace(dim: usize, subspaces: usize, s: usize) -> Range<usize> {
+		(s * dim / subspaces)..((s + 1) * dim / subspaces)
+	}
+
+	/// Lloyd's k-means on one subspace of the samples. Returns the flattened centroids.
+	fn kmeans(rng: &mut SmallRng, samples: &[Vec<f32>], r: Range<usize>) -> Vec<f32> {
+		let len = r.len();
+		let k = PQ_CENTROIDS.min(samples.len());
+		// The initial centroids are randomly picked samples
+		let mut centroids = Vec::with_capacity(k * len);
+		for i in sample(rng, samples.len(), k).into_iter() {
+			centroids.extend_from_slice(&samples[i][r.clone()]);
+		}
+		let mut sums = vec![0.0f32; k * len];
+		let mut counts = vec![0usize; k];
+		for _ in 0..PQ_TRAINING_ITERATIONS {
+			sums.iter_mut().for_each(|s| *s = 0.0);
+			counts.iter_mut().for_each(|c| *c = 0);
+			for s in samples {
+				let sub = &s[r.clone()];
+				let c = Self::nearest(&centroids, sub);
+				counts[c] += 1;
+				for (acc, x) in sums[c * len..(c + 1) * len].iter_mut().zip(sub) {
+					*acc += x;
+				}
+			}
+			for (c, &count) in counts.iter().enumerate() {
+				// An empty cluster keeps its previous centroid
+				if count > 0 {
+					let range = c * len..(c + 1) * len;
+					for (v, acc) in centroids[range.clone()].iter_mut().zip(&sums[range]) {
+						*v = acc / count as f32;
+					}
+				}
+			}
+		}
+		centroids
+	}
+
+	/// Returns the position of the centroid which is the closest to the sub-vector
+	fn nearest(centroids: &[f32], sub: &[f32]) -> usize {
+		let mut nearest = 0;
+		let mut nearest_dist = f32::MAX;
+		for (i, c) in centroids.chunks_exact(sub.len()).enumerate() {
+			let d: f32 = c.iter().zip(sub).map(|(a, b)| (a - b) * (a - b)).sum();
+			if d < nearest_dist {
+				nearest_dist = d;
+				nearest = i;
+			}
+		}
+		nearest
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::idx::trees::hnsw::quantizer::{QuantizedVector, Quantizer};
+	use crate::idx::trees::vector::Vector;
+	use crate::idx::IndexKeyBase;
+	use crate::kvs::LockType::Optimistic;
+	use crate::kvs::{Datastore, TransactionType};
+	use crate::sql::index::{Distance, VectorQuantization};
+	use ndarray::Array1;
+	use test_log::test;
+
+	fn new_vec(values: Vec<f32>) -> Vector {
+		Vector::F32(Array1::from_vec(values))
+	}
+
+	#[test]
+	fn scalar_quantization() {
+		let q = Quantizer::new(&IndexKeyBase::default(), 4, &VectorQuantization::Scalar);
+		assert!(q.is_ready());
+		let v = new_vec(vec![-1.0, 0.0, 0.5, 1.0]);
+		let c = q.encode(&v).unwrap();
+		assert!(matches!(&c, QuantizedVector::Scalar { codes, .. } if codes.len() == 4));
+		let d = Distance::Euclidean.calculate(&q.decode(&c), &v);
+		assert!(d < 0.01, "{d}");
+	}
+
+	#[test(tokio::test)]
+	async fn product_quantization() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let tx = ds.transaction(TransactionType::Write, Optimistic).await.unwrap();
+		let mut q = Quantizer::new(&IndexKeyBase::default(), 4, &VectorQuantization::Product(2));
+		assert!(!q.is_ready());
+		assert!(q.encode(&new_vec(vec![0.0; 4])).is_none());
+		let samples: Vec<Vec<f32>> =
+			(0..64).map(|i| vec![i as f32, (i % 8) as f32, -(i as f32), 1.0]).collect();
+		q.train(&tx, &samples).await.unwrap();
+		// The codebook is only used once it is loaded
+		assert!(!q.is_ready());
+		assert!(q.load(&tx).await.unwrap());
+		assert!(q.is_ready());
+		assert_eq!(q.code_size(), 2);
+		for s in &samples {
+			let v = new_vec(s.clone());
+			let c = q.encode(&v).unwrap();
+			// There are fewer samples than centroids: every sample is a centroid
+			assert_eq!(q.decode(&c), v);
+		}
+		// Another quantizer loads the trained codebook
+		let mut q2 = Quantizer::new(&IndexKeyBase::default(), 4, &VectorQuantization::Product(2));
+		assert!(q2.load(&tx).await.unwrap());
+		assert!(q2.is_ready());
+		tx.cancel().await.unwrap();
+	}
+}
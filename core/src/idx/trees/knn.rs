@@ -374,6 +374,20 @@ impl Ids64 {
 		}
 	}
 
+	/// Returns the doc ids satisfying the predicate
+	pub(in crate::idx) fn filter<F>(&self, f: F) -> Self
+	where
+		F: Fn(DocId) -> bool,
+	{
+		let mut res = Self::Empty;
+		for doc_id in self.iter().filter(|d| f(*d)) {
+			if let Some(r) = res.insert(doc_id) {
+				res = r;
+			}
+		}
+		res
+	}
+
 	fn contains(&self, d: DocId) -> bool {
 		match self {
 			Self::Empty => false,
@@ -545,6 +559,11 @@ impl KnnResultBuilder {
 		true
 	}
 
+	/// Returns true if the document is already part of the result
+	pub(super) fn contains(&self, doc_id: DocId) -> bool {
+		self.docs.contains(doc_id)
+	}
+
 	pub(super) fn add(&mut self, dist: f64, docs: Ids64) -> Ids64 {
 		let pr = FloatKey(dist);
 		docs.append_to(&mut self.docs);
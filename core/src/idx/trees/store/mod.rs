@@ -347,6 +347,9 @@ impl IndexStores {
 			Index::Hnsw(_) => {
 				self.remove_hnsw_index(ikb).await;
 			}
+			Index::Sparse(_) => {
+				self.remove_sparse_caches(ikb);
+			}
 			_ => {}
 		}
 		Ok(())
@@ -364,6 +367,11 @@ impl IndexStores {
 		self.0.mtree_caches.remove_caches(&TreeNodeProvider::Vector(ikb.clone()));
 	}
 
+	fn remove_sparse_caches(&self, ikb: IndexKeyBase) {
+		self.0.btree_trie_caches.remove_caches(&TreeNodeProvider::DocIds(ikb.clone()));
+		self.0.btree_trie_caches.remove_caches(&TreeNodeProvider::Postings(ikb));
+	}
+
 	async fn remove_hnsw_index(&self, ikb: IndexKeyBase) {
 		self.0.hnsw_indexes.remove(&ikb).await;
 	}
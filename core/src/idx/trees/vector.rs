@@ -521,6 +521,17 @@ impl Vector {
 		}
 	}
 
+	/// Returns the components of the vector as single precision floats
+	pub(super) fn to_f32_vec(&self) -> Vec<f32> {
+		match self {
+			Self::F64(v) => v.iter().map(|&x| x as f32).collect(),
+			Self::F32(v) => v.to_vec(),
+			Self::I64(v) => v.iter().map(|&x| x as f32).collect(),
+			Self::I32(v) => v.iter().map(|&x| x as f32).collect(),
+			Self::I16(v) => v.iter().map(|&x| x as f32).collect(),
+		}
+	}
+
 	pub(super) fn check_expected_dimension(current: usize, expected: usize) -> Result<(), Error> {
 		if current != expected {
 			Err(Error::InvalidVectorDimension {
@@ -20,12 +20,24 @@ pub enum Category {
 	AccessRoot,
 	/// crate::key::root::access::gr         /*{ac}!gr{gr}
 	AccessGrant,
+	/// crate::key::root::au                 /!au{ts}{id}
+	Audit,
+	/// crate::key::root::es                 /!es
+	EncryptionSalt,
+	/// crate::key::root::lo                 /!lo{subject}
+	SigninFailures,
 	/// crate::key::root::nd                 /!nd{nd}
 	Node,
 	/// crate::key::root::ni                 /!ni
 	NamespaceIdentifier,
 	/// crate::key::root::ns                 /!ns{ns}
 	Namespace,
+	/// crate::key::root::rl                 /!rl{rl}
+	Role,
+	/// crate::key::root::sp                 /!sp
+	SigninPolicy,
+	/// crate::key::root::tf                 /!tf{us}
+	UserSecondFactor,
 	/// crate::key::root::us                 /!us{us}
 	User,
 	///
@@ -55,6 +67,14 @@ pub enum Category {
 	NamespaceAccessRoot,
 	/// crate::key::namespace::access::gr    /*{ns}*{ac}!gr{gr}
 	NamespaceAccessGrant,
+	/// crate::key::namespace::pl            /*{ns}!pl{pl}
+	NamespacePolicy,
+	/// crate::key::namespace::rl            /*{ns}!rl{rl}
+	NamespaceRole,
+	/// crate::key::namespace::sp            /*{ns}!sp
+	NamespaceSigninPolicy,
+	/// crate::key::namespace::tf            /*{ns}!tf{us}
+	NamespaceUserSecondFactor,
 	/// crate::key::namespace::us            /*{ns}!us{us}
 	NamespaceUser,
 	///
@@ -76,8 +96,16 @@ pub enum Category {
 	DatabaseModel,
 	/// crate::key::database::pa             /*{ns}*{db}!pa{pa}
 	DatabaseParameter,
+	/// crate::key::database::pl             /*{ns}*{db}!pl{pl}
+	DatabasePolicy,
+	/// crate::key::database::rl             /*{ns}*{db}!rl{rl}
+	DatabaseRole,
+	/// crate::key::database::sp             /*{ns}*{db}!sp
+	DatabaseSigninPolicy,
 	/// crate::key::database::tb             /*{ns}*{db}!tb{tb}
 	DatabaseTable,
+	/// crate::key::database::tf             /*{ns}*{db}!tf{us}
+	DatabaseUserSecondFactor,
 	/// crate::key::database::ts             /*{ns}*{db}!ts{ts}
 	DatabaseTimestamp,
 	/// crate::key::database::us             /*{ns}*{db}!us{us}
@@ -165,9 +193,15 @@ impl Display for Category {
 			Self::Access => "Access",
 			Self::AccessRoot => "AccessRoot",
 			Self::AccessGrant => "AccessGrant",
+			Self::Audit => "Audit",
+			Self::EncryptionSalt => "EncryptionSalt",
+			Self::SigninFailures => "SigninFailures",
 			Self::Node => "Node",
 			Self::NamespaceIdentifier => "NamespaceIdentifier",
 			Self::Namespace => "Namespace",
+			Self::Role => "Role",
+			Self::SigninPolicy => "SigninPolicy",
+			Self::UserSecondFactor => "UserSecondFactor",
 			Self::User => "User",
 			Self::NodeRoot => "NodeRoot",
 			Self::NodeLiveQuery => "NodeLiveQuery",
@@ -177,6 +211,10 @@ impl Display for Category {
 			Self::NamespaceAccess => "NamespaceAccess",
 			Self::NamespaceAccessRoot => "NamespaceAccessRoot",
 			Self::NamespaceAccessGrant => "NamespaceAccessGrant",
+			Self::NamespacePolicy => "NamespacePolicy",
+			Self::NamespaceRole => "NamespaceRole",
+			Self::NamespaceSigninPolicy => "NamespaceSigninPolicy",
+			Self::NamespaceUserSecondFactor => "NamespaceUserSecondFactor",
 			Self::NamespaceUser => "NamespaceUser",
 			Self::DatabaseRoot => "DatabaseRoot",
 			Self::DatabaseAccess => "DatabaseAccess",
@@ -186,9 +224,13 @@ impl Display for Category {
 			Self::DatabaseFunction => "DatabaseFunction",
 			Self::DatabaseModel => "DatabaseModel",
 			Self::DatabaseParameter => "DatabaseParameter",
+			Self::DatabasePolicy => "DatabasePolicy",
+			Self::DatabaseRole => "DatabaseRole",
+			Self::DatabaseSigninPolicy => "DatabaseSigninPolicy",
 			Self::DatabaseTable => "DatabaseTable",
 			Self::DatabaseTableIdentifier => "DatabaseTableIdentifier",
 			Self::DatabaseTimestamp => "DatabaseTimestamp",
+			Self::DatabaseUserSecondFactor => "DatabaseUserSecondFactor",
 			Self::DatabaseUser => "DatabaseUser",
 			Self::DatabaseVersionstamp => "DatabaseVersionstamp",
 			Self::TableRoot => "TableRoot",
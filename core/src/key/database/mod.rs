@@ -5,7 +5,11 @@ pub mod az;
 pub mod fc;
 pub mod ml;
 pub mod pa;
+pub mod pl;
+pub mod rl;
+pub mod sp;
 pub mod tb;
+pub mod tf;
 pub mod ti;
 pub mod ts;
 pub mod us;
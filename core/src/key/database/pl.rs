@@ -0,0 +1,87 @@
+//! Stores a DEFINE POLICY ON DATABASE config definition
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Pl<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub policy: &'a str,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, policy: &'a str) -> Pl<'a> {
+	Pl::new(ns, db, policy)
+}
+
+pub fn prefix(ns: &str, db: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db).encode().unwrap();
+	k.extend_from_slice(b"!pl\x00");
+	k
+}
+
+pub fn suffix(ns: &str, db: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db).encode().unwrap();
+	k.extend_from_slice(b"!pl\xff");
+	k
+}
+
+impl Categorise for Pl<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabasePolicy
+	}
+}
+
+impl<'a> Pl<'a> {
+	pub fn new(ns: &'a str, db: &'a str, policy: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b'p',
+			_e: b'l',
+			policy,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Pl::new(
+			"testns",
+			"testdb",
+			"testpolicy",
+		);
+		let enc = Pl::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\x00*testdb\x00!pltestpolicy\x00");
+		let dec = Pl::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix("testns", "testdb");
+		assert_eq!(val, b"/*testns\0*testdb\0!pl\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix("testns", "testdb");
+		assert_eq!(val, b"/*testns\0*testdb\0!pl\xff");
+	}
+}
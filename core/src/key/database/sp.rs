@@ -0,0 +1,60 @@
+//! Stores a DEFINE SIGNIN POLICY ON DATABASE config definition
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Sp<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str) -> Sp<'a> {
+	Sp::new(ns, db)
+}
+
+impl Categorise for Sp<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseSigninPolicy
+	}
+}
+
+impl<'a> Sp<'a> {
+	pub fn new(ns: &'a str, db: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b's',
+			_e: b'p',
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Sp::new(
+			"testns",
+			"testdb",
+		);
+		let enc = Sp::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\x00*testdb\x00!sp");
+		let dec = Sp::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
@@ -0,0 +1,63 @@
+//! Stores the last time step accepted for the second factor of a DEFINE USER ON DATABASE
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Tf<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub user: &'a str,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, user: &'a str) -> Tf<'a> {
+	Tf::new(ns, db, user)
+}
+
+impl Categorise for Tf<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseUserSecondFactor
+	}
+}
+
+impl<'a> Tf<'a> {
+	pub fn new(ns: &'a str, db: &'a str, user: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b't',
+			_e: b'f',
+			user,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Tf::new(
+			"testns",
+			"testdb",
+			"testuser",
+		);
+		let enc = Tf::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\x00*testdb\x00!tftestuser\x00");
+		let dec = Tf::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
@@ -21,6 +21,18 @@ pub struct Ia<'a> {
 	pub i: u32,
 }
 
+pub fn prefix(ns: &str, db: &str, tb: &str, ix: &str) -> Vec<u8> {
+	let mut k = crate::key::index::all::new(ns, db, tb, ix).encode().unwrap();
+	k.extend_from_slice(b"!ia");
+	k
+}
+
+pub fn suffix(ns: &str, db: &str, tb: &str, ix: &str) -> Vec<u8> {
+	let mut k = crate::key::index::all::new(ns, db, tb, ix).encode().unwrap();
+	k.extend_from_slice(b"!ia\xff\xff\xff\xff\xff");
+	k
+}
+
 impl<'a> Ia<'a> {
 	pub fn new(ns: &'a str, db: &'a str, tb: &'a str, ix: &'a str, i: u32) -> Self {
 		Self {
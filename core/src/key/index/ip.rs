@@ -22,6 +22,18 @@ pub struct Ip<'a> {
 	pub id: Id,
 }
 
+pub fn prefix(ns: &str, db: &str, tb: &str, ix: &str) -> Vec<u8> {
+	let mut k = crate::key::index::all::new(ns, db, tb, ix).encode().unwrap();
+	k.extend_from_slice(b"!ip\x00");
+	k
+}
+
+pub fn suffix(ns: &str, db: &str, tb: &str, ix: &str) -> Vec<u8> {
+	let mut k = crate::key::index::all::new(ns, db, tb, ix).encode().unwrap();
+	k.extend_from_slice(b"!ip\xff");
+	k
+}
+
 impl<'a> Ip<'a> {
 	pub fn new(ns: &'a str, db: &'a str, tb: &'a str, ix: &'a str, id: Id) -> Self {
 		Self {
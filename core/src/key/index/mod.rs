@@ -15,9 +15,11 @@ pub mod hd;
 pub mod he;
 pub mod hi;
 pub mod hl;
+pub mod hq;
 pub mod hs;
 pub mod hv;
 pub mod ia;
+pub mod ib;
 pub mod ip;
 pub mod vm;
 
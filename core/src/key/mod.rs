@@ -4,10 +4,16 @@
 ///
 /// crate::key::root::all                /
 /// crate::key::root::ac                 /!ac{ac}
+/// crate::key::root::au                 /!au{ts}{id}
+/// crate::key::root::es                 /!es
 /// crate::key::root::hb                 /!hb{ts}/{nd}
+/// crate::key::root::lo                 /!lo{subject}
 /// crate::key::root::nd                 /!nd{nd}
 /// crate::key::root::ni                 /!ni
 /// crate::key::root::ns                 /!ns{ns}
+/// crate::key::root::rl                 /!rl{rl}
+/// crate::key::root::sp                 /!sp
+/// crate::key::root::tf                 /!tf{us}
 /// crate::key::root::us                 /!us{us}
 ///
 /// crate::key::node::all                /${nd}
@@ -21,6 +27,10 @@
 /// crate::key::namespace::db            /*{ns}!db{db}
 /// crate::key::namespace::di            /+{ns id}!di
 /// crate::key::namespace::lg            /*{ns}!lg{lg}
+/// crate::key::namespace::pl            /*{ns}!pl{pl}
+/// crate::key::namespace::rl            /*{ns}!rl{rl}
+/// crate::key::namespace::sp            /*{ns}!sp
+/// crate::key::namespace::tf            /*{ns}!tf{us}
 /// crate::key::namespace::us            /*{ns}!us{us}
 ///
 /// crate::key::namespace::access::all   /*{ns}&{ac}
@@ -32,7 +42,11 @@
 /// crate::key::database::fc             /*{ns}*{db}!fn{fc}
 /// crate::key::database::ml             /*{ns}*{db}!ml{ml}{vn}
 /// crate::key::database::pa             /*{ns}*{db}!pa{pa}
+/// crate::key::database::pl             /*{ns}*{db}!pl{pl}
+/// crate::key::database::rl             /*{ns}*{db}!rl{rl}
+/// crate::key::database::sp             /*{ns}*{db}!sp
 /// crate::key::database::tb             /*{ns}*{db}!tb{tb}
+/// crate::key::database::tf             /*{ns}*{db}!tf{us}
 /// crate::key::database::ti             /+{ns id}*{db id}!ti
 /// crate::key::database::ts             /*{ns}*{db}!ts{ts}
 /// crate::key::database::us             /*{ns}*{db}!us{us}
@@ -60,6 +74,9 @@
 /// crate::key::index::bs                /*{ns}*{db}*{tb}+{ix}!bs
 /// crate::key::index::bt                /*{ns}*{db}*{tb}+{ix}!bt{id}
 /// crate::key::index::bu                /*{ns}*{db}*{tb}+{ix}!bu{id}
+/// crate::key::index::ia                /*{ns}*{db}*{tb}+{ix}!ia{i}
+/// crate::key::index::ib                /*{ns}*{db}*{tb}+{ix}!ib
+/// crate::key::index::ip                /*{ns}*{db}*{tb}+{ix}!ip{id}
 /// crate::key::index                    /*{ns}*{db}*{tb}+{ix}*{fd}{id}
 ///
 /// crate::key::change                   /*{ns}*{db}#{ts}
@@ -3,4 +3,8 @@ pub mod access;
 pub mod all;
 pub mod db;
 pub mod di;
+pub mod pl;
+pub mod rl;
+pub mod sp;
+pub mod tf;
 pub mod us;
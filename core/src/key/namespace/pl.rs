@@ -0,0 +1,82 @@
+//! Stores a DEFINE POLICY ON NAMESPACE config definition
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Pl<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	_c: u8,
+	_d: u8,
+	pub policy: &'a str,
+}
+
+pub fn new<'a>(ns: &'a str, policy: &'a str) -> Pl<'a> {
+	Pl::new(ns, policy)
+}
+
+pub fn prefix(ns: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns).encode().unwrap();
+	k.extend_from_slice(b"!pl\x00");
+	k
+}
+
+pub fn suffix(ns: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns).encode().unwrap();
+	k.extend_from_slice(b"!pl\xff");
+	k
+}
+
+impl Categorise for Pl<'_> {
+	fn categorise(&self) -> Category {
+		Category::NamespacePolicy
+	}
+}
+
+impl<'a> Pl<'a> {
+	pub fn new(ns: &'a str, policy: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'!',
+			_c: b'p',
+			_d: b'l',
+			policy,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Pl::new(
+			"testns",
+			"testpolicy",
+		);
+		let enc = Pl::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\x00!pltestpolicy\x00");
+		let dec = Pl::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix("testns");
+		assert_eq!(val, b"/*testns\0!pl\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix("testns");
+		assert_eq!(val, b"/*testns\0!pl\xff");
+	}
+}
@@ -0,0 +1,55 @@
+//! Stores a DEFINE SIGNIN POLICY ON NAMESPACE config definition
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Sp<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	_c: u8,
+	_d: u8,
+}
+
+pub fn new(ns: &str) -> Sp<'_> {
+	Sp::new(ns)
+}
+
+impl Categorise for Sp<'_> {
+	fn categorise(&self) -> Category {
+		Category::NamespaceSigninPolicy
+	}
+}
+
+impl<'a> Sp<'a> {
+	pub fn new(ns: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'!',
+			_c: b's',
+			_d: b'p',
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Sp::new(
+			"testns",
+		);
+		let enc = Sp::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\x00!sp");
+		let dec = Sp::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
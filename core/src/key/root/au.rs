@@ -0,0 +1,81 @@
+//! Stores an entry in the audit log
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Au {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub ts: u64,
+	#[serde(with = "uuid::serde::compact")]
+	pub id: Uuid,
+}
+
+pub fn new(ts: u64, id: Uuid) -> Au {
+	Au::new(ts, id)
+}
+
+pub fn prefix() -> Vec<u8> {
+	let mut k = crate::key::root::all::new().encode().unwrap();
+	k.extend_from_slice(b"!au\x00");
+	k
+}
+
+pub fn suffix() -> Vec<u8> {
+	let mut k = crate::key::root::all::new().encode().unwrap();
+	k.extend_from_slice(b"!au\xff");
+	k
+}
+
+/// Returns the first key of the entries recorded at or after a timestamp
+pub fn prefix_ts(ts: u64) -> Vec<u8> {
+	Au::new(ts, Uuid::nil()).encode().unwrap()
+}
+
+impl Categorise for Au {
+	fn categorise(&self) -> Category {
+		Category::Audit
+	}
+}
+
+impl Au {
+	pub fn new(ts: u64, id: Uuid) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'a',
+			_c: b'u',
+			ts,
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		let val = Au::new(1, Uuid::nil());
+		let enc = Au::encode(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/!au\x00\x00\x00\x00\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00"
+		);
+		let dec = Au::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix_ts() {
+		let val = super::prefix_ts(1);
+		assert!(val > super::prefix());
+		assert!(val < super::suffix());
+	}
+}
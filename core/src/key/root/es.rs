@@ -0,0 +1,54 @@
+//! Stores the salt from which the encryption keys are derived
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Es {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+}
+
+pub fn new() -> Es {
+	Es::new()
+}
+
+impl Default for Es {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Categorise for Es {
+	fn categorise(&self) -> Category {
+		Category::EncryptionSalt
+	}
+}
+
+impl Es {
+	pub fn new() -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'e',
+			_c: b's',
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		let val = Es::new();
+		let enc = Es::encode(&val).unwrap();
+		assert_eq!(enc, b"/!es");
+		let dec = Es::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
@@ -0,0 +1,69 @@
+//! Stores the failed signin attempts of a subject
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Lo<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub subject: &'a str,
+}
+
+pub fn new(subject: &str) -> Lo<'_> {
+	Lo::new(subject)
+}
+
+pub fn prefix() -> Vec<u8> {
+	let mut k = super::all::new().encode().unwrap();
+	k.extend_from_slice(b"!lo\x00");
+	k
+}
+
+pub fn suffix() -> Vec<u8> {
+	let mut k = super::all::new().encode().unwrap();
+	k.extend_from_slice(b"!lo\xff");
+	k
+}
+
+impl Categorise for Lo<'_> {
+	fn categorise(&self) -> Category {
+		Category::SigninFailures
+	}
+}
+
+impl<'a> Lo<'a> {
+	pub fn new(subject: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'l',
+			_c: b'o',
+			subject,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Lo::new("testsubject");
+		let enc = Lo::encode(&val).unwrap();
+		assert_eq!(enc, b"/!lotestsubject\x00");
+		let dec = Lo::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix();
+		assert_eq!(val, b"/!lo\0");
+	}
+}
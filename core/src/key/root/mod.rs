@@ -1,7 +1,13 @@
 pub mod ac;
 pub mod access;
 pub mod all;
+pub mod au;
+pub mod es;
+pub mod lo;
 pub mod nd;
 pub mod ni;
 pub mod ns;
+pub mod rl;
+pub mod sp;
+pub mod tf;
 pub mod us;
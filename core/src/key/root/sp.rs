@@ -0,0 +1,54 @@
+//! Stores a DEFINE SIGNIN POLICY ON ROOT config definition
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Sp {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+}
+
+pub fn new() -> Sp {
+	Sp::new()
+}
+
+impl Default for Sp {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Categorise for Sp {
+	fn categorise(&self) -> Category {
+		Category::SigninPolicy
+	}
+}
+
+impl Sp {
+	pub fn new() -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b's',
+			_c: b'p',
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		let val = Sp::new();
+		let enc = Sp::encode(&val).unwrap();
+		assert_eq!(enc, b"/!sp");
+		let dec = Sp::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
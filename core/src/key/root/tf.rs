@@ -0,0 +1,51 @@
+//! Stores the last time step accepted for the second factor of a DEFINE USER ON ROOT
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Tf<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub user: &'a str,
+}
+
+pub fn new(user: &str) -> Tf<'_> {
+	Tf::new(user)
+}
+
+impl Categorise for Tf<'_> {
+	fn categorise(&self) -> Category {
+		Category::UserSecondFactor
+	}
+}
+
+impl<'a> Tf<'a> {
+	pub fn new(user: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b't',
+			_c: b'f',
+			user,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Tf::new("testuser");
+		let enc = Tf::encode(&val).unwrap();
+		assert_eq!(enc, b"/!tftestuser\x00");
+		let dec = Tf::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
@@ -11,6 +11,8 @@ use crate::sql::statements::DefineIndexStatement;
 use crate::sql::statements::DefineModelStatement;
 use crate::sql::statements::DefineNamespaceStatement;
 use crate::sql::statements::DefineParamStatement;
+use crate::sql::statements::DefinePolicyStatement;
+use crate::sql::statements::DefineRoleStatement;
 use crate::sql::statements::DefineTableStatement;
 use crate::sql::statements::DefineUserStatement;
 use crate::sql::statements::LiveStatement;
@@ -52,6 +54,8 @@ pub(super) enum Entry {
 	Nds(Arc<[Node]>),
 	/// A slice of DefineUserStatement specified at the root.
 	Rus(Arc<[DefineUserStatement]>),
+	/// A slice of DefineRoleStatement specified at the root.
+	Rrs(Arc<[DefineRoleStatement]>),
 	/// A slice of DefineAccessStatement specified at the root.
 	Ras(Arc<[DefineAccessStatement]>),
 	/// A slice of AccessGrant specified at the root.
@@ -60,6 +64,10 @@ pub(super) enum Entry {
 	Nss(Arc<[DefineNamespaceStatement]>),
 	/// A slice of DefineUserStatement specified on a namespace.
 	Nus(Arc<[DefineUserStatement]>),
+	/// A slice of DefineRoleStatement specified on a namespace.
+	Nrs(Arc<[DefineRoleStatement]>),
+	/// A slice of DefinePolicyStatement specified on a namespace.
+	Nps(Arc<[DefinePolicyStatement]>),
 	/// A slice of DefineAccessStatement specified on a namespace.
 	Nas(Arc<[DefineAccessStatement]>),
 	/// A slice of AccessGrant specified at on a namespace.
@@ -74,6 +82,10 @@ pub(super) enum Entry {
 	Dag(Arc<[AccessGrant]>),
 	/// A slice of DefineUserStatement specified on a database.
 	Dus(Arc<[DefineUserStatement]>),
+	/// A slice of DefineRoleStatement specified on a database.
+	Drs(Arc<[DefineRoleStatement]>),
+	/// A slice of DefinePolicyStatement specified on a database.
+	Dps(Arc<[DefinePolicyStatement]>),
 	/// A slice of DefineFunctionStatement specified on a database.
 	Fcs(Arc<[DefineFunctionStatement]>),
 	/// A slice of DefineTableStatement specified on a database.
@@ -92,6 +104,8 @@ pub(super) enum Entry {
 	Ixs(Arc<[DefineIndexStatement]>),
 	/// A slice of LiveStatement specified on a table.
 	Lvs(Arc<[LiveStatement]>),
+	/// A slice of DefinePolicyStatement which is attached to a table.
+	Tps(Arc<[DefinePolicyStatement]>),
 }
 
 impl Entry {
@@ -119,6 +133,14 @@ impl Entry {
 			_ => unreachable!(),
 		}
 	}
+	/// Converts this cache entry into a slice of [`DefineRoleStatement`].
+	/// This panics if called on a cache entry that is not an [`Entry::Rrs`].
+	pub(super) fn into_rrs(self) -> Arc<[DefineRoleStatement]> {
+		match self {
+			Entry::Rrs(v) => v,
+			_ => unreachable!(),
+		}
+	}
 	/// Converts this cache entry into a slice of [`DefineAccessStatement`].
 	/// This panics if called on a cache entry that is not an [`Entry::Ras`].
 	pub(super) fn into_ras(self) -> Arc<[DefineAccessStatement]> {
@@ -167,6 +189,22 @@ impl Entry {
 			_ => unreachable!(),
 		}
 	}
+	/// Converts this cache entry into a slice of [`DefineRoleStatement`].
+	/// This panics if called on a cache entry that is not an [`Entry::Nrs`].
+	pub(super) fn into_nrs(self) -> Arc<[DefineRoleStatement]> {
+		match self {
+			Entry::Nrs(v) => v,
+			_ => unreachable!(),
+		}
+	}
+	/// Converts this cache entry into a slice of [`DefinePolicyStatement`].
+	/// This panics if called on a cache entry that is not an [`Entry::Nps`].
+	pub(super) fn into_nps(self) -> Arc<[DefinePolicyStatement]> {
+		match self {
+			Entry::Nps(v) => v,
+			_ => unreachable!(),
+		}
+	}
 	/// Converts this cache entry into a slice of [`DefineDatabaseStatement`].
 	/// This panics if called on a cache entry that is not an [`Entry::Dbs`].
 	pub(super) fn into_dbs(self) -> Arc<[DefineDatabaseStatement]> {
@@ -199,6 +237,22 @@ impl Entry {
 			_ => unreachable!(),
 		}
 	}
+	/// Converts this cache entry into a slice of [`DefineRoleStatement`].
+	/// This panics if called on a cache entry that is not an [`Entry::Drs`].
+	pub(super) fn into_drs(self) -> Arc<[DefineRoleStatement]> {
+		match self {
+			Entry::Drs(v) => v,
+			_ => unreachable!(),
+		}
+	}
+	/// Converts this cache entry into a slice of [`DefinePolicyStatement`].
+	/// This panics if called on a cache entry that is not an [`Entry::Dps`].
+	pub(super) fn into_dps(self) -> Arc<[DefinePolicyStatement]> {
+		match self {
+			Entry::Dps(v) => v,
+			_ => unreachable!(),
+		}
+	}
 	/// Converts this cache entry into a slice of [`DefineAnalyzerStatement`].
 	/// This panics if called on a cache entry that is not an [`Entry::Azs`].
 	pub(super) fn into_azs(self) -> Arc<[DefineAnalyzerStatement]> {
@@ -271,6 +325,14 @@ impl Entry {
 			_ => unreachable!(),
 		}
 	}
+	/// Converts this cache entry into a slice of [`DefinePolicyStatement`].
+	/// This panics if called on a cache entry that is not an [`Entry::Tps`].
+	pub(super) fn into_tps(self) -> Arc<[DefinePolicyStatement]> {
+		match self {
+			Entry::Tps(v) => v,
+			_ => unreachable!(),
+		}
+	}
 	/// Converts this cache entry into a slice of [`LiveStatement`].
 	/// This panics if called on a cache entry that is not an [`Entry::Lvs`].
 	pub(super) fn into_lvs(self) -> Arc<[LiveStatement]> {
@@ -0,0 +1,442 @@
+//! Transparent encryption of stored values at rest, for all storage engines.
+//!
+//! Values are encrypted with AES-256-GCM, using a random nonce for each write. Keys are
+//! stored in plaintext, as range and prefix scans depend on their ordering, so record ids
+//! and the names of namespaces, databases, tables and indexes are not encrypted. Each
+//! value is bound to its key, which is passed as additional authenticated data, so that
+//! a stored value can not be moved or copied to another key without failing to decrypt.
+//!
+//! Encrypted values are stored with the following layout:
+//!
+//! ```text
+//! magic (4 bytes) | key id (4 bytes) | nonce (12 bytes) | ciphertext | tag (16 bytes)
+//! ```
+//!
+//! Versionstamped values, such as change feed entries, are encrypted before the storage
+//! engine decides on the versionstamp within their key. These are stored with a different
+//! magic header, and with the length of the key prefix preceding the versionstamp, so
+//! that they are bound to their key without the versionstamp:
+//!
+//! ```text
+//! magic (4 bytes) | key id (4 bytes) | nonce (12 bytes) | prefix length (2 bytes) | ciphertext | tag (16 bytes)
+//! ```
+//!
+//! Values without a magic header were stored before encryption was enabled, and are
+//! read as plaintext until they are rewritten or re-encrypted in the background.
+//!
+//! Encryption keys are derived from secret material with Argon2id, using a random salt
+//! which is generated once and stored in plaintext in the datastore itself.
+//!
+//! The same keys are used for encrypted fields, whose values are stored with a different
+//! magic header. Fields can be encrypted deterministically, in which case the nonce is
+//! derived from the value itself, so that equal values have equal ciphertexts.
+
+use crate::err::Error;
+use crate::kvs::Val;
+use crate::vs::Versionstamp;
+use argon2::Argon2;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::hkdf;
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::fmt;
+
+/// The header which marks an encrypted value
+const MAGIC: [u8; 4] = *b"\xffenc";
+
+/// The header which marks an encrypted versionstamped value
+const VS_MAGIC: [u8; 4] = *b"\xffevs";
+
+/// The header which marks an encrypted field value
+const FIELD_MAGIC: [u8; 4] = *b"\xfffld";
+
+/// The length of the identifier of the key which encrypted a value
+const ID_LEN: usize = 4;
+
+/// The length of the authentication tag appended to the ciphertext
+const TAG_LEN: usize = 16;
+
+/// The length of the header preceding the ciphertext
+const HEADER_LEN: usize = MAGIC.len() + ID_LEN + NONCE_LEN;
+
+/// The length of the key prefix length of versionstamped values
+const PREFIX_LEN: usize = 2;
+
+/// The length of the salt from which encryption keys are derived
+pub(crate) const SALT_LEN: usize = 16;
+
+/// The length of the key material derived from a secret
+const MATERIAL_LEN: usize = 32;
+
+/// An output length for HKDF, used to derive the key identifier
+struct Len(usize);
+
+impl hkdf::KeyType for Len {
+	fn len(&self) -> usize {
+		self.0
+	}
+}
+
+/// Generates random bytes, such as a salt, or the secret of an encrypted field
+pub(crate) fn random(len: usize) -> Result<Vec<u8>, Error> {
+	let mut out = vec![0u8; len];
+	SystemRandom::new()
+		.fill(&mut out)
+		.map_err(|_| Error::Encryption("Unable to generate random bytes".to_string()))?;
+	Ok(out)
+}
+
+/// Generates a random nonce
+fn nonce() -> Result<[u8; NONCE_LEN], Error> {
+	let mut nonce = [0u8; NONCE_LEN];
+	SystemRandom::new()
+		.fill(&mut nonce)
+		.map_err(|_| Error::Encryption("Unable to generate a nonce".to_string()))?;
+	Ok(nonce)
+}
+
+/// Returns the key id of a value encrypted with the specified header
+fn header(magic: &[u8; 4], extra: usize, val: &[u8]) -> Option<[u8; ID_LEN]> {
+	if val.len() < HEADER_LEN + extra + TAG_LEN || val[..MAGIC.len()] != magic[..] {
+		return None;
+	}
+	let mut id = [0u8; ID_LEN];
+	id.copy_from_slice(&val[MAGIC.len()..MAGIC.len() + ID_LEN]);
+	Some(id)
+}
+
+/// An AES-256-GCM key used to encrypt stored values
+pub struct EncryptionKey {
+	id: [u8; ID_LEN],
+	key: LessSafeKey,
+	// The key used to derive the nonces of deterministically encrypted fields
+	siv: hmac::Key,
+}
+
+impl fmt::Debug for EncryptionKey {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		// Never print the key material itself
+		f.debug_struct("EncryptionKey").field("id", &hex::encode(self.id)).finish()
+	}
+}
+
+impl EncryptionKey {
+	/// Derives an encryption key from secret material, such as a passphrase or the contents
+	/// of a key file, and the salt stored in the datastore, which is retrieved with
+	/// [`Datastore::encryption_salt`](crate::kvs::Datastore::encryption_salt)
+	pub fn new(secret: &[u8], salt: &[u8]) -> Result<Self, Error> {
+		if secret.is_empty() {
+			return Err(Error::Encryption("The encryption key can not be empty".to_string()));
+		}
+		if salt.len() < SALT_LEN {
+			return Err(Error::Encryption("The encryption salt is too short".to_string()));
+		}
+		// Stretch the secret, as it may be a passphrase with little entropy
+		let mut material = [0u8; MATERIAL_LEN];
+		Argon2::default()
+			.hash_password_into(secret, salt, &mut material)
+			.map_err(|e| Error::Encryption(format!("Unable to derive an encryption key: {e}")))?;
+		Self::derive(&material, salt)
+	}
+
+	/// Derives the key of an encrypted field from its random secret. The secret is stored
+	/// with the field definition, so that the values of the field, and the indexes of these
+	/// values, remain valid when the keys used for encryption at rest are rotated.
+	pub(crate) fn field(secret: &[u8]) -> Result<Self, Error> {
+		if secret.len() < MATERIAL_LEN {
+			return Err(Error::Encryption("The field has no encryption secret".to_string()));
+		}
+		Self::derive(secret, b"field")
+	}
+
+	/// Generates a random secret for an encrypted field
+	pub(crate) fn field_secret() -> Result<Vec<u8>, Error> {
+		random(MATERIAL_LEN)
+	}
+
+	/// Derives separate keys for each purpose from key material
+	fn derive(material: &[u8], salt: &[u8]) -> Result<Self, Error> {
+		let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, salt).extract(material);
+		let derive = |info: &[u8]| -> Result<[u8; MATERIAL_LEN], Error> {
+			let mut out = [0u8; MATERIAL_LEN];
+			prk.expand(&[info], Len(MATERIAL_LEN))
+				.and_then(|okm| okm.fill(&mut out))
+				.map_err(|_| Error::Encryption("Unable to derive an encryption key".to_string()))?;
+			Ok(out)
+		};
+		// The identifier is derived from the key, without revealing it
+		let mut id = [0u8; ID_LEN];
+		id.copy_from_slice(&derive(b"id")?[..ID_LEN]);
+		let key = UnboundKey::new(&AES_256_GCM, &derive(b"aes")?)
+			.map_err(|_| Error::Encryption("Invalid encryption key".to_string()))?;
+		Ok(Self {
+			id,
+			key: LessSafeKey::new(key),
+			siv: hmac::Key::new(hmac::HMAC_SHA256, &derive(b"siv")?),
+		})
+	}
+
+	/// Whether a value is an encrypted field value
+	pub(crate) fn is_encrypted_field(val: &[u8]) -> bool {
+		header(&FIELD_MAGIC, 0, val).is_some()
+	}
+
+	/// Encrypts the value of an encrypted field, binding it to the additional data.
+	/// Deterministic encryption derives the nonce from the value, so that equal
+	/// values can be found with an index, at the cost of revealing which are equal.
+	pub(crate) fn encrypt_field(
+		&self,
+		val: Val,
+		aad: &[u8],
+		deterministic: bool,
+	) -> Result<Val, Error> {
+		let nonce = match deterministic {
+			true => {
+				let mut ctx = hmac::Context::with_key(&self.siv);
+				ctx.update(&(aad.len() as u64).to_be_bytes());
+				ctx.update(aad);
+				ctx.update(&val);
+				let mut nonce = [0u8; NONCE_LEN];
+				nonce.copy_from_slice(&ctx.sign().as_ref()[..NONCE_LEN]);
+				nonce
+			}
+			false => nonce()?,
+		};
+		self.seal(&FIELD_MAGIC, nonce, &[], aad, val)
+	}
+
+	/// Decrypts the value of an encrypted field, or returns it unchanged if it is not encrypted
+	pub(crate) fn decrypt_field(&self, val: Val, aad: &[u8]) -> Result<Val, Error> {
+		match header(&FIELD_MAGIC, 0, &val) {
+			Some(id) if id == self.id => self.open(0, aad, val),
+			Some(_) => Err(Error::Encryption(
+				"The value was encrypted with the key of another field".to_string(),
+			)),
+			None => Ok(val),
+		}
+	}
+
+	/// Encrypts a value with this key and the specified nonce, storing any
+	/// extra header bytes after the nonce, and authenticating the additional data
+	fn seal(
+		&self,
+		magic: &[u8; 4],
+		nonce: [u8; NONCE_LEN],
+		extra: &[u8],
+		aad: &[u8],
+		val: Val,
+	) -> Result<Val, Error> {
+		let mut out = Vec::with_capacity(HEADER_LEN + extra.len() + val.len() + TAG_LEN);
+		out.extend_from_slice(magic);
+		out.extend_from_slice(&self.id);
+		out.extend_from_slice(&nonce);
+		out.extend_from_slice(extra);
+		let mut data = val;
+		self.key
+			.seal_in_place_append_tag(
+				Nonce::assume_unique_for_key(nonce),
+				Aad::from(aad),
+				&mut data,
+			)
+			.map_err(|_| Error::Encryption("Unable to encrypt a value".to_string()))?;
+		out.extend_from_slice(&data);
+		Ok(out)
+	}
+
+	/// Decrypts a value which was encrypted with this key, and which has the specified
+	/// number of extra header bytes after the nonce
+	fn open(&self, extra: usize, aad: &[u8], val: Val) -> Result<Val, Error> {
+		let mut nonce = [0u8; NONCE_LEN];
+		nonce.copy_from_slice(&val[MAGIC.len() + ID_LEN..HEADER_LEN]);
+		let mut data = val[HEADER_LEN + extra..].to_vec();
+		let len = self
+			.key
+			.open_in_place(Nonce::assume_unique_for_key(nonce), Aad::from(aad), &mut data)
+			.map_err(|_| Error::Encryption("Unable to decrypt a value".to_string()))?
+			.len();
+		data.truncate(len);
+		Ok(data)
+	}
+}
+
+/// The current encryption key, along with previous keys which are still able to decrypt values
+#[derive(Debug)]
+pub struct Keyring {
+	current: EncryptionKey,
+	previous: Vec<EncryptionKey>,
+}
+
+impl Keyring {
+	/// Creates a keyring which encrypts values with the specified key
+	pub fn new(current: EncryptionKey) -> Self {
+		Self {
+			current,
+			previous: vec![],
+		}
+	}
+
+	/// Adds previous keys, so that values encrypted before a key rotation can still be read
+	pub fn with_previous(mut self, keys: Vec<EncryptionKey>) -> Self {
+		self.previous = keys;
+		self
+	}
+
+	/// Whether a stored value needs to be rewritten with the current key
+	pub(super) fn is_stale(&self, val: &[u8]) -> bool {
+		match header(&MAGIC, 0, val).or_else(|| header(&VS_MAGIC, PREFIX_LEN, val)) {
+			Some(id) => id != self.current.id,
+			None => true,
+		}
+	}
+
+	/// Encrypts a value with the current key, binding it to the key it is stored under
+	pub(super) fn encrypt(&self, key: &[u8], val: Val) -> Result<Val, Error> {
+		self.current.seal(&MAGIC, nonce()?, &[], key, val)
+	}
+
+	/// Encrypts a versionstamped value with the current key, binding it to the key it is
+	/// stored under, without the versionstamp which is decided on by the storage engine
+	pub(super) fn encrypt_versioned(
+		&self,
+		prefix: &[u8],
+		suffix: &[u8],
+		val: Val,
+	) -> Result<Val, Error> {
+		let len = u16::try_from(prefix.len())
+			.map_err(|_| Error::Encryption("The key prefix is too long".to_string()))?;
+		let aad = [prefix, suffix].concat();
+		self.current.seal(&VS_MAGIC, nonce()?, &len.to_be_bytes(), &aad, val)
+	}
+
+	/// Decrypts a value stored under a key, or returns it unchanged if it was stored in plaintext
+	pub(super) fn decrypt(&self, key: &[u8], val: Val) -> Result<Val, Error> {
+		if let Some(id) = header(&MAGIC, 0, &val) {
+			return self.key(id)?.open(0, key, val);
+		}
+		let Some(id) = header(&VS_MAGIC, PREFIX_LEN, &val) else {
+			return Ok(val);
+		};
+		// Remove the versionstamp from the key
+		let len = u16::from_be_bytes([val[HEADER_LEN], val[HEADER_LEN + 1]]) as usize;
+		let end = len + std::mem::size_of::<Versionstamp>();
+		if key.len() < end {
+			return Err(Error::Encryption("Unable to decrypt a value".to_string()));
+		}
+		let aad = [&key[..len], &key[end..]].concat();
+		self.key(id)?.open(PREFIX_LEN, &aad, val)
+	}
+
+	/// Returns the key with the specified id
+	fn key(&self, id: [u8; ID_LEN]) -> Result<&EncryptionKey, Error> {
+		std::iter::once(&self.current).chain(self.previous.iter()).find(|k| k.id == id).ok_or_else(
+			|| {
+				Error::Encryption(format!(
+					"The value was encrypted with an unknown key {}",
+					hex::encode(id)
+				))
+			},
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const SALT: &[u8] = b"0123456789abcdef";
+
+	#[test]
+	fn roundtrip() {
+		let keyring = Keyring::new(EncryptionKey::new(b"secret", SALT).unwrap());
+		let val = b"some value".to_vec();
+		let enc = keyring.encrypt(b"key", val.clone()).unwrap();
+		assert_ne!(enc, val);
+		assert!(!keyring.is_stale(&enc));
+		assert_eq!(keyring.decrypt(b"key", enc).unwrap(), val);
+		// Empty values are encrypted too
+		let enc = keyring.encrypt(b"key", vec![]).unwrap();
+		assert_eq!(keyring.decrypt(b"key", enc).unwrap(), Vec::<u8>::new());
+	}
+
+	#[test]
+	fn plaintext() {
+		let keyring = Keyring::new(EncryptionKey::new(b"secret", SALT).unwrap());
+		let val = b"stored before encryption was enabled".to_vec();
+		assert!(keyring.is_stale(&val));
+		assert_eq!(keyring.decrypt(b"key", val.clone()).unwrap(), val);
+	}
+
+	#[test]
+	fn rotation() {
+		let old = Keyring::new(EncryptionKey::new(b"old", SALT).unwrap());
+		let enc = old.encrypt(b"key", b"value".to_vec()).unwrap();
+		// The value can not be read without the previous key
+		let new = Keyring::new(EncryptionKey::new(b"new", SALT).unwrap());
+		assert!(matches!(new.decrypt(b"key", enc.clone()), Err(Error::Encryption(_))));
+		// The value can be read with the previous key, and needs re-encrypting
+		let new = new.with_previous(vec![EncryptionKey::new(b"old", SALT).unwrap()]);
+		assert!(new.is_stale(&enc));
+		assert_eq!(new.decrypt(b"key", enc).unwrap(), b"value".to_vec());
+	}
+
+	#[test]
+	fn fields() {
+		let key = EncryptionKey::field(&random(32).unwrap()).unwrap();
+		let val = b"123-45-6789".to_vec();
+		// Random encryption produces different ciphertexts
+		let one = key.encrypt_field(val.clone(), b"aad", false).unwrap();
+		let two = key.encrypt_field(val.clone(), b"aad", false).unwrap();
+		assert_ne!(one, two);
+		// Deterministic encryption produces equal ciphertexts
+		let one = key.encrypt_field(val.clone(), b"aad", true).unwrap();
+		let two = key.encrypt_field(val.clone(), b"aad", true).unwrap();
+		assert_eq!(one, two);
+		assert!(EncryptionKey::is_encrypted_field(&one));
+		assert_eq!(key.decrypt_field(one.clone(), b"aad").unwrap(), val);
+		// Field values are bound to the additional data
+		assert!(matches!(key.decrypt_field(one.clone(), b"other"), Err(Error::Encryption(_))));
+		// Field values can not be read with the key of another field
+		let other = EncryptionKey::field(&random(32).unwrap()).unwrap();
+		assert!(matches!(other.decrypt_field(one.clone(), b"aad"), Err(Error::Encryption(_))));
+		// Field values are distinct from values encrypted at rest
+		let keyring = Keyring::new(EncryptionKey::new(b"secret", SALT).unwrap());
+		assert!(keyring.is_stale(&one));
+		assert!(!EncryptionKey::is_encrypted_field(&keyring.encrypt(b"key", val).unwrap()));
+		// Field secrets must be long enough
+		assert!(matches!(EncryptionKey::field(b"short"), Err(Error::Encryption(_))));
+	}
+
+	#[test]
+	fn binding() {
+		let keyring = Keyring::new(EncryptionKey::new(b"secret", SALT).unwrap());
+		let enc = keyring.encrypt(b"key", b"value".to_vec()).unwrap();
+		// A value can not be read under another key
+		assert!(matches!(keyring.decrypt(b"other", enc), Err(Error::Encryption(_))));
+		// Versionstamped values are bound to their key without the versionstamp
+		let enc = keyring.encrypt_versioned(b"prefix", b"suffix", b"value".to_vec()).unwrap();
+		assert!(!keyring.is_stale(&enc));
+		let key = [&b"prefix"[..], &[0u8; 10], &b"suffix"[..]].concat();
+		assert_eq!(keyring.decrypt(&key, enc.clone()).unwrap(), b"value".to_vec());
+		let key = [&b"prefix"[..], &[0u8; 10], &b"other"[..]].concat();
+		assert!(matches!(keyring.decrypt(&key, enc), Err(Error::Encryption(_))));
+	}
+
+	#[test]
+	fn derivation() {
+		// Keys are derived from both the secret and the salt
+		let one = EncryptionKey::new(b"secret", SALT).unwrap();
+		let two = EncryptionKey::new(b"secret", b"fedcba9876543210").unwrap();
+		assert_ne!(one.id, two.id);
+		assert_eq!(one.id, EncryptionKey::new(b"secret", SALT).unwrap().id);
+		assert!(matches!(EncryptionKey::new(b"secret", b"short"), Err(Error::Encryption(_))));
+	}
+
+	#[test]
+	fn tampering() {
+		let keyring = Keyring::new(EncryptionKey::new(b"secret", SALT).unwrap());
+		let mut enc = keyring.encrypt(b"key", b"value".to_vec()).unwrap();
+		let last = enc.len() - 1;
+		enc[last] ^= 0x01;
+		assert!(matches!(keyring.decrypt(b"key", enc), Err(Error::Encryption(_))));
+	}
+}
@@ -2,23 +2,28 @@ use super::tr::Transactor;
 use super::tx::Transaction;
 use super::version::Version;
 use crate::cf;
+use crate::cnf::{AUDIT_MAX_ATTEMPTS, EXPORT_BATCH_SIZE};
 use crate::ctx::MutableContext;
 #[cfg(feature = "jwks")]
 use crate::dbs::capabilities::NetTarget;
 use crate::dbs::node::Timestamp;
 use crate::dbs::{
-	Attach, Capabilities, Executor, Notification, Options, Response, Session, Variables,
+	Attach, Capabilities, Executor, Notification, Options, Response, Row, Session, Variables,
 };
 use crate::err::Error;
+use crate::iam::audit::{AuditEntry, AuditLog};
 #[cfg(feature = "jwks")]
 use crate::iam::jwks::JwksCache;
-use crate::iam::{Action, Auth, Error as IamError, Resource, Role};
+use crate::iam::verify::resolve_grants;
+use crate::iam::{Action, Auth, Error as IamError, Level, Resource, Role};
 use crate::idx::trees::store::IndexStores;
 use crate::kvs::clock::SizedClock;
 #[allow(unused_imports)]
 use crate::kvs::clock::SystemClock;
+use crate::kvs::crypto::{self, Keyring};
 #[cfg(not(target_arch = "wasm32"))]
 use crate::kvs::index::IndexBuilder;
+use crate::kvs::interactive::Interactive;
 use crate::kvs::{LockType, LockType::*, TransactionType, TransactionType::*};
 use crate::sql::{statements::DefineUserStatement, Base, Query, Value};
 use crate::syn;
@@ -46,6 +51,9 @@ const TARGET: &str = "surrealdb::core::kvs::tr";
 // If there are an infinite number of heartbeats, then we want to go batch-by-batch spread over several checks
 const LQ_CHANNEL_SIZE: usize = 100;
 
+// The number of times a batch of values is retried when re-encrypting
+const REENCRYPT_RETRIES: usize = 5;
+
 // The role assigned to the initial user created when starting the server with credentials for the first time
 const INITIAL_USER_ROLE: &str = "owner";
 
@@ -68,6 +76,10 @@ pub struct Datastore {
 	capabilities: Capabilities,
 	// Whether this datastore enables live query notifications to subscribers
 	pub(super) notification_channel: Option<(Sender<Notification>, Receiver<Notification>)>,
+	// The audit log of authentication and privileged operations, if enabled
+	audit: Option<Arc<AuditLog>>,
+	// The transactions which span several requests
+	interactive: Interactive,
 	// The index store cache
 	index_stores: IndexStores,
 	// The index asynchronous builder
@@ -87,6 +99,8 @@ pub(super) struct TransactionFactory {
 	clock: Arc<SizedClock>,
 	// The inner datastore type
 	flavor: Arc<DatastoreFlavor>,
+	// The keys used to encrypt stored values, if encryption at rest is enabled
+	keyring: Option<Arc<Keyring>>,
 }
 
 impl TransactionFactory {
@@ -154,6 +168,7 @@ impl TransactionFactory {
 			stash: super::stash::Stash::default(),
 			cf: cf::Writer::new(),
 			clock: self.clock.clone(),
+			keyring: self.keyring.clone(),
 		}))
 	}
 }
@@ -254,6 +269,8 @@ impl Datastore {
 			transaction_timeout: self.transaction_timeout,
 			capabilities: self.capabilities,
 			notification_channel: self.notification_channel,
+			audit: self.audit,
+			interactive: Default::default(),
 			index_stores: Default::default(),
 			#[cfg(not(target_arch = "wasm32"))]
 			index_builder: IndexBuilder::new(self.transaction_factory.clone()),
@@ -404,6 +421,7 @@ impl Datastore {
 			let tf = TransactionFactory {
 				clock,
 				flavor: Arc::new(flavor),
+				keyring: None,
 			};
 			Self {
 				id: Uuid::new_v4(),
@@ -414,6 +432,8 @@ impl Datastore {
 				transaction_timeout: None,
 				notification_channel: None,
 				capabilities: Capabilities::default(),
+				audit: None,
+				interactive: Interactive::default(),
 				index_stores: IndexStores::default(),
 				#[cfg(not(target_arch = "wasm32"))]
 				index_builder: IndexBuilder::new(tf),
@@ -467,6 +487,23 @@ impl Datastore {
 		self
 	}
 
+	/// Set whether authentication and privileged operations are recorded in an audit log
+	pub fn with_audit(mut self, audit: Option<AuditLog>) -> Self {
+		self.audit = audit.map(Arc::new);
+		self
+	}
+
+	/// Set the keys used to encrypt stored values at rest
+	pub fn with_encryption(mut self, keyring: Option<Keyring>) -> Self {
+		self.transaction_factory.keyring = keyring.map(Arc::new);
+		// The index builder uses its own copy of the transaction factory
+		#[cfg(not(target_arch = "wasm32"))]
+		{
+			self.index_builder = IndexBuilder::new(self.transaction_factory.clone());
+		}
+		self
+	}
+
 	#[cfg(storage)]
 	/// Set a temporary directory for ordering of large result sets
 	pub fn with_temporary_directory(mut self, path: Option<PathBuf>) -> Self {
@@ -498,6 +535,80 @@ impl Datastore {
 		&self.jwks_cache
 	}
 
+	/// Records an entry in the audit log within its own transaction, if auditing is enabled.
+	/// Failures do not alter the audited operation. Storing the entry is retried, and an entry
+	/// which can not be stored is logged as an error, so that it is never dropped silently.
+	pub(crate) async fn audit<F>(&self, entry: F)
+	where
+		F: FnOnce() -> AuditEntry,
+	{
+		if let Some(audit) = &self.audit {
+			let entry = match audit.seal(entry()) {
+				Ok(v) => v,
+				Err(e) => {
+					error!("Unable to sign an entry for the audit log: {e}");
+					return;
+				}
+			};
+			// The sink receives the entry even if it can not be stored
+			audit.stream(&entry);
+			let mut attempt = 1;
+			loop {
+				let res = async {
+					let txn = self.transaction(Write, Optimistic).await?;
+					match audit.store(&txn, &entry).await {
+						Ok(_) => txn.commit().await,
+						Err(e) => {
+							txn.cancel().await?;
+							Err(e)
+						}
+					}
+				}
+				.await;
+				match res {
+					Ok(_) => break,
+					Err(e) if attempt < *AUDIT_MAX_ATTEMPTS => {
+						warn!("Retrying to record an entry in the audit log: {e}");
+						attempt += 1;
+					}
+					Err(e) => {
+						error!("Unable to record an entry in the audit log: {e}: {}", entry.json());
+						break;
+					}
+				}
+			}
+		}
+	}
+
+	/// Checks if the access grant which a session was established with has been revoked
+	pub(crate) async fn revoked(&self, sess: &Session) -> Result<bool, Error> {
+		match (sess.au.level(), &sess.ac, &sess.gr) {
+			(Level::Record(ns, db, _), Some(ac), Some(gr)) => {
+				let txn = self.transaction(Read, Optimistic).await?;
+				let res = txn.get_db_access_grant(ns, db, ac, gr).await;
+				txn.cancel().await?;
+				match res {
+					Ok(gr) => Ok(gr.revocation.is_some()),
+					// The grant or its access method has been removed
+					Err(_) => Ok(true),
+				}
+			}
+			_ => Ok(false),
+		}
+	}
+
+	/// Resolves the grants of the custom roles assigned to the actor of a session.
+	/// This is done for every request, so that the changes to a role apply immediately.
+	pub(crate) async fn resolve_auth(&self, sess: &Session) -> Result<Arc<Auth>, Error> {
+		if sess.au.custom_roles().is_empty() {
+			return Ok(sess.au.clone());
+		}
+		let txn = self.transaction(Read, Optimistic).await?;
+		let res = resolve_grants(&txn, sess.au.custom_roles(), sess.au.level()).await;
+		txn.cancel().await?;
+		Ok(Arc::new(sess.au.as_ref().clone().with_grants(res?)))
+	}
+
 	pub(super) async fn clock_now(&self) -> Timestamp {
 		self.transaction_factory.clock.now().await
 	}
@@ -608,6 +719,9 @@ impl Datastore {
 		self.insert_node(self.id).await?;
 		// Mark expired nodes as archived
 		self.expire_nodes().await?;
+		// Resume the interrupted index buildings
+		#[cfg(not(target_arch = "wasm32"))]
+		self.resume_index_buildings().await?;
 		// Everything ok
 		Ok(())
 	}
@@ -640,10 +754,67 @@ impl Datastore {
 		self.cleanup_nodes().await?;
 		// Garbage collect other data
 		self.garbage_collect().await?;
+		// Cancel abandoned transactions which span several requests
+		self.interactive.expire().await;
+		// Resume the index buildings of expired nodes
+		#[cfg(not(target_arch = "wasm32"))]
+		self.resume_index_buildings().await?;
 		// Everything ok
 		Ok(())
 	}
 
+	/// Resumes the concurrent index buildings which have been interrupted,
+	/// either by a restart, or because the node running them is not active anymore.
+	#[cfg(not(target_arch = "wasm32"))]
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub(crate) async fn resume_index_buildings(&self) -> Result<(), Error> {
+		// Collect the indexes which are built concurrently
+		let mut ixs = vec![];
+		let txn = self.transaction(Read, Optimistic).await?;
+		for ns in catch!(txn, txn.all_ns().await).iter() {
+			for db in catch!(txn, txn.all_db(&ns.name).await).iter() {
+				for tb in catch!(txn, txn.all_tb(&ns.name, &db.name).await).iter() {
+					for ix in catch!(txn, txn.all_tb_indexes(&ns.name, &db.name, &tb.name).await)
+						.iter()
+						.filter(|ix| ix.concurrently)
+					{
+						ixs.push((ns.name.to_raw(), db.name.to_raw(), Arc::new(ix.clone())));
+					}
+				}
+			}
+		}
+		txn.cancel().await?;
+		for (ns, db, ix) in ixs {
+			// Take over the building, only one node can succeed
+			let txn = self.transaction(Write, Optimistic).await?;
+			if !catch!(txn, self.index_builder.claim(&txn, &ns, &db, &ix, self.id).await) {
+				txn.cancel().await?;
+				continue;
+			}
+			if txn.commit().await.is_err() {
+				continue;
+			}
+			trace!(target: TARGET, "Resuming the building of the index {ns}/{db}/{}/{}", ix.what, ix.name);
+			let opt = Options::default()
+				.with_id(self.id)
+				.with_ns(Some(ns.into()))
+				.with_db(Some(db.into()))
+				.with_auth(Arc::new(Auth::for_root(Role::Owner)))
+				.with_strict(self.strict)
+				.with_auth_enabled(self.auth_enabled);
+			let ctx = MutableContext::from_ds(
+				self.query_timeout,
+				self.capabilities.clone(),
+				self.index_stores.clone(),
+				self.index_builder.clone(),
+				#[cfg(storage)]
+				self.temporary_directory.clone(),
+			)?;
+			self.index_builder.resume(&ctx.freeze(), opt, ix)?;
+		}
+		Ok(())
+	}
+
 	// save_timestamp_for_versionstamp saves the current timestamp for the each database's current versionstamp.
 	// Note: the returned VS is flawed, as there are multiple {ts: vs} mappings per (ns, db)
 	pub(crate) async fn save_timestamp_for_versionstamp(
@@ -688,6 +859,61 @@ impl Datastore {
 		Ok(vs)
 	}
 
+	/// Retrieves the salt from which the encryption keys of this datastore are derived,
+	/// generating and storing a random salt the first time that it is requested.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn encryption_salt(&self) -> Result<Vec<u8>, Error> {
+		// Start a new writeable transaction
+		let txn = self.transaction(Write, Pessimistic).await?;
+		// Check if a salt is already set in storage
+		let key = crate::key::root::es::new();
+		if let Some(salt) = catch!(txn, txn.get(key.clone(), None).await) {
+			txn.cancel().await?;
+			return Ok(salt);
+		}
+		// Otherwise generate and store a new salt
+		let salt = catch!(txn, crypto::random(crypto::SALT_LEN));
+		catch!(txn, txn.put(key, salt.clone(), None).await);
+		txn.commit().await?;
+		Ok(salt)
+	}
+
+	/// Re-encrypts the stored values which were not encrypted with the current key,
+	/// such as values stored before encryption was enabled, or encrypted with a
+	/// previous key. Values are processed in batches, each in its own transaction,
+	/// so that this can run in the background while the datastore is in use.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn reencrypt(&self) -> Result<(), Error> {
+		// Check that encryption at rest is enabled
+		if self.transaction_factory.keyring.is_none() {
+			return Ok(());
+		}
+		info!(target: TARGET, "Re-encrypting stored values with the current encryption key");
+		// Process the whole keyspace
+		let mut next = Some(vec![0x00]..vec![0xff]);
+		let mut retries = 0;
+		while let Some(rng) = next {
+			let txn = self.transaction(Write, Optimistic).await?;
+			let res = txn.lock().await.reencrypt(rng.clone(), *EXPORT_BATCH_SIZE).await;
+			let res = catch!(txn, res);
+			match txn.commit().await {
+				Ok(_) => {
+					retries = 0;
+					next = res;
+				}
+				// The batch may conflict with concurrent writes, so try again
+				Err(e) if retries < REENCRYPT_RETRIES => {
+					trace!(target: TARGET, "Retrying a batch of values to re-encrypt: {e}");
+					retries += 1;
+					next = Some(rng);
+				}
+				Err(e) => return Err(e),
+			}
+		}
+		info!(target: TARGET, "Finished re-encrypting stored values");
+		Ok(())
+	}
+
 	// garbage_collect_stale_change_feeds deletes all change feed entries that are older than the watermarks.
 	pub(crate) async fn garbage_collect_stale_change_feeds(&self, ts: u64) -> Result<(), Error> {
 		let tx = self.transaction(Write, Optimistic).await?;
@@ -790,10 +1016,210 @@ impl Datastore {
 		sess: &Session,
 		vars: Variables,
 	) -> Result<Vec<Response>, Error> {
+		// Check if the session can execute queries
+		self.check_session(sess).await?;
+		// Create a new query executor
+		let mut exe = Executor::new(self);
+		// Process all statements
+		self.process_with(&mut exe, ast, sess, vars).await
+	}
+
+	/// Execute a pre-parsed SQL query, streaming the rows of each `SELECT` statement
+	///
+	/// Rows are sent to the channel as soon as they are produced, rather than being
+	/// collected into the responses, whose results are `NONE` for streamed statements.
+	/// When the channel is bounded, the query waits until there is space for further rows.
+	///
+	/// ```rust,no_run
+	/// use surrealdb_core::kvs::Datastore;
+	/// use surrealdb_core::err::Error;
+	/// use surrealdb_core::dbs::Session;
+	/// use surrealdb_core::sql::parse;
+	///
+	/// #[tokio::main]
+	/// async fn main() -> Result<(), Error> {
+	///     let ds = Datastore::new("memory").await?;
+	///     let ses = Session::owner().with_ns("test").with_db("test");
+	///     let ast = parse("SELECT * FROM person")?;
+	///     let (tx, rx) = channel::bounded(100);
+	///     let query = ds.process_stream(ast, &ses, None, tx);
+	///     let rows = async {
+	///         while let Ok(row) = rx.recv().await {
+	///             println!("{}", row.value);
+	///         }
+	///     };
+	///     let (res, _) = futures::join!(query, rows);
+	///     res?;
+	///     Ok(())
+	/// }
+	/// ```
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn process_stream(
+		&self,
+		ast: Query,
+		sess: &Session,
+		vars: Variables,
+		rows: Sender<Row>,
+	) -> Result<Vec<Response>, Error> {
+		// Check if the session can execute queries
+		self.check_session(sess).await?;
+		// Create a new query executor
+		let mut exe = Executor::new(self).with_rows(Some(rows));
+		// Process all statements
+		self.process_with(&mut exe, ast, sess, vars).await
+	}
+
+	/// Begin a transaction which spans several requests, returning its id
+	///
+	/// ```rust,no_run
+	/// use surrealdb_core::kvs::Datastore;
+	/// use surrealdb_core::err::Error;
+	/// use surrealdb_core::dbs::Session;
+	///
+	/// #[tokio::main]
+	/// async fn main() -> Result<(), Error> {
+	///     let ds = Datastore::new("memory").await?;
+	///     let ses = Session::owner().with_ns("test").with_db("test");
+	///     let txn = ds.begin(&ses).await?;
+	///     let res = ds.execute_in(txn, "CREATE person:tobie", &ses, None).await?;
+	///     ds.commit(txn, &ses).await?;
+	///     Ok(())
+	/// }
+	/// ```
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn begin(&self, sess: &Session) -> Result<Uuid, Error> {
+		// Check if the session can execute queries
+		self.check_session(sess).await?;
+		// Create a new writeable transaction
+		let txn = self.transaction(Write, Optimistic).await?;
+		// Store the transaction for subsequent requests
+		self.interactive.insert(txn, sess).await
+	}
+
+	/// Commit a transaction which spans several requests
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn commit(&self, id: Uuid, sess: &Session) -> Result<(), Error> {
+		// Fetch the transaction
+		let handle = self.interactive.remove(id, sess)?;
+		// Lock the transaction
+		let mut txn = handle.txn.lock().await;
+		// Check if any statement has failed
+		if handle.failed {
+			let _ = txn.cancel().await;
+			return Err(Error::TxStatementFailed);
+		}
+		// Commit the transaction
+		if let Err(e) = txn.complete_changes(false).await {
+			let _ = txn.cancel().await;
+			return Err(e);
+		}
+		if let Err(e) = txn.commit().await {
+			let _ = txn.cancel().await;
+			return Err(e);
+		}
+		// Send the live query notifications
+		if let Some(chn) = &self.notification_channel {
+			for v in handle.notifications {
+				if chn.0.send(v).await.is_err() {
+					break;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Cancel a transaction which spans several requests
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn cancel(&self, id: Uuid, sess: &Session) -> Result<(), Error> {
+		// Fetch the transaction
+		let handle = self.interactive.remove(id, sess)?;
+		// Cancel the transaction
+		handle.txn.cancel().await
+	}
+
+	/// Cancel the transactions spanning several requests which were begun on a connection,
+	/// identified by the id of the sessions of the connection, once it is closed
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn close_transactions(&self, conn: &str) {
+		self.interactive.close(conn).await
+	}
+
+	/// Parse and execute an SQL query within a transaction which spans several requests
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn execute_in(
+		&self,
+		id: Uuid,
+		txt: &str,
+		sess: &Session,
+		vars: Variables,
+	) -> Result<Vec<Response>, Error> {
+		// Parse the SQL query text
+		let ast = syn::parse(txt)?;
+		// Process the AST
+		self.process_in(id, ast, sess, vars).await
+	}
+
+	/// Execute a pre-parsed SQL query within a transaction which spans several requests
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn process_in(
+		&self,
+		id: Uuid,
+		ast: Query,
+		sess: &Session,
+		vars: Variables,
+	) -> Result<Vec<Response>, Error> {
+		self.process_interactive(id, ast, sess, vars, None).await
+	}
+
+	/// Execute a pre-parsed SQL query within a transaction which spans several requests,
+	/// streaming the rows of each `SELECT` statement as in [`Datastore::process_stream`]
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn process_stream_in(
+		&self,
+		id: Uuid,
+		ast: Query,
+		sess: &Session,
+		vars: Variables,
+		rows: Sender<Row>,
+	) -> Result<Vec<Response>, Error> {
+		self.process_interactive(id, ast, sess, vars, Some(rows)).await
+	}
+
+	async fn process_interactive(
+		&self,
+		id: Uuid,
+		ast: Query,
+		sess: &Session,
+		vars: Variables,
+		rows: Option<Sender<Row>>,
+	) -> Result<Vec<Response>, Error> {
+		// Check if the session can execute queries
+		self.check_session(sess).await?;
+		// Fetch the transaction
+		let txn = self.interactive.get(id, sess).await?;
+		// Create a new query executor
+		let mut exe = Executor::new_interactive(self, txn).with_rows(rows);
+		// Process all statements
+		let res = self.process_with(&mut exe, ast, sess, vars).await;
+		// Failed statements prevent the transaction from being committed
+		let failed = match &res {
+			Ok(v) => v.iter().any(|v| v.result.is_err()),
+			Err(_) => true,
+		};
+		self.interactive.update(id, failed, exe.take_notifications());
+		res
+	}
+
+	/// Checks whether a session is able to execute queries
+	pub(crate) async fn check_session(&self, sess: &Session) -> Result<(), Error> {
 		// Check if the session has expired
 		if sess.expired() {
 			return Err(Error::ExpiredSession);
 		}
+		// Check if the session has been revoked
+		if self.revoked(sess).await? {
+			return Err(Error::RevokedSession);
+		}
 		// Check if anonymous actors can execute queries when auth is enabled
 		// TODO(sgirones): Check this as part of the authorisation layer
 		if self.auth_enabled && sess.au.is_anon() && !self.capabilities.allows_guest_access() {
@@ -804,17 +1230,26 @@ impl Datastore {
 			}
 			.into());
 		}
+		Ok(())
+	}
+
+	/// Executes a pre-parsed SQL query with the specified executor
+	async fn process_with(
+		&self,
+		exe: &mut Executor<'_>,
+		ast: Query,
+		sess: &Session,
+		vars: Variables,
+	) -> Result<Vec<Response>, Error> {
 		// Create a new query options
 		let opt = Options::default()
 			.with_id(self.id)
 			.with_ns(sess.ns())
 			.with_db(sess.db())
 			.with_live(sess.live())
-			.with_auth(sess.au.clone())
+			.with_auth(self.resolve_auth(sess).await?)
 			.with_strict(self.strict)
 			.with_auth_enabled(self.auth_enabled);
-		// Create a new query executor
-		let mut exe = Executor::new(self);
 		// Create a default context
 		let mut ctx = MutableContext::from_ds(
 			self.query_timeout,
@@ -829,6 +1264,8 @@ impl Datastore {
 		if let Some(channel) = &self.notification_channel {
 			ctx.add_notifications(Some(&channel.0));
 		}
+		// Setup the audit log
+		ctx.add_audit(self.audit.as_ref());
 		// Start an execution context
 		sess.context(&mut ctx);
 		// Store the query variables
@@ -866,6 +1303,10 @@ impl Datastore {
 		if sess.expired() {
 			return Err(Error::ExpiredSession);
 		}
+		// Check if the session has been revoked
+		if self.revoked(sess).await? {
+			return Err(Error::RevokedSession);
+		}
 		// Check if anonymous actors can compute values when auth is enabled
 		// TODO(sgirones): Check this as part of the authorisation layer
 		if sess.au.is_anon() && self.auth_enabled && !self.capabilities.allows_guest_access() {
@@ -884,7 +1325,7 @@ impl Datastore {
 			.with_ns(sess.ns())
 			.with_db(sess.db())
 			.with_live(sess.live())
-			.with_auth(sess.au.clone())
+			.with_auth(self.resolve_auth(sess).await?)
 			.with_strict(self.strict)
 			.with_auth_enabled(self.auth_enabled);
 		// Create a default context
@@ -899,6 +1340,8 @@ impl Datastore {
 		if let Some(channel) = &self.notification_channel {
 			ctx.add_notifications(Some(&channel.0));
 		}
+		// Setup the audit log
+		ctx.add_audit(self.audit.as_ref());
 		// Start an execution context
 		sess.context(&mut ctx);
 		// Store the query variables
@@ -955,6 +1398,10 @@ impl Datastore {
 		if sess.expired() {
 			return Err(Error::ExpiredSession);
 		}
+		// Check if the session has been revoked
+		if self.revoked(sess).await? {
+			return Err(Error::RevokedSession);
+		}
 		// Create a new memory stack
 		let mut stack = TreeStack::new();
 		// Create a new query options
@@ -963,7 +1410,7 @@ impl Datastore {
 			.with_ns(sess.ns())
 			.with_db(sess.db())
 			.with_live(sess.live())
-			.with_auth(sess.au.clone())
+			.with_auth(self.resolve_auth(sess).await?)
 			.with_strict(self.strict)
 			.with_auth_enabled(self.auth_enabled);
 		// Create a default context
@@ -978,6 +1425,8 @@ impl Datastore {
 		if let Some(channel) = &self.notification_channel {
 			ctx.add_notifications(Some(&channel.0));
 		}
+		// Setup the audit log
+		ctx.add_audit(self.audit.as_ref());
 		// Start an execution context
 		sess.context(&mut ctx);
 		// Store the query variables
@@ -1047,6 +1496,10 @@ impl Datastore {
 		if sess.expired() {
 			return Err(Error::ExpiredSession);
 		}
+		// Check if the session has been revoked
+		if self.revoked(sess).await? {
+			return Err(Error::RevokedSession);
+		}
 		// Retrieve the provided NS and DB
 		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
 		// Create a new readonly transaction
@@ -1062,7 +1515,12 @@ impl Datastore {
 
 	/// Checks the required permissions level for this session
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self, sess))]
-	pub fn check(&self, sess: &Session, action: Action, resource: Resource) -> Result<(), Error> {
+	pub async fn check(
+		&self,
+		sess: &Session,
+		action: Action,
+		resource: Resource,
+	) -> Result<(), Error> {
 		// Check if the session has expired
 		if sess.expired() {
 			return Err(Error::ExpiredSession);
@@ -1070,7 +1528,7 @@ impl Datastore {
 		// Skip auth for Anonymous users if auth is disabled
 		let skip_auth = !self.is_auth_enabled() && sess.au.is_anon();
 		if !skip_auth {
-			sess.au.is_allowed(action, &resource)?;
+			self.resolve_auth(sess).await?.is_allowed(action, &resource)?;
 		}
 		// All ok
 		Ok(())
@@ -1131,4 +1589,122 @@ mod test {
 		assert_eq!(res, Value::Number(Number::Int(2)));
 		Ok(())
 	}
+
+	#[tokio::test]
+	async fn interactive_transaction() -> Result<(), Error> {
+		let ds = Datastore::new("memory").await?;
+		let ses = Session::owner().with_ns("test").with_db("test");
+		// Changes are not visible until the transaction is committed
+		let txn = ds.begin(&ses).await?;
+		let res = ds.execute_in(txn, "CREATE person:tobie", &ses, None).await?;
+		assert!(res[0].result.is_ok());
+		let res = ds.execute_in(txn, "SELECT * FROM person", &ses, None).await?;
+		assert_eq!(res[0].result.as_ref().unwrap(), &syn::value("[{ id: person:tobie }]")?);
+		let res = ds.execute("SELECT * FROM person", &ses, None).await?;
+		assert_eq!(res[0].result.as_ref().unwrap(), &syn::value("[]")?);
+		ds.commit(txn, &ses).await?;
+		let res = ds.execute("SELECT * FROM person", &ses, None).await?;
+		assert_eq!(res[0].result.as_ref().unwrap(), &syn::value("[{ id: person:tobie }]")?);
+		// The transaction can not be used once it has been committed
+		let res = ds.execute_in(txn, "SELECT * FROM person", &ses, None).await;
+		assert!(matches!(res, Err(Error::TxNotFound(_))));
+		// Cancelled transactions discard their changes
+		let txn = ds.begin(&ses).await?;
+		ds.execute_in(txn, "CREATE person:jaime", &ses, None).await?;
+		ds.cancel(txn, &ses).await?;
+		let res = ds.execute("SELECT * FROM person:jaime", &ses, None).await?;
+		assert_eq!(res[0].result.as_ref().unwrap(), &syn::value("[]")?);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn interactive_transaction_failure() -> Result<(), Error> {
+		let ds = Datastore::new("memory").await?;
+		let ses = Session::owner().with_ns("test").with_db("test");
+		// Transaction statements can not be used within the transaction
+		let txn = ds.begin(&ses).await?;
+		let res = ds.execute_in(txn, "COMMIT", &ses, None).await?;
+		assert!(matches!(res[0].result, Err(Error::TxStatementNotAllowed)));
+		ds.cancel(txn, &ses).await?;
+		// A failed statement prevents the transaction from being committed
+		let txn = ds.begin(&ses).await?;
+		ds.execute_in(txn, "CREATE person:tobie", &ses, None).await?;
+		ds.execute_in(txn, "THROW 'failed'", &ses, None).await?;
+		assert!(matches!(ds.commit(txn, &ses).await, Err(Error::TxStatementFailed)));
+		let res = ds.execute("SELECT * FROM person", &ses, None).await?;
+		assert_eq!(res[0].result.as_ref().unwrap(), &syn::value("[]")?);
+		// The transaction can only be used by the actor which began it
+		let txn = ds.begin(&ses).await?;
+		let other = Session::viewer().with_ns("test").with_db("test");
+		let res = ds.execute_in(txn, "SELECT * FROM person", &other, None).await;
+		assert!(matches!(res, Err(Error::TxNotFound(_))));
+		assert!(matches!(ds.commit(txn, &other).await, Err(Error::TxNotFound(_))));
+		ds.commit(txn, &ses).await?;
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn interactive_transaction_limit() -> Result<(), Error> {
+		let ds = Datastore::new("memory").await?;
+		let mut ses = Session::owner().with_ns("test").with_db("test");
+		ses.id = Some("connection".to_string());
+		// A connection can only have a limited number of transactions open
+		for _ in 0..*crate::cnf::MAX_INTERACTIVE_TRANSACTIONS {
+			let txn = ds.transaction(Read, Optimistic).await?;
+			assert!(ds.interactive.insert(txn, &ses).await.is_ok());
+		}
+		let txn = ds.transaction(Read, Optimistic).await?;
+		assert!(matches!(ds.interactive.insert(txn, &ses).await, Err(Error::TxLimit(_))));
+		// Other connections are not affected by the limit
+		let mut other = ses.clone();
+		other.id = Some("other".to_string());
+		let txn = ds.transaction(Read, Optimistic).await?;
+		let id = ds.interactive.insert(txn, &other).await?;
+		// Closing the connection cancels its open transactions
+		ds.close_transactions("connection").await;
+		let txn = ds.transaction(Read, Optimistic).await?;
+		assert!(ds.interactive.insert(txn, &ses).await.is_ok());
+		assert!(ds.interactive.get(id, &other).await.is_ok());
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn stream_rows() -> Result<(), Error> {
+		let ds = Datastore::new("memory").await?;
+		let ses = Session::owner().with_ns("test").with_db("test");
+		ds.execute("CREATE |person:1..10| SET age = record::id(id)", &ses, None).await?;
+		// Collect the rows which are streamed from each statement
+		let stream = |sql: &str| {
+			let ast = syn::parse(sql).unwrap();
+			let (tx, rx) = channel::bounded(2);
+			let ds = &ds;
+			let ses = &ses;
+			async move {
+				let query = ds.process_stream(ast, ses, None, tx);
+				let rows = async {
+					let mut rows = vec![];
+					while let Ok(row) = rx.recv().await {
+						rows.push((row.query, row.value));
+					}
+					rows
+				};
+				let (res, rows) = futures::join!(query, rows);
+				(res.unwrap(), rows)
+			}
+		};
+		// Rows are streamed as they are produced, and the results are empty
+		let (res, rows) = stream("SELECT VALUE age FROM person START 2 LIMIT 3").await;
+		assert_eq!(res[0].result.as_ref().unwrap(), &Value::None);
+		let expected: Vec<_> = (3..=5).map(|v| (0, Value::from(v))).collect();
+		assert_eq!(rows, expected);
+		// Rows which need ordering are streamed once the statement completes
+		let (_, rows) =
+			stream("RETURN 1; SELECT VALUE age FROM person ORDER BY age DESC LIMIT 2").await;
+		assert_eq!(rows, vec![(1, Value::from(10)), (1, Value::from(9))]);
+		// Other statements return their results as normal
+		let (res, rows) = stream("SELECT VALUE age FROM ONLY person:1; RETURN 2").await;
+		assert_eq!(rows, vec![(0, Value::from(1))]);
+		assert_eq!(res[1].result.as_ref().unwrap(), &Value::from(2));
+		Ok(())
+	}
 }
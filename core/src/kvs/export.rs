@@ -2,6 +2,7 @@ use super::Transaction;
 use crate::cnf::EXPORT_BATCH_SIZE;
 use crate::err::Error;
 use crate::sql::paths::EDGE;
+use crate::sql::paths::ID;
 use crate::sql::paths::IN;
 use crate::sql::paths::OUT;
 use crate::sql::Value;
@@ -19,6 +20,31 @@ impl Transaction {
 			chn.send(bytes!("OPTION IMPORT;")).await?;
 			chn.send(bytes!("")).await?;
 		}
+		// Output ROLES
+		{
+			let drs = self.all_db_roles(ns, db).await?;
+			if !drs.is_empty() {
+				chn.send(bytes!("-- ------------------------------")).await?;
+				chn.send(bytes!("-- ROLES")).await?;
+				chn.send(bytes!("-- ------------------------------")).await?;
+				chn.send(bytes!("")).await?;
+				for rl in drs.iter() {
+					chn.send(bytes!(format!("{rl};"))).await?;
+				}
+				chn.send(bytes!("")).await?;
+			}
+		}
+		// Output SIGNIN POLICY
+		{
+			if let Some(sp) = crate::iam::lockout::policy(self, Some(ns), Some(db)).await? {
+				chn.send(bytes!("-- ------------------------------")).await?;
+				chn.send(bytes!("-- SIGNIN POLICY")).await?;
+				chn.send(bytes!("-- ------------------------------")).await?;
+				chn.send(bytes!("")).await?;
+				chn.send(bytes!(format!("{sp};"))).await?;
+				chn.send(bytes!("")).await?;
+			}
+		}
 		// Output USERS
 		{
 			let dus = self.all_db_users(ns, db).await?;
@@ -89,6 +115,20 @@ impl Transaction {
 				chn.send(bytes!("")).await?;
 			}
 		}
+		// Output POLICIES
+		{
+			let dps = self.all_db_policies(ns, db).await?;
+			if !dps.is_empty() {
+				chn.send(bytes!("-- ------------------------------")).await?;
+				chn.send(bytes!("-- POLICIES")).await?;
+				chn.send(bytes!("-- ------------------------------")).await?;
+				chn.send(bytes!("")).await?;
+				for pl in dps.iter() {
+					chn.send(bytes!(format!("{pl};"))).await?;
+				}
+				chn.send(bytes!("")).await?;
+			}
+		}
 		// Output TABLES
 		{
 			let tbs = self.all_tb(ns, db).await?;
@@ -138,6 +178,10 @@ impl Transaction {
 					chn.send(bytes!(format!("-- TABLE DATA: {}", tb.name))).await?;
 					chn.send(bytes!("-- ------------------------------")).await?;
 					chn.send(bytes!("")).await?;
+					// Encrypted fields are exported in clear, as the secrets of their
+					// keys are not exported, and are encrypted again when imported
+					let fds = self.all_tb_fields(ns, db, &tb.name).await?;
+					let efs: Vec<_> = fds.iter().filter(|fd| fd.encrypted.is_some()).collect();
 					// Fetch records
 					let beg = crate::key::thing::prefix(ns, db, &tb.name);
 					let end = crate::key::thing::suffix(ns, db, &tb.name);
@@ -154,7 +198,13 @@ impl Transaction {
 						// Categorize the record types
 						for (_, v) in batch.values.into_iter() {
 							// Parse the key and the value
-							let v: Value = (&v).into();
+							let mut v: Value = (&v).into();
+							// Decrypt the values of the encrypted fields
+							if let Value::Thing(rid) = v.pick(&*ID) {
+								for fd in efs.iter() {
+									fd.decrypt_record(&rid, &mut v)?;
+								}
+							}
 							// Check if this is a graph edge
 							match (v.pick(&*EDGE), v.pick(&*IN), v.pick(&*OUT)) {
 								// This is a graph edge record
@@ -4,8 +4,9 @@ use crate::dbs::Options;
 use crate::doc::{CursorDoc, Document};
 use crate::err::Error;
 use crate::idx::index::IndexOperation;
-use crate::key::index::ia::Ia;
-use crate::key::index::ip::Ip;
+use crate::key::index::ia::{self, Ia};
+use crate::key::index::ib::Ib;
+use crate::key::index::ip::{self, Ip};
 use crate::key::thing;
 use crate::kvs::ds::TransactionFactory;
 use crate::kvs::LockType::Optimistic;
@@ -23,14 +24,18 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::task;
 use tokio::task::JoinHandle;
+use uuid::Uuid;
 
-#[derive(Clone)]
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub(crate) enum BuildingStatus {
 	Started,
 	InitialIndexing(usize),
 	UpdatesIndexing(usize),
-	Error(Arc<Error>),
+	Error(String),
 	Built,
+	Cancelled,
 }
 
 pub(crate) enum ConsumeResult {
@@ -45,8 +50,9 @@ impl BuildingStatus {
 		matches!(self, Self::Error(_))
 	}
 
-	fn is_built(&self) -> bool {
-		matches!(self, Self::Built)
+	/// Returns true if the building is neither terminated nor interrupted
+	fn is_running(&self) -> bool {
+		matches!(self, Self::Started | Self::InitialIndexing(_) | Self::UpdatesIndexing(_))
 	}
 }
 
@@ -64,16 +70,87 @@ impl From<BuildingStatus> for Value {
 				"updates"
 			}
 			BuildingStatus::Error(error) => {
-				o.insert("error".to_string(), error.to_string().into());
+				o.insert("error".to_string(), error.into());
 				"error"
 			}
 			BuildingStatus::Built => "built",
+			BuildingStatus::Cancelled => "cancelled",
 		};
 		o.insert("status".to_string(), s.into());
 		o.into()
 	}
 }
 
+/// The state of an index building.
+/// It is persisted, so the progress is visible from every node,
+/// and the building can be resumed after a restart.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Serialize, Deserialize, Store)]
+#[non_exhaustive]
+pub(crate) struct BuildingState {
+	status: BuildingStatus,
+	/// The node running the building
+	node: Uuid,
+	/// The number of appended records waiting to be indexed
+	pending: u32,
+	/// The key from which the initial indexing resumes
+	next: Option<Key>,
+}
+
+impl BuildingState {
+	fn new(status: BuildingStatus, node: Uuid, pending: u32, next: Option<Key>) -> Self {
+		Self {
+			status,
+			node,
+			pending,
+			next,
+		}
+	}
+
+	pub(crate) fn is_cancelled(&self) -> bool {
+		matches!(self.status, BuildingStatus::Cancelled)
+	}
+
+	/// Returns false if the building has been cancelled or has failed, the index is then incomplete
+	pub(crate) fn is_usable(&self) -> bool {
+		!matches!(self.status, BuildingStatus::Cancelled | BuildingStatus::Error(_))
+	}
+
+	pub(crate) async fn get(
+		tx: &Transaction,
+		ns: &str,
+		db: &str,
+		ix: &DefineIndexStatement,
+	) -> Result<Option<Self>, Error> {
+		let key = Ib::new(ns, db, &ix.what, &ix.name);
+		Ok(tx.get(key, None).await?.map(|v| v.into()))
+	}
+
+	async fn set(
+		self,
+		tx: &Transaction,
+		ns: &str,
+		db: &str,
+		ix: &DefineIndexStatement,
+	) -> Result<(), Error> {
+		let key = Ib::new(ns, db, &ix.what, &ix.name);
+		tx.set(key, self, None).await
+	}
+}
+
+impl From<BuildingState> for Value {
+	fn from(st: BuildingState) -> Self {
+		let running = st.status.is_running();
+		let mut v = Value::from(st.status);
+		if running {
+			if let Value::Object(o) = &mut v {
+				o.insert("pending".to_string(), st.pending.into());
+			}
+		}
+		v
+	}
+}
+
 type IndexBuilding = (Arc<Building>, JoinHandle<()>);
 
 #[derive(Clone)]
@@ -90,36 +167,151 @@ impl IndexBuilder {
 		}
 	}
 
-	pub(crate) fn build(
+	pub(crate) async fn build(
+		&self,
+		ctx: &Context,
+		opt: Options,
+		ix: Arc<DefineIndexStatement>,
+	) -> Result<(), Error> {
+		let tx = ctx.tx();
+		// A cancelled building may not have observed its committed cancellation yet
+		if BuildingState::get(&tx, opt.ns()?, opt.db()?, &ix)
+			.await?
+			.is_some_and(|st| st.is_cancelled())
+		{
+			if let Some((_, (b, jh))) = self.indexes.remove(&ix) {
+				jh.abort();
+				b.set_status(BuildingStatus::Cancelled).await;
+			}
+		}
+		// The initial state is persisted with the definition of the index
+		let state = BuildingState::new(BuildingStatus::Started, opt.id()?, 0, None);
+		state.set(&tx, opt.ns()?, opt.db()?, &ix).await?;
+		self.start(ctx, opt, ix, false)
+	}
+
+	/// Takes over an interrupted building, if the node which was running it is not active anymore.
+	/// Returns true if the building has been assigned to the given node and should be resumed.
+	pub(crate) async fn claim(
+		&self,
+		tx: &Transaction,
+		ns: &str,
+		db: &str,
+		ix: &DefineIndexStatement,
+		node: Uuid,
+	) -> Result<bool, Error> {
+		// Is the building already running on this node?
+		if self.indexes.get(ix).is_some_and(|e| !e.value().1.is_finished()) {
+			return Ok(false);
+		}
+		let Some(mut st) = BuildingState::get(tx, ns, db, ix).await? else {
+			return Ok(false);
+		};
+		if !st.status.is_running() {
+			return Ok(false);
+		}
+		if st.node != node {
+			let nds = tx.all_nodes().await?;
+			if nds.iter().any(|nd| nd.id == st.node && nd.is_active()) {
+				// The building is still running on another node
+				return Ok(false);
+			}
+		}
+		st.node = node;
+		st.set(tx, ns, db, ix).await?;
+		Ok(true)
+	}
+
+	/// Resumes an interrupted building from its persisted state
+	pub(crate) fn resume(
 		&self,
 		ctx: &Context,
 		opt: Options,
 		ix: Arc<DefineIndexStatement>,
+	) -> Result<(), Error> {
+		self.start(ctx, opt, ix, true)
+	}
+
+	fn start(
+		&self,
+		ctx: &Context,
+		opt: Options,
+		ix: Arc<DefineIndexStatement>,
+		resume: bool,
 	) -> Result<(), Error> {
 		match self.indexes.entry(ix) {
-			Entry::Occupied(e) => {
+			Entry::Occupied(mut e) => {
 				// If the building is currently running we return error
 				if !e.get().1.is_finished() {
 					return Err(Error::IndexAlreadyBuilding {
 						index: e.key().name.to_string(),
 					});
 				}
+				// The previous building is over, we can start a new one
+				let building = Self::spawn(ctx, &self.tf, opt, e.key().clone(), resume)?;
+				e.insert(building);
 			}
 			Entry::Vacant(e) => {
 				// No index is currently building, we can start building it
-				let building = Arc::new(Building::new(ctx, self.tf.clone(), opt, e.key().clone())?);
-				let b = building.clone();
-				let jh = task::spawn(async move {
-					if let Err(err) = b.compute().await {
-						b.set_status(BuildingStatus::Error(err.into())).await;
-					}
-				});
-				e.insert((building, jh));
+				let building = Self::spawn(ctx, &self.tf, opt, e.key().clone(), resume)?;
+				e.insert(building);
 			}
 		}
 		Ok(())
 	}
 
+	fn spawn(
+		ctx: &Context,
+		tf: &TransactionFactory,
+		opt: Options,
+		ix: Arc<DefineIndexStatement>,
+		resume: bool,
+	) -> Result<IndexBuilding, Error> {
+		let building = Arc::new(Building::new(ctx, tf.clone(), opt, ix, resume)?);
+		let b = building.clone();
+		let jh = task::spawn(async move {
+			if let Err(err) = b.compute().await {
+				b.set_error(err).await;
+			}
+		});
+		Ok((building, jh))
+	}
+
+	/// Stops the building of an index which is being removed
+	pub(crate) fn remove_index(&self, ix: &DefineIndexStatement) {
+		if let Some((_, (_, jh))) = self.indexes.remove(ix) {
+			jh.abort();
+		}
+	}
+
+	/// Cancels a running building.
+	/// The cancellation is persisted, so the building, whichever node is running it,
+	/// stops at its next batch once the transaction is committed.
+	/// The index is then incomplete, and is not used by the query planner.
+	/// Returns false if the index is not being built.
+	pub(crate) async fn cancel(
+		&self,
+		tx: &Transaction,
+		opt: &Options,
+		ix: &DefineIndexStatement,
+	) -> Result<bool, Error> {
+		let (ns, db) = (opt.ns()?, opt.db()?);
+		let running = match BuildingState::get(tx, ns, db, ix).await? {
+			Some(st) => st.status.is_running(),
+			None => false,
+		};
+		if !running {
+			return Ok(false);
+		}
+		let state = BuildingState::new(BuildingStatus::Cancelled, opt.id()?, 0, None);
+		state.set(tx, ns, db, ix).await?;
+		// The appended records will not be indexed
+		let (tb, ix) = (&ix.what, &ix.name);
+		tx.delr(ia::prefix(ns, db, tb, ix)..ia::suffix(ns, db, tb, ix)).await?;
+		tx.delr(ip::prefix(ns, db, tb, ix)..ip::suffix(ns, db, tb, ix)).await?;
+		Ok(true)
+	}
+
 	pub(crate) async fn consume(
 		&self,
 		ctx: &Context,
@@ -135,9 +327,9 @@ impl IndexBuilder {
 		Ok(ConsumeResult::Ignored(old_values, new_values))
 	}
 
-	pub(crate) async fn get_status(&self, ix: &DefineIndexStatement) -> Option<BuildingStatus> {
+	pub(crate) async fn get_status(&self, ix: &DefineIndexStatement) -> Option<BuildingState> {
 		if let Some(a) = self.indexes.get(ix) {
-			Some(a.value().0.status.lock().await.clone())
+			Some(a.value().0.state().await)
 		} else {
 			None
 		}
@@ -177,6 +369,10 @@ impl QueueSequences {
 		i
 	}
 
+	fn len(&self) -> u32 {
+		self.next - self.to_index
+	}
+
 	fn clear(&mut self) {
 		self.to_index = 0;
 		self.next = 0;
@@ -202,6 +398,8 @@ struct Building {
 	status: Arc<Mutex<BuildingStatus>>,
 	// Should be stored on a temporary table
 	queue: Arc<Mutex<QueueSequences>>,
+	/// True if the building restarts from a persisted state
+	resume: bool,
 }
 
 impl Building {
@@ -210,6 +408,7 @@ impl Building {
 		tf: TransactionFactory,
 		opt: Options,
 		ix: Arc<DefineIndexStatement>,
+		resume: bool,
 	) -> Result<Self, Error> {
 		Ok(Self {
 			ctx: MutableContext::new_concurrent(ctx).freeze(),
@@ -219,6 +418,7 @@ impl Building {
 			ix,
 			status: Arc::new(Mutex::new(BuildingStatus::Started)),
 			queue: Default::default(),
+			resume,
 		})
 	}
 
@@ -230,6 +430,50 @@ impl Building {
 		}
 	}
 
+	async fn set_error(&self, err: Error) {
+		let error = err.to_string();
+		self.set_status(BuildingStatus::Error(error.clone())).await;
+		// Persist the error, so it is visible from any node
+		if let Err(e) = self.persist_error(error).await {
+			error!("Failed to persist the error of the index building: {e}");
+		}
+	}
+
+	async fn persist_error(&self, error: String) -> Result<(), Error> {
+		let tx = self.tf.transaction(TransactionType::Write, Optimistic).await?;
+		// A cancellation prevails over the error
+		if catch!(tx, self.is_cancelled(&tx).await) {
+			return tx.cancel().await;
+		}
+		let pending = self.queue.lock().await.len();
+		catch!(tx, self.set_state(&tx, BuildingStatus::Error(error), pending, None).await);
+		tx.commit().await
+	}
+
+	/// Returns the current state of the building
+	async fn state(&self) -> BuildingState {
+		let pending = self.queue.lock().await.len();
+		let status = self.status.lock().await.clone();
+		BuildingState::new(status, self.opt.id().unwrap_or_default(), pending, None)
+	}
+
+	async fn set_state(
+		&self,
+		tx: &Transaction,
+		status: BuildingStatus,
+		pending: u32,
+		next: Option<Key>,
+	) -> Result<(), Error> {
+		let state = BuildingState::new(status, self.opt.id()?, pending, next);
+		state.set(tx, self.opt.ns()?, self.opt.db()?, &self.ix).await
+	}
+
+	/// Checks if the building has been cancelled, possibly by another node
+	async fn is_cancelled(&self, tx: &Transaction) -> Result<bool, Error> {
+		let st = BuildingState::get(tx, self.opt.ns()?, self.opt.db()?, &self.ix).await?;
+		Ok(st.is_some_and(|st| st.is_cancelled()))
+	}
+
 	async fn maybe_consume(
 		&self,
 		ctx: &Context,
@@ -239,12 +483,11 @@ impl Building {
 	) -> Result<ConsumeResult, Error> {
 		let mut queue = self.queue.lock().await;
 		// Now that the queue is locked, we have the possibility to assess if the asynchronous build is done.
-		if queue.is_empty() {
-			// If the appending queue is empty and the index is built...
-			if self.status.lock().await.is_built() {
-				// ... we return the values back, so the document can be updated the usual way
-				return Ok(ConsumeResult::Ignored(old_values, new_values));
-			}
+		// The queue is cleared when the index is built.
+		// If the index is built, or if the building has been interrupted...
+		if !self.status.lock().await.is_running() {
+			// ... we return the values back, so the document can be updated the usual way
+			return Ok(ConsumeResult::Ignored(old_values, new_values));
 		}
 
 		let tx = ctx.tx();
@@ -290,16 +533,58 @@ impl Building {
 		Ok(ctx.freeze())
 	}
 
+	/// Restores the progress of an interrupted building.
+	/// Returns the range of the initial indexing still to be done, and the count of indexed records.
+	async fn restore(&self, end: &Key) -> Result<(Option<Range<Key>>, usize), Error> {
+		let ns = self.opt.ns()?;
+		let db = self.opt.db()?;
+		let tx = self.new_read_tx().await?;
+		let st = catch!(tx, BuildingState::get(&tx, ns, db, &self.ix).await);
+		// The appended records are stored with contiguous sequence numbers
+		let (tb, ix) = (&self.ix.what, &self.ix.name);
+		let mut next = Some(ia::prefix(ns, db, tb, ix)..ia::suffix(ns, db, tb, ix));
+		let mut bounds: Option<(u32, u32)> = None;
+		while let Some(rng) = next {
+			let batch = catch!(tx, tx.batch(rng, *NORMAL_FETCH_SIZE, false).await);
+			next = batch.next;
+			for (k, _) in batch.values.iter() {
+				let ia = catch!(tx, Ia::decode(k));
+				bounds = Some(bounds.map_or((ia.i, ia.i), |(first, _)| (first, ia.i)));
+			}
+		}
+		tx.cancel().await?;
+		if let Some((first, last)) = bounds {
+			let mut queue = self.queue.lock().await;
+			queue.to_index = first;
+			queue.next = last + 1;
+		}
+		let Some(st) = st else {
+			return Ok((Some(thing::prefix(ns, db, &self.tb)..end.clone()), 0));
+		};
+		Ok(match st.status {
+			BuildingStatus::InitialIndexing(count) => {
+				self.set_status(BuildingStatus::InitialIndexing(count)).await;
+				(st.next.map(|beg| beg..end.clone()), count)
+			}
+			BuildingStatus::UpdatesIndexing(count) => (None, count),
+			_ => (Some(thing::prefix(ns, db, &self.tb)..end.clone()), 0),
+		})
+	}
+
 	async fn compute(&self) -> Result<(), Error> {
-		// Set the initial status
-		self.set_status(BuildingStatus::InitialIndexing(0)).await;
 		// First iteration, we index every keys
 		let ns = self.opt.ns()?;
 		let db = self.opt.db()?;
 		let beg = thing::prefix(ns, db, &self.tb);
 		let end = thing::suffix(ns, db, &self.tb);
-		let mut next = Some(beg..end);
-		let mut count = 0;
+		let (mut next, mut count) = if self.resume {
+			// Restart from the last indexed batch
+			self.restore(&end).await?
+		} else {
+			// Set the initial status
+			self.set_status(BuildingStatus::InitialIndexing(0)).await;
+			(Some(beg..end), 0)
+		};
 		while let Some(rng) = next {
 			// Get the next batch of records
 			let tx = self.new_read_tx().await?;
@@ -316,17 +601,34 @@ impl Building {
 			// Create a new context with a write transaction
 			let ctx = self.new_write_tx_ctx().await?;
 			let tx = ctx.tx();
+			// Stop if the building has been cancelled
+			if catch!(tx, self.is_cancelled(&tx).await) {
+				let _queue = self.queue.lock().await;
+				return self.cancelled(&tx).await;
+			}
 			// Index the batch
 			catch!(tx, self.index_initial_batch(&ctx, &tx, batch.values, &mut count).await);
+			// Persist the progress, with the key the next batch starts from
+			let pending = self.queue.lock().await.len();
+			let status = BuildingStatus::InitialIndexing(count);
+			let from = next.as_ref().map(|r| r.start.clone());
+			catch!(tx, self.set_state(&tx, status, pending, from).await);
 			tx.commit().await?;
 		}
 		// Second iteration, we index/remove any records that has been added or removed since the initial indexing
-		self.set_status(BuildingStatus::UpdatesIndexing(0)).await;
+		self.set_status(BuildingStatus::UpdatesIndexing(count)).await;
 		loop {
 			let mut queue = self.queue.lock().await;
 			if queue.is_empty() {
 				// If the batch is empty, we are done.
 				// Due to the lock on self.appended, we know that no external process can add an item to the queue.
+				let ctx = self.new_write_tx_ctx().await?;
+				let tx = ctx.tx();
+				if catch!(tx, self.is_cancelled(&tx).await) {
+					return self.cancelled(&tx).await;
+				}
+				catch!(tx, self.set_state(&tx, BuildingStatus::Built, 0, None).await);
+				tx.commit().await?;
 				self.set_status(BuildingStatus::Built).await;
 				// This is here to be sure the lock on back is not released early
 				queue.clear();
@@ -341,13 +643,32 @@ impl Building {
 			// Create a new context with a write transaction
 			let ctx = self.new_write_tx_ctx().await?;
 			let tx = ctx.tx();
+			// Stop if the building has been cancelled
+			if catch!(tx, self.is_cancelled(&tx).await) {
+				return self.cancelled(&tx).await;
+			}
 			catch!(tx, self.index_appending_range(&ctx, &tx, range, &mut count).await);
+			// Persist the progress
+			let status = BuildingStatus::UpdatesIndexing(count);
+			let pending = queue.next - next_to_index;
+			catch!(tx, self.set_state(&tx, status, pending, None).await);
 			tx.commit().await?;
 			queue.set_to_index(next_to_index);
 		}
 		Ok(())
 	}
 
+	/// Terminates a cancelled building.
+	/// The queue must be locked, so no document is enqueued meanwhile.
+	async fn cancelled(&self, tx: &Transaction) -> Result<(), Error> {
+		self.set_status(BuildingStatus::Cancelled).await;
+		// Remove the appendings enqueued before the cancellation was observed
+		let (ns, db) = (self.opt.ns()?, self.opt.db()?);
+		let (tb, ix) = (&self.ix.what, &self.ix.name);
+		catch!(tx, tx.delr(ia::prefix(ns, db, tb, ix)..ia::suffix(ns, db, tb, ix)).await);
+		catch!(tx, tx.delr(ip::prefix(ns, db, tb, ix)..ip::suffix(ns, db, tb, ix)).await);
+		tx.commit().await
+	}
 	async fn index_initial_batch(
 		&self,
 		ctx: &Context,
@@ -378,7 +699,9 @@ impl Building {
 				let a: Appending = v.into();
 				opt_values = a.old_values;
 			} else {
-				// Otherwise, we normally proceed to the indexing
+				// Otherwise, we normally proceed to the indexing, with any encrypted fields in clear
+				let val =
+					Document::decrypt_fields(ctx, &self.opt, &rid, &val).await?.unwrap_or(val);
 				let doc = CursorDoc::new(Some(rid.clone()), None, val);
 				opt_values = stack
 					.enter(|stk| Document::build_opt_values(stk, ctx, &self.opt, &self.ix, &doc))
@@ -0,0 +1,153 @@
+//! Transactions which span several requests, such as consecutive RPC calls.
+//!
+//! Each transaction is identified by a random id, and can only be used by the actor which
+//! began it. Transactions which have not been used for longer than the configured timeout
+//! are cancelled, so that abandoned transactions do not hold on to resources indefinitely.
+//! Each connection, or each actor when there is no connection, can only have a limited
+//! number of transactions open, and these are cancelled when the connection is closed.
+
+use crate::cnf::{INTERACTIVE_TRANSACTION_TIMEOUT, MAX_INTERACTIVE_TRANSACTIONS};
+use crate::dbs::{Notification, Session};
+use crate::err::Error;
+use crate::iam::Auth;
+use crate::kvs::Transaction;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+use trice::Instant;
+use uuid::Uuid;
+
+/// A transaction which has been begun, but not yet committed or cancelled
+pub(crate) struct Handle {
+	pub(crate) txn: Arc<Transaction>,
+	// The actor which began the transaction
+	auth: Arc<Auth>,
+	// The connection which began the transaction
+	conn: Option<String>,
+	// When the transaction was last used
+	used: Instant,
+	// Whether a statement within the transaction has failed
+	pub(crate) failed: bool,
+	// The live query notifications which are sent once the transaction is committed
+	pub(crate) notifications: Vec<Notification>,
+}
+
+impl Handle {
+	fn is_expired(&self) -> bool {
+		self.used.elapsed() > Duration::from_secs(*INTERACTIVE_TRANSACTION_TIMEOUT)
+	}
+
+	/// Whether the transaction was begun on the connection of the session, or by
+	/// the actor of the session when the session does not belong to a connection
+	fn is_owned_by(&self, sess: &Session) -> bool {
+		match &sess.id {
+			Some(_) => self.conn == sess.id,
+			None => self.conn.is_none() && self.auth == sess.au,
+		}
+	}
+}
+
+/// The transactions spanning several requests which are running on this datastore
+#[derive(Default)]
+pub(crate) struct Interactive {
+	handles: Mutex<HashMap<Uuid, Handle>>,
+}
+
+impl Interactive {
+	/// Stores a new transaction for the actor of the session, returning its id
+	pub(crate) async fn insert(&self, txn: Transaction, sess: &Session) -> Result<Uuid, Error> {
+		let id = Uuid::new_v4();
+		let open = {
+			let mut handles = self.handles.lock().unwrap_or_else(PoisonError::into_inner);
+			// Each connection can only have a limited number of transactions open
+			let open = handles.values().filter(|v| v.is_owned_by(sess) && !v.is_expired()).count();
+			if open < *MAX_INTERACTIVE_TRANSACTIONS {
+				let handle = Handle {
+					txn: txn.enclose(),
+					auth: sess.au.clone(),
+					conn: sess.id.clone(),
+					used: Instant::now(),
+					failed: false,
+					notifications: vec![],
+				};
+				handles.insert(id, handle);
+				return Ok(id);
+			}
+			open
+		};
+		trace!("Cancelling a transaction over the limit of {open} open transactions");
+		txn.cancel().await?;
+		Err(Error::TxLimit(*MAX_INTERACTIVE_TRANSACTIONS))
+	}
+
+	/// Fetches a transaction to run statements within, if it was begun by the actor of the session
+	pub(crate) async fn get(&self, id: Uuid, sess: &Session) -> Result<Arc<Transaction>, Error> {
+		let expired = {
+			let mut handles = self.handles.lock().unwrap_or_else(PoisonError::into_inner);
+			match handles.get_mut(&id) {
+				Some(v) if v.auth != sess.au => None,
+				Some(v) if !v.is_expired() => {
+					v.used = Instant::now();
+					return Ok(v.txn.clone());
+				}
+				Some(_) => handles.remove(&id),
+				None => None,
+			}
+		};
+		// Cancel the transaction if it has timed out
+		if let Some(v) = expired {
+			let _ = v.txn.cancel().await;
+		}
+		Err(Error::TxNotFound(id.to_string()))
+	}
+
+	/// Records the outcome of running statements within a transaction
+	pub(crate) fn update(&self, id: Uuid, failed: bool, notifications: Vec<Notification>) {
+		let mut handles = self.handles.lock().unwrap_or_else(PoisonError::into_inner);
+		if let Some(v) = handles.get_mut(&id) {
+			v.used = Instant::now();
+			v.failed |= failed;
+			v.notifications.extend(notifications);
+		}
+	}
+
+	/// Removes a transaction so that it can be committed or cancelled
+	pub(crate) fn remove(&self, id: Uuid, sess: &Session) -> Result<Handle, Error> {
+		let mut handles = self.handles.lock().unwrap_or_else(PoisonError::into_inner);
+		match handles.get(&id) {
+			Some(v) if v.auth == sess.au && !v.is_expired() => Ok(handles.remove(&id).unwrap()),
+			_ => Err(Error::TxNotFound(id.to_string())),
+		}
+	}
+
+	/// Cancels the transactions which were begun on a connection, once it is closed
+	pub(crate) async fn close(&self, conn: &str) {
+		let closed: Vec<Handle> = {
+			let mut handles = self.handles.lock().unwrap_or_else(PoisonError::into_inner);
+			let ids: Vec<Uuid> = handles
+				.iter()
+				.filter(|(_, v)| v.conn.as_deref() == Some(conn))
+				.map(|(k, _)| *k)
+				.collect();
+			ids.iter().filter_map(|k| handles.remove(k)).collect()
+		};
+		for v in closed {
+			trace!("Cancelling the transaction of a closed connection");
+			let _ = v.txn.cancel().await;
+		}
+	}
+
+	/// Cancels the transactions which have not been used within the timeout
+	pub(crate) async fn expire(&self) {
+		let expired: Vec<Handle> = {
+			let mut handles = self.handles.lock().unwrap_or_else(PoisonError::into_inner);
+			let ids: Vec<Uuid> =
+				handles.iter().filter(|(_, v)| v.is_expired()).map(|(k, _)| *k).collect();
+			ids.iter().filter_map(|k| handles.remove(k)).collect()
+		};
+		for v in expired {
+			trace!("Cancelling abandoned transaction");
+			let _ = v.txn.cancel().await;
+		}
+	}
+}
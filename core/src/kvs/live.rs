@@ -1,3 +1,7 @@
+use crate::err::Error;
+use crate::kvs::Transaction;
+use crate::sql::paths::GR;
+use crate::sql::Value;
 use derive::Store;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
@@ -17,3 +21,28 @@ pub struct Live {
 	/// The table in which this LIVE query exists
 	pub tb: String,
 }
+
+/// Deletes the live queries of the sessions which were established with
+/// an access grant, so that revoking the grant also ends these queries
+pub(crate) async fn delete_grant_queries(
+	txn: &Transaction,
+	ns: &str,
+	db: &str,
+	gr: &str,
+) -> Result<(), Error> {
+	let gr = Value::from(gr);
+	// Loop over all tables in the database
+	for tb in txn.all_tb(ns, db).await?.iter() {
+		// Loop over the table live queries
+		for lv in txn.all_tb_lives(ns, db, &tb.name).await?.iter() {
+			// Check that the query was started by a session with this grant
+			if lv.session.as_ref().is_some_and(|v| v.pick(GR.as_ref()) == gr) {
+				// Delete the node live query
+				txn.del(crate::key::node::lq::new(lv.node.0, lv.id.0)).await?;
+				// Delete the table live query
+				txn.del(crate::key::table::lq::new(ns, db, &tb.name, lv.id.0)).await?;
+			}
+		}
+	}
+	Ok(())
+}
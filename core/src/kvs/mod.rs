@@ -16,8 +16,10 @@ mod api;
 mod batch;
 mod cache;
 mod clock;
+mod crypto;
 mod ds;
 mod export;
+mod interactive;
 mod live;
 mod node;
 mod scanner;
@@ -40,6 +42,7 @@ mod index;
 #[cfg(test)]
 mod tests;
 
+pub use self::crypto::{EncryptionKey, Keyring};
 pub use self::ds::*;
 #[cfg(not(target_arch = "wasm32"))]
 pub(crate) use self::index::*;
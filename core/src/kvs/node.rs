@@ -1,11 +1,14 @@
 use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::dbs::node::Node;
 use crate::err::Error;
+use crate::iam::lockout::Failures;
 use crate::kvs::Datastore;
 use crate::kvs::Live;
 use crate::kvs::LockType::*;
 use crate::kvs::TransactionType::*;
 use crate::sql::statements::LiveStatement;
+use crate::sql::{AccessType, Datetime};
+use chrono::Utc;
 use std::time::Duration;
 
 const TARGET: &str = "surrealdb::core::kvs::node";
@@ -185,7 +188,9 @@ impl Datastore {
 	/// This function clears up all data which might have been missed from
 	/// previous cleanup runs, or when previous runs failed. This function
 	/// currently deletes all live queries, for nodes which no longer exist
-	/// in the cluster, from all namespaces, databases, and tables. It uses
+	/// in the cluster, from all namespaces, databases, and tables, along
+	/// with the refresh grants which were revoked or have expired, and the
+	/// failed signin attempts which were forgotten. It uses
 	/// a number of transactions in order to prevent failure of large or
 	/// long-running transactions on distributed storage engines.
 	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::node", skip(self))]
@@ -199,6 +204,19 @@ impl Datastore {
 			// Filter the archived nodes
 			nds.iter().filter_map(Node::archived).collect::<Vec<_>>()
 		};
+		// Purge the failed signin attempts which were forgotten
+		{
+			let txn = self.transaction(Write, Optimistic).await?;
+			let now = Utc::now();
+			let beg = crate::key::root::lo::prefix();
+			let end = crate::key::root::lo::suffix();
+			for (k, v) in catch!(txn, txn.getr(beg..end).await) {
+				if Failures::from(v).expired(now) {
+					catch!(txn, txn.del(k).await);
+				}
+			}
+			txn.commit().await?;
+		}
 		// Fetch all namespaces
 		let nss = {
 			let txn = self.transaction(Read, Optimistic).await?;
@@ -217,6 +235,32 @@ impl Datastore {
 			for db in dbs.iter() {
 				// Log the namespace
 				trace!(target: TARGET, "Garbage collecting data in database {}/{}", ns.name, db.name);
+				// Purge the refresh grants which were revoked or have expired
+				{
+					let txn = self.transaction(Write, Optimistic).await?;
+					let now = Datetime::default();
+					let acs = catch!(txn, txn.all_db_accesses(&ns.name, &db.name).await);
+					for ac in acs.iter() {
+						// Only refresh grants are issued without a statement
+						if !matches!(&ac.kind, AccessType::Record(at) if at.refresh) {
+							continue;
+						}
+						let ac = ac.name.to_raw();
+						let grs =
+							catch!(txn, txn.all_db_access_grants(&ns.name, &db.name, &ac).await);
+						for gr in grs.iter() {
+							if gr.revocation.is_some()
+								|| gr.expiration.as_ref().is_some_and(|v| *v < now)
+							{
+								let key = crate::key::database::access::gr::new(
+									&ns.name, &db.name, &ac, &gr.id,
+								);
+								catch!(txn, txn.del(key).await);
+							}
+						}
+					}
+					txn.commit().await?;
+				}
 				// Fetch all tables
 				let tbs = {
 					let txn = self.transaction(Read, Optimistic).await?;
@@ -0,0 +1,200 @@
+use crate::kvs::{EncryptionKey, Keyring};
+
+fn keyring(secret: &[u8], salt: &[u8]) -> Option<Keyring> {
+	Some(Keyring::new(EncryptionKey::new(secret, salt).unwrap()))
+}
+
+#[tokio::test]
+#[serial]
+async fn encryption() {
+	// Create a new datastore
+	let node_id = Uuid::parse_str("6a4b1f1e-4f6e-4a5e-9a43-2b7d0c3e8d15").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds(node_id, clock).await;
+	// Store a value before encryption is enabled
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap().inner();
+	tx.set("test1", "one", None).await.unwrap();
+	tx.commit().await.unwrap();
+	// Enable encryption at rest
+	let salt = ds.encryption_salt().await.unwrap();
+	assert_eq!(ds.encryption_salt().await.unwrap(), salt);
+	let ds = ds.with_encryption(keyring(b"secret", &salt));
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap().inner();
+	tx.set("test2", "two", None).await.unwrap();
+	tx.putc("test2", "three", Some("two")).await.unwrap();
+	assert!(matches!(
+		tx.putc("test2", "four", Some("two")).await,
+		Err(crate::err::Error::TxConditionNotMet)
+	));
+	tx.commit().await.unwrap();
+	// Values are decrypted, and plaintext values are still readable
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap().inner();
+	let val = tx.get("test1", None).await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"one")));
+	let val = tx.get("test2", None).await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"three")));
+	let val = tx.getr("test1".."test3").await.unwrap();
+	assert_eq!(
+		val,
+		vec![(b"test1".to_vec(), b"one".to_vec()), (b"test2".to_vec(), b"three".to_vec())]
+	);
+	tx.cancel().await.unwrap();
+	// Values are not stored in plaintext
+	let ds = ds.with_encryption(None);
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap().inner();
+	let val = tx.get("test2", None).await.unwrap().unwrap();
+	assert_ne!(val, b"three".to_vec());
+	tx.cancel().await.unwrap();
+	// Values can not be moved to another key
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap().inner();
+	tx.set("test3", val, None).await.unwrap();
+	tx.commit().await.unwrap();
+	let ds = ds.with_encryption(keyring(b"secret", &salt));
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap().inner();
+	assert!(matches!(tx.get("test3", None).await, Err(crate::err::Error::Encryption(_))));
+	tx.cancel().await.unwrap();
+	// The salt is never encrypted
+	assert_eq!(ds.encryption_salt().await.unwrap(), salt);
+}
+
+#[tokio::test]
+#[serial]
+async fn encryption_rotation() {
+	// Create a new datastore
+	let node_id = Uuid::parse_str("0f1e9a2c-7b3d-4c8e-b5a1-93d2e6f4c7a8").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds(node_id, clock).await;
+	// Store a plaintext value, and a value encrypted with the old key
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap().inner();
+	tx.set("test1", "one", None).await.unwrap();
+	tx.commit().await.unwrap();
+	let salt = ds.encryption_salt().await.unwrap();
+	let ds = ds.with_encryption(keyring(b"old", &salt));
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap().inner();
+	tx.set("test2", "two", None).await.unwrap();
+	tx.commit().await.unwrap();
+	// Rotate the key, and re-encrypt all values
+	let ds = ds.with_encryption(
+		keyring(b"new", &salt)
+			.map(|k| k.with_previous(vec![EncryptionKey::new(b"old", &salt).unwrap()])),
+	);
+	ds.reencrypt().await.unwrap();
+	assert_eq!(ds.encryption_salt().await.unwrap(), salt);
+	// All values are readable without the old key
+	let ds = ds.with_encryption(keyring(b"new", &salt));
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap().inner();
+	let val = tx.get("test1", None).await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"one")));
+	let val = tx.get("test2", None).await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"two")));
+	tx.cancel().await.unwrap();
+}
+
+#[tokio::test]
+#[serial]
+async fn encryption_fields() {
+	use crate::dbs::Session;
+	use crate::sql::{Id, Idiom, Value};
+	// Run a query, returning the result of each statement
+	async fn run(ds: &Datastore, ses: &Session, sql: &str) -> Vec<Result<Value, String>> {
+		let res = ds.execute(sql, ses, None).await.unwrap();
+		res.into_iter().map(|r| r.result.map_err(|e| e.to_string())).collect()
+	}
+	// Create a new datastore with encryption at rest
+	let node_id = Uuid::parse_str("3c8f2d4a-9e1b-4f7c-a6d5-1b2e8c9f0a34").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds(node_id, clock).await;
+	let salt = ds.encryption_salt().await.unwrap();
+	let ds = ds.with_encryption(keyring(b"old", &salt));
+	let owner = Session::owner().with_ns("test").with_db("test");
+	// Define the encrypted fields, and a unique index on the deterministic field
+	let res = run(
+		&ds,
+		&owner,
+		"
+		DEFINE FIELD ssn ON person TYPE string ENCRYPTED DETERMINISTIC MASK 4;
+		DEFINE FIELD note ON person TYPE string ENCRYPTED;
+		DEFINE INDEX ssn ON person FIELDS ssn UNIQUE;
+		CREATE person:tobie SET ssn = '123-45-6789', note = 'secret';
+		",
+	)
+	.await;
+	assert!(res.iter().all(Result::is_ok), "{res:?}");
+	// Indexes can only be defined on deterministic encrypted fields
+	for sql in [
+		"DEFINE INDEX note ON person FIELDS note UNIQUE",
+		"DEFINE INDEX search ON person FIELDS ssn SEARCH",
+		"DEFINE FIELD OVERWRITE ssn ON person TYPE string ENCRYPTED",
+		"DEFINE FIELD OVERWRITE note ON person TYPE string",
+	] {
+		let res = run(&ds, &owner, sql).await;
+		assert!(res[0].as_ref().is_err_and(|e| e.contains("encrypted field")), "{res:?}");
+	}
+	// Values are not stored in clear
+	let tx = ds.transaction(Read, Optimistic).await.unwrap();
+	let val = tx.get_record("test", "test", "person", &Id::from("tobie")).await.unwrap();
+	for (field, clear) in [("ssn", "123-45-6789"), ("note", "secret")] {
+		let val = val.pick(&Idiom::from(field));
+		assert!(matches!(val, Value::Bytes(_)), "{val:?}");
+		assert!(!val.to_raw_string().contains(clear));
+	}
+	tx.cancel().await.unwrap();
+	// Values are selected and compared in clear, and the index finds them
+	async fn check(ds: &Datastore) {
+		let owner = Session::owner().with_ns("test").with_db("test");
+		let viewer = Session::viewer().with_ns("test").with_db("test");
+		let res = run(
+			ds,
+			&owner,
+			"
+			SELECT * FROM person WHERE ssn = '123-45-6789';
+			SELECT * FROM person WHERE note = 'secret';
+			SELECT * FROM person WHERE ssn = '000-00-0000';
+			CREATE person:jaime SET ssn = '123-45-6789', note = 'other';
+			",
+		)
+		.await;
+		let expected = "[{ id: person:tobie, note: 'secret', ssn: '123-45-6789' }]";
+		let expected = crate::syn::value(expected).unwrap();
+		assert_eq!(res[0], Ok(expected.clone()));
+		assert_eq!(res[1], Ok(expected));
+		assert_eq!(res[2], Ok(Value::from(Vec::<Value>::new())));
+		assert!(res[3].as_ref().is_err_and(|e| e.contains("already contains")), "{res:?}");
+		// Users who may not edit records see masked values
+		let res = run(ds, &viewer, "SELECT ssn, note FROM person:tobie").await;
+		let expected = crate::syn::value("[{ note: '******', ssn: '***-**-6789' }]").unwrap();
+		assert_eq!(res[0], Ok(expected));
+	}
+	check(&ds).await;
+	// Rotate the key, and re-encrypt all values
+	let ds = ds.with_encryption(
+		keyring(b"new", &salt)
+			.map(|k| k.with_previous(vec![EncryptionKey::new(b"old", &salt).unwrap()])),
+	);
+	ds.reencrypt().await.unwrap();
+	// Fields and indexes still work without the old key
+	let ds = ds.with_encryption(keyring(b"new", &salt));
+	check(&ds).await;
+	// Encrypted fields are exported in clear
+	let (snd, rcv) = channel::bounded(1);
+	let export = ds.export(&owner, snd).await.unwrap();
+	let (res, sql) = futures::join!(export, async {
+		let mut sql = Vec::new();
+		while let Ok(v) = rcv.recv().await {
+			sql.extend(v);
+			sql.push(b'\n');
+		}
+		String::from_utf8(sql).unwrap()
+	});
+	res.unwrap();
+	assert!(sql.contains("'123-45-6789'"), "{sql}");
+	// Imported fields are encrypted with the secret of the imported definition
+	let node_id = Uuid::parse_str("5d2a7e91-3b4c-4f8a-9c6e-0a1b2c3d4e5f").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds(node_id, clock).await;
+	let salt = ds.encryption_salt().await.unwrap();
+	let ds = ds.with_encryption(keyring(b"other", &salt));
+	let res = ds.import(&sql, &owner).await.unwrap();
+	assert!(res.iter().all(|r| r.result.is_ok()), "{res:?}");
+	check(&ds).await;
+}
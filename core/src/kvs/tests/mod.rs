@@ -49,6 +49,7 @@ mod mem {
 
 	include!("helper.rs");
 	include!("raw.rs");
+	include!("encryption.rs");
 	include!("snapshot.rs");
 	include!("multireader.rs");
 	include!("timestamp_to_versionstamp.rs");
@@ -77,6 +78,7 @@ mod rocksdb {
 
 	include!("helper.rs");
 	include!("raw.rs");
+	include!("encryption.rs");
 	include!("snapshot.rs");
 	include!("multireader.rs");
 	include!("multiwriter_different_keys.rs");
@@ -107,6 +109,7 @@ mod surrealkv {
 	}
 
 	include!("raw.rs");
+	include!("encryption.rs");
 	include!("helper.rs");
 	include!("snapshot.rs");
 	include!("multireader.rs");
@@ -139,6 +142,7 @@ mod tikv {
 
 	include!("helper.rs");
 	include!("raw.rs");
+	include!("encryption.rs");
 	include!("snapshot.rs");
 	include!("multireader.rs");
 	include!("multiwriter_different_keys.rs");
@@ -170,6 +174,7 @@ mod fdb {
 
 	include!("helper.rs");
 	include!("raw.rs");
+	include!("encryption.rs");
 	include!("snapshot.rs");
 	include!("multireader.rs");
 	include!("multiwriter_different_keys.rs");
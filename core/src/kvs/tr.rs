@@ -11,6 +11,7 @@ use crate::idg::u32::U32;
 use crate::key::debug::Sprintable;
 use crate::kvs::batch::Batch;
 use crate::kvs::clock::SizedClock;
+use crate::kvs::crypto::Keyring;
 use crate::kvs::stash::Stash;
 use crate::sql;
 use crate::sql::thing::Thing;
@@ -70,6 +71,7 @@ pub struct Transactor {
 	pub(super) stash: Stash,
 	pub(super) cf: cf::Writer,
 	pub(super) clock: Arc<SizedClock>,
+	pub(super) keyring: Option<Arc<Keyring>>,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -139,6 +141,16 @@ macro_rules! expand_inner {
 	};
 }
 
+/// Checks whether a key stores the versionstamp of a database
+fn is_versionstamp(key: &Key) -> bool {
+	crate::key::database::vs::Vs::decode(key).and_then(|v| v.encode()).is_ok_and(|v| &v == key)
+}
+
+/// Checks whether a key stores the salt of the encryption keys, which is never encrypted
+fn is_encryption_salt(key: &[u8]) -> bool {
+	crate::key::root::es::new().encode().is_ok_and(|v| v == key)
+}
+
 impl Transactor {
 	// Allow unused_variables when no storage is enabled as none of the values are used then.
 	#![cfg_attr(
@@ -213,7 +225,9 @@ impl Transactor {
 		K: Into<Key> + Debug,
 	{
 		let key = key.into();
-		expand_inner!(&mut self.inner, v => { v.get(key, version).await })
+		let res: Result<Option<Val>, Error> =
+			expand_inner!(&mut self.inner, v => { v.get(key.clone(), version).await });
+		res?.map(|v| self.decrypt(&key, v)).transpose()
 	}
 
 	/// Fetch many keys from the datastore.
@@ -223,7 +237,9 @@ impl Transactor {
 		K: Into<Key> + Debug,
 	{
 		let keys = keys.into_iter().map(Into::into).collect::<Vec<Key>>();
-		expand_inner!(&mut self.inner, v => { v.getm(keys).await })
+		let res: Result<Vec<Val>, Error> =
+			expand_inner!(&mut self.inner, v => { v.getm(keys.clone()).await });
+		res?.into_iter().zip(keys.iter()).map(|(v, k)| self.decrypt(k, v)).collect()
 	}
 
 	/// Retrieve a specific range of keys from the datastore.
@@ -236,7 +252,9 @@ impl Transactor {
 	{
 		let beg: Key = rng.start.into();
 		let end: Key = rng.end.into();
-		expand_inner!(&mut self.inner, v => { v.getr(beg..end).await })
+		let res: Result<Vec<(Key, Val)>, Error> =
+			expand_inner!(&mut self.inner, v => { v.getr(beg..end).await });
+		self.decrypt_pairs(res?)
 	}
 
 	/// Retrieve a specific prefixed range of keys from the datastore.
@@ -248,7 +266,9 @@ impl Transactor {
 		K: Into<Key> + Debug,
 	{
 		let key: Key = key.into();
-		expand_inner!(&mut self.inner, v => { v.getp(key).await })
+		let res: Result<Vec<(Key, Val)>, Error> =
+			expand_inner!(&mut self.inner, v => { v.getp(key).await });
+		self.decrypt_pairs(res?)
 	}
 
 	/// Insert or update a key in the datastore.
@@ -259,6 +279,7 @@ impl Transactor {
 		V: Into<Val> + Debug,
 	{
 		let key = key.into();
+		let val = self.encrypt(&key, val.into())?;
 		expand_inner!(&mut self.inner, v => { v.set(key, val, version).await })
 	}
 
@@ -270,6 +291,7 @@ impl Transactor {
 		V: Into<Val> + Debug,
 	{
 		let key = key.into();
+		let val = self.encrypt(&key, val.into())?;
 		expand_inner!(&mut self.inner, v => { v.put(key, val, version).await })
 	}
 
@@ -281,6 +303,8 @@ impl Transactor {
 		V: Into<Val> + Debug,
 	{
 		let key = key.into();
+		let val = self.encrypt(&key, val.into())?;
+		let chk = self.encrypted_check(&key, chk).await?;
 		expand_inner!(&mut self.inner, v => { v.putc(key, val, chk).await })
 	}
 
@@ -302,6 +326,7 @@ impl Transactor {
 		V: Into<Val> + Debug,
 	{
 		let key = key.into();
+		let chk = self.encrypted_check(&key, chk).await?;
 		expand_inner!(&mut self.inner, v => { v.delc(key, chk).await })
 	}
 
@@ -361,7 +386,9 @@ impl Transactor {
 		if beg > end {
 			return Ok(vec![]);
 		}
-		expand_inner!(&mut self.inner, v => { v.scan(beg..end, limit, version).await })
+		let res: Result<Vec<(Key, Val)>, Error> =
+			expand_inner!(&mut self.inner, v => { v.scan(beg..end, limit, version).await });
+		self.decrypt_pairs(res?)
 	}
 
 	/// Retrieve a batched scan over a specific range of keys in the datastore.
@@ -379,7 +406,16 @@ impl Transactor {
 	{
 		let beg: Key = rng.start.into();
 		let end: Key = rng.end.into();
-		expand_inner!(&mut self.inner, v => { v.batch(beg..end, batch, values).await })
+		let res: Result<Batch, Error> =
+			expand_inner!(&mut self.inner, v => { v.batch(beg..end, batch, values).await });
+		let res = res?;
+		match values {
+			true => Ok(Batch {
+				next: res.next,
+				values: self.decrypt_pairs(res.values)?,
+			}),
+			false => Ok(res),
+		}
 	}
 
 	/// Obtain a new change timestamp for a key
@@ -410,9 +446,93 @@ impl Transactor {
 		let ts_key = ts_key.into();
 		let prefix = prefix.into();
 		let suffix = suffix.into();
+		let val = match &self.keyring {
+			Some(keyring) => keyring.encrypt_versioned(&prefix, &suffix, val.into())?,
+			None => val.into(),
+		};
 		expand_inner!(&mut self.inner, v => { v.set_versionstamp(ts_key, prefix, suffix, val).await })
 	}
 
+	// --------------------------------------------------
+	// Encryption methods
+	// --------------------------------------------------
+
+	/// Encrypts a value before it is stored under a key, if encryption at rest is enabled
+	fn encrypt(&self, key: &[u8], val: Val) -> Result<Val, Error> {
+		match &self.keyring {
+			Some(keyring) if !is_encryption_salt(key) => keyring.encrypt(key, val),
+			_ => Ok(val),
+		}
+	}
+
+	/// Decrypts a value stored under a key, if encryption at rest is enabled
+	fn decrypt(&self, key: &[u8], val: Val) -> Result<Val, Error> {
+		match &self.keyring {
+			Some(keyring) if !is_encryption_salt(key) => keyring.decrypt(key, val),
+			_ => Ok(val),
+		}
+	}
+
+	/// Decrypts the stored values of a set of key-value pairs
+	fn decrypt_pairs(&self, res: Vec<(Key, Val)>) -> Result<Vec<(Key, Val)>, Error> {
+		match &self.keyring {
+			Some(_) => res
+				.into_iter()
+				.map(|(k, v)| {
+					let v = self.decrypt(&k, v)?;
+					Ok((k, v))
+				})
+				.collect(),
+			None => Ok(res),
+		}
+	}
+
+	/// Converts the plaintext value of a conditional operation into the stored value.
+	/// As each encryption uses a random nonce, the stored value is fetched, decrypted
+	/// and compared with the condition, and then passed to the storage engine as is.
+	async fn encrypted_check<V>(&mut self, key: &Key, chk: Option<V>) -> Result<Option<Val>, Error>
+	where
+		V: Into<Val> + Debug,
+	{
+		let Some(chk) = chk.map(Into::into) else {
+			return Ok(None);
+		};
+		if self.keyring.is_none() {
+			return Ok(Some(chk));
+		}
+		let res: Result<Option<Val>, Error> =
+			expand_inner!(&mut self.inner, v => { v.get(key.clone(), None).await });
+		match res? {
+			Some(val) if self.decrypt(key, val.clone())? == chk => Ok(Some(val)),
+			_ => Err(Error::TxConditionNotMet),
+		}
+	}
+
+	/// Re-encrypts a batch of stored values which were not encrypted with the current key,
+	/// returning the remaining range of keys to process, if any.
+	pub(crate) async fn reencrypt(
+		&mut self,
+		rng: Range<Key>,
+		batch: u32,
+	) -> Result<Option<Range<Key>>, Error> {
+		let Some(keyring) = self.keyring.clone() else {
+			return Ok(None);
+		};
+		let res: Result<Batch, Error> =
+			expand_inner!(&mut self.inner, v => { v.batch(rng, batch, true).await });
+		let res = res?;
+		for (key, val) in res.values {
+			// Versionstamps are read and written by the storage engine itself
+			if keyring.is_stale(&val) && !is_versionstamp(&key) && !is_encryption_salt(&key) {
+				let val = keyring.encrypt(&key, keyring.decrypt(&key, val)?)?;
+				let res: Result<(), Error> =
+					expand_inner!(&mut self.inner, v => { v.set(key, val, None).await });
+				res?;
+			}
+		}
+		Ok(res.next)
+	}
+
 	// --------------------------------------------------
 	// Additional methods
 	// --------------------------------------------------
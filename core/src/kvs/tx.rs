@@ -9,6 +9,7 @@ use crate::dbs::node::Node;
 use crate::err::Error;
 use crate::kvs::cache::Entry;
 use crate::kvs::cache::EntryWeighter;
+use crate::kvs::crypto::Keyring;
 use crate::kvs::scanner::Scanner;
 use crate::kvs::Transactor;
 use crate::sql::statements::AccessGrant;
@@ -22,6 +23,9 @@ use crate::sql::statements::DefineIndexStatement;
 use crate::sql::statements::DefineModelStatement;
 use crate::sql::statements::DefineNamespaceStatement;
 use crate::sql::statements::DefineParamStatement;
+use crate::sql::statements::DefinePolicyStatement;
+use crate::sql::statements::DefineRoleStatement;
+use crate::sql::statements::DefineSigninPolicyStatement;
 use crate::sql::statements::DefineTableStatement;
 use crate::sql::statements::DefineUserStatement;
 use crate::sql::statements::LiveStatement;
@@ -43,12 +47,15 @@ pub struct Transaction {
 	tx: Mutex<Transactor>,
 	/// The query cache for this store
 	cache: Cache<Key, Entry, EntryWeighter>,
+	/// The keys used to encrypt stored values, if encryption at rest is enabled
+	keyring: Option<Arc<Keyring>>,
 }
 
 impl Transaction {
 	/// Create a new query store
 	pub fn new(tx: Transactor) -> Transaction {
 		Transaction {
+			keyring: tx.keyring.clone(),
 			tx: Mutex::new(tx),
 			cache: Cache::with_weighter(
 				*TRANSACTION_CACHE_SIZE,
@@ -68,6 +75,11 @@ impl Transaction {
 		Arc::new(self)
 	}
 
+	/// Retrieve the keys used to encrypt stored values, if encryption at rest is enabled
+	pub(crate) fn keyring(&self) -> Option<&Keyring> {
+		self.keyring.as_deref()
+	}
+
 	/// Retrieve the underlying transaction
 	pub async fn lock(&self) -> MutexGuard<'_, Transactor> {
 		self.tx.lock().await
@@ -344,6 +356,25 @@ impl Transaction {
 		.into_rus())
 	}
 
+	/// Retrieve all ROOT level roles in a datastore.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn all_root_roles(&self) -> Result<Arc<[DefineRoleStatement]>, Error> {
+		let key = crate::key::root::rl::prefix();
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		Ok(match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let end = crate::key::root::rl::suffix();
+				let val = self.getr(key..end).await?;
+				let val = val.convert().into();
+				let val = Entry::Rrs(Arc::clone(&val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.into_rrs())
+	}
+
 	/// Retrieve all ROOT level accesses in a datastore.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn all_root_accesses(&self) -> Result<Arc<[DefineAccessStatement]>, Error> {
@@ -420,6 +451,44 @@ impl Transaction {
 		.into_nus())
 	}
 
+	/// Retrieve all namespace role definitions for a specific namespace.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn all_ns_roles(&self, ns: &str) -> Result<Arc<[DefineRoleStatement]>, Error> {
+		let key = crate::key::namespace::rl::prefix(ns);
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		Ok(match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let end = crate::key::namespace::rl::suffix(ns);
+				let val = self.getr(key..end).await?;
+				let val = val.convert().into();
+				let val = Entry::Nrs(Arc::clone(&val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.into_nrs())
+	}
+
+	/// Retrieve all namespace policy definitions for a specific namespace.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn all_ns_policies(&self, ns: &str) -> Result<Arc<[DefinePolicyStatement]>, Error> {
+		let key = crate::key::namespace::pl::prefix(ns);
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		Ok(match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let end = crate::key::namespace::pl::suffix(ns);
+				let val = self.getr(key..end).await?;
+				let val = val.convert().into();
+				let val = Entry::Nps(Arc::clone(&val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.into_nps())
+	}
+
 	/// Retrieve all namespace access definitions for a specific namespace.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn all_ns_accesses(&self, ns: &str) -> Result<Arc<[DefineAccessStatement]>, Error> {
@@ -504,6 +573,79 @@ impl Transaction {
 		.into_dus())
 	}
 
+	/// Retrieve all database role definitions for a specific database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn all_db_roles(
+		&self,
+		ns: &str,
+		db: &str,
+	) -> Result<Arc<[DefineRoleStatement]>, Error> {
+		let key = crate::key::database::rl::prefix(ns, db);
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		Ok(match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let end = crate::key::database::rl::suffix(ns, db);
+				let val = self.getr(key..end).await?;
+				let val = val.convert().into();
+				let val = Entry::Drs(Arc::clone(&val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.into_drs())
+	}
+
+	/// Retrieve all database policy definitions for a specific database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn all_db_policies(
+		&self,
+		ns: &str,
+		db: &str,
+	) -> Result<Arc<[DefinePolicyStatement]>, Error> {
+		let key = crate::key::database::pl::prefix(ns, db);
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		Ok(match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let end = crate::key::database::pl::suffix(ns, db);
+				let val = self.getr(key..end).await?;
+				let val = val.convert().into();
+				let val = Entry::Dps(Arc::clone(&val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.into_dps())
+	}
+
+	/// Retrieve the namespace and database policies which are attached to a specific table.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn all_tb_policies(
+		&self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+	) -> Result<Arc<[DefinePolicyStatement]>, Error> {
+		// The policies are not stored per table, the cache entry has its own key
+		let mut key = crate::key::table::all::new(ns, db, tb).encode()?;
+		key.extend_from_slice(b"!pl");
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		Ok(match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let nps = self.all_ns_policies(ns).await?;
+				let dps = self.all_db_policies(ns, db).await?;
+				let val: Arc<[DefinePolicyStatement]> =
+					nps.iter().chain(dps.iter()).filter(|pl| pl.is_attached(tb)).cloned().collect();
+				let val = Entry::Tps(val);
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.into_tps())
+	}
+
 	/// Retrieve all database access definitions for a specific database.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn all_db_accesses(
@@ -822,6 +964,44 @@ impl Transaction {
 		.into_type())
 	}
 
+	/// Retrieve a specific root role definition.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_root_role(&self, rl: &str) -> Result<Arc<DefineRoleStatement>, Error> {
+		let key = crate::key::root::rl::new(rl).encode()?;
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		Ok(match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let val = self.get(key, None).await?.ok_or_else(|| Error::RoleRootNotFound {
+					value: rl.to_owned(),
+				})?;
+				let val: DefineRoleStatement = val.into();
+				let val = Entry::Any(Arc::new(val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.into_type())
+	}
+
+	/// Retrieve the root signin policy definition.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_root_signin_policy(&self) -> Result<Arc<DefineSigninPolicyStatement>, Error> {
+		let key = crate::key::root::sp::new().encode()?;
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		Ok(match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let val = self.get(key, None).await?.ok_or(Error::SpRootNotFound)?;
+				let val: DefineSigninPolicyStatement = val.into();
+				let val = Entry::Any(Arc::new(val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.into_type())
+	}
+
 	/// Retrieve a specific root access definition.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn get_root_access(&self, ra: &str) -> Result<Arc<DefineAccessStatement>, Error> {
@@ -909,6 +1089,50 @@ impl Transaction {
 		.into_type())
 	}
 
+	/// Retrieve a specific namespace role definition.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_ns_role(&self, ns: &str, rl: &str) -> Result<Arc<DefineRoleStatement>, Error> {
+		let key = crate::key::namespace::rl::new(ns, rl).encode()?;
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		Ok(match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let val = self.get(key, None).await?.ok_or_else(|| Error::RoleNsNotFound {
+					value: rl.to_owned(),
+					ns: ns.to_owned(),
+				})?;
+				let val: DefineRoleStatement = val.into();
+				let val = Entry::Any(Arc::new(val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.into_type())
+	}
+
+	/// Retrieve the signin policy definition for a namespace.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_ns_signin_policy(
+		&self,
+		ns: &str,
+	) -> Result<Arc<DefineSigninPolicyStatement>, Error> {
+		let key = crate::key::namespace::sp::new(ns).encode()?;
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		Ok(match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let val = self.get(key, None).await?.ok_or_else(|| Error::SpNsNotFound {
+					ns: ns.to_owned(),
+				})?;
+				let val: DefineSigninPolicyStatement = val.into();
+				let val = Entry::Any(Arc::new(val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.into_type())
+	}
+
 	/// Retrieve a specific namespace access definition.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn get_ns_access(
@@ -1009,6 +1233,110 @@ impl Transaction {
 		.into_type())
 	}
 
+	/// Retrieve a specific role definition from a database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_db_role(
+		&self,
+		ns: &str,
+		db: &str,
+		rl: &str,
+	) -> Result<Arc<DefineRoleStatement>, Error> {
+		let key = crate::key::database::rl::new(ns, db, rl).encode()?;
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		Ok(match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let val = self.get(key, None).await?.ok_or_else(|| Error::RoleDbNotFound {
+					value: rl.to_owned(),
+					ns: ns.to_owned(),
+					db: db.to_owned(),
+				})?;
+				let val: DefineRoleStatement = val.into();
+				let val = Entry::Any(Arc::new(val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.into_type())
+	}
+
+	/// Retrieve the signin policy definition for a database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_db_signin_policy(
+		&self,
+		ns: &str,
+		db: &str,
+	) -> Result<Arc<DefineSigninPolicyStatement>, Error> {
+		let key = crate::key::database::sp::new(ns, db).encode()?;
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		Ok(match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let val = self.get(key, None).await?.ok_or_else(|| Error::SpDbNotFound {
+					ns: ns.to_owned(),
+					db: db.to_owned(),
+				})?;
+				let val: DefineSigninPolicyStatement = val.into();
+				let val = Entry::Any(Arc::new(val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.into_type())
+	}
+
+	/// Retrieve a specific namespace policy definition.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_ns_policy(
+		&self,
+		ns: &str,
+		pl: &str,
+	) -> Result<Arc<DefinePolicyStatement>, Error> {
+		let key = crate::key::namespace::pl::new(ns, pl).encode()?;
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		Ok(match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let val = self.get(key, None).await?.ok_or_else(|| Error::PlNsNotFound {
+					value: pl.to_owned(),
+					ns: ns.to_owned(),
+				})?;
+				let val: DefinePolicyStatement = val.into();
+				let val = Entry::Any(Arc::new(val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.into_type())
+	}
+
+	/// Retrieve a specific database policy definition.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_db_policy(
+		&self,
+		ns: &str,
+		db: &str,
+		pl: &str,
+	) -> Result<Arc<DefinePolicyStatement>, Error> {
+		let key = crate::key::database::pl::new(ns, db, pl).encode()?;
+		let res = self.cache.get_value_or_guard_async(&key).await;
+		Ok(match res {
+			Ok(val) => val,
+			Err(cache) => {
+				let val = self.get(key, None).await?.ok_or_else(|| Error::PlDbNotFound {
+					value: pl.to_owned(),
+					ns: ns.to_owned(),
+					db: db.to_owned(),
+				})?;
+				let val: DefinePolicyStatement = val.into();
+				let val = Entry::Any(Arc::new(val));
+				let _ = cache.insert(val.clone());
+				val
+			}
+		}
+		.into_type())
+	}
+
 	/// Retrieve a specific database access definition.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn get_db_access(
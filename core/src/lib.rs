@@ -1,3 +1,5 @@
+#![recursion_limit = "256"]
+
 #[macro_use]
 extern crate tracing;
 
@@ -40,6 +42,8 @@ pub mod kvs;
 #[doc(hidden)]
 pub mod options;
 #[doc(hidden)]
+pub mod rest;
+#[doc(hidden)]
 pub mod rpc;
 #[doc(hidden)]
 pub mod syn;
@@ -0,0 +1,36 @@
+use super::parse;
+use crate::err::Error;
+use crate::sql::statements::LiveStatement;
+use crate::sql::{Fields, Table, Value};
+use serde::Deserialize;
+
+/// The query parameters accepted when subscribing to the changes of a table
+#[derive(Default, Deserialize, Debug, Clone)]
+pub struct LiveOptions {
+	/// A condition which the changed records must match
+	#[serde(rename = "where")]
+	pub cond: Option<String>,
+	/// The fields to send, instead of the whole record
+	pub fields: Option<Vec<String>>,
+	/// Whether to send the changes as JSON Patch operations, instead of the records
+	pub diff: Option<bool>,
+	/// The record links to fetch
+	pub fetch: Option<Vec<String>>,
+}
+
+impl LiveOptions {
+	/// Compiles the options into a statement which listens for changes to a table
+	pub fn live(&self, tb: &str) -> Result<LiveStatement, Error> {
+		// Send only the requested fields, or the changes
+		let expr = match (&self.fields, self.diff.unwrap_or_default()) {
+			(_, true) => Fields::default(),
+			(Some(fields), false) => parse::fields(fields)?,
+			(None, false) => Fields::all(),
+		};
+		// Filter the changes with the condition
+		let cond = self.cond.as_deref().map(parse::cond).transpose()?;
+		// Fetch the requested record links
+		let fetch = self.fetch.as_deref().map(parse::fetch).transpose()?;
+		Ok(LiveStatement::from_source_parts(expr, Value::Table(Table::from(tb)), cond, fetch))
+	}
+}
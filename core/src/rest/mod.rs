@@ -0,0 +1,14 @@
+//! The REST-style HTTP endpoints, which expose each table as a resource.
+//!
+//! Requests to the `/key` routes are compiled into statements, rather than being formatted
+//! into query text, and the routes are described by an OpenAPI document which is generated
+//! from the table and field definitions of the database. The `/live` routes register a
+//! live query from the same kind of parameters, and stream its notifications.
+
+mod live;
+pub mod openapi;
+mod parse;
+mod query;
+
+pub use live::LiveOptions;
+pub use query::QueryOptions;
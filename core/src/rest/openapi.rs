@@ -0,0 +1,261 @@
+//! Generates an OpenAPI document describing the `/key` routes of a database.
+
+use crate::dbs::Session;
+use crate::env::VERSION;
+use crate::err::Error;
+use crate::iam::check::check_ns_db;
+use crate::iam::{Action, ResourceKind};
+use crate::kvs::Datastore;
+use crate::kvs::LockType::*;
+use crate::kvs::TransactionType::*;
+use crate::sql::statements::{DefineFieldStatement, DefineTableStatement};
+use crate::sql::{Kind, Part};
+use serde_json::{json, Map, Value as Json};
+use std::sync::Arc;
+
+/// The query parameters of the route for a table
+const TABLE_PARAMETERS: [&str; 9] =
+	["ns", "db", "where", "order", "limit", "start", "cursor", "fields", "fetch"];
+
+/// The parameters of the routes for a record
+const RECORD_PARAMETERS: [&str; 5] = ["ns", "db", "id", "fields", "fetch"];
+
+/// The parameters of the routes which write records
+const WRITE_PARAMETERS: [&str; 2] = ["ns", "db"];
+
+/// The parameters of the routes which write a record
+const RECORD_WRITE_PARAMETERS: [&str; 3] = ["ns", "db", "id"];
+
+/// Generates the OpenAPI document for the database selected by the session
+pub async fn generate_document(kvs: &Datastore, sess: &Session) -> Result<Json, Error> {
+	// Ensure a namespace and database are selected
+	let (ns, db) = check_ns_db(sess)?;
+	// Check that the session can view the definitions
+	kvs.check(sess, Action::View, ResourceKind::Any.on_db(&ns, &db)).await?;
+	// Fetch the table and field definitions
+	let txn = kvs.transaction(Read, Optimistic).await?;
+	let res = async {
+		let mut tables = vec![];
+		for tb in txn.all_tb(&ns, &db).await?.iter() {
+			let fields = txn.all_tb_fields(&ns, &db, &tb.name.0).await?;
+			tables.push((tb.clone(), fields));
+		}
+		Ok::<Vec<(DefineTableStatement, Arc<[DefineFieldStatement]>)>, Error>(tables)
+	}
+	.await;
+	txn.cancel().await?;
+	// Describe the routes and records of each table
+	let mut paths = Map::new();
+	let mut schemas = Map::new();
+	for (tb, fields) in res? {
+		let name = tb.name.to_raw();
+		paths.insert(format!("/key/{name}"), table_path(&name));
+		paths.insert(format!("/key/{name}/{{id}}"), record_path(&name));
+		schemas.insert(name, table_schema(&tb, &fields));
+	}
+	Ok(json!({
+		"openapi": "3.0.3",
+		"info": {
+			"title": format!("{ns}/{db}"),
+			"version": VERSION,
+		},
+		"paths": paths,
+		"components": {
+			"schemas": schemas,
+			"parameters": parameters(),
+			"securitySchemes": {
+				"basic": { "type": "http", "scheme": "basic" },
+				"bearer": { "type": "http", "scheme": "bearer" },
+			},
+		},
+		"security": [{ "basic": [] }, { "bearer": [] }],
+	}))
+}
+
+/// Describes the operations on a table
+fn table_path(tb: &str) -> Json {
+	json!({
+		"get": operation(tb, "Select records from the table", &TABLE_PARAMETERS, false),
+		"post": operation(tb, "Create a record in the table", &WRITE_PARAMETERS, true),
+		"put": operation(tb, "Replace the content of every record in the table", &WRITE_PARAMETERS, true),
+		"patch": operation(tb, "Merge data into every record in the table", &WRITE_PARAMETERS, true),
+		"delete": operation(tb, "Delete every record in the table", &WRITE_PARAMETERS, false),
+	})
+}
+
+/// Describes the operations on a record
+fn record_path(tb: &str) -> Json {
+	json!({
+		"get": operation(tb, "Select a record", &RECORD_PARAMETERS, false),
+		"post": operation(tb, "Create a record", &RECORD_WRITE_PARAMETERS, true),
+		"put": operation(tb, "Replace the content of a record", &RECORD_WRITE_PARAMETERS, true),
+		"patch": operation(tb, "Merge data into a record", &RECORD_WRITE_PARAMETERS, true),
+		"delete": operation(tb, "Delete a record", &RECORD_WRITE_PARAMETERS, false),
+	})
+}
+
+/// Describes an operation, which responds with the result of each statement
+fn operation(tb: &str, summary: &str, parameters: &[&str], body: bool) -> Json {
+	let schema = json!({ "$ref": format!("#/components/schemas/{tb}") });
+	let parameters: Vec<Json> = parameters
+		.iter()
+		.map(|v| json!({ "$ref": format!("#/components/parameters/{v}") }))
+		.collect();
+	let mut operation = json!({
+		"tags": [tb],
+		"summary": summary,
+		"parameters": parameters,
+		"responses": {
+			"200": {
+				"description": "The result of each statement",
+				"content": {
+					"application/json": {
+						"schema": {
+							"type": "array",
+							"items": {
+								"type": "object",
+								"properties": {
+									"time": { "type": "string" },
+									"status": { "type": "string" },
+									"result": { "type": "array", "items": schema },
+								},
+							},
+						},
+					},
+				},
+			},
+		},
+	});
+	if body {
+		operation["requestBody"] = json!({
+			"required": true,
+			"content": { "application/json": { "schema": schema } },
+		});
+	}
+	operation
+}
+
+/// Describes the parameters which are shared between operations
+fn parameters() -> Json {
+	let list = |name: &str, description: &str| {
+		json!({
+			"name": name,
+			"in": "query",
+			"description": description,
+			"schema": { "type": "array", "items": { "type": "string" } },
+			"style": "form",
+			"explode": true,
+		})
+	};
+	json!({
+		"ns": {
+			"name": "surreal-ns",
+			"in": "header",
+			"required": true,
+			"schema": { "type": "string" },
+		},
+		"db": {
+			"name": "surreal-db",
+			"in": "header",
+			"required": true,
+			"schema": { "type": "string" },
+		},
+		"id": {
+			"name": "id",
+			"in": "path",
+			"required": true,
+			"schema": { "type": "string" },
+		},
+		"where": {
+			"name": "where",
+			"in": "query",
+			"description": "A SurrealQL condition which the records must match",
+			"schema": { "type": "string" },
+		},
+		"order": list("order", "The fields to order by, which are descending when prefixed with '-'"),
+		"limit": {
+			"name": "limit",
+			"in": "query",
+			"description": "The maximum number of records to select",
+			"schema": { "type": "integer", "default": 100 },
+		},
+		"start": {
+			"name": "start",
+			"in": "query",
+			"description": "The number of records to skip",
+			"schema": { "type": "integer", "default": 0 },
+		},
+		"cursor": {
+			"name": "cursor",
+			"in": "query",
+			"description": "The id of the last record of the previous page",
+			"schema": { "type": "string" },
+		},
+		"fields": list("fields", "The fields to select, instead of the whole record"),
+		"fetch": list("fetch", "The record links to fetch"),
+	})
+}
+
+/// Describes the records of a table from the definitions of its top-level fields
+fn table_schema(tb: &DefineTableStatement, fields: &[DefineFieldStatement]) -> Json {
+	let mut properties = Map::new();
+	let mut required = vec![];
+	properties.insert("id".to_owned(), json!({ "type": "string", "readOnly": true }));
+	for fd in fields {
+		// Nested fields are described by the schema of their parent
+		let [Part::Field(name)] = fd.name.0.as_slice() else {
+			continue;
+		};
+		if name.0 == "id" {
+			continue;
+		}
+		let kind = fd.kind.as_ref().unwrap_or(&Kind::Any);
+		if !matches!(kind, Kind::Any | Kind::Null | Kind::Option(_)) {
+			required.push(name.0.clone());
+		}
+		properties.insert(name.0.clone(), kind_schema(kind));
+	}
+	let mut schema = json!({
+		"type": "object",
+		"properties": properties,
+		"additionalProperties": !tb.full,
+	});
+	if !required.is_empty() {
+		schema["required"] = json!(required);
+	}
+	schema
+}
+
+/// Describes the values of a kind, as they are represented in JSON
+fn kind_schema(kind: &Kind) -> Json {
+	match kind {
+		Kind::Bool => json!({ "type": "boolean" }),
+		Kind::Bytes => json!({ "type": "string", "format": "byte" }),
+		Kind::Datetime => json!({ "type": "string", "format": "date-time" }),
+		Kind::Decimal | Kind::Number => json!({ "type": "number" }),
+		Kind::Duration | Kind::String | Kind::Record(_) => json!({ "type": "string" }),
+		Kind::Float => json!({ "type": "number", "format": "double" }),
+		Kind::Int => json!({ "type": "integer", "format": "int64" }),
+		Kind::Object | Kind::Point | Kind::Geometry(_) => json!({ "type": "object" }),
+		Kind::Uuid => json!({ "type": "string", "format": "uuid" }),
+		Kind::Option(inner) => {
+			let mut schema = kind_schema(inner);
+			schema["nullable"] = json!(true);
+			schema
+		}
+		Kind::Either(kinds) => {
+			json!({ "oneOf": kinds.iter().map(kind_schema).collect::<Vec<_>>() })
+		}
+		Kind::Array(inner, max) | Kind::Set(inner, max) => {
+			let mut schema = json!({ "type": "array", "items": kind_schema(inner) });
+			if let Some(max) = max {
+				schema["maxItems"] = json!(max);
+			}
+			if matches!(kind, Kind::Set(..)) {
+				schema["uniqueItems"] = json!(true);
+			}
+			schema
+		}
+		_ => json!({}),
+	}
+}
@@ -0,0 +1,70 @@
+//! Parses the query parameters which are shared by the REST-style endpoints.
+
+use crate::err::Error;
+use crate::sql::{Cond, Expression, Fetch, Fetchs, Field, Fields, Part, Value};
+use crate::syn;
+
+/// Parses the fields to send, instead of the whole record
+pub(super) fn fields(fields: &[String]) -> Result<Fields, Error> {
+	Ok(Fields(
+		fields
+			.iter()
+			.map(|v| {
+				Ok(Field::Single {
+					expr: Value::Idiom(syn::idiom(v)?),
+					alias: None,
+				})
+			})
+			.collect::<Result<_, Error>>()?,
+		false,
+	))
+}
+
+/// Parses the record links to fetch
+pub(super) fn fetch(fields: &[String]) -> Result<Fetchs, Error> {
+	Ok(Fetchs(
+		fields
+			.iter()
+			.map(|v| Ok(Fetch(Value::Idiom(syn::idiom(v)?))))
+			.collect::<Result<_, Error>>()?,
+	))
+}
+
+/// Parses a condition, which can only read the fields of the records which it filters
+pub(super) fn cond(v: &str) -> Result<Cond, Error> {
+	let v = syn::value(v)?;
+	if !is_filter(&v) {
+		return Err(Error::InvalidCondition);
+	}
+	Ok(Cond(v))
+}
+
+/// Checks that a condition only reads the record which it filters, so that it can neither
+/// write data, nor select other records with a subquery or a graph traversal
+fn is_filter(v: &Value) -> bool {
+	match v {
+		Value::Idiom(v) => v.iter().all(|p| match p {
+			Part::Where(v) | Part::Value(v) | Part::Start(v) => is_filter(v),
+			Part::Method(_, args) => args.iter().all(is_filter),
+			Part::Graph(_) | Part::Destructure(_) => false,
+			_ => true,
+		}),
+		Value::Expression(v) => match v.as_ref() {
+			Expression::Unary {
+				v,
+				..
+			} => is_filter(v),
+			Expression::Binary {
+				l,
+				r,
+				..
+			} => is_filter(l) && is_filter(r),
+		},
+		Value::Array(v) => v.iter().all(is_filter),
+		Value::Object(v) => v.values().all(is_filter),
+		Value::Function(v) => !v.is_custom() && !v.is_script() && v.args().iter().all(is_filter),
+		Value::Cast(v) => is_filter(&v.1),
+		Value::Param(_) | Value::Constant(_) => true,
+		v => v.is_static(),
+	}
+}
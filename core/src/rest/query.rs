@@ -0,0 +1,110 @@
+use super::parse;
+use crate::err::Error;
+use crate::sql::statements::SelectStatement;
+use crate::sql::{
+	After, Fields, Function, Idiom, Limit, Order, Orders, Start, Table, Value, Values, With,
+};
+use crate::syn;
+use serde::Deserialize;
+
+/// The number of records which are selected when no limit is specified
+const DEFAULT_LIMIT: i64 = 100;
+
+/// The query parameters accepted when selecting records from a table
+#[derive(Default, Deserialize, Debug, Clone)]
+pub struct QueryOptions {
+	/// A condition which the records must match
+	#[serde(rename = "where")]
+	pub cond: Option<String>,
+	/// The fields to order by, which are descending when prefixed with `-`
+	pub order: Option<Vec<String>>,
+	/// The maximum number of records to select
+	pub limit: Option<i64>,
+	/// The number of records to skip
+	pub start: Option<i64>,
+	/// The id of the last record of the previous page
+	pub cursor: Option<String>,
+	/// The fields to select, instead of the whole record
+	pub fields: Option<Vec<String>>,
+	/// The record links to fetch
+	pub fetch: Option<Vec<String>>,
+}
+
+impl QueryOptions {
+	/// Compiles the options into a statement which selects from a table, or from a
+	/// single record of the table when an id is specified
+	pub fn select(&self, tb: &str, id: Option<Value>) -> Result<SelectStatement, Error> {
+		// Select from the table or record
+		let what = Values(vec![match id {
+			Some(id) => thing(tb, id),
+			None => Value::Table(Table::from(tb)),
+		}]);
+		// Select only the requested fields
+		let expr = match &self.fields {
+			Some(fields) => parse::fields(fields)?,
+			None => Fields::all(),
+		};
+		// Filter the records with the condition
+		let cond = self.cond.as_deref().map(parse::cond).transpose()?;
+		// Order the records by the requested fields
+		let order = match &self.order {
+			Some(fields) => {
+				fields.iter().map(String::as_str).map(parse_order).collect::<Result<_, _>>()?
+			}
+			None => vec![],
+		};
+		// Resume after the last record of the previous page
+		let after = match &self.cursor {
+			Some(cursor) => {
+				// A cursor only marks a position when ordering by id
+				match order.as_slice() {
+					[] => {}
+					[v] if v.order == Idiom::from("id") && v.direction => {}
+					_ => return Err(Error::InvalidCursor),
+				}
+				// Parse the record id as a SurrealQL value
+				let key = syn::json(cursor).unwrap_or_else(|_| Value::from(cursor.as_str()));
+				Some(After(thing(tb, key)))
+			}
+			None => None,
+		};
+		// Fetch the requested record links
+		let fetch = self.fetch.as_deref().map(parse::fetch).transpose()?;
+		Ok(SelectStatement {
+			expr,
+			what,
+			cond,
+			order: match order.is_empty() {
+				true => None,
+				false => Some(Orders(order)),
+			},
+			limit: Some(Limit(Value::from(self.limit.unwrap_or(DEFAULT_LIMIT)))),
+			start: Some(Start(Value::from(self.start.unwrap_or(0)))),
+			// The table is scanned in the order of the record ids from the cursor
+			with: after.as_ref().map(|_| With::NoIndex),
+			after,
+			fetch,
+			..Default::default()
+		})
+	}
+}
+
+/// Creates a value which computes the id of a record in a table
+fn thing(tb: &str, id: Value) -> Value {
+	Value::from(Function::Normal("type::thing".to_owned(), vec![Value::from(tb), id]))
+}
+
+/// Parses a field to order by, which is descending when prefixed with `-`
+fn parse_order(v: &str) -> Result<Order, Error> {
+	let (v, direction) = match v.strip_prefix('-') {
+		Some(v) => (v, false),
+		None => (v, true),
+	};
+	Ok(Order {
+		order: syn::idiom(v.trim())?,
+		random: false,
+		collate: false,
+		numeric: false,
+		direction,
+	})
+}
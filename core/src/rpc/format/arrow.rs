@@ -0,0 +1,377 @@
+//! Arrow IPC streams, for loading query results into analytics tools.
+//!
+//! Records are converted into record batches, with a column for each of their fields. The
+//! type of each column is taken from the field definitions when the records belong to a
+//! SCHEMAFULL table, which is found from their ids, and is otherwise inferred from the values
+//! in the column. Values without an Arrow equivalent, such as record ids and objects, are
+//! written as text.
+
+use crate::dbs::Session;
+use crate::err::Error;
+use crate::kvs::Datastore;
+use crate::kvs::LockType::*;
+use crate::kvs::TransactionType::*;
+use crate::rpc::{Data, RpcError};
+use crate::sql::statements::DefineFieldStatement;
+use crate::sql::{Kind, Number, Object, Part, Value};
+use arrow_array::{
+	ArrayRef, BinaryArray, BooleanArray, DurationNanosecondArray, Float64Array, Int64Array,
+	NullArray, RecordBatch, RecordBatchOptions, StringArray, TimestampNanosecondArray,
+};
+use arrow_ipc::writer::StreamWriter;
+use arrow_schema::{ArrowError, DataType, Field, Schema, TimeUnit};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::ResTrait;
+
+/// The number of rows written in each record batch
+const BATCH_SIZE: usize = 8192;
+
+/// The type of the values in a column
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Column {
+	Bool,
+	Int,
+	Float,
+	Text,
+	Datetime,
+	Duration,
+	Bytes,
+}
+
+impl Column {
+	/// The column type of a field definition, if the kind maps to a single Arrow type
+	fn from_kind(kind: &Kind) -> Option<Self> {
+		match kind {
+			Kind::Bool => Some(Self::Bool),
+			Kind::Int => Some(Self::Int),
+			Kind::Float => Some(Self::Float),
+			Kind::Decimal | Kind::String | Kind::Uuid | Kind::Record(_) => Some(Self::Text),
+			Kind::Datetime => Some(Self::Datetime),
+			Kind::Duration => Some(Self::Duration),
+			Kind::Bytes => Some(Self::Bytes),
+			Kind::Option(kind) => Self::from_kind(kind),
+			_ => None,
+		}
+	}
+
+	/// The column type of a value, unless the value is empty
+	fn from_value(val: &Value) -> Option<Self> {
+		match val {
+			Value::None | Value::Null => None,
+			Value::Bool(_) => Some(Self::Bool),
+			Value::Number(Number::Int(_)) => Some(Self::Int),
+			Value::Number(Number::Float(_)) => Some(Self::Float),
+			Value::Datetime(_) => Some(Self::Datetime),
+			Value::Duration(_) => Some(Self::Duration),
+			Value::Bytes(_) => Some(Self::Bytes),
+			_ => Some(Self::Text),
+		}
+	}
+
+	/// Combines the types of two values in the same column
+	fn merge(self, other: Self) -> Option<Self> {
+		match (self, other) {
+			(a, b) if a == b => Some(a),
+			(Self::Int, Self::Float) | (Self::Float, Self::Int) => Some(Self::Float),
+			_ => None,
+		}
+	}
+}
+
+/// The Arrow type of a column, where columns without any values are null
+fn data_type(ty: Option<Column>) -> DataType {
+	match ty {
+		None => DataType::Null,
+		Some(Column::Bool) => DataType::Boolean,
+		Some(Column::Int) => DataType::Int64,
+		Some(Column::Float) => DataType::Float64,
+		Some(Column::Text) => DataType::Utf8,
+		Some(Column::Datetime) => DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into())),
+		Some(Column::Duration) => DataType::Duration(TimeUnit::Nanosecond),
+		Some(Column::Bytes) => DataType::Binary,
+	}
+}
+
+/// Converts a value into the text stored in a text column
+fn text(val: &Value) -> String {
+	match val {
+		Value::Object(_) | Value::Array(_) | Value::Geometry(_) => {
+			val.clone().into_json().to_string()
+		}
+		_ => val.to_raw_string(),
+	}
+}
+
+/// Builds the array of a column for a batch of rows
+fn array(ty: Option<Column>, name: &str, rows: &[Object]) -> Result<ArrayRef, Error> {
+	let vals = rows.iter().map(|row| row.get(name));
+	Ok(match ty {
+		None => Arc::new(NullArray::new(rows.len())),
+		Some(Column::Bool) => Arc::new(BooleanArray::from_iter(vals.map(|v| match v {
+			Some(Value::Bool(v)) => Some(*v),
+			_ => None,
+		}))),
+		Some(Column::Int) => Arc::new(Int64Array::from_iter(vals.map(|v| match v {
+			Some(Value::Number(Number::Int(v))) => Some(*v),
+			_ => None,
+		}))),
+		Some(Column::Float) => Arc::new(Float64Array::from_iter(vals.map(|v| match v {
+			Some(Value::Number(v)) => Some(v.to_float()),
+			_ => None,
+		}))),
+		Some(Column::Text) => Arc::new(StringArray::from_iter(vals.map(|v| match v {
+			None | Some(Value::None | Value::Null) => None,
+			Some(v) => Some(text(v)),
+		}))),
+		Some(Column::Datetime) => {
+			let vals = vals
+				.map(|v| match v {
+					Some(Value::Datetime(v)) => {
+						v.0.timestamp_nanos_opt().map(Some).ok_or_else(|| {
+							Error::ArrowConversion(format!("The datetime '{v}' is out of range"))
+						})
+					}
+					_ => Ok(None),
+				})
+				.collect::<Result<Vec<_>, _>>()?;
+			Arc::new(TimestampNanosecondArray::from(vals).with_timezone("UTC"))
+		}
+		Some(Column::Duration) => {
+			let vals = vals
+				.map(|v| match v {
+					Some(Value::Duration(v)) => {
+						i64::try_from(v.0.as_nanos()).map(Some).map_err(|_| {
+							Error::ArrowConversion(format!("The duration '{v}' is out of range"))
+						})
+					}
+					_ => Ok(None),
+				})
+				.collect::<Result<Vec<_>, _>>()?;
+			Arc::new(DurationNanosecondArray::from(vals))
+		}
+		Some(Column::Bytes) => Arc::new(BinaryArray::from_iter(vals.map(|v| match v {
+			Some(Value::Bytes(v)) => Some(v.0.as_slice()),
+			_ => None,
+		}))),
+	})
+}
+
+/// Converts errors raised while writing Arrow data
+fn arrow_error(err: ArrowError) -> Error {
+	Error::ArrowConversion(err.to_string())
+}
+
+/// Converts a list of records into an Arrow IPC stream
+///
+/// Columns are given the types of any top-level fields in the definitions, and all other
+/// columns are inferred. The `id` column comes first, followed by the defined fields in the
+/// order they were defined, and then any other fields in the order they appear.
+pub fn encode(
+	val: Value,
+	fields: &[DefineFieldStatement],
+	metadata: HashMap<String, String>,
+) -> Result<Vec<u8>, Error> {
+	// Collect the records which make up the rows
+	let rows: Vec<Object> = match val {
+		Value::None | Value::Null => vec![],
+		Value::Object(v) => vec![v],
+		Value::Array(v) => v
+			.into_iter()
+			.map(|v| match v {
+				Value::Object(v) => Ok(v),
+				v => Err(Error::ArrowConversion(format!(
+					"Expected a list of records, but found {}",
+					v.kindof()
+				))),
+			})
+			.collect::<Result<_, _>>()?,
+		v => {
+			return Err(Error::ArrowConversion(format!(
+				"Expected a list of records, but found {}",
+				v.kindof()
+			)))
+		}
+	};
+	// Find the column types of the defined fields
+	let defined: Vec<(&str, Option<Column>)> = fields
+		.iter()
+		.filter_map(|fd| match fd.name.0.as_slice() {
+			[Part::Field(name)] => {
+				Some((name.0.as_str(), fd.kind.as_ref().and_then(Column::from_kind)))
+			}
+			_ => None,
+		})
+		.collect();
+	// Order the columns which appear in the rows
+	let mut names: Vec<&str> = vec![];
+	if rows.iter().any(|row| row.contains_key("id")) {
+		names.push("id");
+	}
+	for (name, _) in defined.iter() {
+		if !names.contains(name) && rows.iter().any(|row| row.contains_key(*name)) {
+			names.push(*name);
+		}
+	}
+	for row in rows.iter() {
+		for name in row.keys() {
+			if !names.contains(&name.as_str()) {
+				names.push(name);
+			}
+		}
+	}
+	// Check that each column holds values of a single type
+	let mut columns: Vec<(&str, Option<Column>)> = Vec::with_capacity(names.len());
+	for name in names {
+		let mut ty = defined.iter().find(|(v, _)| *v == name).and_then(|(_, ty)| *ty);
+		for other in rows.iter().filter_map(|row| row.get(name)).filter_map(Column::from_value) {
+			ty = match ty {
+				None => Some(other),
+				Some(ty) => Some(ty.merge(other).ok_or_else(|| {
+					Error::ArrowConversion(format!(
+						"The field '{name}' contains values of different types"
+					))
+				})?),
+			};
+		}
+		columns.push((name, ty));
+	}
+	// Create the schema, where any field can be missing
+	let schema = Arc::new(Schema::new_with_metadata(
+		columns
+			.iter()
+			.map(|(name, ty)| Field::new(*name, data_type(*ty), true))
+			.collect::<Vec<_>>(),
+		metadata,
+	));
+	// Write the rows in batches
+	let mut writer = StreamWriter::try_new(Vec::new(), &schema).map_err(arrow_error)?;
+	for rows in rows.chunks(BATCH_SIZE) {
+		let arrays = columns
+			.iter()
+			.map(|(name, ty)| array(*ty, name, rows))
+			.collect::<Result<Vec<_>, _>>()?;
+		let options = RecordBatchOptions::new().with_row_count(Some(rows.len()));
+		let batch = RecordBatch::try_new_with_options(schema.clone(), arrays, &options)
+			.map_err(arrow_error)?;
+		writer.write(&batch).map_err(arrow_error)?;
+	}
+	writer.into_inner().map_err(arrow_error)
+}
+
+/// Fetches the field definitions of the table which a list of records belongs to, when the
+/// records were all selected from the same SCHEMAFULL table
+pub async fn fields(
+	kvs: &Datastore,
+	sess: &Session,
+	val: &Value,
+) -> Result<Arc<[DefineFieldStatement]>, Error> {
+	// Find the table of the records from their ids
+	let table = |v: &Value| match v {
+		Value::Object(v) => match v.get("id") {
+			Some(Value::Thing(id)) => Some(id.tb.clone()),
+			_ => None,
+		},
+		_ => None,
+	};
+	let tb = match val {
+		Value::Array(v) => {
+			let mut tables = v.iter().map(table);
+			match tables.next() {
+				Some(Some(tb)) if tables.all(|v| v.as_ref() == Some(&tb)) => tb,
+				_ => return Ok(Arc::from([])),
+			}
+		}
+		v => match table(v) {
+			Some(tb) => tb,
+			None => return Ok(Arc::from([])),
+		},
+	};
+	let (Some(ns), Some(db)) = (sess.ns.as_deref(), sess.db.as_deref()) else {
+		return Ok(Arc::from([]));
+	};
+	// Fetch the field definitions of the table
+	let txn = kvs.transaction(Read, Optimistic).await?;
+	let res = match txn.get_tb(ns, db, &tb).await {
+		Ok(def) if def.full => txn.all_tb_fields(ns, db, &tb).await,
+		_ => Ok(Arc::from([])),
+	};
+	txn.cancel().await?;
+	res
+}
+
+/// Wraps a result which is not a list of records, so that it is returned in a single column
+fn records(val: Value) -> Value {
+	match val {
+		v @ (Value::None | Value::Null | Value::Object(_)) => v,
+		Value::Array(v) if v.iter().all(Value::is_object) => Value::Array(v),
+		v => Value::from(Object::from(map! {
+			"result".to_owned() => v,
+		})),
+	}
+}
+
+/// Converts a result into an Arrow IPC stream, using the field definitions of its table
+async fn stream(kvs: &Datastore, sess: &Session, val: Value) -> Result<Value, Error> {
+	let val = records(val);
+	let fields = fields(kvs, sess, &val).await?;
+	let out = encode(val, &fields, HashMap::new())?;
+	Ok(Value::Bytes(out.into()))
+}
+
+/// Converts the result of a request into Arrow IPC streams, before the response is serialized
+///
+/// Each statement of a query is converted into its own stream, so that its records are
+/// written with the field definitions of their table. The response then holds a row for
+/// each statement, with its stream in the `result` column, or its error in the `error` column.
+pub async fn results(kvs: &Datastore, sess: &Session, data: Data) -> Result<Data, Error> {
+	match data {
+		Data::Query(res) => {
+			let mut out = Vec::with_capacity(res.len());
+			for res in res {
+				let time = res.speed();
+				let mut row = Object::default();
+				let res = match res.output() {
+					Ok(v) => stream(kvs, sess, v).await,
+					Err(e) => Err(e),
+				};
+				match res {
+					Ok(v) => {
+						row.insert("status".to_owned(), Value::from("OK"));
+						row.insert("result".to_owned(), v);
+					}
+					Err(e) => {
+						row.insert("status".to_owned(), Value::from("ERR"));
+						row.insert("error".to_owned(), Value::from(e.to_string()));
+					}
+				}
+				row.insert("time".to_owned(), Value::from(time));
+				out.push(Value::from(row));
+			}
+			Ok(Data::Other(Value::from(out)))
+		}
+		Data::Other(v) => Ok(Data::Other(stream(kvs, sess, v).await?)),
+		data => Ok(data),
+	}
+}
+
+pub fn res(res: impl ResTrait) -> Result<Vec<u8>, RpcError> {
+	// Convert the response into a value
+	let val: Value = res.into();
+	let mut val = match val {
+		Value::Object(v) => v,
+		_ => Object::default(),
+	};
+	// The response envelope is stored in the schema metadata
+	let mut metadata = HashMap::new();
+	for key in ["id", "error"] {
+		if let Some(v) = val.remove(key) {
+			metadata.insert(key.to_owned(), v.into_json().to_string());
+		}
+	}
+	// Results which are not records are returned in a single column
+	let val = val.remove("result").map(records).unwrap_or_default();
+	// Serialize the result into an Arrow IPC stream
+	encode(val, &[], metadata).map_err(|e| RpcError::Thrown(e.to_string()))
+}
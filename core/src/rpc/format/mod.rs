@@ -1,3 +1,5 @@
+#[cfg(feature = "arrow")]
+pub mod arrow;
 mod bincode;
 pub mod cbor;
 mod json;
@@ -10,6 +12,7 @@ use serde::Serialize;
 use super::{request::Request, RpcError};
 use crate::sql::Value;
 
+#[cfg(not(feature = "arrow"))]
 pub const PROTOCOLS: [&str; 5] = [
 	"json",     // For basic JSON serialisation
 	"cbor",     // For basic CBOR serialisation
@@ -18,6 +21,16 @@ pub const PROTOCOLS: [&str; 5] = [
 	"revision", // For full versioned serialisation
 ];
 
+#[cfg(feature = "arrow")]
+pub const PROTOCOLS: [&str; 6] = [
+	"json",     // For basic JSON serialisation
+	"cbor",     // For basic CBOR serialisation
+	"msgpack",  // For basic Msgpack serialisation
+	"bincode",  // For full internal serialisation
+	"revision", // For full versioned serialisation
+	"arrow",    // For columnar Arrow IPC responses
+];
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 #[non_exhaustive]
 pub enum Format {
@@ -27,6 +40,7 @@ pub enum Format {
 	Msgpack,     // For basic Msgpack serialisation
 	Bincode,     // For full internal serialisation
 	Revision,    // For full versioned serialisation
+	Arrow,       // For columnar Arrow IPC responses
 	Unsupported, // Unsupported format
 }
 
@@ -41,6 +55,8 @@ impl From<&str> for Format {
 			s if s == PROTOCOLS[2] => Format::Msgpack,
 			s if s == PROTOCOLS[3] => Format::Bincode,
 			s if s == PROTOCOLS[4] => Format::Revision,
+			#[cfg(feature = "arrow")]
+			s if s == PROTOCOLS[5] => Format::Arrow,
 			_ => Format::None,
 		}
 	}
@@ -63,6 +79,8 @@ impl Format {
 			Self::Msgpack => msgpack::req(val),
 			Self::Bincode => bincode::req(&val),
 			Self::Revision => revision::req(val),
+			// Requests are not tabular, so are sent as JSON
+			Self::Arrow => json::req(&val),
 		}
 		.map_err(Into::into)
 	}
@@ -77,6 +95,10 @@ impl Format {
 			Self::Msgpack => msgpack::res(val),
 			Self::Bincode => bincode::res(val),
 			Self::Revision => revision::res(val),
+			#[cfg(feature = "arrow")]
+			Self::Arrow => arrow::res(val),
+			#[cfg(not(feature = "arrow"))]
+			Self::Arrow => Err(RpcError::InvalidRequest),
 		}
 	}
 }
@@ -26,6 +26,15 @@ pub enum Method {
 	Run,
 	GraphQL,
 	InsertRelation,
+	Begin,
+	Commit,
+	Cancel,
+	Abort,
+	Prepare,
+	ExecutePrepared,
+	Unprepare,
+	Batch,
+	SessionClose,
 }
 
 impl Method {
@@ -59,6 +68,15 @@ impl Method {
 			"run" => Self::Run,
 			"graphql" => Self::GraphQL,
 			"insert_relation" => Self::InsertRelation,
+			"begin" => Self::Begin,
+			"commit" => Self::Commit,
+			"cancel" => Self::Cancel,
+			"abort" => Self::Abort,
+			"prepare" => Self::Prepare,
+			"execute_prepared" => Self::ExecutePrepared,
+			"unprepare" => Self::Unprepare,
+			"batch" => Self::Batch,
+			"session_close" => Self::SessionClose,
 			_ => Self::Unknown,
 		}
 	}
@@ -93,6 +111,15 @@ impl Method {
 			Self::Run => "run",
 			Self::GraphQL => "graphql",
 			Self::InsertRelation => "insert_relation",
+			Self::Begin => "begin",
+			Self::Commit => "commit",
+			Self::Cancel => "cancel",
+			Self::Abort => "abort",
+			Self::Prepare => "prepare",
+			Self::ExecutePrepared => "execute_prepared",
+			Self::Unprepare => "unprepare",
+			Self::Batch => "batch",
+			Self::SessionClose => "session_close",
 		}
 	}
 }
@@ -111,15 +138,57 @@ impl Method {
 		matches!(
 			self,
 			Method::Ping
-				| Method::Info | Method::Select
-				| Method::Insert | Method::Create
-				| Method::Update | Method::Upsert
-				| Method::Merge | Method::Patch
-				| Method::Delete | Method::Version
-				| Method::Query | Method::Relate
-				| Method::Run | Method::GraphQL
+				| Method::Info
+				| Method::Select
+				| Method::Insert
+				| Method::Create
+				| Method::Update
+				| Method::Upsert
+				| Method::Merge
+				| Method::Patch
+				| Method::Delete
+				| Method::Version
+				| Method::Query
+				| Method::Relate
+				| Method::Run
+				| Method::GraphQL
 				| Method::InsertRelation
+				| Method::Begin
+				| Method::Commit
+				| Method::Cancel
+				| Method::Abort
+				| Method::Prepare
+				| Method::ExecutePrepared
+				| Method::Unprepare
+				| Method::Batch
 				| Method::Unknown
 		)
 	}
+
+	// should be the same as the methods which accept a transaction in execute
+	pub fn can_be_in_txn(&self) -> bool {
+		matches!(
+			self,
+			Method::Info
+				| Method::Select
+				| Method::Insert
+				| Method::Create
+				| Method::Update
+				| Method::Upsert
+				| Method::Merge
+				| Method::Patch
+				| Method::Delete
+				| Method::Query
+				| Method::Relate
+				| Method::Run
+				| Method::InsertRelation
+				| Method::ExecutePrepared
+				| Method::Batch
+		)
+	}
+
+	// should be the same as the methods which are handled in execute_stream
+	pub fn can_be_streamed(&self) -> bool {
+		matches!(self, Method::Select | Method::Query)
+	}
 }
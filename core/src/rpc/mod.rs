@@ -1,13 +1,17 @@
 pub mod args;
 pub mod basic_context;
+mod failure;
 pub mod format;
 pub mod method;
+pub mod prepared;
 pub mod request;
 mod response;
 pub mod rpc_context;
 mod rpc_error;
 
 pub use basic_context::BasicRpcContext;
+pub use failure::Failure;
+pub use prepared::Prepared;
 pub use response::Data;
 pub use rpc_context::RpcContext;
 pub use rpc_error::RpcError;
@@ -0,0 +1,89 @@
+//! Queries which are parsed once, and executed many times on the same connection.
+//!
+//! Each prepared query is identified by a random id. Only the parsed query is kept, so the
+//! query is checked against the schema each time it is executed. Queries are prepared for the
+//! actor of the session, and the session is checked again if the connection has since
+//! authenticated as a different actor.
+
+use crate::cnf::MAX_PREPARED_QUERIES;
+use crate::dbs::Session;
+use crate::err::Error;
+use crate::iam::Auth;
+use crate::kvs::Datastore;
+use crate::sql::Query;
+use crate::syn;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use uuid::Uuid;
+
+/// A query which has been parsed, along with the actor which prepared it
+struct Statement {
+	// The parsed query
+	query: Query,
+	// The actor which prepared the query
+	auth: Arc<Auth>,
+}
+
+/// The queries which have been prepared on a connection
+#[derive(Default)]
+pub struct Prepared {
+	statements: Mutex<HashMap<Uuid, Statement>>,
+}
+
+impl Prepared {
+	/// Parses a query for the actor of the session, returning the id to execute it with
+	pub async fn prepare(
+		&self,
+		kvs: &Datastore,
+		sess: &Session,
+		text: &str,
+	) -> Result<Uuid, Error> {
+		// Check if the session can execute queries
+		kvs.check_session(sess).await?;
+		// Parse the SQL query text
+		let query = syn::parse(text)?;
+		let mut statements = self.statements.lock().unwrap_or_else(PoisonError::into_inner);
+		// Each connection can only prepare a limited number of queries
+		if statements.len() >= *MAX_PREPARED_QUERIES {
+			return Err(Error::PreparedLimit(*MAX_PREPARED_QUERIES));
+		}
+		let id = Uuid::new_v4();
+		statements.insert(
+			id,
+			Statement {
+				query,
+				auth: sess.au.clone(),
+			},
+		);
+		Ok(id)
+	}
+
+	/// Fetches a prepared query, checking the session again if the actor has changed
+	pub async fn get(&self, kvs: &Datastore, sess: &Session, id: Uuid) -> Result<Query, Error> {
+		let (query, checked) = {
+			let statements = self.statements.lock().unwrap_or_else(PoisonError::into_inner);
+			match statements.get(&id) {
+				Some(v) => (v.query.clone(), v.auth == sess.au),
+				None => return Err(Error::PreparedNotFound(id.to_string())),
+			}
+		};
+		if !checked {
+			// Check if the new actor can execute queries
+			kvs.check_session(sess).await?;
+			let mut statements = self.statements.lock().unwrap_or_else(PoisonError::into_inner);
+			if let Some(v) = statements.get_mut(&id) {
+				v.auth = sess.au.clone();
+			}
+		}
+		Ok(query)
+	}
+
+	/// Removes a prepared query, so that it can no longer be executed
+	pub fn remove(&self, id: Uuid) -> Result<(), Error> {
+		let mut statements = self.statements.lock().unwrap_or_else(PoisonError::into_inner);
+		match statements.remove(&id) {
+			Some(_) => Ok(()),
+			None => Err(Error::PreparedNotFound(id.to_string())),
+		}
+	}
+}
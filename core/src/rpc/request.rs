@@ -4,16 +4,23 @@ use crate::rpc::RpcError;
 use crate::sql::Part;
 use crate::sql::{Array, Value};
 use std::sync::LazyLock;
+use uuid::Uuid;
 
 pub static ID: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("id")]);
 pub static METHOD: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("method")]);
 pub static PARAMS: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("params")]);
+pub static TXN: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("txn")]);
+pub static SESSION: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("session")]);
+pub static STREAM: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("stream")]);
 
 #[derive(Debug)]
 pub struct Request {
 	pub id: Option<Value>,
 	pub method: String,
 	pub params: Array,
+	pub txn: Option<Uuid>,
+	pub session: Option<Uuid>,
+	pub stream: bool,
 }
 
 impl TryFrom<Cbor> for Request {
@@ -53,11 +60,39 @@ impl TryFrom<Value> for Request {
 			Value::Array(v) => v,
 			_ => Array::new(),
 		};
+		// Fetch the 'txn' argument
+		let txn = match val.pick(&*TXN) {
+			Value::None | Value::Null => None,
+			v => Some(parse_uuid(v).ok_or(RpcError::InvalidRequest)?),
+		};
+		// Fetch the 'session' argument
+		let session = match val.pick(&*SESSION) {
+			Value::None | Value::Null => None,
+			v => Some(parse_uuid(v).ok_or(RpcError::InvalidRequest)?),
+		};
+		// Fetch the 'stream' argument
+		let stream = match val.pick(&*STREAM) {
+			Value::None | Value::Null => false,
+			Value::Bool(v) => v,
+			_ => return Err(RpcError::InvalidRequest),
+		};
 		// Return the parsed request
 		Ok(Request {
 			id,
 			method,
 			params,
+			txn,
+			session,
+			stream,
 		})
 	}
 }
+
+/// Parses the id of a transaction or session, specified as a uuid or a string
+pub(crate) fn parse_uuid(val: Value) -> Option<Uuid> {
+	match val {
+		Value::Uuid(v) => Some(v.0),
+		Value::Strand(v) => Uuid::try_parse(v.as_str()).ok(),
+		_ => None,
+	}
+}
@@ -18,6 +18,8 @@ pub enum Data {
 	Query(Vec<dbs::Response>),
 	/// Live queries return a notification
 	Live(Notification),
+	/// Streamed requests return batches of rows before their response
+	Rows(dbs::Batch),
 	// Add new variants here
 }
 
@@ -39,6 +41,12 @@ impl From<Notification> for Data {
 	}
 }
 
+impl From<dbs::Batch> for Data {
+	fn from(v: dbs::Batch) -> Self {
+		Data::Rows(v)
+	}
+}
+
 impl From<Vec<dbs::Response>> for Data {
 	fn from(v: Vec<dbs::Response>) -> Self {
 		Data::Query(v)
@@ -50,6 +58,7 @@ impl From<Data> for Value {
 		match val {
 			Data::Query(v) => sql::to_value(v).unwrap(),
 			Data::Live(v) => sql::to_value(v).unwrap(),
+			Data::Rows(v) => sql::to_value(v).unwrap(),
 			Data::Other(v) => v,
 		}
 	}
@@ -1,19 +1,28 @@
-use std::{collections::BTreeMap, mem};
+use std::{borrow::Cow, collections::BTreeMap, mem};
 
 #[cfg(all(not(target_arch = "wasm32"), surrealdb_unstable))]
 use async_graphql::BatchRequest;
+use channel::Sender;
 use uuid::Uuid;
 
 #[cfg(all(not(target_arch = "wasm32"), surrealdb_unstable))]
 use crate::gql::SchemaCache;
 use crate::{
-	dbs::{QueryType, Response, Session},
+	ctx::Canceller,
+	dbs::{QueryType, Response, Row, Session},
 	kvs::Datastore,
 	rpc::args::Take,
-	sql::{Array, Function, Model, Statement, Strand, Value},
+	sql::{Array, Function, Model, Object, Query, Statement, Strand, Value},
 };
 
-use super::{method::Method, response::Data, rpc_error::RpcError};
+use super::{
+	failure::Failure,
+	method::Method,
+	prepared::Prepared,
+	request::{parse_uuid, Request},
+	response::Data,
+	rpc_error::RpcError,
+};
 
 #[allow(async_fn_in_trait)]
 pub trait RpcContext {
@@ -32,6 +41,19 @@ pub trait RpcContext {
 		async { unimplemented!("handle functions must be redefined if LQ_SUPPORT = true") }
 	}
 
+	/// The canceller of the request which is being processed, when requests can be cancelled
+	fn canceller(&self) -> Option<Canceller> {
+		None
+	}
+	/// Cancels a request which is being processed, returning whether the request was found
+	fn handle_cancel(&self, _id: &Value) -> impl std::future::Future<Output = bool> + Send {
+		async { false }
+	}
+	/// The queries prepared on this connection, when queries can be prepared
+	fn prepared(&self) -> Option<&Prepared> {
+		None
+	}
+
 	#[cfg(all(not(target_arch = "wasm32"), surrealdb_unstable))]
 	const GQL_SUPPORT: bool = false;
 
@@ -40,10 +62,19 @@ pub trait RpcContext {
 		unimplemented!("graphql_schema_cache must be implemented if GQL_SUPPORT = true")
 	}
 
-	async fn execute(&mut self, method: Method, params: Array) -> Result<Data, RpcError> {
+	async fn execute(
+		&mut self,
+		txn: Option<Uuid>,
+		method: Method,
+		params: Array,
+	) -> Result<Data, RpcError> {
+		// Only methods which query data can run within a transaction
+		if txn.is_some() && !method.can_be_in_txn() {
+			return Err(RpcError::InvalidRequest);
+		}
 		match method {
 			Method::Ping => Ok(Value::None.into()),
-			Method::Info => self.info().await.map(Into::into).map_err(Into::into),
+			Method::Info => self.info(txn).await.map(Into::into).map_err(Into::into),
 			Method::Use => self.yuse(params).await.map(Into::into).map_err(Into::into),
 			Method::Signup => self.signup(params).await.map(Into::into).map_err(Into::into),
 			Method::Signin => self.signin(params).await.map(Into::into).map_err(Into::into),
@@ -55,51 +86,114 @@ pub trait RpcContext {
 			Method::Live => self.live(params).await.map(Into::into).map_err(Into::into),
 			Method::Set => self.set(params).await.map(Into::into).map_err(Into::into),
 			Method::Unset => self.unset(params).await.map(Into::into).map_err(Into::into),
-			Method::Select => self.select(params).await.map(Into::into).map_err(Into::into),
-			Method::Insert => self.insert(params).await.map(Into::into).map_err(Into::into),
-			Method::Create => self.create(params).await.map(Into::into).map_err(Into::into),
-			Method::Upsert => self.upsert(params).await.map(Into::into).map_err(Into::into),
-			Method::Update => self.update(params).await.map(Into::into).map_err(Into::into),
-			Method::Merge => self.merge(params).await.map(Into::into).map_err(Into::into),
-			Method::Patch => self.patch(params).await.map(Into::into).map_err(Into::into),
-			Method::Delete => self.delete(params).await.map(Into::into).map_err(Into::into),
+			Method::Select => self.select(txn, params).await.map(Into::into).map_err(Into::into),
+			Method::Insert => self.insert(txn, params).await.map(Into::into).map_err(Into::into),
+			Method::Create => self.create(txn, params).await.map(Into::into).map_err(Into::into),
+			Method::Upsert => self.upsert(txn, params).await.map(Into::into).map_err(Into::into),
+			Method::Update => self.update(txn, params).await.map(Into::into).map_err(Into::into),
+			Method::Merge => self.merge(txn, params).await.map(Into::into).map_err(Into::into),
+			Method::Patch => self.patch(txn, params).await.map(Into::into).map_err(Into::into),
+			Method::Delete => self.delete(txn, params).await.map(Into::into).map_err(Into::into),
 			Method::Version => self.version(params).await.map(Into::into).map_err(Into::into),
-			Method::Query => self.query(params).await.map(Into::into).map_err(Into::into),
-			Method::Relate => self.relate(params).await.map(Into::into).map_err(Into::into),
-			Method::Run => self.run(params).await.map(Into::into).map_err(Into::into),
+			Method::Query => self.query(txn, params).await.map(Into::into).map_err(Into::into),
+			Method::Relate => self.relate(txn, params).await.map(Into::into).map_err(Into::into),
+			Method::Run => self.run(txn, params).await.map(Into::into).map_err(Into::into),
 			Method::GraphQL => self.graphql(params).await.map(Into::into).map_err(Into::into),
 			Method::InsertRelation => {
-				self.insert_relation(params).await.map(Into::into).map_err(Into::into)
+				self.insert_relation(txn, params).await.map(Into::into).map_err(Into::into)
 			}
+			Method::Begin => self.begin(params).await.map(Into::into).map_err(Into::into),
+			Method::Commit => self.commit(params).await.map(Into::into).map_err(Into::into),
+			Method::Cancel => self.cancel(params).await.map(Into::into).map_err(Into::into),
+			Method::Abort => self.abort(params).await.map(Into::into).map_err(Into::into),
+			Method::Prepare => self.prepare(params).await.map(Into::into).map_err(Into::into),
+			Method::ExecutePrepared => {
+				self.execute_prepared(txn, params).await.map(Into::into).map_err(Into::into)
+			}
+			Method::Unprepare => self.unprepare(params).await.map(Into::into).map_err(Into::into),
+			Method::Batch => self.batch(txn, params).await,
+			// Sessions are closed by the connection which holds them
+			Method::SessionClose => Err(RpcError::InvalidRequest),
 			Method::Unknown => Err(RpcError::MethodNotFound),
 		}
 	}
 
-	async fn execute_immut(&self, method: Method, params: Array) -> Result<Data, RpcError> {
+	async fn execute_immut(
+		&self,
+		txn: Option<Uuid>,
+		method: Method,
+		params: Array,
+	) -> Result<Data, RpcError> {
+		match method {
+			Method::Batch => self.batch(txn, params).await,
+			method => self.execute_call(txn, method, params).await,
+		}
+	}
+
+	/// Executes a single method which does not change the state of the connection
+	async fn execute_call(
+		&self,
+		txn: Option<Uuid>,
+		method: Method,
+		params: Array,
+	) -> Result<Data, RpcError> {
+		// Only methods which query data can run within a transaction
+		if txn.is_some() && !method.can_be_in_txn() {
+			return Err(RpcError::InvalidRequest);
+		}
 		match method {
 			Method::Ping => Ok(Value::None.into()),
-			Method::Info => self.info().await.map(Into::into).map_err(Into::into),
-			Method::Select => self.select(params).await.map(Into::into).map_err(Into::into),
-			Method::Insert => self.insert(params).await.map(Into::into).map_err(Into::into),
-			Method::Create => self.create(params).await.map(Into::into).map_err(Into::into),
-			Method::Upsert => self.upsert(params).await.map(Into::into).map_err(Into::into),
-			Method::Update => self.update(params).await.map(Into::into).map_err(Into::into),
-			Method::Merge => self.merge(params).await.map(Into::into).map_err(Into::into),
-			Method::Patch => self.patch(params).await.map(Into::into).map_err(Into::into),
-			Method::Delete => self.delete(params).await.map(Into::into).map_err(Into::into),
+			Method::Info => self.info(txn).await.map(Into::into).map_err(Into::into),
+			Method::Select => self.select(txn, params).await.map(Into::into).map_err(Into::into),
+			Method::Insert => self.insert(txn, params).await.map(Into::into).map_err(Into::into),
+			Method::Create => self.create(txn, params).await.map(Into::into).map_err(Into::into),
+			Method::Upsert => self.upsert(txn, params).await.map(Into::into).map_err(Into::into),
+			Method::Update => self.update(txn, params).await.map(Into::into).map_err(Into::into),
+			Method::Merge => self.merge(txn, params).await.map(Into::into).map_err(Into::into),
+			Method::Patch => self.patch(txn, params).await.map(Into::into).map_err(Into::into),
+			Method::Delete => self.delete(txn, params).await.map(Into::into).map_err(Into::into),
 			Method::Version => self.version(params).await.map(Into::into).map_err(Into::into),
-			Method::Query => self.query(params).await.map(Into::into).map_err(Into::into),
-			Method::Relate => self.relate(params).await.map(Into::into).map_err(Into::into),
-			Method::Run => self.run(params).await.map(Into::into).map_err(Into::into),
+			Method::Query => self.query(txn, params).await.map(Into::into).map_err(Into::into),
+			Method::Relate => self.relate(txn, params).await.map(Into::into).map_err(Into::into),
+			Method::Run => self.run(txn, params).await.map(Into::into).map_err(Into::into),
 			Method::GraphQL => self.graphql(params).await.map(Into::into).map_err(Into::into),
 			Method::InsertRelation => {
-				self.insert_relation(params).await.map(Into::into).map_err(Into::into)
+				self.insert_relation(txn, params).await.map(Into::into).map_err(Into::into)
 			}
+			Method::Begin => self.begin(params).await.map(Into::into).map_err(Into::into),
+			Method::Commit => self.commit(params).await.map(Into::into).map_err(Into::into),
+			Method::Cancel => self.cancel(params).await.map(Into::into).map_err(Into::into),
+			Method::Abort => self.abort(params).await.map(Into::into).map_err(Into::into),
+			Method::Prepare => self.prepare(params).await.map(Into::into).map_err(Into::into),
+			Method::ExecutePrepared => {
+				self.execute_prepared(txn, params).await.map(Into::into).map_err(Into::into)
+			}
+			Method::Unprepare => self.unprepare(params).await.map(Into::into).map_err(Into::into),
 			Method::Unknown => Err(RpcError::MethodNotFound),
 			_ => Err(RpcError::MethodNotFound),
 		}
 	}
 
+	/// Executes a method, sending the rows of its results to the channel as they are produced
+	async fn execute_stream(
+		&self,
+		txn: Option<Uuid>,
+		method: Method,
+		params: Array,
+		rows: Sender<Row>,
+	) -> Result<Data, RpcError> {
+		// Only methods which query data can run within a transaction
+		if txn.is_some() && !method.can_be_in_txn() {
+			return Err(RpcError::InvalidRequest);
+		}
+		match method {
+			Method::Select => self.select_stream(txn, params, rows).await,
+			Method::Query => self.query_stream(txn, params, rows).await,
+			Method::Unknown => Err(RpcError::MethodNotFound),
+			_ => Err(RpcError::InvalidRequest),
+		}
+	}
+
 	// ------------------------------
 	// Methods for authentication
 	// ------------------------------
@@ -163,7 +257,10 @@ pub trait RpcContext {
 	}
 
 	async fn invalidate(&mut self) -> Result<Data, RpcError> {
-		crate::iam::clear::clear(self.session_mut())?;
+		let mut tmp_session = mem::take(self.session_mut());
+		let out = crate::iam::clear::revoke(self.kvs(), &mut tmp_session).await;
+		*self.session_mut() = tmp_session;
+		out?;
 		Ok(Value::None.into())
 	}
 
@@ -181,11 +278,11 @@ pub trait RpcContext {
 	// Methods for identification
 	// ------------------------------
 
-	async fn info(&self) -> Result<Data, RpcError> {
+	async fn info(&self, txn: Option<Uuid>) -> Result<Data, RpcError> {
 		// Specify the SQL query string
 		let sql = "SELECT * FROM $auth";
 		// Execute the query on the database
-		let mut res = self.kvs().execute(sql, self.session(), None).await?;
+		let mut res = self.execute_inner(txn, sql, None).await?;
 		// Extract the first value from the result
 		let res = res.remove(0).result?.first();
 		// Return the result to the client
@@ -238,7 +335,7 @@ pub trait RpcContext {
 		};
 		// Execute the query on the database
 		// let mut res = self.query_with(Value::from(sql), Object::from(var)).await?;
-		let mut res = self.query_inner(Value::from(sql), Some(var)).await?;
+		let mut res = self.query_inner(None, Value::from(sql), Some(var)).await?;
 		// Extract the first query result
 		let response = res.remove(0);
 		response.result.map_err(Into::into).map(Into::into)
@@ -257,7 +354,7 @@ pub trait RpcContext {
 			=> &self.vars()
 		};
 		// Execute the query on the database
-		let mut res = self.query_inner(Value::from(sql), Some(var)).await?;
+		let mut res = self.query_inner(None, Value::from(sql), Some(var)).await?;
 		// Extract the first query result
 		let response = res.remove(0);
 		response.result.map_err(Into::into).map(Into::into)
@@ -267,7 +364,7 @@ pub trait RpcContext {
 	// Methods for selecting
 	// ------------------------------
 
-	async fn select(&self, params: Array) -> Result<Data, RpcError> {
+	async fn select(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		let Ok(what) = params.needs_one() else {
 			return Err(RpcError::InvalidParams);
 		};
@@ -281,7 +378,7 @@ pub trait RpcContext {
 			=> &self.vars()
 		});
 		// Execute the query on the database
-		let mut res = self.kvs().execute(sql, self.session(), var).await?;
+		let mut res = self.execute_inner(txn, sql, var).await?;
 		// Extract the first query result
 		let res = match one {
 			true => res.remove(0).result?.first(),
@@ -291,11 +388,34 @@ pub trait RpcContext {
 		Ok(res.into())
 	}
 
+	async fn select_stream(
+		&self,
+		txn: Option<Uuid>,
+		params: Array,
+		rows: Sender<Row>,
+	) -> Result<Data, RpcError> {
+		let Ok(what) = params.needs_one() else {
+			return Err(RpcError::InvalidParams);
+		};
+		// Specify the SQL query
+		let sql = crate::syn::parse("SELECT * FROM $what")?;
+		// Specify the query parameters
+		let var = Some(map! {
+			String::from("what") => what.could_be_table(),
+			=> &self.vars()
+		});
+		// Execute the query on the database
+		let mut res = self.stream_inner(txn, sql, var, rows).await?;
+		// The rows have been sent, so only an error is returned
+		res.remove(0).result?;
+		Ok(Value::None.into())
+	}
+
 	// ------------------------------
 	// Methods for inserting
 	// ------------------------------
 
-	async fn insert(&self, params: Array) -> Result<Data, RpcError> {
+	async fn insert(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		let Ok((what, data)) = params.needs_two() else {
 			return Err(RpcError::InvalidParams);
 		};
@@ -310,7 +430,7 @@ pub trait RpcContext {
 					String::from("data") => data,
 					=> &self.vars()
 				});
-				self.kvs().execute(sql, self.session(), var).await?
+				self.execute_inner(txn, sql, var).await?
 			}
 			what => {
 				let sql = "INSERT INTO $what $data RETURN AFTER";
@@ -319,7 +439,7 @@ pub trait RpcContext {
 					String::from("data") => data,
 					=> &self.vars()
 				});
-				self.kvs().execute(sql, self.session(), var).await?
+				self.execute_inner(txn, sql, var).await?
 			}
 		};
 
@@ -332,7 +452,11 @@ pub trait RpcContext {
 		Ok(res.into())
 	}
 
-	async fn insert_relation(&self, params: Array) -> Result<impl Into<Data>, RpcError> {
+	async fn insert_relation(
+		&self,
+		txn: Option<Uuid>,
+		params: Array,
+	) -> Result<impl Into<Data>, RpcError> {
 		let Ok((what, data)) = params.needs_two() else {
 			return Err(RpcError::InvalidParams);
 		};
@@ -346,7 +470,7 @@ pub trait RpcContext {
 					String::from("data") => data,
 					=> &self.vars()
 				});
-				self.kvs().execute(sql, self.session(), vars).await?
+				self.execute_inner(txn, sql, vars).await?
 			}
 			Value::Table(_) | Value::Strand(_) => {
 				let sql = "INSERT RELATION INTO $what $data RETURN AFTER";
@@ -355,7 +479,7 @@ pub trait RpcContext {
 				String::from("what") => what.could_be_table(),
 						=> &self.vars()
 					});
-				self.kvs().execute(sql, self.session(), vars).await?
+				self.execute_inner(txn, sql, vars).await?
 			}
 			_ => return Err(RpcError::InvalidParams),
 		};
@@ -371,7 +495,7 @@ pub trait RpcContext {
 	// Methods for creating
 	// ------------------------------
 
-	async fn create(&self, params: Array) -> Result<Data, RpcError> {
+	async fn create(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		let Ok((what, data)) = params.needs_one_or_two() else {
 			return Err(RpcError::InvalidParams);
 		};
@@ -391,7 +515,7 @@ pub trait RpcContext {
 			=> &self.vars()
 		});
 		// Execute the query on the database
-		let mut res = self.kvs().execute(sql, self.session(), var).await?;
+		let mut res = self.execute_inner(txn, sql, var).await?;
 		// Extract the first query result
 		let res = match one {
 			true => res.remove(0).result?.first(),
@@ -405,7 +529,7 @@ pub trait RpcContext {
 	// Methods for upserting
 	// ------------------------------
 
-	async fn upsert(&self, params: Array) -> Result<Data, RpcError> {
+	async fn upsert(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		let Ok((what, data)) = params.needs_one_or_two() else {
 			return Err(RpcError::InvalidParams);
 		};
@@ -424,7 +548,7 @@ pub trait RpcContext {
 			=> &self.vars()
 		});
 		// Execute the query on the database
-		let mut res = self.kvs().execute(sql, self.session(), var).await?;
+		let mut res = self.execute_inner(txn, sql, var).await?;
 		// Extract the first query result
 		let res = match one {
 			true => res.remove(0).result?.first(),
@@ -438,7 +562,7 @@ pub trait RpcContext {
 	// Methods for updating
 	// ------------------------------
 
-	async fn update(&self, params: Array) -> Result<Data, RpcError> {
+	async fn update(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		let Ok((what, data)) = params.needs_one_or_two() else {
 			return Err(RpcError::InvalidParams);
 		};
@@ -457,7 +581,7 @@ pub trait RpcContext {
 			=> &self.vars()
 		});
 		// Execute the query on the database
-		let mut res = self.kvs().execute(sql, self.session(), var).await?;
+		let mut res = self.execute_inner(txn, sql, var).await?;
 		// Extract the first query result
 		let res = match one {
 			true => res.remove(0).result?.first(),
@@ -471,7 +595,7 @@ pub trait RpcContext {
 	// Methods for merging
 	// ------------------------------
 
-	async fn merge(&self, params: Array) -> Result<Data, RpcError> {
+	async fn merge(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		let Ok((what, data)) = params.needs_one_or_two() else {
 			return Err(RpcError::InvalidParams);
 		};
@@ -490,7 +614,7 @@ pub trait RpcContext {
 			=> &self.vars()
 		});
 		// Execute the query on the database
-		let mut res = self.kvs().execute(sql, self.session(), var).await?;
+		let mut res = self.execute_inner(txn, sql, var).await?;
 		// Extract the first query result
 		let res = match one {
 			true => res.remove(0).result?.first(),
@@ -504,7 +628,7 @@ pub trait RpcContext {
 	// Methods for patching
 	// ------------------------------
 
-	async fn patch(&self, params: Array) -> Result<Data, RpcError> {
+	async fn patch(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		let Ok((what, data, diff)) = params.needs_one_two_or_three() else {
 			return Err(RpcError::InvalidParams);
 		};
@@ -522,7 +646,7 @@ pub trait RpcContext {
 			=> &self.vars()
 		});
 		// Execute the query on the database
-		let mut res = self.kvs().execute(sql, self.session(), var).await?;
+		let mut res = self.execute_inner(txn, sql, var).await?;
 		// Extract the first query result
 		let res = match one {
 			true => res.remove(0).result?.first(),
@@ -536,7 +660,7 @@ pub trait RpcContext {
 	// Methods for relating
 	// ------------------------------
 
-	async fn relate(&self, params: Array) -> Result<Data, RpcError> {
+	async fn relate(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		let Ok((from, kind, to, data)) = params.needs_three_or_four() else {
 			return Err(RpcError::InvalidParams);
 		};
@@ -557,7 +681,7 @@ pub trait RpcContext {
 			=> &self.vars()
 		});
 		// Execute the query on the database
-		let mut res = self.kvs().execute(sql, self.session(), var).await?;
+		let mut res = self.execute_inner(txn, sql, var).await?;
 		// Extract the first query result
 		let res = match one {
 			true => res.remove(0).result?.first(),
@@ -571,7 +695,7 @@ pub trait RpcContext {
 	// Methods for deleting
 	// ------------------------------
 
-	async fn delete(&self, params: Array) -> Result<Data, RpcError> {
+	async fn delete(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		let Ok(what) = params.needs_one() else {
 			return Err(RpcError::InvalidParams);
 		};
@@ -585,7 +709,7 @@ pub trait RpcContext {
 			=> &self.vars()
 		});
 		// Execute the query on the database
-		let mut res = self.kvs().execute(sql, self.session(), var).await?;
+		let mut res = self.execute_inner(txn, sql, var).await?;
 		// Extract the first query result
 		let res = match one {
 			true => res.remove(0).result?.first(),
@@ -610,7 +734,7 @@ pub trait RpcContext {
 	// Methods for querying
 	// ------------------------------
 
-	async fn query(&self, params: Array) -> Result<Data, RpcError> {
+	async fn query(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		let Ok((query, o)) = params.needs_one_or_two() else {
 			return Err(RpcError::InvalidParams);
 		};
@@ -629,14 +753,106 @@ pub trait RpcContext {
 			Some(mut v) => Some(mrg! {v.0, &self.vars()}),
 			None => Some(self.vars().clone()),
 		};
-		self.query_inner(query, vars).await.map(Into::into)
+		self.query_inner(txn, query, vars).await.map(Into::into)
+	}
+
+	async fn prepare(&self, params: Array) -> Result<Data, RpcError> {
+		let Some(prepared) = self.prepared() else {
+			return Err(RpcError::MethodNotFound);
+		};
+		let Value::Strand(query) = params.needs_one()? else {
+			return Err(RpcError::InvalidParams);
+		};
+		// Parse the query once, for the current session
+		let id = prepared.prepare(self.kvs(), self.session(), &query).await?;
+		// Return the prepared query id to the client
+		Ok(Value::Uuid(id.into()).into())
+	}
+
+	async fn execute_prepared(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
+		let Some(prepared) = self.prepared() else {
+			return Err(RpcError::MethodNotFound);
+		};
+		let Ok((id, o)) = params.needs_one_or_two() else {
+			return Err(RpcError::InvalidParams);
+		};
+		let Some(id) = parse_uuid(id) else {
+			return Err(RpcError::InvalidParams);
+		};
+
+		let o = match o {
+			Value::Object(v) => Some(v),
+			Value::None | Value::Null => None,
+			_ => return Err(RpcError::InvalidParams),
+		};
+
+		// Bind the query parameters by name
+		let vars = match o {
+			Some(mut v) => Some(mrg! {v.0, &self.vars()}),
+			None => Some(self.vars().clone()),
+		};
+		// Fetch the parsed query, which is checked against the schema when it is executed
+		let query = prepared.get(self.kvs(), self.session(), id).await?;
+		self.query_inner(txn, Value::Query(query), vars).await.map(Into::into)
+	}
+
+	async fn unprepare(&self, params: Array) -> Result<Data, RpcError> {
+		let Some(prepared) = self.prepared() else {
+			return Err(RpcError::MethodNotFound);
+		};
+		let Some(id) = parse_uuid(params.needs_one()?) else {
+			return Err(RpcError::InvalidParams);
+		};
+		// Remove the prepared query from the connection
+		prepared.remove(id)?;
+		Ok(Value::None.into())
+	}
+
+	async fn query_stream(
+		&self,
+		txn: Option<Uuid>,
+		params: Array,
+		rows: Sender<Row>,
+	) -> Result<Data, RpcError> {
+		let Ok((query, o)) = params.needs_one_or_two() else {
+			return Err(RpcError::InvalidParams);
+		};
+		let query = match query {
+			Value::Query(v) => v,
+			Value::Strand(v) => crate::syn::parse(&v)?,
+			_ => return Err(RpcError::InvalidParams),
+		};
+
+		let o = match o {
+			Value::Object(v) => Some(v),
+			Value::None | Value::Null => None,
+			_ => return Err(RpcError::InvalidParams),
+		};
+
+		// Specify the query parameters
+		let vars = match o {
+			Some(mut v) => Some(mrg! {v.0, &self.vars()}),
+			None => Some(self.vars().clone()),
+		};
+		// If no live query handler force realtime off
+		if !Self::LQ_SUPPORT && self.session().rt {
+			return Err(RpcError::BadLQConfig);
+		}
+		// Execute the query on the database
+		let res = self.stream_inner(txn, query, vars, rows).await?;
+		// Post-process hooks for web layer
+		for response in &res {
+			self.handle_live_query_results(response).await;
+		}
+		// Return the result to the client
+		Ok(res.into())
 	}
 
 	// ------------------------------
 	// Methods for running functions
 	// ------------------------------
 
-	async fn run(&self, params: Array) -> Result<Data, RpcError> {
+	async fn run(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		let Ok((Value::Strand(Strand(func_name)), version, args)) = params.needs_one_two_or_three()
 		else {
 			return Err(RpcError::InvalidParams);
@@ -666,12 +882,115 @@ pub trait RpcContext {
 		};
 
 		let mut res = self
-			.kvs()
-			.process(Statement::Value(func).into(), self.session(), Some(self.vars().clone()))
+			.process_inner(txn, Statement::Value(func).into(), Some(self.vars().clone()))
 			.await?;
 		res.remove(0).result.map_err(Into::into).map(Into::into)
 	}
 
+	// ------------------------------
+	// Methods for transactions
+	// ------------------------------
+
+	async fn begin(&self, params: Array) -> Result<Data, RpcError> {
+		if !params.is_empty() {
+			return Err(RpcError::InvalidParams);
+		}
+		// Begin a transaction spanning several requests
+		let id = self.kvs().begin(self.session()).await?;
+		// Return the transaction id to the client
+		Ok(Value::Uuid(id.into()).into())
+	}
+
+	async fn commit(&self, params: Array) -> Result<Data, RpcError> {
+		let Some(id) = parse_uuid(params.needs_one()?) else {
+			return Err(RpcError::InvalidParams);
+		};
+		// Commit the transaction
+		self.kvs().commit(id, self.session()).await?;
+		Ok(Value::None.into())
+	}
+
+	async fn cancel(&self, params: Array) -> Result<Data, RpcError> {
+		let Some(id) = parse_uuid(params.needs_one()?) else {
+			return Err(RpcError::InvalidParams);
+		};
+		// Cancel the transaction
+		self.kvs().cancel(id, self.session()).await?;
+		Ok(Value::None.into())
+	}
+
+	// ------------------------------
+	// Methods for cancelling requests
+	// ------------------------------
+
+	async fn abort(&self, params: Array) -> Result<Data, RpcError> {
+		let id = params.needs_one()?;
+		// Cancel the request with this id, if it is still being processed
+		match self.handle_cancel(&id).await {
+			true => Ok(Value::None.into()),
+			false => Err(RpcError::RequestNotFound),
+		}
+	}
+
+	// ------------------------------
+	// Methods for batching requests
+	// ------------------------------
+
+	async fn batch(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
+		let Ok((Value::Array(calls), atomic)) = params.needs_one_or_two() else {
+			return Err(RpcError::InvalidParams);
+		};
+		let atomic = match atomic {
+			Value::None | Value::Null => false,
+			Value::Bool(v) => v,
+			_ => return Err(RpcError::InvalidParams),
+		};
+		// An atomic batch runs in its own transaction
+		if atomic && txn.is_some() {
+			return Err(RpcError::InvalidParams);
+		}
+		// Parse every call before any of them are run
+		let calls = calls.into_iter().map(Request::try_from).collect::<Result<Vec<_>, _>>()?;
+		if calls.iter().any(|v| v.txn.is_some() || v.session.is_some() || v.stream) {
+			return Err(RpcError::InvalidRequest);
+		}
+		// Begin the transaction of an atomic batch
+		let txn = match atomic {
+			true => Some(self.kvs().begin(self.session()).await?),
+			false => txn,
+		};
+		// Run the calls in order, collecting the result of each
+		let mut out = Vec::with_capacity(calls.len());
+		let mut failed = false;
+		for call in calls {
+			let res = match Method::parse(&call.method) {
+				// Calls can not change the state of the connection, or be batches themselves
+				Method::Batch => Err(RpcError::InvalidRequest),
+				method if method.needs_mut() => Err(RpcError::InvalidRequest),
+				method => self.execute_call(txn, method, call.params).await,
+			};
+			// A query fails when any of its statements failed
+			failed = match &res {
+				Ok(Data::Query(v)) => v.iter().any(|v| v.result.is_err()),
+				Ok(_) => false,
+				Err(_) => true,
+			};
+			out.push(batch_result(call.id, res));
+			// An atomic batch stops at the first error
+			if atomic && failed {
+				break;
+			}
+		}
+		// Commit the transaction of an atomic batch, unless a call failed
+		if let (true, Some(id)) = (atomic, txn) {
+			match failed {
+				true => self.kvs().cancel(id, self.session()).await?,
+				false => self.kvs().commit(id, self.session()).await?,
+			}
+		}
+		Ok(Value::from(out).into())
+	}
+
 	// ------------------------------
 	// Methods for querying with GraphQL
 	// ------------------------------
@@ -791,6 +1110,7 @@ pub trait RpcContext {
 
 	async fn query_inner(
 		&self,
+		txn: Option<Uuid>,
 		query: Value,
 		vars: Option<BTreeMap<String, Value>>,
 	) -> Result<Vec<Response>, RpcError> {
@@ -800,8 +1120,8 @@ pub trait RpcContext {
 		}
 		// Execute the query on the database
 		let res = match query {
-			Value::Query(sql) => self.kvs().process(sql, self.session(), vars).await?,
-			Value::Strand(sql) => self.kvs().execute(&sql, self.session(), vars).await?,
+			Value::Query(sql) => self.process_inner(txn, sql, vars).await?,
+			Value::Strand(sql) => self.execute_inner(txn, &sql, vars).await?,
 			_ => unreachable!(),
 		};
 
@@ -814,6 +1134,59 @@ pub trait RpcContext {
 		Ok(res)
 	}
 
+	async fn execute_inner(
+		&self,
+		txn: Option<Uuid>,
+		sql: &str,
+		vars: Option<BTreeMap<String, Value>>,
+	) -> Result<Vec<Response>, RpcError> {
+		// Execute the query within the transaction, if specified
+		let res = match txn {
+			Some(id) => self.kvs().execute_in(id, sql, &self.query_session(), vars).await?,
+			None => self.kvs().execute(sql, &self.query_session(), vars).await?,
+		};
+		Ok(res)
+	}
+
+	async fn process_inner(
+		&self,
+		txn: Option<Uuid>,
+		ast: Query,
+		vars: Option<BTreeMap<String, Value>>,
+	) -> Result<Vec<Response>, RpcError> {
+		// Process the query within the transaction, if specified
+		let res = match txn {
+			Some(id) => self.kvs().process_in(id, ast, &self.query_session(), vars).await?,
+			None => self.kvs().process(ast, &self.query_session(), vars).await?,
+		};
+		Ok(res)
+	}
+
+	async fn stream_inner(
+		&self,
+		txn: Option<Uuid>,
+		ast: Query,
+		vars: Option<BTreeMap<String, Value>>,
+		rows: Sender<Row>,
+	) -> Result<Vec<Response>, RpcError> {
+		// Process the query within the transaction, if specified
+		let res = match txn {
+			Some(id) => {
+				self.kvs().process_stream_in(id, ast, &self.query_session(), vars, rows).await?
+			}
+			None => self.kvs().process_stream(ast, &self.query_session(), vars, rows).await?,
+		};
+		Ok(res)
+	}
+
+	/// The session which queries run as, which stops them when the request is cancelled
+	fn query_session(&self) -> Cow<'_, Session> {
+		match self.canceller() {
+			Some(canceller) => Cow::Owned(self.session().clone().with_canceller(canceller)),
+			None => Cow::Borrowed(self.session()),
+		}
+	}
+
 	async fn handle_live_query_results(&self, res: &Response) {
 		match &res.query_type {
 			QueryType::Live => {
@@ -830,3 +1203,16 @@ pub trait RpcContext {
 		}
 	}
 }
+
+/// Converts the outcome of a call in a batch into an entry of the batch result
+fn batch_result(id: Option<Value>, res: Result<Data, RpcError>) -> Value {
+	let mut out = Object::default();
+	if let Some(id) = id {
+		out.insert("id".to_owned(), id);
+	}
+	match res {
+		Ok(v) => out.insert("result".to_owned(), v.into()),
+		Err(e) => out.insert("error".to_owned(), Failure::from(e).into()),
+	};
+	Value::from(out)
+}
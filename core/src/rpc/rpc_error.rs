@@ -21,6 +21,8 @@ pub enum RpcError {
 	BadLQConfig,
 	#[error("A GraphQL request was made, but GraphQL is not supported by the context")]
 	BadGQLConfig,
+	#[error("There is no request with this id being processed")]
+	RequestNotFound,
 	#[error("Error: {0}")]
 	Thrown(String),
 }
@@ -1,7 +1,7 @@
 use super::Value;
 use crate::sql::statements::info::InfoStructure;
 use crate::sql::statements::DefineAccessStatement;
-use crate::sql::{escape::quote_str, Algorithm};
+use crate::sql::{escape::quote_str, Algorithm, Ident};
 use revision::revisioned;
 use revision::Error as RevisionError;
 use serde::{Deserialize, Serialize};
@@ -61,6 +61,12 @@ impl Display for AccessType {
 					write!(f, " SIGNIN {v}")?
 				}
 				write!(f, " WITH JWT {}", ac.jwt)?;
+				if ac.refresh {
+					write!(f, " WITH REFRESH")?;
+				}
+				if ac.certificate {
+					write!(f, " WITH CERTIFICATE")?;
+				}
 			}
 			AccessType::Bearer(ac) => {
 				write!(f, "BEARER")?;
@@ -85,6 +91,8 @@ impl InfoStructure for AccessType {
 				"jwt".to_string() => v.jwt.structure(),
 				"signup".to_string(), if let Some(v) = v.signup => v.structure(),
 				"signin".to_string(), if let Some(v) = v.signin => v.structure(),
+				"refresh".to_string() => v.refresh.into(),
+				"certificate".to_string() => v.certificate.into(),
 			}),
 			AccessType::Bearer(ac) => Value::from(map! {
 					"kind".to_string() => "BEARER".into(),
@@ -106,8 +114,10 @@ impl AccessType {
 	#[allow(unreachable_patterns)]
 	pub fn can_issue_grants(&self) -> bool {
 		match self {
-			// The grants for JWT and record access methods are JWT
-			AccessType::Jwt(_) | AccessType::Record(_) => false,
+			// The grants for JWT access methods are JWT
+			AccessType::Jwt(_) => false,
+			// Record access methods can issue refresh grants
+			AccessType::Record(at) => at.refresh,
 			AccessType::Bearer(_) => true,
 		}
 	}
@@ -167,6 +177,9 @@ impl Display for JwtAccess {
 			JwtAccessVerify::Jwks(ref v) => {
 				write!(f, "URL {}", quote_str(&v.url),)?;
 			}
+			JwtAccessVerify::Issuer(ref v) => {
+				write!(f, "ISSUER {}", v)?;
+			}
 		}
 		if let Some(iss) = &self.issue {
 			write!(f, " WITH ISSUER KEY {}", quote_str(&iss.key))?;
@@ -186,6 +199,7 @@ impl InfoStructure for JwtAccess {
 					"alg".to_string() => v.alg.structure(),
 					"key".to_string() => v.key.into(),
 				}),
+				JwtAccessVerify::Issuer(v) => v.structure(),
 			},
 			"issuer".to_string(), if let Some(v) = self.issue => Value::from(map!{
 				"alg".to_string() => v.alg.structure(),
@@ -209,6 +223,8 @@ impl JwtAccess {
 			}
 			// No secrets in JWK
 			JwtAccessVerify::Jwks(jwks) => JwtAccessVerify::Jwks(jwks),
+			// No secrets in OpenID Connect discovery
+			JwtAccessVerify::Issuer(iss) => JwtAccessVerify::Issuer(iss),
 		};
 		jwt.issue = match jwt.issue {
 			Some(mut issue) => {
@@ -240,13 +256,15 @@ impl Default for JwtAccessIssue {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Debug, Serialize, Deserialize, Hash, Clone, Eq, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub enum JwtAccessVerify {
 	Key(JwtAccessVerifyKey),
 	Jwks(JwtAccessVerifyJwks),
+	#[revision(start = 2)]
+	Issuer(JwtAccessVerifyIssuer),
 }
 
 impl Default for JwtAccessVerify {
@@ -267,6 +285,7 @@ impl InfoStructure for JwtAccessVerify {
 				"alg".to_string() => v.alg.structure(),
 				"key".to_string() => v.key.into(),
 			}),
+			JwtAccessVerify::Issuer(v) => v.structure(),
 		}
 	}
 }
@@ -297,7 +316,61 @@ pub struct JwtAccessVerifyJwks {
 	pub url: String,
 }
 
-#[revisioned(revision = 3)]
+/// Verifies tokens from an OpenID Connect provider
+/// The JWKS location is discovered from the metadata of the issuer
+#[revisioned(revision = 1)]
+#[derive(Debug, Default, Serialize, Deserialize, Hash, Clone, Eq, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct JwtAccessVerifyIssuer {
+	// The issuer identifier, which must match the "iss" claim
+	pub url: String,
+	// The audience which must be present in the "aud" claim
+	pub audience: Option<String>,
+	// The path to the claim containing the roles of the user
+	pub roles: Option<String>,
+	// The record of the user, identified by the value of a claim
+	pub record: Option<JwtAccessRecordClaim>,
+}
+
+impl Display for JwtAccessVerifyIssuer {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", quote_str(&self.url))?;
+		if let Some(ref v) = self.audience {
+			write!(f, " AUDIENCE {}", quote_str(v))?;
+		}
+		if let Some(ref v) = self.roles {
+			write!(f, " ROLES FROM {}", quote_str(v))?;
+		}
+		if let Some(ref v) = self.record {
+			write!(f, " RECORD {} FROM {}", v.tb, quote_str(&v.claim))?;
+		}
+		Ok(())
+	}
+}
+
+impl InfoStructure for JwtAccessVerifyIssuer {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"issuer".to_string() => self.url.into(),
+			"audience".to_string(), if let Some(v) = self.audience => v.into(),
+			"roles".to_string(), if let Some(v) = self.roles => v.into(),
+			"record".to_string(), if let Some(v) = self.record => Value::from(map! {
+				"table".to_string() => v.tb.structure(),
+				"claim".to_string() => v.claim.into(),
+			}),
+		})
+	}
+}
+
+#[revisioned(revision = 1)]
+#[derive(Debug, Default, Serialize, Deserialize, Hash, Clone, Eq, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct JwtAccessRecordClaim {
+	pub tb: Ident,
+	pub claim: String,
+}
+
+#[revisioned(revision = 5)]
 #[derive(Debug, Serialize, Deserialize, Hash, Clone, Eq, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct RecordAccess {
@@ -306,6 +379,12 @@ pub struct RecordAccess {
 	pub jwt: JwtAccess,
 	#[revision(start = 2, end = 3, convert_fn = "authenticate_revision")]
 	pub authenticate: Option<Value>,
+	// Whether a refresh token is issued alongside each access token
+	#[revision(start = 4)]
+	pub refresh: bool,
+	// Whether records can authenticate with a client certificate
+	#[revision(start = 5)]
+	pub certificate: bool,
 }
 
 impl RecordAccess {
@@ -328,6 +407,8 @@ impl Default for RecordAccess {
 			jwt: JwtAccess {
 				..Default::default()
 			},
+			refresh: false,
+			certificate: false,
 		}
 	}
 }
@@ -0,0 +1,72 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::sql::id::Id;
+use crate::sql::thing::Thing;
+use crate::sql::value::Value;
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Resumes the iteration of a table after the last record which was seen.
+///
+/// The clause takes either the id of the last record, or the last record itself.
+/// Table scans seek to the key following the record id. Index scans seek to the
+/// index entry following the one of the record, which is computed from the fields
+/// of the given record, so index scans can only be resumed after a record. The
+/// cursor only applies when iterating the table of the record.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct After(pub Value);
+
+/// The position which an iteration resumes after
+pub(crate) struct Cursor {
+	/// The id of the last record which was seen
+	pub(crate) rid: Thing,
+	/// The last record which was seen, if it was given
+	pub(crate) doc: Option<Value>,
+}
+
+impl After {
+	pub(crate) async fn process(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Cursor, Error> {
+		match self.0.compute(stk, ctx, opt, doc).await {
+			// This is a valid record id
+			Ok(Value::Thing(v)) if !matches!(v.id, Id::Range(_)) => Ok(Cursor {
+				rid: v,
+				doc: None,
+			}),
+			// This is a record, with a valid record id
+			Ok(Value::Object(v)) => match v.get("id") {
+				Some(Value::Thing(rid)) if !matches!(rid.id, Id::Range(_)) => Ok(Cursor {
+					rid: rid.clone(),
+					doc: Some(Value::Object(v)),
+				}),
+				_ => Err(Error::InvalidAfter {
+					value: v.to_string(),
+				}),
+			},
+			// An invalid value was specified
+			Ok(v) => Err(Error::InvalidAfter {
+				value: v.to_string(),
+			}),
+			// A different error occurred
+			Err(e) => Err(e),
+		}
+	}
+}
+
+impl fmt::Display for After {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "AFTER {}", self.0)
+	}
+}
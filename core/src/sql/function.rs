@@ -3,7 +3,7 @@ use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::fnc;
-use crate::iam::Action;
+use crate::iam::{Action, ResourceKind};
 use crate::sql::fmt::Fmt;
 use crate::sql::idiom::Idiom;
 use crate::sql::script::Script;
@@ -248,7 +248,7 @@ impl Function {
 				// Get the function definition
 				let val = ctx.tx().get_db_function(opt.ns()?, opt.db()?, s).await?;
 				// Check permissions
-				if opt.check_perms(Action::View)? {
+				if opt.check_perms(Action::Select, ResourceKind::Function)? {
 					match &val.permissions {
 						Permission::Full => (),
 						Permission::None => {
@@ -256,7 +256,9 @@ impl Function {
 								name: s.to_owned(),
 							})
 						}
-						Permission::Specific(e) => {
+						Permission::Specific(_) | Permission::Policy(_) => {
+							// Fetch the permission condition
+							let e = val.permissions.condition(ctx, opt).await?;
 							// Disable permissions
 							let opt = &opt.new_with_perms(false);
 							// Process the PERMISSION clause
@@ -12,7 +12,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::{Display, Formatter};
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -29,6 +29,9 @@ pub enum Index {
 	/// HNSW index for distance based metrics
 	#[revision(start = 2)]
 	Hnsw(HnswParams),
+	/// Index for sparse vectors using dot-product scoring
+	#[revision(start = 3)]
+	Sparse(SparseParams),
 }
 
 #[revisioned(revision = 2)]
@@ -53,6 +56,17 @@ pub struct SearchParams {
 	pub terms_cache: u32,
 }
 
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct SparseParams {
+	pub doc_ids_order: u32,
+	pub doc_ids_cache: u32,
+	pub postings_order: u32,
+	pub postings_cache: u32,
+}
+
 #[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -122,7 +136,7 @@ pub enum Distance1 {
 	Minkowski(Number),
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -136,6 +150,10 @@ pub struct HnswParams {
 	pub extend_candidates: bool,
 	pub keep_pruned_connections: bool,
 	pub ml: Number,
+	#[revision(start = 2)]
+	pub quantization: Option<VectorQuantization>,
+	#[revision(start = 2)]
+	pub rerank: bool,
 }
 
 impl HnswParams {
@@ -150,6 +168,8 @@ impl HnswParams {
 		ef_construction: u16,
 		extend_candidates: bool,
 		keep_pruned_connections: bool,
+		quantization: Option<VectorQuantization>,
+		rerank: bool,
 	) -> Self {
 		Self {
 			dimension,
@@ -161,6 +181,30 @@ impl HnswParams {
 			ml,
 			extend_candidates,
 			keep_pruned_connections,
+			quantization,
+			rerank,
+		}
+	}
+}
+
+/// The compression applied to the vectors held by an HNSW index
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum VectorQuantization {
+	/// Every dimension is stored on a single byte (int8)
+	Scalar,
+	/// The vector is split in the given number of subspaces,
+	/// each one being stored as the (byte) index of its nearest centroid
+	Product(u16),
+}
+
+impl Display for VectorQuantization {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Scalar => f.write_str("SCALAR"),
+			Self::Product(subspaces) => write!(f, "PRODUCT {}", subspaces),
 		}
 	}
 }
@@ -280,8 +324,21 @@ impl Display for Index {
 				if p.keep_pruned_connections {
 					f.write_str(" KEEP_PRUNED_CONNECTIONS")?
 				}
+				if let Some(q) = &p.quantization {
+					write!(f, " QUANTIZE {q}")?
+				}
+				if p.rerank {
+					f.write_str(" RERANK")?
+				}
 				Ok(())
 			}
+			Self::Sparse(p) => {
+				write!(
+					f,
+					"SPARSE DOC_IDS_ORDER {} DOC_IDS_CACHE {} POSTINGS_ORDER {} POSTINGS_CACHE {}",
+					p.doc_ids_order, p.doc_ids_cache, p.postings_order, p.postings_cache
+				)
+			}
 		}
 	}
 }
@@ -2,6 +2,7 @@
 
 pub(crate) mod access;
 pub(crate) mod access_type;
+pub(crate) mod after;
 pub(crate) mod algorithm;
 #[cfg(feature = "arbitrary")]
 pub(crate) mod arbitrary;
@@ -81,6 +82,7 @@ pub mod statements;
 pub use self::access::Access;
 pub use self::access::Accesses;
 pub use self::access_type::{AccessType, JwtAccess, RecordAccess};
+pub use self::after::After;
 pub use self::algorithm::Algorithm;
 pub use self::array::Array;
 pub use self::base::Base;
@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 
 #[cfg(feature = "ml")]
-use crate::iam::Action;
+use crate::iam::{Action, ResourceKind};
 #[cfg(feature = "ml")]
 use crate::ml::errors::error::SurrealError;
 #[cfg(feature = "ml")]
@@ -80,7 +80,7 @@ impl Model {
 			val.hash
 		);
 		// Check permissions
-		if opt.check_perms(Action::View)? {
+		if opt.check_perms(Action::Select, ResourceKind::Model)? {
 			match &val.permissions {
 				Permission::Full => (),
 				Permission::None => {
@@ -88,7 +88,9 @@ impl Model {
 						name: self.name.to_owned(),
 					})
 				}
-				Permission::Specific(e) => {
+				Permission::Specific(_) | Permission::Policy(_) => {
+					// Fetch the permission condition
+					let e = val.permissions.condition(ctx, opt).await?;
 					// Disable permissions
 					let opt = &opt.new_with_perms(false);
 					// Process the PERMISSION clause
@@ -3,7 +3,7 @@ use crate::{
 	dbs::Options,
 	doc::CursorDoc,
 	err::Error,
-	iam::Action,
+	iam::{Action, ResourceKind},
 	sql::{ident::Ident, value::Value, Permission},
 };
 use reblessive::tree::Stk;
@@ -78,7 +78,7 @@ impl Param {
 						// The param has been set globally
 						Ok(val) => {
 							// Check permissions
-							if opt.check_perms(Action::View)? {
+							if opt.check_perms(Action::Select, ResourceKind::Parameter)? {
 								match &val.permissions {
 									Permission::Full => (),
 									Permission::None => {
@@ -86,7 +86,9 @@ impl Param {
 											name: v.to_owned(),
 										})
 									}
-									Permission::Specific(e) => {
+									Permission::Specific(_) | Permission::Policy(_) => {
+										// Fetch the permission condition
+										let e = val.permissions.condition(ctx, opt).await?;
 										// Disable permissions
 										let opt = &opt.new_with_perms(false);
 										// Process the PERMISSION clause
@@ -17,6 +17,8 @@ pub static AC: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from(OBJ_PATH_ACCES
 
 pub static RD: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from(OBJ_PATH_AUTH)]);
 
+pub static GR: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("gr")]);
+
 pub static OR: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("or")]);
 
 pub static TK: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from(OBJ_PATH_TOKEN)]);
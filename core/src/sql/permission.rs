@@ -1,10 +1,14 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
 use crate::sql::fmt::is_pretty;
 use crate::sql::fmt::pretty_indent;
 use crate::sql::fmt::pretty_sequence_item;
 use crate::sql::statements::info::InfoStructure;
-use crate::sql::Value;
+use crate::sql::{Ident, Value};
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fmt::Write;
 use std::fmt::{self, Display, Formatter};
 use std::str;
@@ -143,7 +147,7 @@ impl PermissionKind {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -152,6 +156,8 @@ pub enum Permission {
 	#[default]
 	Full,
 	Specific(Value),
+	#[revision(start = 2)]
+	Policy(Ident),
 }
 
 impl Permission {
@@ -162,6 +168,38 @@ impl Permission {
 	pub fn is_full(&self) -> bool {
 		matches!(self, Permission::Full)
 	}
+
+	/// Fetches the condition which this permission evaluates. A
+	/// referenced policy is looked up on the current database first,
+	/// and then on the current namespace.
+	pub(crate) async fn condition(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+	) -> Result<Cow<'_, Value>, Error> {
+		match self {
+			Permission::None => Ok(Cow::Owned(Value::Bool(false))),
+			Permission::Full => Ok(Cow::Owned(Value::Bool(true))),
+			Permission::Specific(v) => Ok(Cow::Borrowed(v)),
+			Permission::Policy(name) => {
+				// Fetch the transaction
+				let txn = ctx.tx();
+				// Check for a database policy
+				if let Ok(db) = opt.db() {
+					match txn.get_db_policy(opt.ns()?, db, name).await {
+						Ok(pl) => return Ok(Cow::Owned(pl.cond.clone())),
+						Err(Error::PlDbNotFound {
+							..
+						}) => (),
+						Err(e) => return Err(e),
+					}
+				}
+				// Check for a namespace policy
+				let pl = txn.get_ns_policy(opt.ns()?, name).await?;
+				Ok(Cow::Owned(pl.cond.clone()))
+			}
+		}
+	}
 }
 
 impl Display for Permission {
@@ -170,6 +208,7 @@ impl Display for Permission {
 			Self::None => f.write_str("NONE"),
 			Self::Full => f.write_str("FULL"),
 			Self::Specific(ref v) => write!(f, "WHERE {v}"),
+			Self::Policy(ref v) => write!(f, "POLICY {v}"),
 		}
 	}
 }
@@ -180,6 +219,7 @@ impl InfoStructure for Permission {
 			Permission::None => Value::Bool(false),
 			Permission::Full => Value::Bool(true),
 			Permission::Specific(v) => v.to_string().into(),
+			Permission::Policy(v) => format!("POLICY {v}").into(),
 		}
 	}
 }
@@ -120,7 +120,8 @@ impl Statement {
 			Self::Value(v) => v.writeable(),
 			Self::Access(_) => true,
 			Self::Alter(_) => true,
-			Self::Analyze(_) => false,
+			// Analyzing an index may store the codebook of its quantizer
+			Self::Analyze(_) => true,
 			Self::Break(_) => false,
 			Self::Continue(_) => false,
 			Self::Create(v) => v.writeable(),
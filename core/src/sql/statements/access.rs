@@ -2,9 +2,13 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::iam::audit::{self, AuditEntry, AuditEvent};
 use crate::iam::{Action, ResourceKind};
+use crate::kvs::delete_grant_queries;
 use crate::sql::access_type::BearerAccessLevel;
-use crate::sql::{AccessType, Array, Base, Datetime, Id, Ident, Object, Strand, Uuid, Value};
+use crate::sql::{
+	AccessType, Array, Base, Datetime, Id, Ident, Object, Strand, Thing, Uuid, Value,
+};
 use derive::Store;
 use rand::Rng;
 use revision::revisioned;
@@ -160,15 +164,34 @@ impl From<AccessGrant> for Object {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub enum Subject {
+	#[revision(end = 2, convert_fn = "convert_record_id", fields_name = "OldSubjectRecordFields")]
 	Record(Id),
+	#[revision(start = 2)]
+	Record(Thing),
 	User(Ident),
 }
 
+impl Subject {
+	fn convert_record_id(
+		fields: OldSubjectRecordFields,
+		_revision: u16,
+	) -> Result<Self, revision::Error> {
+		// Record subjects used to store the full record id as a string
+		match crate::syn::thing(&fields.0.to_raw()) {
+			Ok(v) => Ok(Subject::Record(v)),
+			Err(_) => Err(revision::Error::Conversion(format!(
+				"Unable to convert the record subject '{}' to a record id",
+				fields.0
+			))),
+		}
+	}
+}
+
 #[revisioned(revision = 1)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -568,6 +591,8 @@ async fn compute_revoke(
 			txn.get_or_add_ns(opt.ns()?, opt.strict).await?;
 			txn.get_or_add_db(opt.ns()?, opt.db()?, opt.strict).await?;
 			txn.set(key, &gr, None).await?;
+			// End the live queries of the sessions established with the grant
+			delete_grant_queries(&txn, opt.ns()?, opt.db()?, &gr_str).await?;
 			Ok(Value::Object(gr.redacted().into()))
 		}
 		_ => Err(Error::Unimplemented(
@@ -585,9 +610,30 @@ impl AccessStatement {
 		_doc: Option<&CursorDoc>,
 	) -> Result<Value, Error> {
 		match self {
-			AccessStatement::Grant(stmt) => compute_grant(stmt, ctx, opt, _doc).await,
+			AccessStatement::Grant(stmt) => {
+				let res = compute_grant(stmt, ctx, opt, _doc).await?;
+				// Record the issued grant in the audit log
+				audit::record(ctx, || {
+					let id = match &res {
+						Value::Object(v) => v.get("id").cloned().unwrap_or_default(),
+						_ => Value::None,
+					};
+					let detail = format!("{self} ({})", id.as_raw_string());
+					AuditEntry::new(AuditEvent::Grant, detail).with_opt(opt)
+				})
+				.await?;
+				Ok(res)
+			}
 			AccessStatement::List(stmt) => compute_list(stmt, ctx, opt, _doc).await,
-			AccessStatement::Revoke(stmt) => compute_revoke(stmt, ctx, opt, _doc).await,
+			AccessStatement::Revoke(stmt) => {
+				let res = compute_revoke(stmt, ctx, opt, _doc).await?;
+				// Record the revoked grant in the audit log
+				audit::record(ctx, || {
+					AuditEntry::new(AuditEvent::Revoke, self.to_string()).with_opt(opt)
+				})
+				.await?;
+				Ok(res)
+			}
 			AccessStatement::Prune(_) => Err(Error::FeatureNotYetImplemented {
 				feature: "Pruning disabled grants".to_string(),
 			}),
@@ -603,7 +649,12 @@ impl Display for AccessStatement {
 				if let Some(ref v) = stmt.base {
 					write!(f, " ON {v}")?;
 				}
-				write!(f, "GRANT")?;
+				write!(f, " GRANT")?;
+				match stmt.subject {
+					Some(Subject::User(ref v)) => write!(f, " FOR USER {v}")?,
+					Some(Subject::Record(ref v)) => write!(f, " FOR RECORD {v}")?,
+					None => (),
+				}
 				Ok(())
 			}
 			Self::List(stmt) => {
@@ -611,7 +662,7 @@ impl Display for AccessStatement {
 				if let Some(ref v) = stmt.base {
 					write!(f, " ON {v}")?;
 				}
-				write!(f, "LIST")?;
+				write!(f, " LIST")?;
 				Ok(())
 			}
 			Self::Revoke(stmt) => {
@@ -619,7 +670,7 @@ impl Display for AccessStatement {
 				if let Some(ref v) = stmt.base {
 					write!(f, " ON {v}")?;
 				}
-				write!(f, "REVOKE {}", stmt.gr)?;
+				write!(f, " REVOKE {}", stmt.gr)?;
 				Ok(())
 			}
 			Self::Prune(stmt) => write!(f, "ACCESS {} PRUNE", stmt),
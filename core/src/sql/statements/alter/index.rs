@@ -0,0 +1,71 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::{Base, Ident, Value};
+use derive::Store;
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct AlterIndexStatement {
+	pub name: Ident,
+	pub what: Ident,
+	pub if_exists: bool,
+	/// Cancels the concurrent building of the index
+	pub cancel: bool,
+}
+
+impl AlterIndexStatement {
+	pub(crate) async fn compute(
+		&self,
+		_stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		_doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Define, ResourceKind::Index, &Base::Db)?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Get the index definition
+		let ix = match txn.get_tb_index(opt.ns()?, opt.db()?, &self.what, &self.name).await {
+			Ok(ix) => ix,
+			Err(Error::IxNotFound {
+				..
+			}) if self.if_exists => return Ok(Value::None),
+			Err(v) => return Err(v),
+		};
+		// Cancel the building of the index, if it is running
+		#[cfg(not(target_arch = "wasm32"))]
+		if self.cancel {
+			if let Some(ib) = ctx.get_index_builder() {
+				ib.cancel(&txn, opt, &ix).await?;
+			}
+		}
+		#[cfg(target_arch = "wasm32")]
+		let _ = ix;
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl Display for AlterIndexStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "ALTER INDEX")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {} ON {}", self.name, self.what)?;
+		if self.cancel {
+			write!(f, " CANCEL")?
+		}
+		Ok(())
+	}
+}
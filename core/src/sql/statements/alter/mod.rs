@@ -1,11 +1,14 @@
+mod index;
 mod table;
 
+pub use index::AlterIndexStatement;
 pub use table::AlterTableStatement;
 
 use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::iam::audit::{self, AuditEntry, AuditEvent};
 use crate::sql::value::Value;
 use derive::Store;
 use reblessive::tree::Stk;
@@ -13,12 +16,14 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub enum AlterStatement {
 	Table(AlterTableStatement),
+	#[revision(start = 2)]
+	Index(AlterIndexStatement),
 }
 
 impl AlterStatement {
@@ -34,9 +39,15 @@ impl AlterStatement {
 		opt: &Options,
 		doc: Option<&CursorDoc>,
 	) -> Result<Value, Error> {
-		match self {
+		let res = match self {
 			Self::Table(ref v) => v.compute(stk, ctx, opt, doc).await,
-		}
+			Self::Index(ref v) => v.compute(stk, ctx, opt, doc).await,
+		}?;
+		// Record the alteration in the audit log
+		audit::record(ctx, || AuditEntry::new(AuditEvent::Alter, self.to_string()).with_opt(opt))
+			.await?;
+		// Ok all good
+		Ok(res)
 	}
 }
 
@@ -44,6 +55,7 @@ impl Display for AlterStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		match self {
 			Self::Table(v) => Display::fmt(v, f),
+			Self::Index(v) => Display::fmt(v, f),
 		}
 	}
 }
@@ -37,7 +37,7 @@ impl AlterTableStatement {
 		_doc: Option<&CursorDoc>,
 	) -> Result<Value, Error> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Db)?;
+		opt.is_allowed(Action::Define, ResourceKind::Table, &Base::Db)?;
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Get the table definition
@@ -4,6 +4,7 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
 use crate::idx::ft::FtIndex;
+use crate::idx::sparse::SparseIndex;
 use crate::idx::trees::mtree::MTreeIndex;
 use crate::idx::IndexKeyBase;
 use crate::kvs::TransactionType;
@@ -36,7 +37,7 @@ impl AnalyzeStatement {
 		match self {
 			AnalyzeStatement::Idx(tb, idx) => {
 				// Allowed to run?
-				opt.is_allowed(Action::View, ResourceKind::Index, &Base::Db)?;
+				opt.is_allowed(Action::Select, ResourceKind::Index, &Base::Db)?;
 				// Read the index
 				let ix = ctx.tx().get_tb_index(opt.ns()?, opt.db()?, tb, idx).await?;
 				let ikb = IndexKeyBase::new(opt.ns()?, opt.db()?, &ix)?;
@@ -60,6 +61,28 @@ impl AnalyzeStatement {
 						.await?;
 						mt.statistics(&tx).await?.into()
 					}
+					Index::Hnsw(p) => {
+						let tx = ctx.tx();
+						let hnsw = ctx.get_index_stores().get_index_hnsw(ctx, opt, &ix, p).await?;
+						let mut hnsw = hnsw.write().await;
+						// Ensure the layers are up-to-date
+						hnsw.check_state(&tx).await?;
+						// Train the codebook of a product quantizer, which is loaded once committed
+						hnsw.train_quantizer(&tx).await?;
+						hnsw.statistics(&tx).await?.into()
+					}
+					Index::Sparse(p) => {
+						let tx = ctx.tx();
+						let sp = SparseIndex::new(
+							ctx.get_index_stores(),
+							&tx,
+							ikb,
+							p,
+							TransactionType::Read,
+						)
+						.await?;
+						sp.statistics(&tx).await?.into()
+					}
 					_ => {
 						return Err(Error::FeatureNotYetImplemented {
 							feature: "Statistics on unique and non-unique indexes.".to_string(),
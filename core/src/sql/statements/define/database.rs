@@ -34,7 +34,7 @@ impl DefineDatabaseStatement {
 		_doc: Option<&CursorDoc>,
 	) -> Result<Value, Error> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Database, &Base::Ns)?;
+		opt.is_allowed(Action::Define, ResourceKind::Database, &Base::Ns)?;
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Check if the definition exists
@@ -43,6 +43,8 @@ impl From<DefineScopeStatement> for DefineAccessStatement {
 						key: sc.code,
 					}),
 				},
+				refresh: false,
+				certificate: false,
 			}),
 			// unused fields
 			authenticate: None,
@@ -35,7 +35,7 @@ impl DefineEventStatement {
 		_doc: Option<&CursorDoc>,
 	) -> Result<Value, Error> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Event, &Base::Db)?;
+		opt.is_allowed(Action::Define, ResourceKind::Event, &Base::Db)?;
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Check if the definition exists
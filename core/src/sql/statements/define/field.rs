@@ -1,20 +1,24 @@
-use crate::ctx::Context;
+use crate::ctx::{Context, MutableContext};
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
+use crate::kvs::EncryptionKey;
 use crate::sql::fmt::{is_pretty, pretty_indent};
 use crate::sql::statements::info::InfoStructure;
-use crate::sql::statements::DefineTableStatement;
+use crate::sql::statements::{DefineIndexStatement, DefineTableStatement};
 use crate::sql::Part;
-use crate::sql::{Base, Ident, Idiom, Kind, Permissions, Strand, Value};
+use crate::sql::{Base, Ident, Idiom, Index, Kind, Permission, Permissions, Strand, Thing, Value};
 use crate::sql::{Relation, TableType};
 use derive::Store;
+use reblessive::tree::Stk;
 use revision::revisioned;
+use revision::Revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Write};
+use std::sync::Arc;
 
-#[revisioned(revision = 4)]
+#[revisioned(revision = 5)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -34,6 +38,8 @@ pub struct DefineFieldStatement {
 	pub if_not_exists: bool,
 	#[revision(start = 4)]
 	pub overwrite: bool,
+	#[revision(start = 5)]
+	pub encrypted: Option<FieldEncryption>,
 }
 
 impl DefineFieldStatement {
@@ -45,22 +51,54 @@ impl DefineFieldStatement {
 		_doc: Option<&CursorDoc>,
 	) -> Result<Value, Error> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Field, &Base::Db)?;
+		opt.is_allowed(Action::Define, ResourceKind::Field, &Base::Db)?;
 		// Get the NS and DB
 		let ns = opt.ns()?;
 		let db = opt.db()?;
 		// Fetch the transaction
 		let txn = ctx.tx();
+		// Encrypted fields use the keys of encryption at rest
+		if self.encrypted.is_some() && txn.keyring().is_none() {
+			return Err(FieldEncryption::disabled());
+		}
 		// Get the name of the field
 		let fd = self.name.to_string();
 		// Check if the definition exists
-		if txn.get_tb_field(ns, db, &self.what, &fd).await.is_ok() {
-			if self.if_not_exists {
-				return Ok(Value::None);
-			} else if !self.overwrite {
+		let existing = match txn.get_tb_field(ns, db, &self.what, &fd).await {
+			Ok(_) if self.if_not_exists => return Ok(Value::None),
+			Ok(_) if !self.overwrite => {
 				return Err(Error::FdAlreadyExists {
 					value: fd,
-				});
+				})
+			}
+			Ok(v) => Some(v),
+			Err(_) => None,
+		};
+		// Process the encryption of the field
+		let encrypted = match &self.encrypted {
+			Some(enc) => {
+				// Check that the existing indexes can index the encrypted values
+				for ix in txn.all_tb_indexes(ns, db, &self.what).await?.iter() {
+					self.check_index(ix)?;
+				}
+				// Keep the secret of an existing definition, so that stored values remain readable
+				let secret = match existing.as_ref().and_then(|v| v.encrypted.as_ref()) {
+					Some(v) => v.secret.clone(),
+					None => EncryptionKey::field_secret()?,
+				};
+				Some(FieldEncryption {
+					secret,
+					..enc.clone()
+				})
+			}
+			None => None,
+		};
+		// Stored values can only be read with the same kind of encryption
+		if let Some(old) = existing.as_ref().and_then(|v| v.encrypted.as_ref()) {
+			if encrypted.as_ref().map(|v| v.deterministic) != Some(old.deterministic) {
+				return Err(Error::Encryption(format!(
+					"The encryption of the encrypted field '{fd}' can not be changed"
+				)));
 			}
 		}
 		// Process the statement
@@ -74,6 +112,7 @@ impl DefineFieldStatement {
 				// Don't persist the `IF NOT EXISTS` clause to schema
 				if_not_exists: false,
 				overwrite: false,
+				encrypted,
 				..self.clone()
 			},
 			None,
@@ -216,6 +255,9 @@ impl Display for DefineFieldStatement {
 		if let Some(ref v) = self.assert {
 			write!(f, " ASSERT {v}")?
 		}
+		if let Some(ref v) = self.encrypted {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
@@ -241,8 +283,212 @@ impl InfoStructure for DefineFieldStatement {
 			"assert".to_string(), if let Some(v) = self.assert => v.structure(),
 			"default".to_string(), if let Some(v) = self.default => v.structure(),
 			"readonly".to_string() => self.readonly.into(),
+			"encrypted".to_string(), if let Some(v) = self.encrypted => v.structure(),
 			"permissions".to_string() => self.permissions.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 		})
 	}
 }
+
+/// The encryption of a field, and who may see its value in clear
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct FieldEncryption {
+	// Whether equal values have equal ciphertexts, so that the field can be indexed
+	pub deterministic: bool,
+	// The number of trailing characters which are shown in masked values
+	pub mask: u32,
+	// Who may see the value in clear, besides users who may edit records
+	pub reveal: Permission,
+	// The random secret from which the key of the field is derived, which is never exported
+	pub(crate) secret: Vec<u8>,
+}
+
+impl Default for FieldEncryption {
+	fn default() -> Self {
+		Self {
+			deterministic: false,
+			mask: 0,
+			// By default, values are masked for anyone who may not edit records
+			reveal: Permission::None,
+			secret: vec![],
+		}
+	}
+}
+
+impl DefineFieldStatement {
+	/// Checks that an index can be defined with this field, if it is encrypted.
+	/// Encrypted fields can only be used in standard and unique indexes, when they are
+	/// encrypted deterministically, as any other index would either store values in clear,
+	/// or could never find a value. Fields within or containing an encrypted field can not
+	/// be indexed either, as their values are only known in clear.
+	pub(crate) fn check_index(&self, ix: &DefineIndexStatement) -> Result<(), Error> {
+		let Some(enc) = &self.encrypted else {
+			return Ok(());
+		};
+		for col in ix.cols.iter() {
+			let allowed = match ix.index {
+				Index::Idx | Index::Uniq => enc.deterministic && *col == self.name,
+				_ => false,
+			};
+			if !allowed && (col.starts_with(&self.name) || self.name.starts_with(col)) {
+				return Err(Error::Encryption(format!(
+					"The index '{}' can not be defined on the encrypted field '{}'",
+					ix.name, self.name
+				)));
+			}
+		}
+		Ok(())
+	}
+
+	/// Encrypts the value of this field, if it is encrypted. Values are bound to the table
+	/// and the field, and to the record unless they are encrypted deterministically, as
+	/// equal values must then be found with an index regardless of the record.
+	pub(crate) fn encrypt_value(&self, rid: Option<&Thing>, val: &Value) -> Result<Value, Error> {
+		let Some(enc) = &self.encrypted else {
+			return Ok(val.clone());
+		};
+		// Values which are not set are not stored either
+		if val.is_none() {
+			return Ok(Value::None);
+		}
+		let mut buf = Vec::new();
+		val.serialize_revisioned(&mut buf)?;
+		let aad = self.aad(rid)?;
+		let key = EncryptionKey::field(&enc.secret)?;
+		Ok(Value::Bytes(key.encrypt_field(buf, &aad, enc.deterministic)?.into()))
+	}
+
+	/// Decrypts the value of this field, or returns it unchanged if it is not encrypted
+	pub(crate) fn decrypt_value(&self, rid: Option<&Thing>, val: Value) -> Result<Value, Error> {
+		match (&self.encrypted, val) {
+			(Some(enc), Value::Bytes(v)) if EncryptionKey::is_encrypted_field(&v) => {
+				let aad = self.aad(rid)?;
+				let key = EncryptionKey::field(&enc.secret)?;
+				let v = key.decrypt_field(v.into_inner(), &aad)?;
+				Ok(Value::deserialize_revisioned(&mut v.as_slice())?)
+			}
+			(_, v) => Ok(v),
+		}
+	}
+
+	/// Decrypts the values of this field within a stored record, in place
+	pub(crate) fn decrypt_record(&self, rid: &Thing, val: &mut Value) -> Result<(), Error> {
+		for k in val.each(&self.name).iter() {
+			let v = self.decrypt_value(Some(rid), val.pick(k))?;
+			val.put(k, v);
+		}
+		Ok(())
+	}
+
+	/// Returns the data which is authenticated along with the values of this field
+	fn aad(&self, rid: Option<&Thing>) -> Result<Vec<u8>, Error> {
+		let deterministic = self.encrypted.as_ref().is_some_and(|v| v.deterministic);
+		let rid = match (deterministic, rid) {
+			(true, _) => None,
+			(false, Some(rid)) => Some(rid.to_string()),
+			(false, None) => {
+				return Err(Error::Unreachable("Encrypted field values are bound to a record"))
+			}
+		};
+		let mut aad = Vec::new();
+		for part in
+			[Some(self.what.to_raw()), Some(self.name.to_string()), rid].into_iter().flatten()
+		{
+			aad.extend_from_slice(&(part.len() as u64).to_be_bytes());
+			aad.extend_from_slice(part.as_bytes());
+		}
+		Ok(aad)
+	}
+}
+
+impl FieldEncryption {
+	/// The error returned when encryption at rest is not enabled
+	fn disabled() -> Error {
+		Error::Encryption("Encrypted fields require an encryption key to be configured".to_string())
+	}
+
+	/// Masks a value, only showing the configured number of trailing letters and digits
+	pub(crate) fn mask(&self, val: &Value) -> Value {
+		if val.is_none_or_null() {
+			return val.clone();
+		}
+		let mut shown = 0;
+		let mut out: Vec<char> = val
+			.to_raw_string()
+			.chars()
+			.rev()
+			.map(|c| {
+				if !c.is_alphanumeric() {
+					c
+				} else if shown < self.mask {
+					shown += 1;
+					c
+				} else {
+					'*'
+				}
+			})
+			.collect();
+		out.reverse();
+		Value::from(out.into_iter().collect::<String>())
+	}
+
+	/// Checks whether the current session may see the value of this field in clear
+	pub(crate) async fn reveal(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: &CursorDoc,
+		val: &Value,
+	) -> Result<bool, Error> {
+		// Users who may edit records always see values in clear
+		if !opt.check_perms(Action::Edit, ResourceKind::Record)? {
+			return Ok(true);
+		}
+		match &self.reveal {
+			Permission::Full => Ok(true),
+			Permission::None => Ok(false),
+			Permission::Specific(_) | Permission::Policy(_) => {
+				// Fetch the permission condition
+				let e = self.reveal.condition(ctx, opt).await?;
+				// Disable permissions
+				let opt = &opt.new_with_perms(false);
+				// Configure the context
+				let mut ctx = MutableContext::new(ctx);
+				ctx.add_value("value", Arc::new(val.clone()));
+				let ctx = ctx.freeze();
+				// Process the REVEAL clause
+				Ok(e.compute(stk, &ctx, opt, Some(doc)).await?.is_truthy())
+			}
+		}
+	}
+}
+
+impl Display for FieldEncryption {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "ENCRYPTED")?;
+		if self.deterministic {
+			write!(f, " DETERMINISTIC")?
+		}
+		if self.mask > 0 {
+			write!(f, " MASK {}", self.mask)?
+		}
+		if !self.reveal.is_none() {
+			write!(f, " REVEAL {}", self.reveal)?
+		}
+		Ok(())
+	}
+}
+
+impl InfoStructure for FieldEncryption {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"deterministic".to_string() => self.deterministic.into(),
+			"mask".to_string() => self.mask.into(),
+			"reveal".to_string() => self.reveal.structure(),
+		})
+	}
+}
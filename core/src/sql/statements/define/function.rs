@@ -38,7 +38,7 @@ impl DefineFunctionStatement {
 		_doc: Option<&CursorDoc>,
 	) -> Result<Value, Error> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Function, &Base::Db)?;
+		opt.is_allowed(Action::Define, ResourceKind::Function, &Base::Db)?;
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Check if the definition exists
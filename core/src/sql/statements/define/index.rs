@@ -5,7 +5,10 @@ use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
 use crate::sql::statements::info::InfoStructure;
 use crate::sql::statements::UpdateStatement;
-use crate::sql::{Base, Ident, Idioms, Index, Output, Part, Strand, Value, Values};
+use crate::sql::{
+	Base, Cond, Expression, Function, Ident, Idioms, Index, Output, Part, Strand, Subquery, Value,
+	Values,
+};
 use derive::Store;
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -13,7 +16,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 use std::sync::Arc;
 
-#[revisioned(revision = 4)]
+#[revisioned(revision = 5)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -29,6 +32,9 @@ pub struct DefineIndexStatement {
 	pub overwrite: bool,
 	#[revision(start = 4)]
 	pub concurrently: bool,
+	/// Only the records matching this condition are indexed
+	#[revision(start = 5)]
+	pub cond: Option<Cond>,
 }
 
 impl DefineIndexStatement {
@@ -41,7 +47,7 @@ impl DefineIndexStatement {
 		doc: Option<&CursorDoc>,
 	) -> Result<Value, Error> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Index, &Base::Db)?;
+		opt.is_allowed(Action::Define, ResourceKind::Index, &Base::Db)?;
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Check if the definition exists
@@ -54,6 +60,19 @@ impl DefineIndexStatement {
 				});
 			}
 		}
+		// The records matching the condition must not change unless they are updated
+		if let Some(cond) = &self.cond {
+			if let Some(v) = Self::non_document_value(&cond.0) {
+				return Err(Error::InvalidIndexCondition {
+					index: self.name.to_string(),
+					value: v.to_string(),
+				});
+			}
+		}
+		// Encrypted fields can only be indexed when they are encrypted deterministically
+		for fd in txn.all_tb_fields(opt.ns()?, opt.db()?, &self.what).await?.iter() {
+			fd.check_index(self)?;
+		}
 		// Does the table exists?
 		match txn.get_tb(opt.ns()?, opt.db()?, &self.what).await {
 			Ok(db) => {
@@ -94,7 +113,7 @@ impl DefineIndexStatement {
 		txn.clear();
 		#[cfg(not(target_arch = "wasm32"))]
 		if self.concurrently {
-			self.async_index(ctx, opt)?;
+			self.async_index(ctx, opt).await?;
 		} else {
 			self.sync_index(stk, ctx, opt, doc).await?;
 		}
@@ -104,6 +123,73 @@ impl DefineIndexStatement {
 		Ok(Value::None)
 	}
 
+	/// Returns the first part of a condition which does not only depend on the fields of the record.
+	/// Parameters, subqueries, graph traversals and non-deterministic functions are rejected.
+	fn non_document_value(v: &Value) -> Option<&Value> {
+		match v {
+			Value::None
+			| Value::Null
+			| Value::Bool(_)
+			| Value::Bytes(_)
+			| Value::Uuid(_)
+			| Value::Number(_)
+			| Value::Strand(_)
+			| Value::Duration(_)
+			| Value::Datetime(_)
+			| Value::Geometry(_)
+			| Value::Constant(_)
+			| Value::Thing(_)
+			| Value::Regex(_) => None,
+			Value::Idiom(i)
+				if i.iter().all(|p| {
+					matches!(
+						p,
+						Part::All
+							| Part::Flatten | Part::Last
+							| Part::First | Part::Field(_)
+							| Part::Index(_) | Part::Optional
+					)
+				}) =>
+			{
+				None
+			}
+			Value::Array(a) => a.iter().find_map(Self::non_document_value),
+			Value::Object(o) => o.values().find_map(Self::non_document_value),
+			Value::Expression(e) => match e.as_ref() {
+				Expression::Unary {
+					v,
+					..
+				} => Self::non_document_value(v),
+				Expression::Binary {
+					l,
+					r,
+					..
+				} => Self::non_document_value(l).or_else(|| Self::non_document_value(r)),
+			},
+			Value::Subquery(s) => match s.as_ref() {
+				Subquery::Value(v) => Self::non_document_value(v),
+				_ => Some(v),
+			},
+			Value::Cast(c) => Self::non_document_value(&c.1),
+			Value::Function(f) => match f.as_ref() {
+				Function::Normal(name, args) if !Self::is_non_deterministic(name) => {
+					args.iter().find_map(Self::non_document_value)
+				}
+				_ => Some(v),
+			},
+			_ => Some(v),
+		}
+	}
+
+	/// Checks if a function returns a result which does not only depend on its arguments
+	fn is_non_deterministic(name: &str) -> bool {
+		["rand", "time::now", "session::", "sleep", "http::", "record::exists", "search::"]
+			.iter()
+			.any(|n| name.starts_with(n))
+			|| name == "count"
+			|| name == "vector::distance::knn"
+	}
+
 	async fn sync_index(
 		&self,
 		stk: &mut Stk,
@@ -124,12 +210,17 @@ impl DefineIndexStatement {
 	}
 
 	#[cfg(not(target_arch = "wasm32"))]
-	fn async_index(&self, ctx: &Context, opt: &Options) -> Result<(), Error> {
-		ctx.get_index_builder().ok_or(Error::Unreachable("No Index Builder"))?.build(
-			ctx,
-			opt.clone(),
-			self.clone().into(),
-		)
+	async fn async_index(&self, ctx: &Context, opt: &Options) -> Result<(), Error> {
+		// The building is identified by the definition as it is persisted
+		let ix = DefineIndexStatement {
+			if_not_exists: false,
+			overwrite: false,
+			..self.clone()
+		};
+		ctx.get_index_builder()
+			.ok_or(Error::Unreachable("No Index Builder"))?
+			.build(ctx, opt.clone(), ix.into())
+			.await
 	}
 }
 
@@ -146,6 +237,9 @@ impl Display for DefineIndexStatement {
 		if Index::Idx != self.index {
 			write!(f, " {}", self.index)?;
 		}
+		if let Some(ref v) = self.cond {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
@@ -163,6 +257,7 @@ impl InfoStructure for DefineIndexStatement {
 			"what".to_string() => self.what.structure(),
 			"cols".to_string() => self.cols.structure(),
 			"index".to_string() => self.index.structure(),
+			"cond".to_string(), if let Some(v) = self.cond => v.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 		})
 	}
@@ -9,6 +9,9 @@ mod index;
 mod model;
 mod namespace;
 mod param;
+mod policy;
+mod role;
+mod signin;
 mod table;
 mod user;
 
@@ -16,12 +19,15 @@ pub use access::DefineAccessStatement;
 pub use analyzer::DefineAnalyzerStatement;
 pub use database::DefineDatabaseStatement;
 pub use event::DefineEventStatement;
-pub use field::DefineFieldStatement;
+pub use field::{DefineFieldStatement, FieldEncryption};
 pub use function::DefineFunctionStatement;
 pub use index::DefineIndexStatement;
 pub use model::DefineModelStatement;
 pub use namespace::DefineNamespaceStatement;
 pub use param::DefineParamStatement;
+pub use policy::DefinePolicyStatement;
+pub use role::DefineRoleStatement;
+pub use signin::DefineSigninPolicyStatement;
 pub use table::DefineTableStatement;
 pub use user::DefineUserStatement;
 
@@ -34,6 +40,7 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::iam::audit::{self, AuditEntry, AuditEvent};
 use crate::sql::value::Value;
 use derive::Store;
 use reblessive::tree::Stk;
@@ -41,7 +48,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 5)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -71,6 +78,12 @@ pub enum DefineStatement {
 	Model(DefineModelStatement),
 	#[revision(start = 2)]
 	Access(DefineAccessStatement),
+	#[revision(start = 3)]
+	Role(DefineRoleStatement),
+	#[revision(start = 4)]
+	Policy(DefinePolicyStatement),
+	#[revision(start = 5)]
+	SigninPolicy(DefineSigninPolicyStatement),
 }
 
 // Revision implementations
@@ -103,7 +116,7 @@ impl DefineStatement {
 		opt: &Options,
 		doc: Option<&CursorDoc>,
 	) -> Result<Value, Error> {
-		match self {
+		let res = match self {
 			Self::Namespace(ref v) => v.compute(ctx, opt, doc).await,
 			Self::Database(ref v) => v.compute(ctx, opt, doc).await,
 			Self::Function(ref v) => v.compute(ctx, opt, doc).await,
@@ -116,6 +129,23 @@ impl DefineStatement {
 			Self::User(ref v) => v.compute(ctx, opt, doc).await,
 			Self::Model(ref v) => v.compute(ctx, opt, doc).await,
 			Self::Access(ref v) => v.compute(ctx, opt, doc).await,
+			Self::Role(ref v) => v.compute(ctx, opt, doc).await,
+			Self::Policy(ref v) => v.compute(ctx, opt, doc).await,
+			Self::SigninPolicy(ref v) => v.compute(ctx, opt, doc).await,
+		}?;
+		// Record the definition in the audit log
+		audit::record(ctx, || AuditEntry::new(AuditEvent::Define, self.redacted()).with_opt(opt))
+			.await?;
+		// Ok all good
+		Ok(res)
+	}
+
+	/// Returns the statement with potential secrets redacted, as it is recorded in the audit log
+	fn redacted(&self) -> String {
+		match self {
+			Self::User(v) => v.redacted().to_string(),
+			Self::Access(v) => v.redacted().to_string(),
+			v => v.to_string(),
 		}
 	}
 }
@@ -135,6 +165,9 @@ impl Display for DefineStatement {
 			Self::Analyzer(v) => Display::fmt(v, f),
 			Self::Model(v) => Display::fmt(v, f),
 			Self::Access(v) => Display::fmt(v, f),
+			Self::Role(v) => Display::fmt(v, f),
+			Self::Policy(v) => Display::fmt(v, f),
+			Self::SigninPolicy(v) => Display::fmt(v, f),
 		}
 	}
 }
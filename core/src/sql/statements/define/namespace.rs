@@ -33,7 +33,7 @@ impl DefineNamespaceStatement {
 		_doc: Option<&CursorDoc>,
 	) -> Result<Value, Error> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Namespace, &Base::Root)?;
+		opt.is_allowed(Action::Define, ResourceKind::Namespace, &Base::Root)?;
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Check if the definition exists
@@ -37,7 +37,7 @@ impl DefineParamStatement {
 		doc: Option<&CursorDoc>,
 	) -> Result<Value, Error> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Parameter, &Base::Db)?;
+		opt.is_allowed(Action::Define, ResourceKind::Parameter, &Base::Db)?;
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Check if the definition exists
@@ -0,0 +1,167 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::fmt::Fmt;
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::{Base, Ident, Regex, Strand, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct DefinePolicyStatement {
+	pub name: Ident,
+	pub base: Base,
+	pub actions: Vec<Action>,
+	pub cond: Value,
+	/// The patterns of the tables the policy is attached to, matching whole table names
+	pub tables: Vec<Regex>,
+	pub comment: Option<Strand>,
+	pub if_not_exists: bool,
+	pub overwrite: bool,
+}
+
+impl DefinePolicyStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+		_doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Define, ResourceKind::Policy, &self.base)?;
+		// Check the statement type
+		match self.base {
+			Base::Ns => {
+				// Fetch the transaction
+				let txn = ctx.tx();
+				// Check if the definition exists
+				if txn.get_ns_policy(opt.ns()?, &self.name).await.is_ok() {
+					if self.if_not_exists {
+						return Ok(Value::None);
+					} else if !self.overwrite {
+						return Err(Error::PlNsAlreadyExists {
+							value: self.name.to_string(),
+							ns: opt.ns()?.into(),
+						});
+					}
+				}
+				// Process the statement
+				let key = crate::key::namespace::pl::new(opt.ns()?, &self.name);
+				txn.get_or_add_ns(opt.ns()?, opt.strict).await?;
+				txn.set(
+					key,
+					DefinePolicyStatement {
+						// Don't persist the `IF NOT EXISTS` clause to schema
+						if_not_exists: false,
+						overwrite: false,
+						..self.clone()
+					},
+					None,
+				)
+				.await?;
+				// Clear the cache
+				txn.clear();
+				// Ok all good
+				Ok(Value::None)
+			}
+			Base::Db => {
+				// Fetch the transaction
+				let txn = ctx.tx();
+				// Check if the definition exists
+				if txn.get_db_policy(opt.ns()?, opt.db()?, &self.name).await.is_ok() {
+					if self.if_not_exists {
+						return Ok(Value::None);
+					} else if !self.overwrite {
+						return Err(Error::PlDbAlreadyExists {
+							value: self.name.to_string(),
+							ns: opt.ns()?.into(),
+							db: opt.db()?.into(),
+						});
+					}
+				}
+				// Process the statement
+				let key = crate::key::database::pl::new(opt.ns()?, opt.db()?, &self.name);
+				txn.get_or_add_ns(opt.ns()?, opt.strict).await?;
+				txn.get_or_add_db(opt.ns()?, opt.db()?, opt.strict).await?;
+				txn.set(
+					key,
+					DefinePolicyStatement {
+						// Don't persist the `IF NOT EXISTS` clause to schema
+						if_not_exists: false,
+						overwrite: false,
+						..self.clone()
+					},
+					None,
+				)
+				.await?;
+				// Clear the cache
+				txn.clear();
+				// Ok all good
+				Ok(Value::None)
+			}
+			// Other levels are not supported
+			_ => Err(Error::InvalidLevel(self.base.to_string())),
+		}
+	}
+	/// Checks if this policy is attached to a table.
+	/// A table pattern has to match the whole name of the table.
+	pub(crate) fn is_attached(&self, tb: &str) -> bool {
+		self.tables.iter().any(|r| {
+			regex::Regex::new(&format!("^(?:{})$", r.regex().as_str()))
+				.is_ok_and(|r| r.is_match(tb))
+		})
+	}
+	/// Checks if this policy applies to an action
+	pub(crate) fn applies(&self, action: &Action) -> bool {
+		self.actions.is_empty() || self.actions.contains(action)
+	}
+}
+
+impl Display for DefinePolicyStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DEFINE POLICY")?;
+		if self.if_not_exists {
+			write!(f, " IF NOT EXISTS")?
+		}
+		if self.overwrite {
+			write!(f, " OVERWRITE")?
+		}
+		write!(f, " {} ON {}", self.name, self.base)?;
+		if !self.actions.is_empty() {
+			write!(
+				f,
+				" FOR {}",
+				Fmt::comma_separated(self.actions.iter().map(|a| a.id().to_lowercase()))
+			)?
+		}
+		write!(f, " WHERE {}", self.cond)?;
+		if !self.tables.is_empty() {
+			write!(f, " TABLES {}", Fmt::comma_separated(&self.tables))?
+		}
+		if let Some(ref v) = self.comment {
+			write!(f, " COMMENT {v}")?
+		}
+		Ok(())
+	}
+}
+
+impl InfoStructure for DefinePolicyStatement {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"name".to_string() => self.name.structure(),
+			"base".to_string() => self.base.structure(),
+			"actions".to_string() => self.actions.iter().map(|a| Value::from(a.id().to_lowercase())).collect(),
+			"cond".to_string() => self.cond.structure(),
+			"tables".to_string() => self.tables.into_iter().map(|r| Value::from(r.to_string())).collect(),
+			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+		})
+	}
+}
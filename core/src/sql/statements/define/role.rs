@@ -0,0 +1,182 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, Grant, ResourceKind, Role};
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::{Base, Ident, Strand, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct DefineRoleStatement {
+	pub name: Ident,
+	pub base: Base,
+	pub grants: Vec<Grant>,
+	pub comment: Option<Strand>,
+	pub if_not_exists: bool,
+	pub overwrite: bool,
+}
+
+impl DefineRoleStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+		_doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Define, ResourceKind::Actor, &self.base)?;
+		// Built-in roles can not be redefined
+		if Role::from_str(&self.name).is_ok() {
+			return Err(Error::RoleReserved {
+				value: self.name.to_string(),
+			});
+		}
+		// Check the statement type
+		match self.base {
+			Base::Root => {
+				// Fetch the transaction
+				let txn = ctx.tx();
+				// Check if the definition exists
+				if txn.get_root_role(&self.name).await.is_ok() {
+					if self.if_not_exists {
+						return Ok(Value::None);
+					} else if !self.overwrite {
+						return Err(Error::RoleRootAlreadyExists {
+							value: self.name.to_string(),
+						});
+					}
+				}
+				// Process the statement
+				let key = crate::key::root::rl::new(&self.name);
+				txn.set(
+					key,
+					DefineRoleStatement {
+						// Don't persist the `IF NOT EXISTS` clause to schema
+						if_not_exists: false,
+						overwrite: false,
+						..self.clone()
+					},
+					None,
+				)
+				.await?;
+				// Clear the cache
+				txn.clear();
+				// Ok all good
+				Ok(Value::None)
+			}
+			Base::Ns => {
+				// Fetch the transaction
+				let txn = ctx.tx();
+				// Check if the definition exists
+				if txn.get_ns_role(opt.ns()?, &self.name).await.is_ok() {
+					if self.if_not_exists {
+						return Ok(Value::None);
+					} else if !self.overwrite {
+						return Err(Error::RoleNsAlreadyExists {
+							value: self.name.to_string(),
+							ns: opt.ns()?.into(),
+						});
+					}
+				}
+				// Process the statement
+				let key = crate::key::namespace::rl::new(opt.ns()?, &self.name);
+				txn.get_or_add_ns(opt.ns()?, opt.strict).await?;
+				txn.set(
+					key,
+					DefineRoleStatement {
+						// Don't persist the `IF NOT EXISTS` clause to schema
+						if_not_exists: false,
+						overwrite: false,
+						..self.clone()
+					},
+					None,
+				)
+				.await?;
+				// Clear the cache
+				txn.clear();
+				// Ok all good
+				Ok(Value::None)
+			}
+			Base::Db => {
+				// Fetch the transaction
+				let txn = ctx.tx();
+				// Check if the definition exists
+				if txn.get_db_role(opt.ns()?, opt.db()?, &self.name).await.is_ok() {
+					if self.if_not_exists {
+						return Ok(Value::None);
+					} else if !self.overwrite {
+						return Err(Error::RoleDbAlreadyExists {
+							value: self.name.to_string(),
+							ns: opt.ns()?.into(),
+							db: opt.db()?.into(),
+						});
+					}
+				}
+				// Process the statement
+				let key = crate::key::database::rl::new(opt.ns()?, opt.db()?, &self.name);
+				txn.get_or_add_ns(opt.ns()?, opt.strict).await?;
+				txn.get_or_add_db(opt.ns()?, opt.db()?, opt.strict).await?;
+				txn.set(
+					key,
+					DefineRoleStatement {
+						// Don't persist the `IF NOT EXISTS` clause to schema
+						if_not_exists: false,
+						overwrite: false,
+						..self.clone()
+					},
+					None,
+				)
+				.await?;
+				// Clear the cache
+				txn.clear();
+				// Ok all good
+				Ok(Value::None)
+			}
+			// Other levels are not supported
+			_ => Err(Error::InvalidLevel(self.base.to_string())),
+		}
+	}
+}
+
+impl Display for DefineRoleStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DEFINE ROLE")?;
+		if self.if_not_exists {
+			write!(f, " IF NOT EXISTS")?
+		}
+		if self.overwrite {
+			write!(f, " OVERWRITE")?
+		}
+		write!(f, " {} ON {}", self.name, self.base)?;
+		for grant in self.grants.iter() {
+			write!(f, " GRANT {grant}")?
+		}
+		if let Some(ref v) = self.comment {
+			write!(f, " COMMENT {v}")?
+		}
+		Ok(())
+	}
+}
+
+impl InfoStructure for DefineRoleStatement {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"name".to_string() => self.name.structure(),
+			"base".to_string() => self.base.structure(),
+			"grants".to_string() => self.grants.into_iter().map(|g| Value::from(map! {
+				"actions".to_string() => g.actions.iter().map(|a| Value::from(Grant::action_name(a))).collect(),
+				"resources".to_string() => g.resources.iter().map(|r| Value::from(Grant::resource_name(r))).collect(),
+			})).collect(),
+			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+		})
+	}
+}
@@ -0,0 +1,216 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::user::PasswordStrength;
+use crate::sql::{Base, Duration, Strand, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct DefineSigninPolicyStatement {
+	pub base: Base,
+	// The minimum number of characters in a password
+	pub length: u32,
+	// The minimum number of character classes used in a password
+	pub classes: u8,
+	// The number of consecutive failed attempts after which signin is locked
+	// A value of zero means that signin is never locked
+	pub attempts: u32,
+	// The delay after the first failed attempt, which doubles with each further failure
+	pub backoff: Duration,
+	// The duration for which signin is locked, after which failed attempts are forgotten
+	pub lockout: Duration,
+	pub comment: Option<Strand>,
+	pub if_not_exists: bool,
+	pub overwrite: bool,
+}
+
+impl Default for DefineSigninPolicyStatement {
+	fn default() -> Self {
+		Self {
+			base: Base::default(),
+			// By default, passwords are not restricted
+			length: 0,
+			classes: 0,
+			// By default, signin is locked after five failed attempts
+			attempts: 5,
+			// By default, the delay after a failed attempt starts at one second
+			backoff: Duration::from_secs(1),
+			// By default, signin is locked for fifteen minutes
+			lockout: Duration::from_mins(15),
+			comment: None,
+			if_not_exists: false,
+			overwrite: false,
+		}
+	}
+}
+
+impl DefineSigninPolicyStatement {
+	/// Checks that a password of the specified strength satisfies this policy
+	pub(crate) fn check_password(&self, strength: &PasswordStrength) -> Result<(), Error> {
+		if strength.length < self.length || strength.classes < self.classes {
+			return Err(Error::PasswordTooWeak {
+				length: self.length,
+				classes: self.classes,
+			});
+		}
+		Ok(())
+	}
+
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+		_doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Define, ResourceKind::Actor, &self.base)?;
+		// Check the statement type
+		match self.base {
+			Base::Root => {
+				// Fetch the transaction
+				let txn = ctx.tx();
+				// Check if the definition exists
+				if txn.get_root_signin_policy().await.is_ok() {
+					if self.if_not_exists {
+						return Ok(Value::None);
+					} else if !self.overwrite {
+						return Err(Error::SpRootAlreadyExists);
+					}
+				}
+				// Process the statement
+				let key = crate::key::root::sp::new();
+				txn.set(
+					key,
+					DefineSigninPolicyStatement {
+						// Don't persist the `IF NOT EXISTS` clause to schema
+						if_not_exists: false,
+						overwrite: false,
+						..self.clone()
+					},
+					None,
+				)
+				.await?;
+				// Clear the cache
+				txn.clear();
+				// Ok all good
+				Ok(Value::None)
+			}
+			Base::Ns => {
+				// Fetch the transaction
+				let txn = ctx.tx();
+				// Check if the definition exists
+				if txn.get_ns_signin_policy(opt.ns()?).await.is_ok() {
+					if self.if_not_exists {
+						return Ok(Value::None);
+					} else if !self.overwrite {
+						return Err(Error::SpNsAlreadyExists {
+							ns: opt.ns()?.into(),
+						});
+					}
+				}
+				// Process the statement
+				let key = crate::key::namespace::sp::new(opt.ns()?);
+				txn.get_or_add_ns(opt.ns()?, opt.strict).await?;
+				txn.set(
+					key,
+					DefineSigninPolicyStatement {
+						// Don't persist the `IF NOT EXISTS` clause to schema
+						if_not_exists: false,
+						overwrite: false,
+						..self.clone()
+					},
+					None,
+				)
+				.await?;
+				// Clear the cache
+				txn.clear();
+				// Ok all good
+				Ok(Value::None)
+			}
+			Base::Db => {
+				// Fetch the transaction
+				let txn = ctx.tx();
+				// Check if the definition exists
+				if txn.get_db_signin_policy(opt.ns()?, opt.db()?).await.is_ok() {
+					if self.if_not_exists {
+						return Ok(Value::None);
+					} else if !self.overwrite {
+						return Err(Error::SpDbAlreadyExists {
+							ns: opt.ns()?.into(),
+							db: opt.db()?.into(),
+						});
+					}
+				}
+				// Process the statement
+				let key = crate::key::database::sp::new(opt.ns()?, opt.db()?);
+				txn.get_or_add_ns(opt.ns()?, opt.strict).await?;
+				txn.get_or_add_db(opt.ns()?, opt.db()?, opt.strict).await?;
+				txn.set(
+					key,
+					DefineSigninPolicyStatement {
+						// Don't persist the `IF NOT EXISTS` clause to schema
+						if_not_exists: false,
+						overwrite: false,
+						..self.clone()
+					},
+					None,
+				)
+				.await?;
+				// Clear the cache
+				txn.clear();
+				// Ok all good
+				Ok(Value::None)
+			}
+			// Other levels are not supported
+			_ => Err(Error::InvalidLevel(self.base.to_string())),
+		}
+	}
+}
+
+impl Display for DefineSigninPolicyStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DEFINE SIGNIN POLICY")?;
+		if self.if_not_exists {
+			write!(f, " IF NOT EXISTS")?
+		}
+		if self.overwrite {
+			write!(f, " OVERWRITE")?
+		}
+		// Always print all settings so defaults can be changed in the future
+		write!(
+			f,
+			" ON {} PASSWORD LENGTH {} CLASSES {} ATTEMPTS {} BACKOFF {} LOCKOUT {}",
+			self.base, self.length, self.classes, self.attempts, self.backoff, self.lockout
+		)?;
+		if let Some(ref v) = self.comment {
+			write!(f, " COMMENT {v}")?
+		}
+		Ok(())
+	}
+}
+
+impl InfoStructure for DefineSigninPolicyStatement {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"base".to_string() => self.base.structure(),
+			"password".to_string() => Value::from(map! {
+				"length".to_string() => self.length.into(),
+				"classes".to_string() => self.classes.into(),
+			}),
+			"attempts".to_string() => self.attempts.into(),
+			"backoff".to_string() => self.backoff.into(),
+			"lockout".to_string() => self.lockout.into(),
+			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+		})
+	}
+}
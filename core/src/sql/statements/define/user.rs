@@ -2,10 +2,13 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::iam::{Action, ResourceKind};
+use crate::iam::{lockout, totp, Action, ResourceKind, Role};
 use crate::sql::statements::info::InfoStructure;
 use crate::sql::{
-	escape::quote_str, fmt::Fmt, user::UserDuration, Base, Duration, Ident, Strand, Value,
+	escape::quote_str,
+	fmt::Fmt,
+	user::{PasswordStrength, UserDuration},
+	Base, Duration, Ident, Strand, Value,
 };
 use argon2::{
 	password_hash::{PasswordHasher, SaltString},
@@ -16,8 +19,9 @@ use rand::{distributions::Alphanumeric, rngs::OsRng, Rng};
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
+use std::str::FromStr;
 
-#[revisioned(revision = 4)]
+#[revisioned(revision = 6)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -34,6 +38,15 @@ pub struct DefineUserStatement {
 	pub if_not_exists: bool,
 	#[revision(start = 4)]
 	pub overwrite: bool,
+	// The base32 secret for time-based one-time passwords
+	#[revision(start = 5)]
+	pub totp: Option<String>,
+	// The hashes of the single-use recovery codes
+	#[revision(start = 5)]
+	pub recovery: Vec<String>,
+	// Whether the user can authenticate with a client certificate
+	#[revision(start = 6)]
+	pub certificate: bool,
 }
 
 impl From<(Base, &str, &str, &str)> for DefineUserStatement {
@@ -55,11 +68,27 @@ impl From<(Base, &str, &str, &str)> for DefineUserStatement {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
+			totp: None,
+			recovery: Vec::new(),
+			certificate: false,
 		}
 	}
 }
 
 impl DefineUserStatement {
+	/// Returns a version of the statement where the password hash and second factors are redacted
+	/// This function should be used when displaying the statement to datastore users
+	/// This function should NOT be used when displaying the statement for export purposes
+	pub fn redacted(&self) -> DefineUserStatement {
+		let mut dus = self.clone();
+		dus.hash = "[REDACTED]".into();
+		if dus.totp.is_some() {
+			dus.totp = Some("[REDACTED]".into());
+		}
+		dus.recovery = dus.recovery.iter().map(|_| "[REDACTED]".into()).collect();
+		dus
+	}
+
 	pub(crate) fn from_parsed_values(
 		name: Ident,
 		base: Base,
@@ -84,13 +113,34 @@ impl DefineUserStatement {
 		self.hash = Argon2::default()
 			.hash_password(password.as_bytes(), &SaltString::generate(&mut OsRng))
 			.unwrap()
-			.to_string()
+			.to_string();
+		PasswordStrength::keep(&self.hash, PasswordStrength::of(password));
 	}
 
 	pub(crate) fn set_passhash(&mut self, passhash: String) {
 		self.hash = passhash;
 	}
 
+	pub(crate) fn set_totp(&mut self, secret: Option<String>) {
+		self.totp = secret;
+	}
+
+	pub(crate) fn set_recovery(&mut self, codes: &[String]) {
+		self.recovery = codes
+			.iter()
+			.map(|code| {
+				Argon2::default()
+					.hash_password(code.as_bytes(), &SaltString::generate(&mut OsRng))
+					.unwrap()
+					.to_string()
+			})
+			.collect();
+	}
+
+	pub(crate) fn set_recoveryhash(&mut self, hashes: Vec<String>) {
+		self.recovery = hashes;
+	}
+
 	pub(crate) fn set_token_duration(&mut self, duration: Option<Duration>) {
 		self.duration.token = duration;
 	}
@@ -99,6 +149,38 @@ impl DefineUserStatement {
 		self.duration.session = duration;
 	}
 
+	/// Check that the custom roles assigned to the user are defined on the user level
+	async fn check_roles(&self, ctx: &Context, opt: &Options) -> Result<(), Error> {
+		let txn = ctx.tx();
+		for role in self.roles.iter().filter(|r| Role::from_str(r).is_err()) {
+			match self.base {
+				Base::Root => txn.get_root_role(role).await?,
+				Base::Ns => txn.get_ns_role(opt.ns()?, role).await?,
+				Base::Db => txn.get_db_role(opt.ns()?, opt.db()?, role).await?,
+				_ => return Err(Error::InvalidLevel(self.base.to_string())),
+			};
+		}
+		Ok(())
+	}
+
+	/// Check that a password provided in plain text satisfies the signin policy of the base
+	async fn check_password(&self, ctx: &Context, opt: &Options) -> Result<(), Error> {
+		// The strength of a hashed password is not known
+		let Some(strength) = PasswordStrength::pending(&self.hash) else {
+			return Ok(());
+		};
+		let (ns, db) = match self.base {
+			Base::Root => (None, None),
+			Base::Ns => (Some(opt.ns()?), None),
+			Base::Db => (Some(opt.ns()?), Some(opt.db()?)),
+			_ => return Err(Error::InvalidLevel(self.base.to_string())),
+		};
+		match lockout::policy(&ctx.tx(), ns, db).await? {
+			Some(policy) => policy.check_password(&strength),
+			None => Ok(()),
+		}
+	}
+
 	/// Process this type returning a computed simple Value
 	pub(crate) async fn compute(
 		&self,
@@ -107,7 +189,17 @@ impl DefineUserStatement {
 		_doc: Option<&CursorDoc>,
 	) -> Result<Value, Error> {
 		// Allowed to run?
-		opt.is_allowed(Action::Edit, ResourceKind::Actor, &self.base)?;
+		opt.is_allowed(Action::Define, ResourceKind::Actor, &self.base)?;
+		// Check the assigned roles
+		self.check_roles(ctx, opt).await?;
+		// Check the password strength
+		self.check_password(ctx, opt).await?;
+		// Check that codes can be generated from the second factor
+		if let Some(secret) = &self.totp {
+			if totp::decode_secret(secret).is_none() {
+				return Err(Error::InvalidTotpSecret);
+			}
+		}
 		// Check the statement type
 		match self.base {
 			Base::Root => {
@@ -231,7 +323,16 @@ impl Display for DefineUserStatement {
 			self.base,
 			quote_str(&self.hash),
 			Fmt::comma_separated(
-				&self.roles.iter().map(|r| r.to_string().to_uppercase()).collect::<Vec<String>>()
+				&self
+					.roles
+					.iter()
+					.map(|r| match Role::from_str(r) {
+						// Built-in roles are printed in uppercase
+						Ok(_) => r.to_string().to_uppercase(),
+						// Custom role names are case-sensitive
+						Err(_) => r.to_string(),
+					})
+					.collect::<Vec<String>>()
 			),
 		)?;
 		// Always print relevant durations so defaults can be changed in the future
@@ -254,6 +355,19 @@ impl Display for DefineUserStatement {
 				None => "NONE".to_string(),
 			}
 		)?;
+		if let Some(ref v) = self.totp {
+			write!(f, " TOTP {}", quote_str(v))?
+		}
+		if !self.recovery.is_empty() {
+			write!(
+				f,
+				" RECOVERYHASH {}",
+				Fmt::comma_separated(self.recovery.iter().map(|v| quote_str(v)))
+			)?
+		}
+		if self.certificate {
+			write!(f, " CERTIFICATE")?
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
@@ -272,6 +386,9 @@ impl InfoStructure for DefineUserStatement {
 				"token".to_string() => self.duration.token.into(),
 				"session".to_string() => self.duration.session.into(),
 			}),
+			"totp".to_string() => self.totp.is_some().into(),
+			"recovery".to_string() => self.recovery.len().into(),
+			"certificate".to_string() => self.certificate.into(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 		})
 	}
@@ -2,16 +2,19 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::iam::lockout;
 use crate::iam::Action;
 use crate::iam::ResourceKind;
-use crate::sql::{Base, Ident, Object, Value};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::kvs::BuildingState;
+use crate::sql::{Base, Datetime, Ident, Object, Value};
 use derive::Store;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::sync::Arc;
 
-#[revisioned(revision = 4)]
+#[revisioned(revision = 5)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -36,6 +39,9 @@ pub enum InfoStatement {
 	#[revision(start = 3)]
 	#[revision(override(revision = 3, discriminant = 10))]
 	Index(Ident, Ident, bool),
+
+	#[revision(start = 5)]
+	Audit(Option<Datetime>, Option<u32>, bool),
 }
 
 impl InfoStatement {
@@ -49,15 +55,19 @@ impl InfoStatement {
 		match self {
 			InfoStatement::Root(structured) => {
 				// Allowed to run?
-				opt.is_allowed(Action::View, ResourceKind::Any, &Base::Root)?;
+				opt.is_allowed(Action::Select, ResourceKind::Any, &Base::Root)?;
 				// Get the transaction
 				let txn = ctx.tx();
+				// Get the signin policy
+				let sp = lockout::policy(&txn, None, None).await?;
 				// Create the result set
 				Ok(match structured {
 					true => Value::from(map! {
 						"accesses".to_string() => process(txn.all_root_accesses().await?.iter().map(|v| v.redacted()).collect()),
 						"namespaces".to_string() => process(txn.all_ns().await?),
 						"nodes".to_string() => process(txn.all_nodes().await?),
+						"roles".to_string() => process(txn.all_root_roles().await?),
+						"signin".to_string(), if let Some(v) = &sp => v.as_ref().clone().structure(),
 						"users".to_string() => process(txn.all_root_users().await?),
 					}),
 					false => Value::from(map! {
@@ -82,6 +92,14 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"roles".to_string() => {
+							let mut out = Object::default();
+							for v in txn.all_root_roles().await?.iter() {
+								out.insert(v.name.to_raw(), v.to_string().into());
+							}
+							out.into()
+						},
+						"signin".to_string(), if let Some(v) = &sp => v.to_string().into(),
 						"users".to_string() => {
 							let mut out = Object::default();
 							for v in txn.all_root_users().await?.iter() {
@@ -94,16 +112,21 @@ impl InfoStatement {
 			}
 			InfoStatement::Ns(structured) => {
 				// Allowed to run?
-				opt.is_allowed(Action::View, ResourceKind::Any, &Base::Ns)?;
+				opt.is_allowed(Action::Select, ResourceKind::Any, &Base::Ns)?;
 				// Get the NS
 				let ns = opt.ns()?;
 				// Get the transaction
 				let txn = ctx.tx();
+				// Get the signin policy
+				let sp = lockout::policy(&txn, Some(ns), None).await?;
 				// Create the result set
 				Ok(match structured {
 					true => Value::from(map! {
 						"accesses".to_string() => process(txn.all_ns_accesses(ns).await?.iter().map(|v| v.redacted()).collect()),
 						"databases".to_string() => process(txn.all_db(ns).await?),
+						"policies".to_string() => process(txn.all_ns_policies(ns).await?),
+						"roles".to_string() => process(txn.all_ns_roles(ns).await?),
+						"signin".to_string(), if let Some(v) = &sp => v.as_ref().clone().structure(),
 						"users".to_string() => process(txn.all_ns_users(ns).await?),
 					}),
 					false => Value::from(map! {
@@ -121,6 +144,21 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"policies".to_string() => {
+							let mut out = Object::default();
+							for v in txn.all_ns_policies(ns).await?.iter() {
+								out.insert(v.name.to_raw(), v.to_string().into());
+							}
+							out.into()
+						},
+						"roles".to_string() => {
+							let mut out = Object::default();
+							for v in txn.all_ns_roles(ns).await?.iter() {
+								out.insert(v.name.to_raw(), v.to_string().into());
+							}
+							out.into()
+						},
+						"signin".to_string(), if let Some(v) = &sp => v.to_string().into(),
 						"users".to_string() => {
 							let mut out = Object::default();
 							for v in txn.all_ns_users(ns).await?.iter() {
@@ -133,12 +171,14 @@ impl InfoStatement {
 			}
 			InfoStatement::Db(structured) => {
 				// Allowed to run?
-				opt.is_allowed(Action::View, ResourceKind::Any, &Base::Db)?;
+				opt.is_allowed(Action::Select, ResourceKind::Any, &Base::Db)?;
 				// Get the NS and DB
 				let ns = opt.ns()?;
 				let db = opt.db()?;
 				// Get the transaction
 				let txn = ctx.tx();
+				// Get the signin policy
+				let sp = lockout::policy(&txn, Some(ns), Some(db)).await?;
 				// Create the result set
 				Ok(match structured {
 					true => Value::from(map! {
@@ -147,6 +187,9 @@ impl InfoStatement {
 						"functions".to_string() => process(txn.all_db_functions(ns, db).await?),
 						"models".to_string() => process(txn.all_db_models(ns, db).await?),
 						"params".to_string() => process(txn.all_db_params(ns, db).await?),
+						"policies".to_string() => process(txn.all_db_policies(ns, db).await?),
+						"roles".to_string() => process(txn.all_db_roles(ns, db).await?),
+						"signin".to_string(), if let Some(v) = &sp => v.as_ref().clone().structure(),
 						"tables".to_string() => process(txn.all_tb(ns, db).await?),
 						"users".to_string() => process(txn.all_db_users(ns, db).await?),
 					}),
@@ -186,6 +229,21 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"policies".to_string() => {
+							let mut out = Object::default();
+							for v in txn.all_db_policies(ns, db).await?.iter() {
+								out.insert(v.name.to_raw(), v.to_string().into());
+							}
+							out.into()
+						},
+						"roles".to_string() => {
+							let mut out = Object::default();
+							for v in txn.all_db_roles(ns, db).await?.iter() {
+								out.insert(v.name.to_raw(), v.to_string().into());
+							}
+							out.into()
+						},
+						"signin".to_string(), if let Some(v) = &sp => v.to_string().into(),
 						"tables".to_string() => {
 							let mut out = Object::default();
 							for v in txn.all_tb(ns, db).await?.iter() {
@@ -205,7 +263,7 @@ impl InfoStatement {
 			}
 			InfoStatement::Tb(tb, structured) => {
 				// Allowed to run?
-				opt.is_allowed(Action::View, ResourceKind::Any, &Base::Db)?;
+				opt.is_allowed(Action::Select, ResourceKind::Table, &Base::Db)?;
 				// Get the NS and DB
 				let ns = opt.ns()?;
 				let db = opt.db()?;
@@ -263,7 +321,7 @@ impl InfoStatement {
 				// Get the base type
 				let base = base.clone().unwrap_or(opt.selected_base()?);
 				// Allowed to run?
-				opt.is_allowed(Action::View, ResourceKind::Actor, &base)?;
+				opt.is_allowed(Action::Select, ResourceKind::Actor, &base)?;
 				// Get the transaction
 				let txn = ctx.tx();
 				// Process the user
@@ -281,22 +339,55 @@ impl InfoStatement {
 			}
 			InfoStatement::Index(index, table, _structured) => {
 				// Allowed to run?
-				opt.is_allowed(Action::View, ResourceKind::Actor, &Base::Db)?;
+				opt.is_allowed(Action::Select, ResourceKind::Actor, &Base::Db)?;
 				// Get the transaction
 				let txn = ctx.tx();
 				// Output
 				#[cfg(not(target_arch = "wasm32"))]
-				if let Some(ib) = ctx.get_index_builder() {
+				{
 					// Obtain the index
 					let res = txn.get_tb_index(opt.ns()?, opt.db()?, table, index).await?;
-					if let Some(status) = ib.get_status(&res).await {
+					// A cancellation is persisted before the building observes it
+					let persisted = BuildingState::get(&txn, opt.ns()?, opt.db()?, &res).await?;
+					let mut state = persisted.clone().filter(|st| st.is_cancelled());
+					// The status of a building running on this node is the most recent one
+					if state.is_none() {
+						if let Some(ib) = ctx.get_index_builder() {
+							state = ib.get_status(&res).await;
+						}
+					}
+					// Otherwise we use the state persisted by the building
+					if state.is_none() {
+						state = persisted;
+					}
+					if let Some(state) = state {
 						let mut out = Object::default();
-						out.insert("building".to_string(), status.into());
+						out.insert("building".to_string(), state.into());
 						return Ok(out.into());
 					}
 				}
 				Ok(Object::default().into())
 			}
+			InfoStatement::Audit(since, limit, _) => {
+				// Entries are filtered by the selected namespace and database
+				let base = opt.selected_base()?;
+				// Allowed to run?
+				opt.is_allowed(Action::Select, ResourceKind::Audit, &base)?;
+				// Entries can only be verified with the key of the audit log
+				let audit = ctx.get_audit().ok_or(Error::AuditDisabled)?;
+				// Get the transaction
+				let txn = ctx.tx();
+				// Fetch and verify the recorded entries
+				let (ns, db) = match base {
+					Base::Root => (None, None),
+					Base::Ns => (Some(opt.ns()?), None),
+					_ => (Some(opt.ns()?), Some(opt.db()?)),
+				};
+				let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+				let entries = audit.entries(&txn, since.as_ref(), ns, db, limit).await?;
+				// Create the result set
+				Ok(entries.into_iter().map(Value::from).collect::<Vec<_>>().into())
+			}
 		}
 	}
 }
@@ -322,6 +413,19 @@ impl fmt::Display for InfoStatement {
 			},
 			Self::Index(ref i, ref t, false) => write!(f, "INFO FOR INDEX {i} ON {t}"),
 			Self::Index(ref i, ref t, true) => write!(f, "INFO FOR INDEX {i} ON {t} STRUCTURE"),
+			Self::Audit(ref since, ref limit, structured) => {
+				f.write_str("INFO FOR AUDIT")?;
+				if let Some(ref v) = since {
+					write!(f, " SINCE {v}")?;
+				}
+				if let Some(ref v) = limit {
+					write!(f, " LIMIT {v}")?;
+				}
+				if *structured {
+					f.write_str(" STRUCTURE")?;
+				}
+				Ok(())
+			}
 		}
 	}
 }
@@ -339,6 +443,7 @@ impl InfoStatement {
 			InfoStatement::Tb(t, _) => InfoStatement::Tb(t, true),
 			InfoStatement::User(u, b, _) => InfoStatement::User(u, b, true),
 			InfoStatement::Index(i, t, _) => InfoStatement::Index(i, t, true),
+			InfoStatement::Audit(s, l, _) => InfoStatement::Audit(s, l, true),
 		}
 	}
 }
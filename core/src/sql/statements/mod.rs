@@ -58,18 +58,19 @@ pub use self::throw::ThrowStatement;
 pub use self::update::UpdateStatement;
 pub use self::upsert::UpsertStatement;
 
-pub use self::alter::{AlterStatement, AlterTableStatement};
+pub use self::alter::{AlterIndexStatement, AlterStatement, AlterTableStatement};
 
 pub use self::define::{
 	DefineAccessStatement, DefineAnalyzerStatement, DefineDatabaseStatement, DefineEventStatement,
 	DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement, DefineModelStatement,
-	DefineNamespaceStatement, DefineParamStatement, DefineStatement, DefineTableStatement,
-	DefineUserStatement,
+	DefineNamespaceStatement, DefineParamStatement, DefinePolicyStatement, DefineRoleStatement,
+	DefineSigninPolicyStatement, DefineStatement, DefineTableStatement, DefineUserStatement,
+	FieldEncryption,
 };
 
 pub use self::remove::{
 	RemoveAccessStatement, RemoveAnalyzerStatement, RemoveDatabaseStatement, RemoveEventStatement,
 	RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement, RemoveModelStatement,
-	RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement, RemoveTableStatement,
-	RemoveUserStatement,
+	RemoveNamespaceStatement, RemoveParamStatement, RemovePolicyStatement, RemoveRoleStatement,
+	RemoveSigninPolicyStatement, RemoveStatement, RemoveTableStatement, RemoveUserStatement,
 };
@@ -70,7 +70,7 @@ impl RebuildIndexStatement {
 	) -> Result<Value, Error> {
 		let future = async {
 			// Allowed to run?
-			opt.is_allowed(Action::Edit, ResourceKind::Index, &Base::Db)?;
+			opt.is_allowed(Action::Define, ResourceKind::Index, &Base::Db)?;
 			// Get the index definition
 			let ix = ctx.tx().get_tb_index(opt.ns()?, opt.db()?, &self.what, &self.name).await?;
 			// Create the remove statement
@@ -22,7 +22,7 @@ impl RemoveAnalyzerStatement {
 	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value, Error> {
 		let future = async {
 			// Allowed to run?
-			opt.is_allowed(Action::Edit, ResourceKind::Analyzer, &Base::Db)?;
+			opt.is_allowed(Action::Remove, ResourceKind::Analyzer, &Base::Db)?;
 			// Get the transaction
 			let txn = ctx.tx();
 			// Get the definition
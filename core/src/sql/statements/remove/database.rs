@@ -23,7 +23,7 @@ impl RemoveDatabaseStatement {
 	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value, Error> {
 		let future = async {
 			// Allowed to run?
-			opt.is_allowed(Action::Edit, ResourceKind::Database, &Base::Ns)?;
+			opt.is_allowed(Action::Remove, ResourceKind::Database, &Base::Ns)?;
 			// Get the transaction
 			let txn = ctx.tx();
 			// Remove the index stores
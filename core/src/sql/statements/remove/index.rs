@@ -24,13 +24,19 @@ impl RemoveIndexStatement {
 	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value, Error> {
 		let future = async {
 			// Allowed to run?
-			opt.is_allowed(Action::Edit, ResourceKind::Index, &Base::Db)?;
+			opt.is_allowed(Action::Remove, ResourceKind::Index, &Base::Db)?;
 			// Get the transaction
 			let txn = ctx.tx();
 			// Clear the index store cache
 			ctx.get_index_stores()
 				.index_removed(&txn, opt.ns()?, opt.db()?, &self.what, &self.name)
 				.await?;
+			// Stop any building of the index running on this node
+			#[cfg(not(target_arch = "wasm32"))]
+			if let Some(ib) = ctx.get_index_builder() {
+				let ix = txn.get_tb_index(opt.ns()?, opt.db()?, &self.what, &self.name).await?;
+				ib.remove_index(&ix);
+			}
 			// Delete the definition
 			let key = crate::key::table::ix::new(opt.ns()?, opt.db()?, &self.what, &self.name);
 			txn.del(key).await?;
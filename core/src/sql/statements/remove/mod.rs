@@ -8,6 +8,9 @@ mod index;
 mod model;
 mod namespace;
 mod param;
+mod policy;
+mod role;
+mod signin;
 mod table;
 mod user;
 
@@ -21,6 +24,9 @@ pub use index::RemoveIndexStatement;
 pub use model::RemoveModelStatement;
 pub use namespace::RemoveNamespaceStatement;
 pub use param::RemoveParamStatement;
+pub use policy::RemovePolicyStatement;
+pub use role::RemoveRoleStatement;
+pub use signin::RemoveSigninPolicyStatement;
 pub use table::RemoveTableStatement;
 pub use user::RemoveUserStatement;
 
@@ -28,13 +34,14 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::iam::audit::{self, AuditEntry, AuditEvent};
 use crate::sql::Value;
 use derive::Store;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -51,6 +58,12 @@ pub enum RemoveStatement {
 	Index(RemoveIndexStatement),
 	User(RemoveUserStatement),
 	Model(RemoveModelStatement),
+	#[revision(start = 2)]
+	Role(RemoveRoleStatement),
+	#[revision(start = 3)]
+	Policy(RemovePolicyStatement),
+	#[revision(start = 4)]
+	SigninPolicy(RemoveSigninPolicyStatement),
 }
 
 impl RemoveStatement {
@@ -65,7 +78,7 @@ impl RemoveStatement {
 		opt: &Options,
 		_doc: Option<&CursorDoc>,
 	) -> Result<Value, Error> {
-		match self {
+		let res = match self {
 			Self::Namespace(ref v) => v.compute(ctx, opt).await,
 			Self::Database(ref v) => v.compute(ctx, opt).await,
 			Self::Function(ref v) => v.compute(ctx, opt).await,
@@ -78,7 +91,15 @@ impl RemoveStatement {
 			Self::Analyzer(ref v) => v.compute(ctx, opt).await,
 			Self::User(ref v) => v.compute(ctx, opt).await,
 			Self::Model(ref v) => v.compute(ctx, opt).await,
-		}
+			Self::Role(ref v) => v.compute(ctx, opt).await,
+			Self::Policy(ref v) => v.compute(ctx, opt).await,
+			Self::SigninPolicy(ref v) => v.compute(ctx, opt).await,
+		}?;
+		// Record the removal in the audit log
+		audit::record(ctx, || AuditEntry::new(AuditEvent::Remove, self.to_string()).with_opt(opt))
+			.await?;
+		// Ok all good
+		Ok(res)
 	}
 }
 
@@ -97,6 +118,9 @@ impl Display for RemoveStatement {
 			Self::Analyzer(v) => Display::fmt(v, f),
 			Self::User(v) => Display::fmt(v, f),
 			Self::Model(v) => Display::fmt(v, f),
+			Self::Role(v) => Display::fmt(v, f),
+			Self::Policy(v) => Display::fmt(v, f),
+			Self::SigninPolicy(v) => Display::fmt(v, f),
 		}
 	}
 }
@@ -24,7 +24,7 @@ impl RemoveModelStatement {
 	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value, Error> {
 		let future = async {
 			// Allowed to run?
-			opt.is_allowed(Action::Edit, ResourceKind::Model, &Base::Db)?;
+			opt.is_allowed(Action::Remove, ResourceKind::Model, &Base::Db)?;
 			// Get the transaction
 			let txn = ctx.tx();
 			// Get the defined model
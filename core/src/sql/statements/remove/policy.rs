@@ -0,0 +1,83 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::{Base, Ident, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct RemovePolicyStatement {
+	pub name: Ident,
+	pub base: Base,
+	pub if_exists: bool,
+}
+
+impl RemovePolicyStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value, Error> {
+		let future = async {
+			// Allowed to run?
+			opt.is_allowed(Action::Remove, ResourceKind::Policy, &self.base)?;
+			// Check the statement type
+			match self.base {
+				Base::Ns => {
+					// Get the transaction
+					let txn = ctx.tx();
+					// Get the definition
+					let pl = txn.get_ns_policy(opt.ns()?, &self.name).await?;
+					// Delete the definition
+					let key = crate::key::namespace::pl::new(opt.ns()?, &pl.name);
+					txn.del(key).await?;
+					// Clear the cache
+					txn.clear();
+					// Ok all good
+					Ok(Value::None)
+				}
+				Base::Db => {
+					// Get the transaction
+					let txn = ctx.tx();
+					// Get the definition
+					let pl = txn.get_db_policy(opt.ns()?, opt.db()?, &self.name).await?;
+					// Delete the definition
+					let key = crate::key::database::pl::new(opt.ns()?, opt.db()?, &pl.name);
+					txn.del(key).await?;
+					// Clear the cache
+					txn.clear();
+					// Ok all good
+					Ok(Value::None)
+				}
+				_ => Err(Error::InvalidLevel(self.base.to_string())),
+			}
+		}
+		.await;
+		match future {
+			Err(e) if self.if_exists => match e {
+				Error::PlNsNotFound {
+					..
+				} => Ok(Value::None),
+				Error::PlDbNotFound {
+					..
+				} => Ok(Value::None),
+				e => Err(e),
+			},
+			v => v,
+		}
+	}
+}
+
+impl Display for RemovePolicyStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "REMOVE POLICY")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {} ON {}", self.name, self.base)?;
+		Ok(())
+	}
+}
@@ -0,0 +1,99 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::{Base, Ident, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct RemoveRoleStatement {
+	pub name: Ident,
+	pub base: Base,
+	pub if_exists: bool,
+}
+
+impl RemoveRoleStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value, Error> {
+		let future = async {
+			// Allowed to run?
+			opt.is_allowed(Action::Remove, ResourceKind::Actor, &self.base)?;
+			// Check the statement type
+			match self.base {
+				Base::Root => {
+					// Get the transaction
+					let txn = ctx.tx();
+					// Get the definition
+					let rl = txn.get_root_role(&self.name).await?;
+					// Process the statement
+					let key = crate::key::root::rl::new(&rl.name);
+					txn.del(key).await?;
+					// Clear the cache
+					txn.clear();
+					// Ok all good
+					Ok(Value::None)
+				}
+				Base::Ns => {
+					// Get the transaction
+					let txn = ctx.tx();
+					// Get the definition
+					let rl = txn.get_ns_role(opt.ns()?, &self.name).await?;
+					// Delete the definition
+					let key = crate::key::namespace::rl::new(opt.ns()?, &rl.name);
+					txn.del(key).await?;
+					// Clear the cache
+					txn.clear();
+					// Ok all good
+					Ok(Value::None)
+				}
+				Base::Db => {
+					// Get the transaction
+					let txn = ctx.tx();
+					// Get the definition
+					let rl = txn.get_db_role(opt.ns()?, opt.db()?, &self.name).await?;
+					// Delete the definition
+					let key = crate::key::database::rl::new(opt.ns()?, opt.db()?, &rl.name);
+					txn.del(key).await?;
+					// Clear the cache
+					txn.clear();
+					// Ok all good
+					Ok(Value::None)
+				}
+				_ => Err(Error::InvalidLevel(self.base.to_string())),
+			}
+		}
+		.await;
+		match future {
+			Err(e) if self.if_exists => match e {
+				Error::RoleRootNotFound {
+					..
+				} => Ok(Value::None),
+				Error::RoleNsNotFound {
+					..
+				} => Ok(Value::None),
+				Error::RoleDbNotFound {
+					..
+				} => Ok(Value::None),
+				e => Err(e),
+			},
+			v => v,
+		}
+	}
+}
+
+impl Display for RemoveRoleStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "REMOVE ROLE")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {} ON {}", self.name, self.base)?;
+		Ok(())
+	}
+}
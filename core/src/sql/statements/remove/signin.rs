@@ -0,0 +1,96 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::{Base, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct RemoveSigninPolicyStatement {
+	pub base: Base,
+	pub if_exists: bool,
+}
+
+impl RemoveSigninPolicyStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value, Error> {
+		let future = async {
+			// Allowed to run?
+			opt.is_allowed(Action::Remove, ResourceKind::Actor, &self.base)?;
+			// Check the statement type
+			match self.base {
+				Base::Root => {
+					// Get the transaction
+					let txn = ctx.tx();
+					// Get the definition
+					txn.get_root_signin_policy().await?;
+					// Delete the definition
+					let key = crate::key::root::sp::new();
+					txn.del(key).await?;
+					// Clear the cache
+					txn.clear();
+					// Ok all good
+					Ok(Value::None)
+				}
+				Base::Ns => {
+					// Get the transaction
+					let txn = ctx.tx();
+					// Get the definition
+					txn.get_ns_signin_policy(opt.ns()?).await?;
+					// Delete the definition
+					let key = crate::key::namespace::sp::new(opt.ns()?);
+					txn.del(key).await?;
+					// Clear the cache
+					txn.clear();
+					// Ok all good
+					Ok(Value::None)
+				}
+				Base::Db => {
+					// Get the transaction
+					let txn = ctx.tx();
+					// Get the definition
+					txn.get_db_signin_policy(opt.ns()?, opt.db()?).await?;
+					// Delete the definition
+					let key = crate::key::database::sp::new(opt.ns()?, opt.db()?);
+					txn.del(key).await?;
+					// Clear the cache
+					txn.clear();
+					// Ok all good
+					Ok(Value::None)
+				}
+				_ => Err(Error::InvalidLevel(self.base.to_string())),
+			}
+		}
+		.await;
+		match future {
+			Err(e) if self.if_exists => match e {
+				Error::SpRootNotFound => Ok(Value::None),
+				Error::SpNsNotFound {
+					..
+				} => Ok(Value::None),
+				Error::SpDbNotFound {
+					..
+				} => Ok(Value::None),
+				e => Err(e),
+			},
+			v => v,
+		}
+	}
+}
+
+impl Display for RemoveSigninPolicyStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "REMOVE SIGNIN POLICY")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " ON {}", self.base)?;
+		Ok(())
+	}
+}
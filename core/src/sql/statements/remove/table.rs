@@ -23,7 +23,7 @@ impl RemoveTableStatement {
 	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value, Error> {
 		let future = async {
 			// Allowed to run?
-			opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Db)?;
+			opt.is_allowed(Action::Remove, ResourceKind::Table, &Base::Db)?;
 			// Get the transaction
 			let txn = ctx.tx();
 			// Remove the index stores
@@ -24,7 +24,7 @@ impl RemoveUserStatement {
 	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value, Error> {
 		let future = async {
 			// Allowed to run?
-			opt.is_allowed(Action::Edit, ResourceKind::Actor, &self.base)?;
+			opt.is_allowed(Action::Remove, ResourceKind::Actor, &self.base)?;
 			// Check the statement type
 			match self.base {
 				Base::Root => {
@@ -35,6 +35,9 @@ impl RemoveUserStatement {
 					// Process the statement
 					let key = crate::key::root::us::new(&us.name);
 					txn.del(key).await?;
+					// Delete the second factor state
+					let key = crate::key::root::tf::new(&us.name);
+					txn.del(key).await?;
 					// Clear the cache
 					txn.clear();
 					// Ok all good
@@ -48,6 +51,9 @@ impl RemoveUserStatement {
 					// Delete the definition
 					let key = crate::key::namespace::us::new(opt.ns()?, &us.name);
 					txn.del(key).await?;
+					// Delete the second factor state
+					let key = crate::key::namespace::tf::new(opt.ns()?, &us.name);
+					txn.del(key).await?;
 					// Clear the cache
 					txn.clear();
 					// Ok all good
@@ -61,6 +67,9 @@ impl RemoveUserStatement {
 					// Delete the definition
 					let key = crate::key::database::us::new(opt.ns()?, opt.db()?, &us.name);
 					txn.del(key).await?;
+					// Delete the second factor state
+					let key = crate::key::database::tf::new(opt.ns()?, opt.db()?, &us.name);
+					txn.del(key).await?;
 					// Clear the cache
 					txn.clear();
 					// Ok all good
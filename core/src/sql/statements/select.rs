@@ -1,10 +1,10 @@
 use crate::ctx::{Context, MutableContext};
-use crate::dbs::{Iterable, Iterator, Options, Statement};
+use crate::dbs::{Iterable, Iterator, Options, RowSender, Statement};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::idx::planner::QueryPlanner;
 use crate::sql::{
-	Cond, Explain, Fetchs, Field, Fields, Groups, Id, Idioms, Limit, Orders, Splits, Start,
+	After, Cond, Explain, Fetchs, Field, Fields, Groups, Id, Idioms, Limit, Orders, Splits, Start,
 	Timeout, Value, Values, Version, With,
 };
 use derive::Store;
@@ -14,7 +14,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::sync::Arc;
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -31,6 +31,8 @@ pub struct SelectStatement {
 	pub order: Option<Orders>,
 	pub limit: Option<Limit>,
 	pub start: Option<Start>,
+	#[revision(start = 4)]
+	pub after: Option<After>,
 	pub fetch: Option<Fetchs>,
 	pub version: Option<Version>,
 	pub timeout: Option<Timeout>,
@@ -65,6 +67,28 @@ impl SelectStatement {
 		ctx: &Context,
 		opt: &Options,
 		doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		self.output(stk, ctx, opt, doc, None).await
+	}
+
+	/// Process this type sending the selected records as rows, rather than returning them
+	pub(crate) async fn stream(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		rows: RowSender,
+	) -> Result<Value, Error> {
+		self.output(stk, ctx, opt, None, Some(rows)).await
+	}
+
+	async fn output(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+		rows: Option<RowSender>,
 	) -> Result<Value, Error> {
 		// Valid options?
 		opt.valid_for_db()?;
@@ -72,6 +96,14 @@ impl SelectStatement {
 		let stm = Statement::from(self);
 		// Create a new iterator
 		let mut i = Iterator::new();
+		// Stream the records as they are produced, unless a single record is selected
+		let rows = match rows {
+			Some(rows) if !self.only => {
+				i.stream(rows);
+				None
+			}
+			rows => rows,
+		};
 		// Ensure futures are stored and the version is set if specified
 		let version = self.version.as_ref().map(|v| v.to_u64());
 		let opt =
@@ -156,18 +188,28 @@ impl SelectStatement {
 		}
 		let ctx = ctx.freeze();
 		// Output the results
-		match i.output(stk, &ctx, &opt, &stm).await? {
+		let res = match i.output(stk, &ctx, &opt, &stm).await? {
 			// This is a single record result
 			Value::Array(mut a) if self.only => match a.len() {
 				// There were no results
-				0 => Ok(Value::None),
+				0 => Value::None,
 				// There was exactly one result
-				1 => Ok(a.remove(0)),
+				1 => a.remove(0),
 				// There were no results
-				_ => Err(Error::SingleOnlyOutput),
+				_ => return Err(Error::SingleOnlyOutput),
 			},
 			// This is standard query result
-			v => Ok(v),
+			v => v,
+		};
+		// Send a single record as a row
+		match rows {
+			Some(rows) => {
+				if !res.is_none() {
+					rows.send(res).await;
+				}
+				Ok(Value::None)
+			}
+			None => Ok(res),
 		}
 	}
 }
@@ -204,6 +246,9 @@ impl fmt::Display for SelectStatement {
 		if let Some(ref v) = self.start {
 			write!(f, " {v}")?
 		}
+		if let Some(ref v) = self.after {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.fetch {
 			write!(f, " {v}")?
 		}
@@ -1,7 +1,18 @@
 use crate::sql::Duration;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::str;
+use std::sync::{LazyLock, Mutex, PoisonError};
+
+/// The number of passwords whose strength is kept until their statement is computed
+const PENDING_STRENGTHS: usize = 1_000;
+
+/// The strengths of the passwords provided in plain text to parsed statements, keyed by
+/// the hash of each password. Statements are stored without the strength, so it is handed
+/// from the parser to the computation of the statement through this map instead.
+static PENDING: LazyLock<Mutex<HashMap<String, PasswordStrength>>> =
+	LazyLock::new(Default::default);
 
 #[revisioned(revision = 1)]
 #[derive(Debug, Serialize, Deserialize, Hash, Clone, Eq, PartialEq, PartialOrd)]
@@ -25,3 +36,41 @@ impl Default for UserDuration {
 		}
 	}
 }
+
+#[derive(Debug, Default, Hash, Clone, Copy, Eq, PartialEq, PartialOrd)]
+// The strength of a password provided in plain text, checked against the signin policy of a base
+// Only the length and character classes are kept, the password itself is discarded once hashed
+pub struct PasswordStrength {
+	// The number of characters in the password
+	pub length: u32,
+	// The number of character classes used: lowercase, uppercase, digits and symbols
+	pub classes: u8,
+}
+
+impl PasswordStrength {
+	pub fn of(password: &str) -> Self {
+		let lower = password.chars().any(|c| c.is_lowercase());
+		let upper = password.chars().any(|c| c.is_uppercase());
+		let digit = password.chars().any(|c| c.is_numeric());
+		let other = password.chars().any(|c| !c.is_alphanumeric());
+		Self {
+			length: password.chars().count() as u32,
+			classes: [lower, upper, digit, other].into_iter().filter(|v| *v).count() as u8,
+		}
+	}
+
+	/// Keeps the strength of a password until the statement which hashed it is computed
+	pub(crate) fn keep(hash: &str, strength: Self) {
+		let mut pending = PENDING.lock().unwrap_or_else(PoisonError::into_inner);
+		// Forget the strengths of statements which were parsed but never computed
+		if pending.len() >= PENDING_STRENGTHS {
+			pending.clear();
+		}
+		pending.insert(hash.to_owned(), strength);
+	}
+
+	/// Fetches the strength of the password with the specified hash, if it was provided in plain text
+	pub(crate) fn pending(hash: &str) -> Option<Self> {
+		PENDING.lock().unwrap_or_else(PoisonError::into_inner).get(hash).copied()
+	}
+}
@@ -70,7 +70,11 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("ASCII") => TokenKind::Keyword(Keyword::Ascii),
 	UniCase::ascii("ASSERT") => TokenKind::Keyword(Keyword::Assert),
 	UniCase::ascii("AT") => TokenKind::Keyword(Keyword::At),
+	UniCase::ascii("ATTEMPTS") => TokenKind::Keyword(Keyword::Attempts),
+	UniCase::ascii("AUDIENCE") => TokenKind::Keyword(Keyword::Audience),
+	UniCase::ascii("AUDIT") => TokenKind::Keyword(Keyword::Audit),
 	UniCase::ascii("AUTHENTICATE") => TokenKind::Keyword(Keyword::Authenticate),
+	UniCase::ascii("BACKOFF") => TokenKind::Keyword(Keyword::Backoff),
 	UniCase::ascii("BEARER") => TokenKind::Keyword(Keyword::Bearer),
 	UniCase::ascii("BEFORE") => TokenKind::Keyword(Keyword::Before),
 	UniCase::ascii("BEGIN") => TokenKind::Keyword(Keyword::Begin),
@@ -83,7 +87,9 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("CHANGEFEED") => TokenKind::Keyword(Keyword::ChangeFeed),
 	UniCase::ascii("CHANGES") => TokenKind::Keyword(Keyword::Changes),
 	UniCase::ascii("CAPACITY") => TokenKind::Keyword(Keyword::Capacity),
+	UniCase::ascii("CERTIFICATE") => TokenKind::Keyword(Keyword::Certificate),
 	UniCase::ascii("CLASS") => TokenKind::Keyword(Keyword::Class),
+	UniCase::ascii("CLASSES") => TokenKind::Keyword(Keyword::Classes),
 	UniCase::ascii("COMMENT") => TokenKind::Keyword(Keyword::Comment),
 	UniCase::ascii("COMMIT") => TokenKind::Keyword(Keyword::Commit),
 	UniCase::ascii("CONCURRENTLY") => TokenKind::Keyword(Keyword::Concurrently),
@@ -97,6 +103,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("DELETE") => TokenKind::Keyword(Keyword::Delete),
 	UniCase::ascii("DESCENDING") => TokenKind::Keyword(Keyword::Descending),
 	UniCase::ascii("DESC") => TokenKind::Keyword(Keyword::Descending),
+	UniCase::ascii("DETERMINISTIC") => TokenKind::Keyword(Keyword::Deterministic),
 	UniCase::ascii("DIFF") => TokenKind::Keyword(Keyword::Diff),
 	UniCase::ascii("DIMENSION") => TokenKind::Keyword(Keyword::Dimension),
 	UniCase::ascii("DISTANCE") => TokenKind::Keyword(Keyword::Distance),
@@ -111,6 +118,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("EFC") => TokenKind::Keyword(Keyword::Efc),
 	UniCase::ascii("EVENT") => TokenKind::Keyword(Keyword::Event),
 	UniCase::ascii("ELSE") => TokenKind::Keyword(Keyword::Else),
+	UniCase::ascii("ENCRYPTED") => TokenKind::Keyword(Keyword::Encrypted),
 	UniCase::ascii("END") => TokenKind::Keyword(Keyword::End),
 	UniCase::ascii("ENFORCED") => TokenKind::Keyword(Keyword::Enforced),
 	UniCase::ascii("EXISTS") => TokenKind::Keyword(Keyword::Exists),
@@ -147,14 +155,17 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("KEY") => TokenKind::Keyword(Keyword::Key),
 	UniCase::ascii("KEEP_PRUNED_CONNECTIONS") => TokenKind::Keyword(Keyword::KeepPrunedConnections),
 	UniCase::ascii("KILL") => TokenKind::Keyword(Keyword::Kill),
+	UniCase::ascii("LENGTH") => TokenKind::Keyword(Keyword::Length),
 	UniCase::ascii("LET") => TokenKind::Keyword(Keyword::Let),
 	UniCase::ascii("LIMIT") => TokenKind::Keyword(Keyword::Limit),
 	UniCase::ascii("LIST") => TokenKind::Keyword(Keyword::List),
 	UniCase::ascii("LIVE") => TokenKind::Keyword(Keyword::Live),
+	UniCase::ascii("LOCKOUT") => TokenKind::Keyword(Keyword::Lockout),
 	UniCase::ascii("LOWERCASE") => TokenKind::Keyword(Keyword::Lowercase),
 	UniCase::ascii("LM") => TokenKind::Keyword(Keyword::Lm),
 	UniCase::ascii("M") => TokenKind::Keyword(Keyword::M),
 	UniCase::ascii("M0") => TokenKind::Keyword(Keyword::M0),
+	UniCase::ascii("MASK") => TokenKind::Keyword(Keyword::Mask),
 	UniCase::ascii("ML") => TokenKind::Keyword(Keyword::ML),
 	UniCase::ascii("MERGE") => TokenKind::Keyword(Keyword::Merge),
 	UniCase::ascii("MODEL") => TokenKind::Keyword(Keyword::Model),
@@ -181,22 +192,32 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("PASSWORD") => TokenKind::Keyword(Keyword::Password),
 	UniCase::ascii("PATCH") => TokenKind::Keyword(Keyword::Patch),
 	UniCase::ascii("PERMISSIONS") => TokenKind::Keyword(Keyword::Permissions),
+	UniCase::ascii("POLICY") => TokenKind::Keyword(Keyword::Policy),
 	UniCase::ascii("POSTINGS_CACHE") => TokenKind::Keyword(Keyword::PostingsCache),
 	UniCase::ascii("POSTINGS_ORDER") => TokenKind::Keyword(Keyword::PostingsOrder),
+	UniCase::ascii("PRODUCT") => TokenKind::Keyword(Keyword::Product),
 	UniCase::ascii("PRUNE") => TokenKind::Keyword(Keyword::Prune),
 	UniCase::ascii("PUNCT") => TokenKind::Keyword(Keyword::Punct),
+	UniCase::ascii("QUANTIZE") => TokenKind::Keyword(Keyword::Quantize),
 	UniCase::ascii("RANGE") => TokenKind::Keyword(Keyword::Range),
 	UniCase::ascii("READONLY") => TokenKind::Keyword(Keyword::Readonly),
 	UniCase::ascii("RELATE") => TokenKind::Keyword(Keyword::Relate),
 	UniCase::ascii("RELATION") => TokenKind::Keyword(Keyword::Relation),
 	UniCase::ascii("REBUILD") => TokenKind::Keyword(Keyword::Rebuild),
+	UniCase::ascii("RECOVERY") => TokenKind::Keyword(Keyword::Recovery),
+	UniCase::ascii("RECOVERYHASH") => TokenKind::Keyword(Keyword::Recoveryhash),
+	UniCase::ascii("REFRESH") => TokenKind::Keyword(Keyword::Refresh),
 	UniCase::ascii("REMOVE") => TokenKind::Keyword(Keyword::Remove),
 	UniCase::ascii("REPLACE") => TokenKind::Keyword(Keyword::Replace),
+	UniCase::ascii("RERANK") => TokenKind::Keyword(Keyword::Rerank),
 	UniCase::ascii("RETURN") => TokenKind::Keyword(Keyword::Return),
+	UniCase::ascii("REVEAL") => TokenKind::Keyword(Keyword::Reveal),
 	UniCase::ascii("REVOKE") => TokenKind::Keyword(Keyword::Revoke),
+	UniCase::ascii("ROLE") => TokenKind::Keyword(Keyword::Role),
 	UniCase::ascii("ROLES") => TokenKind::Keyword(Keyword::Roles),
 	UniCase::ascii("ROOT") => TokenKind::Keyword(Keyword::Root),
 	UniCase::ascii("KV") => TokenKind::Keyword(Keyword::Root),
+	UniCase::ascii("SCALAR") => TokenKind::Keyword(Keyword::Scalar),
 	UniCase::ascii("SCHEMAFULL") => TokenKind::Keyword(Keyword::Schemafull),
 	UniCase::ascii("SCHEMAFUL") => TokenKind::Keyword(Keyword::Schemafull),
 	UniCase::ascii("SCHEMALESS") => TokenKind::Keyword(Keyword::Schemaless),
@@ -212,11 +233,13 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("SINCE") => TokenKind::Keyword(Keyword::Since),
 	UniCase::ascii("SLEEP") => TokenKind::Keyword(Keyword::Sleep),
 	UniCase::ascii("SNOWBALL") => TokenKind::Keyword(Keyword::Snowball),
+	UniCase::ascii("SPARSE") => TokenKind::Keyword(Keyword::Sparse),
 	UniCase::ascii("SPLIT") => TokenKind::Keyword(Keyword::Split),
 	UniCase::ascii("START") => TokenKind::Keyword(Keyword::Start),
 	UniCase::ascii("STRUCTURE") => TokenKind::Keyword(Keyword::Structure),
 	UniCase::ascii("TABLE") => TokenKind::Keyword(Keyword::Table),
 	UniCase::ascii("TB") => TokenKind::Keyword(Keyword::Table),
+	UniCase::ascii("TABLES") => TokenKind::Keyword(Keyword::Tables),
 	UniCase::ascii("TEMPFILES") => TokenKind::Keyword(Keyword::TempFiles),
 	UniCase::ascii("TERMS_CACHE") => TokenKind::Keyword(Keyword::TermsCache),
 	UniCase::ascii("TERMS_ORDER") => TokenKind::Keyword(Keyword::TermsOrder),
@@ -226,6 +249,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("TO") => TokenKind::Keyword(Keyword::To),
 	UniCase::ascii("TOKENIZERS") => TokenKind::Keyword(Keyword::Tokenizers),
 	UniCase::ascii("TOKEN") => TokenKind::Keyword(Keyword::Token),
+	UniCase::ascii("TOTP") => TokenKind::Keyword(Keyword::Totp),
 	UniCase::ascii("TRANSACTION") => TokenKind::Keyword(Keyword::Transaction),
 	UniCase::ascii("true") => TokenKind::Keyword(Keyword::True),
 	UniCase::ascii("TYPE") => TokenKind::Keyword(Keyword::Type),
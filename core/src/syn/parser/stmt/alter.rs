@@ -2,7 +2,7 @@ use reblessive::Stk;
 
 use crate::{
 	sql::{
-		statements::{AlterStatement, AlterTableStatement},
+		statements::{AlterIndexStatement, AlterStatement, AlterTableStatement},
 		TableType,
 	},
 	syn::{
@@ -19,6 +19,7 @@ impl Parser<'_> {
 		let next = self.next();
 		match next.kind {
 			t!("TABLE") => self.parse_alter_table(ctx).await.map(AlterStatement::Table),
+			t!("INDEX") => self.parse_alter_index().map(AlterStatement::Index),
 			_ => unexpected!(self, next, "a alter statement keyword"),
 		}
 	}
@@ -100,4 +101,24 @@ impl Parser<'_> {
 
 		Ok(res)
 	}
+
+	pub fn parse_alter_index(&mut self) -> ParseResult<AlterIndexStatement> {
+		let if_exists = if self.eat(t!("IF")) {
+			expected!(self, t!("EXISTS"));
+			true
+		} else {
+			false
+		};
+		let name = self.next_token_value()?;
+		expected!(self, t!("ON"));
+		self.eat(t!("TABLE"));
+		let what = self.next_token_value()?;
+		let cancel = self.eat(t!("CANCEL"));
+		Ok(AlterIndexStatement {
+			name,
+			what,
+			if_exists,
+			cancel,
+		})
+	}
 }
@@ -1,9 +1,11 @@
 use reblessive::Stk;
 
 use crate::cnf::EXPERIMENTAL_BEARER_ACCESS;
+use crate::iam::{Action, Grant};
 use crate::sql::access_type::JwtAccessVerify;
 use crate::sql::index::HnswParams;
 use crate::sql::Value;
+use crate::syn::error::bail;
 use crate::{
 	sql::{
 		access_type,
@@ -13,8 +15,9 @@ use crate::{
 		statements::{
 			DefineAccessStatement, DefineAnalyzerStatement, DefineDatabaseStatement,
 			DefineEventStatement, DefineFieldStatement, DefineFunctionStatement,
-			DefineIndexStatement, DefineNamespaceStatement, DefineParamStatement, DefineStatement,
-			DefineTableStatement, DefineUserStatement,
+			DefineIndexStatement, DefineNamespaceStatement, DefineParamStatement,
+			DefinePolicyStatement, DefineRoleStatement, DefineSigninPolicyStatement,
+			DefineStatement, DefineTableStatement, DefineUserStatement, FieldEncryption,
 		},
 		table_type,
 		tokenizer::Tokenizer,
@@ -40,6 +43,9 @@ impl Parser<'_> {
 			t!("DATABASE") => self.parse_define_database().map(DefineStatement::Database),
 			t!("FUNCTION") => self.parse_define_function(ctx).await.map(DefineStatement::Function),
 			t!("USER") => self.parse_define_user().map(DefineStatement::User),
+			t!("ROLE") => self.parse_define_role().map(DefineStatement::Role),
+			t!("POLICY") => self.parse_define_policy(ctx).await.map(DefineStatement::Policy),
+			t!("SIGNIN") => self.parse_define_signin_policy().map(DefineStatement::SigninPolicy),
 			t!("TOKEN") => self.parse_define_token().map(DefineStatement::Access),
 			t!("SCOPE") => self.parse_define_scope(ctx).await.map(DefineStatement::Access),
 			t!("PARAM") => self.parse_define_param(ctx).await.map(DefineStatement::Param),
@@ -231,6 +237,34 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.set_passhash(self.next_token_value::<Strand>()?.0);
 				}
+				t!("TOTP") => {
+					self.pop_peek();
+					if self.eat(t!("NONE")) {
+						res.set_totp(None);
+					} else {
+						res.set_totp(Some(self.next_token_value::<Strand>()?.0));
+					}
+				}
+				t!("CERTIFICATE") => {
+					self.pop_peek();
+					res.certificate = !self.eat(t!("NONE"));
+				}
+				t!("RECOVERY") => {
+					self.pop_peek();
+					let mut codes = vec![self.next_token_value::<Strand>()?.0];
+					while self.eat(t!(",")) {
+						codes.push(self.next_token_value::<Strand>()?.0);
+					}
+					res.set_recovery(&codes);
+				}
+				t!("RECOVERYHASH") => {
+					self.pop_peek();
+					let mut hashes = vec![self.next_token_value::<Strand>()?.0];
+					while self.eat(t!(",")) {
+						hashes.push(self.next_token_value::<Strand>()?.0);
+					}
+					res.set_recoveryhash(hashes);
+				}
 				t!("ROLES") => {
 					self.pop_peek();
 					res.roles = vec![self.next_token_value()?];
@@ -276,6 +310,222 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	pub fn parse_define_role(&mut self) -> ParseResult<DefineRoleStatement> {
+		let (if_not_exists, overwrite) = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			(true, false)
+		} else if self.eat(t!("OVERWRITE")) {
+			(false, true)
+		} else {
+			(false, false)
+		};
+		let name = self.next_token_value()?;
+		expected!(self, t!("ON"));
+		let base = self.parse_base(false)?;
+
+		let mut res = DefineRoleStatement {
+			name,
+			base,
+			if_not_exists,
+			overwrite,
+			..Default::default()
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = Some(self.next_token_value()?);
+				}
+				t!("GRANT") => {
+					self.pop_peek();
+					res.grants.push(self.parse_role_grant()?);
+				}
+				_ => break,
+			}
+		}
+
+		Ok(res)
+	}
+
+	/// Parses the actions and resource kinds of a role grant
+	///
+	/// # Parser State
+	/// Expects the parser to have already eaten the `GRANT` keyword
+	fn parse_role_grant(&mut self) -> ParseResult<Grant> {
+		let mut actions = Vec::new();
+		loop {
+			let span = self.peek().span;
+			let name = self.next_token_value::<Ident>()?;
+			let Some(action) = Grant::parse_action(&name) else {
+				bail!("Invalid role action `{name}`, expected one of `select`, `create`, `update`, `delete`, `define` or `remove`", @span);
+			};
+			actions.push(action);
+			if !self.eat(t!(",")) {
+				break;
+			}
+		}
+		expected!(self, t!("ON"));
+		let mut resources = Vec::new();
+		loop {
+			let span = self.peek().span;
+			let name = self.next_token_value::<Ident>()?;
+			let Some(resource) = Grant::parse_resource(&name) else {
+				bail!("Invalid role resource `{name}`, expected a resource kind like `table`, `index` or `any`", @span);
+			};
+			resources.push(resource);
+			if !self.eat(t!(",")) {
+				break;
+			}
+		}
+		Ok(Grant::new(actions, resources))
+	}
+
+	pub fn parse_define_signin_policy(&mut self) -> ParseResult<DefineSigninPolicyStatement> {
+		expected!(self, t!("POLICY"));
+		let (if_not_exists, overwrite) = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			(true, false)
+		} else if self.eat(t!("OVERWRITE")) {
+			(false, true)
+		} else {
+			(false, false)
+		};
+		expected!(self, t!("ON"));
+		let base = self.parse_base(false)?;
+
+		let mut res = DefineSigninPolicyStatement {
+			base,
+			if_not_exists,
+			overwrite,
+			..Default::default()
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = Some(self.next_token_value()?);
+				}
+				t!("PASSWORD") => {
+					self.pop_peek();
+					loop {
+						match self.peek_kind() {
+							t!("LENGTH") => {
+								self.pop_peek();
+								res.length = self.next_token_value()?;
+							}
+							t!("CLASSES") => {
+								self.pop_peek();
+								let span = self.peek().span;
+								res.classes = self.next_token_value()?;
+								if res.classes > 4 {
+									bail!("Invalid number of password classes `{}`, expected at most 4 for lowercase letters, uppercase letters, digits and symbols", res.classes, @span);
+								}
+							}
+							_ => break,
+						}
+					}
+				}
+				t!("ATTEMPTS") => {
+					self.pop_peek();
+					res.attempts = self.next_token_value()?;
+				}
+				t!("BACKOFF") => {
+					self.pop_peek();
+					res.backoff = self.next_token_value()?;
+				}
+				t!("LOCKOUT") => {
+					self.pop_peek();
+					res.lockout = self.next_token_value()?;
+				}
+				_ => break,
+			}
+		}
+
+		Ok(res)
+	}
+
+	pub async fn parse_define_policy(
+		&mut self,
+		ctx: &mut Stk,
+	) -> ParseResult<DefinePolicyStatement> {
+		let (if_not_exists, overwrite) = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			(true, false)
+		} else if self.eat(t!("OVERWRITE")) {
+			(false, true)
+		} else {
+			(false, false)
+		};
+		let name = self.next_token_value()?;
+		expected!(self, t!("ON"));
+		let base = self.parse_base(false)?;
+
+		let mut res = DefinePolicyStatement {
+			name,
+			base,
+			if_not_exists,
+			overwrite,
+			..Default::default()
+		};
+		// A policy must always have a condition
+		let mut cond = false;
+
+		loop {
+			match self.peek_kind() {
+				t!("FOR") => {
+					self.pop_peek();
+					loop {
+						let next = self.next();
+						let action = match next.kind {
+							t!("SELECT") => Action::Select,
+							t!("CREATE") => Action::Create,
+							t!("UPDATE") => Action::Update,
+							t!("DELETE") => Action::Delete,
+							_ => {
+								unexpected!(self, next, "'SELECT', 'CREATE', 'UPDATE' or 'DELETE'")
+							}
+						};
+						if !res.actions.contains(&action) {
+							res.actions.push(action);
+						}
+						if !self.eat(t!(",")) {
+							break;
+						}
+					}
+				}
+				t!("WHERE") => {
+					self.pop_peek();
+					res.cond = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
+					cond = true;
+				}
+				t!("TABLES") => {
+					self.pop_peek();
+					res.tables = vec![self.next_token_value()?];
+					while self.eat(t!(",")) {
+						res.tables.push(self.next_token_value()?);
+					}
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = Some(self.next_token_value()?);
+				}
+				_ => break,
+			}
+		}
+
+		if !cond {
+			let next = self.peek();
+			unexpected!(self, next, "a WHERE clause");
+		}
+
+		Ok(res)
+	}
+
 	pub async fn parse_define_access(
 		&mut self,
 		stk: &mut Stk,
@@ -340,9 +590,25 @@ impl Parser<'_> {
 									_ => break,
 								}
 							}
-							if self.eat(t!("WITH")) {
-								expected!(self, t!("JWT"));
-								ac.jwt = self.parse_jwt()?;
+							while self.eat(t!("WITH")) {
+								let peek = self.peek();
+								match peek.kind {
+									t!("JWT") => {
+										self.pop_peek();
+										ac.jwt = self.parse_jwt()?;
+									}
+									t!("REFRESH") => {
+										self.pop_peek();
+										ac.refresh = true;
+									}
+									t!("CERTIFICATE") => {
+										self.pop_peek();
+										ac.certificate = true;
+									}
+									_ => {
+										unexpected!(self, peek, "`JWT`, `REFRESH` or `CERTIFICATE`")
+									}
+								}
 							}
 							res.kind = AccessType::Record(ac);
 						}
@@ -822,6 +1088,10 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.default = Some(ctx.run(|ctx| self.parse_value(ctx)).await?);
 				}
+				t!("ENCRYPTED") => {
+					self.pop_peek();
+					res.encrypted = Some(ctx.run(|ctx| self.parse_field_encryption(ctx)).await?);
+				}
 				t!("PERMISSIONS") => {
 					self.pop_peek();
 					res.permissions = ctx.run(|ctx| self.parse_permission(ctx, true)).await?;
@@ -837,6 +1107,33 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	/// Parses the options of an encrypted field
+	///
+	/// # Parser State
+	///
+	/// Expects the parser to just have eaten the `ENCRYPTED` keyword.
+	pub async fn parse_field_encryption(&mut self, ctx: &mut Stk) -> ParseResult<FieldEncryption> {
+		let mut res = FieldEncryption::default();
+		loop {
+			match self.peek_kind() {
+				t!("DETERMINISTIC") => {
+					self.pop_peek();
+					res.deterministic = true;
+				}
+				t!("MASK") => {
+					self.pop_peek();
+					res.mask = self.next_token_value()?;
+				}
+				t!("REVEAL") => {
+					self.pop_peek();
+					res.reveal = ctx.run(|ctx| self.parse_permission_value(ctx)).await?;
+				}
+				_ => break,
+			}
+		}
+		Ok(res)
+	}
+
 	pub async fn parse_define_index(&mut self, ctx: &mut Stk) -> ParseResult<DefineIndexStatement> {
 		let (if_not_exists, overwrite) = if self.eat(t!("IF")) {
 			expected!(self, t!("NOT"));
@@ -1030,6 +1327,8 @@ impl Parser<'_> {
 					let mut ef_construction = 150;
 					let mut extend_candidates = false;
 					let mut keep_pruned_connections = false;
+					let mut quantization = None;
+					let mut rerank = false;
 					loop {
 						match self.peek_kind() {
 							t!("DISTANCE") => {
@@ -1064,6 +1363,14 @@ impl Parser<'_> {
 								self.pop_peek();
 								keep_pruned_connections = true;
 							}
+							t!("QUANTIZE") => {
+								self.pop_peek();
+								quantization = Some(self.parse_vector_quantization()?);
+							}
+							t!("RERANK") => {
+								self.pop_peek();
+								rerank = true;
+							}
 							_ => {
 								break;
 							}
@@ -1083,8 +1390,47 @@ impl Parser<'_> {
 						ef_construction,
 						extend_candidates,
 						keep_pruned_connections,
+						quantization,
+						rerank,
 					));
 				}
+				t!("SPARSE") => {
+					self.pop_peek();
+					let mut doc_ids_order = 100;
+					let mut doc_ids_cache = 100;
+					let mut postings_order = 100;
+					let mut postings_cache = 100;
+					loop {
+						match self.peek_kind() {
+							t!("DOC_IDS_ORDER") => {
+								self.pop_peek();
+								doc_ids_order = self.next_token_value()?;
+							}
+							t!("DOC_IDS_CACHE") => {
+								self.pop_peek();
+								doc_ids_cache = self.next_token_value()?;
+							}
+							t!("POSTINGS_ORDER") => {
+								self.pop_peek();
+								postings_order = self.next_token_value()?;
+							}
+							t!("POSTINGS_CACHE") => {
+								self.pop_peek();
+								postings_cache = self.next_token_value()?;
+							}
+							_ => break,
+						}
+					}
+					res.index = Index::Sparse(crate::sql::index::SparseParams {
+						doc_ids_order,
+						doc_ids_cache,
+						postings_order,
+						postings_cache,
+					});
+				}
+				t!("WHERE") => {
+					res.cond = self.try_parse_condition(ctx).await?;
+				}
 				t!("CONCURRENTLY") => {
 					self.pop_peek();
 					res.concurrently = true;
@@ -1301,11 +1647,44 @@ impl Parser<'_> {
 					url,
 				});
 			}
-			_ => unexpected!(self, peek, "`ALGORITHM`, or `URL`"),
+			t!("ISSUER") => {
+				self.pop_peek();
+				let mut ver = access_type::JwtAccessVerifyIssuer {
+					url: self.next_token_value::<Strand>()?.0,
+					..Default::default()
+				};
+				loop {
+					match self.peek_kind() {
+						t!("AUDIENCE") => {
+							self.pop_peek();
+							ver.audience = Some(self.next_token_value::<Strand>()?.0);
+						}
+						t!("ROLES") => {
+							self.pop_peek();
+							expected!(self, t!("FROM"));
+							ver.roles = Some(self.next_token_value::<Strand>()?.0);
+						}
+						t!("RECORD") => {
+							self.pop_peek();
+							let tb = self.next_token_value()?;
+							expected!(self, t!("FROM"));
+							ver.record = Some(access_type::JwtAccessRecordClaim {
+								tb,
+								claim: self.next_token_value::<Strand>()?.0,
+							});
+						}
+						_ => break,
+					}
+				}
+				res.verify = access_type::JwtAccessVerify::Issuer(ver);
+			}
+			_ => unexpected!(self, peek, "`ALGORITHM`, `URL`, or `ISSUER`"),
 		}
 
-		if self.eat(t!("WITH")) {
-			expected!(self, t!("ISSUER"));
+		// The issuer is optional, and other clauses can follow with `WITH`
+		if self.peek_kind() == t!("WITH") && self.peek_token_at(1).kind == t!("ISSUER") {
+			self.pop_peek();
+			self.pop_peek();
 			loop {
 				let peek = self.peek();
 				match peek.kind {
@@ -78,20 +78,30 @@ impl Parser<'_> {
 		matches!(
 			kind,
 			t!("ACCESS")
-				| t!("ALTER") | t!("ANALYZE")
-				| t!("BEGIN") | t!("BREAK")
-				| t!("CANCEL") | t!("COMMIT")
-				| t!("CONTINUE") | t!("CREATE")
-				| t!("DEFINE") | t!("DELETE")
+				| t!("ALTER")
+				| t!("ANALYZE")
+				| t!("BEGIN")
+				| t!("BREAK")
+				| t!("CANCEL")
+				| t!("COMMIT")
+				| t!("CONTINUE")
+				| t!("CREATE")
+				| t!("DEFINE")
+				| t!("DELETE")
 				| t!("FOR") | t!("IF")
 				| t!("INFO") | t!("INSERT")
 				| t!("KILL") | t!("LIVE")
-				| t!("OPTION") | t!("REBUILD")
-				| t!("RETURN") | t!("RELATE")
-				| t!("REMOVE") | t!("SELECT")
+				| t!("OPTION")
+				| t!("REBUILD")
+				| t!("RETURN")
+				| t!("RELATE")
+				| t!("REMOVE")
+				| t!("SELECT")
 				| t!("LET") | t!("SHOW")
-				| t!("SLEEP") | t!("THROW")
-				| t!("UPDATE") | t!("UPSERT")
+				| t!("SLEEP")
+				| t!("THROW")
+				| t!("UPDATE")
+				| t!("UPSERT")
 				| t!("USE")
 		)
 	}
@@ -533,6 +543,11 @@ impl Parser<'_> {
 				let table = self.next_token_value()?;
 				InfoStatement::Index(index, table, false)
 			}
+			t!("AUDIT") => {
+				let since = self.eat(t!("SINCE")).then(|| self.next_token_value()).transpose()?;
+				let limit = self.eat(t!("LIMIT")).then(|| self.next_token_value()).transpose()?;
+				InfoStatement::Audit(since, limit, false)
+			}
 			_ => unexpected!(self, next, "an info target"),
 		};
 
@@ -7,7 +7,7 @@ use crate::syn::error::bail;
 use crate::{
 	sql::{
 		changefeed::ChangeFeed,
-		index::{Distance, VectorType},
+		index::{Distance, VectorQuantization, VectorType},
 		Base, Cond, Data, Duration, Fetchs, Field, Fields, Group, Groups, Ident, Idiom, Output,
 		Permission, Permissions, Tables, Timeout, Value, View,
 	},
@@ -364,7 +364,8 @@ impl Parser<'_> {
 			t!("NONE") => Ok(Permission::None),
 			t!("FULL") => Ok(Permission::Full),
 			t!("WHERE") => Ok(Permission::Specific(self.parse_value_field(stk).await?)),
-			_ => unexpected!(self, next, "'NONE', 'FULL', or 'WHERE'"),
+			t!("POLICY") => Ok(Permission::Policy(self.next_token_value()?)),
+			_ => unexpected!(self, next, "'NONE', 'FULL', 'WHERE', or 'POLICY'"),
 		}
 	}
 
@@ -484,6 +485,22 @@ impl Parser<'_> {
 		}
 	}
 
+	pub fn parse_vector_quantization(&mut self) -> ParseResult<VectorQuantization> {
+		let next = self.next();
+		match next.kind {
+			t!("SCALAR") => Ok(VectorQuantization::Scalar),
+			t!("PRODUCT") => {
+				let subspaces: u16 = self.next_token_value()?;
+				if subspaces == 0 {
+					let span = self.last_span();
+					bail!("The number of subspaces should be greater than 0", @span);
+				}
+				Ok(VectorQuantization::Product(subspaces))
+			}
+			_ => unexpected!(self, next, "a vector quantization"),
+		}
+	}
+
 	pub fn parse_custom_function_name(&mut self) -> ParseResult<Ident> {
 		expected!(self, t!("fn"));
 		expected!(self, t!("::"));
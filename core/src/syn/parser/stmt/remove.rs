@@ -5,8 +5,9 @@ use crate::{
 		statements::{
 			remove::RemoveAnalyzerStatement, RemoveAccessStatement, RemoveDatabaseStatement,
 			RemoveEventStatement, RemoveFieldStatement, RemoveFunctionStatement,
-			RemoveIndexStatement, RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement,
-			RemoveUserStatement,
+			RemoveIndexStatement, RemoveNamespaceStatement, RemoveParamStatement,
+			RemovePolicyStatement, RemoveRoleStatement, RemoveSigninPolicyStatement,
+			RemoveStatement, RemoveUserStatement,
 		},
 		Param,
 	},
@@ -199,6 +200,56 @@ impl Parser<'_> {
 					if_exists,
 				})
 			}
+			t!("ROLE") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = self.next_token_value()?;
+				expected!(self, t!("ON"));
+				let base = self.parse_base(false)?;
+
+				RemoveStatement::Role(RemoveRoleStatement {
+					name,
+					base,
+					if_exists,
+				})
+			}
+			t!("POLICY") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = self.next_token_value()?;
+				expected!(self, t!("ON"));
+				let base = self.parse_base(false)?;
+
+				RemoveStatement::Policy(RemovePolicyStatement {
+					name,
+					base,
+					if_exists,
+				})
+			}
+			t!("SIGNIN") => {
+				expected!(self, t!("POLICY"));
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				expected!(self, t!("ON"));
+				let base = self.parse_base(false)?;
+
+				RemoveStatement::SigninPolicy(RemoveSigninPolicyStatement {
+					base,
+					if_exists,
+				})
+			}
 			_ => unexpected!(self, next, "a remove statement keyword"),
 		};
 		Ok(res)
@@ -2,8 +2,8 @@ use reblessive::Stk;
 
 use crate::{
 	sql::{
-		statements::SelectStatement, Explain, Field, Fields, Ident, Idioms, Limit, Order, Orders,
-		Split, Splits, Start, Values, Version, With,
+		statements::SelectStatement, After, Explain, Field, Fields, Ident, Idioms, Limit, Order,
+		Orders, Split, Splits, Start, Values, Version, With,
 	},
 	syn::{
 		parser::{
@@ -55,6 +55,7 @@ impl Parser<'_> {
 			let start = self.try_parse_start(stk).await?;
 			(limit, start)
 		};
+		let after = self.try_parse_after(stk).await?;
 		let fetch = self.try_parse_fetch(stk).await?;
 		let version = self.try_parse_version()?;
 		let timeout = self.try_parse_timeout()?;
@@ -74,6 +75,7 @@ impl Parser<'_> {
 			order,
 			limit,
 			start,
+			after,
 			fetch,
 			version,
 			timeout,
@@ -230,6 +232,14 @@ impl Parser<'_> {
 		Ok(Some(Start(value)))
 	}
 
+	async fn try_parse_after(&mut self, ctx: &mut Stk) -> ParseResult<Option<After>> {
+		if !self.eat(t!("AFTER")) {
+			return Ok(None);
+		}
+		let value = ctx.run(|ctx| self.parse_value(ctx)).await?;
+		Ok(Some(After(value)))
+	}
+
 	pub(crate) fn try_parse_version(&mut self) -> ParseResult<Option<Version>> {
 		if !self.eat(t!("VERSION")) {
 			return Ok(None);
@@ -1,14 +1,18 @@
 use crate::{
+	iam::{Action, Grant, ResourceKind},
 	sql::{
 		access::AccessDuration,
 		access_type::{
-			AccessType, JwtAccess, JwtAccessIssue, JwtAccessVerify, JwtAccessVerifyJwks,
-			JwtAccessVerifyKey, RecordAccess,
+			AccessType, JwtAccess, JwtAccessIssue, JwtAccessRecordClaim, JwtAccessVerify,
+			JwtAccessVerifyIssuer, JwtAccessVerifyJwks, JwtAccessVerifyKey, RecordAccess,
 		},
 		block::Entry,
 		changefeed::ChangeFeed,
 		filter::Filter,
-		index::{Distance, HnswParams, MTreeParams, SearchParams, VectorType},
+		index::{
+			Distance, HnswParams, MTreeParams, SearchParams, SparseParams, VectorQuantization,
+			VectorType,
+		},
 		language::Language,
 		statements::{
 			access,
@@ -16,22 +20,25 @@ use crate::{
 			analyze::AnalyzeStatement,
 			show::{ShowSince, ShowStatement},
 			sleep::SleepStatement,
-			AccessStatement, BeginStatement, BreakStatement, CancelStatement, CommitStatement,
-			ContinueStatement, CreateStatement, DefineAccessStatement, DefineAnalyzerStatement,
-			DefineDatabaseStatement, DefineEventStatement, DefineFieldStatement,
-			DefineFunctionStatement, DefineIndexStatement, DefineNamespaceStatement,
-			DefineParamStatement, DefineStatement, DefineTableStatement, DeleteStatement,
-			ForeachStatement, IfelseStatement, InfoStatement, InsertStatement, KillStatement,
-			OptionStatement, OutputStatement, RelateStatement, RemoveAccessStatement,
-			RemoveAnalyzerStatement, RemoveDatabaseStatement, RemoveEventStatement,
-			RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement,
-			RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement, RemoveTableStatement,
+			AccessStatement, AlterIndexStatement, AlterStatement, BeginStatement, BreakStatement,
+			CancelStatement, CommitStatement, ContinueStatement, CreateStatement,
+			DefineAccessStatement, DefineAnalyzerStatement, DefineDatabaseStatement,
+			DefineEventStatement, DefineFieldStatement, DefineFunctionStatement,
+			DefineIndexStatement, DefineNamespaceStatement, DefineParamStatement,
+			DefineRoleStatement, DefineSigninPolicyStatement, DefineStatement,
+			DefineTableStatement, DeleteStatement, FieldEncryption, ForeachStatement,
+			IfelseStatement, InfoStatement, InsertStatement, KillStatement, OptionStatement,
+			OutputStatement, RelateStatement, RemoveAccessStatement, RemoveAnalyzerStatement,
+			RemoveDatabaseStatement, RemoveEventStatement, RemoveFieldStatement,
+			RemoveFunctionStatement, RemoveIndexStatement, RemoveNamespaceStatement,
+			RemoveParamStatement, RemovePolicyStatement, RemoveRoleStatement,
+			RemoveSigninPolicyStatement, RemoveStatement, RemoveTableStatement,
 			RemoveUserStatement, SelectStatement, SetStatement, ThrowStatement, UpdateStatement,
 			UpsertStatement, UseStatement,
 		},
 		tokenizer::Tokenizer,
-		user::UserDuration,
-		Algorithm, Array, Base, Block, Cond, Data, Datetime, Dir, Duration, Edges, Explain,
+		user::{PasswordStrength, UserDuration},
+		After, Algorithm, Array, Base, Block, Cond, Data, Datetime, Dir, Duration, Edges, Explain,
 		Expression, Fetch, Fetchs, Field, Fields, Future, Graph, Group, Groups, Id, Ident, Idiom,
 		Idioms, Index, Kind, Limit, Number, Object, Operator, Order, Orders, Output, Param, Part,
 		Permission, Permissions, Scoring, Split, Splits, Start, Statement, Strand, Subquery, Table,
@@ -353,6 +360,183 @@ fn parse_define_user() {
 			res
 		);
 	}
+	// Second factor.
+	{
+		let res = test_parse!(
+			parse_stmt,
+			r#"DEFINE USER user ON ROOT PASSHASH 'hunter2' TOTP 'JBSWY3DPEHPK3PXP' RECOVERY 'first', 'second'"#
+		)
+		.unwrap();
+
+		let Statement::Define(DefineStatement::User(stmt)) = res else {
+			panic!()
+		};
+
+		assert_eq!(stmt.totp, Some("JBSWY3DPEHPK3PXP".to_owned()));
+		assert_eq!(stmt.recovery.len(), 2);
+		assert!(stmt.recovery.iter().all(|h| h.starts_with("$argon2id$")));
+	}
+	// Second factor with recovery hashes.
+	{
+		let res = test_parse!(
+			parse_stmt,
+			r#"DEFINE USER user ON ROOT PASSHASH 'hunter2' TOTP NONE RECOVERYHASH 'first', 'second'"#
+		)
+		.unwrap();
+
+		let Statement::Define(DefineStatement::User(stmt)) = res else {
+			panic!()
+		};
+
+		assert_eq!(stmt.totp, None);
+		assert_eq!(stmt.recovery, vec!["first".to_owned(), "second".to_owned()]);
+	}
+	// Client certificates.
+	{
+		let res =
+			test_parse!(parse_stmt, r#"DEFINE USER user ON ROOT PASSHASH 'hunter2' CERTIFICATE"#)
+				.unwrap();
+
+		let Statement::Define(DefineStatement::User(stmt)) = res else {
+			panic!()
+		};
+
+		assert!(stmt.certificate);
+	}
+	// Password strength.
+	{
+		let res =
+			test_parse!(parse_stmt, r#"DEFINE USER user ON ROOT PASSWORD 'Hunter2!'"#).unwrap();
+
+		let Statement::Define(DefineStatement::User(stmt)) = res else {
+			panic!()
+		};
+
+		let strength = PasswordStrength::pending(&stmt.hash);
+		assert_eq!(strength.map(|s| (s.length, s.classes)), Some((8, 4)));
+
+		let res =
+			test_parse!(parse_stmt, r#"DEFINE USER user ON ROOT PASSHASH 'hunter2'"#).unwrap();
+
+		let Statement::Define(DefineStatement::User(stmt)) = res else {
+			panic!()
+		};
+
+		assert_eq!(PasswordStrength::pending(&stmt.hash), None);
+	}
+}
+
+#[test]
+fn parse_define_role() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE ROLE IF NOT EXISTS indexer ON DATABASE GRANT define, remove ON index GRANT SELECT ON table, record COMMENT "test""#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Role(DefineRoleStatement {
+			name: Ident("indexer".to_string()),
+			base: Base::Db,
+			grants: vec![
+				Grant::new(vec![Action::Define, Action::Remove], vec![ResourceKind::Index]),
+				Grant::new(vec![Action::Select], vec![ResourceKind::Table, ResourceKind::Record]),
+			],
+			comment: Some(Strand("test".to_string())),
+			if_not_exists: true,
+			overwrite: false,
+		}))
+	);
+	assert_eq!(
+		res.to_string(),
+		"DEFINE ROLE IF NOT EXISTS indexer ON DATABASE GRANT define, remove ON index GRANT select ON table, record COMMENT 'test'"
+	);
+	// Invalid actions and resource kinds are rejected
+	test_parse!(parse_stmt, r#"DEFINE ROLE r ON ROOT GRANT view ON table"#).unwrap_err();
+	test_parse!(parse_stmt, r#"DEFINE ROLE r ON ROOT GRANT select ON thing"#).unwrap_err();
+}
+
+#[test]
+fn parse_define_policy() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE POLICY OVERWRITE tenant ON DATABASE FOR select, update WHERE tenant = $auth.tenant TABLES /^order_/, /^invoice$/ COMMENT "test""#
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::Policy(ref stmt)) = res else {
+		panic!("expected a DEFINE POLICY statement, found {res:?}");
+	};
+	assert_eq!(stmt.name, Ident("tenant".to_string()));
+	assert_eq!(stmt.base, Base::Db);
+	assert_eq!(stmt.actions, vec![Action::Select, Action::Update]);
+	assert_eq!(stmt.cond.to_string(), "tenant = $auth.tenant");
+	assert_eq!(stmt.tables.len(), 2);
+	assert_eq!(stmt.comment, Some(Strand("test".to_string())));
+	assert!(!stmt.if_not_exists);
+	assert!(stmt.overwrite);
+	assert_eq!(
+		res.to_string(),
+		"DEFINE POLICY OVERWRITE tenant ON DATABASE FOR select, update WHERE tenant = $auth.tenant TABLES /^order_/, /^invoice$/ COMMENT 'test'"
+	);
+	// A policy requires a condition
+	test_parse!(parse_stmt, r#"DEFINE POLICY tenant ON DATABASE TABLES /^order_/"#).unwrap_err();
+	// Permissions can reference a policy by name
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE TABLE orders PERMISSIONS FOR select, create POLICY tenant, FOR update, delete NONE"#
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::Table(ref stmt)) = res else {
+		panic!("expected a DEFINE TABLE statement, found {res:?}");
+	};
+	assert_eq!(
+		stmt.permissions,
+		Permissions {
+			select: Permission::Policy(Ident("tenant".to_string())),
+			create: Permission::Policy(Ident("tenant".to_string())),
+			update: Permission::None,
+			delete: Permission::None,
+		}
+	);
+	assert_eq!(
+		stmt.permissions.to_string(),
+		"PERMISSIONS FOR select, create POLICY tenant, FOR update, delete NONE"
+	);
+}
+
+#[test]
+fn parse_define_signin_policy() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE SIGNIN POLICY IF NOT EXISTS ON DATABASE PASSWORD LENGTH 12 CLASSES 3 ATTEMPTS 10 BACKOFF 500ms LOCKOUT 1h COMMENT "test""#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::SigninPolicy(DefineSigninPolicyStatement {
+			base: Base::Db,
+			length: 12,
+			classes: 3,
+			attempts: 10,
+			backoff: Duration(std::time::Duration::from_millis(500)),
+			lockout: Duration(std::time::Duration::from_secs(3600)),
+			comment: Some(Strand("test".to_string())),
+			if_not_exists: true,
+			overwrite: false,
+		}))
+	);
+	assert_eq!(
+		res.to_string(),
+		"DEFINE SIGNIN POLICY IF NOT EXISTS ON DATABASE PASSWORD LENGTH 12 CLASSES 3 ATTEMPTS 10 BACKOFF 500ms LOCKOUT 1h COMMENT 'test'"
+	);
+	// Unspecified settings use the defaults
+	let res = test_parse!(parse_stmt, r#"DEFINE SIGNIN POLICY ON ROOT"#).unwrap();
+	assert_eq!(
+		res.to_string(),
+		"DEFINE SIGNIN POLICY ON ROOT PASSWORD LENGTH 0 CLASSES 0 ATTEMPTS 5 BACKOFF 1s LOCKOUT 15m"
+	);
+	// There are only four character classes
+	test_parse!(parse_stmt, r#"DEFINE SIGNIN POLICY ON ROOT PASSWORD CLASSES 5"#).unwrap_err();
 }
 
 // TODO(gguillemas): This test is kept in 2.0.0 for backward compatibility. Drop in 3.0.0.
@@ -1049,6 +1233,87 @@ fn parse_define_access_jwt_jwks() {
 	}
 }
 
+#[test]
+fn parse_define_access_jwt_issuer() {
+	// Verify only.
+	{
+		let res = test_parse!(
+			parse_stmt,
+			r#"DEFINE ACCESS a ON DATABASE TYPE JWT ISSUER "https://auth.example.com""#
+		)
+		.unwrap();
+		assert_eq!(
+			res,
+			Statement::Define(DefineStatement::Access(DefineAccessStatement {
+				name: Ident("a".to_string()),
+				base: Base::Db,
+				kind: AccessType::Jwt(JwtAccess {
+					verify: JwtAccessVerify::Issuer(JwtAccessVerifyIssuer {
+						url: "https://auth.example.com".to_string(),
+						audience: None,
+						roles: None,
+						record: None,
+					}),
+					issue: None,
+				}),
+				authenticate: None,
+				// Default durations.
+				duration: AccessDuration {
+					grant: None,
+					token: Some(Duration::from_hours(1)),
+					session: None,
+				},
+				comment: None,
+				if_not_exists: false,
+				overwrite: false,
+			})),
+		)
+	}
+	// With audience and claim mapping.
+	{
+		let res = test_parse!(
+			parse_stmt,
+			r#"DEFINE ACCESS a ON DATABASE TYPE RECORD WITH JWT ISSUER "https://auth.example.com" AUDIENCE "surrealdb" ROLES FROM "realm_access.roles" RECORD user FROM "sub""#
+		)
+		.unwrap();
+		assert_eq!(
+			res,
+			Statement::Define(DefineStatement::Access(DefineAccessStatement {
+				name: Ident("a".to_string()),
+				base: Base::Db,
+				kind: AccessType::Record(RecordAccess {
+					signup: None,
+					signin: None,
+					jwt: JwtAccess {
+						verify: JwtAccessVerify::Issuer(JwtAccessVerifyIssuer {
+							url: "https://auth.example.com".to_string(),
+							audience: Some("surrealdb".to_string()),
+							roles: Some("realm_access.roles".to_string()),
+							record: Some(JwtAccessRecordClaim {
+								tb: Ident("user".to_string()),
+								claim: "sub".to_string(),
+							}),
+						}),
+						issue: None,
+					},
+					refresh: false,
+					certificate: false,
+				}),
+				authenticate: None,
+				// Default durations.
+				duration: AccessDuration {
+					grant: None,
+					token: Some(Duration::from_hours(1)),
+					session: None,
+				},
+				comment: None,
+				if_not_exists: false,
+				overwrite: false,
+			})),
+		)
+	}
+}
+
 #[test]
 fn parse_define_access_record() {
 	// With comment. Nothing is explicitly defined.
@@ -1169,6 +1434,8 @@ fn parse_define_access_record() {
 							key: "foo".to_string(),
 						}),
 					},
+					refresh: false,
+					certificate: false,
 				}),
 				authenticate: None,
 				duration: AccessDuration {
@@ -1207,6 +1474,8 @@ fn parse_define_access_record() {
 							key: "bar".to_string(),
 						}),
 					},
+					refresh: false,
+					certificate: false,
 				}),
 				authenticate: None,
 				duration: AccessDuration {
@@ -1220,6 +1489,46 @@ fn parse_define_access_record() {
 			})),
 		);
 	}
+	// Verification and issuing with JWT are explicitly defined with two different keys. Refresh tokens and client certificates are enabled.
+	{
+		let res = test_parse!(
+			parse_stmt,
+			r#"DEFINE ACCESS a ON DB TYPE RECORD WITH JWT ALGORITHM PS512 KEY "foo" WITH ISSUER KEY "bar" WITH REFRESH WITH CERTIFICATE DURATION FOR GRANT 15d, FOR TOKEN 10s, FOR SESSION 15m"#
+		)
+		.unwrap();
+		assert_eq!(
+			res,
+			Statement::Define(DefineStatement::Access(DefineAccessStatement {
+				name: Ident("a".to_string()),
+				base: Base::Db,
+				kind: AccessType::Record(RecordAccess {
+					signup: None,
+					signin: None,
+					jwt: JwtAccess {
+						verify: JwtAccessVerify::Key(JwtAccessVerifyKey {
+							alg: Algorithm::Ps512,
+							key: "foo".to_string(),
+						}),
+						issue: Some(JwtAccessIssue {
+							alg: Algorithm::Ps512,
+							key: "bar".to_string(),
+						}),
+					},
+					refresh: true,
+					certificate: true,
+				}),
+				authenticate: None,
+				duration: AccessDuration {
+					grant: Some(Duration::from_days(15)),
+					token: Some(Duration::from_secs(10)),
+					session: Some(Duration::from_mins(15)),
+				},
+				comment: None,
+				if_not_exists: false,
+				overwrite: false,
+			})),
+		);
+	}
 	// Verification and issuing with JWT are explicitly defined with two different keys. Token duration is explicitly defined.
 	{
 		let res = test_parse!(
@@ -1245,6 +1554,8 @@ fn parse_define_access_record() {
 							key: "bar".to_string(),
 						}),
 					},
+					refresh: false,
+					certificate: false,
 				}),
 				authenticate: None,
 				duration: AccessDuration {
@@ -1314,6 +1625,8 @@ fn parse_define_access_record_with_jwt() {
 					}),
 					issue: None,
 				},
+				refresh: false,
+				certificate: false,
 			}),
 			authenticate: None,
 			// Default durations.
@@ -1459,8 +1772,38 @@ fn parse_define_field() {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
+			encrypted: None,
 		}))
-	)
+	);
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE FIELD ssn ON TABLE person TYPE string ENCRYPTED DETERMINISTIC MASK 4 REVEAL WHERE $auth.admin = true"#
+	).unwrap();
+
+	let Statement::Define(DefineStatement::Field(res)) = res else {
+		panic!()
+	};
+	assert_eq!(
+		res.encrypted,
+		Some(FieldEncryption {
+			deterministic: true,
+			mask: 4,
+			reveal: Permission::Specific(Value::Expression(Box::new(Expression::Binary {
+				l: Value::Idiom(Idiom(vec![
+					Part::Start(Value::Param(Param(Ident("auth".to_owned())))),
+					Part::Field(Ident("admin".to_owned())),
+				])),
+				o: Operator::Equal,
+				r: Value::Bool(true),
+			}))),
+			secret: vec![],
+		})
+	);
+	assert_eq!(
+		res.to_string(),
+		"DEFINE FIELD ssn ON person TYPE string ENCRYPTED DETERMINISTIC MASK 4 REVEAL WHERE $auth.admin = true PERMISSIONS FULL"
+	);
 }
 
 #[test]
@@ -1508,7 +1851,8 @@ fn parse_define_index() {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
-			concurrently: false
+			concurrently: false,
+			cond: None,
 		}))
 	);
 
@@ -1525,7 +1869,8 @@ fn parse_define_index() {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
-			concurrently: false
+			concurrently: false,
+			cond: None,
 		}))
 	);
 
@@ -1550,7 +1895,8 @@ fn parse_define_index() {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
-			concurrently: false
+			concurrently: false,
+			cond: None,
 		}))
 	);
 
@@ -1573,13 +1919,114 @@ fn parse_define_index() {
 				extend_candidates: true,
 				keep_pruned_connections: true,
 				ml: 0.5.into(),
+				quantization: None,
+				rerank: false,
 			}),
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
-			concurrently: false
+			concurrently: false,
+			cond: None,
 		}))
 	);
+
+	let res =
+		test_parse!(parse_stmt, r#"DEFINE INDEX index ON TABLE table FIELDS a HNSW DIMENSION 128 TYPE F32 M 6 QUANTIZE PRODUCT 16 RERANK"#).unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Index(DefineIndexStatement {
+			name: Ident("index".to_owned()),
+			what: Ident("table".to_owned()),
+			cols: Idioms(vec![Idiom(vec![Part::Field(Ident("a".to_owned()))]),]),
+			index: Index::Hnsw(HnswParams {
+				dimension: 128,
+				distance: Distance::Euclidean,
+				vector_type: VectorType::F32,
+				m: 6,
+				m0: 12,
+				ef_construction: 150,
+				extend_candidates: false,
+				keep_pruned_connections: false,
+				ml: (1.0 / 6f64.ln()).into(),
+				quantization: Some(VectorQuantization::Product(16)),
+				rerank: true,
+			}),
+			comment: None,
+			if_not_exists: false,
+			overwrite: false,
+			concurrently: false,
+			cond: None,
+		}))
+	);
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE INDEX index ON TABLE table FIELDS a HNSW DIMENSION 4 QUANTIZE SCALAR"#
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::Index(DefineIndexStatement {
+		index: Index::Hnsw(p),
+		..
+	})) = res
+	else {
+		panic!()
+	};
+	assert_eq!(p.quantization, Some(VectorQuantization::Scalar));
+	assert!(!p.rerank);
+}
+
+#[test]
+fn parse_define_index_sparse() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE INDEX index ON TABLE table FIELDS a SPARSE DOC_IDS_ORDER 50 POSTINGS_CACHE 200"#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Index(DefineIndexStatement {
+			name: Ident("index".to_owned()),
+			what: Ident("table".to_owned()),
+			cols: Idioms(vec![Idiom(vec![Part::Field(Ident("a".to_owned()))])]),
+			index: Index::Sparse(SparseParams {
+				doc_ids_order: 50,
+				doc_ids_cache: 100,
+				postings_order: 100,
+				postings_cache: 200,
+			}),
+			comment: None,
+			if_not_exists: false,
+			overwrite: false,
+			concurrently: false,
+			cond: None,
+		}))
+	);
+}
+
+#[test]
+fn parse_define_index_partial() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE INDEX index ON TABLE table FIELDS a WHERE status = 'active' CONCURRENTLY"#
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::Index(stm)) = res else {
+		panic!()
+	};
+	assert_eq!(
+		stm.cond,
+		Some(Cond(Value::Expression(Box::new(Expression::Binary {
+			l: Value::Idiom(Idiom(vec![Part::Field(Ident("status".to_owned()))])),
+			o: Operator::Equal,
+			r: Value::Strand(Strand("active".to_owned())),
+		}))))
+	);
+	assert!(stm.concurrently);
+	assert_eq!(
+		stm.to_string(),
+		"DEFINE INDEX index ON table FIELDS a WHERE status = 'active' CONCURRENTLY"
+	);
 }
 
 #[test]
@@ -1776,6 +2223,23 @@ fn parse_info() {
 		res,
 		Statement::Info(InfoStatement::User(Ident("user".to_owned()), Some(Base::Ns), false))
 	);
+
+	let res = test_parse!(parse_stmt, "INFO FOR AUDIT").unwrap();
+	assert_eq!(res, Statement::Info(InfoStatement::Audit(None, None, false)));
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"INFO FOR AUDIT SINCE d"2024-01-01T00:00:00Z" LIMIT 10 STRUCTURE"#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Info(InfoStatement::Audit(
+			Some(Datetime::try_from("2024-01-01T00:00:00Z").unwrap()),
+			Some(10),
+			true
+		))
+	);
 }
 
 #[test]
@@ -1791,6 +2255,7 @@ SELECT bar as foo,[1,2],bar OMIT bar FROM ONLY a,1
     ORDER BY foo COLLATE NUMERIC ASC
     START AT { a: true }
     LIMIT BY a:b
+    AFTER a:c
     FETCH foo
     VERSION d"2012-04-23T18:25:43.0000511Z"
     EXPLAIN FULL
@@ -1860,6 +2325,10 @@ SELECT bar as foo,[1,2],bar OMIT bar FROM ONLY a,1
 			start: Some(Start(Value::Object(Object(
 				[("a".to_owned(), Value::Bool(true))].into_iter().collect()
 			)))),
+			after: Some(After(Value::Thing(Thing {
+				tb: "a".to_owned(),
+				id: Id::from("c"),
+			}))),
 			fetch: Some(Fetchs(vec![Fetch(Value::Idiom(Idiom(vec![Part::Field(Ident(
 				"foo".to_owned()
 			))])))])),
@@ -2203,6 +2672,21 @@ fn parse_relate() {
 	)
 }
 
+#[test]
+fn parse_alter_index() {
+	let res = test_parse!(parse_stmt, r#"ALTER INDEX IF EXISTS idx ON TABLE tbl CANCEL"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Alter(AlterStatement::Index(AlterIndexStatement {
+			name: Ident("idx".to_owned()),
+			what: Ident("tbl".to_owned()),
+			if_exists: true,
+			cancel: true,
+		}))
+	);
+	assert_eq!(res.to_string(), "ALTER INDEX IF EXISTS idx ON tbl CANCEL");
+}
+
 #[test]
 fn parse_remove() {
 	let res = test_parse!(parse_stmt, r#"REMOVE NAMESPACE ns"#).unwrap();
@@ -2320,6 +2804,35 @@ fn parse_remove() {
 			if_exists: false,
 		}))
 	);
+
+	let res = test_parse!(parse_stmt, r#"REMOVE ROLE IF EXISTS foo ON NAMESPACE"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Role(RemoveRoleStatement {
+			name: Ident("foo".to_owned()),
+			base: Base::Ns,
+			if_exists: true,
+		}))
+	);
+
+	let res = test_parse!(parse_stmt, r#"REMOVE POLICY tenant ON DATABASE"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Policy(RemovePolicyStatement {
+			name: Ident("tenant".to_owned()),
+			base: Base::Db,
+			if_exists: false,
+		}))
+	);
+
+	let res = test_parse!(parse_stmt, r#"REMOVE SIGNIN POLICY IF EXISTS ON ROOT"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::SigninPolicy(RemoveSigninPolicyStatement {
+			base: Base::Root,
+			if_exists: true,
+		}))
+	);
 }
 
 #[test]
@@ -36,7 +36,11 @@ keyword! {
 	Ascii => "ASCII",
 	Assert => "ASSERT",
 	At => "AT",
+	Attempts => "ATTEMPTS",
+	Audience => "AUDIENCE",
+	Audit => "AUDIT",
 	Authenticate => "AUTHENTICATE",
+	Backoff => "BACKOFF",
 	Bearer => "BEARER",
 	Before => "BEFORE",
 	Begin => "BEGIN",
@@ -49,7 +53,9 @@ keyword! {
 	ChangeFeed => "CHANGEFEED",
 	Changes => "CHANGES",
 	Capacity => "CAPACITY",
+	Certificate => "CERTIFICATE",
 	Class => "CLASS",
+	Classes => "CLASSES",
 	Comment => "COMMENT",
 	Commit => "COMMIT",
 	Concurrently => "CONCURRENTLY",
@@ -61,6 +67,7 @@ keyword! {
 	Define => "DEFINE",
 	Delete => "DELETE",
 	Descending => "DESCENDING",
+	Deterministic => "DETERMINISTIC",
 	Diff => "DIFF",
 	Dimension => "DIMENSION",
 	Distance => "DISTANCE",
@@ -74,6 +81,7 @@ keyword! {
 	Edgengram => "EDGENGRAM",
 	Event => "EVENT",
 	Else => "ELSE",
+	Encrypted => "ENCRYPTED",
 	End => "END",
 	Enforced => "ENFORCED",
 	Exists => "EXISTS",
@@ -107,14 +115,17 @@ keyword! {
 	Key => "KEY",
 	KeepPrunedConnections => "KEEP_PRUNED_CONNECTIONS",
 	Kill => "KILL",
+	Length => "LENGTH",
 	Let => "LET",
 	Limit => "LIMIT",
 	List => "LIST",
 	Live => "LIVE",
+	Lockout => "LOCKOUT",
 	Lowercase => "LOWERCASE",
 	Lm => "LM",
 	M => "M",
 	M0 => "M0",
+	Mask => "MASK",
 	Merge => "MERGE",
 	Model => "MODEL",
 	MTree => "MTREE",
@@ -139,21 +150,31 @@ keyword! {
 	Password => "PASSWORD",
 	Patch => "PATCH",
 	Permissions => "PERMISSIONS",
+	Policy => "POLICY",
 	PostingsCache => "POSTINGS_CACHE",
 	PostingsOrder => "POSTINGS_ORDER",
+	Product => "PRODUCT",
 	Prune => "PRUNE",
 	Punct => "PUNCT",
+	Quantize => "QUANTIZE",
 	Range => "RANGE",
 	Readonly => "READONLY",
 	Rebuild => "REBUILD",
+	Recovery => "RECOVERY",
+	Recoveryhash => "RECOVERYHASH",
+	Refresh => "REFRESH",
 	Relate => "RELATE",
 	Relation => "RELATION",
 	Remove => "REMOVE",
 	Replace => "REPLACE",
+	Rerank => "RERANK",
 	Return => "RETURN",
+	Reveal => "REVEAL",
 	Revoke => "REVOKE",
+	Role => "ROLE",
 	Roles => "ROLES",
 	Root => "ROOT",
+	Scalar => "SCALAR",
 	Schemafull => "SCHEMAFULL",
 	Schemaless => "SCHEMALESS",
 	Scope => "SCOPE",
@@ -167,10 +188,12 @@ keyword! {
 	Since => "SINCE",
 	Sleep => "SLEEP",
 	Snowball => "SNOWBALL",
+	Sparse => "SPARSE",
 	Split => "SPLIT",
 	Start => "START",
 	Structure => "STRUCTURE",
 	Table => "TABLE",
+	Tables => "TABLES",
 	TempFiles => "TEMPFILES",
 	TermsCache => "TERMS_CACHE",
 	TermsOrder => "TERMS_ORDER",
@@ -179,6 +202,7 @@ keyword! {
 	Timeout => "TIMEOUT",
 	Tokenizers => "TOKENIZERS",
 	Token => "TOKEN",
+	Totp => "TOTP",
 	To => "TO",
 	Transaction => "TRANSACTION",
 	True => "true",
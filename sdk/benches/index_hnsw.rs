@@ -214,6 +214,8 @@ async fn hnsw(tx: &Transaction) -> HnswIndex {
 		EF_CONSTRUCTION,
 		false,
 		false,
+		None,
+		false,
 	);
 	HnswIndex::new(tx, IndexKeyBase::default(), "test".to_string(), &p).await.unwrap()
 }
@@ -29,6 +29,7 @@ pub(crate) enum Command {
 		token: String,
 	},
 	Invalidate,
+	CloseSession,
 	Create {
 		what: Resource,
 		data: Option<CoreValue>,
@@ -61,6 +62,10 @@ pub(crate) enum Command {
 	Select {
 		what: Resource,
 	},
+	SelectStream {
+		what: Resource,
+		rows: Sender<Result<CoreValue>>,
+	},
 	Delete {
 		what: Resource,
 	},
@@ -68,6 +73,20 @@ pub(crate) enum Command {
 		query: Query,
 		variables: CoreObject,
 	},
+	Prepare {
+		query: String,
+	},
+	ExecutePrepared {
+		id: Uuid,
+		variables: CoreObject,
+	},
+	Unprepare {
+		id: Uuid,
+	},
+	Batch {
+		calls: CoreArray,
+		atomic: bool,
+	},
 	ExportFile {
 		path: PathBuf,
 	},
@@ -109,17 +128,35 @@ pub(crate) enum Command {
 		version: Option<String>,
 		args: CoreArray,
 	},
+	Begin,
+	Commit {
+		txn: Uuid,
+	},
+	Cancel {
+		txn: Uuid,
+	},
+	CancelRequest {
+		id: i64,
+	},
 }
 
 impl Command {
 	#[cfg(any(feature = "protocol-ws", feature = "protocol-http"))]
-	pub(crate) fn into_router_request(self, id: Option<i64>) -> Option<RouterRequest> {
+	pub(crate) fn into_router_request(
+		self,
+		id: Option<i64>,
+		session: Option<Uuid>,
+		txn: Option<Uuid>,
+	) -> Option<RouterRequest> {
 		let res = match self {
 			Command::Use {
 				namespace,
 				database,
 			} => RouterRequest {
 				id,
+				session,
+				txn,
+				stream: false,
 				method: "use",
 				params: Some(vec![CoreValue::from(namespace), CoreValue::from(database)].into()),
 			},
@@ -127,6 +164,9 @@ impl Command {
 				credentials,
 			} => RouterRequest {
 				id,
+				session,
+				txn,
+				stream: false,
 				method: "signup",
 				params: Some(vec![CoreValue::from(credentials)].into()),
 			},
@@ -134,6 +174,9 @@ impl Command {
 				credentials,
 			} => RouterRequest {
 				id,
+				session,
+				txn,
+				stream: false,
 				method: "signin",
 				params: Some(vec![CoreValue::from(credentials)].into()),
 			},
@@ -141,11 +184,17 @@ impl Command {
 				token,
 			} => RouterRequest {
 				id,
+				session,
+				txn,
+				stream: false,
 				method: "authenticate",
 				params: Some(vec![CoreValue::from(token)].into()),
 			},
 			Command::Invalidate => RouterRequest {
 				id,
+				session,
+				txn,
+				stream: false,
 				method: "invalidate",
 				params: None,
 			},
@@ -160,6 +209,9 @@ impl Command {
 
 				RouterRequest {
 					id,
+					session,
+					txn,
+					stream: false,
 					method: "create",
 					params: Some(params.into()),
 				}
@@ -176,6 +228,9 @@ impl Command {
 
 				RouterRequest {
 					id,
+					session,
+					txn,
+					stream: false,
 					method: "upsert",
 					params: Some(params.into()),
 				}
@@ -193,6 +248,9 @@ impl Command {
 
 				RouterRequest {
 					id,
+					session,
+					txn,
+					stream: false,
 					method: "update",
 					params: Some(params.into()),
 				}
@@ -214,6 +272,9 @@ impl Command {
 
 				RouterRequest {
 					id,
+					session,
+					txn,
+					stream: false,
 					method: "insert",
 					params: Some(params.into()),
 				}
@@ -234,6 +295,9 @@ impl Command {
 
 				RouterRequest {
 					id,
+					session,
+					txn,
+					stream: false,
 					method: "insert_relation",
 					params: Some(params.into()),
 				}
@@ -251,6 +315,9 @@ impl Command {
 
 				RouterRequest {
 					id,
+					session,
+					txn,
+					stream: false,
 					method: "patch",
 					params: Some(params.into()),
 				}
@@ -267,6 +334,9 @@ impl Command {
 
 				RouterRequest {
 					id,
+					session,
+					txn,
+					stream: false,
 					method: "merge",
 					params: Some(params.into()),
 				}
@@ -276,6 +346,20 @@ impl Command {
 				..
 			} => RouterRequest {
 				id,
+				session,
+				txn,
+				stream: false,
+				method: "select",
+				params: Some(CoreValue::Array(vec![what.into_core_value()].into())),
+			},
+			Command::SelectStream {
+				what,
+				..
+			} => RouterRequest {
+				id,
+				session,
+				txn,
+				stream: true,
 				method: "select",
 				params: Some(CoreValue::Array(vec![what.into_core_value()].into())),
 			},
@@ -284,6 +368,9 @@ impl Command {
 				..
 			} => RouterRequest {
 				id,
+				session,
+				txn,
+				stream: false,
 				method: "delete",
 				params: Some(CoreValue::Array(vec![what.into_core_value()].into())),
 			},
@@ -294,10 +381,61 @@ impl Command {
 				let params: Vec<CoreValue> = vec![query.into(), variables.into()];
 				RouterRequest {
 					id,
+					session,
+					txn,
+					stream: false,
 					method: "query",
 					params: Some(params.into()),
 				}
 			}
+			Command::Prepare {
+				query,
+			} => RouterRequest {
+				id,
+				session,
+				txn,
+				stream: false,
+				method: "prepare",
+				params: Some(CoreValue::from(vec![CoreValue::from(query)])),
+			},
+			Command::ExecutePrepared {
+				id: prepared,
+				variables,
+			} => {
+				let params: Vec<CoreValue> = vec![prepared.into(), variables.into()];
+				RouterRequest {
+					id,
+					session,
+					txn,
+					stream: false,
+					method: "execute_prepared",
+					params: Some(params.into()),
+				}
+			}
+			Command::Unprepare {
+				id: prepared,
+			} => RouterRequest {
+				id,
+				session,
+				txn,
+				stream: false,
+				method: "unprepare",
+				params: Some(CoreValue::from(vec![CoreValue::from(prepared)])),
+			},
+			Command::Batch {
+				calls,
+				atomic,
+			} => {
+				let params: Vec<CoreValue> = vec![calls.into(), atomic.into()];
+				RouterRequest {
+					id,
+					session,
+					txn,
+					stream: false,
+					method: "batch",
+					params: Some(params.into()),
+				}
+			}
 			Command::ExportFile {
 				..
 			}
@@ -316,13 +454,27 @@ impl Command {
 			| Command::ImportMl {
 				..
 			} => return None,
+			Command::CloseSession => RouterRequest {
+				id,
+				session,
+				txn,
+				stream: false,
+				method: "session_close",
+				params: None,
+			},
 			Command::Health => RouterRequest {
 				id,
+				session,
+				txn,
+				stream: false,
 				method: "ping",
 				params: None,
 			},
 			Command::Version => RouterRequest {
 				id,
+				session,
+				txn,
+				stream: false,
 				method: "version",
 				params: None,
 			},
@@ -331,6 +483,9 @@ impl Command {
 				value,
 			} => RouterRequest {
 				id,
+				session,
+				txn,
+				stream: false,
 				method: "let",
 				params: Some(CoreValue::from(vec![CoreValue::from(key), value])),
 			},
@@ -338,6 +493,9 @@ impl Command {
 				key,
 			} => RouterRequest {
 				id,
+				session,
+				txn,
+				stream: false,
 				method: "unset",
 				params: Some(CoreValue::from(vec![CoreValue::from(key)])),
 			},
@@ -348,6 +506,9 @@ impl Command {
 				uuid,
 			} => RouterRequest {
 				id,
+				session,
+				txn,
+				stream: false,
 				method: "kill",
 				params: Some(CoreValue::from(vec![CoreValue::from(uuid)])),
 			},
@@ -357,12 +518,53 @@ impl Command {
 				args,
 			} => RouterRequest {
 				id,
+				session,
+				txn,
+				stream: false,
 				method: "run",
 				params: Some(
 					vec![CoreValue::from(name), CoreValue::from(version), CoreValue::Array(args)]
 						.into(),
 				),
 			},
+			Command::Begin => RouterRequest {
+				id,
+				session,
+				txn,
+				stream: false,
+				method: "begin",
+				params: None,
+			},
+			Command::Commit {
+				txn: txn_id,
+			} => RouterRequest {
+				id,
+				session,
+				txn,
+				stream: false,
+				method: "commit",
+				params: Some(CoreValue::from(vec![CoreValue::from(txn_id)])),
+			},
+			Command::Cancel {
+				txn: txn_id,
+			} => RouterRequest {
+				id,
+				session,
+				txn,
+				stream: false,
+				method: "cancel",
+				params: Some(CoreValue::from(vec![CoreValue::from(txn_id)])),
+			},
+			Command::CancelRequest {
+				id: request,
+			} => RouterRequest {
+				id,
+				session,
+				txn,
+				stream: false,
+				method: "abort",
+				params: Some(CoreValue::from(vec![CoreValue::from(request)])),
+			},
 		};
 		Some(res)
 	}
@@ -409,6 +611,9 @@ pub(crate) struct RouterRequest {
 	id: Option<i64>,
 	method: &'static str,
 	params: Option<CoreValue>,
+	session: Option<Uuid>,
+	txn: Option<Uuid>,
+	stream: bool,
 }
 
 impl Serialize for RouterRequest {
@@ -464,7 +669,12 @@ impl Serialize for RouterRequest {
 			where
 				S: serde::Serializer,
 			{
-				let size = 1 + self.0.id.is_some() as usize + self.0.params.is_some() as usize;
+				let size = 1
+					+ self.0.id.is_some() as usize
+					+ self.0.params.is_some() as usize
+					+ self.0.session.is_some() as usize
+					+ self.0.txn.is_some() as usize
+					+ self.0.stream as usize;
 				let mut map = serializer.serialize_map(Some(size))?;
 				if let Some(id) = self.0.id.as_ref() {
 					map.serialize_entry("id", &InnerNumberVariant(*id))?;
@@ -473,6 +683,15 @@ impl Serialize for RouterRequest {
 				if let Some(params) = self.0.params.as_ref() {
 					map.serialize_entry("params", params)?;
 				}
+				if let Some(session) = self.0.session {
+					map.serialize_entry("session", &CoreValue::from(session))?;
+				}
+				if let Some(txn) = self.0.txn {
+					map.serialize_entry("txn", &CoreValue::from(txn))?;
+				}
+				if self.0.stream {
+					map.serialize_entry("stream", &CoreValue::Bool(true))?;
+				}
 				map.end()
 			}
 		}
@@ -511,7 +730,12 @@ impl Revisioned for RouterRequest {
 		// object wrapper version
 		Revisioned::serialize_revisioned(&1u32, w)?;
 
-		let size = 1 + self.id.is_some() as usize + self.params.is_some() as usize;
+		let size = 1
+			+ self.id.is_some() as usize
+			+ self.params.is_some() as usize
+			+ self.session.is_some() as usize
+			+ self.txn.is_some() as usize
+			+ self.stream as usize;
 		size.serialize_revisioned(w)?;
 
 		let serializer = bincode::options()
@@ -564,6 +788,27 @@ impl Revisioned for RouterRequest {
 			x.serialize_revisioned(w)?;
 		}
 
+		if let Some(x) = self.session {
+			serializer
+				.serialize_into(&mut *w, "session")
+				.map_err(|err| revision::Error::Serialize(err.to_string()))?;
+			CoreValue::from(x).serialize_revisioned(w)?;
+		}
+
+		if let Some(x) = self.txn {
+			serializer
+				.serialize_into(&mut *w, "txn")
+				.map_err(|err| revision::Error::Serialize(err.to_string()))?;
+			CoreValue::from(x).serialize_revisioned(w)?;
+		}
+
+		if self.stream {
+			serializer
+				.serialize_into(&mut *w, "stream")
+				.map_err(|err| revision::Error::Serialize(err.to_string()))?;
+			CoreValue::Bool(true).serialize_revisioned(w)?;
+		}
+
 		Ok(())
 	}
 
@@ -581,6 +826,7 @@ mod test {
 
 	use revision::Revisioned;
 	use surrealdb_core::sql::{Number, Value};
+	use uuid::Uuid;
 
 	use super::RouterRequest;
 
@@ -608,6 +854,12 @@ mod test {
 		assert_eq!(x.0, req.method);
 
 		assert_eq!(obj.get("params").cloned(), req.params);
+
+		assert_eq!(obj.get("session").cloned(), req.session.map(Value::from));
+
+		assert_eq!(obj.get("txn").cloned(), req.txn.map(Value::from));
+
+		assert_eq!(obj.get("stream").cloned(), req.stream.then_some(Value::Bool(true)));
 	}
 
 	#[test]
@@ -616,6 +868,9 @@ mod test {
 			id: Some(1234),
 			method: "request",
 			params: Some(vec![Value::from(1234i64), Value::from("request")].into()),
+			session: Some(Uuid::new_v4()),
+			txn: Some(Uuid::new_v4()),
+			stream: true,
 		};
 
 		println!("test convert bincode");
@@ -13,7 +13,9 @@ use serde::de::DeserializeOwned;
 use std::collections::HashSet;
 use std::sync::atomic::AtomicI64;
 use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use surrealdb_core::sql::{from_value as from_core_value, Value as CoreValue};
+use uuid::Uuid;
 
 mod cmd;
 pub(crate) use cmd::Command;
@@ -25,6 +27,8 @@ pub(crate) use cmd::RouterRequest;
 pub struct RequestData {
 	pub(crate) id: i64,
 	pub(crate) command: Command,
+	pub(crate) session: Option<Uuid>,
+	pub(crate) transaction: Option<Uuid>,
 }
 
 #[derive(Debug)]
@@ -34,12 +38,81 @@ pub(crate) struct Route {
 	pub(crate) response: Sender<Result<DbResponse>>,
 }
 
+/// A request which has been sent to the router, and which is waiting for its response
+#[derive(Debug)]
+pub(crate) struct Pending {
+	receiver: Receiver<Result<DbResponse>>,
+	guard: RequestGuard,
+}
+
+impl Pending {
+	/// Waits for the response, after which the request is no longer cancelled when dropped
+	pub(crate) async fn recv(self) -> Result<DbResponse> {
+		let (response, guard) = self.recv_guarded().await;
+		guard.complete();
+		response
+	}
+
+	/// Waits for the response, returning the guard which still cancels the request when dropped
+	pub(crate) async fn recv_guarded(self) -> (Result<DbResponse>, RequestGuard) {
+		let response = match self.receiver.recv().await {
+			Ok(response) => response,
+			Err(error) => Err(error.into()),
+		};
+		(response, self.guard)
+	}
+}
+
+/// Cancels a request on the server if it is dropped before the request has completed
+#[derive(Debug)]
+pub(crate) struct RequestGuard {
+	id: i64,
+	sender: Sender<Route>,
+	last_id: Arc<AtomicI64>,
+	completed: bool,
+}
+
+impl RequestGuard {
+	/// Marks the request as completed, so that it is no longer cancelled when dropped
+	pub(crate) fn complete(mut self) {
+		self.completed = true;
+	}
+}
+
+impl Drop for RequestGuard {
+	fn drop(&mut self) {
+		if self.completed {
+			return;
+		}
+		// Nobody waits for the response of the cancellation itself
+		let (response, _) = channel::bounded(1);
+		let route = Route {
+			request: RequestData {
+				id: self.last_id.fetch_add(1, Ordering::SeqCst),
+				command: Command::CancelRequest {
+					id: self.id,
+				},
+				session: None,
+				transaction: None,
+			},
+			response,
+		};
+		// The router can not be waited for while dropping, so the request is left running
+		// when the router is too busy to accept the cancellation
+		let _ = self.sender.try_send(route);
+	}
+}
+
 /// Message router
 #[derive(Debug)]
 pub struct Router {
 	pub(crate) sender: Sender<Route>,
-	pub(crate) last_id: AtomicI64,
+	pub(crate) last_id: Arc<AtomicI64>,
 	pub(crate) features: HashSet<ExtraFeatures>,
+	/// The session which requests sent through this router run as
+	pub(crate) session: Option<Uuid>,
+	/// The transaction which requests sent through this router run within
+	pub(crate) transaction: Option<Uuid>,
 }
 
 impl Router {
@@ -47,10 +120,29 @@ impl Router {
 		self.last_id.fetch_add(1, Ordering::SeqCst)
 	}
 
-	pub(crate) fn send(
-		&self,
-		command: Command,
-	) -> BoxFuture<'_, Result<Receiver<Result<DbResponse>>>> {
+	/// Creates a router which sends requests over the same connection, as a separate session
+	pub(crate) fn with_session(&self, session: Uuid) -> Self {
+		Self {
+			sender: self.sender.clone(),
+			last_id: self.last_id.clone(),
+			features: self.features.clone(),
+			session: Some(session),
+			transaction: None,
+		}
+	}
+
+	/// Creates a router which sends requests over the same connection, within a transaction
+	pub(crate) fn with_transaction(&self, transaction: Uuid) -> Self {
+		Self {
+			sender: self.sender.clone(),
+			last_id: self.last_id.clone(),
+			features: self.features.clone(),
+			session: self.session,
+			transaction: Some(transaction),
+		}
+	}
+
+	pub(crate) fn send(&self, command: Command) -> BoxFuture<'_, Result<Pending>> {
 		Box::pin(async move {
 			let id = self.next_id();
 			let (sender, receiver) = channel::bounded(1);
@@ -58,22 +150,28 @@ impl Router {
 				request: RequestData {
 					id,
 					command,
+					session: self.session,
+					transaction: self.transaction,
 				},
 				response: sender,
 			};
 			self.sender.send(route).await?;
-			Ok(receiver)
+			Ok(Pending {
+				receiver,
+				guard: RequestGuard {
+					id,
+					sender: self.sender.clone(),
+					last_id: self.last_id.clone(),
+					completed: false,
+				},
+			})
 		})
 	}
 
 	/// Receive responses for all methods except `query`
-	pub(crate) fn recv(
-		&self,
-		receiver: Receiver<Result<DbResponse>>,
-	) -> BoxFuture<'_, Result<CoreValue>> {
+	pub(crate) fn recv(&self, receiver: Pending) -> BoxFuture<'_, Result<CoreValue>> {
 		Box::pin(async move {
-			let response = receiver.recv().await?;
-			match response? {
+			match receiver.recv().await? {
 				DbResponse::Other(value) => Ok(value),
 				DbResponse::Query(..) => unreachable!(),
 			}
@@ -81,13 +179,9 @@ impl Router {
 	}
 
 	/// Receive the response of the `query` method
-	pub(crate) fn recv_query(
-		&self,
-		receiver: Receiver<Result<DbResponse>>,
-	) -> BoxFuture<'_, Result<Response>> {
+	pub(crate) fn recv_query(&self, receiver: Pending) -> BoxFuture<'_, Result<Response>> {
 		Box::pin(async move {
-			let response = receiver.recv().await?;
-			match response? {
+			match receiver.recv().await? {
 				DbResponse::Query(results) => Ok(results),
 				DbResponse::Other(..) => unreachable!(),
 			}
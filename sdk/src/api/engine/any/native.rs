@@ -217,7 +217,9 @@ impl Connection for Any {
 				Arc::new(OnceLock::with_value(Router {
 					features,
 					sender: route_tx,
-					last_id: AtomicI64::new(0),
+					last_id: Arc::new(AtomicI64::new(0)),
+					session: None,
+					transaction: None,
 				})),
 				Arc::new(watch::channel(Some(WaitFor::Connection))),
 			))
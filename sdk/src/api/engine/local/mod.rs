@@ -42,6 +42,7 @@ use surrealdb_core::{
 	dbs::{Response, Session},
 	iam,
 	kvs::Datastore,
+	rpc::{method::Method, BasicRpcContext, Prepared, RpcContext},
 	sql::{
 		statements::{
 			CreateStatement, DeleteStatement, InsertStatement, KillStatement, SelectStatement,
@@ -480,7 +481,7 @@ async fn export_ml(
 	// Ensure a NS and DB are set
 	let (nsv, dbv) = check_ns_db(sess)?;
 	// Check the permissions level
-	kvs.check(sess, Action::View, ResourceKind::Model.on_db(&nsv, &dbv))?;
+	kvs.check(sess, Action::View, ResourceKind::Model.on_db(&nsv, &dbv)).await?;
 	// Start a new readonly transaction
 	let tx = kvs.transaction(TransactionType::Read, LockType::Optimistic).await?;
 	// Attempt to get the model definition
@@ -528,15 +529,91 @@ async fn kill_live_query(
 	take(true, response).await
 }
 
+async fn process_query(
+	kvs: &Datastore,
+	txn: Option<Uuid>,
+	query: Query,
+	session: &Session,
+	vars: BTreeMap<String, CoreValue>,
+) -> Result<Vec<Response>> {
+	let response = match txn {
+		Some(id) => kvs.process_in(id, query, session, Some(vars)).await?,
+		None => kvs.process(query, session, Some(vars)).await?,
+	};
+	Ok(response)
+}
+
+/// The authentication, selected namespace and database, variables and prepared queries of each session
+type Sessions = HashMap<Option<Uuid>, (Session, BTreeMap<String, CoreValue>, Prepared)>;
+
+/// The live queries of all sessions, along with the session which started each of them
+type LiveQueries = HashMap<Uuid, (Option<Uuid>, Sender<Notification<CoreValue>>)>;
+
+/// Routes a request to the session which it runs as, creating the session on first use
+async fn route(
+	request: RequestData,
+	kvs: &Arc<Datastore>,
+	sessions: &mut Sessions,
+	live_queries: &mut LiveQueries,
+) -> Result<DbResponse> {
+	// Closing a session also kills the live queries which it started
+	if let Command::CloseSession = request.command {
+		close_session(kvs, request.session, sessions, live_queries).await?;
+		return Ok(DbResponse::Other(CoreValue::None));
+	}
+	// Invalidating the main session also closes all other sessions
+	let invalidate = request.session.is_none() && matches!(request.command, Command::Invalidate);
+	let (session, vars, prepared) = sessions.entry(request.session).or_insert_with(|| {
+		(Session::default().with_rt(true), BTreeMap::new(), Prepared::default())
+	});
+	let res = router(request, kvs, session, vars, prepared, live_queries).await?;
+	if invalidate {
+		let ids: Vec<_> = sessions.keys().filter(|v| v.is_some()).copied().collect();
+		for id in ids {
+			close_session(kvs, id, sessions, live_queries).await?;
+		}
+	}
+	Ok(res)
+}
+
+/// Closes a session created with `new_session`, killing the live queries which it started
+async fn close_session(
+	kvs: &Datastore,
+	id: Option<Uuid>,
+	sessions: &mut Sessions,
+	live_queries: &mut LiveQueries,
+) -> Result<()> {
+	// The main session can not be closed
+	if id.is_none() {
+		return Err(Error::SessionNotClosable.into());
+	}
+	// Sessions are created on first use, so unknown sessions are already closed
+	let Some((session, vars, _)) = sessions.remove(&id) else {
+		return Ok(());
+	};
+	let lqids: Vec<_> =
+		live_queries.iter().filter(|(_, (v, _))| *v == id).map(|(lqid, _)| *lqid).collect();
+	for lqid in lqids {
+		live_queries.remove(&lqid);
+		if let Err(error) = kill_live_query(kvs, lqid, &session, vars.clone()).await {
+			warn!("Failed to kill live query '{lqid}'; {error}");
+		}
+	}
+	Ok(())
+}
+
 async fn router(
 	RequestData {
 		command,
+		session: session_id,
+		transaction,
 		..
 	}: RequestData,
 	kvs: &Arc<Datastore>,
 	session: &mut Session,
 	vars: &mut BTreeMap<String, CoreValue>,
-	live_queries: &mut HashMap<Uuid, Sender<Notification<CoreValue>>>,
+	prepared: &Prepared,
+	live_queries: &mut LiveQueries,
 ) -> Result<DbResponse> {
 	match command {
 		Command::Use {
@@ -570,7 +647,7 @@ async fn router(
 			Ok(DbResponse::Other(CoreValue::None))
 		}
 		Command::Invalidate => {
-			iam::clear::clear(session)?;
+			iam::clear::revoke(kvs, session).await?;
 			Ok(DbResponse::Other(CoreValue::None))
 		}
 		Command::Create {
@@ -586,7 +663,7 @@ async fn router(
 				stmt
 			};
 			query.0 .0 = vec![Statement::Create(statement)];
-			let response = kvs.process(query, &*session, Some(vars.clone())).await?;
+			let response = process_query(kvs, transaction, query, session, vars.clone()).await?;
 			let value = take(true, response).await?;
 			Ok(DbResponse::Other(value))
 		}
@@ -605,7 +682,7 @@ async fn router(
 			};
 			query.0 .0 = vec![Statement::Upsert(statement)];
 			let vars = vars.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
-			let response = kvs.process(query, &*session, Some(vars)).await?;
+			let response = process_query(kvs, transaction, query, session, vars).await?;
 			let value = take(one, response).await?;
 			Ok(DbResponse::Other(value))
 		}
@@ -624,7 +701,7 @@ async fn router(
 			};
 			query.0 .0 = vec![Statement::Update(statement)];
 			let vars = vars.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
-			let response = kvs.process(query, &*session, Some(vars)).await?;
+			let response = process_query(kvs, transaction, query, session, vars).await?;
 			let value = take(one, response).await?;
 			Ok(DbResponse::Other(value))
 		}
@@ -643,7 +720,7 @@ async fn router(
 			};
 			query.0 .0 = vec![Statement::Insert(statement)];
 			let vars = vars.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
-			let response = kvs.process(query, &*session, Some(vars)).await?;
+			let response = process_query(kvs, transaction, query, session, vars).await?;
 			let value = take(one, response).await?;
 			Ok(DbResponse::Other(value))
 		}
@@ -662,7 +739,7 @@ async fn router(
 				stmt
 			};
 			query.0 .0 = vec![Statement::Insert(statement)];
-			let response = kvs.process(query, &*session, Some(vars.clone())).await?;
+			let response = process_query(kvs, transaction, query, session, vars.clone()).await?;
 			let value = take(one, response).await?;
 			Ok(DbResponse::Other(value))
 		}
@@ -681,7 +758,7 @@ async fn router(
 			};
 			query.0 .0 = vec![Statement::Update(statement)];
 			let vars = vars.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
-			let response = kvs.process(query, &*session, Some(vars)).await?;
+			let response = process_query(kvs, transaction, query, session, vars).await?;
 			let value = take(one, response).await?;
 			Ok(DbResponse::Other(value))
 		}
@@ -700,7 +777,7 @@ async fn router(
 			};
 			query.0 .0 = vec![Statement::Update(statement)];
 			let vars = vars.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
-			let response = kvs.process(query, &*session, Some(vars)).await?;
+			let response = process_query(kvs, transaction, query, session, vars).await?;
 			let value = take(one, response).await?;
 			Ok(DbResponse::Other(value))
 		}
@@ -717,10 +794,62 @@ async fn router(
 			};
 			query.0 .0 = vec![Statement::Select(statement)];
 			let vars = vars.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
-			let response = kvs.process(query, &*session, Some(vars)).await?;
+			let response = process_query(kvs, transaction, query, session, vars).await?;
 			let value = take(one, response).await?;
 			Ok(DbResponse::Other(value))
 		}
+		Command::SelectStream {
+			what,
+			rows,
+		} => {
+			let mut query = Query::default();
+			let statement = {
+				let mut stmt = SelectStatement::default();
+				stmt.what = resource_to_values(what);
+				stmt.expr.0 = vec![Field::All];
+				stmt
+			};
+			query.0 .0 = vec![Statement::Select(statement)];
+			let (tx, rx) = crate::channel::bounded(1);
+
+			let kvs = kvs.clone();
+			let session = session.clone();
+			let vars = vars.clone();
+			let stream = async move {
+				let select = async {
+					let response = match transaction {
+						Some(id) => {
+							kvs.process_stream_in(id, query, &session, Some(vars), tx).await
+						}
+						None => kvs.process_stream(query, &session, Some(vars), tx).await,
+					};
+					let result = match response {
+						Ok(response) => take(false, response).await,
+						Err(error) => Err(error.into()),
+					};
+					if let Err(error) = result {
+						let _ = rows.send(Err(error)).await;
+					}
+				};
+
+				let bridge = async {
+					while let Ok(row) = rx.recv().await {
+						if rows.send(Ok(row.value)).await.is_err() {
+							break;
+						}
+					}
+				};
+
+				futures::join!(select, bridge);
+			};
+
+			#[cfg(not(target_arch = "wasm32"))]
+			tokio::spawn(stream);
+			#[cfg(target_arch = "wasm32")]
+			wasm_bindgen_futures::spawn_local(stream);
+
+			Ok(DbResponse::Other(CoreValue::None))
+		}
 		Command::Delete {
 			what,
 		} => {
@@ -734,7 +863,7 @@ async fn router(
 			};
 			query.0 .0 = vec![Statement::Delete(statement)];
 			let vars = vars.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
-			let response = kvs.process(query, &*session, Some(vars)).await?;
+			let response = process_query(kvs, transaction, query, session, vars).await?;
 			let value = take(one, response).await?;
 			Ok(DbResponse::Other(value))
 		}
@@ -744,10 +873,50 @@ async fn router(
 		} => {
 			let mut vars = vars.clone();
 			vars.append(&mut variables.0);
-			let response = kvs.process(query, &*session, Some(vars)).await?;
+			let response = process_query(kvs, transaction, query, session, vars).await?;
+			let response = process(response);
+			Ok(DbResponse::Query(response))
+		}
+		Command::Prepare {
+			query,
+		} => {
+			let id = prepared.prepare(kvs, session, &query).await?;
+			Ok(DbResponse::Other(CoreValue::from(id)))
+		}
+		Command::ExecutePrepared {
+			id,
+			mut variables,
+		} => {
+			let query = prepared.get(kvs, session, id).await?;
+			let mut vars = vars.clone();
+			vars.append(&mut variables.0);
+			let response = process_query(kvs, transaction, query, session, vars).await?;
 			let response = process(response);
 			Ok(DbResponse::Query(response))
 		}
+		Command::Unprepare {
+			id,
+		} => {
+			prepared.remove(id)?;
+			Ok(DbResponse::Other(CoreValue::None))
+		}
+		Command::Batch {
+			calls,
+			atomic,
+		} => {
+			let rpc = BasicRpcContext::new(
+				kvs,
+				session.clone(),
+				vars.clone(),
+				surrealdb_core::env::VERSION.to_string(),
+			);
+			let params = vec![CoreValue::from(calls), CoreValue::from(atomic)];
+			let response = rpc
+				.execute_immut(transaction, Method::Batch, params.into())
+				.await
+				.map_err(crate::error::Db::from)?;
+			Ok(DbResponse::Other(response.into()))
+		}
 
 		#[cfg(target_arch = "wasm32")]
 		Command::ExportFile {
@@ -970,7 +1139,7 @@ async fn router(
 			// Ensure a NS and DB are set
 			let (nsv, dbv) = check_ns_db(session)?;
 			// Check the permissions level
-			kvs.check(session, Action::Edit, ResourceKind::Model.on_db(&nsv, &dbv))?;
+			kvs.check(session, Action::Edit, ResourceKind::Model.on_db(&nsv, &dbv)).await?;
 			// Create a new buffer
 			let mut buffer = Vec::new();
 			// Load all the uploaded file chunks
@@ -1046,7 +1215,7 @@ async fn router(
 			uuid,
 			notification_sender,
 		} => {
-			live_queries.insert(uuid, notification_sender);
+			live_queries.insert(uuid, (session_id, notification_sender));
 			Ok(DbResponse::Other(CoreValue::None))
 		}
 		Command::Kill {
@@ -1084,10 +1253,34 @@ async fn router(
 
 			let stmt = Statement::Value(func);
 
-			let response = kvs.process(stmt.into(), &*session, Some(vars.clone())).await?;
+			let response =
+				process_query(kvs, transaction, stmt.into(), session, vars.clone()).await?;
 			let value = take(true, response).await?;
 
 			Ok(DbResponse::Other(value))
 		}
+
+		Command::Begin => {
+			let id = kvs.begin(session).await?;
+			Ok(DbResponse::Other(CoreValue::from(id)))
+		}
+		Command::Commit {
+			txn,
+		} => {
+			kvs.commit(txn, session).await?;
+			Ok(DbResponse::Other(CoreValue::None))
+		}
+		Command::Cancel {
+			txn,
+		} => {
+			kvs.cancel(txn, session).await?;
+			Ok(DbResponse::Other(CoreValue::None))
+		}
+		// Embedded requests are run one at a time, so they have finished by the time they are cancelled
+		Command::CancelRequest {
+			..
+		} => Ok(DbResponse::Other(CoreValue::None)),
+		// Sessions are closed before requests are routed to them
+		Command::CloseSession => Ok(DbResponse::Other(CoreValue::None)),
 	}
 }
@@ -14,11 +14,11 @@ use crate::{
 use channel::{Receiver, Sender};
 use futures::{stream::poll_fn, StreamExt};
 use std::{
-	collections::{BTreeMap, HashMap, HashSet},
+	collections::{HashMap, HashSet},
 	sync::{atomic::AtomicI64, Arc, OnceLock},
 	task::Poll,
 };
-use surrealdb_core::{dbs::Session, iam::Level, kvs::Datastore, options::EngineOptions};
+use surrealdb_core::{iam::Level, kvs::Datastore, options::EngineOptions};
 use tokio::sync::watch;
 
 impl crate::api::Connection for Db {}
@@ -45,7 +45,9 @@ impl Connection for Db {
 				Arc::new(OnceLock::with_value(Router {
 					features,
 					sender: route_tx,
-					last_id: AtomicI64::new(0),
+					last_id: Arc::new(AtomicI64::new(0)),
+					session: None,
+					transaction: None,
 				})),
 				Arc::new(watch::channel(Some(WaitFor::Connection))),
 			))
@@ -112,9 +114,9 @@ pub(crate) async fn run_router(
 	let kvs = kvs.with_temporary_directory(address.config.temporary_directory);
 
 	let kvs = Arc::new(kvs);
-	let mut vars = BTreeMap::default();
+	// Each session has its own authentication, selected namespace and database, and variables
+	let mut sessions = HashMap::new();
 	let mut live_queries = HashMap::new();
-	let mut session = Session::default().with_rt(true);
 
 	let opt = {
 		let mut engine_options = EngineOptions::default();
@@ -140,16 +142,8 @@ pub(crate) async fn run_router(
 				let Ok(route) = route else {
 					break
 				};
-				match super::router(route.request, &kvs, &mut session, &mut vars, &mut live_queries)
-					.await
-				{
-					Ok(value) => {
-						let _ = route.response.send(Ok(value)).await;
-					}
-					Err(error) => {
-						let _ = route.response.send(Err(error)).await;
-					}
-				}
+				let res = super::route(route.request, &kvs, &mut sessions, &mut live_queries).await;
+				let _ = route.response.send(res).await;
 			}
 			notification = notification_stream.next() => {
 				let Some(notification) = notification else {
@@ -165,14 +159,18 @@ pub(crate) async fn run_router(
 				};
 
 				let id = notification.query_id;
-				if let Some(sender) = live_queries.get(&id) {
+				if let Some((session_id, sender)) = live_queries.get(&id) {
 
 					if sender.send(notification).await.is_err() {
+						let session_id = *session_id;
 						live_queries.remove(&id);
-						if let Err(error) =
-							super::kill_live_query(&kvs, id, &session, vars.clone()).await
-						{
-							warn!("Failed to kill live query '{id}'; {error}");
+						// The live query is killed within the session which started it
+						if let Some((session, vars, _)) = sessions.get(&session_id) {
+							if let Err(error) =
+								super::kill_live_query(&kvs, id, session, vars.clone()).await
+							{
+								warn!("Failed to kill live query '{id}'; {error}");
+							}
 						}
 					}
 				}
@@ -9,7 +9,6 @@ use crate::api::ExtraFeatures;
 use crate::api::OnceLockExt;
 use crate::api::Result;
 use crate::api::Surreal;
-use crate::dbs::Session;
 use crate::engine::tasks::start_tasks;
 use crate::iam::Level;
 use crate::kvs::Datastore;
@@ -21,7 +20,6 @@ use channel::{Receiver, Sender};
 use futures::stream::poll_fn;
 use futures::FutureExt;
 use futures::StreamExt;
-use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::sync::atomic::AtomicI64;
@@ -54,7 +52,9 @@ impl Connection for Db {
 				Arc::new(OnceLock::with_value(Router {
 					features,
 					sender: route_tx,
-					last_id: AtomicI64::new(0),
+					last_id: Arc::new(AtomicI64::new(0)),
+					session: None,
+					transaction: None,
 				})),
 				Arc::new(watch::channel(Some(WaitFor::Connection))),
 			))
@@ -109,9 +109,9 @@ pub(crate) async fn run_router(
 		.with_capabilities(address.config.capabilities);
 
 	let kvs = Arc::new(kvs);
-	let mut vars = BTreeMap::new();
+	// Each session has its own authentication, selected namespace and database, and variables
+	let mut sessions = HashMap::new();
 	let mut live_queries = HashMap::new();
-	let mut session = Session::default().with_rt(true);
 
 	let mut opt = EngineOptions::default();
 	opt.tick_interval = address.config.tick_interval.unwrap_or(DEFAULT_TICK_INTERVAL);
@@ -132,22 +132,8 @@ pub(crate) async fn run_router(
 					break
 				};
 
-				match super::router(
-					route.request,
-					&kvs,
-					&mut session,
-					&mut vars,
-					&mut live_queries,
-				)
-				.await
-				{
-					Ok(value) => {
-						let _ = route.response.send(Ok(value)).await;
-					}
-					Err(error) => {
-						let _ = route.response.send(Err(error)).await;
-					}
-				}
+				let res = super::route(route.request, &kvs, &mut sessions, &mut live_queries).await;
+				let _ = route.response.send(res).await;
 			}
 			notification = notification_stream.next().fuse() => {
 				let Some(notification) = notification else {
@@ -157,7 +143,7 @@ pub(crate) async fn run_router(
 				};
 
 				let id = notification.id;
-				if let Some(sender) = live_queries.get(&id) {
+				if let Some((session_id, sender)) = live_queries.get(&id) {
 
 					let notification = Notification {
 						query_id: notification.id.0,
@@ -166,11 +152,15 @@ pub(crate) async fn run_router(
 					};
 
 					if sender.send(notification).await.is_err() {
+						let session_id = *session_id;
 						live_queries.remove(&id);
-						if let Err(error) =
-							super::kill_live_query(&kvs, *id, &session, vars.clone()).await
-						{
-							warn!("Failed to kill live query '{id}'; {error}");
+						// The live query is killed within the session which started it
+						if let Some((session, vars, _)) = sessions.get(&session_id) {
+							if let Err(error) =
+								super::kill_live_query(&kvs, *id, session, vars.clone()).await
+							{
+								warn!("Failed to kill live query '{id}'; {error}");
+							}
 						}
 					}
 				}
@@ -24,12 +24,14 @@ use reqwest::header::CONTENT_TYPE;
 use reqwest::RequestBuilder;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use surrealdb_core::sql::{
 	from_value as from_core_value, statements::OutputStatement, Object as CoreObject, Param, Query,
 	Statement, Value as CoreValue,
 };
 use url::Url;
+use uuid::Uuid;
 
 #[cfg(not(target_arch = "wasm32"))]
 use std::path::PathBuf;
@@ -284,6 +286,34 @@ fn flatten_dbresponse_array(res: DbResponse) -> DbResponse {
 	}
 }
 
+/// The headers, variables and credentials of a session
+type SessionState = (HeaderMap, IndexMap<String, CoreValue>, Option<Auth>);
+
+/// Routes a request to the session which it runs as, creating the session on first use
+async fn route(
+	req: RequestData,
+	base_url: &Url,
+	client: &reqwest::Client,
+	sessions: &mut HashMap<Option<Uuid>, SessionState>,
+) -> Result<DbResponse> {
+	// Closing a session drops its headers, variables and credentials
+	if let Command::CloseSession = req.command {
+		if req.session.is_none() {
+			return Err(Error::SessionNotClosable.into());
+		}
+		sessions.remove(&req.session);
+		return Ok(DbResponse::Other(CoreValue::None));
+	}
+	// Invalidating the main session also closes all other sessions
+	let invalidate = req.session.is_none() && matches!(req.command, Command::Invalidate);
+	let (headers, vars, auth) = sessions.entry(req.session).or_default();
+	let res = router(req, base_url, client, headers, vars, auth).await?;
+	if invalidate {
+		sessions.retain(|id, _| id.is_none());
+	}
+	Ok(res)
+}
+
 async fn router(
 	req: RequestData,
 	base_url: &Url,
@@ -302,7 +332,7 @@ async fn router(
 				query,
 				variables,
 			}
-			.into_router_request(None)
+			.into_router_request(None, None, req.transaction)
 			.expect("query should be valid request");
 			send_request(req, base_url, client, headers, auth).await
 		}
@@ -314,7 +344,7 @@ async fn router(
 				namespace: namespace.clone(),
 				database: database.clone(),
 			}
-			.into_router_request(None)
+			.into_router_request(None, None, None)
 			.unwrap();
 			// process request to check permissions
 			let out = send_request(req, base_url, client, headers, auth).await?;
@@ -337,7 +367,7 @@ async fn router(
 			let req = Command::Signin {
 				credentials: credentials.clone(),
 			}
-			.into_router_request(None)
+			.into_router_request(None, None, None)
 			.expect("signin should be a valid router request");
 
 			let DbResponse::Other(value) =
@@ -376,7 +406,7 @@ async fn router(
 			let req = Command::Authenticate {
 				token: token.clone(),
 			}
-			.into_router_request(None)
+			.into_router_request(None, None, None)
 			.expect("authenticate should be a valid router request");
 			send_request(req, base_url, client, headers, auth).await?;
 
@@ -402,7 +432,7 @@ async fn router(
 				query,
 				variables,
 			}
-			.into_router_request(None)
+			.into_router_request(None, None, None)
 			.expect("query is valid request");
 			let DbResponse::Query(mut res) =
 				send_request(req, base_url, client, headers, auth).await?
@@ -523,9 +553,54 @@ async fn router(
 		Command::SubscribeLive {
 			..
 		} => Err(Error::LiveQueriesNotSupported.into()),
+		Command::Prepare {
+			..
+		}
+		| Command::ExecutePrepared {
+			..
+		}
+		| Command::Unprepare {
+			..
+		} => Err(Error::PreparedQueriesNotSupported.into()),
+		Command::SelectStream {
+			what,
+			rows,
+		} => {
+			// HTTP requests can not be streamed, so the rows are sent once they have all been fetched
+			let req = Command::Select {
+				what,
+			}
+			.into_router_request(None, None, req.transaction)
+			.unwrap();
+			let values = match send_request(req, base_url, client, headers, auth).await? {
+				DbResponse::Other(CoreValue::Array(array)) => array.0,
+				DbResponse::Other(CoreValue::None) => vec![],
+				DbResponse::Other(value) => vec![value],
+				DbResponse::Query(..) => unreachable!(),
+			};
+			let future = async move {
+				for value in values {
+					if rows.send(Ok(value)).await.is_err() {
+						break;
+					}
+				}
+			};
+
+			#[cfg(not(target_arch = "wasm32"))]
+			tokio::spawn(future);
+
+			#[cfg(target_arch = "wasm32")]
+			spawn_local(future);
+
+			Ok(DbResponse::Other(CoreValue::None))
+		}
+		// Each HTTP request runs on its own, so there is no running request to cancel
+		Command::CancelRequest {
+			..
+		} => Ok(DbResponse::Other(CoreValue::None)),
 		cmd => {
 			let needs_flatten = cmd.needs_flatten();
-			let req = cmd.into_router_request(None).unwrap();
+			let req = cmd.into_router_request(None, None, req.transaction).unwrap();
 			let mut res = send_request(req, base_url, client, headers, auth).await?;
 			if needs_flatten {
 				res = flatten_dbresponse_array(res);
@@ -12,15 +12,14 @@ use crate::api::Result;
 use crate::api::Surreal;
 use crate::opt::WaitFor;
 use channel::Receiver;
-use indexmap::IndexMap;
-use reqwest::header::HeaderMap;
 use reqwest::ClientBuilder;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::AtomicI64;
 use std::sync::Arc;
 use std::sync::OnceLock;
 use tokio::sync::watch;
 use url::Url;
+use uuid::Uuid;
 
 impl crate::api::Connection for Client {}
 
@@ -62,7 +61,9 @@ impl Connection for Client {
 				Arc::new(OnceLock::with_value(Router {
 					features,
 					sender: route_tx,
-					last_id: AtomicI64::new(0),
+					last_id: Arc::new(AtomicI64::new(0)),
+					session: None,
+					transaction: None,
 				})),
 				Arc::new(watch::channel(Some(WaitFor::Connection))),
 			))
@@ -71,14 +72,11 @@ impl Connection for Client {
 }
 
 pub(crate) async fn run_router(base_url: Url, client: reqwest::Client, route_rx: Receiver<Route>) {
-	let mut headers = HeaderMap::new();
-	let mut vars = IndexMap::new();
-	let mut auth = None;
+	// Each session has its own headers, variables and credentials
+	let mut sessions: HashMap<Option<Uuid>, super::SessionState> = HashMap::new();
 
 	while let Ok(route) = route_rx.recv().await {
-		let result =
-			super::router(route.request, &base_url, &client, &mut headers, &mut vars, &mut auth)
-				.await;
+		let result = super::route(route.request, &base_url, &client, &mut sessions).await;
 		let _ = route.response.send(result).await;
 	}
 }
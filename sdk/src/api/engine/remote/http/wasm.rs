@@ -9,15 +9,14 @@ use crate::api::Result;
 use crate::api::Surreal;
 use crate::opt::WaitFor;
 use channel::{Receiver, Sender};
-use indexmap::IndexMap;
-use reqwest::header::HeaderMap;
 use reqwest::ClientBuilder;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::AtomicI64;
 use std::sync::Arc;
 use std::sync::OnceLock;
 use tokio::sync::watch;
 use url::Url;
+use uuid::Uuid;
 use wasm_bindgen_futures::spawn_local;
 
 impl crate::api::Connection for Client {}
@@ -40,7 +39,9 @@ impl Connection for Client {
 				Arc::new(OnceLock::with_value(Router {
 					features: HashSet::new(),
 					sender: route_tx,
-					last_id: AtomicI64::new(0),
+					last_id: Arc::new(AtomicI64::new(0)),
+					session: None,
+					transaction: None,
 				})),
 				Arc::new(watch::channel(Some(WaitFor::Connection))),
 			))
@@ -75,20 +76,11 @@ pub(crate) async fn run_router(
 		}
 	};
 
-	let mut headers = HeaderMap::new();
-	let mut vars = IndexMap::new();
-	let mut auth = None;
+	// Each session has its own headers, variables and credentials
+	let mut sessions: HashMap<Option<Uuid>, super::SessionState> = HashMap::new();
 
 	while let Ok(route) = route_rx.recv().await {
-		match super::router(route.request, &base_url, &client, &mut headers, &mut vars, &mut auth)
-			.await
-		{
-			Ok(value) => {
-				let _ = route.response.send(Ok(value)).await;
-			}
-			Err(error) => {
-				let _ = route.response.send(Err(error)).await;
-			}
-		}
+		let result = super::route(route.request, &base_url, &client, &mut sessions).await;
+		let _ = route.response.send(result).await;
 	}
 }
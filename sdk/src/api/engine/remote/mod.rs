@@ -91,6 +91,7 @@ pub(crate) enum Data {
 	Other(CoreValue),
 	Query(Vec<dbs::QueryMethodResponse>),
 	Live(dbs::Notification),
+	Rows(dbs::Batch),
 }
 
 type ServerResult = std::result::Result<Data, Failure>;
@@ -145,8 +146,8 @@ impl DbResponse {
 					..api::Response::new()
 				}))
 			}
-			// Live notifications don't call this method
-			Data::Live(..) => unreachable!(),
+			// Live notifications and streamed rows don't call this method
+			Data::Live(..) | Data::Rows(..) => unreachable!(),
 		}
 	}
 }
@@ -28,15 +28,21 @@ const REVISION_HEADER: &str = "revision";
 enum RequestEffect {
 	/// Completing this request sets a variable to a give value.
 	Set {
+		session: Option<Uuid>,
 		key: String,
 		value: CoreValue,
 	},
 	/// Completing this request sets a variable to a give value.
 	Clear {
+		session: Option<Uuid>,
 		key: String,
 	},
 	/// Insert requests repsonses need to be flattened in an array.
 	Insert,
+	/// Streamed requests forward their rows until the request completes.
+	Stream {
+		rows: Sender<Result<CoreValue>>,
+	},
 	/// No effect
 	None,
 }
@@ -58,10 +64,10 @@ struct PendingRequest {
 }
 
 struct RouterState<Sink, Stream> {
-	/// Vars currently set by the set method,
-	vars: IndexMap<String, CoreValue>,
-	/// Messages which aught to be replayed on a reconnect.
-	replay: IndexMap<ReplayMethod, Command>,
+	/// Vars currently set by the set method, for each session
+	vars: IndexMap<(Option<Uuid>, String), CoreValue>,
+	/// Messages which aught to be replayed on a reconnect, for each session
+	replay: IndexMap<(Option<Uuid>, ReplayMethod), Command>,
 	/// Pending live queries
 	live_queries: HashMap<Uuid, channel::Sender<Notification<CoreValue>>>,
 	/// Send requests which are still awaiting an awnser.
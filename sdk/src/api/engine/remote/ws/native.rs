@@ -134,7 +134,9 @@ impl Connection for Client {
 				Arc::new(OnceLock::with_value(Router {
 					features,
 					sender: route_tx,
-					last_id: AtomicI64::new(0),
+					last_id: Arc::new(AtomicI64::new(0)),
+					session: None,
+					transaction: None,
 				})),
 				Arc::new(watch::channel(Some(WaitFor::Connection))),
 			))
@@ -153,6 +155,8 @@ async fn router_handle_route(
 	let RequestData {
 		id,
 		command,
+		session,
+		transaction,
 	} = request;
 
 	// We probably shouldn't be sending duplicate id requests.
@@ -173,6 +177,7 @@ async fn router_handle_route(
 			ref value,
 		} => {
 			effect = RequestEffect::Set {
+				session,
 				key: key.clone(),
 				value: value.clone(),
 			};
@@ -181,6 +186,7 @@ async fn router_handle_route(
 			ref key,
 		} => {
 			effect = RequestEffect::Clear {
+				session,
 				key: key.clone(),
 			};
 		}
@@ -189,6 +195,24 @@ async fn router_handle_route(
 		} => {
 			effect = RequestEffect::Insert;
 		}
+		Command::SelectStream {
+			ref rows,
+			..
+		} => {
+			// Rows are forwarded without blocking the router, even when they are not being consumed
+			let (inbox, outbox) = channel::unbounded();
+			let rows = rows.clone();
+			tokio::spawn(async move {
+				while let Ok(row) = outbox.recv().await {
+					if rows.send(row).await.is_err() {
+						break;
+					}
+				}
+			});
+			effect = RequestEffect::Stream {
+				rows: inbox,
+			};
+		}
 		Command::SubscribeLive {
 			ref uuid,
 			ref notification_sender,
@@ -208,33 +232,43 @@ async fn router_handle_route(
 		Command::Use {
 			..
 		} => {
-			state.replay.insert(ReplayMethod::Use, command.clone());
+			state.replay.insert((session, ReplayMethod::Use), command.clone());
 		}
 		Command::Signup {
 			..
 		} => {
-			state.replay.insert(ReplayMethod::Signup, command.clone());
+			state.replay.insert((session, ReplayMethod::Signup), command.clone());
 		}
 		Command::Signin {
 			..
 		} => {
-			state.replay.insert(ReplayMethod::Signin, command.clone());
+			state.replay.insert((session, ReplayMethod::Signin), command.clone());
 		}
 		Command::Invalidate {
 			..
 		} => {
-			state.replay.insert(ReplayMethod::Invalidate, command.clone());
+			// Invalidating the main session also closes all other sessions
+			if session.is_none() {
+				state.replay.retain(|(session, _), _| session.is_none());
+				state.vars.retain(|(session, _), _| session.is_none());
+			}
+			state.replay.insert((session, ReplayMethod::Invalidate), command.clone());
+		}
+		Command::CloseSession => {
+			// Closed sessions are not restored when reconnecting
+			state.replay.retain(|(id, _), _| *id != session);
+			state.vars.retain(|(id, _), _| *id != session);
 		}
 		Command::Authenticate {
 			..
 		} => {
-			state.replay.insert(ReplayMethod::Authenticate, command.clone());
+			state.replay.insert((session, ReplayMethod::Authenticate), command.clone());
 		}
 		_ => {}
 	}
 
 	let message = {
-		let Some(request) = command.into_router_request(Some(id)) else {
+		let Some(request) = command.into_router_request(Some(id), session, transaction) else {
 			let _ = response.send(Err(Error::BackupsNotSupported.into())).await;
 			return HandleResult::Ok;
 		};
@@ -246,6 +280,15 @@ async fn router_handle_route(
 	match state.sink.send(message).await {
 		Ok(_) => {
 			state.last_activity = Instant::now();
+			// Streamed requests are ready to be consumed as soon as they are sent
+			if let RequestEffect::Stream {
+				..
+			} = effect
+			{
+				if response.clone().send(Ok(DbResponse::Other(CoreValue::None))).await.is_err() {
+					trace!("Receiver dropped");
+				}
+			}
 			entry.insert(PendingRequest {
 				effect,
 				response_channel: response,
@@ -276,11 +319,39 @@ async fn router_handle_response(
 					// If `id` is set this is a normal response
 					Some(id) => {
 						if let Ok(id) = id.coerce_to_i64() {
+							let result = match response.result {
+								// Streamed rows arrive before the response of their request
+								Ok(Data::Rows(batch)) => {
+									if let Some(PendingRequest {
+										effect: RequestEffect::Stream {
+											rows,
+										},
+										..
+									}) = state.pending_requests.get(&id)
+									{
+										for row in batch.rows {
+											let _ = rows.send(Ok(row)).await;
+										}
+									}
+									return HandleResult::Ok;
+								}
+								result => result,
+							};
 							if let Some(pending) = state.pending_requests.remove(&id) {
-								let resp = match DbResponse::from_server_result(response.result) {
+								let resp = match DbResponse::from_server_result(result) {
 									Ok(x) => x,
 									Err(e) => {
-										let _ = pending.response_channel.send(Err(e)).await;
+										match pending.effect {
+											// Streamed requests have already been responded to
+											RequestEffect::Stream {
+												rows,
+											} => {
+												let _ = rows.send(Err(e)).await;
+											}
+											_ => {
+												let _ = pending.response_channel.send(Err(e)).await;
+											}
+										}
 										return HandleResult::Ok;
 									}
 								};
@@ -309,16 +380,21 @@ async fn router_handle_response(
 										}
 									}
 									RequestEffect::Set {
+										session,
 										key,
 										value,
 									} => {
-										state.vars.insert(key, value);
+										state.vars.insert((session, key), value);
 									}
 									RequestEffect::Clear {
+										session,
 										key,
 									} => {
-										state.vars.shift_remove(&key);
+										state.vars.shift_remove(&(session, key));
 									}
+									RequestEffect::Stream {
+										..
+									} => return HandleResult::Ok,
 								}
 								let _res = pending.response_channel.send(Ok(resp)).await;
 							} else {
@@ -346,7 +422,7 @@ async fn router_handle_response(
 											let request = Command::Kill {
 												uuid: live_query_id.0,
 											}
-											.into_router_request(None)
+											.into_router_request(None, None, None)
 											.unwrap();
 											let value =
 												serialize(&request, endpoint.supports_revision)
@@ -411,10 +487,10 @@ async fn router_reconnect(
 				let (new_sink, new_stream) = s.split();
 				state.sink = new_sink;
 				state.stream = new_stream;
-				for commands in state.replay.values() {
+				for ((session, _), commands) in &state.replay {
 					let request = commands
 						.clone()
-						.into_router_request(None)
+						.into_router_request(None, *session, None)
 						.expect("replay commands should always convert to route requests");
 
 					let message = serialize(&request, endpoint.supports_revision).unwrap();
@@ -425,12 +501,12 @@ async fn router_reconnect(
 						continue;
 					}
 				}
-				for (key, value) in &state.vars {
+				for ((session, key), value) in &state.vars {
 					let request = Command::Set {
 						key: key.as_str().into(),
 						value: value.clone(),
 					}
-					.into_router_request(None)
+					.into_router_request(None, *session, None)
 					.unwrap();
 					trace!("Request {:?}", request);
 					let payload = serialize(&request, endpoint.supports_revision).unwrap();
@@ -460,7 +536,7 @@ pub(crate) async fn run_router(
 	route_rx: Receiver<Route>,
 ) {
 	let ping = {
-		let request = Command::Health.into_router_request(None).unwrap();
+		let request = Command::Health.into_router_request(None, None, None).unwrap();
 		let value = serialize(&request, endpoint.supports_revision).unwrap();
 		Message::Binary(value)
 	};
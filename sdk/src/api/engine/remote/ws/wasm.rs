@@ -78,7 +78,9 @@ impl Connection for Client {
 				Arc::new(OnceLock::with_value(Router {
 					features,
 					sender: route_tx,
-					last_id: AtomicI64::new(0),
+					last_id: Arc::new(AtomicI64::new(0)),
+					session: None,
+					transaction: None,
 				})),
 				Arc::new(watch::channel(Some(WaitFor::Connection))),
 			))
@@ -97,6 +99,8 @@ async fn router_handle_request(
 	let RequestData {
 		id,
 		command,
+		session,
+		transaction,
 	} = request;
 
 	let entry = state.pending_requests.entry(id);
@@ -117,6 +121,7 @@ async fn router_handle_request(
 			ref value,
 		} => {
 			effect = RequestEffect::Set {
+				session,
 				key: key.clone(),
 				value: value.clone(),
 			};
@@ -125,6 +130,7 @@ async fn router_handle_request(
 			ref key,
 		} => {
 			effect = RequestEffect::Clear {
+				session,
 				key: key.clone(),
 			};
 		}
@@ -133,6 +139,24 @@ async fn router_handle_request(
 		} => {
 			effect = RequestEffect::Insert;
 		}
+		Command::SelectStream {
+			ref rows,
+			..
+		} => {
+			// Rows are forwarded without blocking the router, even when they are not being consumed
+			let (inbox, outbox) = channel::unbounded();
+			let rows = rows.clone();
+			spawn_local(async move {
+				while let Ok(row) = outbox.recv().await {
+					if rows.send(row).await.is_err() {
+						break;
+					}
+				}
+			});
+			effect = RequestEffect::Stream {
+				rows: inbox,
+			};
+		}
 		Command::SubscribeLive {
 			ref uuid,
 			ref notification_sender,
@@ -152,33 +176,43 @@ async fn router_handle_request(
 		Command::Use {
 			..
 		} => {
-			state.replay.insert(ReplayMethod::Use, command.clone());
+			state.replay.insert((session, ReplayMethod::Use), command.clone());
 		}
 		Command::Signup {
 			..
 		} => {
-			state.replay.insert(ReplayMethod::Signup, command.clone());
+			state.replay.insert((session, ReplayMethod::Signup), command.clone());
 		}
 		Command::Signin {
 			..
 		} => {
-			state.replay.insert(ReplayMethod::Signin, command.clone());
+			state.replay.insert((session, ReplayMethod::Signin), command.clone());
 		}
 		Command::Invalidate {
 			..
 		} => {
-			state.replay.insert(ReplayMethod::Invalidate, command.clone());
+			// Invalidating the main session also closes all other sessions
+			if session.is_none() {
+				state.replay.retain(|(session, _), _| session.is_none());
+				state.vars.retain(|(session, _), _| session.is_none());
+			}
+			state.replay.insert((session, ReplayMethod::Invalidate), command.clone());
+		}
+		Command::CloseSession => {
+			// Closed sessions are not restored when reconnecting
+			state.replay.retain(|(id, _), _| *id != session);
+			state.vars.retain(|(id, _), _| *id != session);
 		}
 		Command::Authenticate {
 			..
 		} => {
-			state.replay.insert(ReplayMethod::Authenticate, command.clone());
+			state.replay.insert((session, ReplayMethod::Authenticate), command.clone());
 		}
 		_ => {}
 	}
 
 	let message = {
-		let Some(req) = command.into_router_request(Some(id)) else {
+		let Some(req) = command.into_router_request(Some(id), session, transaction) else {
 			let _ = response.send(Err(Error::BackupsNotSupported.into())).await;
 			return HandleResult::Ok;
 		};
@@ -190,6 +224,15 @@ async fn router_handle_request(
 	match state.sink.send(message).await {
 		Ok(..) => {
 			state.last_activity = Instant::now();
+			// Streamed requests are ready to be consumed as soon as they are sent
+			if let RequestEffect::Stream {
+				..
+			} = effect
+			{
+				if response.clone().send(Ok(DbResponse::Other(CoreValue::None))).await.is_err() {
+					trace!("Receiver dropped");
+				}
+			}
 			entry.insert(PendingRequest {
 				effect,
 				response_channel: response,
@@ -220,6 +263,21 @@ async fn router_handle_response(
 					// If `id` is set this is a normal response
 					Some(id) => {
 						if let Ok(id) = id.coerce_to_i64() {
+							// Streamed rows arrive before the response of their request
+							if let Ok(Data::Rows(batch)) = response.result {
+								if let Some(PendingRequest {
+									effect: RequestEffect::Stream {
+										rows,
+									},
+									..
+								}) = state.pending_requests.get(&id)
+								{
+									for row in batch.rows {
+										let _ = rows.send(Ok(row)).await;
+									}
+								}
+								return HandleResult::Ok;
+							}
 							// We can only route responses with IDs
 							if let Some(pending) = state.pending_requests.remove(&id) {
 								match pending.effect {
@@ -250,15 +308,28 @@ async fn router_handle_response(
 										}
 									}
 									RequestEffect::Set {
+										session,
 										key,
 										value,
 									} => {
-										state.vars.insert(key, value);
+										state.vars.insert((session, key), value);
 									}
 									RequestEffect::Clear {
+										session,
 										key,
 									} => {
-										state.vars.shift_remove(&key);
+										state.vars.shift_remove(&(session, key));
+									}
+									RequestEffect::Stream {
+										rows,
+									} => {
+										// Streamed requests have already been responded to
+										if let Err(error) =
+											DbResponse::from_server_result(response.result)
+										{
+											let _ = rows.send(Err(error)).await;
+										}
+										return HandleResult::Ok;
 									}
 								}
 								let _res = pending
@@ -289,7 +360,7 @@ async fn router_handle_response(
 										let request = Command::Kill {
 											uuid: live_query_id.0,
 										}
-										.into_router_request(None);
+										.into_router_request(None, None, None);
 										let value = serialize(&request, endpoint.supports_revision)
 											.unwrap();
 										Message::Binary(value)
@@ -371,8 +442,8 @@ async fn router_reconnect(
 						}
 					}
 				};
-				for (_, message) in &state.replay {
-					let message = message.clone().into_router_request(None);
+				for ((session, _), message) in &state.replay {
+					let message = message.clone().into_router_request(None, *session, None);
 					let message = serialize(&message, endpoint.supports_revision).unwrap();
 
 					if let Err(error) = state.sink.send(Message::Binary(message)).await {
@@ -381,12 +452,12 @@ async fn router_reconnect(
 						continue;
 					}
 				}
-				for (key, value) in &state.vars {
+				for ((session, key), value) in &state.vars {
 					let request = Command::Set {
 						key: key.as_str().into(),
 						value: value.clone(),
 					}
-					.into_router_request(None);
+					.into_router_request(None, *session, None);
 					trace!("Request {:?}", request);
 					let serialize = serialize(&request, false).unwrap();
 					if let Err(error) = state.sink.send(Message::Binary(serialize)).await {
@@ -173,6 +173,14 @@ pub enum Error {
 	#[error("The protocol or storage engine does not support live queries on this architecture")]
 	LiveQueriesNotSupported,
 
+	/// Tried to close the main session of a connection
+	#[error("Only sessions created with `new_session` can be closed")]
+	SessionNotClosable,
+
+	/// The protocol does not keep prepared queries between requests
+	#[error("The protocol does not support prepared queries")]
+	PreparedQueriesNotSupported,
+
 	/// Tried to use a range query on an object
 	#[error("Live queries on objects not supported")]
 	LiveOnObject,
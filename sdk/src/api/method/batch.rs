@@ -0,0 +1,225 @@
+use crate::api::conn::Command;
+use crate::api::err::Error;
+use crate::api::method::BoxFuture;
+use crate::api::opt::Resource;
+use crate::api::opt::Table;
+use crate::api::Connection;
+use crate::api::Result;
+use crate::method::OnceLockExt;
+use crate::Surreal;
+use indexmap::IndexMap;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::future::IntoFuture;
+use surrealdb_core::sql::{
+	from_value as from_core_value, to_value as to_core_value, Array as CoreArray,
+	Object as CoreObject, Value as CoreValue,
+};
+
+/// A batch future
+///
+/// The calls are sent to the database in a single request, and are run in the order
+/// they were added to the batch.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Batch<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) calls: Result<Vec<CoreValue>>,
+	pub(super) atomic: bool,
+}
+
+impl<C> Batch<'_, C>
+where
+	C: Connection,
+{
+	fn call(mut self, method: &str, params: Result<Vec<CoreValue>>) -> Self {
+		match (&mut self.calls, params) {
+			(Ok(calls), Ok(params)) => {
+				let mut call = CoreObject::default();
+				call.insert("method".to_owned(), CoreValue::from(method));
+				call.insert("params".to_owned(), CoreValue::from(params));
+				calls.push(CoreValue::from(call));
+			}
+			(Ok(_), Err(error)) => self.calls = Err(error),
+			(Err(_), _) => {}
+		}
+		self
+	}
+
+	/// Selects all records in a table, or a specific record
+	pub fn select(self, resource: impl Into<Resource>) -> Self {
+		let params = vec![resource.into().into_core_value()];
+		self.call("select", Ok(params))
+	}
+
+	/// Creates a record in the database with the specified content
+	pub fn create(self, resource: impl Into<Resource>, data: impl Serialize + 'static) -> Self {
+		let params = to_core_value(data).map(|data| vec![resource.into().into_core_value(), data]);
+		self.call("create", params.map_err(Into::into))
+	}
+
+	/// Inserts one or more records into a table
+	pub fn insert(self, table: impl Into<String>, data: impl Serialize + 'static) -> Self {
+		let params = to_core_value(data).map(|data| vec![Table(table).into_core().into(), data]);
+		self.call("insert", params.map_err(Into::into))
+	}
+
+	/// Creates or replaces the content of all records in a table, or a specific record
+	pub fn upsert(self, resource: impl Into<Resource>, data: impl Serialize + 'static) -> Self {
+		let params = to_core_value(data).map(|data| vec![resource.into().into_core_value(), data]);
+		self.call("upsert", params.map_err(Into::into))
+	}
+
+	/// Replaces the content of all records in a table, or a specific record
+	pub fn update(self, resource: impl Into<Resource>, data: impl Serialize + 'static) -> Self {
+		let params = to_core_value(data).map(|data| vec![resource.into().into_core_value(), data]);
+		self.call("update", params.map_err(Into::into))
+	}
+
+	/// Merges data into all records in a table, or a specific record
+	pub fn merge(self, resource: impl Into<Resource>, data: impl Serialize + 'static) -> Self {
+		let params = to_core_value(data).map(|data| vec![resource.into().into_core_value(), data]);
+		self.call("merge", params.map_err(Into::into))
+	}
+
+	/// Deletes all records in a table, or a specific record
+	pub fn delete(self, resource: impl Into<Resource>) -> Self {
+		let params = vec![resource.into().into_core_value()];
+		self.call("delete", Ok(params))
+	}
+
+	/// Runs a SurrealQL query
+	pub fn query(self, query: impl Into<String>) -> Self {
+		let params = vec![CoreValue::from(query.into())];
+		self.call("query", Ok(params))
+	}
+
+	/// Runs the calls in a single transaction
+	///
+	/// The batch stops at the first call which fails, and none of its changes are kept.
+	/// A query fails when any of its statements fail.
+	pub fn atomic(mut self) -> Self {
+		self.atomic = true;
+		self
+	}
+
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> Batch<'static, C> {
+		Batch {
+			client: Cow::Owned(self.client.into_owned()),
+			calls: self.calls,
+			atomic: self.atomic,
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for Batch<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<BatchResponse>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let calls = self.calls?;
+			let router = self.client.router.extract()?;
+			let rx = router
+				.send(Command::Batch {
+					calls: CoreArray::from(calls),
+					atomic: self.atomic,
+				})
+				.await?;
+			let results = match router.recv(rx).await? {
+				CoreValue::Array(results) => results,
+				value => {
+					return Err(Error::FromValue {
+						value: crate::Value::from_inner(value),
+						error: "expected the database to return a list of results".to_owned(),
+					}
+					.into())
+				}
+			};
+			Ok(BatchResponse {
+				results: results.into_iter().map(call_result).enumerate().collect(),
+			})
+		})
+	}
+}
+
+/// Converts an entry of the batch result into the result of a call
+fn call_result(value: CoreValue) -> Result<CoreValue> {
+	let CoreValue::Object(mut object) = value else {
+		return Ok(value);
+	};
+	match object.remove("error") {
+		Some(CoreValue::Object(mut error)) => {
+			let message = match error.remove("message") {
+				Some(message) => message.to_raw_string(),
+				None => "The call failed".to_owned(),
+			};
+			Err(Error::Query(message).into())
+		}
+		_ => Ok(object.remove("result").unwrap_or_default()),
+	}
+}
+
+/// The results of the calls in a batch
+///
+/// When the batch is atomic, the calls which follow a failed call are not run, and
+/// have no result.
+#[derive(Debug)]
+pub struct BatchResponse {
+	results: IndexMap<usize, Result<CoreValue>>,
+}
+
+impl BatchResponse {
+	/// Takes and deserializes the result of a call, by its position in the batch
+	///
+	/// ```no_run
+	/// # #[derive(serde::Deserialize)]
+	/// # struct Person;
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let mut response = db.batch().select("person").select(("person", "tobie")).await?;
+	/// let people: Vec<Person> = response.take(0)?;
+	/// let tobie: Option<Person> = response.take(1)?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn take<R>(&mut self, index: usize) -> Result<R>
+	where
+		R: DeserializeOwned,
+	{
+		match self.results.swap_remove(&index) {
+			Some(Ok(value)) => from_core_value(value).map_err(Into::into),
+			Some(Err(error)) => Err(error),
+			None => from_core_value(CoreValue::None).map_err(Into::into),
+		}
+	}
+
+	/// Takes the errors of the calls which failed, keyed by their position in the batch
+	pub fn take_errors(&mut self) -> HashMap<usize, crate::Error> {
+		let mut keys = Vec::new();
+		for (key, result) in &self.results {
+			if result.is_err() {
+				keys.push(*key);
+			}
+		}
+		let mut errors = HashMap::with_capacity(keys.len());
+		for key in keys {
+			if let Some(Err(error)) = self.results.swap_remove(&key) {
+				errors.insert(key, error);
+			}
+		}
+		errors
+	}
+
+	/// The number of calls which have a result that has not been taken
+	pub fn num_results(&self) -> usize {
+		self.results.len()
+	}
+}
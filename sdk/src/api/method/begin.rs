@@ -1,12 +1,19 @@
+use crate::api::conn::Command;
+use crate::api::err::Error;
 use crate::api::method::BoxFuture;
 use crate::api::method::Cancel;
 use crate::api::method::Commit;
 use crate::api::Connection;
+use crate::api::OnceLockExt;
 use crate::api::Result;
 use crate::api::Surreal;
+use crate::Value;
 use std::future::IntoFuture;
 use std::ops::Deref;
-use surrealdb_core::sql::statements::BeginStatement;
+use std::sync::Arc;
+use std::sync::OnceLock;
+use surrealdb_core::sql::Value as CoreValue;
+use uuid::Uuid;
 
 /// A beginning of a transaction
 #[derive(Debug)]
@@ -24,29 +31,73 @@ where
 
 	fn into_future(self) -> Self::IntoFuture {
 		Box::pin(async move {
-			self.client.query(BeginStatement::default()).await?;
+			let router = self.client.router.extract()?;
+			let rx = router.send(Command::Begin).await?;
+			let id = match router.recv(rx).await? {
+				CoreValue::Uuid(id) => id.0,
+				value => {
+					return Err(Error::FromValue {
+						value: Value::from_inner(value),
+						error: "expected the database to return a transaction id".to_owned(),
+					}
+					.into())
+				}
+			};
+			// Requests sent through this client run within the transaction
+			let inner = Surreal::new_from_router_waiter(
+				Arc::new(OnceLock::with_value(router.with_transaction(id))),
+				self.client.waiter.clone(),
+			);
 			Ok(Transaction {
+				id,
 				client: self.client,
+				inner,
 			})
 		})
 	}
 }
 
 /// An ongoing transaction
+///
+/// Methods called on a transaction, such as `select`, `create` or `query`, run within the
+/// transaction, and their changes are only visible to other clients once it is committed.
+///
+/// ```no_run
+/// # #[derive(serde::Deserialize)]
+/// # struct Account;
+/// # #[tokio::main]
+/// # async fn main() -> surrealdb::Result<()> {
+/// # let db = surrealdb::engine::any::connect("mem://").await?;
+/// let txn = db.transaction().await?;
+/// txn.query("UPDATE account:one SET balance -= 100").await?;
+/// txn.query("UPDATE account:two SET balance += 100").await?;
+/// let accounts: Vec<Account> = txn.select("account").await?;
+/// txn.commit().await?;
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Debug)]
 #[must_use = "transactions must be committed or cancelled to complete them"]
 pub struct Transaction<C: Connection> {
+	id: Uuid,
 	client: Surreal<C>,
+	inner: Surreal<C>,
 }
 
 impl<C> Transaction<C>
 where
 	C: Connection,
 {
+	/// The id of the transaction
+	pub fn id(&self) -> Uuid {
+		self.id
+	}
+
 	/// Creates a commit future
 	pub fn commit(self) -> Commit<C> {
 		Commit {
 			client: self.client,
+			txn: self.id,
 		}
 	}
 
@@ -54,6 +105,7 @@ where
 	pub fn cancel(self) -> Cancel<C> {
 		Cancel {
 			client: self.client,
+			txn: self.id,
 		}
 	}
 }
@@ -65,6 +117,6 @@ where
 	type Target = Surreal<C>;
 
 	fn deref(&self) -> &Self::Target {
-		&self.client
+		&self.inner
 	}
 }
@@ -0,0 +1,47 @@
+use crate::api::conn::Command;
+use crate::api::err::Error;
+use crate::api::method::BoxFuture;
+use crate::api::Connection;
+use crate::api::Result;
+use crate::method::OnceLockExt;
+use crate::Surreal;
+use std::borrow::Cow;
+use std::future::IntoFuture;
+
+/// A session close future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct CloseSession<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+}
+
+impl<C> CloseSession<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> CloseSession<'static, C> {
+		CloseSession {
+			client: Cow::Owned(self.client.into_owned()),
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for CloseSession<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<()>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.router.extract()?;
+			// Only sessions created with `new_session` can be closed
+			if router.session.is_none() {
+				return Err(Error::SessionNotClosable.into());
+			}
+			router.execute_unit(Command::CloseSession).await
+		})
+	}
+}
@@ -20,13 +20,16 @@ use std::sync::Arc;
 use std::sync::OnceLock;
 use std::time::Duration;
 use surrealdb_core::sql::to_value as to_core_value;
+use uuid::Uuid;
 
 pub(crate) mod live;
 pub(crate) mod query;
 
 mod authenticate;
+mod batch;
 mod begin;
 mod cancel;
+mod close_session;
 mod commit;
 mod content;
 mod create;
@@ -39,6 +42,8 @@ mod insert_relation;
 mod invalidate;
 mod merge;
 mod patch;
+mod prepare;
+mod rows;
 mod run;
 mod select;
 mod set;
@@ -55,12 +60,14 @@ mod version;
 mod tests;
 
 pub use authenticate::Authenticate;
+pub use batch::{Batch, BatchResponse};
 #[doc(hidden)] // Not supported yet
 pub use begin::Begin;
 #[doc(hidden)] // Not supported yet
 pub use begin::Transaction;
 #[doc(hidden)] // Not supported yet
 pub use cancel::Cancel;
+pub use close_session::CloseSession;
 #[doc(hidden)] // Not supported yet
 pub use commit::Commit;
 pub use content::Content;
@@ -75,8 +82,10 @@ pub use invalidate::Invalidate;
 pub use live::Stream;
 pub use merge::Merge;
 pub use patch::Patch;
+pub use prepare::{ExecutePrepared, Prepare, Prepared, Unprepare};
 pub use query::Query;
 pub use query::QueryStream;
+pub use rows::RowStream;
 pub use run::IntoFn;
 pub use run::Run;
 pub use select::Select;
@@ -111,6 +120,9 @@ pub struct Model;
 /// Live query marker type
 pub struct Live;
 
+/// Streamed rows marker type
+pub struct Rows;
+
 /// Responses returned with statistics
 #[derive(Debug)]
 pub struct WithStats<T>(pub T);
@@ -249,13 +261,93 @@ where
 		}
 	}
 
-	#[doc(hidden)] // Not supported yet
+	/// Begins a transaction which spans several requests
+	///
+	/// Transactions which are neither committed nor cancelled are cancelled by the
+	/// database once they have not been used for a while.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let txn = db.clone().transaction().await?;
+	/// txn.query("CREATE person:tobie").await?;
+	/// txn.commit().await?;
+	/// # Ok(())
+	/// # }
+	/// ```
 	pub fn transaction(self) -> Begin<C> {
 		Begin {
 			client: self,
 		}
 	}
 
+	/// Creates a new session on the same connection
+	///
+	/// Each session has its own authentication, selected namespace and database, parameters
+	/// and live queries, so many users can be served over a single connection. Sessions are
+	/// cheap to create and to clone, as they share the connection of this client.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use surrealdb::opt::auth::Record;
+	///
+	/// # #[derive(serde::Serialize)]
+	/// # struct Credentials<'a> { email: &'a str, pass: &'a str }
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("ws://localhost:8000").await?;
+	/// let session = db.new_session()?;
+	/// session.use_ns("namespace").use_db("database").await?;
+	/// session
+	///     .signin(Record {
+	///         namespace: "namespace",
+	///         database: "database",
+	///         access: "user",
+	///         params: Credentials {
+	///             email: "info@surrealdb.com",
+	///             pass: "123456",
+	///         },
+	///     })
+	///     .await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn new_session(&self) -> crate::api::Result<Self> {
+		let router = self.router.extract()?.with_session(Uuid::new_v4());
+		Ok(Surreal::new_from_router_waiter(
+			Arc::new(OnceLock::with_value(router)),
+			self.waiter.clone(),
+		))
+	}
+
+	/// Closes a session created with [`new_session`](Self::new_session)
+	///
+	/// The live queries which were started by the session are killed, and its authentication,
+	/// parameters and prepared queries are dropped. Sessions are also closed when the main
+	/// session of the connection is invalidated.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("ws://localhost:8000").await?;
+	/// let session = db.new_session()?;
+	/// session.use_ns("namespace").use_db("database").await?;
+	/// session.close_session().await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn close_session(&self) -> CloseSession<C> {
+		CloseSession {
+			client: Cow::Borrowed(self),
+		}
+	}
+
 	/// Switch to a specific namespace
 	///
 	/// # Examples
@@ -655,6 +747,59 @@ where
 		}
 	}
 
+	/// Prepares a query, so that it is parsed once by the database and can be executed many times
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let query = db.prepare("CREATE person SET name = $name").await?;
+	/// query.execute().bind(("name", "Tobie")).await?;
+	/// query.execute().bind(("name", "Jaime")).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn prepare(&self, query: impl Into<String>) -> Prepare<C> {
+		Prepare {
+			client: Cow::Borrowed(self),
+			query: query.into(),
+		}
+	}
+
+	/// Sends several calls to the database in a single request
+	///
+	/// The calls are run in order, and each call has its own result or error. An atomic
+	/// batch runs the calls in a single transaction, which is cancelled if any call fails.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[derive(serde::Deserialize)]
+	/// # struct Person;
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let mut response = db
+	///     .batch()
+	///     .create(("person", "tobie"), serde_json::json!({ "name": "Tobie" }))
+	///     .update(("person", "jaime"), serde_json::json!({ "name": "Jaime" }))
+	///     .select("person")
+	///     .atomic()
+	///     .await?;
+	/// let people: Vec<Person> = response.take(2)?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn batch(&self) -> Batch<C> {
+		Batch {
+			client: Cow::Borrowed(self),
+			calls: Ok(Vec::new()),
+			atomic: false,
+		}
+	}
+
 	/// Selects all records in a table, or a specific record
 	///
 	/// # Examples
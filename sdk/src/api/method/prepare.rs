@@ -0,0 +1,222 @@
+use crate::api::conn::Command;
+use crate::api::err::Error;
+use crate::api::method::query::append_bindings;
+use crate::api::method::BoxFuture;
+use crate::api::Connection;
+use crate::api::Response;
+use crate::api::Result;
+use crate::method::OnceLockExt;
+use crate::Surreal;
+use crate::Value;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::future::IntoFuture;
+use surrealdb_core::sql::{Object as CoreObject, Value as CoreValue};
+use uuid::Uuid;
+
+/// A prepare future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Prepare<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) query: String,
+}
+
+impl<C> Prepare<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> Prepare<'static, C> {
+		Prepare {
+			client: Cow::Owned(self.client.into_owned()),
+			query: self.query,
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for Prepare<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<Prepared<Client>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.router.extract()?;
+			let rx = router
+				.send(Command::Prepare {
+					query: self.query,
+				})
+				.await?;
+			let id = match router.recv(rx).await? {
+				CoreValue::Uuid(id) => id.0,
+				value => {
+					return Err(Error::FromValue {
+						value: Value::from_inner(value),
+						error: "expected the database to return a prepared query id".to_owned(),
+					}
+					.into())
+				}
+			};
+			Ok(Prepared {
+				id,
+				client: self.client.into_owned(),
+			})
+		})
+	}
+}
+
+/// A query which has been parsed by the database, and can be executed many times
+///
+/// Prepared queries belong to the connection and session which prepared them, and are checked
+/// against the schema each time they are executed. The number of queries which can be prepared
+/// on a connection is limited, so queries which are no longer needed should be unprepared.
+///
+/// ```no_run
+/// # #[derive(serde::Deserialize)]
+/// # struct Person;
+/// # #[tokio::main]
+/// # async fn main() -> surrealdb::Result<()> {
+/// # let db = surrealdb::engine::any::connect("mem://").await?;
+/// let query = db.prepare("SELECT * FROM person WHERE age > $age").await?;
+/// for age in [18, 21, 65] {
+///     let mut response = query.execute().bind(("age", age)).await?;
+///     let people: Vec<Person> = response.take(0)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Prepared<C: Connection> {
+	id: Uuid,
+	client: Surreal<C>,
+}
+
+impl<C> Prepared<C>
+where
+	C: Connection,
+{
+	/// The id of the prepared query
+	pub fn id(&self) -> Uuid {
+		self.id
+	}
+
+	/// Executes the prepared query
+	pub fn execute(&self) -> ExecutePrepared<C> {
+		ExecutePrepared {
+			client: Cow::Borrowed(&self.client),
+			id: self.id,
+			bindings: Ok(CoreObject::default()),
+		}
+	}
+
+	/// Removes the prepared query from the database, so that it can no longer be executed
+	pub fn unprepare(self) -> Unprepare<'static, C> {
+		Unprepare {
+			client: Cow::Owned(self.client),
+			id: self.id,
+		}
+	}
+}
+
+/// A prepared query future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ExecutePrepared<'r, C: Connection> {
+	client: Cow<'r, Surreal<C>>,
+	id: Uuid,
+	bindings: Result<CoreObject>,
+}
+
+impl<C> ExecutePrepared<'_, C>
+where
+	C: Connection,
+{
+	/// Binds a parameter or parameters to the prepared query
+	///
+	/// Parameters are bound by name, either as a key/value tuple or as the fields of an object.
+	pub fn bind(mut self, bindings: impl Serialize + 'static) -> Self {
+		if let Ok(object) = &mut self.bindings {
+			if let Err(error) = append_bindings(object, bindings) {
+				self.bindings = Err(error);
+			}
+		}
+		self
+	}
+
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> ExecutePrepared<'static, C> {
+		ExecutePrepared {
+			client: Cow::Owned(self.client.into_owned()),
+			id: self.id,
+			bindings: self.bindings,
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for ExecutePrepared<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<Response>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.router.extract()?;
+			let mut response = router
+				.execute_query(Command::ExecutePrepared {
+					id: self.id,
+					variables: self.bindings?,
+				})
+				.await?;
+			response.client = Surreal::new_from_router_waiter(
+				self.client.router.clone(),
+				self.client.waiter.clone(),
+			);
+			Ok(response)
+		})
+	}
+}
+
+/// An unprepare future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Unprepare<'r, C: Connection> {
+	client: Cow<'r, Surreal<C>>,
+	id: Uuid,
+}
+
+impl<C> Unprepare<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> Unprepare<'static, C> {
+		Unprepare {
+			client: Cow::Owned(self.client.into_owned()),
+			id: self.id,
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for Unprepare<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<()>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.router.extract()?;
+			router
+				.execute_unit(Command::Unprepare {
+					id: self.id,
+				})
+				.await
+		})
+	}
+}
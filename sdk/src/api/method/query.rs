@@ -273,35 +273,43 @@ where
 	/// ```
 	pub fn bind(self, bindings: impl Serialize + 'static) -> Self {
 		self.map_valid(move |mut valid| {
-			let bindings = to_core_value(bindings)?;
-			match bindings {
-				CoreValue::Object(mut map) => valid.bindings.append(&mut map.0),
-				CoreValue::Array(array) => {
-					if array.len() != 2 || !matches!(array[0], CoreValue::Strand(_)) {
-						let bindings = CoreValue::Array(array);
-						let bindings = Value::from_inner(bindings);
-						return Err(Error::InvalidBindings(bindings).into());
-					}
+			append_bindings(&mut valid.bindings, bindings)?;
+			Ok(valid)
+		})
+	}
+}
 
-					let mut iter = array.into_iter();
-					let Some(CoreValue::Strand(key)) = iter.next() else {
-						unreachable!()
-					};
-					let Some(value) = iter.next() else {
-						unreachable!()
-					};
-
-					valid.bindings.0.insert(key.0, value);
-				}
-				_ => {
-					let bindings = Value::from_inner(bindings);
-					return Err(Error::InvalidBindings(bindings).into());
-				}
+/// Adds a key/value tuple, or the fields of an object, to the parameters of a query
+pub(crate) fn append_bindings(
+	object: &mut CoreObject,
+	bindings: impl Serialize + 'static,
+) -> Result<()> {
+	let bindings = to_core_value(bindings)?;
+	match bindings {
+		CoreValue::Object(mut map) => object.append(&mut map.0),
+		CoreValue::Array(array) => {
+			if array.len() != 2 || !matches!(array[0], CoreValue::Strand(_)) {
+				let bindings = CoreValue::Array(array);
+				let bindings = Value::from_inner(bindings);
+				return Err(Error::InvalidBindings(bindings).into());
 			}
 
-			Ok(valid)
-		})
+			let mut iter = array.into_iter();
+			let Some(CoreValue::Strand(key)) = iter.next() else {
+				unreachable!()
+			};
+			let Some(value) = iter.next() else {
+				unreachable!()
+			};
+
+			object.0.insert(key.0, value);
+		}
+		_ => {
+			let bindings = Value::from_inner(bindings);
+			return Err(Error::InvalidBindings(bindings).into());
+		}
 	}
+	Ok(())
 }
 
 pub(crate) type QueryResult = Result<CoreValue>;
@@ -0,0 +1,119 @@
+use crate::api::conn::Command;
+use crate::api::conn::RequestGuard;
+use crate::api::method::BoxFuture;
+use crate::api::Connection;
+use crate::api::Result;
+use crate::method::OnceLockExt;
+use crate::method::Rows;
+use crate::method::Select;
+use crate::Value;
+use channel::Receiver;
+use futures::StreamExt;
+use serde::de::DeserializeOwned;
+use std::future::IntoFuture;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+use surrealdb_core::sql::{from_value as from_core_value, Value as CoreValue};
+
+/// The number of rows which are buffered before the database waits for them to be consumed
+const ROW_BUFFER_SIZE: usize = 100;
+
+fn into_future<C, O>(this: Select<C, O, Rows>) -> BoxFuture<Result<RowStream<O>>>
+where
+	C: Connection,
+{
+	let Select {
+		client,
+		resource,
+		..
+	} = this;
+	Box::pin(async move {
+		let router = client.router.extract()?;
+		let (tx, rx) = crate::channel::bounded(ROW_BUFFER_SIZE);
+		let pending = router
+			.send(Command::SelectStream {
+				what: resource?,
+				rows: tx,
+			})
+			.await?;
+		// The request keeps running while the rows are consumed
+		let (response, guard) = pending.recv_guarded().await;
+		response?;
+		Ok(RowStream {
+			rx,
+			guard: Some(guard),
+			response_type: PhantomData,
+		})
+	})
+}
+
+impl<'r, Client> IntoFuture for Select<'r, Client, Value, Rows>
+where
+	Client: Connection,
+{
+	type Output = Result<RowStream<Value>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		into_future(self)
+	}
+}
+
+impl<'r, Client, R> IntoFuture for Select<'r, Client, Vec<R>, Rows>
+where
+	Client: Connection,
+	R: DeserializeOwned,
+{
+	type Output = Result<RowStream<Vec<R>>>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		into_future(self)
+	}
+}
+
+/// A stream of the rows returned by a select query, as they are fetched
+#[derive(Debug)]
+#[must_use = "streams do nothing unless you poll them"]
+pub struct RowStream<R> {
+	rx: Receiver<Result<CoreValue>>,
+	/// Cancels the request if the stream is dropped before all the rows are received
+	guard: Option<RequestGuard>,
+	response_type: PhantomData<R>,
+}
+
+impl<R> RowStream<R> {
+	fn poll_row(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<CoreValue>>> {
+		let row = ready!(self.rx.poll_next_unpin(cx));
+		// The request has completed once all of its rows are received
+		if row.is_none() {
+			if let Some(guard) = self.guard.take() {
+				guard.complete();
+			}
+		}
+		Poll::Ready(row)
+	}
+}
+
+impl futures::Stream for RowStream<Value> {
+	type Item = Result<Value>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		self.poll_row(cx).map(|row| row.map(|row| row.map(Value::from_inner)))
+	}
+}
+
+impl<R> futures::Stream for RowStream<Vec<R>>
+where
+	R: DeserializeOwned + Unpin,
+{
+	type Item = Result<R>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		self.poll_row(cx)
+			.map(|row| row.map(|row| row.and_then(|row| from_core_value(row).map_err(Into::into))))
+	}
+}
@@ -5,6 +5,7 @@ use crate::api::opt::Resource;
 use crate::api::Connection;
 use crate::api::Result;
 use crate::method::Live;
+use crate::method::Rows;
 use crate::opt::KeyRange;
 use crate::Surreal;
 use crate::Value;
@@ -115,6 +116,49 @@ where
 	C: Connection,
 	R: DeserializeOwned,
 {
+	/// Streams the selected records as they are fetched, rather than waiting for all of them
+	///
+	/// Over WebSockets, and with the embedded engines, records are sent in batches while the
+	/// query is still running. Other connections fetch all the records before streaming them.
+	/// Records can be received before the query fails, in which case the error is returned by
+	/// the stream after them.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # use futures::StreamExt;
+	/// # #[derive(Debug, serde::Deserialize)]
+	/// # struct Person;
+	/// #
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// #
+	/// // Select the namespace/database to use
+	/// db.use_ns("namespace").use_db("database").await?;
+	///
+	/// // Stream all the records in a table
+	/// let mut stream = db.select::<Vec<Person>>("person").stream().await?;
+	/// while let Some(person) = stream.next().await {
+	///     println!("{:?}", person?);
+	/// }
+	///
+	/// // Stream a range of records
+	/// let mut stream = db.select::<Vec<Person>>("person").range("jane".."john").stream().await?;
+	/// # let _ = stream.next().await;
+	/// #
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn stream(self) -> Select<'r, C, R, Rows> {
+		Select {
+			client: self.client,
+			resource: self.resource,
+			response_type: self.response_type,
+			query_type: PhantomData,
+		}
+	}
+
 	/// Turns a normal select query into a live query
 	///
 	/// # Examples
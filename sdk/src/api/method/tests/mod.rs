@@ -6,6 +6,7 @@ mod server;
 mod types;
 
 use crate::api::method::tests::types::AuthParams;
+use crate::api::method::RowStream;
 use crate::api::opt::auth::Database;
 use crate::api::opt::auth::Jwt;
 use crate::api::opt::auth::Namespace;
@@ -110,6 +111,16 @@ async fn api() {
 		.await
 		.unwrap();
 
+	// prepare
+	let prepared = DB.prepare("CREATE user SET name = $name").await.unwrap();
+	let _: QueryResponse = prepared.execute().await.unwrap();
+	let _: QueryResponse = prepared.execute().bind(("name", "John Doe")).await.unwrap();
+	prepared.unprepare().await.unwrap();
+
+	// batch
+	let _ = DB.batch().select(USER).create((USER, "john"), User::default()).await.unwrap();
+	let _ = DB.batch().select(USER).delete((USER, "john")).atomic().await.unwrap();
+
 	// create
 	let _: Option<User> = DB.create(USER).await.unwrap();
 	let _: Option<User> = DB.create((USER, "john")).await.unwrap();
@@ -128,6 +139,8 @@ async fn api() {
 	let _: Vec<User> = DB.select(USER).range("jane"..="john").await.unwrap();
 	let _: Vec<User> =
 		DB.select(USER).range((Bound::Excluded("jane"), Bound::Included("john"))).await.unwrap();
+	let _: RowStream<Vec<User>> = DB.select(USER).stream().await.unwrap();
+	let _: RowStream<Vec<User>> = DB.select(USER).range("jane".."john").stream().await.unwrap();
 
 	// update
 	let _: Vec<User> = DB.update(USER).await.unwrap();
@@ -170,6 +183,21 @@ async fn api() {
 
 	// run
 	let _: Option<User> = DB.run("foo").await.unwrap();
+
+	// transaction
+	let txn = DB.clone().transaction().await.unwrap();
+	let _: Vec<User> = txn.select(USER).await.unwrap();
+	let _ = txn.query("SELECT * FROM user").await.unwrap();
+	let _ = txn.commit().await.unwrap();
+	let txn = DB.clone().transaction().await.unwrap();
+	let _ = txn.cancel().await.unwrap();
+
+	// session
+	let session = DB.new_session().unwrap();
+	let _ = session.use_ns("test-ns").use_db("test-db").await.unwrap();
+	let _: Option<User> = session.select((USER, "john")).await.unwrap();
+	let txn = session.clone().transaction().await.unwrap();
+	let _ = txn.commit().await.unwrap();
 }
 
 fn assert_send_sync(_: impl Send + Sync) {}
@@ -4,6 +4,7 @@ use crate::api::Response as QueryResponse;
 use crate::opt::Resource;
 use channel::Receiver;
 use surrealdb_core::sql::{to_value as to_core_value, Value as CoreValue};
+use uuid::Uuid;
 
 pub(super) fn mock(route_rx: Receiver<Route>) {
 	tokio::spawn(async move {
@@ -15,7 +16,9 @@ pub(super) fn mock(route_rx: Receiver<Route>) {
 			let cmd = request.command;
 
 			let result = match cmd {
-				Command::Invalidate | Command::Health => Ok(DbResponse::Other(CoreValue::None)),
+				Command::Invalidate | Command::CloseSession | Command::Health => {
+					Ok(DbResponse::Other(CoreValue::None))
+				}
 				Command::Authenticate {
 					..
 				}
@@ -43,7 +46,16 @@ pub(super) fn mock(route_rx: Receiver<Route>) {
 				} => Ok(DbResponse::Other(CoreValue::None)),
 				Command::Query {
 					..
+				}
+				| Command::ExecutePrepared {
+					..
 				} => Ok(DbResponse::Query(QueryResponse::new())),
+				Command::Prepare {
+					..
+				} => Ok(DbResponse::Other(CoreValue::Uuid(Uuid::new_v4().into()))),
+				Command::Batch {
+					..
+				} => Ok(DbResponse::Other(CoreValue::Array(Default::default()))),
 				Command::Create {
 					data,
 					..
@@ -112,7 +124,23 @@ pub(super) fn mock(route_rx: Receiver<Route>) {
 				Command::Run {
 					..
 				} => Ok(DbResponse::Other(CoreValue::None)),
-				Command::ExportMl {
+				Command::Begin => Ok(DbResponse::Other(CoreValue::from(Uuid::new_v4()))),
+				Command::Commit {
+					..
+				}
+				| Command::Cancel {
+					..
+				}
+				| Command::CancelRequest {
+					..
+				}
+				| Command::Unprepare {
+					..
+				} => Ok(DbResponse::Other(CoreValue::None)),
+				Command::SelectStream {
+					..
+				}
+				| Command::ExportMl {
 					..
 				}
 				| Command::ExportBytesMl {
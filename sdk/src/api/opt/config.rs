@@ -83,6 +83,28 @@ impl Config {
 	}
 
 	/// Use Rustls to configure TLS connections
+	///
+	/// A client certificate configured on the `ClientConfig` is presented to servers which
+	/// verify client certificates, and authenticates the connection as the user or record
+	/// named by the certificate subject.
+	///
+	/// ```no_run
+	/// use rustls::pki_types::{CertificateDer, PrivatePkcs8KeyDer};
+	/// use rustls::{ClientConfig, RootCertStore};
+	/// use surrealdb::opt::Config;
+	///
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// let mut roots = RootCertStore::empty();
+	/// roots.add(CertificateDer::from(std::fs::read("ca.der")?))?;
+	/// let cert = CertificateDer::from(std::fs::read("client.der")?);
+	/// let key = PrivatePkcs8KeyDer::from(std::fs::read("client.key.der")?);
+	/// let tls = ClientConfig::builder()
+	///     .with_root_certificates(roots)
+	///     .with_client_auth_cert(vec![cert], key.into())?;
+	/// let config = Config::new().rustls(tls);
+	/// # Ok(())
+	/// # }
+	/// ```
 	#[cfg(feature = "rustls")]
 	#[cfg_attr(docsrs, doc(cfg(feature = "rustls")))]
 	pub fn rustls(mut self, config: rustls::ClientConfig) -> Self {
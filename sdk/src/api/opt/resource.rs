@@ -5,12 +5,9 @@ use crate::{
 use std::ops::{self, Bound};
 use surrealdb_core::sql::{
 	Edges as CoreEdges, Id as CoreId, IdRange as CoreIdRange, Table as CoreTable,
-	Thing as CoreThing,
+	Thing as CoreThing, Value as CoreValue,
 };
 
-#[cfg(any(feature = "protocol-ws", feature = "protocol-http"))]
-use surrealdb_core::sql::Value as CoreValue;
-
 /// A wrapper type to assert that you ment to use a string as a table name.
 ///
 /// To prevent some possible errors, by defauit [`IntoResource`] does not allow `:` in table names
@@ -93,7 +90,6 @@ impl Resource {
 		}
 	}
 
-	#[cfg(any(feature = "protocol-ws", feature = "protocol-http"))]
 	pub(crate) fn into_core_value(self) -> CoreValue {
 		match self {
 			Resource::Table(x) => Table(x).into_core().into(),
@@ -48,6 +48,8 @@ async fn define_alter_table() -> Result<(), Error> {
 			functions: {},
 			models: {},
 			params: {},
+			policies: {},
+			roles: {},
 			tables: { test: 'DEFINE TABLE test TYPE ANY SCHEMALESS PERMISSIONS NONE' },
 			users: {},
 		}",
@@ -65,6 +67,8 @@ async fn define_alter_table() -> Result<(), Error> {
 			functions: {},
 			models: {},
 			params: {},
+			policies: {},
+			roles: {},
 			tables: { test: 'DEFINE TABLE test TYPE NORMAL DROP SCHEMALESS COMMENT \\'test\\' CHANGEFEED 1d PERMISSIONS FOR select, update, delete NONE, FOR create FULL' },
 			users: {},
 		}",
@@ -82,6 +86,8 @@ async fn define_alter_table() -> Result<(), Error> {
 			functions: {},
 			models: {},
 			params: {},
+			policies: {},
+			roles: {},
 			tables: { test: 'DEFINE TABLE test TYPE ANY SCHEMAFULL PERMISSIONS NONE' },
 			users: {},
 		}",
@@ -120,6 +126,8 @@ async fn define_alter_table_if_exists() -> Result<(), Error> {
 			functions: {},
 			models: {},
 			params: {},
+			policies: {},
+			roles: {},
 			tables: {},
 			users: {},
 		}",
@@ -52,6 +52,36 @@ async fn invalidate() {
 	);
 }
 
+#[test_log::test(tokio::test)]
+async fn close_session() {
+	let (permit, db) = new_db().await;
+	let database = Ulid::new().to_string();
+	db.use_ns(NS).use_db(&database).await.unwrap();
+	drop(permit);
+	// The main session can not be closed
+	db.close_session().await.unwrap_err();
+	let session = db.new_session().unwrap();
+	session
+		.signin(Root {
+			username: ROOT_USER,
+			password: ROOT_PASS,
+		})
+		.await
+		.unwrap();
+	session.use_ns(NS).use_db(&database).await.unwrap();
+	session.close_session().await.unwrap();
+	// The closed session no longer holds its authentication
+	session.use_ns(NS).use_db(&database).await.unwrap();
+	let error = session.create::<Option<ApiRecordId>>(("user", "john")).await.unwrap_err();
+	assert!(
+		error.to_string().contains("Not enough permissions to perform this action"),
+		"Unexpected error: {:?}",
+		error
+	);
+	// The main session is left untouched
+	let _: Option<ApiRecordId> = db.create(("user", "john")).await.unwrap();
+}
+
 #[test_log::test(tokio::test)]
 async fn signup_record() {
 	let (permit, db) = new_db().await;
@@ -1146,6 +1176,60 @@ async fn delete_record_range() {
 	);
 }
 
+#[test_log::test(tokio::test)]
+async fn batch() {
+	let (permit, db) = new_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let mut response = db
+		.batch()
+		.create(("user", "john"), json!({ "name": "John" }))
+		.create(("user", "john"), json!({ "name": "John" }))
+		.select(("user", "john"))
+		.await
+		.unwrap();
+	let john: Option<RecordBuf> = response.take(0).unwrap();
+	assert_eq!(john.unwrap().name, "John");
+	let errors = response.take_errors();
+	assert_eq!(errors.len(), 1);
+	assert!(errors.contains_key(&1));
+	let john: Option<RecordBuf> = response.take(2).unwrap();
+	assert!(john.is_some());
+}
+
+#[test_log::test(tokio::test)]
+async fn batch_atomic() {
+	let (permit, db) = new_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let mut response = db
+		.batch()
+		.create(("user", "jane"), json!({ "name": "Jane" }))
+		.create(("user", "jane"), json!({ "name": "Jane" }))
+		.select("user")
+		.atomic()
+		.await
+		.unwrap();
+	// The calls after the failed call are not run
+	assert_eq!(response.num_results(), 2);
+	response.take::<Option<RecordBuf>>(1).unwrap_err();
+	// The changes of the batch are cancelled
+	let users: Vec<RecordBuf> = db.select("user").await.unwrap();
+	assert!(users.is_empty());
+	// A query whose statements fail also cancels the batch
+	let response = db
+		.batch()
+		.create(("user", "jane"), json!({ "name": "Jane" }))
+		.query("CREATE user:jane SET name = 'Jane'")
+		.select("user")
+		.atomic()
+		.await
+		.unwrap();
+	assert_eq!(response.num_results(), 2);
+	let users: Vec<RecordBuf> = db.select("user").await.unwrap();
+	assert!(users.is_empty());
+}
+
 #[test_log::test(tokio::test)]
 async fn changefeed() {
 	let (permit, db) = new_db().await;
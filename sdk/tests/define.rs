@@ -11,7 +11,7 @@ use surrealdb::dbs::Session;
 use surrealdb::err::Error;
 use surrealdb::iam::Role;
 use surrealdb::sql::Idiom;
-use surrealdb::sql::{Part, Value};
+use surrealdb::sql::{Part, Thing, Value};
 use surrealdb_core::cnf::{INDEXING_BATCH_SIZE, NORMAL_FETCH_SIZE};
 use test_log::test;
 use tracing::info;
@@ -36,6 +36,7 @@ async fn define_statement_namespace() -> Result<(), Error> {
 			accesses: {},
 			namespaces: { test: 'DEFINE NAMESPACE test' },
 			nodes: {},
+			roles: {},
 			users: {},
 		}",
 	);
@@ -63,6 +64,8 @@ async fn define_statement_database() -> Result<(), Error> {
 		"{
 			accesses: {},
 			databases: { test: 'DEFINE DATABASE test' },
+			policies: {},
+			roles: {},
 			users: {},
 		}",
 	);
@@ -95,6 +98,8 @@ async fn define_statement_function() -> Result<(), Error> {
 			functions: { test: 'DEFINE FUNCTION fn::test($first: string, $last: string) { RETURN $first + $last; } PERMISSIONS FULL' },
 			models: {},
 			params: {},
+			policies: {},
+			roles: {},
 			tables: {},
 			users: {},
 		}",
@@ -126,6 +131,8 @@ async fn define_statement_table_drop() -> Result<(), Error> {
 			functions: {},
 			models: {},
 			params: {},
+			policies: {},
+			roles: {},
 			tables: { test: 'DEFINE TABLE test TYPE ANY DROP SCHEMALESS PERMISSIONS NONE' },
 			users: {},
 		}",
@@ -157,6 +164,8 @@ async fn define_statement_table_schemaless() -> Result<(), Error> {
 			functions: {},
 			models: {},
 			params: {},
+			policies: {},
+			roles: {},
 			tables: { test: 'DEFINE TABLE test TYPE ANY SCHEMALESS PERMISSIONS NONE' },
 			users: {},
 		}",
@@ -183,6 +192,8 @@ async fn define_statement_table_schemafull() -> Result<(), Error> {
 			functions: {},
 			models: {},
 			params: {},
+			policies: {},
+			roles: {},
 			tables: { test: 'DEFINE TABLE test TYPE NORMAL SCHEMAFULL PERMISSIONS NONE' },
 			users: {},
 		}",
@@ -212,6 +223,8 @@ async fn define_statement_table_schemaful() -> Result<(), Error> {
 			functions: {},
 			models: {},
 			params: {},
+			policies: {},
+			roles: {},
 			tables: { test: 'DEFINE TABLE test TYPE NORMAL SCHEMAFULL PERMISSIONS NONE' },
 			users: {},
 		}",
@@ -251,6 +264,8 @@ async fn define_statement_table_foreigntable() -> Result<(), Error> {
 			functions: {},
 			models: {},
 			params: {},
+			policies: {},
+			roles: {},
 			tables: {
 				test: 'DEFINE TABLE test TYPE NORMAL SCHEMAFULL PERMISSIONS NONE',
 				view: 'DEFINE TABLE view TYPE ANY SCHEMALESS AS SELECT count() FROM test GROUP ALL PERMISSIONS NONE',
@@ -283,6 +298,8 @@ async fn define_statement_table_foreigntable() -> Result<(), Error> {
 			functions: {},
 			models: {},
 			params: {},
+			policies: {},
+			roles: {},
 			tables: {
 				test: 'DEFINE TABLE test TYPE NORMAL SCHEMAFULL PERMISSIONS NONE',
 			},
@@ -961,6 +978,55 @@ async fn define_statement_index_concurrently_building_status() -> Result<(), Err
 	Ok(())
 }
 
+#[test(tokio::test)]
+async fn define_statement_index_concurrently_cancel() -> Result<(), Error> {
+	let sql = "
+		CREATE |user:10000| SET email = rand::string();
+		DEFINE INDEX test ON user FIELDS email CONCURRENTLY;
+		ALTER INDEX test ON user CANCEL;
+		INFO FOR INDEX test ON user;
+		SELECT * FROM user WHERE email = 'test' EXPLAIN;
+		ALTER INDEX IF EXISTS unknown ON user CANCEL;
+		ALTER INDEX unknown ON user CANCEL;
+		REBUILD INDEX test ON user;
+		SLEEP 2s;
+		INFO FOR INDEX test ON user;
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(3)?;
+	t.expect_val(
+		"{
+			building: { status: 'cancelled' }
+		}",
+	)?;
+	// The incomplete index is not used
+	t.expect_val(
+		"[
+			{
+				detail: {
+					table: 'user'
+				},
+				operation: 'Iterate Table'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	t.expect_val("None")?;
+	t.expect_error("The index 'unknown' does not exist")?;
+	t.skip_ok(2)?;
+	t.expect_val(
+		"{
+			building: { status: 'built' }
+		}",
+	)?;
+	Ok(())
+}
+
 #[tokio::test]
 async fn define_statement_index_multiple() -> Result<(), Error> {
 	let sql = "
@@ -1312,6 +1378,8 @@ async fn define_statement_analyzer() -> Result<(), Error> {
 			},
 			models: {},
 			params: {},
+			policies: {},
+			roles: {},
 			tables: {},
 			users: {},
 		}"#,
@@ -1523,6 +1591,202 @@ async fn define_statement_user_db() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn define_statement_policy() -> Result<(), Error> {
+	let sql = "
+		DEFINE POLICY tenant ON DATABASE WHERE tenant = $auth.tenant TABLES /order_.*/;
+		DEFINE POLICY owner ON DATABASE FOR select WHERE owner = $auth;
+		DEFINE TABLE order_item PERMISSIONS FULL;
+		DEFINE TABLE invoice PERMISSIONS FOR select POLICY owner, FOR create, update, delete NONE;
+		DEFINE TABLE product PERMISSIONS FULL;
+		DEFINE TABLE preorder_item PERMISSIONS FULL;
+		CREATE user:john SET tenant = 'acme';
+		CREATE order_item:1 SET tenant = 'acme';
+		CREATE order_item:2 SET tenant = 'other';
+		CREATE preorder_item:1 SET tenant = 'other';
+		CREATE invoice:1 SET owner = user:john;
+		CREATE invoice:2 SET owner = user:mary;
+		CREATE product:1;
+		INFO FOR DB;
+	";
+	let dbs = new_ds().await?.with_auth_enabled(true);
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 14);
+	//
+	for _ in 0..13 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = tmp.pick(&["policies".into(), "tenant".into()]);
+	assert_eq!(
+		val,
+		Value::from(
+			"DEFINE POLICY tenant ON DATABASE WHERE tenant = $auth.tenant TABLES /order_.*/"
+		)
+	);
+	//
+	let sql = "
+		SELECT VALUE id FROM order_item;
+		SELECT VALUE id FROM invoice;
+		SELECT VALUE id FROM product;
+		SELECT VALUE id FROM preorder_item;
+	";
+	let ses = Session::for_record("test", "test", "test", Thing::from(("user", "john")).into());
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[order_item:1]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[invoice:1]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[product:1]");
+	assert_eq!(tmp, val);
+	// The table patterns have to match the whole name of the table
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[preorder_item:1]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_statement_signin_policy() -> Result<(), Error> {
+	let sql = "
+		DEFINE SIGNIN POLICY ON DATABASE PASSWORD LENGTH 10 CLASSES 2 ATTEMPTS 3;
+		DEFINE SIGNIN POLICY ON DATABASE;
+		DEFINE USER weak ON DATABASE PASSWORD 'password';
+		DEFINE USER strong ON DATABASE PASSWORD 'password12';
+		DEFINE USER hashed ON DATABASE PASSHASH '$argon2id$v=19$m=19456,t=2,p=1$abc$def';
+		INFO FOR DB;
+		REMOVE SIGNIN POLICY ON DATABASE;
+		DEFINE USER weak ON DATABASE PASSWORD 'password';
+		REMOVE SIGNIN POLICY ON DATABASE;
+		REMOVE SIGNIN POLICY IF EXISTS ON DATABASE;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 10);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result.unwrap_err();
+	assert_eq!(tmp.to_string(), "The signin policy already exists in the database 'test'");
+	//
+	let tmp = res.remove(0).result.unwrap_err();
+	assert_eq!(
+		tmp.to_string(),
+		"The password must be at least 10 characters long and use at least 2 of lowercase letters, uppercase letters, digits and symbols"
+	);
+	//
+	for _ in 0..2 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = tmp.pick(&["signin".into()]);
+	assert_eq!(
+		val,
+		Value::from(
+			"DEFINE SIGNIN POLICY ON DATABASE PASSWORD LENGTH 10 CLASSES 2 ATTEMPTS 3 BACKOFF 1s LOCKOUT 15m"
+		)
+	);
+	//
+	for _ in 0..2 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	//
+	let tmp = res.remove(0).result.unwrap_err();
+	assert_eq!(tmp.to_string(), "The signin policy does not exist in the database 'test'");
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_statement_role_db() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("ns").with_db("db");
+
+	// Create a custom role and a user which has been assigned it
+	let sql = "
+		DEFINE ROLE indexer ON DB GRANT define, remove ON index GRANT select ON table;
+		DEFINE ROLE owner ON DB GRANT select ON record;
+		DEFINE USER test ON DB PASSWORD 'test' ROLES missing;
+		DEFINE USER test ON DB PASSWORD 'test' ROLES indexer;
+		DEFINE TABLE person;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+
+	assert!(res[0].result.is_ok());
+	assert_eq!(
+		res[1].result.as_ref().unwrap_err().to_string(),
+		"The role 'owner' is a built-in role and can not be redefined"
+	);
+	assert_eq!(
+		res[2].result.as_ref().unwrap_err().to_string(),
+		"The role 'missing' does not exist in the database 'db'"
+	);
+	assert!(res[3].result.is_ok());
+
+	// Authenticate as the user with the custom role
+	let mut ses = Session::default();
+	surrealdb::iam::verify::basic(&dbs, &mut ses, "test", "test", Some("ns"), Some("db")).await?;
+
+	// The granted privileges are allowed, everything else is not
+	let sql = "
+		DEFINE INDEX name ON person FIELDS name;
+		INFO FOR TABLE person;
+		REMOVE INDEX name ON person;
+		DEFINE TABLE other;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+
+	assert!(res[0].result.is_ok());
+	assert!(res[1].result.is_ok());
+	assert!(res[2].result.is_ok());
+	assert!(res[3]
+		.result
+		.as_ref()
+		.unwrap_err()
+		.to_string()
+		.starts_with("IAM error: Not enough permissions to perform this action"));
+
+	// The changes to the role apply to the authenticated session
+	let sql = "DEFINE ROLE OVERWRITE indexer ON DB GRANT define ON table";
+	let res = &mut dbs.execute(sql, &Session::owner().with_ns("ns").with_db("db"), None).await?;
+	assert!(res[0].result.is_ok());
+	let res = &mut dbs
+		.execute("DEFINE TABLE other; DEFINE INDEX name ON person FIELDS name", &ses, None)
+		.await?;
+	assert!(res[0].result.is_ok());
+	assert!(res[1].result.is_err());
+
+	// A removed role grants nothing, but does not prevent the user from signing in
+	let sql = "REMOVE ROLE indexer ON DB";
+	let res = &mut dbs.execute(sql, &Session::owner().with_ns("ns").with_db("db"), None).await?;
+	assert!(res[0].result.is_ok());
+	let res = &mut dbs.execute("DEFINE TABLE another", &ses, None).await?;
+	assert!(res[0].result.is_err());
+	let mut ses = Session::default();
+	surrealdb::iam::verify::basic(&dbs, &mut ses, "test", "test", Some("ns"), Some("db")).await?;
+
+	Ok(())
+}
+
 fn check_path<F>(val: &Value, path: &[&str], check: F)
 where
 	F: Fn(Value),
@@ -1551,8 +1815,8 @@ async fn permissions_checks_define_ns() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-		vec!["{ accesses: {  }, namespaces: { NS: 'DEFINE NAMESPACE NS' }, nodes: {  }, users: {  } }"],
-		vec!["{ accesses: {  }, namespaces: {  }, nodes: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, namespaces: { NS: 'DEFINE NAMESPACE NS' }, nodes: {  }, roles: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, namespaces: {  }, nodes: {  }, roles: {  }, users: {  } }"],
 	];
 
 	let test_cases = [
@@ -1590,8 +1854,10 @@ async fn permissions_checks_define_db() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-		vec!["{ accesses: {  }, databases: { DB: 'DEFINE DATABASE DB' }, users: {  } }"],
-		vec!["{ accesses: {  }, databases: {  }, users: {  } }"],
+		vec![
+			"{ accesses: {  }, databases: { DB: 'DEFINE DATABASE DB' }, policies: {  }, roles: {  }, users: {  } }",
+		],
+		vec!["{ accesses: {  }, databases: {  }, policies: {  }, roles: {  }, users: {  } }"],
 	];
 
 	let test_cases = [
@@ -1632,8 +1898,8 @@ async fn permissions_checks_define_function() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: {  }, analyzers: {  }, functions: { greet: \"DEFINE FUNCTION fn::greet() { RETURN 'Hello'; } PERMISSIONS FULL\" }, models: {  }, params: {  }, tables: {  }, users: {  } }"],
-		vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"]
+        vec!["{ accesses: {  }, analyzers: {  }, functions: { greet: \"DEFINE FUNCTION fn::greet() { RETURN 'Hello'; } PERMISSIONS FULL\" }, models: {  }, params: {  }, policies: {  }, roles: {  }, tables: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, policies: {  }, roles: {  }, tables: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -1674,8 +1940,8 @@ async fn permissions_checks_define_analyzer() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: {  }, analyzers: { analyzer: 'DEFINE ANALYZER analyzer TOKENIZERS BLANK' }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"],
-		vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"]
+        vec!["{ accesses: {  }, analyzers: { analyzer: 'DEFINE ANALYZER analyzer TOKENIZERS BLANK' }, functions: {  }, models: {  }, params: {  }, policies: {  }, roles: {  }, tables: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, policies: {  }, roles: {  }, tables: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -1716,8 +1982,8 @@ async fn permissions_checks_define_access_root() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: { access: \"DEFINE ACCESS access ON ROOT TYPE JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE\" }, namespaces: {  }, nodes: {  }, users: {  } }"],
-		vec!["{ accesses: {  }, namespaces: {  }, nodes: {  }, users: {  } }"]
+        vec!["{ accesses: { access: \"DEFINE ACCESS access ON ROOT TYPE JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE\" }, namespaces: {  }, nodes: {  }, roles: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, namespaces: {  }, nodes: {  }, roles: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -1758,8 +2024,8 @@ async fn permissions_checks_define_access_ns() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: { access: \"DEFINE ACCESS access ON NAMESPACE TYPE JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE\" }, databases: {  }, users: {  } }"],
-		vec!["{ accesses: {  }, databases: {  }, users: {  } }"]
+        vec!["{ accesses: { access: \"DEFINE ACCESS access ON NAMESPACE TYPE JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE\" }, databases: {  }, policies: {  }, roles: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, databases: {  }, policies: {  }, roles: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -1800,8 +2066,8 @@ async fn permissions_checks_define_access_db() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: { access: \"DEFINE ACCESS access ON DATABASE TYPE JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE\" }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"],
-		vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"]
+        vec!["{ accesses: { access: \"DEFINE ACCESS access ON DATABASE TYPE JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE\" }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, policies: {  }, roles: {  }, tables: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, policies: {  }, roles: {  }, tables: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -1842,8 +2108,8 @@ async fn permissions_checks_define_user_root() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: {  }, namespaces: {  }, nodes: {  }, users: { user: \"DEFINE USER user ON ROOT PASSHASH 'secret' ROLES VIEWER DURATION FOR TOKEN 15m, FOR SESSION 6h\" } }"],
-		vec!["{ accesses: {  }, namespaces: {  }, nodes: {  }, users: {  } }"]
+        vec!["{ accesses: {  }, namespaces: {  }, nodes: {  }, roles: {  }, users: { user: \"DEFINE USER user ON ROOT PASSHASH 'secret' ROLES VIEWER DURATION FOR TOKEN 15m, FOR SESSION 6h\" } }"],
+		vec!["{ accesses: {  }, namespaces: {  }, nodes: {  }, roles: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -1884,8 +2150,8 @@ async fn permissions_checks_define_user_ns() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: {  }, databases: {  }, users: { user: \"DEFINE USER user ON NAMESPACE PASSHASH 'secret' ROLES VIEWER DURATION FOR TOKEN 15m, FOR SESSION 6h\" } }"],
-		vec!["{ accesses: {  }, databases: {  }, users: {  } }"]
+        vec!["{ accesses: {  }, databases: {  }, policies: {  }, roles: {  }, users: { user: \"DEFINE USER user ON NAMESPACE PASSHASH 'secret' ROLES VIEWER DURATION FOR TOKEN 15m, FOR SESSION 6h\" } }"],
+		vec!["{ accesses: {  }, databases: {  }, policies: {  }, roles: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -1926,8 +2192,8 @@ async fn permissions_checks_define_user_db() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: { user: \"DEFINE USER user ON DATABASE PASSHASH 'secret' ROLES VIEWER DURATION FOR TOKEN 15m, FOR SESSION 6h\" } }"],
-		vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"]
+        vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, policies: {  }, roles: {  }, tables: {  }, users: { user: \"DEFINE USER user ON DATABASE PASSHASH 'secret' ROLES VIEWER DURATION FOR TOKEN 15m, FOR SESSION 6h\" } }"],
+		vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, policies: {  }, roles: {  }, tables: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -1968,8 +2234,8 @@ async fn permissions_checks_define_access_record() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: { account: \"DEFINE ACCESS account ON DATABASE TYPE RECORD WITH JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 15m, FOR SESSION 12h\" }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"],
-		vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"]
+        vec!["{ accesses: { account: \"DEFINE ACCESS account ON DATABASE TYPE RECORD WITH JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 15m, FOR SESSION 12h\" }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, policies: {  }, roles: {  }, tables: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, policies: {  }, roles: {  }, tables: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -2010,8 +2276,8 @@ async fn permissions_checks_define_param() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: { param: \"DEFINE PARAM $param VALUE 'foo' PERMISSIONS FULL\" }, tables: {  }, users: {  } }"],
-		vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"]
+        vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: { param: \"DEFINE PARAM $param VALUE 'foo' PERMISSIONS FULL\" }, policies: {  }, roles: {  }, tables: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, policies: {  }, roles: {  }, tables: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -2049,8 +2315,8 @@ async fn permissions_checks_define_table() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, tables: { TB: 'DEFINE TABLE TB TYPE ANY SCHEMALESS PERMISSIONS NONE' }, users: {  } }"],
-		vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"]
+        vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, policies: {  }, roles: {  }, tables: { TB: 'DEFINE TABLE TB TYPE ANY SCHEMALESS PERMISSIONS NONE' }, users: {  } }"],
+		vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, policies: {  }, roles: {  }, tables: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -2239,6 +2505,8 @@ async fn define_statement_table_permissions() -> Result<(), Error> {
 			functions: {},
 			models: {},
 			params: {},
+			policies: {},
+			roles: {},
 			tables: {
 					default: 'DEFINE TABLE default TYPE ANY SCHEMALESS PERMISSIONS NONE',
 					full: 'DEFINE TABLE full TYPE ANY SCHEMALESS PERMISSIONS FULL',
@@ -2643,6 +2911,8 @@ async fn define_table_relation_redefinition_info() -> Result<(), Error> {
 			functions: {},
 			models: {},
 			params: {},
+			policies: {},
+			roles: {},
 			tables: { likes: 'DEFINE TABLE likes TYPE RELATION IN person OUT person SCHEMALESS PERMISSIONS NONE' },
 			users: {},
 		}",
@@ -2664,6 +2934,8 @@ async fn define_table_relation_redefinition_info() -> Result<(), Error> {
 			functions: {},
 			models: {},
 			params: {},
+			policies: {},
+			roles: {},
 			tables: { likes: 'DEFINE TABLE likes TYPE RELATION IN person OUT person | thing SCHEMALESS PERMISSIONS NONE' },
 			users: {},
 		}",
@@ -2685,6 +2957,8 @@ async fn define_table_relation_redefinition_info() -> Result<(), Error> {
 			functions: {},
 			models: {},
 			params: {},
+			policies: {},
+			roles: {},
 			tables: { likes: 'DEFINE TABLE likes TYPE RELATION IN person OUT person | thing | other SCHEMALESS PERMISSIONS NONE' },
 			users: {},
 		}",
@@ -5,7 +5,10 @@ use std::collections::HashMap;
 
 use regex::Regex;
 use surrealdb::dbs::Session;
+use surrealdb::iam::audit::AuditLog;
 use surrealdb::iam::Role;
+use surrealdb::kvs::{LockType::*, TransactionType::*};
+use surrealdb::sql::Value;
 
 #[tokio::test]
 async fn info_for_root() {
@@ -25,7 +28,7 @@ async fn info_for_root() {
 	assert!(out.is_ok(), "Unexpected error: {:?}", out);
 
 	let output_regex =
-		Regex::new(r"\{ accesses: \{ access: .* \}, namespaces: \{ NS: .* \}, nodes: \{ .* \}, users: \{ user: .* \} \}")
+		Regex::new(r"\{ accesses: \{ access: .* \}, namespaces: \{ NS: .* \}, nodes: \{ .* \}, roles: {  }, users: \{ user: .* \} \}")
 			.unwrap();
 	let out_str = out.unwrap().to_string();
 	assert!(
@@ -54,7 +57,7 @@ async fn info_for_ns() {
 	assert!(out.is_ok(), "Unexpected error: {:?}", out);
 
 	let output_regex = Regex::new(
-		r"\{ accesses: \{ access: .* \}, databases: \{ DB: .* \}, users: \{ user: .* \} \}",
+		r"\{ accesses: \{ access: .* \}, databases: \{ DB: .* \}, policies: {  }, roles: {  }, users: \{ user: .* \} \}",
 	)
 	.unwrap();
 	let out_str = out.unwrap().to_string();
@@ -87,7 +90,7 @@ async fn info_for_db() {
 	let out = res.pop().unwrap().output();
 	assert!(out.is_ok(), "Unexpected error: {:?}", out);
 
-	let output_regex = Regex::new(r"\{ accesses: \{ jwt: .*, record: .* \}, analyzers: \{ analyzer: .* \}, functions: \{ greet: .* \}, params: \{ param: .* \}, tables: \{ TB: .* \}, users: \{ user: .* \} \}").unwrap();
+	let output_regex = Regex::new(r"\{ accesses: \{ jwt: .*, record: .* \}, analyzers: \{ analyzer: .* \}, functions: \{ greet: .* \}, params: \{ param: .* \}, policies: {  }, roles: {  }, tables: \{ TB: .* \}, users: \{ user: .* \} \}").unwrap();
 	let out_str = out.unwrap().to_string();
 	assert!(
 		output_regex.is_match(&out_str),
@@ -205,6 +208,89 @@ async fn info_for_user() {
 // Permissions
 //
 
+#[tokio::test]
+async fn info_for_audit() {
+	let sql = r#"
+        DEFINE TABLE person;
+        DEFINE USER user ON DATABASE PASSWORD 'pass';
+        REMOVE TABLE person;
+        INFO FOR AUDIT;
+    "#;
+	let dbs = new_ds().await.unwrap().with_audit(Some(AuditLog::new(b"secret")));
+	let ses = Session::owner().with_ns("ns").with_db("db");
+
+	let mut res = dbs.execute(sql, &ses, None).await.unwrap();
+	assert_eq!(res.len(), 4);
+
+	let out = res.pop().unwrap().output();
+	let Ok(Value::Array(entries)) = out else {
+		panic!("Unexpected output: {:?}", out);
+	};
+	let entries: Vec<(String, String, bool)> = entries
+		.into_iter()
+		.map(|v| match v {
+			Value::Object(v) => (
+				v.get("event").unwrap().clone().as_raw_string(),
+				v.get("detail").unwrap().clone().as_raw_string(),
+				v.get("valid").unwrap().is_true(),
+			),
+			v => panic!("Unexpected entry: {v}"),
+		})
+		.collect();
+	assert_eq!(entries.len(), 3);
+	assert_eq!(entries[0].0, "define");
+	assert!(entries[0].1.starts_with("DEFINE TABLE person"), "{}", entries[0].1);
+	assert_eq!(entries[1].0, "define");
+	assert!(entries[1].1.contains("PASSHASH '[REDACTED]'"), "{}", entries[1].1);
+	assert_eq!(entries[2].0, "remove");
+	assert_eq!(entries[2].1, "REMOVE TABLE person");
+	assert!(entries.iter().all(|(_, _, valid)| *valid));
+
+	// Entries are only visible within the selected database
+	let ses = Session::owner().with_ns("ns").with_db("other");
+	let mut res = dbs.execute("INFO FOR AUDIT", &ses, None).await.unwrap();
+	let out = res.pop().unwrap().output().unwrap();
+	assert_eq!(out, Value::from(Vec::<Value>::new()));
+
+	// Only owners can view the audit log
+	let ses = Session::for_level(("ns", "db").into(), Role::Viewer).with_ns("ns").with_db("db");
+	let mut res = dbs.execute("INFO FOR AUDIT", &ses, None).await.unwrap();
+	let err = res.pop().unwrap().output().unwrap_err();
+	assert!(err.to_string().contains("Not enough permissions"), "{}", err);
+
+	// Entries signed with another key are reported as invalid
+	let dbs = dbs.with_audit(Some(AuditLog::new(b"other")));
+	let ses = Session::owner().with_ns("ns").with_db("db");
+	let mut res = dbs.execute("INFO FOR AUDIT", &ses, None).await.unwrap();
+	let out = res.pop().unwrap().output().unwrap();
+	let Value::Array(entries) = out else {
+		panic!("Unexpected output: {:?}", out);
+	};
+	assert_eq!(entries.len(), 3);
+	assert!(entries
+		.iter()
+		.all(|v| matches!(v, Value::Object(v) if v.get("valid") == Some(&Value::Bool(false)))));
+
+	// Entries which can not be read are reported as invalid
+	let tx = dbs.transaction(Write, Optimistic).await.unwrap();
+	let mut key = b"/!au".to_vec();
+	key.extend([0x7f; 24]);
+	tx.set(key, vec![0xff, 0x00], None).await.unwrap();
+	tx.commit().await.unwrap();
+	let mut res = dbs.execute("INFO FOR AUDIT", &ses, None).await.unwrap();
+	let out = res.pop().unwrap().output().unwrap();
+	let Value::Array(entries) = out else {
+		panic!("Unexpected output: {:?}", out);
+	};
+	assert_eq!(entries.len(), 4);
+	assert!(matches!(&entries[3], Value::Object(v) if v.get("valid") == Some(&Value::Bool(false))));
+
+	// The limit is applied while the entries are read
+	let mut res = dbs.execute("INFO FOR AUDIT LIMIT 2", &ses, None).await.unwrap();
+	let out = res.pop().unwrap().output().unwrap();
+	assert!(matches!(out, Value::Array(v) if v.len() == 2));
+}
+
 #[tokio::test]
 async fn permissions_checks_info_root() {
 	let scenario =
@@ -212,8 +298,8 @@ async fn permissions_checks_info_root() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-		vec!["{ accesses: {  }, namespaces: {  }, nodes: {  }, users: {  } }"],
-		vec!["{ accesses: {  }, namespaces: {  }, nodes: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, namespaces: {  }, nodes: {  }, roles: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, namespaces: {  }, nodes: {  }, roles: {  }, users: {  } }"],
 	];
 
 	let test_cases = [
@@ -251,8 +337,8 @@ async fn permissions_checks_info_ns() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-		vec!["{ accesses: {  }, databases: {  }, users: {  } }"],
-		vec!["{ accesses: {  }, databases: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, databases: {  }, policies: {  }, roles: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, databases: {  }, policies: {  }, roles: {  }, users: {  } }"],
 	];
 
 	let test_cases = [
@@ -290,8 +376,8 @@ async fn permissions_checks_info_db() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"],
-        vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"],
+        vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, policies: {  }, roles: {  }, tables: {  }, users: {  } }"],
+        vec!["{ accesses: {  }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, policies: {  }, roles: {  }, tables: {  }, users: {  } }"],
     ];
 
 	let test_cases = [
@@ -508,7 +594,7 @@ async fn access_info_redacted() {
 		assert!(out.is_ok(), "Unexpected error: {:?}", out);
 
 		let out_expected =
-			r#"{ accesses: { access: "DEFINE ACCESS access ON NAMESPACE TYPE JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE" }, databases: {  }, users: {  } }"#.to_string();
+			r#"{ accesses: { access: "DEFINE ACCESS access ON NAMESPACE TYPE JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE" }, databases: {  }, policies: {  }, roles: {  }, users: {  } }"#.to_string();
 		let out_str = out.unwrap().to_string();
 		assert_eq!(
 			out_str, out_expected,
@@ -531,7 +617,7 @@ async fn access_info_redacted() {
 		assert!(out.is_ok(), "Unexpected error: {:?}", out);
 
 		let out_expected =
-			r#"{ accesses: { access: "DEFINE ACCESS access ON NAMESPACE TYPE JWT ALGORITHM PS512 KEY 'public' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE" }, databases: {  }, users: {  } }"#.to_string();
+			r#"{ accesses: { access: "DEFINE ACCESS access ON NAMESPACE TYPE JWT ALGORITHM PS512 KEY 'public' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE" }, databases: {  }, policies: {  }, roles: {  }, users: {  } }"#.to_string();
 		let out_str = out.unwrap().to_string();
 		assert_eq!(
 			out_str, out_expected,
@@ -554,7 +640,7 @@ async fn access_info_redacted() {
 		assert!(out.is_ok(), "Unexpected error: {:?}", out);
 
 		let out_expected =
-			r#"{ accesses: { access: "DEFINE ACCESS access ON DATABASE TYPE RECORD WITH JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE" }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"#.to_string();
+			r#"{ accesses: { access: "DEFINE ACCESS access ON DATABASE TYPE RECORD WITH JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE" }, analyzers: {  }, functions: {  }, models: {  }, params: {  }, policies: {  }, roles: {  }, tables: {  }, users: {  } }"#.to_string();
 		let out_str = out.unwrap().to_string();
 		assert_eq!(
 			out_str, out_expected,
@@ -581,7 +667,7 @@ async fn access_info_redacted_structure() {
 		assert!(out.is_ok(), "Unexpected error: {:?}", out);
 
 		let out_expected =
-			r#"{ accesses: [{ base: 'NAMESPACE', duration: { session: 6h, token: 15m }, kind: { jwt: { issuer: { alg: 'HS512', key: '[REDACTED]' }, verify: { alg: 'HS512', key: '[REDACTED]' } }, kind: 'JWT' }, name: 'access' }], databases: [], users: [] }"#.to_string();
+			r#"{ accesses: [{ base: 'NAMESPACE', duration: { session: 6h, token: 15m }, kind: { jwt: { issuer: { alg: 'HS512', key: '[REDACTED]' }, verify: { alg: 'HS512', key: '[REDACTED]' } }, kind: 'JWT' }, name: 'access' }], databases: [], policies: [], roles: [], users: [] }"#.to_string();
 		let out_str = out.unwrap().to_string();
 		assert_eq!(
 			out_str, out_expected,
@@ -604,7 +690,7 @@ async fn access_info_redacted_structure() {
 		assert!(out.is_ok(), "Unexpected error: {:?}", out);
 
 		let out_expected =
-			r#"{ accesses: [{ base: 'NAMESPACE', duration: { session: 6h, token: 15m }, kind: { jwt: { issuer: { alg: 'PS512', key: '[REDACTED]' }, verify: { alg: 'PS512', key: 'public' } }, kind: 'JWT' }, name: 'access' }], databases: [], users: [] }"#.to_string();
+			r#"{ accesses: [{ base: 'NAMESPACE', duration: { session: 6h, token: 15m }, kind: { jwt: { issuer: { alg: 'PS512', key: '[REDACTED]' }, verify: { alg: 'PS512', key: 'public' } }, kind: 'JWT' }, name: 'access' }], databases: [], policies: [], roles: [], users: [] }"#.to_string();
 		let out_str = out.unwrap().to_string();
 		assert_eq!(
 			out_str, out_expected,
@@ -627,7 +713,7 @@ async fn access_info_redacted_structure() {
 		assert!(out.is_ok(), "Unexpected error: {:?}", out);
 
 		let out_expected =
-			r#"{ accesses: [{ base: 'DATABASE', duration: { session: 6h, token: 15m }, kind: { jwt: { issuer: { alg: 'HS512', key: '[REDACTED]' }, verify: { alg: 'HS512', key: '[REDACTED]' } }, kind: 'RECORD' }, name: 'access' }], analyzers: [], functions: [], models: [], params: [], tables: [], users: [] }"#.to_string();
+			r#"{ accesses: [{ base: 'DATABASE', duration: { session: 6h, token: 15m }, kind: { jwt: { issuer: { alg: 'HS512', key: '[REDACTED]' }, verify: { alg: 'HS512', key: '[REDACTED]' } }, kind: 'RECORD', refresh: false }, name: 'access' }], analyzers: [], functions: [], models: [], params: [], policies: [], roles: [], tables: [], users: [] }"#.to_string();
 		let out_str = out.unwrap().to_string();
 		assert_eq!(
 			out_str, out_expected,
@@ -652,7 +738,7 @@ async fn function_info_structure() {
 	assert!(out.is_ok(), "Unexpected error: {:?}", out);
 
 	let out_expected =
-		r#"{ accesses: [], analyzers: [], functions: [{ args: [['name', 'string']], block: "{ RETURN 'Hello, ' + $name + '!'; }", name: 'example', permissions: true, returns: 'string' }], models: [], params: [], tables: [], users: [] }"#.to_string();
+		r#"{ accesses: [], analyzers: [], functions: [{ args: [['name', 'string']], block: "{ RETURN 'Hello, ' + $name + '!'; }", name: 'example', permissions: true, returns: 'string' }], models: [], params: [], policies: [], roles: [], tables: [], users: [] }"#.to_string();
 	let out_str = out.unwrap().to_string();
 	assert_eq!(
 		out_str, out_expected,
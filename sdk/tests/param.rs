@@ -31,6 +31,8 @@ async fn define_global_param() -> Result<(), Error> {
 			functions: {},
 			models: {},
 			params: { test: 'DEFINE PARAM $test VALUE 12345 PERMISSIONS FULL' },
+			policies: {},
+			roles: {},
 			tables: {},
 			users: {},
 		}",
@@ -2779,3 +2779,84 @@ async fn select_from_unique_index_ascending() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_with_partial_index() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX idx ON TABLE t COLUMNS name WHERE status = 'active';
+		CREATE t:1 SET name = 'a', status = 'active';
+		CREATE t:2 SET name = 'a', status = 'inactive';
+		CREATE t:3 SET name = 'b', status = 'active';
+		SELECT id FROM t WHERE name = 'a' AND status = 'active' EXPLAIN;
+		SELECT id FROM t WHERE name = 'a' AND status = 'active';
+		SELECT id FROM t WHERE name = 'a' EXPLAIN;
+		SELECT id FROM t WHERE name = 'a';
+		UPDATE t:1 SET status = 'inactive';
+		UPDATE t:2 SET status = 'active';
+		SELECT id FROM t WHERE status = 'active' AND name = 'a';
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(4)?;
+	t.expect_vals(&[
+		"[
+			{
+				detail: {
+					plan: {
+						index: 'idx',
+						operator: '=',
+						value: 'a'
+					},
+					table: 't'
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+		"[{ id: t:1 }]",
+		"[
+			{
+				detail: {
+					table: 't'
+				},
+				operation: 'Iterate Table'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+		"[{ id: t:1 }, { id: t:2 }]",
+	])?;
+	t.skip_ok(2)?;
+	t.expect_val("[{ id: t:2 }]")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_partial_index_with_invalid_condition() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX idx ON TABLE t COLUMNS name WHERE created > time::now();
+		DEFINE INDEX idx ON TABLE t COLUMNS name WHERE status = $status;
+		DEFINE INDEX idx ON TABLE t COLUMNS name WHERE status IN (SELECT VALUE status FROM s);
+		DEFINE INDEX idx ON TABLE t COLUMNS name WHERE string::len(status) > 0 AND !archived;
+	";
+	let mut t = Test::new(sql).await?;
+	t.expect_error(
+		"The condition of the index 'idx' can only read the fields of the record, found 'time::now()'",
+	)?;
+	t.expect_error(
+		"The condition of the index 'idx' can only read the fields of the record, found '$status'",
+	)?;
+	t.expect_error(
+		"The condition of the index 'idx' can only read the fields of the record, found '(SELECT VALUE status FROM s)'",
+	)?;
+	t.skip_ok(1)?;
+	Ok(())
+}
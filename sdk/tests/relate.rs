@@ -285,6 +285,8 @@ async fn relate_enforced() -> Result<(), Error> {
 	functions: {},
 	models: {},
 	params: {},
+	policies: {},
+	roles: {},
 	tables: {
 		a: 'DEFINE TABLE a TYPE ANY SCHEMALESS PERMISSIONS NONE',
 		edge: 'DEFINE TABLE edge TYPE RELATION ENFORCED SCHEMALESS PERMISSIONS NONE'
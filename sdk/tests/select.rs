@@ -1,7 +1,7 @@
 mod parse;
 use parse::Parse;
 mod helpers;
-use helpers::new_ds;
+use helpers::{new_ds, skip_ok};
 use surrealdb_core::dbs::Session;
 use surrealdb_core::err::Error;
 use surrealdb_core::iam::Role;
@@ -1298,3 +1298,96 @@ async fn select_field_from_graph_no_flattening() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_after() -> Result<(), Error> {
+	let sql = "
+		CREATE person:1, person:2, person:3, person:4;
+		SELECT VALUE id FROM person LIMIT 2 AFTER person:1;
+		SELECT VALUE id FROM person:1..=3 AFTER person:2;
+		SELECT VALUE id FROM person AFTER person:4;
+		SELECT VALUE id FROM person AFTER 1;
+		SELECT VALUE id FROM person ORDER BY id DESC AFTER person:3;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	let _ = res.remove(0).result?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[person:2, person:3]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[person:3]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found 1 but the AFTER clause must evaluate to a record id, or a record"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "The AFTER clause can not be used with an ORDER BY clause"
+	));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_after_with_index() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX person_age ON TABLE person COLUMNS age;
+		DEFINE INDEX user_name ON TABLE user COLUMNS name UNIQUE;
+		CREATE person:1 SET age = 30;
+		CREATE person:2 SET age = 20;
+		CREATE person:3 SET age = 20;
+		CREATE person:4 SET age = 10;
+		CREATE user:1 SET name = 'd';
+		CREATE user:2 SET name = 'c';
+		CREATE user:3 SET name = 'b';
+		CREATE user:4 SET name = 'a';
+		SELECT VALUE id FROM person WHERE age >= 10 AFTER { id: person:2, age: 20 };
+		SELECT VALUE id FROM person WHERE age = 20 AFTER { id: person:2, age: 20 };
+		SELECT VALUE id FROM user WHERE name > 'a' AFTER { id: user:3, name: 'b' };
+		SELECT VALUE id FROM person WHERE age = 20 AFTER person:2;
+		SELECT VALUE id FROM person WHERE age = 20 AFTER { id: person:2, age: 30 };
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 15);
+	//
+	skip_ok(res, 10)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[person:3, person:1]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[person:3]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[user:2, user:1]");
+	assert_eq!(tmp, val);
+	//
+	for _ in 0..2 {
+		let tmp = res.remove(0).result;
+		assert!(matches!(
+			tmp.err(),
+			Some(e) if e.to_string() == "The AFTER clause must be given the record person:2, as found in the index 'person_age'"
+		));
+	}
+	//
+	Ok(())
+}
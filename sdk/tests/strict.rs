@@ -236,6 +236,7 @@ async fn loose_mode_all_ok() -> Result<(), Error> {
 			accesses: {},
 			namespaces: { test: 'DEFINE NAMESPACE test' },
 			nodes: {},
+			roles: {},
 			users: {},
 		}",
 	);
@@ -246,6 +247,8 @@ async fn loose_mode_all_ok() -> Result<(), Error> {
 		"{
 			accesses: {},
 			databases: { test: 'DEFINE DATABASE test' },
+			policies: {},
+			roles: {},
 			users: {},
 		}",
 	);
@@ -259,6 +262,8 @@ async fn loose_mode_all_ok() -> Result<(), Error> {
 			functions: {},
 			models: {},
 			params: {},
+			policies: {},
+			roles: {},
 			tables: { test: 'DEFINE TABLE test TYPE ANY SCHEMALESS PERMISSIONS NONE' },
 			users: {},
 		}",
@@ -293,6 +293,48 @@ async fn select_where_hnsw_knn() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn select_where_hnsw_knn_quantized() -> Result<(), Error> {
+	let sql = r"
+		CREATE pts:1 SET point = [1,2,3,4];
+		CREATE pts:2 SET point = [4,5,6,7];
+		CREATE pts:3 SET point = [8,9,10,11];
+		DEFINE INDEX hnsw_pts ON pts FIELDS point HNSW DIMENSION 4 DIST EUCLIDEAN TYPE F32 EFC 500 M 12 QUANTIZE SCALAR RERANK;
+		LET $pt = [2,3,4,5];
+		SELECT id, vector::distance::knn() AS dist FROM pts WHERE point <|2,100|> $pt;
+		ANALYZE INDEX hnsw_pts ON pts;
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(5)?;
+	// The distances are computed on the full-precision vectors
+	t.expect_val(
+		"[
+			{
+				id: pts:1,
+				dist: 2f
+			},
+			{
+				id: pts:2,
+				dist: 4f
+			}
+		]",
+	)?;
+	// Statistics of the quantized index
+	let stats = t.next_value()?;
+	for (field, expected) in [
+		("elements", Value::from(3)),
+		("quantization", Value::from("SCALAR")),
+		("quantized", Value::from(true)),
+		("rerank", Value::from(true)),
+		("element_bytes", Value::from(12)),
+		("full_element_bytes", Value::from(16)),
+		("codebook_bytes", Value::from(0)),
+	] {
+		assert_eq!(stats.pick(&[field.into()]), expected, "{field}");
+	}
+	Ok(())
+}
+
 #[tokio::test]
 async fn select_mtree_knn_with_condition() -> Result<(), Error> {
 	let sql = r"
@@ -437,6 +479,159 @@ async fn select_hnsw_knn_with_condition() -> Result<(), Error> {
 	Ok(())
 }
 
+#[test_log::test(tokio::test)]
+async fn select_hnsw_knn_with_restrictive_condition() -> Result<(), Error> {
+	// Most of the points belong to the tenant 'a'.
+	// pts:21 shares its vector with pts:1, but belongs to the tenant 'b'.
+	let mut points: Vec<String> = (1..=20)
+		.map(|i| {
+			let tenant = if i > 16 {
+				"b"
+			} else {
+				"a"
+			};
+			format!("{{ id: pts:{i}, point: [ {}f ], tenant: '{tenant}' }}", i * 10)
+		})
+		.collect();
+	points.push("{ id: pts:21, point: [ 10f ], tenant: 'b' }".to_string());
+	let sql = format!(
+		r"
+		DEFINE INDEX hn_pt ON pts FIELDS point HNSW DIMENSION 1 M 4;
+		DEFINE INDEX ix_tenant ON pts FIELDS tenant;
+		INSERT INTO pts [ {} ];
+		LET $pt = [0f];
+		SELECT id, vector::distance::knn() AS distance FROM pts
+			WHERE point <|3,3|> $pt AND tenant = 'b'
+			ORDER BY distance;
+		SELECT id, vector::distance::knn() AS distance FROM pts
+			WHERE tenant = 'c' AND point <|3,3|> $pt;
+	",
+		points.join(",")
+	);
+	let mut t = Test::new(&sql).await?;
+	t.skip_ok(4)?;
+	// The filter is applied while traversing the graph, so k results are returned
+	t.expect_val(
+		"[
+			{
+				distance: 10f,
+				id: pts:21
+			},
+			{
+				distance: 170f,
+				id: pts:17
+			},
+			{
+				distance: 180f,
+				id: pts:18
+			}
+		]",
+	)?;
+	// No record matches the filter
+	t.expect_val("[]")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_where_hnsw_knn_multi_vector() -> Result<(), Error> {
+	let sql = r"
+		DEFINE INDEX hnsw_pts ON pts FIELDS point HNSW DIMENSION 2;
+		CREATE pts:1 SET point = [[30,40],[3,4]];
+		CREATE pts:2 SET point = [[6,8]];
+		CREATE pts:3 SET point = [[12,16],[60,80]];
+		LET $pt = [0,0];
+		SELECT id, vector::distance::knn() AS dist FROM pts WHERE point <|3,10|> $pt ORDER BY dist;
+		UPDATE pts:1 SET point = [[30,40]];
+		SELECT id, vector::distance::knn() AS dist FROM pts WHERE point <|2,10|> $pt ORDER BY dist;
+		CREATE pts:4 SET point = [1,2,3];
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(5)?;
+	// Each record is returned once, with the distance of its closest vector
+	t.expect_val(
+		"[
+			{
+				id: pts:1,
+				dist: 5f
+			},
+			{
+				id: pts:2,
+				dist: 10f
+			},
+			{
+				id: pts:3,
+				dist: 20f
+			}
+		]",
+	)?;
+	t.skip_ok(1)?;
+	t.expect_val(
+		"[
+			{
+				id: pts:2,
+				dist: 10f
+			},
+			{
+				id: pts:3,
+				dist: 20f
+			}
+		]",
+	)?;
+	t.expect_error("Incorrect vector dimension (3). Expected a vector of 2 dimension.")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_where_sparse_knn() -> Result<(), Error> {
+	let sql = r"
+		DEFINE INDEX sp_emb ON docs FIELDS embedding SPARSE;
+		CREATE docs:1 SET embedding = { 1: 1.0, 5: 2.0 };
+		CREATE docs:2 SET embedding = { 1: 0.5, 7: 3.0 };
+		CREATE docs:3 SET embedding = { 5: 0.5, 9: 1.0 };
+		LET $q = { 5: 2.0, 9: 4.0 };
+		SELECT id, vector::distance::knn() AS dist FROM docs WHERE embedding <|2|> $q ORDER BY dist;
+		SELECT id, vector::distance::knn() AS dist FROM docs WHERE embedding <|1|> $q AND id != docs:3;
+		DELETE docs:3;
+		SELECT id, vector::distance::knn() AS dist FROM docs WHERE embedding <|2|> $q;
+		CREATE docs:4 SET embedding = [1, 2];
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(5)?;
+	// The distance is the negated dot product
+	t.expect_val(
+		"[
+			{
+				id: docs:3,
+				dist: -5f
+			},
+			{
+				id: docs:1,
+				dist: -4f
+			}
+		]",
+	)?;
+	t.expect_val(
+		"[
+			{
+				id: docs:1,
+				dist: -4f
+			}
+		]",
+	)?;
+	t.skip_ok(1)?;
+	// Records without any dimension in common with the query are not returned
+	t.expect_val(
+		"[
+			{
+				id: docs:1,
+				dist: -4f
+			}
+		]",
+	)?;
+	t.expect_error("The value cannot be converted to a vector: [1, 2]")?;
+	Ok(())
+}
+
 #[test_log::test(tokio::test)]
 async fn select_bruteforce_knn_with_condition() -> Result<(), Error> {
 	let sql = r"
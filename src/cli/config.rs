@@ -15,6 +15,8 @@ pub struct Config {
 	pub pass: Option<String>,
 	pub crt: Option<PathBuf>,
 	pub key: Option<PathBuf>,
+	pub ca: Option<PathBuf>,
+	pub ca_required: bool,
 	pub engine: Option<EngineOptions>,
 	pub no_identification_headers: bool,
 }
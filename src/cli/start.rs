@@ -132,6 +132,13 @@ struct StartCommandWebTlsOptions {
 	#[arg(help = "Path to the private key file for encrypted client connections")]
 	#[arg(env = "SURREAL_WEB_KEY", long = "web-key", value_parser = super::validator::file_exists)]
 	web_key: Option<PathBuf>,
+	#[arg(help = "Path to the CA file used to verify client certificates for mutual TLS")]
+	#[arg(env = "SURREAL_WEB_CA", long = "web-ca", value_parser = super::validator::file_exists)]
+	web_ca: Option<PathBuf>,
+	#[arg(help = "Whether to reject clients which do not present a certificate signed by the CA")]
+	#[arg(env = "SURREAL_WEB_CA_REQUIRED", long = "web-ca-required", requires = "web_ca")]
+	#[arg(default_value_t = false)]
+	web_ca_required: bool,
 }
 
 pub async fn init(
@@ -166,11 +173,11 @@ pub async fn init(
 	} else {
 		endpoint.path
 	};
-	// Extract the certificate and key
-	let (crt, key) = if let Some(val) = web {
-		(val.web_crt, val.web_key)
+	// Extract the certificate, key, and client certificate authority
+	let (crt, key, ca, ca_required) = if let Some(val) = web {
+		(val.web_crt, val.web_key, val.web_ca, val.web_ca_required)
 	} else {
-		(None, None)
+		(None, None, None, false)
 	};
 	// Setup the command-line options
 	let _ = config::CF.set(Config {
@@ -183,6 +190,8 @@ pub async fn init(
 		engine: Some(EngineOptions::default().with_tick_interval(tick_interval)),
 		crt,
 		key,
+		ca,
+		ca_required,
 	});
 	// This is the cancellation token propagated down to
 	// all the async functions that needs to be stopped gracefully.
@@ -191,6 +200,15 @@ pub async fn init(
 	env::init().await?;
 	// Start the datastore
 	let ds = Arc::new(dbs::init(dbs).await?);
+	// Re-encrypt stored values with the current encryption key
+	let reencrypt = tokio::spawn({
+		let ds = ds.clone();
+		async move {
+			if let Err(e) = ds.reencrypt().await {
+				error!("Failed to re-encrypt stored values: {e}");
+			}
+		}
+	});
 	// Start the node agent
 	let (tasks, task_chans) =
 		start_tasks(&config::CF.get().unwrap().engine.unwrap_or_default(), ds.clone());
@@ -203,6 +221,7 @@ pub async fn init(
 		}
 	});
 	ct.cancel();
+	reencrypt.abort();
 	tasks.resolve().await?;
 	// All ok
 	Ok(())
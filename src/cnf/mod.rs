@@ -71,6 +71,14 @@ pub static WEBSOCKET_MAX_MESSAGE_SIZE: LazyLock<usize> =
 pub static WEBSOCKET_MAX_CONCURRENT_REQUESTS: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_WEBSOCKET_MAX_CONCURRENT_REQUESTS", usize, 24);
 
+/// How many additional sessions can be opened on each WebSocket (defaults to 100)
+pub static WEBSOCKET_MAX_SESSIONS: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_WEBSOCKET_MAX_SESSIONS", usize, 100);
+
+/// How many rows are sent in each batch of a streamed WebSocket request (defaults to 1000)
+pub static WEBSOCKET_STREAM_BATCH_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_WEBSOCKET_STREAM_BATCH_SIZE", usize, 1000);
+
 /// What is the runtime thread memory stack size (defaults to 10MiB)
 pub static RUNTIME_STACK_SIZE: LazyLock<usize> =
 	lazy_env_parse_or_else!("SURREAL_RUNTIME_STACK_SIZE", usize, |_| {
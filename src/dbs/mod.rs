@@ -5,7 +5,8 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use surrealdb::dbs::capabilities::{Capabilities, FuncTarget, NetTarget, Targets};
-use surrealdb::kvs::Datastore;
+use surrealdb::iam::audit::{AuditLog, AuditSink};
+use surrealdb::kvs::{Datastore, EncryptionKey, Keyring};
 
 #[derive(Args, Debug)]
 pub struct StartCommandDbsOptions {
@@ -32,6 +33,31 @@ pub struct StartCommandDbsOptions {
 	#[arg(env = "SURREAL_TEMPORARY_DIRECTORY", long = "temporary-directory")]
 	#[arg(value_parser = super::cli::validator::dir_exists)]
 	temporary_directory: Option<PathBuf>,
+	#[arg(help = "Whether to record authentication and privileged operations in the audit log")]
+	#[arg(env = "SURREAL_AUDIT", long = "audit", requires = "audit_key")]
+	#[arg(default_value_t = false)]
+	audit: bool,
+	#[arg(
+		help = "The secret used to sign audit log entries, so that modified entries can be detected"
+	)]
+	#[arg(env = "SURREAL_AUDIT_KEY", long = "audit-key", requires = "audit")]
+	#[arg(hide_env_values = true)]
+	audit_key: Option<String>,
+	#[arg(help = "Streams audit log entries to a sink, either 'log' or the path of a file")]
+	#[arg(env = "SURREAL_AUDIT_SINK", long = "audit-sink", requires = "audit")]
+	audit_sink: Option<String>,
+	#[arg(help = "The secret used to encrypt stored values at rest")]
+	#[arg(env = "SURREAL_ENCRYPTION_KEY", long = "encryption-key")]
+	#[arg(conflicts_with = "encryption_key_file", hide_env_values = true)]
+	encryption_key: Option<String>,
+	#[arg(help = "The path of a file containing the secret used to encrypt stored values at rest")]
+	#[arg(env = "SURREAL_ENCRYPTION_KEY_FILE", long = "encryption-key-file")]
+	#[arg(value_parser = super::cli::validator::file_exists)]
+	encryption_key_file: Option<PathBuf>,
+	#[arg(help = "Previous encryption secrets, used to read values until they are re-encrypted")]
+	#[arg(env = "SURREAL_PREVIOUS_ENCRYPTION_KEYS", long = "previous-encryption-keys")]
+	#[arg(value_delimiter = ',', hide_env_values = true)]
+	previous_encryption_keys: Vec<String>,
 }
 
 #[derive(Args, Debug)]
@@ -208,6 +234,12 @@ pub async fn init(
 		unauthenticated,
 		capabilities,
 		temporary_directory,
+		audit,
+		audit_key,
+		audit_sink,
+		encryption_key,
+		encryption_key_file,
+		previous_encryption_keys,
 	}: StartCommandDbsOptions,
 ) -> Result<Datastore, Error> {
 	// Get local copy of options
@@ -230,6 +262,34 @@ pub async fn init(
 	}
 	// Log the specified server capabilities
 	debug!("Server capabilities: {capabilities}");
+	// Setup the audit log if enabled
+	let audit = match audit_key.filter(|_| audit) {
+		None => None,
+		Some(key) => {
+			let audit = AuditLog::new(key.as_bytes());
+			Some(match audit_sink.as_deref() {
+				None => audit,
+				Some("log") => audit.with_sink(Some(AuditSink::Log)),
+				Some(path) => audit.with_sink(Some(AuditSink::file(path)?)),
+			})
+		}
+	};
+	if audit.is_some() {
+		debug!("Audit logging is enabled");
+	}
+	// Setup encryption at rest if enabled
+	let secret = match (encryption_key, encryption_key_file) {
+		(Some(key), _) => Some(key.into_bytes()),
+		(None, Some(path)) => {
+			let mut secret = std::fs::read(path)?;
+			// Ignore a trailing newline in the key file
+			while secret.last().is_some_and(u8::is_ascii_whitespace) {
+				secret.pop();
+			}
+			Some(secret)
+		}
+		(None, None) => None,
+	};
 	// Parse and setup the desired kv datastore
 	let dbs = Datastore::new(&opt.path)
 		.await?
@@ -239,9 +299,24 @@ pub async fn init(
 		.with_transaction_timeout(transaction_timeout)
 		.with_auth_enabled(!unauthenticated)
 		.with_temporary_directory(temporary_directory)
-		.with_capabilities(capabilities);
+		.with_capabilities(capabilities)
+		.with_audit(audit);
 	// Ensure the storage version is up-to-date to prevent corruption
 	dbs.check_version().await?;
+	// Derive the encryption keys from the salt stored in the datastore
+	let dbs = match secret {
+		Some(secret) => {
+			let salt = dbs.encryption_salt().await?;
+			let previous = previous_encryption_keys
+				.iter()
+				.map(|v| EncryptionKey::new(v.as_bytes(), &salt))
+				.collect::<Result<Vec<_>, _>>()?;
+			let keyring = Keyring::new(EncryptionKey::new(&secret, &salt)?).with_previous(previous);
+			debug!("Encryption at rest is enabled");
+			dbs.with_encryption(Some(keyring))
+		}
+		None => dbs,
+	};
 	// Setup initial server auth credentials
 	if let (Some(user), Some(pass)) = (opt.user.as_ref(), opt.pass.as_ref()) {
 		dbs.initialise_credentials(user, pass).await?;
@@ -46,6 +46,9 @@ pub enum Error {
 	#[error("Couldn't open the specified file: {0}")]
 	Io(#[from] IoError),
 
+	#[error("There was a problem with TLS: {0}")]
+	Tls(String),
+
 	#[error("There was an error with the network: {0}")]
 	Axum(#[from] AxumError),
 
@@ -10,7 +10,7 @@ use http::{request::Parts, StatusCode};
 use hyper::{Request, Response};
 use surrealdb::{
 	dbs::Session,
-	iam::verify::{basic, token},
+	iam::verify::{basic, certificate, token},
 };
 use tower_http::auth::AsyncAuthorizeRequest;
 use uuid::Uuid;
@@ -23,12 +23,13 @@ use super::{
 		parse_typed_header, SurrealAuthDatabase, SurrealAuthNamespace, SurrealDatabase, SurrealId,
 		SurrealNamespace,
 	},
+	tls::ClientCertificate,
 	AppState,
 };
 
 ///
 /// SurrealAuth is a tower layer that implements the AsyncAuthorizeRequest trait.
-/// It is used to authorize requests to SurrealDB using Basic or Token authentication.
+/// It is used to authorize requests to SurrealDB using Basic, Token or client certificate authentication.
 ///
 /// It has to be used in conjunction with the tower_http::auth::RequireAuthorizationLayer layer:
 ///
@@ -134,6 +135,22 @@ async fn check_auth(parts: &mut Parts) -> Result<Session, Error> {
 	session.ns = ns;
 	session.db = db;
 
+	// If a verified client certificate was presented without any other authentication data,
+	// the level is the one bound by the certificate, regardless of the authentication headers
+	if let Some(Some(cert)) = parts.extensions.get::<Option<ClientCertificate>>() {
+		if !parts.headers.contains_key(http::header::AUTHORIZATION) {
+			certificate(
+				kvs,
+				&mut session,
+				&cert.name,
+				cert.access.as_deref(),
+				cert.ns.as_deref(),
+				cert.db.as_deref(),
+			)
+			.await?;
+		}
+	}
+
 	// If Basic authentication data was supplied
 	if let Ok(au) = parts.extract::<TypedHeader<Authorization<Basic>>>().await {
 		basic(
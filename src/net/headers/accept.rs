@@ -11,6 +11,7 @@ pub enum Accept {
 	ApplicationPack,
 	ApplicationOctetStream,
 	Surrealdb,
+	ApplicationArrow,
 }
 
 impl std::fmt::Display for Accept {
@@ -22,6 +23,7 @@ impl std::fmt::Display for Accept {
 			Accept::ApplicationPack => write!(f, "application/pack"),
 			Accept::ApplicationOctetStream => write!(f, "application/octet-stream"),
 			Accept::Surrealdb => write!(f, "application/surrealdb"),
+			Accept::ApplicationArrow => write!(f, "application/vnd.apache.arrow.stream"),
 		}
 	}
 }
@@ -46,6 +48,7 @@ impl Header for Accept {
 			"application/pack" => Ok(Accept::ApplicationPack),
 			"application/octet-stream" => Ok(Accept::ApplicationOctetStream),
 			"application/surrealdb" => Ok(Accept::Surrealdb),
+			"application/vnd.apache.arrow.stream" => Ok(Accept::ApplicationArrow),
 			// TODO: Support more (all?) mime-types
 			_ => Err(headers::Error::invalid()),
 		}
@@ -37,7 +37,7 @@ async fn handler(
 	// Convert the body to a byte slice
 	let sql = bytes_to_utf8(&sql)?;
 	// Check the permissions level
-	db.check(&session, Edit, Any.on_level(session.au.level().to_owned()))?;
+	db.check(&session, Edit, Any.on_level(session.au.level().to_owned())).await?;
 	// Execute the sql query in the database
 	match db.import(sql, &session).await {
 		Ok(res) => match accept.as_deref() {
@@ -13,25 +13,19 @@ use axum::Router;
 use axum_extra::extract::Query;
 use axum_extra::TypedHeader;
 use bytes::Bytes;
-use serde::Deserialize;
-use std::str;
 use surrealdb::dbs::Session;
 use surrealdb::iam::check::check_ns_db;
-use surrealdb::sql::Value;
+use surrealdb::rest::openapi::generate_document;
+use surrealdb::rest::QueryOptions;
+use surrealdb::sql::{Query as SqlQuery, Statement, Value};
 use tower_http::limit::RequestBodyLimitLayer;
 
-#[derive(Default, Deserialize, Debug, Clone)]
-struct QueryOptions {
-	pub limit: Option<i64>,
-	pub start: Option<i64>,
-	pub fields: Option<Vec<String>>,
-}
-
 pub(super) fn router<S>() -> Router<S>
 where
 	S: Clone + Send + Sync + 'static,
 {
 	Router::new()
+		.route("/key", options(|| async {}).get(openapi))
 		.route(
 			"/key/:table",
 			options(|| async {})
@@ -59,6 +53,24 @@ where
 		)
 }
 
+// ------------------------------
+// Routes for the database
+// ------------------------------
+
+async fn openapi(
+	Extension(state): Extension<AppState>,
+	Extension(session): Extension<Session>,
+) -> Result<impl IntoResponse, impl IntoResponse> {
+	// Get the datastore reference
+	let db = &state.datastore;
+	// Generate the document from the table definitions
+	match generate_document(db, &session).await {
+		Ok(doc) => Ok(output::json(&doc)),
+		// There was an error when reading the definitions
+		Err(err) => Err(Error::from(err)),
+	}
+}
+
 // ------------------------------
 // Routes for a table
 // ------------------------------
@@ -74,20 +86,10 @@ async fn select_all(
 	let db = &state.datastore;
 	// Ensure a NS and DB are set
 	let _ = check_ns_db(&session)?;
-	// Specify the request statement
-	let sql = match query.fields {
-		None => "SELECT * FROM type::table($table) LIMIT $limit START $start",
-		_ => "SELECT type::fields($fields) FROM type::table($table) LIMIT $limit START $start",
-	};
-	// Specify the request variables
-	let vars = map! {
-		String::from("table") => Value::from(table),
-		String::from("start") => Value::from(query.start.unwrap_or(0)),
-		String::from("limit") => Value::from(query.limit.unwrap_or(100)),
-		String::from("fields") => Value::from(query.fields.unwrap_or_default()),
-	};
+	// Compile the query parameters into a statement
+	let stm = query.select(&table, None)?;
 	// Execute the query and return the result
-	match db.execute(sql, &session, Some(vars)).await {
+	match db.process(SqlQuery::from(Statement::Select(stm)), &session, None).await {
 		Ok(res) => match accept.as_deref() {
 			// Simple serialization
 			Some(Accept::ApplicationJson) => Ok(output::json(&output::simplify(res))),
@@ -289,24 +291,15 @@ async fn select_one(
 	let db = &state.datastore;
 	// Ensure a NS and DB are set
 	let _ = check_ns_db(&session)?;
-	// Specify the request statement
-	let sql = match query.fields {
-		None => "SELECT * FROM type::thing($table, $id)",
-		_ => "SELECT type::fields($fields) FROM type::thing($table, $id)",
-	};
 	// Parse the Record ID as a SurrealQL value
 	let rid = match surrealdb::sql::json(&id) {
 		Ok(id) => id,
 		Err(_) => Value::from(id),
 	};
-	// Specify the request variables
-	let vars = map! {
-		String::from("table") => Value::from(table),
-		String::from("id") => rid,
-		String::from("fields") => Value::from(query.fields.unwrap_or_default()),
-	};
+	// Compile the query parameters into a statement
+	let stm = query.select(&table, Some(rid))?;
 	// Execute the query and return the result
-	match db.execute(sql, &session, Some(vars)).await {
+	match db.process(SqlQuery::from(Statement::Select(stm)), &session, None).await {
 		Ok(res) => match accept.as_deref() {
 			// Simple serialization
 			Some(Accept::ApplicationJson) => Ok(output::json(&output::simplify(res))),
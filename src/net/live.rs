@@ -0,0 +1,119 @@
+use super::AppState;
+use crate::err::Error;
+use crate::rpc::RpcState;
+use axum::extract::{Path, State};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::IntoResponse;
+use axum::routing::options;
+use axum::Extension;
+use axum::Router;
+use axum_extra::extract::Query;
+use chrono::Utc;
+use futures::{future, stream, StreamExt};
+use serde_json::json;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+use surrealdb::channel;
+use surrealdb::dbs::{Notification, Session};
+use surrealdb::iam::check::check_ns_db;
+use surrealdb::kvs::Datastore;
+use surrealdb::rest::LiveOptions;
+use surrealdb::sql::{Query as SqlQuery, Statement, Value};
+use tokio::time::sleep;
+use uuid::Uuid;
+
+/// The number of notifications which are queued for a client before delivery waits
+const EVENT_STREAM_CAPACITY: usize = 64;
+
+pub(super) fn router() -> Router<Arc<RpcState>> {
+	Router::new().route("/live/:table", options(|| async {}).get(handler))
+}
+
+async fn handler(
+	Extension(state): Extension<AppState>,
+	Extension(mut session): Extension<Session>,
+	State(rpc_state): State<Arc<RpcState>>,
+	Path(table): Path<String>,
+	Query(query): Query<LiveOptions>,
+) -> Result<impl IntoResponse, impl IntoResponse> {
+	// Get the datastore reference
+	let db = state.datastore.clone();
+	// Ensure a NS and DB are set
+	let _ = check_ns_db(&session)?;
+	// Live queries can only run on a realtime session
+	session.rt = true;
+	// Compile the query parameters into a statement
+	let stm = query.live(&table)?;
+	// Route the notifications of the live query to this stream, before any are sent
+	let id = stm.id.0;
+	let (sender, receiver) = channel::bounded(EVENT_STREAM_CAPACITY);
+	rpc_state.event_streams.write().await.insert(id, sender);
+	// Register the live query
+	let res = db.process(SqlQuery::from(Statement::Live(stm)), &session, None).await;
+	let res = match res.map(|mut v| v.remove(0).result) {
+		Ok(Ok(Value::Uuid(v))) if v.0 == id => Ok(()),
+		Ok(Ok(_)) => Err(Error::Other("Expected the id of the live query".to_owned())),
+		Ok(Err(err)) | Err(err) => Err(Error::from(err)),
+	};
+	if let Err(err) = res {
+		rpc_state.event_streams.write().await.remove(&id);
+		return Err(err);
+	}
+	// The live query is killed when the client disconnects
+	let guard = LiveGuard {
+		id,
+		datastore: db,
+		state: rpc_state,
+	};
+	let events = stream::unfold((receiver, guard), |(receiver, guard)| async move {
+		let notification = receiver.recv().await.ok()?;
+		Some((Ok::<_, Infallible>(event(notification)), (receiver, guard)))
+	});
+	// The stream ends once the session expires
+	let exp = session.exp;
+	let expired = async move {
+		match exp {
+			Some(exp) => {
+				let secs = (exp + 1 - Utc::now().timestamp()).max(0) as u64;
+				sleep(Duration::from_secs(secs)).await
+			}
+			None => future::pending().await,
+		}
+	};
+	let events = events.take_until(expired);
+	Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// Converts a notification into an event, which is named after its action
+fn event(notification: Notification) -> Event {
+	let action = notification.action.to_string();
+	let data = json!({
+		"id": notification.id.to_raw(),
+		"action": action,
+		"result": notification.result.into_json(),
+	});
+	Event::default().event(action).data(data.to_string())
+}
+
+/// Kills a live query which is streamed over HTTP, once the stream is dropped
+struct LiveGuard {
+	id: Uuid,
+	datastore: Arc<Datastore>,
+	state: Arc<RpcState>,
+}
+
+impl Drop for LiveGuard {
+	fn drop(&mut self) {
+		let id = self.id;
+		let datastore = self.datastore.clone();
+		let state = self.state.clone();
+		tokio::spawn(async move {
+			trace!("Removing live query: {}", id);
+			state.event_streams.write().await.remove(&id);
+			if let Err(err) = datastore.delete_queries(vec![id]).await {
+				error!("Error killing live query {}: {}", id, err);
+			}
+		});
+	}
+}
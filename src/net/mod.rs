@@ -8,6 +8,7 @@ mod health;
 mod import;
 mod input;
 mod key;
+mod live;
 mod ml;
 pub(crate) mod output;
 mod params;
@@ -17,6 +18,7 @@ mod signin;
 mod signup;
 mod sql;
 mod sync;
+mod tls;
 mod tracer;
 mod version;
 
@@ -29,7 +31,6 @@ use crate::telemetry::metrics::HttpMetricsLayer;
 use axum::response::Redirect;
 use axum::routing::get;
 use axum::{middleware, Router};
-use axum_server::tls_rustls::RustlsConfig;
 use axum_server::Handle;
 use http::header;
 use std::net::SocketAddr;
@@ -95,7 +96,9 @@ pub async fn init(ds: Arc<Datastore>, ct: CancellationToken) -> Result<(), Error
 				// Don't compress gRPC
 				.and(NotForContentType::GRPC)
 				// Don't compress images
-				.and(NotForContentType::IMAGES),
+				.and(NotForContentType::IMAGES)
+				// Don't compress event streams
+				.and(NotForContentType::const_new("text/event-stream")),
 		),
 	);
 
@@ -172,6 +175,7 @@ pub async fn init(ds: Arc<Datastore>, ct: CancellationToken) -> Result<(), Error
 		.merge(signin::router())
 		.merge(signup::router())
 		.merge(key::router())
+		.merge(live::router())
 		.merge(ml::router());
 
 	let axum_app = if *GRAPHQL_ENABLE {
@@ -206,9 +210,9 @@ pub async fn init(ds: Arc<Datastore>, ct: CancellationToken) -> Result<(), Error
 	// If a certificate and key are specified then setup TLS
 	if let (Some(cert), Some(key)) = (&opt.crt, &opt.key) {
 		// Configure certificate and private key used by https
-		let tls = RustlsConfig::from_pem_file(cert, key).await.unwrap();
-		// Setup the Axum server with TLS
-		let server = axum_server::bind_rustls(opt.bind, tls);
+		let tls = tls::config(cert, key, opt.ca.as_deref(), opt.ca_required).await?;
+		// Setup the Axum server with TLS, verifying any client certificates
+		let server = axum_server::bind(opt.bind).acceptor(tls::ClientCertAcceptor::new(tls));
 		// Log the server startup to the CLI
 		info!(target: LOG, "Started web server on {}", &opt.bind);
 		// Start the server and listen for connections
@@ -15,6 +15,8 @@ pub enum Output {
 	Cbor(Vec<u8>), // CBOR
 	Pack(Vec<u8>), // MessagePack
 	Full(Vec<u8>), // Full type serialization
+	#[cfg(feature = "arrow")]
+	Arrow(Vec<u8>), // Arrow IPC stream
 }
 
 pub fn none() -> Output {
@@ -66,6 +68,11 @@ where
 	}
 }
 
+#[cfg(feature = "arrow")]
+pub fn arrow(val: Vec<u8>) -> Output {
+	Output::Arrow(val)
+}
+
 /// Convert and simplify the value into JSON
 pub fn simplify<T: Serialize + 'static>(v: T) -> Json {
 	sql::to_value(v).unwrap().into()
@@ -89,6 +96,10 @@ impl IntoResponse for Output {
 			Output::Full(v) => {
 				([(CONTENT_TYPE, HeaderValue::from(Accept::Surrealdb))], v).into_response()
 			}
+			#[cfg(feature = "arrow")]
+			Output::Arrow(v) => {
+				([(CONTENT_TYPE, HeaderValue::from(Accept::ApplicationArrow))], v).into_response()
+			}
 			Output::None => StatusCode::OK.into_response(),
 			Output::Fail => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
 		}
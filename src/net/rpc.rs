@@ -26,6 +26,8 @@ use http::HeaderMap;
 use http::HeaderValue;
 use surrealdb::dbs::Session;
 use surrealdb::kvs::Datastore;
+#[cfg(feature = "arrow")]
+use surrealdb::rpc::format::arrow;
 use surrealdb::rpc::format::Format;
 use surrealdb::rpc::format::PROTOCOLS;
 use surrealdb::rpc::method::Method;
@@ -38,6 +40,7 @@ use super::headers::ContentType;
 use super::AppState;
 
 use surrealdb::rpc::rpc_context::RpcContext;
+use surrealdb::rpc::RpcError;
 
 pub(super) fn router() -> Router<Arc<RpcState>> {
 	Router::new()
@@ -154,7 +157,19 @@ async fn post_handler(
 
 	match fmt.req_http(body) {
 		Ok(req) => {
-			let res = rpc_ctx.execute(Method::parse(req.method), req.params).await;
+			let res = match (req.session, req.stream) {
+				// Sessions and streaming are only supported over WebSocket connections
+				(Some(_), _) | (_, true) => Err(RpcError::InvalidRequest),
+				_ => rpc_ctx.execute(req.txn, Method::parse(req.method), req.params).await,
+			};
+			// Arrow responses encode each result with the field definitions of its table
+			#[cfg(feature = "arrow")]
+			let res = match (fmt, res) {
+				(Format::Arrow, Ok(data)) => {
+					arrow::results(rpc_ctx.kvs(), rpc_ctx.session(), data).await.map_err(Into::into)
+				}
+				(_, res) => res,
+			};
 			fmt.res_http(res.into_response(None)).map_err(Error::from)
 		}
 		Err(err) => Err(Error::from(err)),
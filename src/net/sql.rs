@@ -4,6 +4,8 @@ use crate::cnf::HTTP_MAX_SQL_BODY_SIZE;
 use crate::err::Error;
 use crate::net::input::bytes_to_utf8;
 use crate::net::output;
+#[cfg(feature = "arrow")]
+use crate::net::output::Output;
 use crate::net::params::Params;
 use axum::extract::ws::Message;
 use axum::extract::ws::WebSocket;
@@ -17,7 +19,17 @@ use axum::Router;
 use axum_extra::TypedHeader;
 use bytes::Bytes;
 use futures::{SinkExt, StreamExt};
+#[cfg(feature = "arrow")]
+use std::collections::HashMap;
+#[cfg(feature = "arrow")]
+use surrealdb::dbs::Response;
 use surrealdb::dbs::Session;
+#[cfg(feature = "arrow")]
+use surrealdb::kvs::Datastore;
+#[cfg(feature = "arrow")]
+use surrealdb::rpc::format::arrow;
+#[cfg(feature = "arrow")]
+use surrealdb::sql::Value;
 use tower_http::limit::RequestBodyLimitLayer;
 
 pub(super) fn router<S>() -> Router<S>
@@ -50,6 +62,9 @@ async fn post_handler(
 			Some(Accept::ApplicationPack) => Ok(output::pack(&output::simplify(res))),
 			// Internal serialization
 			Some(Accept::Surrealdb) => Ok(output::full(&res)),
+			// Columnar serialization
+			#[cfg(feature = "arrow")]
+			Some(Accept::ApplicationArrow) => to_arrow(db, &session, res).await,
 			// An incorrect content-type was requested
 			_ => Err(Error::InvalidType),
 		},
@@ -58,6 +73,21 @@ async fn post_handler(
 	}
 }
 
+/// Converts the result of the final statement into an Arrow IPC stream
+#[cfg(feature = "arrow")]
+async fn to_arrow(db: &Datastore, session: &Session, res: Vec<Response>) -> Result<Output, Error> {
+	// Return the first error if any statement failed
+	let mut val = Value::None;
+	for res in res {
+		val = res.result?;
+	}
+	// Use the field definitions of the table the records belong to
+	let fields = arrow::fields(db, session, &val).await?;
+	// Serialize the records into record batches
+	let out = arrow::encode(val, &fields, HashMap::new())?;
+	Ok(output::arrow(out))
+}
+
 async fn ws_handler(
 	ws: WebSocketUpgrade,
 	Extension(state): Extension<AppState>,
@@ -0,0 +1,243 @@
+use crate::err::Error;
+use axum::middleware::AddExtension;
+use axum::Extension;
+use axum_server::accept::Accept;
+use axum_server::tls_rustls::{RustlsAcceptor, RustlsConfig};
+use futures_util::future::BoxFuture;
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
+use rustls_pemfile::{certs, private_key};
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::Path;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::server::TlsStream;
+use tower::Layer;
+use x509_parser::prelude::{AttributeTypeAndValue, FromDer, GeneralName, X509Certificate};
+
+/// The scheme of the subject alternative name which binds a certificate to a level
+const LEVEL_SCHEME: &str = "surrealdb://";
+
+///
+/// ClientCertificate is the identity presented by a client with a verified certificate.
+///
+/// The common name of the certificate subject is the name of a system user, or the id of
+/// a record when the organizational unit names the record access method to authenticate with.
+/// The namespace and database are bound by a `surrealdb://<ns>/<db>` or `surrealdb://<ns>`
+/// subject alternative name, and certificates without one authenticate root users.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct ClientCertificate {
+	pub name: String,
+	pub access: Option<String>,
+	pub ns: Option<String>,
+	pub db: Option<String>,
+}
+
+impl ClientCertificate {
+	/// Extracts the identity from a DER encoded certificate
+	fn from_der(der: &[u8]) -> Option<Self> {
+		let (_, cert) = X509Certificate::from_der(der).ok()?;
+		// Subjects with several names or access methods are ambiguous
+		let name = attribute(cert.subject().iter_common_name())??;
+		let access = attribute(cert.subject().iter_organizational_unit())?;
+		// Certificates bound to several levels are ambiguous
+		let levels = match cert.subject_alternative_name().ok()? {
+			Some(san) => san
+				.value
+				.general_names
+				.iter()
+				.filter_map(|v| match v {
+					GeneralName::URI(uri) => uri.strip_prefix(LEVEL_SCHEME),
+					_ => None,
+				})
+				.collect(),
+			None => vec![],
+		};
+		let (ns, db) = match levels.as_slice() {
+			[] => (None, None),
+			[level] => match level.split_once('/') {
+				Some((ns, db)) if !ns.is_empty() && !db.is_empty() && !db.contains('/') => {
+					(Some(ns.to_owned()), Some(db.to_owned()))
+				}
+				None if !level.is_empty() => (Some(level.to_string()), None),
+				_ => return None,
+			},
+			_ => return None,
+		};
+		Some(Self {
+			name,
+			access,
+			ns,
+			db,
+		})
+	}
+}
+
+/// Returns the value of an attribute which is present at most once, or
+/// nothing if the attribute is repeated or is not a string
+fn attribute<'a, 'b: 'a>(
+	mut values: impl Iterator<Item = &'a AttributeTypeAndValue<'b>>,
+) -> Option<Option<String>> {
+	let value = match values.next() {
+		Some(v) => Some(v.as_str().ok()?.to_owned()),
+		None => None,
+	};
+	match values.next() {
+		Some(_) => None,
+		None => Some(value),
+	}
+}
+
+/// Configures the certificate and private key used by https, and optionally the
+/// certificate authority which client certificates are verified against.
+pub(super) async fn config(
+	crt: &Path,
+	key: &Path,
+	ca: Option<&Path>,
+	required: bool,
+) -> Result<RustlsConfig, Error> {
+	// Without a certificate authority clients are not asked for a certificate
+	let Some(ca) = ca else {
+		return Ok(RustlsConfig::from_pem_file(crt, key).await?);
+	};
+	// Load the certificate authorities which sign client certificates
+	let mut roots = RootCertStore::empty();
+	for cert in certs(&mut BufReader::new(File::open(ca)?)) {
+		roots.add(cert?).map_err(|e| Error::Tls(e.to_string()))?;
+	}
+	let verifier = WebPkiClientVerifier::builder(Arc::new(roots));
+	// Unless required, clients can still connect without a certificate
+	let verifier = match required {
+		true => verifier,
+		false => verifier.allow_unauthenticated(),
+	};
+	let verifier = verifier.build().map_err(|e| Error::Tls(e.to_string()))?;
+	// Load the certificate and private key used by https
+	let crt = certs(&mut BufReader::new(File::open(crt)?)).collect::<Result<Vec<_>, _>>()?;
+	let key = private_key(&mut BufReader::new(File::open(key)?))?
+		.ok_or_else(|| Error::Tls("The private key file does not contain a key".to_string()))?;
+	let mut config = ServerConfig::builder()
+		.with_client_cert_verifier(verifier)
+		.with_single_cert(crt, key)
+		.map_err(|e| Error::Tls(e.to_string()))?;
+	config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+	Ok(RustlsConfig::from_config(Arc::new(config)))
+}
+
+///
+/// ClientCertAcceptor accepts TLS connections, and makes the identity of any verified
+/// client certificate available to each request on the connection.
+///
+#[derive(Clone)]
+pub(super) struct ClientCertAcceptor {
+	inner: RustlsAcceptor,
+}
+
+impl ClientCertAcceptor {
+	pub(super) fn new(config: RustlsConfig) -> Self {
+		Self {
+			inner: RustlsAcceptor::new(config),
+		}
+	}
+}
+
+impl<I, S> Accept<I, S> for ClientCertAcceptor
+where
+	I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+	S: Send + 'static,
+{
+	type Stream = TlsStream<I>;
+	type Service = AddExtension<S, Option<ClientCertificate>>;
+	type Future = BoxFuture<'static, io::Result<(Self::Stream, Self::Service)>>;
+
+	fn accept(&self, stream: I, service: S) -> Self::Future {
+		let acceptor = self.inner.clone();
+		Box::pin(async move {
+			let (stream, service) = acceptor.accept(stream, service).await?;
+			// The certificate chain has been verified during the handshake
+			let cert = stream
+				.get_ref()
+				.1
+				.peer_certificates()
+				.and_then(|certs| certs.first())
+				.and_then(|cert| ClientCertificate::from_der(cert.as_ref()));
+			Ok((stream, Extension(cert).layer(service)))
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rcgen::{Certificate, CertificateParams, DistinguishedName, DnType, SanType};
+
+	fn certificate(dn: &[(DnType, &str)], levels: &[&str]) -> Vec<u8> {
+		let mut params = CertificateParams::new(vec!["localhost".to_string()]);
+		params.distinguished_name = DistinguishedName::new();
+		for (ty, val) in dn {
+			params.distinguished_name.push(ty.clone(), *val);
+		}
+		for level in levels {
+			params.subject_alt_names.push(SanType::URI(format!("{LEVEL_SCHEME}{level}")));
+		}
+		Certificate::from_params(params).unwrap().serialize_der().unwrap()
+	}
+
+	#[test]
+	fn client_certificate_user() {
+		let der = certificate(
+			&[(DnType::OrganizationName, "SurrealDB"), (DnType::CommonName, "alice")],
+			&[],
+		);
+		let cert = ClientCertificate::from_der(&der).unwrap();
+		assert_eq!(
+			cert,
+			ClientCertificate {
+				name: "alice".to_string(),
+				access: None,
+				ns: None,
+				db: None,
+			}
+		);
+		let der = certificate(&[(DnType::CommonName, "alice")], &["test"]);
+		let cert = ClientCertificate::from_der(&der).unwrap();
+		assert_eq!(cert.ns, Some("test".to_string()));
+		assert_eq!(cert.db, None);
+	}
+
+	#[test]
+	fn client_certificate_record() {
+		let der = certificate(
+			&[
+				(DnType::CommonName, "service:billing"),
+				(DnType::OrganizationalUnitName, "services"),
+			],
+			&["test/test"],
+		);
+		let cert = ClientCertificate::from_der(&der).unwrap();
+		assert_eq!(
+			cert,
+			ClientCertificate {
+				name: "service:billing".to_string(),
+				access: Some("services".to_string()),
+				ns: Some("test".to_string()),
+				db: Some("test".to_string()),
+			}
+		);
+	}
+
+	#[test]
+	fn client_certificate_invalid() {
+		// Certificates without a name
+		let der = certificate(&[(DnType::OrganizationName, "SurrealDB")], &[]);
+		assert_eq!(ClientCertificate::from_der(&der), None);
+		assert_eq!(ClientCertificate::from_der(&der[..der.len() / 2]), None);
+		// Certificates bound to several or to invalid levels
+		for levels in [&["test", "other"][..], &["test/"], &["/test"], &["a/b/c"], &[""]] {
+			let der = certificate(&[(DnType::CommonName, "alice")], levels);
+			assert_eq!(ClientCertificate::from_der(&der), None, "{levels:?}");
+		}
+	}
+}
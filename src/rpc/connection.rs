@@ -1,9 +1,10 @@
 use crate::cnf::{
-	PKG_NAME, PKG_VERSION, WEBSOCKET_MAX_CONCURRENT_REQUESTS, WEBSOCKET_PING_FREQUENCY,
+	PKG_NAME, PKG_VERSION, WEBSOCKET_MAX_CONCURRENT_REQUESTS, WEBSOCKET_MAX_SESSIONS,
+	WEBSOCKET_PING_FREQUENCY, WEBSOCKET_STREAM_BATCH_SIZE,
 };
 use crate::rpc::failure::Failure;
 use crate::rpc::format::WsFormat;
-use crate::rpc::response::{failure, IntoRpcResponse};
+use crate::rpc::response::{failure, success, IntoRpcResponse};
 use crate::rpc::CONN_CLOSED_ERR;
 use crate::telemetry;
 use crate::telemetry::metrics::ws::RequestContext;
@@ -13,17 +14,23 @@ use futures_util::stream::{SplitSink, SplitStream};
 use futures_util::{SinkExt, StreamExt};
 use opentelemetry::trace::FutureExt;
 use opentelemetry::Context as TelemetryContext;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 use surrealdb::channel::{self, Receiver, Sender};
-use surrealdb::dbs::Session;
+use surrealdb::ctx::Canceller;
+use surrealdb::dbs::{Batch, Session};
 #[cfg(surrealdb_unstable)]
 use surrealdb::gql::{Pessimistic, SchemaCache};
 use surrealdb::kvs::Datastore;
+#[cfg(feature = "arrow")]
+use surrealdb::rpc::format::arrow;
 use surrealdb::rpc::format::Format;
 use surrealdb::rpc::method::Method;
 use surrealdb::rpc::Data;
+use surrealdb::rpc::Prepared;
 use surrealdb::rpc::RpcContext;
+#[cfg(feature = "arrow")]
+use surrealdb::rpc::RpcError;
 use surrealdb::sql::Array;
 use surrealdb::sql::Value;
 use tokio::sync::{RwLock, Semaphore};
@@ -35,11 +42,20 @@ use uuid::Uuid;
 
 use super::RpcState;
 
+tokio::task_local! {
+	/// The canceller of the request which is being processed by the current task
+	static CANCELLER: Canceller;
+}
+
 pub struct Connection {
 	pub(crate) id: Uuid,
 	pub(crate) format: Format,
 	pub(crate) session: Session,
 	pub(crate) vars: BTreeMap<String, Value>,
+	pub(crate) sessions: HashMap<Uuid, Arc<RwLock<Connection>>>,
+	pub(crate) live_queries: RwLock<HashSet<Uuid>>,
+	pub(crate) requests: Arc<RwLock<HashMap<Value, Canceller>>>,
+	pub(crate) prepared: Prepared,
 	pub(crate) limiter: Arc<Semaphore>,
 	pub(crate) canceller: CancellationToken,
 	pub(crate) channels: (Sender<Message>, Receiver<Message>),
@@ -66,6 +82,10 @@ impl Connection {
 			format,
 			session,
 			vars: BTreeMap::new(),
+			sessions: HashMap::new(),
+			live_queries: RwLock::new(HashSet::new()),
+			requests: Arc::new(RwLock::new(HashMap::new())),
+			prepared: Prepared::default(),
 			limiter: Arc::new(Semaphore::new(*WEBSOCKET_MAX_CONCURRENT_REQUESTS)),
 			canceller: CancellationToken::new(),
 			channels: channel::bounded(*WEBSOCKET_MAX_CONCURRENT_REQUESTS),
@@ -138,6 +158,9 @@ impl Connection {
 			error!("Error handling RPC connection: {}", err);
 		}
 
+		// Cancel all open transactions
+		ds.close_transactions(&id.to_string()).await;
+
 		if let Err(err) = telemetry::metrics::ws::on_disconnect() {
 			error!("Error running metrics::ws::on_disconnect hook: {}", err);
 		}
@@ -215,6 +238,8 @@ impl Connection {
 		let mut tasks = JoinSet::new();
 		// Clone the WebSocket cancellation token
 		let canceller = rpc.read().await.canceller.clone();
+		// Clone the requests which are being processed
+		let requests = rpc.read().await.requests.clone();
 		// Loop, and listen for messages to write
 		loop {
 			tokio::select! {
@@ -271,6 +296,10 @@ impl Connection {
 				}
 			}
 		}
+		// Cancel any requests which are still being processed
+		for canceller in requests.read().await.values() {
+			canceller.cancel();
+		}
 		// Wait for all tasks to finish
 		while let Some(res) = tasks.join_next().await {
 			if let Err(err) = res {
@@ -330,11 +359,57 @@ impl Connection {
 					let otel_cx = Arc::new(TelemetryContext::current_with_value(
 						req_cx.with_method(&req.method).with_size(len),
 					));
+					// Requests with an id can be cancelled while they are being processed
+					let id = req.id.clone();
+					let canceller = Canceller::default();
+					if let Some(id) = &id {
+						let requests = rpc.read().await.requests.clone();
+						requests.write().await.insert(id.clone(), canceller.clone());
+					}
 					// Process the message
-					let res =
-						Connection::process_message(rpc.clone(), &req.method, req.params).await;
+					let res = CANCELLER
+						.scope(canceller.clone(), async {
+							match req.stream {
+								true => {
+									// Send the rows in batches before the response
+									let send = |batch: Batch| {
+										success(req.id.clone(), batch)
+											.send(otel_cx.clone(), fmt, &chn)
+											.with_context(otel_cx.as_ref().clone())
+									};
+									Connection::process_stream(
+										rpc.clone(),
+										req.session,
+										req.txn,
+										&req.method,
+										req.params,
+										send,
+									)
+									.await
+								}
+								false => {
+									Connection::process_message(
+										rpc.clone(),
+										req.session,
+										req.txn,
+										&req.method,
+										req.params,
+									)
+									.await
+								}
+							}
+						})
+						.await;
+					// The request can no longer be cancelled
+					if let Some(id) = &id {
+						let requests = rpc.read().await.requests.clone();
+						let mut requests = requests.write().await;
+						if requests.get(id) == Some(&canceller) {
+							requests.remove(id);
+						}
+					}
 					// Process the response
-					res.into_response(req.id)
+					res.into_response(id)
 						.send(otel_cx.clone(), fmt, &chn)
 						.with_context(otel_cx.as_ref().clone())
 						.await
@@ -356,6 +431,8 @@ impl Connection {
 
 	pub async fn process_message(
 		rpc: Arc<RwLock<Connection>>,
+		session: Option<Uuid>,
+		txn: Option<Uuid>,
 		method: &str,
 		params: Array,
 	) -> Result<Data, Failure> {
@@ -364,12 +441,193 @@ impl Connection {
 		if !method.is_valid() {
 			return Err(Failure::METHOD_NOT_FOUND);
 		}
+		// Sessions are closed by the connection which holds them
+		if matches!(method, Method::SessionClose) {
+			return Connection::close_session(&rpc, session).await;
+		}
+		// Requests for other sessions run with their own state
+		let conn = match session {
+			Some(id) => Connection::session(&rpc, id).await?,
+			None => rpc.clone(),
+		};
 
+		// Invalidating the connection also closes its additional sessions
+		let invalidate = session.is_none() && matches!(method, Method::Invalidate);
 		// if the write lock is a bottleneck then execute could be refactored into execute_mut and execute
 		// rpc.write().await.execute(method, params).await.map_err(Into::into)
-		match method.needs_mut() {
-			true => rpc.write().await.execute(method, params).await.map_err(Into::into),
-			false => rpc.read().await.execute_immut(method, params).await.map_err(Into::into),
+		let res = match method.needs_mut() {
+			true => conn.write().await.execute(txn, method, params).await.map_err(Into::into),
+			false => conn.read().await.execute_immut(txn, method, params).await.map_err(Into::into),
+		};
+		// Arrow responses encode each result with the field definitions of its table
+		#[cfg(feature = "arrow")]
+		let format = rpc.read().await.format;
+		#[cfg(feature = "arrow")]
+		let res = match (format, res) {
+			(Format::Arrow, Ok(data)) => {
+				let conn = conn.read().await;
+				arrow::results(&conn.datastore, &conn.session, data)
+					.await
+					.map_err(|e| Failure::from(RpcError::from(e)))
+			}
+			(_, res) => res,
+		};
+		if invalidate {
+			Connection::close_sessions(&rpc).await;
+		}
+		res
+	}
+
+	/// Processes a request whose rows are sent in batches while the request is running
+	///
+	/// The rows of each statement are sent as soon as they are produced, so a client can
+	/// receive rows from a statement which then fails, or from a transaction which is then
+	/// cancelled. The response which follows the rows holds the outcome of each statement.
+	pub async fn process_stream<F, Fut>(
+		rpc: Arc<RwLock<Connection>>,
+		session: Option<Uuid>,
+		txn: Option<Uuid>,
+		method: &str,
+		params: Array,
+		send: F,
+	) -> Result<Data, Failure>
+	where
+		F: Fn(Batch) -> Fut,
+		Fut: std::future::Future<Output = ()>,
+	{
+		debug!("Process RPC stream request");
+		let method = Method::parse(method);
+		if !method.is_valid() {
+			return Err(Failure::METHOD_NOT_FOUND);
+		}
+		if !method.can_be_streamed() {
+			return Err(Failure::INVALID_REQUEST);
+		}
+		// Requests for other sessions run with their own state
+		let rpc = match session {
+			Some(id) => Connection::session(&rpc, id).await?,
+			None => rpc,
+		};
+		// Take a copy of the session state, so the connection is not locked while rows are sent
+		let lock = rpc.read().await;
+		let stream = Stream {
+			datastore: lock.datastore.clone(),
+			session: lock.session.clone(),
+			vars: lock.vars.clone(),
+			rpc: rpc.clone(),
+		};
+		drop(lock);
+		// The query waits for the rows to be sent once the channel is full
+		let size = *WEBSOCKET_STREAM_BATCH_SIZE;
+		let (tx, rx) = channel::bounded(size);
+		let query = stream.execute_stream(txn, method, params, tx);
+		let rows = async {
+			let mut batch = Batch::default();
+			while let Ok(row) = rx.recv().await {
+				// Send the batch once it is full, or once a different statement produces rows
+				if batch.rows.len() >= size || (batch.query != row.query && !batch.rows.is_empty())
+				{
+					send(std::mem::take(&mut batch)).await;
+				}
+				batch.query = row.query;
+				batch.rows.push(row.value);
+			}
+			// Send any remaining rows
+			if !batch.rows.is_empty() {
+				send(batch).await;
+			}
+		};
+		let (res, _) = futures::join!(query, rows);
+		res.map_err(Into::into)
+	}
+
+	/// Retrieves an additional session on this connection, creating it on first use
+	async fn session(
+		rpc: &Arc<RwLock<Connection>>,
+		id: Uuid,
+	) -> Result<Arc<RwLock<Connection>>, Failure> {
+		// Check if the session already exists
+		if let Some(v) = rpc.read().await.sessions.get(&id) {
+			return Ok(v.clone());
+		}
+		let mut lock = rpc.write().await;
+		let conn = &mut *lock;
+		// Each connection can only open a limited number of sessions
+		if !conn.sessions.contains_key(&id) && conn.sessions.len() >= *WEBSOCKET_MAX_SESSIONS {
+			return Err(Failure::custom("Too many sessions are open on this connection"));
+		}
+		let session = conn
+			.sessions
+			.entry(id)
+			.or_insert_with(|| {
+				// The session starts unauthenticated, on the same connection
+				let mut session = Session::default().with_rt(true);
+				session.ip.clone_from(&conn.session.ip);
+				session.or.clone_from(&conn.session.or);
+				session.id.clone_from(&conn.session.id);
+				// The session shares the connection, but has its own state
+				Arc::new(RwLock::new(Connection {
+					id: conn.id,
+					format: conn.format,
+					session,
+					vars: BTreeMap::new(),
+					sessions: HashMap::new(),
+					live_queries: RwLock::new(HashSet::new()),
+					requests: conn.requests.clone(),
+					prepared: Prepared::default(),
+					limiter: conn.limiter.clone(),
+					canceller: conn.canceller.clone(),
+					channels: conn.channels.clone(),
+					state: conn.state.clone(),
+					#[cfg(surrealdb_unstable)]
+					gql_schema: SchemaCache::new(conn.datastore.clone()),
+					datastore: conn.datastore.clone(),
+				}))
+			})
+			.clone();
+		Ok(session)
+	}
+
+	/// Closes an additional session on this connection, along with its live queries
+	async fn close_session(
+		rpc: &Arc<RwLock<Connection>>,
+		session: Option<Uuid>,
+	) -> Result<Data, Failure> {
+		// The main session of the connection can not be closed
+		let Some(id) = session else {
+			return Err(Failure::INVALID_REQUEST);
+		};
+		// Sessions are created on first use, so unknown sessions are already closed
+		let session = rpc.write().await.sessions.remove(&id);
+		if let Some(session) = session {
+			Connection::kill_session(session).await;
+		}
+		Ok(Value::None.into())
+	}
+
+	/// Closes all the additional sessions on this connection
+	async fn close_sessions(rpc: &Arc<RwLock<Connection>>) {
+		let sessions = std::mem::take(&mut rpc.write().await.sessions);
+		for session in sessions.into_values() {
+			Connection::kill_session(session).await;
+		}
+	}
+
+	/// Removes the live queries which were started by a closed session
+	async fn kill_session(session: Arc<RwLock<Connection>>) {
+		let lock = session.read().await;
+		let gc: Vec<Uuid> = lock.live_queries.write().await.drain().collect();
+		if gc.is_empty() {
+			return;
+		}
+		let mut live_queries = lock.state.live_queries.write().await;
+		for id in gc.iter() {
+			trace!("Removing live query: {}", id);
+			live_queries.remove(id);
+		}
+		drop(live_queries);
+		if let Err(err) = lock.datastore.delete_queries(gc).await {
+			error!("Error closing RPC session: {}", err);
 		}
 	}
 }
@@ -402,16 +660,37 @@ impl RpcContext for Connection {
 	const LQ_SUPPORT: bool = true;
 
 	async fn handle_live(&self, lqid: &Uuid) {
+		self.live_queries.write().await.insert(*lqid);
 		self.state.live_queries.write().await.insert(*lqid, self.id);
 		trace!("Registered live query {} on websocket {}", lqid, self.id);
 	}
 
 	async fn handle_kill(&self, lqid: &Uuid) {
+		self.live_queries.write().await.remove(lqid);
 		if let Some(id) = self.state.live_queries.write().await.remove(lqid) {
 			trace!("Unregistered live query {} on websocket {}", lqid, id);
 		}
 	}
 
+	fn canceller(&self) -> Option<Canceller> {
+		CANCELLER.try_with(Canceller::clone).ok()
+	}
+
+	fn prepared(&self) -> Option<&Prepared> {
+		Some(&self.prepared)
+	}
+
+	async fn handle_cancel(&self, id: &Value) -> bool {
+		match self.requests.read().await.get(id) {
+			Some(canceller) => {
+				canceller.cancel();
+				trace!("Cancelled request {} on websocket {}", id, self.id);
+				true
+			}
+			None => false,
+		}
+	}
+
 	#[cfg(surrealdb_unstable)]
 	const GQL_SUPPORT: bool = true;
 	#[cfg(surrealdb_unstable)]
@@ -419,3 +698,51 @@ impl RpcContext for Connection {
 		&self.gql_schema
 	}
 }
+
+/// The state which a streamed request runs with, copied from its connection
+struct Stream {
+	rpc: Arc<RwLock<Connection>>,
+	datastore: Arc<Datastore>,
+	session: Session,
+	vars: BTreeMap<String, Value>,
+}
+
+impl RpcContext for Stream {
+	fn kvs(&self) -> &Datastore {
+		&self.datastore
+	}
+
+	fn session(&self) -> &Session {
+		&self.session
+	}
+
+	fn session_mut(&mut self) -> &mut Session {
+		&mut self.session
+	}
+
+	fn vars(&self) -> &BTreeMap<String, Value> {
+		&self.vars
+	}
+
+	fn vars_mut(&mut self) -> &mut BTreeMap<String, Value> {
+		&mut self.vars
+	}
+
+	fn version_data(&self) -> Data {
+		format!("{PKG_NAME}-{}", *PKG_VERSION).into()
+	}
+
+	const LQ_SUPPORT: bool = true;
+
+	async fn handle_live(&self, lqid: &Uuid) {
+		self.rpc.read().await.handle_live(lqid).await
+	}
+
+	async fn handle_kill(&self, lqid: &Uuid) {
+		self.rpc.read().await.handle_kill(lqid).await
+	}
+
+	fn canceller(&self) -> Option<Canceller> {
+		CANCELLER.try_with(Canceller::clone).ok()
+	}
+}
@@ -19,6 +19,7 @@ impl From<&Accept> for Format {
 			Accept::ApplicationPack => Format::Msgpack,
 			Accept::ApplicationOctetStream => Format::Unsupported,
 			Accept::Surrealdb => Format::Bincode,
+			Accept::ApplicationArrow => Format::Arrow,
 		}
 	}
 }
@@ -11,6 +11,8 @@ use opentelemetry::Context as TelemetryContext;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
+use surrealdb::channel::Sender;
+use surrealdb::dbs::Notification;
 use surrealdb::kvs::Datastore;
 use tokio::sync::RwLock;
 use tokio_util::sync::CancellationToken;
@@ -23,12 +25,16 @@ type WebSocket = Arc<RwLock<Connection>>;
 type WebSockets = RwLock<HashMap<Uuid, WebSocket>>;
 /// Mapping of LIVE Query ID to WebSocket ID
 type LiveQueries = RwLock<HashMap<Uuid, Uuid>>;
+/// Mapping of LIVE Query ID to the sender of an HTTP event stream
+type EventStreams = RwLock<HashMap<Uuid, Sender<Notification>>>;
 
 pub struct RpcState {
 	/// Stores the currently connected WebSockets
 	pub web_sockets: WebSockets,
 	/// Stores the currently initiated LIVE queries
 	pub live_queries: LiveQueries,
+	/// Stores the LIVE queries which are streamed over HTTP
+	pub event_streams: EventStreams,
 }
 
 impl RpcState {
@@ -36,6 +42,7 @@ impl RpcState {
 		RpcState {
 			web_sockets: WebSockets::default(),
 			live_queries: LiveQueries::default(),
+			event_streams: EventStreams::default(),
 		}
 	}
 }
@@ -84,6 +91,16 @@ pub(crate) async fn notifications(
 							// Send the notification to the client
 							message.send(cx, format, &sender).await
 						}
+					} else {
+						// Check to see if an event stream exists
+						let maybe_stream = {
+							// We remove the lock ASAP
+							state.event_streams.read().await.get(&notification.id).cloned()
+						};
+						if let Some(sender) = maybe_stream {
+							// Send the notification to the client
+							let _ = sender.send(notification).await;
+						}
 					}
 				},
 			}
@@ -93,6 +110,8 @@ pub(crate) async fn notifications(
 
 /// Closes all WebSocket connections, waiting for graceful shutdown
 pub(crate) async fn graceful_shutdown(state: Arc<RpcState>) {
+	// Close event streams, which finish once their queued notifications are sent
+	state.event_streams.write().await.clear();
 	// Close WebSocket connections, ensuring queued messages are processed
 	for (_, rpc) in state.web_sockets.read().await.iter() {
 		rpc.read().await.canceller.cancel();
@@ -109,4 +128,8 @@ pub(crate) fn shutdown(state: Arc<RpcState>) {
 	if let Ok(mut writer) = state.web_sockets.try_write() {
 		writer.drain();
 	}
+	// Close all event streams immediately
+	if let Ok(mut writer) = state.event_streams.try_write() {
+		writer.drain();
+	}
 }
@@ -995,6 +995,75 @@ mod http_integration {
 		Ok(())
 	}
 
+	#[test(tokio::test)]
+	#[cfg(feature = "arrow")]
+	async fn sql_endpoint_with_arrow() -> Result<(), Box<dyn std::error::Error>> {
+		use arrow_array::Array;
+		use arrow_ipc::reader::StreamReader;
+		use arrow_schema::DataType;
+
+		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+		let url = &format!("http://{addr}/sql");
+
+		// Prepare HTTP client
+		let mut headers = reqwest::header::HeaderMap::new();
+		headers.insert("surreal-ns", Ulid::new().to_string().parse()?);
+		headers.insert("surreal-db", Ulid::new().to_string().parse()?);
+		headers.insert(header::ACCEPT, "application/vnd.apache.arrow.stream".parse()?);
+
+		let client = reqwest::Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+
+		// Selecting from a SCHEMAFULL table uses the field definitions
+		{
+			let res = client
+				.post(url)
+				.basic_auth(USER, Some(PASS))
+				.body(
+					r#"
+					DEFINE TABLE person SCHEMAFULL;
+					DEFINE FIELD name ON person TYPE string;
+					DEFINE FIELD age ON person TYPE option<int>;
+					CREATE person:1 SET name = 'Tobie', age = 34;
+					CREATE person:2 SET name = 'Jaime';
+					SELECT * FROM person;
+					"#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			assert_eq!(res.headers()[header::CONTENT_TYPE], "application/vnd.apache.arrow.stream");
+
+			let body = res.bytes().await?;
+			let reader = StreamReader::try_new(body.as_ref(), None)?;
+			let schema = reader.schema();
+			let names: Vec<&str> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+			assert_eq!(names, ["id", "name", "age"]);
+			assert_eq!(schema.field(1).data_type(), &DataType::Utf8);
+			assert_eq!(schema.field(2).data_type(), &DataType::Int64);
+
+			let batches = reader.collect::<Result<Vec<_>, _>>()?;
+			assert_eq!(batches.len(), 1);
+			assert_eq!(batches[0].num_rows(), 2);
+			assert_eq!(batches[0].column(2).null_count(), 1);
+		}
+
+		// Results which are not records are not allowed
+		{
+			let res = client
+				.post(url)
+				.basic_auth(USER, Some(PASS))
+				.body("SELECT VALUE name FROM person")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 400);
+		}
+
+		Ok(())
+	}
+
 	#[test(tokio::test)]
 	async fn sync_endpoint() -> Result<(), Box<dyn std::error::Error>> {
 		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
@@ -1136,6 +1205,86 @@ mod http_integration {
 			assert_eq!(body[0]["result"].as_array().unwrap()[0]["id"], "table:11", "body: {body}");
 		}
 
+		// GET records matching a condition
+		{
+			let res = client
+				.get(url)
+				.query(&[("where", "id > table:40")])
+				.basic_auth(USER, Some(PASS))
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?).unwrap();
+			assert_eq!(body[0]["result"].as_array().unwrap().len(), 10, "body: {body}");
+		}
+
+		// GET records in descending order
+		{
+			let res = client
+				.get(url)
+				.query(&[("order", "-id"), ("limit", "1")])
+				.basic_auth(USER, Some(PASS))
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?).unwrap();
+			assert_eq!(body[0]["result"].as_array().unwrap().len(), 1, "body: {body}");
+			assert_eq!(body[0]["result"].as_array().unwrap()[0]["id"], "table:50", "body: {body}");
+		}
+
+		// GET records after a cursor
+		{
+			let res = client
+				.get(url)
+				.query(&[("cursor", "40")])
+				.basic_auth(USER, Some(PASS))
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?).unwrap();
+			assert_eq!(body[0]["result"].as_array().unwrap().len(), 10, "body: {body}");
+			assert_eq!(body[0]["result"].as_array().unwrap()[0]["id"], "table:41", "body: {body}");
+		}
+
+		// GET records with a cursor and a different order is not allowed
+		{
+			let res = client
+				.get(url)
+				.query(&[("cursor", "40"), ("order", "-id")])
+				.basic_auth(USER, Some(PASS))
+				.send()
+				.await?;
+			assert_eq!(res.status(), 400, "body: {}", res.text().await?);
+		}
+
+		// GET records with a condition which writes data or selects other records is not allowed
+		for cond in ["id IN (SELECT VALUE id FROM table)", "(DELETE table) = []", "fn::test()"] {
+			let res = client
+				.get(url)
+				.query(&[("where", cond)])
+				.basic_auth(USER, Some(PASS))
+				.send()
+				.await?;
+			assert_eq!(res.status(), 400, "body: {}", res.text().await?);
+		}
+
+		// GET only some fields of the records
+		{
+			let res = client
+				.get(url)
+				.query(&[("fields", "default"), ("limit", "1")])
+				.basic_auth(USER, Some(PASS))
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?).unwrap();
+			assert_eq!(body[0]["result"], json!([{ "default": "content" }]), "body: {body}");
+		}
+
 		// GET without authentication returns no records
 		{
 			let res = client.get(url).send().await?;
@@ -1148,6 +1297,72 @@ mod http_integration {
 		Ok(())
 	}
 
+	#[test(tokio::test)]
+	async fn key_endpoint_openapi() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_with_guests().await.unwrap();
+		let url = &format!("http://{addr}/key");
+
+		// Prepare HTTP client
+		let mut headers = reqwest::header::HeaderMap::new();
+		headers.insert("surreal-ns", Ulid::new().to_string().parse()?);
+		headers.insert("surreal-db", Ulid::new().to_string().parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = reqwest::Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+
+		// Define the tables
+		{
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.basic_auth(USER, Some(PASS))
+				.body(
+					r#"
+					DEFINE TABLE person SCHEMAFULL;
+					DEFINE FIELD name ON person TYPE string;
+					DEFINE FIELD age ON person TYPE option<int>;
+					DEFINE FIELD tags ON person TYPE set<string>;
+					"#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+		}
+
+		// GET the document describing the tables
+		{
+			let res = client.get(url).basic_auth(USER, Some(PASS)).send().await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?).unwrap();
+			assert_eq!(body["openapi"], "3.0.3", "body: {body}");
+			assert!(body["paths"]["/key/person"]["get"].is_object(), "body: {body}");
+			assert!(body["paths"]["/key/person/{id}"]["delete"].is_object(), "body: {body}");
+			let schema = &body["components"]["schemas"]["person"];
+			assert_eq!(schema["additionalProperties"], false, "body: {body}");
+			assert_eq!(schema["required"], json!(["name", "tags"]), "body: {body}");
+			assert_eq!(
+				schema["properties"]["age"],
+				json!({ "type": "integer", "format": "int64", "nullable": true }),
+				"body: {body}"
+			);
+			assert_eq!(
+				schema["properties"]["tags"],
+				json!({ "type": "array", "items": { "type": "string" }, "uniqueItems": true }),
+				"body: {body}"
+			);
+		}
+
+		// GET without authentication is not allowed
+		{
+			let res = client.get(url).send().await?;
+			assert_eq!(res.status(), 403, "body: {}", res.text().await?);
+		}
+
+		Ok(())
+	}
+
 	#[test(tokio::test)]
 	async fn key_endpoint_create_all() -> Result<(), Box<dyn std::error::Error>> {
 		let (addr, _server) = common::start_server_with_guests().await.unwrap();
@@ -1727,4 +1942,85 @@ mod http_integration {
 
 		Ok(())
 	}
+
+	#[test(tokio::test)]
+	async fn live_endpoint_event_stream() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_with_guests().await.unwrap();
+		let url = &format!("http://{addr}/live/person");
+
+		// Prepare HTTP client
+		let mut headers = reqwest::header::HeaderMap::new();
+		headers.insert("surreal-ns", Ulid::new().to_string().parse()?);
+		headers.insert("surreal-db", Ulid::new().to_string().parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = reqwest::Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+
+		// Conditions can not select other records
+		{
+			let res = client
+				.get(url)
+				.basic_auth(USER, Some(PASS))
+				.query(&[("where", "age > (SELECT VALUE age FROM ONLY person:tobie)")])
+				.send()
+				.await?;
+			assert_eq!(res.status(), 400, "body: {}", res.text().await?);
+		}
+
+		// Listen for changes to the table
+		let mut res = client
+			.get(url)
+			.basic_auth(USER, Some(PASS))
+			.query(&[("where", "age > 18")])
+			.send()
+			.await?;
+		assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+		assert_eq!(res.headers()[header::CONTENT_TYPE], "text/event-stream");
+
+		// Create records, of which only one matches the condition
+		{
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.basic_auth(USER, Some(PASS))
+				.body("CREATE person:tobie SET age = 30; CREATE person:jaime SET age = 10;")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+		}
+
+		// Receive the notification of the matching record
+		{
+			let mut body = String::new();
+			while !body.contains("\n\n") {
+				let chunk = tokio::time::timeout(Duration::from_secs(5), res.chunk()).await??;
+				body.push_str(std::str::from_utf8(&chunk.unwrap())?);
+			}
+			assert!(body.starts_with("event: CREATE\n"), "body: {body}");
+			assert!(body.contains(r#""id":"person:tobie""#), "body: {body}");
+			assert!(!body.contains("person:jaime"), "body: {body}");
+		}
+
+		// The live query is killed when the client disconnects
+		drop(res);
+		let mut lives = 1;
+		for _ in 0..50 {
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.basic_auth(USER, Some(PASS))
+				.body("INFO FOR TABLE person")
+				.send()
+				.await?;
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?).unwrap();
+			lives = body[0]["result"]["lives"].as_object().unwrap().len();
+			if lives == 0 {
+				break;
+			}
+			tokio::time::sleep(Duration::from_millis(100)).await;
+		}
+		assert_eq!(lives, 0);
+
+		Ok(())
+	}
 }